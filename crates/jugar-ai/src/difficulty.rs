@@ -0,0 +1,472 @@
+//! Dynamic difficulty that follows how the player is doing.
+//!
+//! An [`AdaptiveDifficulty`] keeps a rolling window of the player's recent
+//! hits and misses and how long they last between failures. When the player
+//! wins too easily it steps the level up; when they struggle it steps down,
+//! aiming to keep them in the flow channel between boredom and anxiety.
+//!
+//! Two things stop the level from flapping. Between the two hit-rate bounds
+//! nothing changes, and after every change the window starts over and the
+//! level holds for a cooldown before it may move again. A parental cap puts
+//! a ceiling on the level, whatever the player's performance says.
+//!
+//! ```
+//! use jugar_ai::{AdaptiveDifficulty, AiComponent};
+//!
+//! let mut difficulty = AdaptiveDifficulty::new(10).with_level(5).with_cap(7);
+//! for _ in 0..20 {
+//!     difficulty.record_hit();
+//! }
+//! assert_eq!(difficulty.update(30.0), Some(6));
+//!
+//! let enemy = difficulty.apply(AiComponent::new("chase"));
+//! assert_eq!(enemy.difficulty, 6);
+//! ```
+
+use alloc::collections::VecDeque;
+
+use jugar_apr::AprMetadata;
+use serde::{Deserialize, Serialize};
+
+use crate::AiComponent;
+
+/// Levels used when a model does not say how many it has
+pub const DEFAULT_DIFFICULTY_LEVELS: u8 = 10;
+
+/// Where the player sits relative to the challenge
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum FlowChannel {
+    /// Winning too easily: the level should go up
+    Boredom,
+    /// Challenged about right: the level stays
+    #[default]
+    Flow,
+    /// Struggling: the level should go down
+    Anxiety,
+}
+
+/// Rolling record of the player's recent performance
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PerformanceWindow {
+    capacity: usize,
+    hits: VecDeque<bool>,
+    times_to_fail: VecDeque<f32>,
+    since_failure: f32,
+}
+
+impl PerformanceWindow {
+    /// Creates a window remembering the last `capacity` hits and failures
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            hits: VecDeque::new(),
+            times_to_fail: VecDeque::new(),
+            since_failure: 0.0,
+        }
+    }
+
+    /// Records an attempt that hit (or dodged, or scored)
+    pub fn record_hit(&mut self) {
+        self.push_attempt(true);
+    }
+
+    /// Records an attempt that missed
+    pub fn record_miss(&mut self) {
+        self.push_attempt(false);
+    }
+
+    /// Records a failure (a lost life or round), ending the current run
+    pub fn record_failure(&mut self) {
+        if self.times_to_fail.len() == self.capacity {
+            let _ = self.times_to_fail.pop_front();
+        }
+        self.times_to_fail.push_back(self.since_failure);
+        self.since_failure = 0.0;
+    }
+
+    /// Advances the clock of the current run
+    pub fn tick(&mut self, dt: f32) {
+        self.since_failure += dt.max(0.0);
+    }
+
+    /// Share of recent attempts that hit, if there were any
+    #[must_use]
+    pub fn hit_rate(&self) -> Option<f32> {
+        let hits = self.hits.iter().filter(|&&hit| hit).count();
+        (!self.hits.is_empty()).then(|| hits as f32 / self.hits.len() as f32)
+    }
+
+    /// Mean seconds between recent failures, if there were any
+    #[must_use]
+    pub fn mean_time_to_fail(&self) -> Option<f32> {
+        let total: f32 = self.times_to_fail.iter().sum();
+        (!self.times_to_fail.is_empty()).then(|| total / self.times_to_fail.len() as f32)
+    }
+
+    /// Seconds since the last failure
+    #[must_use]
+    pub const fn since_failure(&self) -> f32 {
+        self.since_failure
+    }
+
+    /// Number of attempts in the window
+    #[must_use]
+    pub fn attempts(&self) -> usize {
+        self.hits.len()
+    }
+
+    /// Forgets everything recorded so far
+    pub fn clear(&mut self) {
+        self.hits.clear();
+        self.times_to_fail.clear();
+        self.since_failure = 0.0;
+    }
+
+    fn push_attempt(&mut self, hit: bool) {
+        if self.hits.len() == self.capacity {
+            let _ = self.hits.pop_front();
+        }
+        self.hits.push_back(hit);
+    }
+}
+
+/// Snapshot of an [`AdaptiveDifficulty`], for HUDs, saves and the YAML runtime
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DifficultyState {
+    /// Current level (1 to `levels`)
+    pub level: u8,
+    /// Number of levels
+    pub levels: u8,
+    /// Highest level allowed by a parent or teacher
+    pub cap: u8,
+    /// Where the player sits right now
+    pub channel: FlowChannel,
+    /// Share of recent attempts that hit
+    pub hit_rate: Option<f32>,
+    /// Mean seconds between recent failures
+    pub time_to_fail: Option<f32>,
+}
+
+/// Difficulty level that adapts to the player's performance
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AdaptiveDifficulty {
+    level: u8,
+    levels: u8,
+    cap: u8,
+    window: PerformanceWindow,
+    min_attempts: usize,
+    hit_rate_band: (f32, f32),
+    time_to_fail_band: (f32, f32),
+    cooldown: f32,
+    since_change: f32,
+}
+
+impl AdaptiveDifficulty {
+    /// Creates a controller over `levels` levels, starting in the middle
+    ///
+    /// The player is in flow while hitting 40-80% of attempts and lasting
+    /// 10-90 seconds between failures. The level may move every 20 seconds
+    /// at most, once at least 8 attempts have been seen.
+    #[must_use]
+    pub fn new(levels: u8) -> Self {
+        let levels = levels.max(1);
+        Self {
+            level: levels.div_ceil(2),
+            levels,
+            cap: levels,
+            window: PerformanceWindow::new(20),
+            min_attempts: 8,
+            hit_rate_band: (0.4, 0.8),
+            time_to_fail_band: (10.0, 90.0),
+            cooldown: 20.0,
+            since_change: 0.0,
+        }
+    }
+
+    /// Creates a controller over the levels a .apr model declares
+    #[must_use]
+    pub fn for_model(metadata: &AprMetadata) -> Self {
+        Self::new(
+            metadata
+                .difficulty_levels
+                .unwrap_or(DEFAULT_DIFFICULTY_LEVELS),
+        )
+    }
+
+    /// Sets the starting level
+    #[must_use]
+    pub fn with_level(mut self, level: u8) -> Self {
+        self.level = level.clamp(1, self.cap);
+        self
+    }
+
+    /// Sets the parental cap, lowering the level if it is above it
+    #[must_use]
+    pub fn with_cap(mut self, cap: u8) -> Self {
+        self.set_cap(cap);
+        self
+    }
+
+    /// Sets how many recent attempts and failures are remembered
+    #[must_use]
+    pub fn with_window(mut self, capacity: usize) -> Self {
+        self.window = PerformanceWindow::new(capacity);
+        self.min_attempts = self.min_attempts.min(self.window.capacity);
+        self
+    }
+
+    /// Sets the hit rates between which the player is in flow
+    #[must_use]
+    pub const fn with_hit_rate_band(mut self, low: f32, high: f32) -> Self {
+        self.hit_rate_band = (low, high);
+        self
+    }
+
+    /// Sets the seconds between failures within which the player is in flow
+    #[must_use]
+    pub const fn with_time_to_fail_band(mut self, short: f32, long: f32) -> Self {
+        self.time_to_fail_band = (short, long);
+        self
+    }
+
+    /// Sets the seconds the level holds after each change
+    #[must_use]
+    pub const fn with_cooldown(mut self, seconds: f32) -> Self {
+        self.cooldown = seconds;
+        self
+    }
+
+    /// Changes the parental cap while playing
+    pub fn set_cap(&mut self, cap: u8) {
+        self.cap = cap.clamp(1, self.levels);
+        self.level = self.level.min(self.cap);
+    }
+
+    /// Records an attempt that hit
+    pub fn record_hit(&mut self) {
+        self.window.record_hit();
+    }
+
+    /// Records an attempt that missed
+    pub fn record_miss(&mut self) {
+        self.window.record_miss();
+    }
+
+    /// Records a failure (a lost life or round)
+    pub fn record_failure(&mut self) {
+        self.window.record_failure();
+    }
+
+    /// Advances time and adjusts the level if the player is out of flow
+    ///
+    /// Returns the new level when it changed.
+    pub fn update(&mut self, dt: f32) -> Option<u8> {
+        self.window.tick(dt);
+        self.since_change += dt.max(0.0);
+        if self.since_change < self.cooldown {
+            return None;
+        }
+        let level = match self.channel() {
+            FlowChannel::Flow => return None,
+            FlowChannel::Boredom => self.level.saturating_add(1).min(self.cap),
+            FlowChannel::Anxiety => self.level.saturating_sub(1).max(1),
+        };
+        if level == self.level {
+            return None;
+        }
+        log::debug!("Difficulty {} -> {level}", self.level);
+        self.level = level;
+        self.since_change = 0.0;
+        self.window.clear();
+        Some(level)
+    }
+
+    /// Where the player sits, judging by the window
+    ///
+    /// Struggling on either measure counts as anxiety, since backing off too
+    /// late costs more than stepping up too late.
+    #[must_use]
+    pub fn channel(&self) -> FlowChannel {
+        let (low, high) = self.hit_rate_band;
+        let by_hits = self
+            .window
+            .hit_rate()
+            .filter(|_| self.window.attempts() >= self.min_attempts)
+            .map_or(FlowChannel::Flow, |rate| {
+                if rate < low {
+                    FlowChannel::Anxiety
+                } else if rate > high {
+                    FlowChannel::Boredom
+                } else {
+                    FlowChannel::Flow
+                }
+            });
+
+        let (short, long) = self.time_to_fail_band;
+        let by_time = match self.window.mean_time_to_fail() {
+            Some(mean) if mean < short => FlowChannel::Anxiety,
+            Some(mean) if mean > long && self.window.since_failure() > long => FlowChannel::Boredom,
+            None if self.window.since_failure() > long => FlowChannel::Boredom,
+            _ => FlowChannel::Flow,
+        };
+
+        match (by_hits, by_time) {
+            (FlowChannel::Anxiety, _) | (_, FlowChannel::Anxiety) => FlowChannel::Anxiety,
+            (FlowChannel::Boredom, _) | (_, FlowChannel::Boredom) => FlowChannel::Boredom,
+            _ => FlowChannel::Flow,
+        }
+    }
+
+    /// Current level (1 to `levels`)
+    #[must_use]
+    pub const fn level(&self) -> u8 {
+        self.level
+    }
+
+    /// Number of levels
+    #[must_use]
+    pub const fn levels(&self) -> u8 {
+        self.levels
+    }
+
+    /// Highest level allowed
+    #[must_use]
+    pub const fn cap(&self) -> u8 {
+        self.cap
+    }
+
+    /// Recent performance
+    #[must_use]
+    pub const fn window(&self) -> &PerformanceWindow {
+        &self.window
+    }
+
+    /// Level as 0.0 (easiest) to 1.0 (hardest)
+    #[must_use]
+    pub fn normalized(&self) -> f32 {
+        if self.levels <= 1 {
+            return 0.0;
+        }
+        f32::from(self.level - 1) / f32::from(self.levels - 1)
+    }
+
+    /// Interpolates a behavior parameter between its easiest and hardest value
+    ///
+    /// For reaction times, chase speeds, utility weights and the like.
+    #[must_use]
+    pub fn scale(&self, easiest: f32, hardest: f32) -> f32 {
+        (hardest - easiest).mul_add(self.normalized(), easiest)
+    }
+
+    /// Sets an AI component's 1-10 difficulty from the current level
+    #[must_use]
+    pub fn apply(&self, component: AiComponent) -> AiComponent {
+        let difficulty = self.scale(1.0, 10.0).round() as u8;
+        component.with_difficulty(difficulty)
+    }
+
+    /// Snapshot of the current state
+    #[must_use]
+    pub fn state(&self) -> DifficultyState {
+        DifficultyState {
+            level: self.level,
+            levels: self.levels,
+            cap: self.cap,
+            channel: self.channel(),
+            hit_rate: self.window.hit_rate(),
+            time_to_fail: self.window.mean_time_to_fail(),
+        }
+    }
+}
+
+impl Default for AdaptiveDifficulty {
+    fn default() -> Self {
+        Self::new(DEFAULT_DIFFICULTY_LEVELS)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_level_follows_performance() {
+        let mut difficulty = AdaptiveDifficulty::new(10).with_level(5);
+        for _ in 0..10 {
+            difficulty.record_hit();
+        }
+        assert_eq!(difficulty.channel(), FlowChannel::Boredom);
+        // Held by the cooldown
+        assert_eq!(difficulty.update(5.0), None);
+        assert_eq!(difficulty.update(20.0), Some(6));
+        // The window starts over after a change
+        assert_eq!(difficulty.window().attempts(), 0);
+
+        for _ in 0..4 {
+            difficulty.record_miss();
+            difficulty.record_hit();
+            difficulty.record_miss();
+        }
+        assert_eq!(difficulty.channel(), FlowChannel::Anxiety);
+        assert_eq!(difficulty.update(20.0), Some(5));
+
+        // Quick failures alone count as struggling
+        for _ in 0..3 {
+            let _ = difficulty.update(2.0);
+            difficulty.record_failure();
+        }
+        assert_eq!(difficulty.channel(), FlowChannel::Anxiety);
+        assert_eq!(difficulty.update(20.0), Some(4));
+    }
+
+    #[test]
+    fn test_hysteresis_holds_the_level_in_flow() {
+        let mut difficulty = AdaptiveDifficulty::new(10).with_level(5);
+        for _ in 0..20 {
+            difficulty.record_hit();
+            difficulty.record_hit();
+            difficulty.record_miss();
+        }
+        // 2 in 3 sits between the bounds
+        assert_eq!(difficulty.channel(), FlowChannel::Flow);
+        for _ in 0..10 {
+            assert_eq!(difficulty.update(5.0), None);
+        }
+
+        // Too few attempts to judge
+        let mut early = AdaptiveDifficulty::new(10);
+        early.record_hit();
+        early.record_hit();
+        assert_eq!(early.update(60.0), None);
+    }
+
+    #[test]
+    fn test_parental_cap_and_model_levels() {
+        let mut difficulty = AdaptiveDifficulty::new(10).with_level(9).with_cap(6);
+        assert_eq!(difficulty.level(), 6);
+        for _ in 0..10 {
+            difficulty.record_hit();
+        }
+        assert_eq!(difficulty.update(60.0), None);
+        assert_eq!(difficulty.level(), 6);
+
+        let metadata = AprMetadata::builder()
+            .name("ghost")
+            .version("1.0.0")
+            .author("kid")
+            .license("MIT")
+            .difficulty_levels(3)
+            .build()
+            .unwrap();
+        let model = AdaptiveDifficulty::for_model(&metadata);
+        assert_eq!((model.level(), model.levels()), (2, 3));
+        assert!((model.normalized() - 0.5).abs() < f32::EPSILON);
+        assert_eq!(model.apply(AiComponent::new("chase")).difficulty, 6);
+
+        let state = model.state();
+        assert_eq!(state.cap, 3);
+        assert_eq!(state.hit_rate, None);
+    }
+}
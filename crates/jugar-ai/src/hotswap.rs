@@ -0,0 +1,431 @@
+//! Hot-swapping .apr models at runtime ("trading cards").
+//!
+//! Entities reference a behavior through a [`ModelSlot`] component instead
+//! of a concrete model. Swapping the model installed in a slot changes the
+//! behavior of every entity on that slot at once, without restarting the
+//! game. Replacements are checked against the current model's input and
+//! output schemas first, so a card that expects different sensors can't be
+//! dropped into the wrong game.
+//!
+//! Models are registered in the [`AiSystem`] under their content hash, so an
+//! entity may also pin an exact model with [`ModelSlot::Pinned`] and keep it
+//! across swaps.
+
+use std::collections::HashMap;
+
+use jugar_apr::{AprMetadata, AprModel, ModelArchitecture, Schema};
+use jugar_core::World;
+
+use crate::{AiError, AiInputs, AiOutputs, AiSystem, Result};
+
+/// Component selecting which model drives an entity
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ModelSlot {
+    /// Whatever model is installed in the named slot
+    Named(String),
+    /// One exact model, by content hash
+    Pinned(u64),
+}
+
+impl ModelSlot {
+    /// Follows a named slot
+    #[must_use]
+    pub fn named(name: impl Into<String>) -> Self {
+        Self::Named(name.into())
+    }
+
+    /// Pins an exact model
+    #[must_use]
+    pub const fn pinned(hash: u64) -> Self {
+        Self::Pinned(hash)
+    }
+}
+
+/// Result of a successful swap
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SwapReport {
+    /// Hash of the model that was replaced
+    pub previous: u64,
+    /// Hash of the model now installed
+    pub current: u64,
+    /// Entities whose behavior changed
+    pub entities: usize,
+}
+
+/// Content hash of a model (FNV-1a over metadata identity and parameters)
+#[must_use]
+pub fn model_hash(model: &AprModel) -> u64 {
+    const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+    let mut hash = OFFSET;
+    let mut feed = |bytes: &[u8]| {
+        for &byte in bytes {
+            hash = (hash ^ u64::from(byte)).wrapping_mul(PRIME);
+        }
+    };
+    feed(model.metadata.name.as_bytes());
+    feed(model.metadata.version.to_string().as_bytes());
+    feed(model.data.architecture.to_string().as_bytes());
+    for value in model.data.weights.iter().chain(&model.data.biases) {
+        feed(&value.to_bits().to_le_bytes());
+    }
+    hash
+}
+
+/// Input and output sizes implied by a model's architecture
+fn arity(architecture: &ModelArchitecture) -> Option<(usize, usize)> {
+    match architecture {
+        ModelArchitecture::Mlp { layers } => match (layers.first(), layers.last()) {
+            (Some(&inputs), Some(&outputs)) => Some((inputs, outputs)),
+            _ => None,
+        },
+        ModelArchitecture::BehaviorTree { .. } => None,
+    }
+}
+
+fn describe(metadata: &AprMetadata) -> String {
+    format!("{} v{}", metadata.name, metadata.version)
+}
+
+/// Checks that `replacement` can stand in for `current`
+///
+/// Inputs must match exactly (the game supplies a fixed input vector).
+/// Outputs must start with the current outputs; extra trailing outputs are
+/// allowed because consumers ignore what they don't read. Without schemas
+/// on both sides, MLP layer sizes are compared instead.
+///
+/// # Errors
+///
+/// Returns [`AiError::IncompatibleModel`] describing the first mismatch.
+pub fn check_compatible(current: &AprModel, replacement: &AprModel) -> Result<()> {
+    let (old, new) = (&current.metadata, &replacement.metadata);
+    let mismatch = |what: String| {
+        Err(AiError::IncompatibleModel(format!(
+            "{} can't replace {}: {what}",
+            describe(new),
+            describe(old)
+        )))
+    };
+
+    if let (Some(old_inputs), Some(new_inputs)) = (&old.input_schema, &new.input_schema) {
+        if old_inputs != new_inputs {
+            let names = |schema: &Schema| {
+                schema
+                    .fields
+                    .iter()
+                    .map(|field| format!("{}: {}", field.name, field.field_type))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+            return mismatch(format!(
+                "expects inputs [{}] but the game provides [{}]",
+                names(new_inputs),
+                names(old_inputs)
+            ));
+        }
+    }
+
+    if let (Some(old_outputs), Some(new_outputs)) = (&old.output_schema, &new.output_schema) {
+        let missing = old_outputs
+            .fields
+            .iter()
+            .enumerate()
+            .find(|(i, field)| new_outputs.fields.get(*i) != Some(*field));
+        if let Some((_, field)) = missing {
+            return mismatch(format!("output '{}' is missing or moved", field.name));
+        }
+    }
+
+    if let (Some((old_in, old_out)), Some((new_in, new_out))) = (
+        arity(&current.data.architecture),
+        arity(&replacement.data.architecture),
+    ) {
+        if old_in != new_in {
+            return mismatch(format!("takes {new_in} inputs instead of {old_in}"));
+        }
+        if new_out < old_out {
+            return mismatch(format!("produces {new_out} outputs instead of {old_out}"));
+        }
+    }
+
+    Ok(())
+}
+
+/// Named model slots and the models installed in them
+#[derive(Debug, Default)]
+pub struct ModelSlots {
+    slots: HashMap<String, u64>,
+    revisions: HashMap<String, u32>,
+}
+
+impl ModelSlots {
+    /// Creates an empty slot table
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Model id used for a hash in the [`AiSystem`]
+    #[must_use]
+    pub fn model_id(hash: u64) -> String {
+        format!("apr:{hash:016x}")
+    }
+
+    /// Installs a model into an empty slot, returning its hash
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the slot is already filled (use
+    /// [`Self::swap`]) or the model can't be prepared for inference.
+    pub fn install(&mut self, system: &mut AiSystem, slot: &str, model: AprModel) -> Result<u64> {
+        if self.slots.contains_key(slot) {
+            return Err(AiError::PreconditionsNotMet(format!(
+                "Slot '{slot}' is already filled"
+            )));
+        }
+        let hash = model_hash(&model);
+        system.register_model(&Self::model_id(hash), model)?;
+        let _ = self.slots.insert(slot.to_string(), hash);
+        let _ = self.revisions.insert(slot.to_string(), 0);
+        Ok(hash)
+    }
+
+    /// Replaces the model in a slot for every entity that follows it
+    ///
+    /// The previous model stays registered for entities that pinned it;
+    /// call [`Self::prune`] to unload models nothing uses any more.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AiError::UnknownSlot`] for an empty slot and
+    /// [`AiError::IncompatibleModel`] if the replacement's schemas don't
+    /// fit. On error nothing changes.
+    pub fn swap(
+        &mut self,
+        system: &mut AiSystem,
+        world: &World,
+        slot: &str,
+        model: AprModel,
+    ) -> Result<SwapReport> {
+        let previous = self
+            .hash(slot)
+            .ok_or_else(|| AiError::UnknownSlot(slot.to_string()))?;
+        let current = system
+            .model(&Self::model_id(previous))
+            .ok_or_else(|| AiError::UnknownSlot(slot.to_string()))?;
+        check_compatible(current, &model)?;
+
+        let hash = model_hash(&model);
+        if hash != previous {
+            system.register_model(&Self::model_id(hash), model)?;
+            let _ = self.slots.insert(slot.to_string(), hash);
+            if let Some(revision) = self.revisions.get_mut(slot) {
+                *revision += 1;
+            }
+            log::info!("Swapped model in slot '{slot}': {previous:016x} -> {hash:016x}");
+        }
+        Ok(SwapReport {
+            previous,
+            current: hash,
+            entities: self.users(world, slot),
+        })
+    }
+
+    /// Hash of the model installed in a slot
+    #[must_use]
+    pub fn hash(&self, slot: &str) -> Option<u64> {
+        self.slots.get(slot).copied()
+    }
+
+    /// Number of swaps a slot has seen
+    #[must_use]
+    pub fn revision(&self, slot: &str) -> u32 {
+        self.revisions.get(slot).copied().unwrap_or(0)
+    }
+
+    /// Slot currently holding a model, by hash
+    #[must_use]
+    pub fn find_by_hash(&self, hash: u64) -> Option<&str> {
+        self.slots
+            .iter()
+            .find(|(_, installed)| **installed == hash)
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// [`AiSystem`] model id an entity's slot currently resolves to
+    #[must_use]
+    pub fn resolve(&self, slot: &ModelSlot) -> Option<String> {
+        match slot {
+            ModelSlot::Named(name) => self.hash(name).map(Self::model_id),
+            ModelSlot::Pinned(hash) => Some(Self::model_id(*hash)),
+        }
+    }
+
+    /// Runs inference through a slot
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the slot is empty or the model isn't loaded.
+    pub fn infer(
+        &self,
+        system: &AiSystem,
+        slot: &ModelSlot,
+        inputs: &AiInputs,
+    ) -> Result<AiOutputs> {
+        let id = self.resolve(slot).ok_or_else(|| match slot {
+            ModelSlot::Named(name) => AiError::UnknownSlot(name.clone()),
+            ModelSlot::Pinned(hash) => AiError::UnknownSlot(format!("{hash:016x}")),
+        })?;
+        system.infer(&id, inputs)
+    }
+
+    /// Number of entities following a named slot
+    #[must_use]
+    pub fn users(&self, world: &World, slot: &str) -> usize {
+        world
+            .entities()
+            .filter(|&entity| {
+                matches!(
+                    world.get_component::<ModelSlot>(entity),
+                    Some(ModelSlot::Named(name)) if name == slot
+                )
+            })
+            .count()
+    }
+
+    /// Unloads models no slot holds and no entity pins; returns how many
+    pub fn prune(&self, system: &mut AiSystem, world: &World) -> usize {
+        let mut keep: Vec<u64> = self.slots.values().copied().collect();
+        keep.extend(world.entities().filter_map(|entity| {
+            match world.get_component::<ModelSlot>(entity) {
+                Some(ModelSlot::Pinned(hash)) => Some(*hash),
+                _ => None,
+            }
+        }));
+        let stale: Vec<String> = system
+            .model_ids()
+            .filter(|id| {
+                id.strip_prefix("apr:")
+                    .and_then(|hex| u64::from_str_radix(hex, 16).ok())
+                    .is_some_and(|hash| !keep.contains(&hash))
+            })
+            .map(str::to_string)
+            .collect();
+        stale.iter().filter(|id| system.unload_model(id)).count()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use glam::Vec2;
+    use jugar_apr::{ModelData, SchemaField};
+
+    fn field(name: &str) -> SchemaField {
+        SchemaField {
+            name: name.to_string(),
+            field_type: "f32".to_string(),
+            description: String::new(),
+        }
+    }
+
+    fn card(name: &str, layers: Vec<usize>, inputs: &[&str], outputs: &[&str]) -> AprModel {
+        let mut model = AprModel::new_test_model();
+        model.metadata.name = name.to_string();
+        model.metadata.input_schema = Some(Schema {
+            fields: inputs.iter().map(|n| field(n)).collect(),
+        });
+        model.metadata.output_schema = Some(Schema {
+            fields: outputs.iter().map(|n| field(n)).collect(),
+        });
+        let weights = layers.windows(2).map(|w| w[0] * w[1]).sum();
+        let biases = layers.iter().skip(1).sum();
+        model.data = ModelData {
+            weights: vec![0.5; weights],
+            biases: vec![0.0; biases],
+            architecture: ModelArchitecture::Mlp { layers },
+        };
+        model
+    }
+
+    const INPUTS: [&str; 4] = ["dx", "dy", "distance", "dt"];
+
+    #[test]
+    fn test_swap_changes_every_follower() {
+        let mut system = AiSystem::new();
+        let mut slots = ModelSlots::new();
+        let mut world = World::new();
+        let original = card("ghost", vec![4, 8, 2], &INPUTS, &["move_x", "move_y"]);
+        let first = slots.install(&mut system, "ghost", original).unwrap();
+
+        for _ in 0..3 {
+            let entity = world.spawn();
+            world.add_component(entity, ModelSlot::named("ghost"));
+        }
+        let pinned = world.spawn();
+        world.add_component(pinned, ModelSlot::pinned(first));
+
+        let smarter = card(
+            "smart-ghost",
+            vec![4, 16, 3],
+            &INPUTS,
+            &["move_x", "move_y", "speed"],
+        );
+        let report = slots.swap(&mut system, &world, "ghost", smarter).unwrap();
+        assert_eq!(report.previous, first);
+        assert_ne!(report.current, first);
+        assert_eq!(report.entities, 3);
+        assert_eq!(slots.revision("ghost"), 1);
+        assert_eq!(slots.find_by_hash(report.current), Some("ghost"));
+
+        // Followers resolve to the new card, the pinned entity keeps the old one
+        let follower = slots.resolve(&ModelSlot::named("ghost")).unwrap();
+        assert_eq!(follower, ModelSlots::model_id(report.current));
+        let inputs = AiInputs::from_positions(Vec2::ZERO, Vec2::new(10.0, 0.0), 0.016);
+        assert!(slots
+            .infer(&system, &ModelSlot::pinned(first), &inputs)
+            .is_ok());
+        assert_eq!(slots.prune(&mut system, &world), 0);
+        let _ = world.remove_component::<ModelSlot>(pinned);
+        assert_eq!(slots.prune(&mut system, &world), 1);
+    }
+
+    #[test]
+    fn test_incompatible_swap_is_rejected() {
+        let mut system = AiSystem::new();
+        let mut slots = ModelSlots::new();
+        let world = World::new();
+        let original = card("ghost", vec![4, 8, 2], &INPUTS, &["move_x", "move_y"]);
+        let hash = slots.install(&mut system, "ghost", original).unwrap();
+
+        let wrong_inputs = card(
+            "sniper",
+            vec![4, 8, 2],
+            &["x", "y", "z", "w"],
+            &["move_x", "move_y"],
+        );
+        let result = slots.swap(&mut system, &world, "ghost", wrong_inputs);
+        assert!(matches!(result, Err(AiError::IncompatibleModel(_))));
+
+        let fewer_outputs = card("lazy", vec![4, 8, 1], &INPUTS, &["move_x"]);
+        assert!(slots
+            .swap(&mut system, &world, "ghost", fewer_outputs)
+            .is_err());
+        assert_eq!(slots.hash("ghost"), Some(hash));
+        assert_eq!(slots.revision("ghost"), 0);
+        assert!(matches!(
+            slots.swap(&mut system, &world, "bat", AprModel::new_test_model()),
+            Err(AiError::UnknownSlot(_))
+        ));
+    }
+
+    #[test]
+    fn test_hash_tracks_content() {
+        let a = card("ghost", vec![4, 8, 2], &INPUTS, &["move_x", "move_y"]);
+        let mut b = a.clone();
+        assert_eq!(model_hash(&a), model_hash(&b));
+        b.data.weights[0] = 0.25;
+        assert_ne!(model_hash(&a), model_hash(&b));
+    }
+}
@@ -1,6 +1,8 @@
 //! # jugar-ai
 //!
-//! AI systems for Jugar including Behavior Trees, GOAP, and Aprender integration.
+//! AI systems for Jugar including Behavior Trees, GOAP, and Aprender integration,
+//! plus adaptive difficulty that keeps players in flow and decision tracing for
+//! debugging.
 //!
 //! Per spec Section 5.3: Aprender AI Integration for YAML-first game creation.
 //!
@@ -28,15 +30,32 @@
 #![forbid(unsafe_code)]
 #![warn(missing_docs)]
 
+extern crate alloc;
+
+mod difficulty;
+mod hotswap;
+mod perception;
 mod system;
+mod trace;
 
+use alloc::collections::{BTreeMap, VecDeque};
 use core::fmt;
+use core::hash::{Hash, Hasher};
+use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+pub use difficulty::{
+    AdaptiveDifficulty, DifficultyState, FlowChannel, PerformanceWindow, DEFAULT_DIFFICULTY_LEVELS,
+};
+pub use hotswap::{check_compatible, model_hash, ModelSlot, ModelSlots, SwapReport};
+pub use perception::{
+    Hearing, LineOfSight, OpenSight, PerceivedTarget, Perception, SightCone, SoundStimulus,
+};
 pub use system::{AiComponent, AiInputs, AiOutputs, AiSystem, BehaviorState, YamlAiBridge};
+pub use trace::{AiTrace, Decision, TraceAssertion, TraceEntry, DEFAULT_TRACE_CAPACITY};
 
 /// AI system errors
 #[derive(Error, Debug, Clone, PartialEq, Eq)]
@@ -47,6 +66,12 @@ pub enum AiError {
     /// Action preconditions not met
     #[error("Action preconditions not met: {0}")]
     PreconditionsNotMet(String),
+    /// Replacement model doesn't fit the slot
+    #[error("Incompatible model: {0}")]
+    IncompatibleModel(String),
+    /// No model installed in the slot
+    #[error("No model in slot: {0}")]
+    UnknownSlot(String),
 }
 
 /// Result type for AI operations
@@ -207,9 +232,72 @@ impl Goal {
     }
 }
 
+/// Plans kept by a [`Planner`] unless changed with
+/// [`with_cache_capacity`](Planner::with_cache_capacity)
+pub const DEFAULT_PLAN_CACHE_CAPACITY: usize = 256;
+
+/// Planning counters for debugging
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlanStats {
+    /// Plans asked for through the cache or [`Planner::replan`]
+    pub requests: u64,
+    /// Requests answered from the cache
+    pub cache_hits: u64,
+    /// Requests answered by keeping the rest of the previous plan
+    pub reused: u64,
+    /// Requests that needed a full search
+    pub searches: u64,
+    /// Requests with no plan
+    pub failures: u64,
+    /// Sum of the lengths of returned plans
+    pub total_plan_length: u64,
+    /// Longest plan returned
+    pub longest_plan: usize,
+}
+
+impl PlanStats {
+    /// Fraction of requests that skipped the search
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn hit_rate(&self) -> f32 {
+        if self.requests == 0 {
+            0.0
+        } else {
+            (self.cache_hits + self.reused) as f32 / self.requests as f32
+        }
+    }
+
+    /// Average length of returned plans
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn average_plan_length(&self) -> f32 {
+        let planned = self.requests - self.failures;
+        if planned == 0 {
+            0.0
+        } else {
+            self.total_plan_length as f32 / planned as f32
+        }
+    }
+}
+
+/// Cache key: goal name and a hash of everything the search reads
+type PlanKey = (String, u64);
+
 /// GOAP Planner
+///
+/// [`plan`](Self::plan) always searches. [`plan_cached`](Self::plan_cached)
+/// and [`replan`](Self::replan) remember plans by goal and the facts the
+/// search depends on (goal conditions and action preconditions), so agents
+/// whose worlds differ only in unrelated facts share one search.
 pub struct Planner {
     actions: Vec<Action>,
+    /// Fact keys any action checks, sorted
+    precondition_keys: Vec<String>,
+    cache: BTreeMap<PlanKey, Result<Vec<Action>>>,
+    /// Cache keys oldest first, for eviction
+    cache_order: VecDeque<PlanKey>,
+    cache_capacity: usize,
+    stats: PlanStats,
 }
 
 impl Planner {
@@ -218,12 +306,116 @@ impl Planner {
     pub const fn new() -> Self {
         Self {
             actions: Vec::new(),
+            precondition_keys: Vec::new(),
+            cache: BTreeMap::new(),
+            cache_order: VecDeque::new(),
+            cache_capacity: DEFAULT_PLAN_CACHE_CAPACITY,
+            stats: PlanStats {
+                requests: 0,
+                cache_hits: 0,
+                reused: 0,
+                searches: 0,
+                failures: 0,
+                total_plan_length: 0,
+                longest_plan: 0,
+            },
         }
     }
 
+    /// Sets how many plans the cache keeps (0 disables caching)
+    #[must_use]
+    pub fn with_cache_capacity(mut self, capacity: usize) -> Self {
+        self.cache_capacity = capacity;
+        self.evict();
+        self
+    }
+
     /// Adds an action
+    ///
+    /// Cached plans were found without it, so the cache is cleared.
     pub fn add_action(&mut self, action: Action) {
+        for key in action.preconditions.facts.keys() {
+            if let Err(at) = self.precondition_keys.binary_search(key) {
+                self.precondition_keys.insert(at, key.clone());
+            }
+        }
         self.actions.push(action);
+        self.clear_cache();
+    }
+
+    /// Planning counters since creation or [`reset_stats`](Self::reset_stats)
+    #[must_use]
+    pub const fn stats(&self) -> PlanStats {
+        self.stats
+    }
+
+    /// Zeroes the planning counters
+    pub fn reset_stats(&mut self) {
+        self.stats = PlanStats::default();
+    }
+
+    /// Number of plans in the cache
+    #[must_use]
+    pub fn cached_plans(&self) -> usize {
+        self.cache.len()
+    }
+
+    /// Forgets every cached plan
+    pub fn clear_cache(&mut self) {
+        self.cache.clear();
+        self.cache_order.clear();
+    }
+
+    /// Like [`plan`](Self::plan), but answers repeated requests from the cache
+    ///
+    /// # Errors
+    ///
+    /// Returns `AiError::NoPlanFound` if no valid plan can be found.
+    pub fn plan_cached(&mut self, current_state: &WorldState, goal: &Goal) -> Result<Vec<Action>> {
+        let key = self.cache_key(current_state, goal);
+        let result = if let Some(cached) = self.cache.get(&key) {
+            self.stats.cache_hits += 1;
+            cached.clone()
+        } else {
+            self.stats.searches += 1;
+            let result = self.plan(current_state, goal);
+            if self.cache_capacity > 0 {
+                let _ = self.cache.insert(key.clone(), result.clone());
+                self.cache_order.push_back(key);
+                self.evict();
+            }
+            result
+        };
+        self.record(&result);
+        result
+    }
+
+    /// Plans again after the world changed, keeping what still works
+    ///
+    /// If a tail of `previous` still reaches the goal from `current_state`,
+    /// the shortest such tail is returned without searching, which drops
+    /// steps whose effects already hold. Otherwise this falls back to
+    /// [`plan_cached`](Self::plan_cached).
+    ///
+    /// # Errors
+    ///
+    /// Returns `AiError::NoPlanFound` if no valid plan can be found.
+    pub fn replan(
+        &mut self,
+        current_state: &WorldState,
+        goal: &Goal,
+        previous: &[Action],
+    ) -> Result<Vec<Action>> {
+        if let Some(start) = (0..=previous.len())
+            .rev()
+            .find(|&start| reaches_goal(&previous[start..], current_state, goal))
+        {
+            self.stats.reused += 1;
+            let result = Ok(previous[start..].to_vec());
+            self.record(&result);
+            return result;
+        }
+        self.plan_cached(current_state, goal)
     }
 
     /// Plans a sequence of actions to achieve the goal
@@ -279,6 +471,43 @@ impl Planner {
     }
 }
 
+impl Planner {
+    fn cache_key(&self, state: &WorldState, goal: &Goal) -> PlanKey {
+        let mut hasher = DefaultHasher::new();
+        let mut conditions: Vec<(&String, &bool)> = goal.desired_state.facts.iter().collect();
+        conditions.sort_unstable();
+        for (key, desired) in conditions {
+            (key, desired, state.get(key)).hash(&mut hasher);
+        }
+        for key in &self.precondition_keys {
+            state.get(key).hash(&mut hasher);
+        }
+        (goal.name.clone(), hasher.finish())
+    }
+
+    fn record(&mut self, result: &Result<Vec<Action>>) {
+        self.stats.requests += 1;
+        match result {
+            Ok(plan) => {
+                self.stats.total_plan_length += plan.len() as u64;
+                self.stats.longest_plan = self.stats.longest_plan.max(plan.len());
+            }
+            Err(_) => self.stats.failures += 1,
+        }
+    }
+
+    fn evict(&mut self) {
+        while self.cache.len() > self.cache_capacity {
+            match self.cache_order.pop_front() {
+                Some(oldest) => {
+                    let _ = self.cache.remove(&oldest);
+                }
+                None => break,
+            }
+        }
+    }
+}
+
 impl Default for Planner {
     fn default() -> Self {
         Self::new()
@@ -289,10 +518,25 @@ impl fmt::Debug for Planner {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Planner")
             .field("action_count", &self.actions.len())
-            .finish()
+            .field("cached_plans", &self.cache.len())
+            .field("cache_capacity", &self.cache_capacity)
+            .field("stats", &self.stats)
+            .finish_non_exhaustive()
     }
 }
 
+/// Returns true if running `plan` in order from `state` satisfies `goal`
+fn reaches_goal(plan: &[Action], state: &WorldState, goal: &Goal) -> bool {
+    let mut state = state.clone();
+    for action in plan {
+        if !action.can_run(&state) {
+            return false;
+        }
+        state = action.apply(&state);
+    }
+    goal.is_satisfied(&state)
+}
+
 fn count_satisfied(state: &WorldState, goal: &WorldState) -> i32 {
     goal.facts
         .iter()
@@ -549,6 +793,112 @@ mod tests {
         assert_eq!(plan[1].name, "attack");
     }
 
+    fn armed_planner() -> Planner {
+        let mut planner = Planner::new();
+        planner.add_action(Action::new("pickup_weapon").with_effect("has_weapon", true));
+        planner.add_action(
+            Action::new("attack")
+                .with_precondition("has_weapon", true)
+                .with_effect("enemy_dead", true),
+        );
+        planner
+    }
+
+    #[test]
+    fn test_plan_cache_ignores_unrelated_facts() {
+        let mut planner = armed_planner();
+        let goal = Goal::new("win").with_condition("enemy_dead", true);
+
+        let mut state = WorldState::new();
+        let first = planner.plan_cached(&state, &goal).unwrap();
+        state.set("is_raining", true);
+        let second = planner.plan_cached(&state, &goal).unwrap();
+        assert_eq!(first, second);
+
+        // A fact an action checks changes the plan
+        state.set("has_weapon", true);
+        let third = planner.plan_cached(&state, &goal).unwrap();
+        assert_eq!(third.len(), 1);
+
+        let stats = planner.stats();
+        assert_eq!(stats.requests, 3);
+        assert_eq!(stats.cache_hits, 1);
+        assert_eq!(stats.searches, 2);
+        assert_eq!(stats.longest_plan, 2);
+        assert!((stats.average_plan_length() - 5.0 / 3.0).abs() < 1e-6);
+        assert_eq!(planner.cached_plans(), 2);
+    }
+
+    #[test]
+    fn test_plan_cache_separates_goals_and_conditions() {
+        let mut planner = armed_planner();
+        let state = WorldState::new();
+        let win = Goal::new("win").with_condition("enemy_dead", true);
+        let armed = Goal::new("win").with_condition("has_weapon", true);
+
+        assert_eq!(planner.plan_cached(&state, &win).unwrap().len(), 2);
+        assert_eq!(planner.plan_cached(&state, &armed).unwrap().len(), 1);
+        assert_eq!(planner.stats().cache_hits, 0);
+    }
+
+    #[test]
+    fn test_plan_cache_remembers_failures_and_clears_on_new_action() {
+        let mut planner = Planner::new();
+        let state = WorldState::new();
+        let goal = Goal::new("magic").with_condition("has_magic", true);
+
+        assert!(planner.plan_cached(&state, &goal).is_err());
+        assert!(planner.plan_cached(&state, &goal).is_err());
+        assert_eq!(planner.stats().failures, 2);
+        assert_eq!(planner.stats().cache_hits, 1);
+
+        planner.add_action(Action::new("learn_spell").with_effect("has_magic", true));
+        assert_eq!(planner.cached_plans(), 0);
+        assert_eq!(planner.plan_cached(&state, &goal).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_plan_cache_capacity_evicts_oldest() {
+        let mut planner = armed_planner().with_cache_capacity(1);
+        let state = WorldState::new();
+        let win = Goal::new("win").with_condition("enemy_dead", true);
+        let armed = Goal::new("armed").with_condition("has_weapon", true);
+
+        let _ = planner.plan_cached(&state, &win);
+        let _ = planner.plan_cached(&state, &armed);
+        let _ = planner.plan_cached(&state, &win);
+        assert_eq!(planner.cached_plans(), 1);
+        assert_eq!(planner.stats().cache_hits, 0);
+
+        let mut uncached = armed_planner().with_cache_capacity(0);
+        let _ = uncached.plan_cached(&state, &win);
+        assert_eq!(uncached.cached_plans(), 0);
+    }
+
+    #[test]
+    fn test_replan_keeps_remaining_steps() {
+        let mut planner = armed_planner();
+        let goal = Goal::new("win").with_condition("enemy_dead", true);
+        let mut state = WorldState::new();
+        let plan = planner.plan_cached(&state, &goal).unwrap();
+
+        // First step done: the rest of the plan still works
+        state.set("has_weapon", true);
+        state.set("is_raining", true);
+        let rest = planner.replan(&state, &goal, &plan).unwrap();
+        assert_eq!(rest.len(), 1);
+        assert_eq!(rest[0].name, "attack");
+        assert_eq!(planner.stats().reused, 1);
+        assert_eq!(planner.stats().searches, 1);
+
+        // Weapon lost: "attack" alone no longer works, so plan again
+        state.set("has_weapon", false);
+        let again = planner.replan(&state, &goal, &rest).unwrap();
+        assert_eq!(again.len(), 2);
+        assert_eq!(planner.stats().cache_hits, 1);
+        assert!((planner.stats().hit_rate() - 2.0 / 3.0).abs() < 1e-6);
+    }
+
     #[test]
     fn test_node_status() {
         assert_ne!(NodeStatus::Running, NodeStatus::Success);
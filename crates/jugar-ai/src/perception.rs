@@ -0,0 +1,420 @@
+//! Perception for AI agents: sight cones, hearing and short-term memory.
+//!
+//! Perception is engine-agnostic. Obstacles are queried through the
+//! [`LineOfSight`] trait, which jugar-physics implements for its world with a
+//! raycast, and hearing consumes the [`SoundStimulus`] events that the audio
+//! system emits for every sound it plays.
+//! Results are written into a GOAP [`WorldState`] as `<target>_visible`,
+//! `<target>_heard` and `<target>_remembered` facts.
+
+use std::collections::HashMap;
+
+use glam::Vec2;
+use serde::{Deserialize, Serialize};
+
+use crate::WorldState;
+
+pub use jugar_core::{LineOfSight, OpenSight, SoundStimulus};
+
+/// A vision cone in front of an agent.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SightCone {
+    /// Maximum view distance
+    pub range: f32,
+    /// Half of the field-of-view angle in radians
+    pub half_angle: f32,
+}
+
+impl SightCone {
+    /// Creates a sight cone from a range and full field of view in radians
+    #[must_use]
+    pub fn new(range: f32, fov: f32) -> Self {
+        Self {
+            range,
+            half_angle: fov * 0.5,
+        }
+    }
+
+    /// Checks whether `target` lies inside the cone and is not occluded
+    #[must_use]
+    pub fn can_see(
+        &self,
+        origin: Vec2,
+        facing: Vec2,
+        target: Vec2,
+        sight: &impl LineOfSight,
+    ) -> bool {
+        let to_target = target - origin;
+        let distance = to_target.length();
+        if distance > self.range {
+            return false;
+        }
+        if distance > f32::EPSILON {
+            let facing = facing.normalize_or_zero();
+            if facing != Vec2::ZERO {
+                let cos = facing.dot(to_target / distance).clamp(-1.0, 1.0);
+                if cos.acos() > self.half_angle {
+                    return false;
+                }
+            }
+        }
+        sight.is_clear(origin, target)
+    }
+}
+
+impl Default for SightCone {
+    fn default() -> Self {
+        Self::new(10.0, core::f32::consts::FRAC_PI_2)
+    }
+}
+
+/// Hearing sense with linear falloff.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Hearing {
+    /// Distance at which a sound of loudness 1.0 becomes silent
+    pub range: f32,
+    /// Minimum perceived loudness that counts as heard
+    pub threshold: f32,
+}
+
+impl Hearing {
+    /// Creates a new hearing sense
+    #[must_use]
+    pub const fn new(range: f32, threshold: f32) -> Self {
+        Self { range, threshold }
+    }
+
+    /// Returns the loudness of a stimulus as perceived at `listener`
+    #[must_use]
+    pub fn perceived_loudness(&self, listener: Vec2, stimulus: &SoundStimulus) -> f32 {
+        if self.range <= 0.0 {
+            return 0.0;
+        }
+        let falloff = 1.0 - listener.distance(stimulus.position) / self.range;
+        stimulus.loudness * falloff.max(0.0)
+    }
+
+    /// Checks whether the stimulus is loud enough to be heard
+    #[must_use]
+    pub fn can_hear(&self, listener: Vec2, stimulus: &SoundStimulus) -> bool {
+        self.perceived_loudness(listener, stimulus) >= self.threshold
+    }
+}
+
+impl Default for Hearing {
+    fn default() -> Self {
+        Self::new(15.0, 0.1)
+    }
+}
+
+/// What an agent remembers about a target.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PerceivedTarget {
+    /// Last known position
+    pub last_known: Vec2,
+    /// Seconds since the target was last seen or heard
+    pub age: f32,
+    /// Seen during the latest update
+    pub visible: bool,
+    /// Heard during the latest update
+    pub heard: bool,
+}
+
+impl PerceivedTarget {
+    /// Confidence in the memory, decaying linearly over `memory_duration`
+    #[must_use]
+    pub fn confidence(&self, memory_duration: f32) -> f32 {
+        if memory_duration <= 0.0 {
+            return if self.age <= 0.0 { 1.0 } else { 0.0 };
+        }
+        (1.0 - self.age / memory_duration).clamp(0.0, 1.0)
+    }
+}
+
+/// Combined sight, hearing and memory for a single agent.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Perception {
+    /// Vision cone
+    pub sight: SightCone,
+    /// Hearing sense
+    pub hearing: Hearing,
+    /// Seconds a target is remembered after it was last perceived
+    pub memory_duration: f32,
+    memory: HashMap<String, PerceivedTarget>,
+}
+
+impl Perception {
+    /// Creates a perception component with default senses
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            sight: SightCone::default(),
+            hearing: Hearing::default(),
+            memory_duration: 5.0,
+            memory: HashMap::new(),
+        }
+    }
+
+    /// Sets the sight cone
+    #[must_use]
+    pub const fn with_sight(mut self, sight: SightCone) -> Self {
+        self.sight = sight;
+        self
+    }
+
+    /// Sets the hearing sense
+    #[must_use]
+    pub const fn with_hearing(mut self, hearing: Hearing) -> Self {
+        self.hearing = hearing;
+        self
+    }
+
+    /// Sets how long targets are remembered
+    #[must_use]
+    pub const fn with_memory_duration(mut self, seconds: f32) -> Self {
+        self.memory_duration = seconds;
+        self
+    }
+
+    /// Updates perception for one tick.
+    ///
+    /// `targets` are the candidate entities `(id, position)` this agent may see.
+    /// Memories older than `memory_duration` are forgotten.
+    pub fn update(
+        &mut self,
+        dt: f32,
+        origin: Vec2,
+        facing: Vec2,
+        targets: &[(&str, Vec2)],
+        sounds: &[SoundStimulus],
+        sight: &impl LineOfSight,
+    ) {
+        for entry in self.memory.values_mut() {
+            entry.age += dt;
+            entry.visible = false;
+            entry.heard = false;
+        }
+
+        for &(id, position) in targets {
+            if self.sight.can_see(origin, facing, position, sight) {
+                let entry = self.remember(id, position);
+                entry.visible = true;
+            }
+        }
+
+        for stimulus in sounds {
+            if self.hearing.can_hear(origin, stimulus) {
+                let entry = self.remember(&stimulus.source, stimulus.position);
+                entry.heard = true;
+            }
+        }
+
+        let duration = self.memory_duration;
+        self.memory.retain(|_, entry| entry.age <= duration);
+    }
+
+    fn remember(&mut self, id: &str, position: Vec2) -> &mut PerceivedTarget {
+        let entry = self
+            .memory
+            .entry(id.to_string())
+            .or_insert(PerceivedTarget {
+                last_known: position,
+                age: 0.0,
+                visible: false,
+                heard: false,
+            });
+        entry.last_known = position;
+        entry.age = 0.0;
+        entry
+    }
+
+    /// Returns what is remembered about a target
+    #[must_use]
+    pub fn get(&self, id: &str) -> Option<&PerceivedTarget> {
+        self.memory.get(id)
+    }
+
+    /// Returns the last known position of a target
+    #[must_use]
+    pub fn last_known_position(&self, id: &str) -> Option<Vec2> {
+        self.memory.get(id).map(|entry| entry.last_known)
+    }
+
+    /// Checks whether a target was seen this tick
+    #[must_use]
+    pub fn is_visible(&self, id: &str) -> bool {
+        self.memory.get(id).is_some_and(|entry| entry.visible)
+    }
+
+    /// Number of remembered targets
+    #[must_use]
+    pub fn memory_count(&self) -> usize {
+        self.memory.len()
+    }
+
+    /// Forgets all remembered targets
+    pub fn forget_all(&mut self) {
+        self.memory.clear();
+    }
+
+    /// Writes perception facts for the given targets into a GOAP world state.
+    ///
+    /// Sets `<id>_visible`, `<id>_heard` and `<id>_remembered` for each id.
+    pub fn write_facts(&self, state: &mut WorldState, ids: &[&str]) {
+        for &id in ids {
+            let entry = self.memory.get(id);
+            state.set(format!("{id}_visible"), entry.is_some_and(|e| e.visible));
+            state.set(format!("{id}_heard"), entry.is_some_and(|e| e.heard));
+            state.set(format!("{id}_remembered"), entry.is_some());
+        }
+    }
+}
+
+impl Default for Perception {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sight_cone_in_front() {
+        let cone = SightCone::new(10.0, core::f32::consts::FRAC_PI_2);
+        assert!(cone.can_see(Vec2::ZERO, Vec2::X, Vec2::new(5.0, 1.0), &OpenSight));
+    }
+
+    #[test]
+    fn test_sight_cone_behind() {
+        let cone = SightCone::new(10.0, core::f32::consts::FRAC_PI_2);
+        assert!(!cone.can_see(Vec2::ZERO, Vec2::X, Vec2::new(-5.0, 0.0), &OpenSight));
+    }
+
+    #[test]
+    fn test_sight_cone_out_of_range() {
+        let cone = SightCone::new(10.0, core::f32::consts::PI);
+        assert!(!cone.can_see(Vec2::ZERO, Vec2::X, Vec2::new(20.0, 0.0), &OpenSight));
+    }
+
+    #[test]
+    fn test_sight_blocked_by_obstacle() {
+        let cone = SightCone::default();
+        let wall = |from: Vec2, to: Vec2| !(from.x < 2.0 && to.x > 2.0);
+        assert!(!cone.can_see(Vec2::ZERO, Vec2::X, Vec2::new(5.0, 0.0), &wall));
+        assert!(cone.can_see(Vec2::ZERO, Vec2::X, Vec2::new(1.0, 0.0), &wall));
+    }
+
+    #[test]
+    fn test_hearing_threshold() {
+        let hearing = Hearing::new(10.0, 0.3);
+        let near = SoundStimulus::new("player", Vec2::new(2.0, 0.0), 1.0);
+        let far = SoundStimulus::new("player", Vec2::new(9.0, 0.0), 1.0);
+        assert!(hearing.can_hear(Vec2::ZERO, &near));
+        assert!(!hearing.can_hear(Vec2::ZERO, &far));
+    }
+
+    #[test]
+    fn test_hearing_quiet_sound() {
+        let hearing = Hearing::new(10.0, 0.3);
+        let whisper = SoundStimulus::new("player", Vec2::new(1.0, 0.0), 0.2);
+        assert!(!hearing.can_hear(Vec2::ZERO, &whisper));
+    }
+
+    #[test]
+    fn test_perception_sees_target() {
+        let mut perception = Perception::new();
+        perception.update(
+            0.1,
+            Vec2::ZERO,
+            Vec2::X,
+            &[("player", Vec2::new(3.0, 0.0))],
+            &[],
+            &OpenSight,
+        );
+        assert!(perception.is_visible("player"));
+        assert_eq!(
+            perception.last_known_position("player"),
+            Some(Vec2::new(3.0, 0.0))
+        );
+    }
+
+    #[test]
+    fn test_perception_memory_decays() {
+        let mut perception = Perception::new().with_memory_duration(1.0);
+        perception.update(
+            0.1,
+            Vec2::ZERO,
+            Vec2::X,
+            &[("player", Vec2::new(3.0, 0.0))],
+            &[],
+            &OpenSight,
+        );
+        perception.update(0.5, Vec2::ZERO, Vec2::X, &[], &[], &OpenSight);
+        let memory = perception.get("player").unwrap();
+        assert!(!memory.visible);
+        assert!((memory.confidence(1.0) - 0.5).abs() < 0.001);
+
+        perception.update(0.6, Vec2::ZERO, Vec2::X, &[], &[], &OpenSight);
+        assert_eq!(perception.memory_count(), 0);
+    }
+
+    #[test]
+    fn test_perception_hears_target_behind() {
+        let mut perception = Perception::new();
+        let sounds = [SoundStimulus::new("player", Vec2::new(-2.0, 0.0), 1.0)];
+        perception.update(
+            0.1,
+            Vec2::ZERO,
+            Vec2::X,
+            &[("player", Vec2::new(-2.0, 0.0))],
+            &sounds,
+            &OpenSight,
+        );
+        let memory = perception.get("player").unwrap();
+        assert!(!memory.visible);
+        assert!(memory.heard);
+    }
+
+    #[test]
+    fn test_perception_writes_world_state() {
+        let mut perception = Perception::new().with_memory_duration(2.0);
+        perception.update(
+            0.1,
+            Vec2::ZERO,
+            Vec2::X,
+            &[("player", Vec2::new(3.0, 0.0))],
+            &[],
+            &OpenSight,
+        );
+        let mut state = WorldState::new();
+        perception.write_facts(&mut state, &["player", "ghost"]);
+        assert!(state.get("player_visible"));
+        assert!(state.get("player_remembered"));
+        assert!(!state.get("player_heard"));
+        assert!(!state.get("ghost_remembered"));
+
+        perception.update(0.5, Vec2::ZERO, Vec2::X, &[], &[], &OpenSight);
+        perception.write_facts(&mut state, &["player"]);
+        assert!(!state.get("player_visible"));
+        assert!(state.get("player_remembered"));
+    }
+
+    #[test]
+    fn test_perception_forget_all() {
+        let mut perception = Perception::default();
+        perception.update(
+            0.1,
+            Vec2::ZERO,
+            Vec2::X,
+            &[("a", Vec2::X), ("b", Vec2::new(1.0, 0.5))],
+            &[],
+            &OpenSight,
+        );
+        assert_eq!(perception.memory_count(), 2);
+        perception.forget_all();
+        assert_eq!(perception.memory_count(), 0);
+    }
+}
@@ -376,6 +376,17 @@ impl AiSystem {
         self.models.len()
     }
 
+    /// Get a loaded model
+    #[must_use]
+    pub fn model(&self, id: &str) -> Option<&AprModel> {
+        self.models.get(id).map(|loaded| &loaded.model)
+    }
+
+    /// Ids of all loaded models
+    pub fn model_ids(&self) -> impl Iterator<Item = &str> {
+        self.models.keys().map(String::as_str)
+    }
+
     /// Remove a model
     pub fn unload_model(&mut self, id: &str) -> bool {
         self.models.remove(id).is_some()
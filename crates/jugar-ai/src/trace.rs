@@ -0,0 +1,377 @@
+//! Recording AI decisions for debugging and tests.
+//!
+//! An [`AiTrace`] keeps what every agent decided on every tick: the behavior
+//! tree path it ran, the GOAP plan it is following or the utility scores it
+//! weighed, plus the path it is steering along. The newest entries are kept
+//! in a ring buffer, so a trace can stay on for a whole play session and be
+//! exported as JSON when something looks wrong.
+//!
+//! Traces can also be checked. Each check returns a [`TraceAssertion`]
+//! shaped like probar's `Assertion`, so a probar test reads as a sentence:
+//!
+//! ```
+//! use jugar_ai::{AiTrace, Decision, TraceEntry};
+//!
+//! let mut trace = AiTrace::new(256);
+//! for tick in 0..60_u64 {
+//!     let time = tick as f64 / 60.0;
+//!     let decision = Decision::BehaviorPath(vec!["root".into(), "chase".into()]);
+//!     trace.record(TraceEntry::new("ghost", tick, time, decision));
+//! }
+//! let check = trace.assert_no_oscillation("ghost", "chase", "flee", 2);
+//! assert!(check.passed, "{}", check.message);
+//! ```
+
+use alloc::collections::VecDeque;
+
+use glam::Vec2;
+use serde::{Deserialize, Serialize};
+
+use crate::Action;
+
+/// Entries kept by [`AiTrace::default`]
+pub const DEFAULT_TRACE_CAPACITY: usize = 4096;
+
+/// What an agent decided on one tick
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Decision {
+    /// Behavior tree nodes from the root to the leaf that ran
+    BehaviorPath(Vec<String>),
+    /// GOAP actions still to run, next first
+    Plan(Vec<String>),
+    /// Score of every option a utility AI weighed
+    Utility(Vec<(String, f32)>),
+}
+
+impl Decision {
+    /// A GOAP plan, by action name
+    #[must_use]
+    pub fn plan(actions: &[Action]) -> Self {
+        Self::Plan(actions.iter().map(|action| action.name.clone()).collect())
+    }
+
+    /// The behavior that was picked: the leaf, the next action, or the
+    /// highest score
+    #[must_use]
+    pub fn chosen(&self) -> Option<&str> {
+        match self {
+            Self::BehaviorPath(path) => path.last(),
+            Self::Plan(actions) => actions.first(),
+            Self::Utility(scores) => scores
+                .iter()
+                .filter(|(_, score)| !score.is_nan())
+                .max_by(|a, b| a.1.total_cmp(&b.1))
+                .map(|(option, _)| option),
+        }
+        .map(String::as_str)
+    }
+}
+
+/// One agent's decision on one tick
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TraceEntry {
+    /// Agent that decided
+    pub agent: String,
+    /// Game tick
+    pub tick: u64,
+    /// Game time in seconds
+    pub time: f64,
+    /// What was decided
+    pub decision: Decision,
+    /// Waypoints the agent is steering along, nearest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub path: Vec<Vec2>,
+}
+
+impl TraceEntry {
+    /// Creates an entry with no navigation path
+    #[must_use]
+    pub fn new(agent: impl Into<String>, tick: u64, time: f64, decision: Decision) -> Self {
+        Self {
+            agent: agent.into(),
+            tick,
+            time,
+            decision,
+            path: Vec::new(),
+        }
+    }
+
+    /// Sets the waypoints the agent is steering along
+    #[must_use]
+    pub fn with_path(mut self, path: Vec<Vec2>) -> Self {
+        self.path = path;
+        self
+    }
+}
+
+/// Outcome of checking a trace
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceAssertion {
+    /// Whether the trace passed
+    pub passed: bool,
+    /// What was checked, and what broke it if it failed
+    pub message: String,
+}
+
+impl TraceAssertion {
+    const fn check(passed: bool, message: String) -> Self {
+        Self { passed, message }
+    }
+}
+
+/// Ring buffer of AI decisions
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AiTrace {
+    capacity: usize,
+    entries: VecDeque<TraceEntry>,
+    dropped: u64,
+}
+
+impl Default for AiTrace {
+    fn default() -> Self {
+        Self::new(DEFAULT_TRACE_CAPACITY)
+    }
+}
+
+impl AiTrace {
+    /// Creates a trace keeping the newest `capacity` entries
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            entries: VecDeque::new(),
+            dropped: 0,
+        }
+    }
+
+    /// Records an entry, dropping the oldest if the trace is full
+    pub fn record(&mut self, entry: TraceEntry) {
+        if self.entries.len() == self.capacity {
+            let _ = self.entries.pop_front();
+            self.dropped += 1;
+        }
+        self.entries.push_back(entry);
+    }
+
+    /// Entries kept, oldest first
+    pub fn entries(&self) -> impl Iterator<Item = &TraceEntry> {
+        self.entries.iter()
+    }
+
+    /// Entries kept for one agent, oldest first
+    pub fn agent<'a>(&'a self, agent: &'a str) -> impl Iterator<Item = &'a TraceEntry> + 'a {
+        self.entries
+            .iter()
+            .filter(move |entry| entry.agent == agent)
+    }
+
+    /// Number of entries kept
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if nothing is kept
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Number of entries dropped to make room
+    #[must_use]
+    pub const fn dropped(&self) -> u64 {
+        self.dropped
+    }
+
+    /// Forgets every entry
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.dropped = 0;
+    }
+
+    /// Exports the kept entries as a JSON array
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(&self.entries).unwrap_or_else(|_| "[]".to_string())
+    }
+
+    /// Times at which `agent` switched between behaviors `a` and `b`
+    ///
+    /// Ticks choosing anything else in between do not break a switch, so
+    /// chase, idle, flee counts as one.
+    #[must_use]
+    pub fn switches(&self, agent: &str, a: &str, b: &str) -> Vec<f64> {
+        let mut last = None;
+        let mut times = Vec::new();
+        for entry in self.agent(agent) {
+            let Some(chosen) = entry.decision.chosen().filter(|&c| c == a || c == b) else {
+                continue;
+            };
+            if last.is_some_and(|last| last != chosen) {
+                times.push(entry.time);
+            }
+            last = Some(chosen);
+        }
+        times
+    }
+
+    /// Checks `agent` never switches between `a` and `b` more than
+    /// `max_per_second` times within one second
+    #[must_use]
+    pub fn assert_no_oscillation(
+        &self,
+        agent: &str,
+        a: &str,
+        b: &str,
+        max_per_second: usize,
+    ) -> TraceAssertion {
+        let times = self.switches(agent, a, b);
+        let burst = times
+            .windows(max_per_second + 1)
+            .find(|window| window[max_per_second] - window[0] < 1.0);
+        burst.map_or_else(
+            || {
+                TraceAssertion::check(
+                    true,
+                    format!(
+                        "{agent} switched between {a} and {b} at most {max_per_second} times a second"
+                    ),
+                )
+            },
+            |window| {
+                TraceAssertion::check(
+                    false,
+                    format!(
+                        "{agent} switched between {a} and {b} {} times from {:.2}s to {:.2}s",
+                        window.len(),
+                        window[0],
+                        window[max_per_second]
+                    ),
+                )
+            },
+        )
+    }
+
+    /// Checks `agent` never chose `behavior`
+    #[must_use]
+    pub fn assert_never_chooses(&self, agent: &str, behavior: &str) -> TraceAssertion {
+        let first = self
+            .agent(agent)
+            .find(|entry| entry.decision.chosen() == Some(behavior));
+        first.map_or_else(
+            || TraceAssertion::check(true, format!("{agent} never chose {behavior}")),
+            |entry| {
+                TraceAssertion::check(
+                    false,
+                    format!("{agent} chose {behavior} at tick {}", entry.tick),
+                )
+            },
+        )
+    }
+
+    /// Checks `agent` decided something on every tick from its first entry
+    /// to its last
+    #[must_use]
+    pub fn assert_every_tick(&self, agent: &str) -> TraceAssertion {
+        let mut ticks = self.agent(agent).map(|entry| entry.tick);
+        let Some(mut previous) = ticks.next() else {
+            return TraceAssertion::check(false, format!("{agent} has no entries"));
+        };
+        for tick in ticks {
+            if tick > previous + 1 {
+                return TraceAssertion::check(
+                    false,
+                    format!("{agent} skipped ticks {} to {}", previous + 1, tick - 1),
+                );
+            }
+            previous = tick;
+        }
+        TraceAssertion::check(true, format!("{agent} decided on every tick"))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn path(leaf: &str) -> Decision {
+        Decision::BehaviorPath(vec!["root".to_string(), leaf.to_string()])
+    }
+
+    #[test]
+    fn test_decisions_name_the_chosen_behavior() {
+        assert_eq!(path("chase").chosen(), Some("chase"));
+        let plan = Decision::plan(&[Action::new("open_door"), Action::new("grab_key")]);
+        assert_eq!(plan.chosen(), Some("open_door"));
+        let utility = Decision::Utility(vec![
+            ("flee".to_string(), 0.2),
+            ("chase".to_string(), 0.7),
+            ("broken".to_string(), f32::NAN),
+        ]);
+        assert_eq!(utility.chosen(), Some("chase"));
+        assert_eq!(Decision::Plan(Vec::new()).chosen(), None);
+    }
+
+    #[test]
+    fn test_ring_buffer_and_json() {
+        let mut trace = AiTrace::new(3);
+        for tick in 0..5 {
+            trace.record(
+                TraceEntry::new("bat", tick, tick as f64 * 0.5, path("wander"))
+                    .with_path(vec![Vec2::new(1.0, 2.0)]),
+            );
+        }
+        assert_eq!(trace.len(), 3);
+        assert_eq!(trace.dropped(), 2);
+        assert_eq!(trace.entries().next().unwrap().tick, 2);
+
+        let exported: Vec<TraceEntry> = serde_json::from_str(&trace.to_json()).unwrap();
+        assert_eq!(exported, trace.entries().cloned().collect::<Vec<_>>());
+        assert!(trace.assert_every_tick("bat").passed);
+    }
+
+    #[test]
+    fn test_oscillation_is_caught() {
+        let mut trace = AiTrace::default();
+        // Steady for a second, then flipping every tenth of a second
+        for tick in 0..20_u64 {
+            let leaf = if tick < 10 || tick % 2 == 0 {
+                "chase"
+            } else {
+                "flee"
+            };
+            trace.record(TraceEntry::new(
+                "ghost",
+                tick,
+                tick as f64 * 0.1,
+                path(leaf),
+            ));
+            trace.record(TraceEntry::new(
+                "bat",
+                tick,
+                tick as f64 * 0.1,
+                path("chase"),
+            ));
+        }
+        assert_eq!(trace.switches("ghost", "chase", "flee").len(), 9);
+
+        let check = trace.assert_no_oscillation("ghost", "chase", "flee", 2);
+        assert!(!check.passed);
+        assert!(check.message.contains("ghost"));
+        assert!(
+            trace
+                .assert_no_oscillation("bat", "chase", "flee", 2)
+                .passed
+        );
+        assert!(
+            trace
+                .assert_no_oscillation("ghost", "chase", "flee", 10)
+                .passed
+        );
+
+        assert!(!trace.assert_never_chooses("ghost", "flee").passed);
+        assert!(trace.assert_never_chooses("bat", "flee").passed);
+        assert!(!trace.assert_every_tick("owl").passed);
+    }
+}
@@ -92,6 +92,36 @@ pub enum AprError {
         field: &'static str,
     },
 
+    /// Model reference couldn't be parsed
+    #[error("Invalid model reference: '{reference}' (use builtin:, bundle: or https:)")]
+    InvalidReference {
+        /// The reference as written
+        reference: String,
+    },
+
+    /// Referenced model isn't available
+    #[error("Model not found: {reference}")]
+    NotFound {
+        /// The reference or URL
+        reference: String,
+    },
+
+    /// Model file has a different size than pinned
+    #[error("Model size mismatch: expected {expected} bytes, got {actual}")]
+    SizeMismatch {
+        /// Pinned size
+        expected: usize,
+        /// Actual size
+        actual: usize,
+    },
+
+    /// Remote model without a checksum pin
+    #[error("Remote model must be pinned with #crc32=...: {url}")]
+    UnpinnedRemote {
+        /// The URL
+        url: String,
+    },
+
     /// I/O error
     #[error("I/O error: {0}")]
     Io(#[from] std::io::Error),
@@ -30,15 +30,22 @@
 #![warn(clippy::nursery)]
 #![allow(clippy::module_name_repetitions)]
 
+extern crate alloc;
+
 mod error;
 mod format;
 mod metadata;
 mod model;
+mod repository;
 
 pub use error::AprError;
 pub use format::{AprFile, APR_MAGIC, APR_VERSION};
-pub use metadata::AprMetadata;
+pub use metadata::{AprMetadata, Schema, SchemaField};
 pub use model::{AprModel, ModelArchitecture, ModelData};
+pub use repository::{
+    AprRepository, FetchBackend, FileSystemFetch, MemoryFetch, ModelRef, ModelSource,
+    DEFAULT_CACHE_CAPACITY,
+};
 
 /// Maximum allowed model size (1 MB per spec Section 9.1)
 pub const MAX_MODEL_SIZE: usize = 1024 * 1024;
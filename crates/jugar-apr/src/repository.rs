@@ -0,0 +1,475 @@
+//! Resolving model references from builtins, game bundles and the web.
+//!
+//! Level 3 games refer to models with short references:
+//!
+//! ```text
+//! builtin:chase
+//! bundle:models/ghost.apr
+//! https://models.example.org/ghost.apr#crc32=1a2b3c4d&size=2048
+//! ```
+//!
+//! [`AprRepository`] resolves them, verifies size and CRC32 when the
+//! reference pins them and keeps recently used models in an LRU cache.
+//! Downloads go through a [`FetchBackend`]: the browser host preloads
+//! responses into a [`MemoryFetch`], native tools mirror them on disk with
+//! [`FileSystemFetch`]. Remote models must be pinned by checksum unless the
+//! repository is told otherwise.
+
+use alloc::sync::Arc;
+use core::fmt;
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+
+use crate::error::AprError;
+use crate::format::AprFile;
+use crate::model::AprModel;
+use crate::MAX_MODEL_SIZE;
+
+/// Models kept in the cache by default
+pub const DEFAULT_CACHE_CAPACITY: usize = 16;
+
+/// Where a referenced model comes from
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ModelSource {
+    /// Shipped with the engine (`builtin:chase`)
+    Builtin(String),
+    /// Embedded in the game's .jugar bundle (`bundle:models/ghost.apr`)
+    Bundle(String),
+    /// Downloaded over HTTPS
+    Https(String),
+}
+
+/// A parsed model reference with optional integrity pins
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ModelRef {
+    /// Where the model comes from
+    pub source: ModelSource,
+    /// Expected CRC32 of the whole .apr file
+    pub checksum: Option<u32>,
+    /// Expected file size in bytes
+    pub size: Option<usize>,
+}
+
+impl ModelRef {
+    /// Parses a reference string
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AprError::InvalidReference`] for unknown schemes (including
+    /// plain `http:`), empty names and malformed `#crc32=`/`size=` pins.
+    pub fn parse(reference: &str) -> Result<Self, AprError> {
+        let invalid = || AprError::InvalidReference {
+            reference: reference.to_string(),
+        };
+        let (location, pins) = reference
+            .split_once('#')
+            .map_or((reference, None), |(location, pins)| (location, Some(pins)));
+
+        let source = if let Some(name) = location.strip_prefix("builtin:") {
+            ModelSource::Builtin(name.to_string())
+        } else if let Some(name) = location.strip_prefix("bundle:") {
+            ModelSource::Bundle(name.trim_start_matches('/').to_string())
+        } else if location.starts_with("https://") {
+            ModelSource::Https(location.to_string())
+        } else {
+            return Err(invalid());
+        };
+        let name = match &source {
+            ModelSource::Builtin(name) | ModelSource::Bundle(name) => name.as_str(),
+            ModelSource::Https(url) => url.trim_start_matches("https://"),
+        };
+        if name.is_empty() {
+            return Err(invalid());
+        }
+
+        let mut parsed = Self {
+            source,
+            checksum: None,
+            size: None,
+        };
+        for pin in pins.into_iter().flat_map(|pins| pins.split('&')) {
+            match pin.split_once('=') {
+                Some(("crc32", hex)) => {
+                    parsed.checksum = Some(u32::from_str_radix(hex, 16).map_err(|_| invalid())?);
+                }
+                Some(("size", bytes)) => {
+                    parsed.size = Some(bytes.parse().map_err(|_| invalid())?);
+                }
+                _ => return Err(invalid()),
+            }
+        }
+        Ok(parsed)
+    }
+
+    /// Checks downloaded or embedded bytes against the pins
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AprError::ModelTooLarge`] or [`AprError::SizeMismatch`]
+    /// for a wrong size and [`AprError::ChecksumMismatch`] for a wrong CRC32.
+    pub fn verify(&self, bytes: &[u8]) -> Result<(), AprError> {
+        if bytes.len() > MAX_MODEL_SIZE {
+            return Err(AprError::ModelTooLarge {
+                size: bytes.len(),
+                max: MAX_MODEL_SIZE,
+            });
+        }
+        if let Some(expected) = self.size {
+            if bytes.len() != expected {
+                return Err(AprError::SizeMismatch {
+                    expected,
+                    actual: bytes.len(),
+                });
+            }
+        }
+        if let Some(expected) = self.checksum {
+            let computed = crc32fast::hash(bytes);
+            if computed != expected {
+                return Err(AprError::ChecksumMismatch { expected, computed });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ModelRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.source {
+            ModelSource::Builtin(name) => write!(f, "builtin:{name}"),
+            ModelSource::Bundle(name) => write!(f, "bundle:{name}"),
+            ModelSource::Https(url) => write!(f, "{url}"),
+        }
+    }
+}
+
+/// Downloads remote models
+pub trait FetchBackend: fmt::Debug {
+    /// Fetches the bytes at an HTTPS URL, refusing more than `max_bytes`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AprError::NotFound`] if the model isn't available and
+    /// [`AprError::ModelTooLarge`] if it exceeds `max_bytes`.
+    fn fetch(&mut self, url: &str, max_bytes: usize) -> Result<Vec<u8>, AprError>;
+}
+
+/// Responses preloaded by the host (e.g. by the browser's `fetch`)
+#[derive(Debug, Clone, Default)]
+pub struct MemoryFetch {
+    responses: HashMap<String, Vec<u8>>,
+}
+
+impl MemoryFetch {
+    /// Creates an empty backend
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Makes a response available for a URL
+    pub fn insert(&mut self, url: impl Into<String>, bytes: Vec<u8>) {
+        let _ = self.responses.insert(url.into(), bytes);
+    }
+}
+
+impl FetchBackend for MemoryFetch {
+    fn fetch(&mut self, url: &str, max_bytes: usize) -> Result<Vec<u8>, AprError> {
+        let bytes = self.responses.get(url).ok_or_else(|| AprError::NotFound {
+            reference: url.to_string(),
+        })?;
+        if bytes.len() > max_bytes {
+            return Err(AprError::ModelTooLarge {
+                size: bytes.len(),
+                max: max_bytes,
+            });
+        }
+        Ok(bytes.clone())
+    }
+}
+
+/// Serves URLs from a local mirror laid out as `<root>/<host>/<path>`
+#[derive(Debug, Clone)]
+pub struct FileSystemFetch {
+    root: PathBuf,
+}
+
+impl FileSystemFetch {
+    /// Creates a backend reading below `root`
+    #[must_use]
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Local path for a URL, or `None` if it would escape the root
+    #[must_use]
+    pub fn path_for(&self, url: &str) -> Option<PathBuf> {
+        let relative = Path::new(url.strip_prefix("https://")?);
+        let safe = relative
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)));
+        safe.then(|| self.root.join(relative))
+    }
+}
+
+impl FetchBackend for FileSystemFetch {
+    fn fetch(&mut self, url: &str, max_bytes: usize) -> Result<Vec<u8>, AprError> {
+        let not_found = || AprError::NotFound {
+            reference: url.to_string(),
+        };
+        let path = self.path_for(url).ok_or_else(not_found)?;
+        let len = std::fs::metadata(&path).map_err(|_| not_found())?.len();
+        let too_large = |size| AprError::ModelTooLarge {
+            size,
+            max: max_bytes,
+        };
+        // Files too big to address are too big to load
+        let size = usize::try_from(len).map_err(|_| too_large(usize::MAX))?;
+        if size > max_bytes {
+            return Err(too_large(size));
+        }
+        Ok(std::fs::read(path)?)
+    }
+}
+
+/// Resolves, verifies and caches models by reference
+#[derive(Debug)]
+pub struct AprRepository {
+    backend: Box<dyn FetchBackend>,
+    bundle: HashMap<String, Vec<u8>>,
+    /// Most recently used last
+    cache: Vec<(ModelRef, Arc<AprModel>)>,
+    capacity: usize,
+    require_remote_checksum: bool,
+}
+
+impl AprRepository {
+    /// Creates a repository fetching remote models through `backend`
+    #[must_use]
+    pub fn new(backend: impl FetchBackend + 'static) -> Self {
+        Self {
+            backend: Box::new(backend),
+            bundle: HashMap::new(),
+            cache: Vec::new(),
+            capacity: DEFAULT_CACHE_CAPACITY,
+            require_remote_checksum: true,
+        }
+    }
+
+    /// Sets how many models the cache keeps (at least 1)
+    #[must_use]
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity.max(1);
+        self
+    }
+
+    /// Allows `https:` references without a `#crc32=` pin (trusted mirrors)
+    #[must_use]
+    pub const fn allow_unpinned_remote(mut self) -> Self {
+        self.require_remote_checksum = false;
+        self
+    }
+
+    /// Makes a bundle's embedded model files available to `bundle:` references
+    pub fn mount_bundle(&mut self, assets: impl IntoIterator<Item = (String, Vec<u8>)>) {
+        for (name, bytes) in assets {
+            let _ = self
+                .bundle
+                .insert(name.trim_start_matches('/').to_string(), bytes);
+        }
+    }
+
+    /// Resolves a reference string
+    ///
+    /// # Errors
+    ///
+    /// See [`ModelRef::parse`] and [`Self::load`].
+    pub fn resolve(&mut self, reference: &str) -> Result<Arc<AprModel>, AprError> {
+        self.load(&ModelRef::parse(reference)?)
+    }
+
+    /// Loads a model, from the cache if possible
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AprError::NotFound`] for missing models,
+    /// [`AprError::UnpinnedRemote`] for `https:` references without a
+    /// checksum (unless allowed), verification errors for bytes that don't
+    /// match the pins and parse errors for invalid .apr files.
+    pub fn load(&mut self, reference: &ModelRef) -> Result<Arc<AprModel>, AprError> {
+        if let Some(index) = self.cache.iter().position(|(key, _)| key == reference) {
+            let entry = self.cache.remove(index);
+            let model = Arc::clone(&entry.1);
+            self.cache.push(entry);
+            return Ok(model);
+        }
+
+        let model = match &reference.source {
+            ModelSource::Builtin(name) => AprModel::builtin(name)?,
+            ModelSource::Bundle(name) => {
+                let bytes = self.bundle.get(name).ok_or_else(|| AprError::NotFound {
+                    reference: reference.to_string(),
+                })?;
+                reference.verify(bytes)?;
+                AprFile::from_bytes(bytes)?.model
+            }
+            ModelSource::Https(url) => {
+                if self.require_remote_checksum && reference.checksum.is_none() {
+                    return Err(AprError::UnpinnedRemote { url: url.clone() });
+                }
+                let limit = reference.size.unwrap_or(MAX_MODEL_SIZE).min(MAX_MODEL_SIZE);
+                let bytes = self.backend.fetch(url, limit)?;
+                reference.verify(&bytes)?;
+                AprFile::from_bytes(&bytes)?.model
+            }
+        };
+
+        let model = Arc::new(model);
+        if self.cache.len() >= self.capacity {
+            let _ = self.cache.remove(0);
+        }
+        self.cache.push((reference.clone(), Arc::clone(&model)));
+        Ok(model)
+    }
+
+    /// Returns true if the reference is cached
+    #[must_use]
+    pub fn is_cached(&self, reference: &ModelRef) -> bool {
+        self.cache.iter().any(|(key, _)| key == reference)
+    }
+
+    /// Number of cached models
+    #[must_use]
+    pub fn cached(&self) -> usize {
+        self.cache.len()
+    }
+
+    /// Drops every cached model
+    pub fn clear_cache(&mut self) {
+        self.cache.clear();
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn ghost_bytes() -> Vec<u8> {
+        AprModel::new_test_model().to_bytes().unwrap()
+    }
+
+    #[test]
+    fn test_parse_references() {
+        let remote =
+            ModelRef::parse("https://zoo.example/ghost.apr#crc32=1a2b3c4d&size=10").unwrap();
+        assert_eq!(
+            remote.source,
+            ModelSource::Https("https://zoo.example/ghost.apr".to_string())
+        );
+        assert_eq!(remote.checksum, Some(0x1a2b_3c4d));
+        assert_eq!(remote.size, Some(10));
+        assert_eq!(
+            ModelRef::parse("bundle:/models/ghost.apr").unwrap().source,
+            ModelSource::Bundle("models/ghost.apr".to_string())
+        );
+
+        for bad in [
+            "http://zoo.example/a.apr",
+            "builtin:",
+            "ftp:x",
+            "bundle:a#crc32=zz",
+            "bundle:a#md5=1",
+        ] {
+            assert!(ModelRef::parse(bad).is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn test_builtin_and_bundle() {
+        let mut repo = AprRepository::new(MemoryFetch::new());
+        assert_eq!(
+            repo.resolve("builtin:chase").unwrap().metadata.name,
+            "builtin-chase"
+        );
+
+        let bytes = ghost_bytes();
+        let pinned = format!("bundle:ghost.apr#crc32={:08x}", crc32fast::hash(&bytes));
+        repo.mount_bundle([("ghost.apr".to_string(), bytes)]);
+        assert!(repo.resolve(&pinned).is_ok());
+        assert!(matches!(
+            repo.resolve("bundle:ghost.apr#crc32=00000000"),
+            Err(AprError::ChecksumMismatch { .. })
+        ));
+        assert!(matches!(
+            repo.resolve("bundle:missing.apr"),
+            Err(AprError::NotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_remote_models_must_be_pinned() {
+        let bytes = ghost_bytes();
+        let url = "https://zoo.example/ghost.apr";
+        let mut fetch = MemoryFetch::new();
+        fetch.insert(url, bytes.clone());
+        let mut repo = AprRepository::new(fetch.clone());
+
+        assert!(matches!(
+            repo.resolve(url),
+            Err(AprError::UnpinnedRemote { .. })
+        ));
+        let pinned = format!(
+            "{url}#crc32={:08x}&size={}",
+            crc32fast::hash(&bytes),
+            bytes.len()
+        );
+        assert!(repo.resolve(&pinned).is_ok());
+        assert!(matches!(
+            repo.resolve(&format!(
+                "{url}#size=3&crc32={:08x}",
+                crc32fast::hash(&bytes)
+            )),
+            Err(AprError::ModelTooLarge { .. })
+        ));
+
+        let mut trusting = AprRepository::new(fetch).allow_unpinned_remote();
+        assert!(trusting.resolve(url).is_ok());
+    }
+
+    #[test]
+    fn test_lru_eviction() {
+        let mut repo = AprRepository::new(MemoryFetch::new()).with_capacity(2);
+        let chase = ModelRef::parse("builtin:chase").unwrap();
+        let patrol = ModelRef::parse("builtin:patrol").unwrap();
+        let wander = ModelRef::parse("builtin:wander").unwrap();
+        let _ = repo.load(&chase).unwrap();
+        let _ = repo.load(&patrol).unwrap();
+        // Touch chase so patrol becomes least recently used
+        let _ = repo.load(&chase).unwrap();
+        let _ = repo.load(&wander).unwrap();
+        assert_eq!(repo.cached(), 2);
+        assert!(repo.is_cached(&chase));
+        assert!(!repo.is_cached(&patrol));
+    }
+
+    #[test]
+    fn test_file_system_mirror_stays_in_root() {
+        let root = std::env::temp_dir().join(format!("jugar-apr-mirror-{}", std::process::id()));
+        let dir = root.join("zoo.example");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("ghost.apr"), ghost_bytes()).unwrap();
+
+        let mut fetch = FileSystemFetch::new(&root);
+        assert!(fetch
+            .path_for("https://zoo.example/../../etc/passwd")
+            .is_none());
+        assert!(fetch
+            .fetch("https://zoo.example/ghost.apr", MAX_MODEL_SIZE)
+            .is_ok());
+        assert!(matches!(
+            fetch.fetch("https://zoo.example/ghost.apr", 4),
+            Err(AprError::ModelTooLarge { .. })
+        ));
+        let _ = std::fs::remove_dir_all(root);
+    }
+}
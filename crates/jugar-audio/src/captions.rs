@@ -0,0 +1,137 @@
+//! Closed captions for sound effects and speech.
+//!
+//! A [`SoundSource`](crate::SoundSource) may carry a [`Caption`]. When the
+//! sound is played, the [`AudioSystem`](crate::AudioSystem) queues a
+//! [`CaptionEvent`] that the UI layer drains each frame and renders as a
+//! subtitle, so players who cannot hear still get the information.
+
+use glam::Vec2;
+use serde::{Deserialize, Serialize};
+
+use crate::AudioHandle;
+
+/// Default time a caption stays on screen in seconds
+pub const DEFAULT_CAPTION_DURATION: f32 = 2.0;
+
+/// What kind of sound a caption describes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+pub enum CaptionCategory {
+    /// Spoken dialogue
+    Speech,
+    /// Sound effect
+    #[default]
+    Effect,
+    /// Music cue
+    Music,
+    /// Background ambience
+    Ambient,
+}
+
+impl CaptionCategory {
+    /// Formats caption text the way subtitles conventionally show it
+    ///
+    /// Speech is shown as-is; other sounds are wrapped in brackets.
+    #[must_use]
+    pub fn format(self, text: &str) -> String {
+        match self {
+            Self::Speech => text.to_string(),
+            Self::Effect | Self::Ambient => format!("[{text}]"),
+            Self::Music => format!("[♪ {text}]"),
+        }
+    }
+}
+
+/// Caption attached to a sound source
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Caption {
+    /// Text describing the sound
+    pub text: String,
+    /// Caption category
+    #[serde(default)]
+    pub category: CaptionCategory,
+    /// Time on screen in seconds
+    #[serde(default = "default_duration")]
+    pub duration: f32,
+}
+
+const fn default_duration() -> f32 {
+    DEFAULT_CAPTION_DURATION
+}
+
+impl Caption {
+    /// Creates a caption with the default duration
+    #[must_use]
+    pub fn new(text: impl Into<String>, category: CaptionCategory) -> Self {
+        Self {
+            text: text.into(),
+            category,
+            duration: DEFAULT_CAPTION_DURATION,
+        }
+    }
+
+    /// Sets the on-screen duration
+    #[must_use]
+    pub const fn with_duration(mut self, duration: f32) -> Self {
+        self.duration = duration;
+        self
+    }
+}
+
+/// A caption emitted when a captioned sound starts playing
+#[derive(Debug, Clone, PartialEq)]
+pub struct CaptionEvent {
+    /// Handle of the sound that produced the caption
+    pub handle: AudioHandle,
+    /// Caption text
+    pub text: String,
+    /// Caption category
+    pub category: CaptionCategory,
+    /// World position of the sound
+    pub position: Vec2,
+    /// Time on screen in seconds
+    pub duration: f32,
+}
+
+impl CaptionEvent {
+    /// Returns the text formatted for display
+    #[must_use]
+    pub fn display_text(&self) -> String {
+        self.category.format(&self.text)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_caption_defaults() {
+        let caption = Caption::new("door creaks", CaptionCategory::Effect);
+        assert!((caption.duration - DEFAULT_CAPTION_DURATION).abs() < f32::EPSILON);
+        let caption = caption.with_duration(4.0);
+        assert!((caption.duration - 4.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_category_format() {
+        assert_eq!(CaptionCategory::Speech.format("Hello!"), "Hello!");
+        assert_eq!(CaptionCategory::Effect.format("pop"), "[pop]");
+        assert_eq!(
+            CaptionCategory::Music.format("happy tune"),
+            "[♪ happy tune]"
+        );
+    }
+
+    #[test]
+    fn test_event_display_text() {
+        let event = CaptionEvent {
+            handle: AudioHandle(0),
+            text: "wind howls".to_string(),
+            category: CaptionCategory::Ambient,
+            position: Vec2::ZERO,
+            duration: 1.0,
+        };
+        assert_eq!(event.display_text(), "[wind howls]");
+    }
+}
@@ -0,0 +1,179 @@
+//! Doppler pitch shifting for moving sources and listeners.
+//!
+//! The raw Doppler factor is clamped to a gentle pitch range and the applied
+//! value is rate-limited each frame, so fast objects whoosh past without the
+//! wobbly, nausea-inducing pitch jumps of an unfiltered simulation.
+
+use glam::Vec2;
+use serde::{Deserialize, Serialize};
+
+/// Doppler effect settings
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DopplerSettings {
+    /// Whether Doppler shifting is applied
+    pub enabled: bool,
+    /// Speed of sound in world units per second
+    pub speed_of_sound: f32,
+    /// Strength multiplier (0.0 = off, 1.0 = physical)
+    pub scale: f32,
+    /// Lowest allowed pitch factor
+    pub min_factor: f32,
+    /// Highest allowed pitch factor
+    pub max_factor: f32,
+    /// Maximum change in pitch factor per second
+    pub max_rate: f32,
+}
+
+impl DopplerSettings {
+    /// Creates settings tuned for kid-friendly games
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            enabled: true,
+            speed_of_sound: 343.0,
+            scale: 1.0,
+            min_factor: 0.7,
+            max_factor: 1.4,
+            max_rate: 1.5,
+        }
+    }
+
+    /// Creates settings with Doppler shifting turned off
+    #[must_use]
+    pub const fn disabled() -> Self {
+        let mut settings = Self::new();
+        settings.enabled = false;
+        settings
+    }
+
+    /// Sets the speed of sound
+    #[must_use]
+    pub const fn with_speed_of_sound(mut self, speed: f32) -> Self {
+        self.speed_of_sound = speed;
+        self
+    }
+
+    /// Sets the strength multiplier
+    #[must_use]
+    pub const fn with_scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Computes the clamped target pitch factor for a source heard by a
+    /// listener
+    #[must_use]
+    pub fn factor(
+        &self,
+        source_position: Vec2,
+        source_velocity: Vec2,
+        listener_position: Vec2,
+        listener_velocity: Vec2,
+    ) -> f32 {
+        if !self.enabled || self.speed_of_sound <= 0.0 {
+            return 1.0;
+        }
+        let to_listener = listener_position - source_position;
+        if to_listener.length_squared() < 0.001 {
+            return 1.0;
+        }
+        let direction = to_listener.normalize();
+        // Keep both speeds well below the speed of sound so the ratio stays finite
+        let limit = self.speed_of_sound * 0.9;
+        let source_speed = (source_velocity.dot(direction) * self.scale).clamp(-limit, limit);
+        let listener_speed = (listener_velocity.dot(direction) * self.scale).clamp(-limit, limit);
+        let factor = (self.speed_of_sound - listener_speed) / (self.speed_of_sound - source_speed);
+        factor.clamp(self.min_factor, self.max_factor)
+    }
+
+    /// Moves `current` toward `target` without exceeding the rate limit
+    #[must_use]
+    pub fn smooth(&self, current: f32, target: f32, dt: f32) -> f32 {
+        let step = self.max_rate.max(0.0) * dt;
+        current + (target - current).clamp(-step, step)
+    }
+}
+
+impl Default for DopplerSettings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stationary_is_neutral() {
+        let doppler = DopplerSettings::new();
+        let factor = doppler.factor(Vec2::new(100.0, 0.0), Vec2::ZERO, Vec2::ZERO, Vec2::ZERO);
+        assert!((factor - 1.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_approaching_raises_pitch() {
+        let doppler = DopplerSettings::new();
+        let approaching = doppler.factor(
+            Vec2::new(100.0, 0.0),
+            Vec2::new(-50.0, 0.0),
+            Vec2::ZERO,
+            Vec2::ZERO,
+        );
+        let receding = doppler.factor(
+            Vec2::new(100.0, 0.0),
+            Vec2::new(50.0, 0.0),
+            Vec2::ZERO,
+            Vec2::ZERO,
+        );
+        assert!(approaching > 1.0);
+        assert!(receding < 1.0);
+    }
+
+    #[test]
+    fn test_listener_motion() {
+        let doppler = DopplerSettings::new();
+        let toward = doppler.factor(
+            Vec2::new(100.0, 0.0),
+            Vec2::ZERO,
+            Vec2::ZERO,
+            Vec2::new(50.0, 0.0),
+        );
+        assert!(toward > 1.0);
+    }
+
+    #[test]
+    fn test_factor_is_clamped() {
+        let doppler = DopplerSettings::new();
+        let factor = doppler.factor(
+            Vec2::new(100.0, 0.0),
+            Vec2::new(-10_000.0, 0.0),
+            Vec2::ZERO,
+            Vec2::ZERO,
+        );
+        assert!((factor - doppler.max_factor).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_disabled() {
+        let doppler = DopplerSettings::disabled();
+        let factor = doppler.factor(
+            Vec2::new(100.0, 0.0),
+            Vec2::new(-100.0, 0.0),
+            Vec2::ZERO,
+            Vec2::ZERO,
+        );
+        assert!((factor - 1.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_smooth_is_rate_limited() {
+        let doppler = DopplerSettings::new();
+        let next = doppler.smooth(1.0, 1.4, 0.1);
+        assert!((next - 1.15).abs() < 0.001);
+        let next = doppler.smooth(1.0, 1.05, 0.1);
+        assert!((next - 1.05).abs() < 0.001);
+    }
+}
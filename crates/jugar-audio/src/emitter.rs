@@ -0,0 +1,235 @@
+//! Sounds attached to entities
+//!
+//! An [`AudioEmitter`] component holds a [`SoundSource`] template. Each
+//! frame [`EmitterSync::update`] starts the sound of newly spawned emitters,
+//! moves playing sounds to their entity's `Position` (and `Velocity`, for
+//! Doppler), and stops the sounds of emitters that were removed or
+//! despawned, so spatial audio tracks moving entities without manual
+//! bookkeeping.
+//!
+//! ```
+//! use jugar_audio::{AudioEmitter, AudioSystem, EmitterSync, SoundSource};
+//! use jugar_core::{Position, World};
+//!
+//! let mut world = World::new();
+//! let mut audio = AudioSystem::new();
+//! let mut emitters = EmitterSync::new(&mut world);
+//!
+//! let bee = world.spawn();
+//! world.add_component(bee, Position::new(10.0, 0.0));
+//! world.add_component(bee, AudioEmitter::new(SoundSource::new("buzz").with_looping(true)));
+//! emitters.update(&mut world, &mut audio);
+//! assert_eq!(audio.playing_count(), 1);
+//!
+//! world.despawn(bee).unwrap();
+//! emitters.update(&mut world, &mut audio);
+//! assert_eq!(audio.playing_count(), 0);
+//! ```
+
+use alloc::sync::Arc;
+use std::sync::{Mutex, PoisonError};
+
+use glam::Vec2;
+use jugar_core::{Entity, Position, Velocity, World};
+
+use crate::{AudioHandle, AudioSystem, SoundSource};
+
+/// Component that plays a sound from an entity
+#[derive(Debug, Clone, PartialEq)]
+pub struct AudioEmitter {
+    /// Sound played for this entity
+    pub source: SoundSource,
+    /// Move the sound with the entity's `Position` each frame
+    pub follow: bool,
+    /// Start the sound as soon as the emitter is synced
+    pub play_on_spawn: bool,
+    /// Stop the sound when the emitter is removed or the entity despawned
+    pub stop_on_despawn: bool,
+    /// Handle of the sound once started
+    pub handle: Option<AudioHandle>,
+}
+
+impl AudioEmitter {
+    /// Emitter that plays `source` on spawn, follows the entity and stops
+    /// on despawn
+    #[must_use]
+    pub const fn new(source: SoundSource) -> Self {
+        Self {
+            source,
+            follow: true,
+            play_on_spawn: true,
+            stop_on_despawn: true,
+            handle: None,
+        }
+    }
+
+    /// Sets whether the sound follows the entity
+    #[must_use]
+    pub const fn with_follow(mut self, follow: bool) -> Self {
+        self.follow = follow;
+        self
+    }
+
+    /// Sets whether the sound starts on spawn
+    #[must_use]
+    pub const fn with_play_on_spawn(mut self, play: bool) -> Self {
+        self.play_on_spawn = play;
+        self
+    }
+
+    /// Sets whether the sound stops on despawn
+    #[must_use]
+    pub const fn with_stop_on_despawn(mut self, stop: bool) -> Self {
+        self.stop_on_despawn = stop;
+        self
+    }
+
+    /// Plays the source at `position`, replacing any earlier sound handle
+    ///
+    /// Used for emitters that don't play on spawn (e.g. a jump sound).
+    pub fn play(&mut self, audio: &mut AudioSystem, position: Vec2) -> AudioHandle {
+        let handle = audio.play(self.source.clone().with_position(position));
+        self.handle = Some(handle);
+        handle
+    }
+}
+
+/// Keeps [`AudioEmitter`] sounds in step with their entities
+#[derive(Debug)]
+pub struct EmitterSync {
+    /// Sounds of emitters removed since the last update
+    stopped: Arc<Mutex<Vec<AudioHandle>>>,
+}
+
+impl EmitterSync {
+    /// Creates the sync system, registering its observers on `world`
+    #[must_use]
+    pub fn new(world: &mut World) -> Self {
+        let stopped = Arc::new(Mutex::new(Vec::new()));
+        let queue = Arc::clone(&stopped);
+        world.on_remove::<AudioEmitter>(move |_, emitter, _| {
+            if let Some(handle) = emitter.handle.filter(|_| emitter.stop_on_despawn) {
+                queue
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .push(handle);
+            }
+        });
+        Self { stopped }
+    }
+
+    /// Stops removed emitters, starts new ones and moves followers
+    pub fn update(&mut self, world: &mut World, audio: &mut AudioSystem) {
+        let stopped: Vec<AudioHandle> = self
+            .stopped
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .drain(..)
+            .collect();
+        for handle in stopped {
+            audio.stop(handle);
+        }
+
+        let emitters: Vec<(Entity, bool, bool)> = world
+            .query::<AudioEmitter>()
+            .map(|(entity, emitter)| {
+                let start = emitter.play_on_spawn && emitter.handle.is_none();
+                (entity, start, emitter.follow)
+            })
+            .filter(|&(_, start, follow)| start || follow)
+            .collect();
+        for (entity, start, follow) in emitters {
+            let position = world
+                .get_component::<Position>(entity)
+                .map(|position| position.as_vec2());
+            let velocity = world
+                .get_component::<Velocity>(entity)
+                .map_or(Vec2::ZERO, |velocity| velocity.as_vec2());
+            if start {
+                if let Some(emitter) = world.get_component_mut::<AudioEmitter>(entity) {
+                    let at = position.unwrap_or(emitter.source.position);
+                    let _ = emitter.play(audio, at);
+                }
+            } else if let Some(position) = position.filter(|_| follow) {
+                if let Some(handle) = world
+                    .get_component::<AudioEmitter>(entity)
+                    .and_then(|emitter| emitter.handle)
+                {
+                    audio.set_source_motion(handle, position, velocity);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn setup() -> (World, AudioSystem, EmitterSync) {
+        let mut world = World::new();
+        let audio = AudioSystem::new();
+        let sync = EmitterSync::new(&mut world);
+        (world, audio, sync)
+    }
+
+    #[test]
+    fn test_spawned_emitter_plays_at_entity_and_follows() {
+        let (mut world, mut audio, mut sync) = setup();
+        let car = world.spawn();
+        world.add_component(car, Position::new(5.0, 0.0));
+        world.add_component(car, Velocity::new(2.0, 0.0));
+        world.add_component(car, AudioEmitter::new(SoundSource::new("engine")));
+
+        sync.update(&mut world, &mut audio);
+        let handle = world.get_component::<AudioEmitter>(car).unwrap().handle;
+        let handle = handle.unwrap();
+        let source = &audio.get(handle).unwrap().source;
+        assert!((source.position.x - 5.0).abs() < f32::EPSILON);
+
+        *world.get_component_mut::<Position>(car).unwrap() = Position::new(9.0, 1.0);
+        sync.update(&mut world, &mut audio);
+        let source = &audio.get(handle).unwrap().source;
+        assert!((source.position.x - 9.0).abs() < f32::EPSILON);
+        assert!((source.velocity.x - 2.0).abs() < f32::EPSILON);
+        // Started only once
+        assert_eq!(audio.playing_count(), 1);
+    }
+
+    #[test]
+    fn test_options_control_start_follow_and_stop() {
+        let (mut world, mut audio, mut sync) = setup();
+        let quiet = world.spawn();
+        world.add_component(
+            quiet,
+            AudioEmitter::new(SoundSource::new("jump")).with_play_on_spawn(false),
+        );
+        let fixed = world.spawn();
+        world.add_component(fixed, Position::new(1.0, 1.0));
+        world.add_component(
+            fixed,
+            AudioEmitter::new(SoundSource::new("hum").with_looping(true))
+                .with_follow(false)
+                .with_stop_on_despawn(false),
+        );
+
+        sync.update(&mut world, &mut audio);
+        assert!(world
+            .get_component::<AudioEmitter>(quiet)
+            .unwrap()
+            .handle
+            .is_none());
+        let handle = world.get_component::<AudioEmitter>(fixed).unwrap().handle;
+        let handle = handle.unwrap();
+
+        *world.get_component_mut::<Position>(fixed).unwrap() = Position::new(50.0, 0.0);
+        sync.update(&mut world, &mut audio);
+        let source = &audio.get(handle).unwrap().source;
+        assert!((source.position.x - 1.0).abs() < f32::EPSILON);
+
+        world.despawn(fixed).unwrap();
+        sync.update(&mut world, &mut audio);
+        assert!(audio.is_playing(handle));
+    }
+}
@@ -5,13 +5,30 @@
 #![forbid(unsafe_code)]
 #![warn(missing_docs)]
 
+extern crate alloc;
+
+mod captions;
+mod doppler;
+mod emitter;
+mod music;
+mod sequencer;
+mod smoothing;
+
 use core::fmt;
 use std::collections::HashMap;
 
 use glam::Vec2;
+use jugar_core::{FramePool, PoolStats, SoundStimulus};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+pub use captions::{Caption, CaptionCategory, CaptionEvent, DEFAULT_CAPTION_DURATION};
+pub use doppler::DopplerSettings;
+pub use emitter::{AudioEmitter, EmitterSync};
+pub use music::{AdaptiveMusic, MusicLayer};
+pub use sequencer::{midi_to_frequency, Pattern, Sequencer, Step, Track, Voice, Waveform};
+pub use smoothing::{SmoothingSettings, DEFAULT_STOP_FADE};
+
 /// Audio system errors
 #[derive(Error, Debug, Clone, PartialEq, Eq)]
 pub enum AudioError {
@@ -59,6 +76,8 @@ pub enum PlaybackState {
     Playing,
     /// Paused
     Paused,
+    /// Fading out after a stop; removed once silent
+    Stopping,
 }
 
 /// Sound source for spatial audio
@@ -82,6 +101,12 @@ pub struct SoundSource {
     pub reference_distance: f32,
     /// Rolloff factor for distance attenuation
     pub rolloff: f32,
+    /// Caption shown when the sound plays
+    #[serde(default)]
+    pub caption: Option<Caption>,
+    /// Velocity in world units per second (for Doppler)
+    #[serde(default)]
+    pub velocity: Vec2,
 }
 
 impl SoundSource {
@@ -98,6 +123,8 @@ impl SoundSource {
             max_distance: 1000.0,
             reference_distance: 1.0,
             rolloff: 1.0,
+            caption: None,
+            velocity: Vec2::ZERO,
         }
     }
 
@@ -115,6 +142,13 @@ impl SoundSource {
         self
     }
 
+    /// Sets the velocity
+    #[must_use]
+    pub const fn with_velocity(mut self, velocity: Vec2) -> Self {
+        self.velocity = velocity;
+        self
+    }
+
     /// Sets looping
     #[must_use]
     pub const fn with_looping(mut self, looping: bool) -> Self {
@@ -129,6 +163,13 @@ impl SoundSource {
         self
     }
 
+    /// Sets the caption
+    #[must_use]
+    pub fn with_caption(mut self, text: impl Into<String>, category: CaptionCategory) -> Self {
+        self.caption = Some(Caption::new(text, category));
+        self
+    }
+
     /// Calculates volume based on distance from listener
     #[must_use]
     pub fn calculate_attenuation(&self, listener_pos: Vec2) -> f32 {
@@ -164,6 +205,9 @@ pub struct AudioListener {
     pub position: Vec2,
     /// Facing direction (for stereo panning)
     pub direction: Vec2,
+    /// Velocity in world units per second (for Doppler)
+    #[serde(default)]
+    pub velocity: Vec2,
 }
 
 impl AudioListener {
@@ -173,6 +217,7 @@ impl AudioListener {
         Self {
             position: Vec2::ZERO,
             direction: Vec2::new(0.0, 1.0), // Facing up
+            velocity: Vec2::ZERO,
         }
     }
 
@@ -274,6 +319,16 @@ pub struct PlayingSound {
     pub time: f32,
     /// Duration in seconds (0 if unknown)
     pub duration: f32,
+    /// Smoothed Doppler pitch factor (1.0 = unshifted)
+    pub doppler: f32,
+    /// Smoothed volume after attenuation and channel mixing
+    pub level: f32,
+    /// Smoothed stereo pan (-1.0 left, 1.0 right)
+    pub pan: f32,
+    /// Stop fade gain, 1.0 until stopped and 0.0 once silent
+    pub fade: f32,
+    /// Length of the stop fade in seconds
+    pub fade_time: f32,
 }
 
 impl PlayingSound {
@@ -286,6 +341,21 @@ impl PlayingSound {
             state: PlaybackState::Stopped,
             time: 0.0,
             duration: 0.0,
+            doppler: 1.0,
+            level: 0.0,
+            pan: 0.0,
+            fade: 1.0,
+            fade_time: 0.0,
+        }
+    }
+
+    /// Volume to send to the backend this frame (smoothed, including fade)
+    #[must_use]
+    pub fn output_volume(&self) -> f32 {
+        if self.state == PlaybackState::Stopped {
+            0.0
+        } else {
+            self.level * self.fade
         }
     }
 
@@ -302,6 +372,22 @@ pub struct AudioSystem {
     volumes: ChannelVolumes,
     playing: HashMap<AudioHandle, PlayingSound>,
     next_handle: u32,
+    captions: FramePool<CaptionEvent>,
+    captions_enabled: bool,
+    stimuli: FramePool<SoundStimulus>,
+    music: AdaptiveMusic,
+    doppler: DopplerSettings,
+    smoothing: SmoothingSettings,
+}
+
+/// Unsmoothed volume and pan of a source heard by the listener
+fn mix_targets(
+    listener: &AudioListener,
+    volumes: &ChannelVolumes,
+    source: &SoundSource,
+) -> (f32, f32) {
+    let level = source.calculate_attenuation(listener.position) * volumes.get(source.channel);
+    (level, listener.calculate_pan(source.position))
 }
 
 impl AudioSystem {
@@ -313,6 +399,12 @@ impl AudioSystem {
             volumes: ChannelVolumes::default(),
             playing: HashMap::new(),
             next_handle: 0,
+            captions: FramePool::new(),
+            captions_enabled: true,
+            stimuli: FramePool::new(),
+            music: AdaptiveMusic::default(),
+            doppler: DopplerSettings::new(),
+            smoothing: SmoothingSettings::new(),
         }
     }
 
@@ -333,6 +425,41 @@ impl AudioSystem {
         self.listener.position = position;
     }
 
+    /// Sets the listener velocity
+    pub const fn set_listener_velocity(&mut self, velocity: Vec2) {
+        self.listener.velocity = velocity;
+    }
+
+    /// Gets the Doppler settings
+    #[must_use]
+    pub const fn doppler(&self) -> &DopplerSettings {
+        &self.doppler
+    }
+
+    /// Replaces the Doppler settings
+    pub const fn set_doppler(&mut self, doppler: DopplerSettings) {
+        self.doppler = doppler;
+    }
+
+    /// Gets the stop fade and slew settings
+    #[must_use]
+    pub const fn smoothing(&self) -> &SmoothingSettings {
+        &self.smoothing
+    }
+
+    /// Replaces the stop fade and slew settings
+    pub const fn set_smoothing(&mut self, smoothing: SmoothingSettings) {
+        self.smoothing = smoothing;
+    }
+
+    /// Updates a playing sound's position and velocity (e.g. from physics)
+    pub fn set_source_motion(&mut self, handle: AudioHandle, position: Vec2, velocity: Vec2) {
+        if let Some(playing) = self.playing.get_mut(&handle) {
+            playing.source.position = position;
+            playing.source.velocity = velocity;
+        }
+    }
+
     /// Gets channel volumes
     #[must_use]
     pub const fn volumes(&self) -> &ChannelVolumes {
@@ -350,17 +477,144 @@ impl AudioSystem {
         let handle = AudioHandle(self.next_handle);
         self.next_handle += 1;
 
+        if self.captions_enabled {
+            if let Some(caption) = &source.caption {
+                self.captions.push(CaptionEvent {
+                    handle,
+                    text: caption.text.clone(),
+                    category: caption.category,
+                    position: source.position,
+                    duration: caption.duration,
+                });
+            }
+        }
+
+        // Music is not a sound in the world, so agents don't hear it
+        if source.channel != AudioChannel::Music {
+            self.stimuli.push(SoundStimulus::new(
+                source.id.clone(),
+                source.position,
+                source.volume,
+            ));
+        }
+
+        // New sounds start at their targets; only later changes are smoothed
+        let (level, pan) = mix_targets(&self.listener, &self.volumes, &source);
         let mut playing = PlayingSound::new(handle, source);
         playing.state = PlaybackState::Playing;
+        playing.level = level;
+        playing.pan = pan;
 
         let _ = self.playing.insert(handle, playing);
         handle
     }
 
-    /// Stops a playing sound
+    /// Returns whether captions are emitted
+    #[must_use]
+    pub const fn captions_enabled(&self) -> bool {
+        self.captions_enabled
+    }
+
+    /// Enables or disables caption events
+    pub fn set_captions_enabled(&mut self, enabled: bool) {
+        self.captions_enabled = enabled;
+        if !enabled {
+            self.captions.clear();
+        }
+    }
+
+    /// Returns captions emitted since the last drain
+    #[must_use]
+    pub fn pending_captions(&self) -> &[CaptionEvent] {
+        self.captions.as_slice()
+    }
+
+    /// Takes captions emitted since the last drain, for the UI to render
+    ///
+    /// Hand the `Vec` back with [`recycle_captions`](Self::recycle_captions)
+    /// once rendered so the next frame doesn't allocate.
+    pub fn drain_captions(&mut self) -> Vec<CaptionEvent> {
+        self.captions.take()
+    }
+
+    /// Returns a drained caption buffer for reuse
+    pub fn recycle_captions(&mut self, captions: Vec<CaptionEvent>) {
+        self.captions.recycle(captions);
+    }
+
+    /// Allocation counters of the caption buffer
+    #[must_use]
+    pub const fn caption_stats(&self) -> PoolStats {
+        self.captions.stats()
+    }
+
+    /// Returns AI hearing stimuli emitted since the last drain
+    #[must_use]
+    pub fn pending_stimuli(&self) -> &[SoundStimulus] {
+        self.stimuli.as_slice()
+    }
+
+    /// Takes the stimuli of sounds played since the last drain, for AI
+    /// perception to hear
+    ///
+    /// Hand the `Vec` back with [`recycle_stimuli`](Self::recycle_stimuli)
+    /// once perception has run.
+    pub fn drain_stimuli(&mut self) -> Vec<SoundStimulus> {
+        self.stimuli.take()
+    }
+
+    /// Returns a drained stimulus buffer for reuse
+    pub fn recycle_stimuli(&mut self, stimuli: Vec<SoundStimulus>) {
+        self.stimuli.recycle(stimuli);
+    }
+
+    /// Starts adaptive music, replacing any current music
+    pub fn play_music(&mut self, music: AdaptiveMusic) {
+        self.stop_music();
+        self.music = music;
+        self.music.snap_to_targets();
+        for index in 0..self.music.layers.len() {
+            let layer = &self.music.layers[index];
+            let source = layer.source.clone().with_volume(layer.volume());
+            let handle = self.play(source);
+            self.music.set_handle(index, Some(handle));
+        }
+    }
+
+    /// Stops the adaptive music stems
+    pub fn stop_music(&mut self) {
+        for index in 0..self.music.layers.len() {
+            if let Some(handle) = self.music.layers[index].handle() {
+                self.stop(handle);
+            }
+            self.music.set_handle(index, None);
+        }
+    }
+
+    /// Gets the adaptive music
+    #[must_use]
+    pub const fn music(&self) -> &AdaptiveMusic {
+        &self.music
+    }
+
+    /// Gets the adaptive music mutably (e.g. to call `set_parameter`)
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn music_mut(&mut self) -> &mut AdaptiveMusic {
+        &mut self.music
+    }
+
+    /// Stops a playing sound, fading it out over the default stop fade
+    ///
+    /// The sound stops counting as playing immediately; it keeps sounding
+    /// (ever quieter) until the fade ends so the cut doesn't click.
     pub fn stop(&mut self, handle: AudioHandle) {
+        self.stop_with_fade(handle, self.smoothing.stop_fade);
+    }
+
+    /// Stops a playing sound with a fade-out of `seconds` (0 = cut)
+    pub fn stop_with_fade(&mut self, handle: AudioHandle, seconds: f32) {
         if let Some(playing) = self.playing.get_mut(&handle) {
-            playing.state = PlaybackState::Stopped;
+            begin_stop(playing, seconds);
         }
     }
 
@@ -407,9 +661,43 @@ impl AudioSystem {
 
     /// Updates the audio system (advances time, removes finished)
     pub fn update(&mut self, dt: f32) {
+        // Crossfade adaptive music layers
+        self.music.update(dt);
+        for layer in &self.music.layers {
+            if let Some(playing) = layer.handle().and_then(|h| self.playing.get_mut(&h)) {
+                playing.source.volume = layer.volume();
+            }
+        }
+
         // Update playback times
+        let listener = &self.listener;
+        let volumes = &self.volumes;
+        let smoothing = &self.smoothing;
         for playing in self.playing.values_mut() {
-            if playing.state == PlaybackState::Playing {
+            let target = self.doppler.factor(
+                playing.source.position,
+                playing.source.velocity,
+                listener.position,
+                listener.velocity,
+            );
+            playing.doppler = self.doppler.smooth(playing.doppler, target, dt);
+
+            let (level, pan) = mix_targets(listener, volumes, &playing.source);
+            playing.level = smoothing.smooth_volume(playing.level, level, dt);
+            playing.pan = smoothing.smooth_pan(playing.pan, pan, dt);
+
+            if playing.state == PlaybackState::Stopping {
+                playing.fade -= dt / playing.fade_time;
+                if playing.fade <= 0.0 {
+                    playing.fade = 0.0;
+                    playing.state = PlaybackState::Stopped;
+                }
+            }
+
+            if matches!(
+                playing.state,
+                PlaybackState::Playing | PlaybackState::Stopping
+            ) {
                 playing.time += dt;
 
                 // Handle looping
@@ -428,6 +716,9 @@ impl AudioSystem {
     }
 
     /// Calculates final volume for a sound (with attenuation and channel mixing)
+    ///
+    /// This is the unsmoothed target; backends should play
+    /// [`output_volume`](Self::output_volume).
     #[must_use]
     pub fn calculate_final_volume(&self, handle: AudioHandle) -> f32 {
         let Some(playing) = self.playing.get(&handle) else {
@@ -440,7 +731,32 @@ impl AudioSystem {
         attenuation * channel_volume
     }
 
+    /// Calculates final pitch for a sound (source pitch with Doppler shift)
+    #[must_use]
+    pub fn calculate_final_pitch(&self, handle: AudioHandle) -> f32 {
+        self.playing
+            .get(&handle)
+            .map_or(1.0, |p| p.source.pitch * p.doppler)
+    }
+
+    /// Volume to send to the backend (smoothed and faded)
+    #[must_use]
+    pub fn output_volume(&self, handle: AudioHandle) -> f32 {
+        self.playing
+            .get(&handle)
+            .map_or(0.0, PlayingSound::output_volume)
+    }
+
+    /// Pan to send to the backend (smoothed)
+    #[must_use]
+    pub fn output_pan(&self, handle: AudioHandle) -> f32 {
+        self.playing.get(&handle).map_or(0.0, |p| p.pan)
+    }
+
     /// Calculates stereo pan for a sound
+    ///
+    /// This is the unsmoothed target; backends should play
+    /// [`output_pan`](Self::output_pan).
     #[must_use]
     pub fn calculate_pan(&self, handle: AudioHandle) -> f32 {
         let Some(playing) = self.playing.get(&handle) else {
@@ -450,23 +766,43 @@ impl AudioSystem {
         self.listener.calculate_pan(playing.source.position)
     }
 
-    /// Stops all sounds
+    /// Stops all sounds, fading them out
     pub fn stop_all(&mut self) {
+        let fade = self.smoothing.stop_fade;
         for playing in self.playing.values_mut() {
-            playing.state = PlaybackState::Stopped;
+            begin_stop(playing, fade);
         }
     }
 
-    /// Stops all sounds in a channel
+    /// Stops all sounds in a channel, fading them out
     pub fn stop_channel(&mut self, channel: AudioChannel) {
+        let fade = self.smoothing.stop_fade;
         for playing in self.playing.values_mut() {
             if playing.source.channel == channel {
-                playing.state = PlaybackState::Stopped;
+                begin_stop(playing, fade);
             }
         }
     }
 }
 
+/// Starts a stop fade, or cuts at once if paused or `seconds` is 0
+fn begin_stop(playing: &mut PlayingSound, seconds: f32) {
+    match playing.state {
+        PlaybackState::Playing if seconds > 0.0 => {
+            playing.state = PlaybackState::Stopping;
+            playing.fade_time = seconds;
+        }
+        PlaybackState::Stopping if seconds > 0.0 => {
+            // Keep the current gain; only shorten the remaining fade
+            playing.fade_time = playing.fade_time.min(seconds);
+        }
+        _ => {
+            playing.state = PlaybackState::Stopped;
+            playing.fade = 0.0;
+        }
+    }
+}
+
 impl Default for AudioSystem {
     fn default() -> Self {
         Self::new()
@@ -710,4 +1046,230 @@ mod tests {
 
         assert!(!playing.is_finished());
     }
+
+    // ==================== CAPTION TESTS ====================
+
+    #[test]
+    fn test_play_emits_caption() {
+        let mut system = AudioSystem::new();
+        let handle = system.play(
+            SoundSource::new("pop")
+                .with_position(Vec2::new(5.0, 0.0))
+                .with_caption("pop!", CaptionCategory::Effect),
+        );
+        let _ = system.play(SoundSource::new("silent"));
+
+        let captions = system.drain_captions();
+        assert_eq!(captions.len(), 1);
+        assert_eq!(captions[0].handle, handle);
+        assert_eq!(captions[0].text, "pop!");
+        assert_eq!(captions[0].position, Vec2::new(5.0, 0.0));
+        assert!(system.pending_captions().is_empty());
+    }
+
+    #[test]
+    fn test_play_emits_hearing_stimulus() {
+        let mut system = AudioSystem::new();
+        let _ = system.play(
+            SoundSource::new("footstep")
+                .with_position(Vec2::new(3.0, 4.0))
+                .with_volume(0.5),
+        );
+        let _ = system.play(SoundSource::new("theme").with_channel(AudioChannel::Music));
+
+        let stimuli = system.drain_stimuli();
+        assert_eq!(
+            stimuli,
+            vec![SoundStimulus::new("footstep", Vec2::new(3.0, 4.0), 0.5)]
+        );
+        assert!(system.pending_stimuli().is_empty());
+        system.recycle_stimuli(stimuli);
+    }
+
+    #[test]
+    fn test_recycled_captions_stop_allocating() {
+        let mut system = AudioSystem::new();
+        let mut warmed_up = 0;
+        for frame in 0..30 {
+            let _ =
+                system.play(SoundSource::new("pop").with_caption("pop!", CaptionCategory::Effect));
+            let captions = system.drain_captions();
+            assert_eq!(captions.len(), 1);
+            system.recycle_captions(captions);
+            if frame == 1 {
+                warmed_up = system.caption_stats().allocations;
+            }
+        }
+        assert_eq!(system.caption_stats().allocations, warmed_up);
+        assert_eq!(system.caption_stats().frames, 30);
+    }
+
+    #[test]
+    fn test_captions_disabled() {
+        let mut system = AudioSystem::new();
+        system.set_captions_enabled(false);
+        let _ = system.play(SoundSource::new("hi").with_caption("Hi!", CaptionCategory::Speech));
+        assert!(!system.captions_enabled());
+        assert!(system.pending_captions().is_empty());
+    }
+
+    // ==================== MUSIC TESTS ====================
+
+    #[test]
+    fn test_adaptive_music_crossfades_in_update() {
+        let mut system = AudioSystem::new();
+        system.play_music(
+            AdaptiveMusic::new("level")
+                .with_layer(MusicLayer::new("base", "level_base"))
+                .with_layer(MusicLayer::new("drums", "level_drums").bound_to("danger", 0.0, 1.0)),
+        );
+        assert_eq!(system.playing_count(), 2);
+        let drums = system.music().layer("drums").unwrap().handle().unwrap();
+        assert!(system.get(drums).unwrap().source.volume.abs() < f32::EPSILON);
+
+        system.music_mut().set_parameter("danger", 0.8);
+        system.update(0.5);
+        let volume = system.get(drums).unwrap().source.volume;
+        assert!(volume > 0.0 && volume < 0.8);
+
+        system.update(2.0);
+        let volume = system.get(drums).unwrap().source.volume;
+        assert!((volume - 0.8).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_stop_music() {
+        let mut system = AudioSystem::new();
+        system.play_music(
+            AdaptiveMusic::new("level").with_layer(MusicLayer::new("base", "level_base")),
+        );
+        system.stop_music();
+        system.update(0.0);
+        assert_eq!(system.playing_count(), 0);
+        assert!(system.music().layer("base").unwrap().handle().is_none());
+    }
+
+    // ==================== SMOOTHING TESTS ====================
+
+    #[test]
+    fn test_stop_fades_out_before_removal() {
+        let mut system = AudioSystem::new();
+        let handle = system.play(SoundSource::new("hum").with_looping(true));
+        assert!((system.output_volume(handle) - 1.0).abs() < f32::EPSILON);
+
+        system.stop(handle);
+        assert!(!system.is_playing(handle));
+        assert_eq!(system.get(handle).unwrap().state, PlaybackState::Stopping);
+
+        system.update(0.01);
+        let halfway = system.output_volume(handle);
+        assert!((halfway - 0.5).abs() < 0.01);
+
+        system.update(0.01);
+        assert!(system.get(handle).is_none());
+        assert!(system.output_volume(handle).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_stop_with_zero_fade_cuts() {
+        let mut system = AudioSystem::new();
+        let handle = system.play(SoundSource::new("beep"));
+        system.stop_with_fade(handle, 0.0);
+        assert!(system.output_volume(handle).abs() < f32::EPSILON);
+        system.update(0.0);
+        assert!(system.get(handle).is_none());
+    }
+
+    #[test]
+    fn test_paused_sound_stops_without_fade() {
+        let mut system = AudioSystem::new();
+        let handle = system.play(SoundSource::new("beep"));
+        system.pause(handle);
+        system.stop(handle);
+        assert_eq!(system.get(handle).unwrap().state, PlaybackState::Stopped);
+        system.resume(handle);
+        assert!(!system.is_playing(handle));
+    }
+
+    #[test]
+    fn test_listener_teleport_slews_pan_and_volume() {
+        let mut system = AudioSystem::new();
+        system.set_smoothing(SmoothingSettings::new().with_max_rates(2.0, 4.0));
+        let handle = system.play(SoundSource::new("fire").with_position(Vec2::new(-10.0, 0.0)));
+        assert!(system.output_pan(handle) < -0.9);
+
+        // Jump far away, directly below the source
+        let start_volume = system.output_volume(handle);
+        system.set_listener_position(Vec2::new(-10.0, -500.0));
+        let target_volume = system.calculate_final_volume(handle);
+        system.update(0.1);
+        assert!((system.output_pan(handle) + 0.6).abs() < 0.001);
+        assert!((system.output_volume(handle) - (start_volume - 0.2)).abs() < 0.001);
+
+        for _ in 0..20 {
+            system.update(0.1);
+        }
+        assert!((system.output_volume(handle) - target_volume).abs() < 0.001);
+        assert!((system.output_pan(handle) - system.calculate_pan(handle)).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_smoothing_disabled_follows_targets() {
+        let mut system = AudioSystem::new();
+        system.set_smoothing(SmoothingSettings::disabled());
+        let handle = system.play(SoundSource::new("fire").with_position(Vec2::new(10.0, 0.0)));
+        system.set_listener_position(Vec2::new(20.0, 0.0));
+        system.update(0.016);
+        assert!((system.output_pan(handle) - system.calculate_pan(handle)).abs() < f32::EPSILON);
+
+        system.stop(handle);
+        assert!(system.output_volume(handle).abs() < f32::EPSILON);
+    }
+
+    // ==================== DOPPLER TESTS ====================
+
+    #[test]
+    fn test_doppler_shifts_pitch_smoothly() {
+        let mut system = AudioSystem::new();
+        let handle = system.play(
+            SoundSource::new("rocket")
+                .with_position(Vec2::new(200.0, 0.0))
+                .with_velocity(Vec2::new(-150.0, 0.0)),
+        );
+        assert!((system.calculate_final_pitch(handle) - 1.0).abs() < f32::EPSILON);
+
+        system.update(0.05);
+        let first = system.calculate_final_pitch(handle);
+        assert!(first > 1.0);
+
+        for _ in 0..20 {
+            system.update(0.05);
+        }
+        let settled = system.calculate_final_pitch(handle);
+        assert!(settled > first);
+        assert!(settled <= system.doppler().max_factor + f32::EPSILON);
+    }
+
+    #[test]
+    fn test_doppler_disabled() {
+        let mut system = AudioSystem::new();
+        system.set_doppler(DopplerSettings::disabled());
+        let handle = system.play(
+            SoundSource::new("car")
+                .with_position(Vec2::new(50.0, 0.0))
+                .with_velocity(Vec2::new(-100.0, 0.0)),
+        );
+        system.update(0.5);
+        assert!((system.calculate_final_pitch(handle) - 1.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_set_source_motion() {
+        let mut system = AudioSystem::new();
+        let handle = system.play(SoundSource::new("bee"));
+        system.set_source_motion(handle, Vec2::new(5.0, 5.0), Vec2::new(1.0, 0.0));
+        let source = &system.get(handle).unwrap().source;
+        assert_eq!(source.position, Vec2::new(5.0, 5.0));
+        assert_eq!(source.velocity, Vec2::new(1.0, 0.0));
+    }
 }
@@ -0,0 +1,231 @@
+//! Adaptive music built from layered stems.
+//!
+//! An [`AdaptiveMusic`] track is a set of [`MusicLayer`]s that all play in
+//! sync. Each layer can be bound to a named game parameter ("danger",
+//! "speed"); its target volume follows that parameter and the audible volume
+//! eases toward it in [`AudioSystem::update`](crate::AudioSystem::update), so
+//! drums fade in as enemies approach without hard cuts.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{AudioChannel, AudioHandle, SoundSource};
+
+/// One stem of an adaptive track
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MusicLayer {
+    /// Layer name
+    pub name: String,
+    /// Stem audio source
+    pub source: SoundSource,
+    /// Parameter controlling the layer (`None` = always at full volume)
+    pub parameter: Option<String>,
+    /// Parameter value at which the layer starts fading in
+    pub fade_in_start: f32,
+    /// Parameter value at which the layer reaches full volume
+    pub fade_in_end: f32,
+    /// Volume at full strength (0.0 to 1.0)
+    pub max_volume: f32,
+    #[serde(skip)]
+    volume: f32,
+    #[serde(skip)]
+    handle: Option<AudioHandle>,
+}
+
+impl MusicLayer {
+    /// Creates a layer that always plays at full volume
+    #[must_use]
+    pub fn new(name: impl Into<String>, stem: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            source: SoundSource::new(stem)
+                .with_channel(AudioChannel::Music)
+                .with_looping(true),
+            parameter: None,
+            fade_in_start: 0.0,
+            fade_in_end: 1.0,
+            max_volume: 1.0,
+            volume: 0.0,
+            handle: None,
+        }
+    }
+
+    /// Binds the layer to a parameter: silent below `start`, full at `end`
+    #[must_use]
+    pub fn bound_to(mut self, parameter: impl Into<String>, start: f32, end: f32) -> Self {
+        self.parameter = Some(parameter.into());
+        self.fade_in_start = start;
+        self.fade_in_end = end;
+        self
+    }
+
+    /// Sets the volume at full strength
+    #[must_use]
+    pub const fn with_max_volume(mut self, volume: f32) -> Self {
+        self.max_volume = volume;
+        self
+    }
+
+    /// Current (smoothed) volume
+    #[must_use]
+    pub const fn volume(&self) -> f32 {
+        self.volume
+    }
+
+    /// Handle of the playing stem, if started
+    #[must_use]
+    pub const fn handle(&self) -> Option<AudioHandle> {
+        self.handle
+    }
+
+    /// Volume the layer is fading toward for the given parameters
+    #[must_use]
+    pub fn target_volume(&self, parameters: &HashMap<String, f32>) -> f32 {
+        let Some(parameter) = &self.parameter else {
+            return self.max_volume;
+        };
+        let value = parameters.get(parameter).copied().unwrap_or(0.0);
+        let span = self.fade_in_end - self.fade_in_start;
+        let t = if span.abs() < f32::EPSILON {
+            if value >= self.fade_in_end {
+                1.0
+            } else {
+                0.0
+            }
+        } else {
+            ((value - self.fade_in_start) / span).clamp(0.0, 1.0)
+        };
+        t * self.max_volume
+    }
+}
+
+/// Layered music whose mix follows game parameters
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AdaptiveMusic {
+    /// Track name
+    pub name: String,
+    /// Stems, all played in sync
+    pub layers: Vec<MusicLayer>,
+    /// Volume change per second while crossfading
+    pub fade_speed: f32,
+    #[serde(default)]
+    parameters: HashMap<String, f32>,
+}
+
+impl AdaptiveMusic {
+    /// Creates an empty adaptive track
+    #[must_use]
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            layers: Vec::new(),
+            fade_speed: 0.5,
+            parameters: HashMap::new(),
+        }
+    }
+
+    /// Adds a layer
+    #[must_use]
+    pub fn with_layer(mut self, layer: MusicLayer) -> Self {
+        self.layers.push(layer);
+        self
+    }
+
+    /// Sets the crossfade speed (volume per second)
+    #[must_use]
+    pub const fn with_fade_speed(mut self, fade_speed: f32) -> Self {
+        self.fade_speed = fade_speed;
+        self
+    }
+
+    /// Sets a game parameter, clamped to 0.0..=1.0
+    pub fn set_parameter(&mut self, name: impl Into<String>, value: f32) {
+        let _ = self.parameters.insert(name.into(), value.clamp(0.0, 1.0));
+    }
+
+    /// Gets a game parameter (0.0 if never set)
+    #[must_use]
+    pub fn parameter(&self, name: &str) -> f32 {
+        self.parameters.get(name).copied().unwrap_or(0.0)
+    }
+
+    /// Gets a layer by name
+    #[must_use]
+    pub fn layer(&self, name: &str) -> Option<&MusicLayer> {
+        self.layers.iter().find(|l| l.name == name)
+    }
+
+    /// Snaps every layer to its target volume (used when starting playback)
+    pub fn snap_to_targets(&mut self) {
+        for layer in &mut self.layers {
+            layer.volume = layer.target_volume(&self.parameters);
+        }
+    }
+
+    /// Eases layer volumes toward their targets
+    pub fn update(&mut self, dt: f32) {
+        let step = self.fade_speed.max(0.0) * dt;
+        for layer in &mut self.layers {
+            let target = layer.target_volume(&self.parameters);
+            let delta = (target - layer.volume).clamp(-step, step);
+            layer.volume += delta;
+        }
+    }
+
+    pub(crate) fn set_handle(&mut self, index: usize, handle: Option<AudioHandle>) {
+        if let Some(layer) = self.layers.get_mut(index) {
+            layer.handle = handle;
+        }
+    }
+}
+
+impl Default for AdaptiveMusic {
+    fn default() -> Self {
+        Self::new("")
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn battle() -> AdaptiveMusic {
+        AdaptiveMusic::new("battle")
+            .with_layer(MusicLayer::new("base", "battle_base"))
+            .with_layer(MusicLayer::new("drums", "battle_drums").bound_to("danger", 0.2, 0.6))
+            .with_fade_speed(1.0)
+    }
+
+    #[test]
+    fn test_unbound_layer_full_volume() {
+        let music = battle();
+        let base = music.layer("base").unwrap();
+        assert!((base.target_volume(&HashMap::new()) - 1.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_bound_layer_follows_parameter() {
+        let mut music = battle();
+        let drums = music.layer("drums").unwrap().clone();
+        assert!(drums.target_volume(&music.parameters).abs() < f32::EPSILON);
+        music.set_parameter("danger", 0.4);
+        assert!((drums.target_volume(&music.parameters) - 0.5).abs() < 0.001);
+        music.set_parameter("danger", 2.0);
+        assert!((music.parameter("danger") - 1.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_update_crossfades_smoothly() {
+        let mut music = battle();
+        music.snap_to_targets();
+        music.set_parameter("danger", 1.0);
+        music.update(0.25);
+        let drums = music.layer("drums").unwrap().volume();
+        assert!((drums - 0.25).abs() < 0.001);
+        music.update(1.0);
+        let drums = music.layer("drums").unwrap().volume();
+        assert!((drums - 1.0).abs() < f32::EPSILON);
+    }
+}
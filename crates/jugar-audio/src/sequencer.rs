@@ -0,0 +1,557 @@
+//! Pattern-based chiptune sequencer.
+//!
+//! A [`Track`] is plain data: a tempo, a set of [`Voice`]s (square, triangle
+//! or noise) and [`Pattern`]s written as compact note strings such as
+//! `"C4 - E4 - G4 . . ."`, where `-` holds the previous note and `.` is
+//! silence. A [`Sequencer`] parses the track once and synthesizes samples,
+//! so background music needs no audio assets.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{AudioError, Result};
+
+/// Oscillator shape for a voice
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+pub enum Waveform {
+    /// 50% pulse wave (lead and bass)
+    #[default]
+    Square,
+    /// Triangle wave (soft bass and flutes)
+    Triangle,
+    /// Pseudo-random noise (drums)
+    Noise,
+}
+
+/// One row of a channel in a pattern
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    /// Silence
+    Rest,
+    /// Keep playing the previous note
+    Hold,
+    /// Start a note (MIDI note number)
+    Note(u8),
+}
+
+impl Step {
+    /// Parses a single token: `.`, `-` or a note name like `C4`, `F#3`, `Bb2`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the token is not a valid step
+    pub fn parse(token: &str) -> Result<Self> {
+        let invalid = || AudioError::InvalidFormat(format!("invalid note '{token}'"));
+        match token {
+            "." => return Ok(Self::Rest),
+            "-" => return Ok(Self::Hold),
+            _ => {}
+        }
+
+        let mut chars = token.chars();
+        let letter = chars.next().ok_or_else(invalid)?;
+        let base: i32 = match letter.to_ascii_uppercase() {
+            'C' => 0,
+            'D' => 2,
+            'E' => 4,
+            'F' => 5,
+            'G' => 7,
+            'A' => 9,
+            'B' => 11,
+            _ => return Err(invalid()),
+        };
+        let rest = chars.as_str();
+        let (accidental, octave) = rest.strip_prefix('#').map_or_else(
+            || {
+                rest.strip_prefix('b')
+                    .map_or((0, rest), |octave| (-1, octave))
+            },
+            |octave| (1, octave),
+        );
+        let octave: i32 = octave.parse().map_err(|_| invalid())?;
+        let midi = (octave + 1) * 12 + base + accidental;
+        u8::try_from(midi)
+            .ok()
+            .filter(|m| *m <= 127)
+            .map(Self::Note)
+            .ok_or_else(invalid)
+    }
+}
+
+/// Converts a MIDI note number to a frequency in Hz
+#[must_use]
+pub fn midi_to_frequency(note: u8) -> f32 {
+    440.0 * ((f32::from(note) - 69.0) / 12.0).exp2()
+}
+
+/// An instrument channel of a track
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Voice {
+    /// Oscillator shape
+    pub waveform: Waveform,
+    /// Volume (0.0 to 1.0)
+    pub volume: f32,
+}
+
+impl Voice {
+    /// Creates a voice
+    #[must_use]
+    pub const fn new(waveform: Waveform, volume: f32) -> Self {
+        Self { waveform, volume }
+    }
+}
+
+/// A block of rows, one note string per voice
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Pattern {
+    /// Whitespace-separated steps for each voice
+    pub channels: Vec<String>,
+}
+
+impl Pattern {
+    /// Creates a pattern from one note string per voice
+    #[must_use]
+    pub fn new<S: Into<String>>(channels: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            channels: channels.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Parses every channel into steps
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any token is invalid
+    pub fn parse(&self) -> Result<Vec<Vec<Step>>> {
+        self.channels
+            .iter()
+            .map(|line| line.split_whitespace().map(Step::parse).collect())
+            .collect()
+    }
+}
+
+/// A complete piece of music as data
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Track {
+    /// Track name
+    pub name: String,
+    /// Beats per minute
+    pub tempo: f32,
+    /// Rows per beat
+    pub steps_per_beat: u32,
+    /// Instrument channels
+    pub voices: Vec<Voice>,
+    /// Pattern pool
+    pub patterns: Vec<Pattern>,
+    /// Order in which patterns play (indices into `patterns`)
+    pub order: Vec<usize>,
+    /// Order index to jump back to at the end (`None` plays once)
+    #[serde(default)]
+    pub loop_start: Option<usize>,
+}
+
+impl Track {
+    /// Creates an empty track
+    #[must_use]
+    pub fn new(name: impl Into<String>, tempo: f32) -> Self {
+        Self {
+            name: name.into(),
+            tempo,
+            steps_per_beat: 4,
+            voices: Vec::new(),
+            patterns: Vec::new(),
+            order: Vec::new(),
+            loop_start: Some(0),
+        }
+    }
+
+    /// Adds a voice
+    #[must_use]
+    pub fn with_voice(mut self, waveform: Waveform, volume: f32) -> Self {
+        self.voices.push(Voice::new(waveform, volume));
+        self
+    }
+
+    /// Adds a pattern and appends it to the play order
+    #[must_use]
+    pub fn with_pattern(mut self, pattern: Pattern) -> Self {
+        self.order.push(self.patterns.len());
+        self.patterns.push(pattern);
+        self
+    }
+
+    /// Sets the order index the track loops back to
+    #[must_use]
+    pub const fn with_loop_start(mut self, loop_start: Option<usize>) -> Self {
+        self.loop_start = loop_start;
+        self
+    }
+
+    /// Duration of one row in seconds
+    #[must_use]
+    pub fn step_duration(&self) -> f32 {
+        60.0 / (self.tempo.max(1.0) * self.steps_per_beat.max(1) as f32)
+    }
+
+    /// Built-in track for a YAML `music:` word (gentle, adventure, happy,
+    /// calm, exciting)
+    #[must_use]
+    pub fn for_music_word(word: &str) -> Option<Self> {
+        let track = match word {
+            "gentle" => Self::new(word, 80.0)
+                .with_voice(Waveform::Triangle, 0.6)
+                .with_voice(Waveform::Triangle, 0.4)
+                .with_pattern(Pattern::new([
+                    "C5 - E5 - G5 - E5 - D5 - F5 - A5 - F5 -",
+                    "C3 - - - - - - - D3 - - - - - - -",
+                ]))
+                .with_pattern(Pattern::new([
+                    "E5 - G5 - C6 - G5 - D5 - B4 - C5 - - -",
+                    "A2 - - - - - - - G2 - - - C3 - - -",
+                ])),
+            "calm" => Self::new(word, 66.0)
+                .with_voice(Waveform::Triangle, 0.5)
+                .with_voice(Waveform::Triangle, 0.4)
+                .with_pattern(Pattern::new([
+                    "A4 - - - C5 - - - E5 - - - D5 - - -",
+                    "A2 - - - - - - - F2 - - - - - - -",
+                ])),
+            "happy" => Self::new(word, 132.0)
+                .with_voice(Waveform::Square, 0.35)
+                .with_voice(Waveform::Triangle, 0.5)
+                .with_voice(Waveform::Noise, 0.2)
+                .with_pattern(Pattern::new([
+                    "C5 E5 G5 E5 C5 E5 G5 C6 A4 C5 F5 C5 G4 B4 D5 G5",
+                    "C3 . C3 . C3 . C3 . F2 . F2 . G2 . G2 .",
+                    "C6 . C3 . C6 . C3 . C6 . C3 . C6 . C3 .",
+                ])),
+            "adventure" => Self::new(word, 120.0)
+                .with_voice(Waveform::Square, 0.35)
+                .with_voice(Waveform::Triangle, 0.5)
+                .with_voice(Waveform::Noise, 0.2)
+                .with_pattern(Pattern::new([
+                    "D5 - A4 - D5 E5 F5 - E5 - C5 - D5 - - -",
+                    "D3 D3 . D3 D3 . D3 . C3 C3 . C3 C3 . C3 .",
+                    "C3 . C6 . C3 C3 C6 . C3 . C6 . C3 C3 C6 .",
+                ]))
+                .with_pattern(Pattern::new([
+                    "F5 - E5 - D5 - C5 - Bb4 - C5 - D5 - - -",
+                    "Bb2 Bb2 . Bb2 Bb2 . Bb2 . A2 A2 . A2 D3 . D3 .",
+                    "C3 . C6 . C3 C3 C6 . C3 . C6 . C3 C6 C6 C6",
+                ])),
+            "exciting" => Self::new(word, 160.0)
+                .with_voice(Waveform::Square, 0.3)
+                .with_voice(Waveform::Square, 0.25)
+                .with_voice(Waveform::Noise, 0.25)
+                .with_pattern(Pattern::new([
+                    "E5 E5 G5 E5 A5 G5 E5 D5 E5 E5 G5 E5 B5 A5 G5 A5",
+                    "E3 E4 E3 E4 E3 E4 E3 E4 C3 C4 C3 C4 D3 D4 D3 D4",
+                    "C3 C6 C6 C6 C3 C6 C6 C6 C3 C6 C6 C6 C3 C6 C3 C6",
+                ])),
+            _ => return None,
+        };
+        Some(track)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct VoiceState {
+    note: Option<u8>,
+    phase: f32,
+    lfsr: u16,
+    noise_value: f32,
+}
+
+impl Default for VoiceState {
+    fn default() -> Self {
+        Self {
+            note: None,
+            phase: 0.0,
+            lfsr: 1,
+            noise_value: 1.0,
+        }
+    }
+}
+
+/// Plays a [`Track`] by synthesizing samples
+#[derive(Debug, Clone)]
+pub struct Sequencer {
+    track: Track,
+    patterns: Vec<Vec<Vec<Step>>>,
+    voices: Vec<VoiceState>,
+    order_index: usize,
+    row: usize,
+    step_time: f32,
+    started: bool,
+    finished: bool,
+}
+
+impl Sequencer {
+    /// Parses a track for playback
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a pattern contains an invalid note or the order
+    /// refers to a missing pattern
+    pub fn new(track: Track) -> Result<Self> {
+        let patterns = track
+            .patterns
+            .iter()
+            .map(Pattern::parse)
+            .collect::<Result<Vec<_>>>()?;
+        if let Some(bad) = track.order.iter().find(|&&i| i >= patterns.len()) {
+            return Err(AudioError::InvalidFormat(format!(
+                "order refers to missing pattern {bad}"
+            )));
+        }
+        if track.loop_start.is_some_and(|i| i >= track.order.len()) {
+            return Err(AudioError::InvalidFormat(
+                "loop start is past the end of the order".to_string(),
+            ));
+        }
+        let voices = vec![VoiceState::default(); track.voices.len()];
+        let finished = track.order.is_empty();
+        Ok(Self {
+            track,
+            patterns,
+            voices,
+            order_index: 0,
+            row: 0,
+            step_time: 0.0,
+            started: false,
+            finished,
+        })
+    }
+
+    /// Returns the track being played
+    #[must_use]
+    pub const fn track(&self) -> &Track {
+        &self.track
+    }
+
+    /// Current (order index, row) position
+    #[must_use]
+    pub const fn position(&self) -> (usize, usize) {
+        (self.order_index, self.row)
+    }
+
+    /// Returns true once a non-looping track has ended
+    #[must_use]
+    pub const fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Note currently sounding on a voice
+    #[must_use]
+    pub fn note(&self, voice: usize) -> Option<u8> {
+        self.voices.get(voice).and_then(|v| v.note)
+    }
+
+    /// Restarts from the beginning
+    pub fn reset(&mut self) {
+        self.voices.fill(VoiceState::default());
+        self.order_index = 0;
+        self.row = 0;
+        self.step_time = 0.0;
+        self.started = false;
+        self.finished = self.track.order.is_empty();
+    }
+
+    fn current_rows(&self) -> usize {
+        self.patterns[self.track.order[self.order_index]]
+            .iter()
+            .map(Vec::len)
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn apply_row(&mut self) {
+        let pattern = &self.patterns[self.track.order[self.order_index]];
+        for (voice, state) in self.voices.iter_mut().enumerate() {
+            let step = pattern
+                .get(voice)
+                .and_then(|steps| steps.get(self.row))
+                .copied()
+                .unwrap_or(Step::Rest);
+            match step {
+                Step::Rest => state.note = None,
+                Step::Hold => {}
+                Step::Note(note) => {
+                    state.note = Some(note);
+                    state.phase = 0.0;
+                }
+            }
+        }
+    }
+
+    fn next_row(&mut self) {
+        self.row += 1;
+        if self.row < self.current_rows() {
+            return;
+        }
+        self.row = 0;
+        self.order_index += 1;
+        if self.order_index >= self.track.order.len() {
+            if let Some(start) = self.track.loop_start {
+                self.order_index = start;
+            } else {
+                self.order_index = self.track.order.len() - 1;
+                self.finished = true;
+                for state in &mut self.voices {
+                    state.note = None;
+                }
+            }
+        }
+    }
+
+    /// Advances playback by `dt` seconds without synthesizing
+    pub fn advance(&mut self, dt: f32) {
+        if self.finished {
+            return;
+        }
+        if !self.started {
+            self.started = true;
+            self.apply_row();
+        }
+        let step = self.track.step_duration();
+        self.step_time += dt;
+        #[allow(clippy::while_float)]
+        while self.step_time >= step && !self.finished {
+            self.step_time -= step;
+            self.next_row();
+            if !self.finished {
+                self.apply_row();
+            }
+        }
+    }
+
+    /// Fills `out` with mono samples in the range -1.0 to 1.0
+    pub fn render(&mut self, out: &mut [f32], sample_rate: u32) {
+        let dt = 1.0 / sample_rate.max(1) as f32;
+        for sample in out.iter_mut() {
+            self.advance(dt);
+            let mut mix = 0.0;
+            for (state, voice) in self.voices.iter_mut().zip(&self.track.voices) {
+                let Some(note) = state.note else {
+                    continue;
+                };
+                state.phase += midi_to_frequency(note) * dt;
+                let wrapped = state.phase >= 1.0;
+                state.phase = state.phase.fract();
+                let value = match voice.waveform {
+                    Waveform::Square => {
+                        if state.phase < 0.5 {
+                            1.0
+                        } else {
+                            -1.0
+                        }
+                    }
+                    Waveform::Triangle => 4.0_f32.mul_add((state.phase - 0.5).abs(), -1.0),
+                    Waveform::Noise => {
+                        if wrapped {
+                            let bit = (state.lfsr ^ (state.lfsr >> 1)) & 1;
+                            state.lfsr = (state.lfsr >> 1) | (bit << 14);
+                            state.noise_value = if state.lfsr & 1 == 0 { 1.0 } else { -1.0 };
+                        }
+                        state.noise_value
+                    }
+                };
+                mix = value.mul_add(voice.volume, mix);
+            }
+            *sample = (mix * 0.5).clamp(-1.0, 1.0);
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_steps() {
+        assert_eq!(Step::parse(".").unwrap(), Step::Rest);
+        assert_eq!(Step::parse("-").unwrap(), Step::Hold);
+        assert_eq!(Step::parse("A4").unwrap(), Step::Note(69));
+        assert_eq!(Step::parse("C4").unwrap(), Step::Note(60));
+        assert_eq!(Step::parse("F#3").unwrap(), Step::Note(54));
+        assert_eq!(Step::parse("Bb2").unwrap(), Step::Note(46));
+        assert!(Step::parse("H4").is_err());
+        assert!(Step::parse("C").is_err());
+    }
+
+    #[test]
+    fn test_midi_to_frequency() {
+        assert!((midi_to_frequency(69) - 440.0).abs() < 0.01);
+        assert!((midi_to_frequency(81) - 880.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_invalid_order_rejected() {
+        let mut track = Track::new("bad", 120.0);
+        track.order.push(3);
+        assert!(Sequencer::new(track).is_err());
+    }
+
+    #[test]
+    fn test_steps_advance_with_tempo() {
+        let track = Track::new("t", 60.0)
+            .with_voice(Waveform::Square, 1.0)
+            .with_pattern(Pattern::new(["C4 - E4 ."]));
+        let mut seq = Sequencer::new(track).unwrap();
+        seq.advance(0.0);
+        assert_eq!(seq.note(0), Some(60));
+        seq.advance(0.25);
+        assert_eq!(seq.position(), (0, 1));
+        assert_eq!(seq.note(0), Some(60));
+        seq.advance(0.25);
+        assert_eq!(seq.note(0), Some(64));
+        seq.advance(0.25);
+        assert_eq!(seq.note(0), None);
+    }
+
+    #[test]
+    fn test_loops_to_loop_start() {
+        let track = Track::new("t", 60.0)
+            .with_voice(Waveform::Square, 1.0)
+            .with_pattern(Pattern::new(["C4"]))
+            .with_pattern(Pattern::new(["D4"]))
+            .with_loop_start(Some(1));
+        let mut seq = Sequencer::new(track).unwrap();
+        seq.advance(0.5);
+        assert_eq!(seq.note(0), Some(62));
+        seq.advance(0.25);
+        assert_eq!(seq.position(), (1, 0));
+        assert!(!seq.is_finished());
+    }
+
+    #[test]
+    fn test_play_once_finishes() {
+        let track = Track::new("t", 60.0)
+            .with_voice(Waveform::Triangle, 1.0)
+            .with_pattern(Pattern::new(["C4 E4"]))
+            .with_loop_start(None);
+        let mut seq = Sequencer::new(track).unwrap();
+        seq.advance(1.0);
+        assert!(seq.is_finished());
+        assert_eq!(seq.note(0), None);
+    }
+
+    #[test]
+    fn test_render_produces_sound() {
+        let track = Track::for_music_word("happy").unwrap();
+        let mut seq = Sequencer::new(track).unwrap();
+        let mut buffer = vec![0.0; 4410];
+        seq.render(&mut buffer, 44_100);
+        assert!(buffer.iter().any(|s| s.abs() > 0.01));
+        assert!(buffer.iter().all(|s| (-1.0..=1.0).contains(s)));
+    }
+
+    #[test]
+    fn test_all_music_words_parse() {
+        for word in ["gentle", "adventure", "happy", "calm", "exciting"] {
+            let track = Track::for_music_word(word).unwrap();
+            assert!(Sequencer::new(track).is_ok(), "{word} should parse");
+        }
+        assert!(Track::for_music_word("spooky").is_none());
+    }
+}
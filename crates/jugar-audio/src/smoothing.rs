@@ -0,0 +1,126 @@
+//! Click-free stops and parameter smoothing.
+//!
+//! Cutting a waveform off mid-cycle, or jumping its gain or pan between two
+//! buffers, produces an audible click. Stopped sounds therefore fade out over
+//! a few milliseconds, and per-sound volume and pan move toward their targets
+//! at a bounded rate, so a teleporting listener sweeps rather than zippers.
+
+use serde::{Deserialize, Serialize};
+
+/// Default fade-out applied when a sound is stopped, in seconds
+pub const DEFAULT_STOP_FADE: f32 = 0.02;
+
+/// Stop fade and parameter slew settings
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SmoothingSettings {
+    /// Whether volume and pan are rate-limited
+    pub enabled: bool,
+    /// Fade-out length when a sound is stopped, in seconds (0 = cut)
+    pub stop_fade: f32,
+    /// Maximum change in volume per second
+    pub max_volume_rate: f32,
+    /// Maximum change in pan per second (full left to right is 2.0)
+    pub max_pan_rate: f32,
+}
+
+impl SmoothingSettings {
+    /// Creates settings that remove clicks without audible lag
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            enabled: true,
+            stop_fade: DEFAULT_STOP_FADE,
+            max_volume_rate: 8.0,
+            max_pan_rate: 8.0,
+        }
+    }
+
+    /// Creates settings that apply every change immediately
+    #[must_use]
+    pub const fn disabled() -> Self {
+        Self {
+            enabled: false,
+            stop_fade: 0.0,
+            max_volume_rate: 8.0,
+            max_pan_rate: 8.0,
+        }
+    }
+
+    /// Sets the stop fade length in seconds
+    #[must_use]
+    pub const fn with_stop_fade(mut self, seconds: f32) -> Self {
+        self.stop_fade = seconds;
+        self
+    }
+
+    /// Sets the maximum volume and pan change per second
+    #[must_use]
+    pub const fn with_max_rates(mut self, volume: f32, pan: f32) -> Self {
+        self.max_volume_rate = volume;
+        self.max_pan_rate = pan;
+        self
+    }
+
+    /// Moves a volume toward `target` without exceeding the rate limit
+    #[must_use]
+    pub fn smooth_volume(&self, current: f32, target: f32, dt: f32) -> f32 {
+        self.slew(current, target, self.max_volume_rate, dt)
+    }
+
+    /// Moves a pan toward `target` without exceeding the rate limit
+    #[must_use]
+    pub fn smooth_pan(&self, current: f32, target: f32, dt: f32) -> f32 {
+        self.slew(current, target, self.max_pan_rate, dt)
+    }
+
+    fn slew(&self, current: f32, target: f32, rate: f32, dt: f32) -> f32 {
+        if !self.enabled {
+            return target;
+        }
+        let step = rate.max(0.0) * dt.max(0.0);
+        current + (target - current).clamp(-step, step)
+    }
+}
+
+impl Default for SmoothingSettings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_fade_is_twenty_ms() {
+        let smoothing = SmoothingSettings::default();
+        assert!((smoothing.stop_fade - 0.02).abs() < f32::EPSILON);
+        assert!(smoothing.enabled);
+    }
+
+    #[test]
+    fn test_volume_is_rate_limited() {
+        let smoothing = SmoothingSettings::new().with_max_rates(2.0, 4.0);
+        let next = smoothing.smooth_volume(0.0, 1.0, 0.1);
+        assert!((next - 0.2).abs() < 0.001);
+        let next = smoothing.smooth_volume(0.5, 0.45, 0.1);
+        assert!((next - 0.45).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_pan_is_rate_limited() {
+        let smoothing = SmoothingSettings::new().with_max_rates(2.0, 4.0);
+        let next = smoothing.smooth_pan(-1.0, 1.0, 0.1);
+        assert!((next + 0.6).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_disabled_jumps_to_target() {
+        let smoothing = SmoothingSettings::disabled();
+        assert!((smoothing.smooth_pan(-1.0, 1.0, 0.0) - 1.0).abs() < f32::EPSILON);
+        assert!(smoothing.stop_fade.abs() < f32::EPSILON);
+    }
+}
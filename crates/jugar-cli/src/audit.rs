@@ -0,0 +1,327 @@
+//! Accessibility audit of a compiled game.
+//!
+//! [`AccessibilityAudit::run`] combines what the YAML declares with what a
+//! headless run of the game shows: declared colors are measured against the
+//! backdrop, the run's frames are checked for flashing, sound rules for
+//! captions and the drawn entities for touch-target size. The measurements
+//! feed [`AccessibilityValidator`] so each game gets one consolidated report.
+
+use alloc::collections::VecDeque;
+use core::fmt::Write;
+
+use jugar_render::{rasterize, Color, Palette, RenderCommand, RenderQueue};
+use jugar_yaml::safety::Frame;
+use jugar_yaml::{
+    AccessibilityReport, AccessibilityValidator, CompiledAction, CompiledGame, ControlScheme,
+    GameAccessibilityInfo, PhotosensitivityGuard, YamlError,
+};
+
+use crate::scene::{background_color, HeadlessRunner, SCENE_SIZE};
+
+/// Frames simulated when looking for flashes (five seconds at 60 fps)
+pub const AUDIT_FRAMES: u64 = 300;
+
+/// Frames per second of the simulated run
+const AUDIT_FRAME_RATE: usize = 60;
+
+/// CSS width of the smallest screen touch targets are measured on (a
+/// landscape tablet)
+const AUDIT_SCREEN_WIDTH: f32 = 1024.0;
+
+/// Resolution frames are sampled at for flash detection
+const SAMPLE_WIDTH: u32 = 64;
+const SAMPLE_HEIGHT: u32 = 36;
+
+/// Contrast of one declared color against the game's backdrop
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContrastCheck {
+    /// Color word from the YAML
+    pub color: String,
+    /// WCAG contrast ratio against the backdrop (1 to 21)
+    pub ratio: f32,
+}
+
+/// Everything the audit measured for one game
+#[derive(Debug, Clone)]
+pub struct AccessibilityAudit {
+    /// The game's name
+    pub game: String,
+    /// Declared colors that the palette knows, with their contrast
+    pub contrast: Vec<ContrastCheck>,
+    /// Most flashes seen within one second of the simulated run
+    pub peak_flash_rate: f32,
+    /// Rules that play a sound
+    pub sounds: usize,
+    /// Of those, rules that also show a caption
+    pub captioned_sounds: usize,
+    /// Smallest drawn entity in CSS pixels, if the game is played by touch
+    pub smallest_touch_target: Option<f32>,
+    /// The validator's findings from all of the above
+    pub report: AccessibilityReport,
+}
+
+impl AccessibilityAudit {
+    /// Audits `game`, which was compiled from `yaml`
+    ///
+    /// # Errors
+    ///
+    /// Returns error if `yaml` can't be parsed
+    pub fn run(yaml: &str, game: &CompiledGame) -> Result<Self, YamlError> {
+        let validator = AccessibilityValidator::new();
+        let mut info = GameAccessibilityInfo::from_yaml(yaml)?;
+
+        let backdrop = Palette::backgrounds()
+            .get(game.background.as_deref().unwrap_or_default())
+            .unwrap_or_else(|| Color::from_array(background_color(game.background.as_deref())));
+        let crayons = Palette::crayons();
+        let contrast: Vec<ContrastCheck> = info
+            .declared_colors
+            .iter()
+            .filter_map(|color| {
+                crayons.get(color).map(|crayon| ContrastCheck {
+                    color: color.clone(),
+                    ratio: crayon.contrast_ratio(backdrop),
+                })
+            })
+            .collect();
+        if let Some(lowest) = contrast.iter().map(|check| check.ratio).reduce(f32::min) {
+            info.min_contrast_ratio = lowest;
+        }
+
+        let mut runner = HeadlessRunner::new(game);
+        let frames = (0..=AUDIT_FRAMES).map(|frame| {
+            runner.run_to(frame);
+            sample_frame(&runner)
+        });
+        let peak_flash_rate = peak_flash_rate(frames);
+        info.max_flash_rate = peak_flash_rate;
+
+        let (sounds, captioned_sounds) = caption_coverage(game);
+        info.uncaptioned_sounds = sounds - captioned_sounds;
+
+        let touch_played = info.has_touch_input
+            || game
+                .entities
+                .iter()
+                .any(|entity| entity.controls().is_some_and(ControlScheme::uses_pointer));
+        let smallest_touch_target = if touch_played {
+            smallest_entity(&HeadlessRunner::new(game))
+        } else {
+            None
+        };
+        if let Some(size) = smallest_touch_target {
+            // Truncating rounds down, so a target just under the limit fails
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            {
+                info.min_touch_target = size as u32;
+            }
+        }
+
+        Ok(Self {
+            game: game.name.clone(),
+            contrast,
+            peak_flash_rate,
+            sounds,
+            captioned_sounds,
+            smallest_touch_target,
+            report: validator.validate(&info),
+        })
+    }
+
+    /// Returns true if nothing blocks the game from being shared
+    #[must_use]
+    pub const fn passes(&self) -> bool {
+        self.report.passes_minimum
+    }
+
+    /// Share of sound rules with a caption (1.0 when the game has no sounds)
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn caption_coverage(&self) -> f32 {
+        if self.sounds == 0 {
+            1.0
+        } else {
+            self.captioned_sounds as f32 / self.sounds as f32
+        }
+    }
+
+    /// Report for `jugar audit`
+    #[must_use]
+    pub fn render(&self) -> String {
+        let mut text = format!("♿ Accessibility audit for {}\n", self.game);
+        for check in &self.contrast {
+            let _ = writeln!(text, "  contrast  {}: {:.1}:1", check.color, check.ratio);
+        }
+        let _ = writeln!(
+            text,
+            "  flashing  {:.0} per second at most",
+            self.peak_flash_rate
+        );
+        let _ = writeln!(
+            text,
+            "  captions  {} of {} sounds",
+            self.captioned_sounds, self.sounds
+        );
+        if let Some(size) = self.smallest_touch_target {
+            let _ = writeln!(text, "  touch     smallest target {size:.0}px");
+        }
+        for issue in &self.report.issues {
+            let _ = writeln!(
+                text,
+                "❌ {} ({})\n   {}",
+                issue.description,
+                issue.code.wcag_ref(),
+                issue.suggestion
+            );
+        }
+        for warning in &self.report.warnings {
+            let _ = writeln!(
+                text,
+                "⚠️  {} ({})\n   {}",
+                warning.description,
+                warning.code.wcag_ref(),
+                warning.suggestion
+            );
+        }
+        if self.report.is_clean() {
+            text.push_str("✅ Everyone can play this game!\n");
+        }
+        text
+    }
+}
+
+/// Average color of the runner's current frame
+fn sample_frame(runner: &HeadlessRunner<'_>) -> Frame {
+    let mut queue = RenderQueue::new();
+    runner.draw(&mut queue);
+    let image = rasterize(queue.commands(), SCENE_SIZE, SAMPLE_WIDTH, SAMPLE_HEIGHT);
+    let mut sum = [0.0_f32; 3];
+    for pixel in image.pixels().chunks_exact(4) {
+        for (total, &channel) in sum.iter_mut().zip(pixel) {
+            *total += f32::from(channel) / 255.0;
+        }
+    }
+    #[allow(clippy::cast_precision_loss)]
+    let count = (SAMPLE_WIDTH * SAMPLE_HEIGHT) as f32;
+    Frame::new(sum[0] / count, sum[1] / count, sum[2] / count)
+}
+
+/// Most flashes within any one-second window of consecutive frames
+#[allow(clippy::cast_precision_loss)]
+fn peak_flash_rate(frames: impl IntoIterator<Item = Frame>) -> f32 {
+    let guard = PhotosensitivityGuard::new();
+    let mut recent = VecDeque::new();
+    let mut peak = 0;
+    let mut frames = frames.into_iter();
+    let Some(mut prev) = frames.next() else {
+        return 0.0;
+    };
+    for (index, frame) in frames.enumerate() {
+        if guard.detect_flash(&prev, &frame).is_flash {
+            recent.push_back(index);
+            while recent
+                .front()
+                .is_some_and(|&at| index - at >= AUDIT_FRAME_RATE)
+            {
+                let _ = recent.pop_front();
+            }
+            peak = peak.max(recent.len());
+        }
+        prev = frame;
+    }
+    peak as f32
+}
+
+/// Rules that play a sound, and how many of them also show a caption
+fn caption_coverage(game: &CompiledGame) -> (usize, usize) {
+    game.rules
+        .iter()
+        .filter(|rule| {
+            rule.then
+                .iter()
+                .any(|action| matches!(action, CompiledAction::PlaySound(_)))
+        })
+        .fold((0, 0), |(sounds, captioned), rule| {
+            let has_caption = rule
+                .then
+                .iter()
+                .any(|action| matches!(action, CompiledAction::Caption(_)));
+            (sounds + 1, captioned + usize::from(has_caption))
+        })
+}
+
+/// Diameter of the smallest circle drawn, in CSS pixels on the audit screen
+fn smallest_entity(runner: &HeadlessRunner<'_>) -> Option<f32> {
+    let mut queue = RenderQueue::new();
+    runner.draw(&mut queue);
+    queue
+        .commands()
+        .iter()
+        .filter_map(|command| match command {
+            RenderCommand::DrawCircle { radius, .. } => {
+                Some(radius * 2.0 * AUDIT_SCREEN_WIDTH / SCENE_SIZE.x)
+            }
+            _ => None,
+        })
+        .reduce(f32::min)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use jugar_yaml::AccessibilityCode;
+
+    fn audit(yaml: &str) -> AccessibilityAudit {
+        let game = jugar_yaml::compile_game(yaml).unwrap();
+        AccessibilityAudit::run(yaml, &game).unwrap()
+    }
+
+    #[test]
+    fn test_audit_consolidates_measurements() {
+        let audit = audit(
+            "game: stars\ncharacter: bunny\nmove: touch\nbackground: snow\ncolor: white\n\
+             when_touch:\n  target: star\n  sound: twinkle\n",
+        );
+        assert_eq!(audit.game, "stars");
+        assert_eq!(audit.contrast.len(), 1);
+        assert!(audit.contrast[0].ratio < 1.5);
+        assert!(audit.peak_flash_rate.abs() < f32::EPSILON);
+        assert_eq!((audit.sounds, audit.captioned_sounds), (1, 0));
+        assert!(audit.caption_coverage().abs() < f32::EPSILON);
+        assert!(audit.smallest_touch_target.unwrap() >= 44.0);
+
+        let codes: Vec<_> = audit.report.warnings.iter().map(|w| w.code).collect();
+        assert!(codes.contains(&AccessibilityCode::LowContrast));
+        assert!(codes.contains(&AccessibilityCode::MissingCaptions));
+        assert!(!codes.contains(&AccessibilityCode::SmallTouchTargets));
+        assert!(audit.passes());
+        assert!(audit.render().contains("0 of 1 sounds"));
+    }
+
+    #[test]
+    fn test_keyboard_game_has_no_touch_targets() {
+        let audit = audit("game: stars\ncharacter: bunny\nmove: arrows\nbackground: space\n");
+        assert_eq!(audit.smallest_touch_target, None);
+        assert!(audit.contrast.is_empty());
+        assert!((audit.caption_coverage() - 1.0).abs() < f32::EPSILON);
+        assert!(audit.render().contains("Everyone can play"));
+    }
+
+    #[test]
+    fn test_peak_flash_rate_counts_one_second_windows() {
+        // Alternating every 6 frames is 10 flashes a second
+        let strobe = (0..120).map(|i| {
+            if (i / 6) % 2 == 0 {
+                Frame::black()
+            } else {
+                Frame::white()
+            }
+        });
+        assert!((peak_flash_rate(strobe) - 10.0).abs() < f32::EPSILON);
+
+        let steady = vec![Frame::black(); 120];
+        assert!(peak_flash_rate(steady).abs() < f32::EPSILON);
+        assert!(peak_flash_rate(core::iter::empty()).abs() < f32::EPSILON);
+    }
+}
@@ -0,0 +1,807 @@
+//! # jugar-cli
+//!
+//! Command-line entry point to the Jugar ecosystem for teachers and CI
+//! pipelines that work outside an editor.
+//!
+//! ```text
+//! jugar check game.yaml                 # kid-friendly diagnostics
+//! jugar audit game.yaml                 # accessibility audit
+//! jugar build game.yaml -o game.jugar   # shareable bundle
+//! jugar inspect model.apr               # .apr model metadata
+//! jugar upgrade old.jugar               # bundle from an older release
+//! jugar new --template catch-the-stars  # start from a template
+//! jugar serve game.yaml                 # live preview in the browser
+//! ```
+//!
+//! Exit codes are stable so scripts can branch on them: `0` success, `1`
+//! the game or model has problems, `2` bad arguments or unreadable files.
+
+#![forbid(unsafe_code)]
+#![warn(missing_docs)]
+#![warn(clippy::pedantic)]
+#![allow(clippy::module_name_repetitions)]
+
+extern crate alloc;
+
+mod audit;
+mod scene;
+mod serve;
+mod websocket;
+
+use core::fmt::Write as _;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use jugar_apr::AprFile;
+use jugar_yaml::{
+    compile_game, detect_schema_level, BundleError, BundleMetadata, GameBundle, GameTemplate,
+    ScaffoldingEngine, SchemaLevel, TemplateCatalog, YamlError,
+};
+use thiserror::Error;
+
+pub use audit::{AccessibilityAudit, ContrastCheck, AUDIT_FRAMES};
+pub use serve::{serve, LiveSession, PreviewUpdate};
+
+/// Exit code for success
+pub const EXIT_OK: u8 = 0;
+
+/// Exit code when the game, bundle or model has problems
+pub const EXIT_INVALID: u8 = 1;
+
+/// Exit code for bad arguments or file system errors
+pub const EXIT_USAGE: u8 = 2;
+
+/// Port `jugar serve` listens on by default
+pub const DEFAULT_PORT: u16 = 8080;
+
+/// Usage text printed by `jugar help`
+pub const USAGE: &str = "\
+Usage: jugar <command> [options]
+
+Commands:
+  check <game.yaml>                  Check a game for mistakes
+  audit <game.yaml>                  Check that everyone can play a game
+  build <game.yaml> [-o <out>]       Bundle a game for sharing (.jugar)
+        [--title <title>]
+  inspect <model.apr>                Show what's inside an AI model
+  upgrade <game.jugar> [-o <out>]    Update a bundle from an older Jugar
+          [--dry-run]
+  new --template <name> [-o <out>]   Start a new game from a template
+      [--force]
+  new --list                         List the available templates
+  serve <game.yaml> [--port <port>]  Live preview in the browser
+  help                               Show this message
+  version                            Show the version
+";
+
+/// CLI errors
+#[derive(Error, Debug)]
+pub enum CliError {
+    /// Bad command line
+    #[error("{0}\n\n{USAGE}")]
+    Usage(String),
+
+    /// A file couldn't be read or written
+    #[error("{path}: {source}")]
+    Io {
+        /// File involved
+        path: PathBuf,
+        /// Underlying error
+        source: std::io::Error,
+    },
+
+    /// The game has mistakes (message is already kid-friendly)
+    #[error("{0}")]
+    Game(String),
+
+    /// The game couldn't be bundled
+    #[error("Couldn't build the bundle: {0}")]
+    Bundle(String),
+
+    /// The model file is not a valid .apr
+    #[error("{path}: not a valid .apr model: {message}")]
+    Model {
+        /// File involved
+        path: PathBuf,
+        /// What was wrong
+        message: String,
+    },
+
+    /// The bundle can't be read or upgraded
+    #[error("{path}: {message}")]
+    Upgrade {
+        /// File involved
+        path: PathBuf,
+        /// What was wrong
+        message: String,
+    },
+
+    /// No template with that name
+    #[error("No template called '{0}'. Try `jugar new --list`.")]
+    UnknownTemplate(String),
+
+    /// Refused to overwrite an existing file
+    #[error("{0} already exists (use --force to replace it)")]
+    Exists(PathBuf),
+}
+
+impl CliError {
+    /// Process exit code for this error
+    #[must_use]
+    pub const fn exit_code(&self) -> u8 {
+        match self {
+            Self::Game(_) | Self::Bundle(_) | Self::Model { .. } | Self::Upgrade { .. } => {
+                EXIT_INVALID
+            }
+            Self::Usage(_) | Self::Io { .. } | Self::UnknownTemplate(_) | Self::Exists(_) => {
+                EXIT_USAGE
+            }
+        }
+    }
+}
+
+/// Result type for CLI operations
+pub type Result<T> = core::result::Result<T, CliError>;
+
+/// A parsed command line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// Compile a game and report problems
+    Check {
+        /// Game YAML file
+        path: PathBuf,
+    },
+    /// Compile a game and audit its accessibility
+    Audit {
+        /// Game YAML file
+        path: PathBuf,
+    },
+    /// Compile a game and write a shareable bundle
+    Build {
+        /// Game YAML file
+        path: PathBuf,
+        /// Bundle file (defaults to the game path with `.jugar`)
+        output: Option<PathBuf>,
+        /// Bundle title (defaults to the game's name)
+        title: Option<String>,
+    },
+    /// Print an .apr model's metadata
+    Inspect {
+        /// Model file
+        path: PathBuf,
+    },
+    /// Rewrite a bundle in the current format
+    Upgrade {
+        /// Bundle file
+        path: PathBuf,
+        /// Upgraded file (defaults to replacing the bundle)
+        output: Option<PathBuf>,
+        /// Only report what would change
+        dry_run: bool,
+    },
+    /// Write a template to a new game file
+    New {
+        /// Template id or name
+        template: String,
+        /// Output file (defaults to `<template-id>.yaml`)
+        output: Option<PathBuf>,
+        /// Overwrite an existing file
+        force: bool,
+    },
+    /// Live preview in the browser
+    Serve {
+        /// Game YAML file
+        path: PathBuf,
+        /// Local port to listen on
+        port: u16,
+    },
+    /// List the available templates
+    Templates,
+    /// Print usage
+    Help,
+    /// Print the version
+    Version,
+}
+
+/// Parses command-line arguments (without the program name)
+///
+/// # Errors
+///
+/// Returns [`CliError::Usage`] for unknown commands, unknown flags or
+/// missing values.
+pub fn parse_args<I, S>(args: I) -> Result<Command>
+where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+{
+    let mut args = args.into_iter().map(Into::into);
+    let Some(command) = args.next() else {
+        return Ok(Command::Help);
+    };
+
+    let mut positional = Vec::new();
+    let mut output = None;
+    let mut title = None;
+    let mut template = None;
+    let mut force = false;
+    let mut list = false;
+    let mut dry_run = false;
+    let mut port = DEFAULT_PORT;
+    while let Some(arg) = args.next() {
+        let mut value = |flag: &str| {
+            args.next()
+                .ok_or_else(|| CliError::Usage(format!("{flag} needs a value")))
+        };
+        match arg.as_str() {
+            "-o" | "--output" => output = Some(PathBuf::from(value(arg.as_str())?)),
+            "--title" => title = Some(value(arg.as_str())?),
+            "-t" | "--template" => template = Some(value(arg.as_str())?),
+            "--port" => {
+                let text = value(arg.as_str())?;
+                port = text
+                    .parse()
+                    .map_err(|_| CliError::Usage(format!("'{text}' is not a port number")))?;
+            }
+            "--force" => force = true,
+            "--list" => list = true,
+            "--dry-run" => dry_run = true,
+            "-h" | "--help" => return Ok(Command::Help),
+            flag if flag.starts_with('-') => {
+                return Err(CliError::Usage(format!("unknown option '{flag}'")));
+            }
+            _ => positional.push(arg),
+        }
+    }
+
+    let single_path = |what: &str| match positional.as_slice() {
+        [path] => Ok(PathBuf::from(path)),
+        [] => Err(CliError::Usage(format!("`jugar {command}` needs a {what}"))),
+        _ => Err(CliError::Usage(format!(
+            "`jugar {command}` takes a single {what}"
+        ))),
+    };
+
+    match command.as_str() {
+        "check" => Ok(Command::Check {
+            path: single_path("game file")?,
+        }),
+        "audit" => Ok(Command::Audit {
+            path: single_path("game file")?,
+        }),
+        "build" => Ok(Command::Build {
+            path: single_path("game file")?,
+            output,
+            title,
+        }),
+        "inspect" => Ok(Command::Inspect {
+            path: single_path("model file")?,
+        }),
+        "upgrade" => Ok(Command::Upgrade {
+            path: single_path("bundle file")?,
+            output,
+            dry_run,
+        }),
+        "new" if list => Ok(Command::Templates),
+        "new" => {
+            let template = match (template, positional.as_slice()) {
+                (Some(template), []) => template,
+                (None, [template]) => template.clone(),
+                _ => {
+                    return Err(CliError::Usage(
+                        "`jugar new` needs one --template".to_string(),
+                    ))
+                }
+            };
+            Ok(Command::New {
+                template,
+                output,
+                force,
+            })
+        }
+        "serve" => Ok(Command::Serve {
+            path: single_path("game file")?,
+            port,
+        }),
+        "templates" => Ok(Command::Templates),
+        "help" | "-h" | "--help" => Ok(Command::Help),
+        "version" | "-V" | "--version" => Ok(Command::Version),
+        other => Err(CliError::Usage(format!("unknown command '{other}'"))),
+    }
+}
+
+/// Runs a command, writing its report to `out`
+///
+/// # Errors
+///
+/// Returns an error describing what went wrong; see
+/// [`CliError::exit_code`] for the matching process exit code.
+pub fn run(command: &Command, out: &mut dyn Write) -> Result<()> {
+    match command {
+        Command::Check { path } => check(path, out),
+        Command::Audit { path } => audit(path, out),
+        Command::Build {
+            path,
+            output,
+            title,
+        } => build(path, output.as_deref(), title.as_deref(), out),
+        Command::Inspect { path } => inspect(path, out),
+        Command::Upgrade {
+            path,
+            output,
+            dry_run,
+        } => upgrade(path, output.as_deref(), *dry_run, out),
+        Command::New {
+            template,
+            output,
+            force,
+        } => new_game(template, output.as_deref(), *force, out),
+        Command::Serve { path, port } => serve(path, *port, out),
+        Command::Templates => list_templates(out),
+        Command::Help => write(out, USAGE),
+        Command::Version => write(out, &format!("jugar {}\n", env!("CARGO_PKG_VERSION"))),
+    }
+}
+
+pub(crate) fn write(out: &mut dyn Write, text: &str) -> Result<()> {
+    out.write_all(text.as_bytes())
+        .map_err(|source| CliError::Io {
+            path: PathBuf::from("<stdout>"),
+            source,
+        })
+}
+
+fn read_to_string(path: &Path) -> Result<String> {
+    fs::read_to_string(path).map_err(|source| CliError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+pub(crate) const fn level_number(level: SchemaLevel) -> u8 {
+    match level {
+        SchemaLevel::Level1 => 1,
+        SchemaLevel::Level2 => 2,
+        SchemaLevel::Level3 => 3,
+    }
+}
+
+/// Kid-friendly explanation of a compile error, with a worked example
+pub(crate) fn explain(yaml: &str, error: &YamlError) -> String {
+    let level = detect_schema_level(yaml).unwrap_or_default();
+    ScaffoldingEngine::new(level).scaffold(yaml, error).render()
+}
+
+/// Compiles a game, turning errors into scaffolded kid-friendly text
+fn compile(path: &Path) -> Result<(String, jugar_yaml::CompiledGame)> {
+    let yaml = read_to_string(path)?;
+    match compile_game(&yaml) {
+        Ok(game) => Ok((yaml, game)),
+        Err(error) => Err(CliError::Game(format!(
+            "{}: {}",
+            path.display(),
+            explain(&yaml, &error)
+        ))),
+    }
+}
+
+fn check(path: &Path, out: &mut dyn Write) -> Result<()> {
+    let (_, game) = compile(path)?;
+    write(
+        out,
+        &format!(
+            "✅ {} looks great! (Level {}, {} things, {} rules)\n",
+            game.name,
+            level_number(game.level),
+            game.entities.len(),
+            game.rules.len()
+        ),
+    )
+}
+
+fn audit(path: &Path, out: &mut dyn Write) -> Result<()> {
+    let (yaml, game) = compile(path)?;
+    let audit = AccessibilityAudit::run(&yaml, &game)
+        .map_err(|error| CliError::Game(explain(&yaml, &error)))?;
+    write(out, &audit.render())?;
+    if audit.passes() {
+        Ok(())
+    } else {
+        Err(CliError::Game(format!(
+            "{}: some players can't play this game yet",
+            path.display()
+        )))
+    }
+}
+
+fn build(
+    path: &Path,
+    output: Option<&Path>,
+    title: Option<&str>,
+    out: &mut dyn Write,
+) -> Result<()> {
+    let (yaml, game) = compile(path)?;
+    let title = title.map_or_else(|| game.name.clone(), str::to_string);
+    let mut metadata = BundleMetadata::new(title);
+    metadata.schema_level = level_number(game.level);
+    let mut bundle =
+        GameBundle::from_yaml(yaml, metadata).map_err(|e| CliError::Bundle(e.to_string()))?;
+    bundle
+        .set_thumbnail(&scene::thumbnail(&game))
+        .map_err(|e| CliError::Bundle(e.to_string()))?;
+    let json = bundle
+        .to_json()
+        .map_err(|e| CliError::Bundle(e.to_string()))?;
+
+    let output = output.map_or_else(|| path.with_extension("jugar"), Path::to_path_buf);
+    fs::write(&output, &json).map_err(|source| CliError::Io {
+        path: output.clone(),
+        source,
+    })?;
+    write(
+        out,
+        &format!("📦 Built {} ({} bytes)\n", output.display(), json.len()),
+    )
+}
+
+fn inspect(path: &Path, out: &mut dyn Write) -> Result<()> {
+    let bytes = fs::read(path).map_err(|source| CliError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let file = AprFile::from_bytes(&bytes).map_err(|e| CliError::Model {
+        path: path.to_path_buf(),
+        message: e.to_string(),
+    })?;
+
+    let meta = &file.model.metadata;
+    let data = &file.model.data;
+    let mut report = format!(
+        "{} v{}\n  format:       APR v{}\n  author:       {}\n  license:      {}\n  \
+         architecture: {}\n  parameters:   {} weights, {} biases\n  size:         {} bytes\n",
+        meta.name,
+        meta.version,
+        file.version,
+        meta.author,
+        meta.license,
+        data.architecture,
+        data.weights.len(),
+        data.biases.len(),
+        bytes.len(),
+    );
+    if !meta.description.is_empty() {
+        let _ = writeln!(report, "  description:  {}", meta.description);
+    }
+    if let Some(levels) = meta.difficulty_levels {
+        let _ = writeln!(report, "  difficulty:   {levels} levels");
+    }
+    for (label, schema) in [
+        ("inputs", &meta.input_schema),
+        ("outputs", &meta.output_schema),
+    ] {
+        if let Some(schema) = schema {
+            let _ = writeln!(report, "  {label}:");
+            for field in &schema.fields {
+                let _ = writeln!(report, "    {} ({})", field.name, field.field_type);
+            }
+        }
+    }
+    write(out, &report)
+}
+
+fn upgrade(path: &Path, output: Option<&Path>, dry_run: bool, out: &mut dyn Write) -> Result<()> {
+    let json = read_to_string(path)?;
+    let invalid = |e: BundleError| CliError::Upgrade {
+        path: path.to_path_buf(),
+        message: e.to_string(),
+    };
+    let report = GameBundle::check_upgrade(&json).map_err(invalid)?;
+    if report.is_current() {
+        return write(
+            out,
+            &format!(
+                "✅ {} is up to date (version {})\n",
+                path.display(),
+                report.to
+            ),
+        );
+    }
+
+    let mut text = format!(
+        "{} {} from version {} to {}\n",
+        if dry_run {
+            "🔍 Would upgrade"
+        } else {
+            "⬆️ Upgraded"
+        },
+        path.display(),
+        report.from,
+        report.to
+    );
+    for step in &report.steps {
+        let _ = writeln!(text, "  - {step}");
+    }
+    if !dry_run {
+        let upgraded = GameBundle::from_json(&json)
+            .and_then(|bundle| bundle.to_json())
+            .map_err(invalid)?;
+        let output = output.unwrap_or(path);
+        fs::write(output, upgraded).map_err(|source| CliError::Io {
+            path: output.to_path_buf(),
+            source,
+        })?;
+    }
+    write(out, &text)
+}
+
+/// Lowercase, dash-separated form of a template name
+fn slug(name: &str) -> String {
+    name.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Finds a template by id or by slugified name ("catch-the-stars")
+fn find_template<'a>(catalog: &'a TemplateCatalog, query: &str) -> Option<&'a GameTemplate> {
+    let wanted = slug(query);
+    catalog
+        .get(query)
+        .or_else(|| catalog.templates.iter().find(|t| slug(&t.name) == wanted))
+}
+
+fn new_game(template: &str, output: Option<&Path>, force: bool, out: &mut dyn Write) -> Result<()> {
+    let catalog = TemplateCatalog::with_defaults();
+    let template = find_template(&catalog, template)
+        .ok_or_else(|| CliError::UnknownTemplate(template.to_string()))?;
+    let output = output.map_or_else(
+        || PathBuf::from(format!("{}.yaml", template.id)),
+        Path::to_path_buf,
+    );
+    if output.exists() && !force {
+        return Err(CliError::Exists(output));
+    }
+    fs::write(&output, &template.yaml).map_err(|source| CliError::Io {
+        path: output.clone(),
+        source,
+    })?;
+    write(
+        out,
+        &format!(
+            "🎮 Created {} from \"{}\"\n   Next: jugar check {}\n",
+            output.display(),
+            template.name,
+            output.display()
+        ),
+    )
+}
+
+fn list_templates(out: &mut dyn Write) -> Result<()> {
+    let catalog = TemplateCatalog::with_defaults();
+    let mut report = String::new();
+    for template in &catalog.templates {
+        let _ = writeln!(
+            report,
+            "{:<18} Level {}  {} - {}",
+            template.id,
+            level_number(template.level),
+            template.name,
+            template.description
+        );
+    }
+    write(out, &report)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use jugar_apr::AprModel;
+
+    /// Fresh scratch directory per test
+    fn scratch(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("jugar-cli-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn run_to_string(command: &Command) -> (Result<()>, String) {
+        let mut out = Vec::new();
+        let result = run(command, &mut out);
+        (result, String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn test_parse_commands() {
+        assert_eq!(
+            parse_args(["build", "game.yaml", "-o", "out.jugar"]).unwrap(),
+            Command::Build {
+                path: PathBuf::from("game.yaml"),
+                output: Some(PathBuf::from("out.jugar")),
+                title: None,
+            }
+        );
+        assert_eq!(
+            parse_args(["new", "--template", "catch-the-stars"]).unwrap(),
+            Command::New {
+                template: "catch-the-stars".to_string(),
+                output: None,
+                force: false,
+            }
+        );
+        assert_eq!(parse_args(["new", "--list"]).unwrap(), Command::Templates);
+        assert_eq!(
+            parse_args(["serve", "game.yaml", "--port", "3000"]).unwrap(),
+            Command::Serve {
+                path: PathBuf::from("game.yaml"),
+                port: 3000,
+            }
+        );
+        assert!(parse_args(["serve", "game.yaml", "--port", "http"]).is_err());
+        assert_eq!(parse_args(Vec::<String>::new()).unwrap(), Command::Help);
+
+        let bad = parse_args(["check"]).unwrap_err();
+        assert_eq!(bad.exit_code(), EXIT_USAGE);
+        assert!(parse_args(["check", "a.yaml", "--wat"]).is_err());
+        assert!(parse_args(["build", "a.yaml", "-o"]).is_err());
+        assert!(parse_args(["launch"]).is_err());
+    }
+
+    #[test]
+    fn test_check_reports_kid_friendly_errors() {
+        let dir = scratch("check");
+        let good = dir.join("good.yaml");
+        fs::write(
+            &good,
+            "game: catch-the-stars\ncharacter: bunny\nmove: arrows\n",
+        )
+        .unwrap();
+        let (result, text) = run_to_string(&Command::Check { path: good });
+        assert!(result.is_ok());
+        assert!(text.contains("catch-the-stars"));
+
+        let bad = dir.join("bad.yaml");
+        fs::write(&bad, "character: dinosaur\n").unwrap();
+        let (result, _) = run_to_string(&Command::Check { path: bad });
+        let error = result.unwrap_err();
+        assert_eq!(error.exit_code(), EXIT_INVALID);
+        assert!(error.to_string().contains("bad.yaml"));
+
+        let (result, _) = run_to_string(&Command::Check {
+            path: dir.join("missing.yaml"),
+        });
+        assert_eq!(result.unwrap_err().exit_code(), EXIT_USAGE);
+    }
+
+    #[test]
+    fn test_audit_reports_findings() {
+        let dir = scratch("audit");
+        let game = dir.join("game.yaml");
+        fs::write(
+            &game,
+            "game: stars\ncharacter: bunny\nbackground: snow\ncolor: white\n",
+        )
+        .unwrap();
+        assert_eq!(
+            parse_args(["audit", "game.yaml"]).unwrap(),
+            Command::Audit {
+                path: PathBuf::from("game.yaml"),
+            }
+        );
+        let (result, text) = run_to_string(&Command::Audit { path: game });
+        assert!(result.is_ok());
+        assert!(text.contains("contrast  white"));
+        assert!(text.contains("WCAG 1.4.3"));
+    }
+
+    #[test]
+    fn test_build_writes_verifiable_bundle() {
+        let dir = scratch("build");
+        let game = dir.join("game.yaml");
+        fs::write(&game, "game: star-catcher\ncharacter: bunny\n").unwrap();
+        let (result, _) = run_to_string(&Command::Build {
+            path: game.clone(),
+            output: None,
+            title: None,
+        });
+        assert!(result.is_ok());
+        let json = fs::read_to_string(game.with_extension("jugar")).unwrap();
+        let bundle = GameBundle::from_json(&json).unwrap();
+        assert_eq!(bundle.metadata().title, "star-catcher");
+        assert!(bundle.yaml().contains("bunny"));
+        assert!(bundle
+            .thumbnail()
+            .is_some_and(|png| png.starts_with(b"\x89PNG")));
+    }
+
+    #[test]
+    fn test_inspect_model() {
+        let dir = scratch("inspect");
+        let model = dir.join("chase.apr");
+        fs::write(
+            &model,
+            AprModel::builtin("chase").unwrap().to_bytes().unwrap(),
+        )
+        .unwrap();
+        let (result, text) = run_to_string(&Command::Inspect { path: model });
+        assert!(result.is_ok());
+        assert!(text.contains("architecture:"));
+
+        let junk = dir.join("junk.apr");
+        fs::write(&junk, b"not a model").unwrap();
+        let (result, _) = run_to_string(&Command::Inspect { path: junk });
+        assert_eq!(result.unwrap_err().exit_code(), EXIT_INVALID);
+    }
+
+    #[test]
+    fn test_upgrade_old_bundle() {
+        let dir = scratch("upgrade");
+        let path = dir.join("hop.jugar");
+        // Written by a release that used version 1 bundles
+        let old = r#"{"version":1,"game_yaml":"character: bunny\n","metadata":{"creator_nickname":null,"created_at":0,"title":"Bunny Hop","description":"","schema_level":1,"tags":[]},"assets":[],"checksum":1480726113}"#;
+        fs::write(&path, old).unwrap();
+
+        let (result, text) = run_to_string(&Command::Upgrade {
+            path: path.clone(),
+            output: None,
+            dry_run: true,
+        });
+        assert!(result.is_ok());
+        assert!(text.contains("Would upgrade"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), old);
+
+        let upgrade = Command::Upgrade {
+            path: path.clone(),
+            output: None,
+            dry_run: false,
+        };
+        assert!(run_to_string(&upgrade).0.is_ok());
+        let bundle = GameBundle::from_json(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(bundle.version, jugar_yaml::BUNDLE_VERSION);
+        let (result, text) = run_to_string(&upgrade);
+        assert!(result.is_ok());
+        assert!(text.contains("up to date"));
+
+        fs::write(&path, old.replace("Bunny Hop", "Bunny Flop")).unwrap();
+        let (result, _) = run_to_string(&upgrade);
+        assert_eq!(result.unwrap_err().exit_code(), EXIT_INVALID);
+
+        assert_eq!(
+            parse_args(["upgrade", "old.jugar", "--dry-run"]).unwrap(),
+            Command::Upgrade {
+                path: PathBuf::from("old.jugar"),
+                output: None,
+                dry_run: true,
+            }
+        );
+    }
+
+    #[test]
+    fn test_new_from_template_name_or_id() {
+        let dir = scratch("new");
+        let output = dir.join("mine.yaml");
+        let command = Command::New {
+            template: "catch-the-stars".to_string(),
+            output: Some(output.clone()),
+            force: false,
+        };
+        assert!(run_to_string(&command).0.is_ok());
+        assert!(compile_game(&fs::read_to_string(&output).unwrap()).is_ok());
+
+        // Refuses to clobber the kid's work without --force
+        assert!(matches!(
+            run_to_string(&command).0,
+            Err(CliError::Exists(_))
+        ));
+
+        let catalog = TemplateCatalog::with_defaults();
+        assert_eq!(find_template(&catalog, "pong").unwrap().id, "pong");
+        assert_eq!(
+            find_template(&catalog, "Avoid the Spiders").unwrap().id,
+            "avoid-spiders"
+        );
+        assert!(find_template(&catalog, "chess").is_none());
+    }
+}
@@ -0,0 +1,15 @@
+//! `jugar` command-line tool
+
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let result = jugar_cli::parse_args(std::env::args().skip(1))
+        .and_then(|command| jugar_cli::run(&command, &mut std::io::stdout().lock()));
+    match result {
+        Ok(()) => ExitCode::from(jugar_cli::EXIT_OK),
+        Err(error) => {
+            eprintln!("{error}");
+            ExitCode::from(error.exit_code())
+        }
+    }
+}
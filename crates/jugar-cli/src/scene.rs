@@ -0,0 +1,298 @@
+//! Headless preview runner.
+//!
+//! Steps a compiled game without a window and draws its overview scene: the
+//! backdrop, every entity as a labelled circle and a summary line. Entities
+//! with a movement pattern follow it over time; player-controlled ones stay
+//! put since nobody is pressing keys, and quiz questions take turns on
+//! screen. `jugar serve` shows frame 0 and `jugar build` rasterizes frame
+//! [`THUMBNAIL_FRAME`] into the bundle thumbnail.
+
+use glam::Vec2;
+use jugar_render::{rasterize, RenderCommand, RenderQueue};
+use jugar_yaml::CompiledGame;
+
+use crate::level_number;
+
+/// Size the scene is drawn at
+pub const SCENE_SIZE: Vec2 = Vec2::new(1920.0, 1080.0);
+
+/// Frame rasterized for bundle thumbnails (one second in)
+const THUMBNAIL_FRAME: u64 = 60;
+
+/// Thumbnail size in pixels
+const THUMBNAIL_WIDTH: u32 = 320;
+
+/// Thumbnail height in pixels
+const THUMBNAIL_HEIGHT: u32 = 180;
+
+/// Simulation rate of the runner
+const FRAME_RATE: f32 = 60.0;
+
+/// How long each quiz question stays up
+const QUESTION_SECONDS: f32 = 5.0;
+
+/// Color of all scene text
+pub const WHITE: [f32; 4] = [1.0, 1.0, 1.0, 1.0];
+
+/// Background color for a named background
+pub fn background_color(name: Option<&str>) -> [f32; 4] {
+    match name.unwrap_or_default() {
+        "space" | "night" => [0.04, 0.05, 0.16, 1.0],
+        "forest" | "grass" => [0.12, 0.35, 0.16, 1.0],
+        "ocean" | "water" | "underwater" => [0.05, 0.25, 0.45, 1.0],
+        "sky" | "clouds" => [0.45, 0.7, 0.95, 1.0],
+        "desert" | "beach" => [0.85, 0.72, 0.45, 1.0],
+        "castle" | "dungeon" | "cave" => [0.2, 0.18, 0.2, 1.0],
+        _ => [0.15, 0.15, 0.2, 1.0],
+    }
+}
+
+/// Stable color per entity type so the same thing always looks the same
+fn entity_color(kind: &str) -> [f32; 4] {
+    const PALETTE: [[f32; 4]; 8] = [
+        [1.0, 0.85, 0.2, 1.0],
+        [1.0, 0.45, 0.5, 1.0],
+        [0.45, 0.85, 1.0, 1.0],
+        [0.55, 1.0, 0.55, 1.0],
+        [0.8, 0.55, 1.0, 1.0],
+        [1.0, 0.65, 0.3, 1.0],
+        [0.95, 0.95, 0.95, 1.0],
+        [0.4, 0.6, 1.0, 1.0],
+    ];
+    let hash = kind.bytes().fold(0_usize, |hash, byte| {
+        hash.wrapping_mul(31).wrapping_add(usize::from(byte))
+    });
+    PALETTE[hash % PALETTE.len()]
+}
+
+/// Offset from the start position after `t` seconds of a movement pattern
+fn motion_offset(movement: Option<&str>, t: f32) -> Vec2 {
+    match movement.unwrap_or_default() {
+        "circle" => Vec2::new(t.cos() - 1.0, t.sin()) * 90.0,
+        "zigzag" => Vec2::new(t * 100.0, (0.5 - ((t * 2.0).fract() - 0.5).abs()) * 240.0),
+        "patrol" => Vec2::new(t.sin() * 200.0, 0.0),
+        "bounce" => Vec2::new(0.0, -(t * 3.0).sin().abs() * 140.0),
+        "wander" => Vec2::new(
+            (t * 0.7).sin() * 110.0,
+            (t * 1.3).cos().mul_add(70.0, -70.0),
+        ),
+        "auto" | "chase" => Vec2::new(t * 80.0, 0.0),
+        _ => Vec2::ZERO,
+    }
+}
+
+/// Runs a compiled game without a window
+#[derive(Debug)]
+pub struct HeadlessRunner<'a> {
+    game: &'a CompiledGame,
+    frame: u64,
+}
+
+impl<'a> HeadlessRunner<'a> {
+    /// Starts at frame 0
+    pub const fn new(game: &'a CompiledGame) -> Self {
+        Self { game, frame: 0 }
+    }
+
+    /// Advances to `frame` (never backwards)
+    pub fn run_to(&mut self, frame: u64) {
+        self.frame = self.frame.max(frame);
+    }
+
+    /// Draws the current frame
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    pub fn draw(&self, queue: &mut RenderQueue) {
+        let game = self.game;
+        let t = self.frame as f32 / FRAME_RATE;
+        queue.push(RenderCommand::Clear {
+            color: background_color(game.background.as_deref()),
+        });
+        queue.text(&game.name, Vec2::new(48.0, 40.0), 64.0, WHITE);
+
+        if !game.questions.is_empty() {
+            let index = (t / QUESTION_SECONDS) as usize % game.questions.len();
+            let question = &game.questions[index];
+            queue.text(&question.ask, Vec2::new(48.0, 160.0), 48.0, WHITE);
+            let spacing = SCENE_SIZE.x / (question.choices.len() as f32 + 1.0);
+            for (slot, choice) in question.choices.iter().enumerate() {
+                let x = spacing * (slot as f32 + 1.0) - 48.0;
+                queue.text(choice, Vec2::new(x, 240.0), 40.0, WHITE);
+            }
+        }
+
+        let unplaced = game
+            .entities
+            .iter()
+            .filter(|entity| entity.position.is_none())
+            .count();
+        let spacing = SCENE_SIZE.x / (unplaced as f32 + 1.0);
+        let mut slot = 0.0;
+        for entity in &game.entities {
+            let start = entity.position.map_or_else(
+                || {
+                    slot += 1.0;
+                    Vec2::new(spacing * slot, SCENE_SIZE.y * 0.5)
+                },
+                |(x, y)| Vec2::new(x, y),
+            );
+            let center = start + motion_offset(entity.movement.as_deref(), t);
+            queue.fill_circle(center, 56.0, entity_color(&entity.entity_type));
+            queue.text(
+                &entity.entity_type,
+                center + Vec2::new(-56.0, 72.0),
+                36.0,
+                WHITE,
+            );
+        }
+
+        queue.text(
+            format!(
+                "Level {} · {} rules{}",
+                level_number(game.level),
+                game.rules.len(),
+                game.music
+                    .as_ref()
+                    .map(|music| format!(" · music: {music}"))
+                    .unwrap_or_default()
+            ),
+            Vec2::new(48.0, SCENE_SIZE.y - 80.0),
+            36.0,
+            WHITE,
+        );
+    }
+}
+
+/// PNG thumbnail of a game at [`THUMBNAIL_FRAME`]
+pub fn thumbnail(game: &CompiledGame) -> Vec<u8> {
+    let mut runner = HeadlessRunner::new(game);
+    runner.run_to(THUMBNAIL_FRAME);
+    let mut queue = RenderQueue::new();
+    runner.draw(&mut queue);
+    rasterize(
+        queue.commands(),
+        SCENE_SIZE,
+        THUMBNAIL_WIDTH,
+        THUMBNAIL_HEIGHT,
+    )
+    .to_png()
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::float_cmp,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+mod tests {
+    use super::*;
+
+    fn compiled(yaml: &str) -> CompiledGame {
+        jugar_yaml::compile_game(yaml).unwrap()
+    }
+
+    fn circle_centers(runner: &HeadlessRunner<'_>) -> Vec<Vec2> {
+        let mut queue = RenderQueue::new();
+        runner.draw(&mut queue);
+        queue
+            .commands()
+            .iter()
+            .filter_map(|command| match command {
+                RenderCommand::DrawCircle { center, .. } => Some(*center),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_entity_colors_are_stable() {
+        assert_eq!(entity_color("star"), entity_color("star"));
+        assert!(background_color(Some("space"))[2] > background_color(Some("space"))[0]);
+    }
+
+    #[test]
+    fn test_player_entities_stay_put() {
+        let game = compiled("game: stars\ncharacter: bunny\nmove: arrows\n");
+        let mut runner = HeadlessRunner::new(&game);
+        let before = circle_centers(&runner);
+        runner.run_to(THUMBNAIL_FRAME);
+        assert_eq!(circle_centers(&runner), before);
+    }
+
+    #[test]
+    fn test_patterns_move_over_time() {
+        assert_eq!(motion_offset(Some("patrol"), 0.0), Vec2::ZERO);
+        assert!(motion_offset(Some("patrol"), 1.0).x > 0.0);
+        assert!(motion_offset(Some("bounce"), 0.5).y < 0.0);
+        assert_eq!(motion_offset(None, 3.0), Vec2::ZERO);
+    }
+
+    #[test]
+    fn test_thumbnail_is_png() {
+        let png = thumbnail(&compiled(
+            "game: stars\ncharacter: bunny\nbackground: space\n",
+        ));
+        assert_eq!(&png[1..4], b"PNG");
+        assert_eq!(&png[16..20], &THUMBNAIL_WIDTH.to_be_bytes());
+        assert_eq!(&png[20..24], &THUMBNAIL_HEIGHT.to_be_bytes());
+    }
+
+    fn texts(runner: &HeadlessRunner<'_>) -> Vec<String> {
+        let mut queue = RenderQueue::new();
+        runner.draw(&mut queue);
+        queue
+            .commands()
+            .iter()
+            .filter_map(|command| match command {
+                RenderCommand::DrawText { text, .. } => Some(text.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_new_templates_compile_and_run() {
+        let catalog = jugar_yaml::TemplateCatalog::with_defaults();
+        for id in ["maze-explorer", "simple-platformer", "quiz"] {
+            let template = catalog.get(id).unwrap();
+            for stage in &template.stages {
+                let game = compiled(&stage.yaml);
+                let mut runner = HeadlessRunner::new(&game);
+                runner.run_to(THUMBNAIL_FRAME);
+                assert_eq!(
+                    circle_centers(&runner).len(),
+                    game.entities.len(),
+                    "{id}: {}",
+                    stage.title
+                );
+                assert_eq!(&thumbnail(&game)[1..4], b"PNG");
+            }
+        }
+    }
+
+    #[test]
+    fn test_quiz_cycles_questions() {
+        let game = compiled(
+            &jugar_yaml::TemplateCatalog::with_defaults()
+                .get("quiz")
+                .unwrap()
+                .yaml,
+        );
+        let mut runner = HeadlessRunner::new(&game);
+        let first = texts(&runner);
+        assert!(first.iter().any(|text| text == "What color is the sky?"));
+        assert!(["blue", "green", "purple"]
+            .iter()
+            .all(|choice| first.iter().any(|text| text == choice)));
+
+        runner.run_to((QUESTION_SECONDS * FRAME_RATE) as u64);
+        assert!(texts(&runner)
+            .iter()
+            .any(|text| text == "Which animal says moo?"));
+    }
+}
@@ -0,0 +1,292 @@
+//! `jugar serve`: live preview of a YAML game in the browser.
+//!
+//! The game file is polled for changes and recompiled through
+//! [`LivePreview`]. Each result is drawn into a jugar-web [`RenderFrame`]
+//! and pushed over a WebSocket to a small page that only executes `Canvas2D`
+//! commands, so nothing needs to be installed besides a browser. A broken
+//! edit keeps the last good game on screen under the kid-friendly error.
+
+use alloc::sync::Arc;
+use core::time::Duration;
+use std::fs;
+use std::io::{BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::thread;
+
+use glam::Vec2;
+use jugar_core::Rect;
+use jugar_render::{RenderCommand, RenderQueue};
+use jugar_web::{convert_render_queue, RenderFrame};
+use jugar_yaml::{LivePreview, PreviewResult};
+use serde::Serialize;
+
+use crate::scene::{HeadlessRunner, SCENE_SIZE, WHITE};
+use crate::{explain, websocket, CliError, Result};
+
+/// How often the game file is checked for edits
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Error lines shown over the preview
+const MAX_ERROR_LINES: usize = 14;
+
+/// The preview page (executes `Canvas2D` commands, nothing else)
+const PAGE: &str = include_str!("preview.html");
+
+/// Message pushed to the browser
+#[derive(Debug, Serialize)]
+struct PreviewMessage<'a> {
+    ok: bool,
+    width: f32,
+    height: f32,
+    commands: &'a RenderFrame,
+}
+
+/// One recompilation pushed to viewers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreviewUpdate {
+    /// True if the game compiled
+    pub ok: bool,
+    /// One-line status for the terminal
+    pub status: String,
+    /// JSON message for the browser
+    pub json: String,
+}
+
+/// Watches one game file and turns edits into preview frames
+#[derive(Debug)]
+pub struct LiveSession {
+    path: PathBuf,
+    preview: LivePreview,
+    yaml: Option<String>,
+}
+
+impl LiveSession {
+    /// Creates a session for a game file
+    #[must_use]
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            preview: LivePreview::new(),
+            yaml: None,
+        }
+    }
+
+    /// Checks the file, returning an update when the preview changed
+    ///
+    /// Rapid saves are debounced by [`LivePreview`]; keep polling and the
+    /// latest text compiles once the file settles.
+    pub fn poll(&mut self) -> Option<PreviewUpdate> {
+        let changed = fs::read_to_string(&self.path)
+            .ok()
+            .filter(|yaml| self.yaml.as_ref() != Some(yaml));
+        let result = if let Some(yaml) = changed {
+            let result = self.preview.on_yaml_change(&yaml);
+            self.yaml = Some(yaml);
+            result
+        } else {
+            self.preview.check_pending(self.yaml.as_deref()?)?
+        };
+        self.update(&result)
+    }
+
+    fn update(&self, result: &PreviewResult) -> Option<PreviewUpdate> {
+        let mut queue = RenderQueue::new();
+        let (ok, status) = match result {
+            PreviewResult::Success { game, compile_time } => {
+                HeadlessRunner::new(game).draw(&mut queue);
+                (
+                    true,
+                    format!("✅ {} updated ({compile_time:.1?})", game.name),
+                )
+            }
+            PreviewResult::Error { errors } => {
+                if let Some(game) = self.preview.last_valid_game() {
+                    HeadlessRunner::new(game).draw(&mut queue);
+                } else {
+                    queue.push(RenderCommand::Clear {
+                        color: [0.1, 0.1, 0.12, 1.0],
+                    });
+                }
+                let yaml = self.yaml.as_deref().unwrap_or_default();
+                let text = errors
+                    .iter()
+                    .map(|error| explain(yaml, error))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                draw_error(&mut queue, &text);
+                (
+                    false,
+                    "❌ The game has a mistake (see the browser)".to_string(),
+                )
+            }
+            PreviewResult::Debounced => return None,
+        };
+        let frame = convert_render_queue(queue.commands());
+        let json = serde_json::to_string(&PreviewMessage {
+            ok,
+            width: SCENE_SIZE.x,
+            height: SCENE_SIZE.y,
+            commands: &frame,
+        })
+        .ok()?;
+        Some(PreviewUpdate { ok, status, json })
+    }
+}
+
+/// Draws the kid-friendly error panel over the scene
+fn draw_error(queue: &mut RenderQueue, text: &str) {
+    let panel = Rect::new(120.0, 160.0, SCENE_SIZE.x - 240.0, SCENE_SIZE.y - 320.0);
+    queue.push(RenderCommand::DrawRect {
+        rect: panel,
+        color: [0.0, 0.0, 0.0, 0.82],
+    });
+    let mut y = panel.y + 32.0;
+    for line in text.lines().take(MAX_ERROR_LINES) {
+        queue.text(line, Vec2::new(panel.x + 40.0, y), 36.0, WHITE);
+        y += 52.0;
+    }
+}
+
+/// Viewers connected over WebSocket, plus the latest frame for newcomers
+#[derive(Debug, Default)]
+struct Hub {
+    clients: Mutex<Vec<TcpStream>>,
+    latest: Mutex<Option<String>>,
+}
+
+impl Hub {
+    fn join(&self, mut stream: TcpStream) {
+        let Ok(mut clients) = self.clients.lock() else {
+            return;
+        };
+        if let Ok(latest) = self.latest.lock() {
+            if let Some(json) = latest.as_deref() {
+                if websocket::send_text(&mut stream, json).is_err() {
+                    return;
+                }
+            }
+        }
+        clients.push(stream);
+    }
+
+    fn broadcast(&self, json: String) {
+        let Ok(mut clients) = self.clients.lock() else {
+            return;
+        };
+        // Viewers that closed the tab drop out on the next write
+        clients.retain_mut(|stream| websocket::send_text(stream, &json).is_ok());
+        if let Ok(mut latest) = self.latest.lock() {
+            *latest = Some(json);
+        }
+    }
+}
+
+fn handle(stream: TcpStream, hub: &Hub) -> std::io::Result<()> {
+    let mut stream = stream;
+    let request = websocket::read_request(&mut BufReader::new(stream.try_clone()?))?;
+    match (request.path.as_str(), request.websocket_key()) {
+        ("/live", Some(key)) => {
+            websocket::accept(&mut stream, key)?;
+            hub.join(stream);
+            Ok(())
+        }
+        ("/" | "/index.html", _) => websocket::respond(
+            &mut stream,
+            "200 OK",
+            "text/html; charset=utf-8",
+            PAGE.as_bytes(),
+        ),
+        _ => websocket::respond(&mut stream, "404 Not Found", "text/plain", b"not found"),
+    }
+}
+
+/// Serves the live preview until the process is stopped
+///
+/// # Errors
+///
+/// Returns an error if the game file or the port can't be opened.
+pub fn serve(path: &Path, port: u16, out: &mut dyn Write) -> Result<()> {
+    if !path.is_file() {
+        return Err(CliError::Io {
+            path: path.to_path_buf(),
+            source: std::io::ErrorKind::NotFound.into(),
+        });
+    }
+    let listener = TcpListener::bind(("127.0.0.1", port)).map_err(|source| CliError::Io {
+        path: PathBuf::from(format!("127.0.0.1:{port}")),
+        source,
+    })?;
+    crate::write(
+        out,
+        &format!(
+            "🌐 Live preview at http://127.0.0.1:{port}/\n   Edit {} and save to see changes. Ctrl+C to stop.\n",
+            path.display()
+        ),
+    )?;
+
+    let hub = Arc::new(Hub::default());
+    let viewers = Arc::clone(&hub);
+    let _ = thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let hub = Arc::clone(&viewers);
+            let _ = thread::spawn(move || handle(stream, &hub));
+        }
+    });
+
+    let mut session = LiveSession::new(path);
+    loop {
+        if let Some(update) = session.poll() {
+            crate::write(out, &format!("{}\n", update.status))?;
+            hub.broadcast(update.json);
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn scratch_file(name: &str, yaml: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("jugar-serve-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("game.yaml");
+        fs::write(&path, yaml).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_first_poll_compiles_and_draws() {
+        let path = scratch_file(
+            "first",
+            "game: stars\ncharacter: bunny\nbackground: space\n",
+        );
+        let mut session = LiveSession::new(&path);
+        let update = session.poll().unwrap();
+        assert!(update.ok);
+        assert!(update.json.contains("FillCircle"));
+        assert!(update.json.contains("bunny"));
+        // Nothing changed, nothing to push
+        assert!(session.poll().is_none());
+    }
+
+    #[test]
+    fn test_error_keeps_last_good_game() {
+        let path = scratch_file("error", "game: stars\ncharacter: bunny\n");
+        let mut session = LiveSession::new(&path);
+        assert!(session.poll().unwrap().ok);
+
+        fs::write(&path, "character: dinosaur\n").unwrap();
+        let update = session.preview.compile_now("character: dinosaur\n");
+        session.yaml = Some("character: dinosaur\n".to_string());
+        let update = session.update(&update).unwrap();
+        assert!(!update.ok);
+        // Scene of the last good game is still drawn under the error
+        assert!(update.json.contains("bunny"));
+        assert!(update.json.contains("FillRect"));
+    }
+}
@@ -0,0 +1,253 @@
+//! Minimal HTTP/1.1 and WebSocket (RFC 6455) server pieces.
+//!
+//! Only what the live preview needs: parsing a request head, the opening
+//! handshake and unmasked server-to-client text frames. Messages from the
+//! browser are never read.
+
+use std::io::{self, BufRead, Write};
+
+use base64::Engine;
+
+/// GUID appended to the client key during the handshake (RFC 6455 §1.3)
+const WEBSOCKET_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+/// Request heads larger than this are rejected
+const MAX_HEAD_BYTES: usize = 8 * 1024;
+
+/// Parsed HTTP request head
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+    /// Method (`GET`)
+    pub method: String,
+    /// Request path (`/live`)
+    pub path: String,
+    headers: Vec<(String, String)>,
+}
+
+impl Request {
+    /// Parses a request head (request line plus headers)
+    #[must_use]
+    pub fn parse(head: &str) -> Option<Self> {
+        let mut lines = head.lines();
+        let mut request_line = lines.next()?.split_whitespace();
+        let method = request_line.next()?.to_string();
+        let path = request_line.next()?.to_string();
+        let headers = lines
+            .filter_map(|line| line.split_once(':'))
+            .map(|(name, value)| (name.trim().to_ascii_lowercase(), value.trim().to_string()))
+            .collect();
+        Some(Self {
+            method,
+            path,
+            headers,
+        })
+    }
+
+    /// Header value by case-insensitive name
+    #[must_use]
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Client key if this is a WebSocket upgrade request
+    #[must_use]
+    pub fn websocket_key(&self) -> Option<&str> {
+        self.header("upgrade")
+            .filter(|upgrade| upgrade.eq_ignore_ascii_case("websocket"))
+            .and_then(|_| self.header("sec-websocket-key"))
+    }
+}
+
+/// Reads a request head up to the blank line
+///
+/// # Errors
+///
+/// Returns an error if the stream fails, closes early or the head is
+/// malformed or too large.
+pub fn read_request(reader: &mut impl BufRead) -> io::Result<Request> {
+    let mut head = String::new();
+    loop {
+        let read = reader.read_line(&mut head)?;
+        if read == 0 || head.len() > MAX_HEAD_BYTES {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "incomplete request",
+            ));
+        }
+        if head.ends_with("\r\n\r\n") || head.ends_with("\n\n") {
+            break;
+        }
+    }
+    Request::parse(&head)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "malformed request"))
+}
+
+/// Writes a complete HTTP response
+///
+/// # Errors
+///
+/// Returns an error if the stream fails.
+pub fn respond(
+    stream: &mut impl Write,
+    status: &str,
+    content_type: &str,
+    body: &[u8],
+) -> io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\n\
+         Cache-Control: no-store\r\nConnection: close\r\n\r\n",
+        body.len()
+    )?;
+    stream.write_all(body)?;
+    stream.flush()
+}
+
+/// `Sec-WebSocket-Accept` value for a client key
+#[must_use]
+pub fn accept_key(key: &str) -> String {
+    let digest = sha1(format!("{key}{WEBSOCKET_GUID}").as_bytes());
+    base64::engine::general_purpose::STANDARD.encode(digest)
+}
+
+/// Completes the opening handshake
+///
+/// # Errors
+///
+/// Returns an error if the stream fails.
+pub fn accept(stream: &mut impl Write, key: &str) -> io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\n\
+         Sec-WebSocket-Accept: {}\r\n\r\n",
+        accept_key(key)
+    )?;
+    stream.flush()
+}
+
+/// Encodes a single unmasked text frame
+#[must_use]
+pub fn text_frame(text: &str) -> Vec<u8> {
+    let payload = text.as_bytes();
+    let mut frame = Vec::with_capacity(payload.len() + 10);
+    // FIN + text opcode
+    frame.push(0x81);
+    if let Some(len) = u8::try_from(payload.len()).ok().filter(|len| *len <= 125) {
+        frame.push(len);
+    } else if let Ok(len) = u16::try_from(payload.len()) {
+        frame.push(126);
+        frame.extend_from_slice(&len.to_be_bytes());
+    } else {
+        frame.push(127);
+        frame.extend_from_slice(&(payload.len() as u64).to_be_bytes());
+    }
+    frame.extend_from_slice(payload);
+    frame
+}
+
+/// Sends a text message
+///
+/// # Errors
+///
+/// Returns an error if the client has gone away.
+pub fn send_text(stream: &mut impl Write, text: &str) -> io::Result<()> {
+    stream.write_all(&text_frame(text))?;
+    stream.flush()
+}
+
+/// SHA-1 digest, needed only for the handshake
+fn sha1(data: &[u8]) -> [u8; 20] {
+    let mut state: [u32; 5] = [
+        0x6745_2301,
+        0xEFCD_AB89,
+        0x98BA_DCFE,
+        0x1032_5476,
+        0xC3D2_E1F0,
+    ];
+    let mut message = data.to_vec();
+    let bit_len = (data.len() as u64).wrapping_mul(8);
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend_from_slice(&bit_len.to_be_bytes());
+
+    for block in message.chunks_exact(64) {
+        let mut schedule = [0_u32; 80];
+        for (word, bytes) in schedule.iter_mut().zip(block.chunks_exact(4)) {
+            *word = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        }
+        for i in 16..80 {
+            schedule[i] = (schedule[i - 3] ^ schedule[i - 8] ^ schedule[i - 14] ^ schedule[i - 16])
+                .rotate_left(1);
+        }
+
+        let mut working = state;
+        for (round, word) in schedule.iter().enumerate() {
+            let [v0, v1, v2, v3, v4] = working;
+            let (mix, constant) = match round {
+                0..=19 => ((v1 & v2) | (!v1 & v3), 0x5A82_7999),
+                20..=39 => (v1 ^ v2 ^ v3, 0x6ED9_EBA1),
+                40..=59 => ((v1 & v2) | (v1 & v3) | (v2 & v3), 0x8F1B_BCDC),
+                _ => (v1 ^ v2 ^ v3, 0xCA62_C1D6),
+            };
+            let next = v0
+                .rotate_left(5)
+                .wrapping_add(mix)
+                .wrapping_add(v4)
+                .wrapping_add(constant)
+                .wrapping_add(*word);
+            working = [next, v0, v1.rotate_left(30), v2, v3];
+        }
+        for (slot, value) in state.iter_mut().zip(working) {
+            *slot = slot.wrapping_add(value);
+        }
+    }
+
+    let mut digest = [0_u8; 20];
+    for (bytes, value) in digest.chunks_exact_mut(4).zip(state) {
+        bytes.copy_from_slice(&value.to_be_bytes());
+    }
+    digest
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_accept_key_matches_rfc_example() {
+        assert_eq!(
+            accept_key("dGhlIHNhbXBsZSBub25jZQ=="),
+            "s3pPLMBiTxaQ9kYGzzhZRbK+xOo="
+        );
+    }
+
+    #[test]
+    fn test_parse_upgrade_request() {
+        let head = "GET /live HTTP/1.1\r\nHost: localhost\r\nUpgrade: WebSocket\r\n\
+                    Connection: Upgrade\r\nSec-WebSocket-Key: abc==\r\n\r\n";
+        let request = read_request(&mut head.as_bytes()).unwrap();
+        assert_eq!(request.method, "GET");
+        assert_eq!(request.path, "/live");
+        assert_eq!(request.websocket_key(), Some("abc=="));
+
+        let page = Request::parse("GET / HTTP/1.1\r\nHost: x\r\n\r\n").unwrap();
+        assert_eq!(page.websocket_key(), None);
+        assert!(read_request(&mut &b"GET / HTTP/1.1\r\n"[..]).is_err());
+    }
+
+    #[test]
+    fn test_frame_length_encoding() {
+        assert_eq!(text_frame("hi"), vec![0x81, 2, b'h', b'i']);
+        let medium = text_frame(&"x".repeat(300));
+        assert_eq!(&medium[..4], &[0x81, 126, 1, 44]);
+        let large = text_frame(&"x".repeat(70_000));
+        assert_eq!(large[1], 127);
+        assert_eq!(large.len(), 70_000 + 10);
+    }
+}
@@ -3,7 +3,7 @@
 #![allow(missing_docs, unused_results)]
 
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
-use jugar_core::{GameLoop, GameLoopConfig, Position, Velocity, World};
+use jugar_core::{GameLoop, GameLoopConfig, Position, TransformSoa, Velocity, World};
 
 fn bench_game_loop_update(c: &mut Criterion) {
     let mut group = c.benchmark_group("game_loop");
@@ -69,11 +69,36 @@ fn bench_ecs_iteration(c: &mut Criterion) {
     group.finish();
 }
 
+fn bench_soa_integrate(c: &mut Criterion) {
+    let mut group = c.benchmark_group("soa_integrate");
+
+    for count in &[100, 1000, 10000] {
+        let mut world = World::new();
+        for i in 0..*count {
+            let entity = world.spawn();
+            world.add_component(entity, Position::new(i as f32, i as f32));
+            world.add_component(entity, Velocity::new(1.0, 1.0));
+        }
+        let mut soa = TransformSoa::with_capacity(*count);
+
+        group.bench_with_input(BenchmarkId::new("entities", count), count, |b, _| {
+            b.iter(|| {
+                soa.gather(&world);
+                soa.integrate(black_box(1.0 / 60.0));
+                soa.scatter(&mut world);
+            });
+        });
+    }
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
     bench_game_loop_update,
     bench_ecs_spawn,
     bench_ecs_component_add,
-    bench_ecs_iteration
+    bench_ecs_iteration,
+    bench_soa_integrate
 );
 criterion_main!(benches);
@@ -0,0 +1,53 @@
+//! `perf_check` Example - Fail CI on Benchmark Regressions
+//!
+//! Compares criterion's latest run against a saved baseline and exits
+//! with status 1 if any benchmark slowed down past its threshold.
+//!
+//! # Running
+//!
+//! ```bash
+//! cargo bench --workspace -- --save-baseline main   # on main
+//! cargo bench --workspace                           # on the change
+//! cargo run --example perf_check -p jugar-core -- target/criterion main 10
+//! ```
+//!
+//! Arguments are the criterion directory, the baseline name and the
+//! allowed slowdown in percent (default 10).
+
+#![allow(clippy::uninlined_format_args)]
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use jugar_core::{perf_report, PerfThresholds, DEFAULT_THRESHOLD_PERCENT};
+
+fn main() -> ExitCode {
+    let mut args = std::env::args().skip(1);
+    let dir = PathBuf::from(
+        args.next()
+            .unwrap_or_else(|| "target/criterion".to_string()),
+    );
+    let baseline = args.next().unwrap_or_else(|| "main".to_string());
+    let percent = args
+        .next()
+        .and_then(|arg| arg.parse().ok())
+        .unwrap_or(DEFAULT_THRESHOLD_PERCENT);
+
+    // Whole-game YAML compiles allocate heavily and vary more between runs
+    let thresholds = PerfThresholds::new(percent).with("yaml_compile", percent * 1.5);
+
+    match perf_report(&dir, &baseline, &thresholds) {
+        Ok(report) => {
+            println!("{}", report);
+            if report.is_ok() {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            }
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::from(2)
+        }
+    }
+}
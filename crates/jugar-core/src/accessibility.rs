@@ -0,0 +1,199 @@
+//! Player accessibility preferences.
+//!
+//! [`AccessibilitySettings`] is a single engine-wide resource that rendering,
+//! effects and UI code query instead of keeping their own flags: high
+//! contrast swaps palettes for stronger ones, large text raises the default
+//! font scale, and reduced motion turns off screen shake, particles and
+//! parallax, and motor assist asks input filters to ignore brief presses and
+//! slow key repeat. Browsers expose the same choices as `prefers-contrast` and
+//! `prefers-reduced-motion`, so the host can seed them on startup.
+//! [`ColorVision`] picks a colorblind-safe palette that the renderer swaps
+//! in for every game, without per-game support.
+
+use serde::{Deserialize, Serialize};
+
+/// Font scale with large text off
+pub const DEFAULT_FONT_SCALE: f32 = 1.0;
+
+/// Font scale with large text on
+pub const LARGE_TEXT_FONT_SCALE: f32 = 1.5;
+
+/// Type of color vision to draw for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ColorVision {
+    /// No palette changes
+    #[default]
+    Typical,
+    /// Green-weak (most common)
+    Deuteranopia,
+    /// Red-weak
+    Protanopia,
+    /// Blue-yellow confusion
+    Tritanopia,
+}
+
+impl ColorVision {
+    /// Every kind, typical first
+    pub const ALL: [Self; 4] = [
+        Self::Typical,
+        Self::Deuteranopia,
+        Self::Protanopia,
+        Self::Tritanopia,
+    ];
+
+    /// Returns true if colors should be swapped for a safe palette
+    #[must_use]
+    pub const fn needs_safe_palette(self) -> bool {
+        !matches!(self, Self::Typical)
+    }
+}
+
+/// Engine-wide accessibility modes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[allow(clippy::struct_excessive_bools)] // Each is a separate player choice
+pub struct AccessibilitySettings {
+    /// Use high-contrast palettes
+    #[serde(default)]
+    pub high_contrast: bool,
+    /// Draw text at [`LARGE_TEXT_FONT_SCALE`]
+    #[serde(default)]
+    pub large_text: bool,
+    /// Disable screen shake, particles and parallax
+    #[serde(default)]
+    pub reduced_motion: bool,
+    /// Colorblind-safe palette to use
+    #[serde(default)]
+    pub color_vision: ColorVision,
+    /// Ignore brief presses and slow key repeat for unsteady hands
+    #[serde(default)]
+    pub motor_assist: bool,
+}
+
+impl AccessibilitySettings {
+    /// Creates settings with every mode off
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            high_contrast: false,
+            large_text: false,
+            reduced_motion: false,
+            color_vision: ColorVision::Typical,
+            motor_assist: false,
+        }
+    }
+
+    /// Turns high contrast on or off
+    #[must_use]
+    pub const fn with_high_contrast(mut self, enabled: bool) -> Self {
+        self.high_contrast = enabled;
+        self
+    }
+
+    /// Turns large text on or off
+    #[must_use]
+    pub const fn with_large_text(mut self, enabled: bool) -> Self {
+        self.large_text = enabled;
+        self
+    }
+
+    /// Turns reduced motion on or off
+    #[must_use]
+    pub const fn with_reduced_motion(mut self, enabled: bool) -> Self {
+        self.reduced_motion = enabled;
+        self
+    }
+
+    /// Sets the color vision to draw for
+    #[must_use]
+    pub const fn with_color_vision(mut self, vision: ColorVision) -> Self {
+        self.color_vision = vision;
+        self
+    }
+
+    /// Turns motor assist on or off
+    #[must_use]
+    pub const fn with_motor_assist(mut self, enabled: bool) -> Self {
+        self.motor_assist = enabled;
+        self
+    }
+
+    /// Multiplier for default font sizes
+    #[must_use]
+    pub const fn font_scale(&self) -> f32 {
+        if self.large_text {
+            LARGE_TEXT_FONT_SCALE
+        } else {
+            DEFAULT_FONT_SCALE
+        }
+    }
+
+    /// Scales a font size by [`Self::font_scale`]
+    #[must_use]
+    pub fn scaled_font_size(&self, size: f32) -> f32 {
+        size * self.font_scale()
+    }
+
+    /// Returns true if the camera may shake
+    #[must_use]
+    pub const fn allows_screen_shake(&self) -> bool {
+        !self.reduced_motion
+    }
+
+    /// Returns true if particle bursts may be spawned
+    #[must_use]
+    pub const fn allows_particles(&self) -> bool {
+        !self.reduced_motion
+    }
+
+    /// Returns true if background layers may scroll at different speeds
+    #[must_use]
+    pub const fn allows_parallax(&self) -> bool {
+        !self.reduced_motion
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults_allow_everything() {
+        let settings = AccessibilitySettings::default();
+        assert_eq!(settings, AccessibilitySettings::new());
+        assert!(settings.allows_screen_shake());
+        assert!(settings.allows_particles());
+        assert!(settings.allows_parallax());
+        assert!((settings.scaled_font_size(16.0) - 16.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_modes_are_independent() {
+        let settings = AccessibilitySettings::new()
+            .with_reduced_motion(true)
+            .with_large_text(true);
+        assert!(!settings.high_contrast);
+        assert!(!settings.motor_assist);
+        assert!(!settings.allows_screen_shake());
+        assert!(!settings.allows_particles());
+        assert!(!settings.allows_parallax());
+        assert!((settings.scaled_font_size(16.0) - 24.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_missing_fields_deserialize_off() {
+        let settings: AccessibilitySettings =
+            serde_json::from_str(r#"{"high_contrast":true}"#).unwrap();
+        assert_eq!(
+            settings,
+            AccessibilitySettings::new().with_high_contrast(true)
+        );
+
+        let settings: AccessibilitySettings =
+            serde_json::from_str(r#"{"color_vision":"tritanopia"}"#).unwrap();
+        assert_eq!(settings.color_vision, ColorVision::Tritanopia);
+        assert!(settings.color_vision.needs_safe_palette());
+        assert!(!ColorVision::default().needs_safe_palette());
+    }
+}
@@ -0,0 +1,348 @@
+//! Achievements and badges.
+//!
+//! An [`Achievements`] registry holds a game's badges ("First Star",
+//! "Tutorial Graduate"). Each is unlocked by a call to
+//! [`Achievements::unlock`] or automatically once a named counter reaches
+//! its target. Unlocks are announced on the event bus as
+//! [`AchievementUnlocked`] so the UI can pop a toast, and progress is saved
+//! through [`Storage`] so badges survive a reload.
+
+use alloc::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Events, Result, Storage};
+
+/// Prefix of every achievement storage key
+pub const ACHIEVEMENTS_KEY_PREFIX: &str = "jugar.achievements.";
+
+/// Icon used when an achievement doesn't name one
+pub const DEFAULT_ACHIEVEMENT_ICON: &str = "star";
+
+/// How an achievement is earned
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum UnlockCondition {
+    /// Only by calling [`Achievements::unlock`]
+    #[default]
+    Manual,
+    /// When a counter reaches a target ("collect 100 stars")
+    Counter {
+        /// Counter name
+        counter: String,
+        /// Value that unlocks the achievement
+        target: u32,
+    },
+}
+
+/// A badge a player can earn
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Achievement {
+    /// Stable identifier, used for saving
+    pub id: String,
+    /// Kid-friendly name ("Star Catcher")
+    pub name: String,
+    /// What to do to earn it
+    pub description: String,
+    /// Icon asset name
+    pub icon: String,
+    /// How it is earned
+    pub condition: UnlockCondition,
+}
+
+impl Achievement {
+    /// Creates a manually unlocked achievement
+    #[must_use]
+    pub fn new(id: impl Into<String>, name: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+            description: String::new(),
+            icon: DEFAULT_ACHIEVEMENT_ICON.to_string(),
+            condition: UnlockCondition::Manual,
+        }
+    }
+
+    /// Sets the description
+    #[must_use]
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    /// Sets the icon
+    #[must_use]
+    pub fn with_icon(mut self, icon: impl Into<String>) -> Self {
+        self.icon = icon.into();
+        self
+    }
+
+    /// Unlocks when a counter reaches a target
+    #[must_use]
+    pub fn when_counter(mut self, counter: impl Into<String>, target: u32) -> Self {
+        self.condition = UnlockCondition::Counter {
+            counter: counter.into(),
+            target,
+        };
+        self
+    }
+}
+
+/// Sent when a player earns an achievement
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AchievementUnlocked {
+    /// Achievement id
+    pub id: String,
+    /// Kid-friendly name
+    pub name: String,
+    /// Icon asset name
+    pub icon: String,
+}
+
+/// A game's achievements and the player's progress toward them
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Achievements {
+    game: String,
+    registered: Vec<Achievement>,
+    unlocked: BTreeSet<String>,
+    counters: BTreeMap<String, u32>,
+}
+
+impl Achievements {
+    /// Creates an empty registry for a game
+    #[must_use]
+    pub fn new(game: impl Into<String>) -> Self {
+        Self {
+            game: game.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Adds an achievement, replacing one with the same id
+    pub fn register(&mut self, achievement: Achievement) {
+        if let Some(existing) = self
+            .registered
+            .iter_mut()
+            .find(|existing| existing.id == achievement.id)
+        {
+            *existing = achievement;
+        } else {
+            self.registered.push(achievement);
+        }
+    }
+
+    /// Adds an achievement (builder form)
+    #[must_use]
+    pub fn with(mut self, achievement: Achievement) -> Self {
+        self.register(achievement);
+        self
+    }
+
+    /// Looks up an achievement
+    #[must_use]
+    pub fn get(&self, id: &str) -> Option<&Achievement> {
+        self.registered
+            .iter()
+            .find(|achievement| achievement.id == id)
+    }
+
+    /// Registered achievements, in registration order
+    pub fn iter(&self) -> impl Iterator<Item = &Achievement> {
+        self.registered.iter()
+    }
+
+    /// Number of registered achievements
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.registered.len()
+    }
+
+    /// Returns true if nothing is registered
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.registered.is_empty()
+    }
+
+    /// Returns true if the player has earned an achievement
+    #[must_use]
+    pub fn is_unlocked(&self, id: &str) -> bool {
+        self.unlocked.contains(id)
+    }
+
+    /// Number of earned achievements
+    #[must_use]
+    pub fn unlocked_count(&self) -> usize {
+        self.registered
+            .iter()
+            .filter(|achievement| self.is_unlocked(&achievement.id))
+            .count()
+    }
+
+    /// Unlocks an achievement; returns true if it was newly earned
+    ///
+    /// Unknown ids are ignored.
+    pub fn unlock(&mut self, id: &str, events: &mut Events<AchievementUnlocked>) -> bool {
+        let Some(achievement) = self.get(id) else {
+            return false;
+        };
+        let unlocked = AchievementUnlocked {
+            id: achievement.id.clone(),
+            name: achievement.name.clone(),
+            icon: achievement.icon.clone(),
+        };
+        if !self.unlocked.insert(unlocked.id.clone()) {
+            return false;
+        }
+        events.send(unlocked);
+        true
+    }
+
+    /// Current value of a counter
+    #[must_use]
+    pub fn counter(&self, counter: &str) -> u32 {
+        self.counters.get(counter).copied().unwrap_or(0)
+    }
+
+    /// Adds to a counter, unlocking any achievement whose target it reaches
+    pub fn increment(
+        &mut self,
+        counter: &str,
+        amount: u32,
+        events: &mut Events<AchievementUnlocked>,
+    ) {
+        let value = self.counters.entry(counter.to_string()).or_insert(0);
+        *value = value.saturating_add(amount);
+        let value = *value;
+
+        let reached: Vec<String> = self
+            .registered
+            .iter()
+            .filter(|achievement| {
+                matches!(
+                    &achievement.condition,
+                    UnlockCondition::Counter { counter: name, target }
+                        if name == counter && value >= *target
+                )
+            })
+            .map(|achievement| achievement.id.clone())
+            .collect();
+        for id in reached {
+            let _ = self.unlock(&id, events);
+        }
+    }
+
+    /// Storage key of this game's progress
+    #[must_use]
+    pub fn storage_key(&self) -> String {
+        format!("{ACHIEVEMENTS_KEY_PREFIX}{}", self.game)
+    }
+
+    /// Restores saved progress, without announcing anything
+    ///
+    /// Unreadable lines are skipped, so a damaged save loses at most the
+    /// badges on those lines.
+    pub fn load(&mut self, storage: &dyn Storage) {
+        let Some(saved) = storage.get(&self.storage_key()) else {
+            return;
+        };
+        for line in saved.lines() {
+            if let Some(id) = line.strip_prefix("unlocked:") {
+                let _ = self.unlocked.insert(id.to_string());
+            } else if let Some((name, value)) = line
+                .strip_prefix("counter:")
+                .and_then(|rest| rest.rsplit_once('='))
+            {
+                if let Ok(value) = value.parse() {
+                    let _ = self.counters.insert(name.to_string(), value);
+                }
+            }
+        }
+    }
+
+    /// Saves progress, one `unlocked:<id>` or `counter:<name>=<value>` per line
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CoreError::StorageFull`](crate::CoreError::StorageFull) if
+    /// the backend's quota would be exceeded.
+    pub fn save(&self, storage: &mut dyn Storage) -> Result<()> {
+        let lines: Vec<String> = self
+            .unlocked
+            .iter()
+            .map(|id| format!("unlocked:{id}"))
+            .chain(
+                self.counters
+                    .iter()
+                    .map(|(name, value)| format!("counter:{name}={value}")),
+            )
+            .collect();
+        storage.set(&self.storage_key(), &lines.join("\n"))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::MemoryStorage;
+
+    fn registry() -> Achievements {
+        Achievements::new("bunny")
+            .with(Achievement::new("first_hop", "First Hop").with_icon("bunny"))
+            .with(
+                Achievement::new("star_catcher", "Star Catcher")
+                    .with_description("Catch 10 stars")
+                    .when_counter("stars", 10),
+            )
+    }
+
+    #[test]
+    fn test_manual_unlock_announces_once() {
+        let mut events = Events::new();
+        let mut achievements = registry();
+        assert!(achievements.unlock("first_hop", &mut events));
+        assert!(!achievements.unlock("first_hop", &mut events));
+        assert!(!achievements.unlock("unknown", &mut events));
+
+        let sent: Vec<_> = events.drain().collect();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].name, "First Hop");
+        assert_eq!(sent[0].icon, "bunny");
+        assert_eq!(achievements.unlocked_count(), 1);
+    }
+
+    #[test]
+    fn test_counter_unlocks_at_target() {
+        let mut events = Events::new();
+        let mut achievements = registry();
+        achievements.increment("stars", 9, &mut events);
+        assert!(!achievements.is_unlocked("star_catcher"));
+        achievements.increment("stars", 1, &mut events);
+        achievements.increment("stars", 1, &mut events);
+        assert!(achievements.is_unlocked("star_catcher"));
+        assert_eq!(events.len(), 1);
+        assert_eq!(achievements.counter("stars"), 11);
+    }
+
+    #[test]
+    fn test_progress_survives_save_and_load() {
+        let mut events = Events::new();
+        let mut storage = MemoryStorage::new();
+        let mut achievements = registry();
+        let _ = achievements.unlock("first_hop", &mut events);
+        achievements.increment("stars", 4, &mut events);
+        achievements.save(&mut storage).unwrap();
+
+        let mut restored = registry();
+        restored.load(&storage);
+        assert!(restored.is_unlocked("first_hop"));
+        assert_eq!(restored.counter("stars"), 4);
+        assert_eq!(restored, achievements);
+
+        storage
+            .set("jugar.achievements.bunny", "garbage\ncounter:stars=x")
+            .unwrap();
+        let mut damaged = registry();
+        damaged.load(&storage);
+        assert_eq!(damaged.unlocked_count(), 0);
+    }
+}
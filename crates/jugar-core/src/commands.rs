@@ -0,0 +1,182 @@
+//! Deferred world changes (command buffer)
+//!
+//! Queries borrow the world, so a system can't spawn or despawn while it
+//! iterates. It records the changes in [`Commands`] instead and applies them
+//! at a sync point, typically the end of the system or the frame:
+//!
+//! ```
+//! use jugar_core::{Commands, Position, World};
+//!
+//! let mut world = World::new();
+//! let spark = world.spawn();
+//! world.add_component(spark, Position::new(0.0, 700.0));
+//!
+//! let mut commands = Commands::new();
+//! for (entity, position) in world.query::<Position>() {
+//!     if position.y > 600.0 {
+//!         commands.despawn(entity);
+//!         let ember = commands.spawn(&world);
+//!         commands.insert(ember, Position::new(position.x, 600.0));
+//!     }
+//! }
+//! commands.apply(&mut world);
+//!
+//! assert!(!world.contains(spark));
+//! assert_eq!(world.entity_count(), 1);
+//! ```
+
+use core::any::Any;
+use core::fmt;
+
+use crate::{Entity, World};
+
+/// One recorded change
+type Command = Box<dyn FnOnce(&mut World) + Send + Sync>;
+
+/// Spawns, despawns and component changes waiting for a sync point
+///
+/// Commands apply in the order they were recorded. Changes aimed at an
+/// entity that is gone by then are skipped, so two systems despawning the
+/// same entity, or inserting into one that was despawned, is harmless.
+#[derive(Default)]
+pub struct Commands {
+    queue: Vec<Command>,
+}
+
+impl Commands {
+    /// Creates an empty buffer
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queues a new entity and returns its handle
+    ///
+    /// The handle is reserved right away, so components can be queued for
+    /// it before it exists.
+    pub fn spawn(&mut self, world: &World) -> Entity {
+        let entity = world.reserve_entity();
+        self.queue
+            .push(Box::new(move |world| world.spawn_reserved(entity)));
+        entity
+    }
+
+    /// Queues removing an entity and all its components
+    pub fn despawn(&mut self, entity: Entity) {
+        self.queue.push(Box::new(move |world| {
+            let _ = world.despawn(entity);
+        }));
+    }
+
+    /// Queues adding (or replacing) a component
+    pub fn insert<T: Any + Send + Sync>(&mut self, entity: Entity, component: T) {
+        self.queue.push(Box::new(move |world| {
+            if world.contains(entity) {
+                world.add_component(entity, component);
+            }
+        }));
+    }
+
+    /// Queues removing a component
+    pub fn remove<T: Any>(&mut self, entity: Entity) {
+        self.queue.push(Box::new(move |world| {
+            let _ = world.remove_component::<T>(entity);
+        }));
+    }
+
+    /// Number of queued commands
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Returns true if nothing is queued
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// Applies every queued command in order, leaving the buffer empty for
+    /// reuse
+    pub fn apply(&mut self, world: &mut World) {
+        for command in self.queue.drain(..) {
+            command(world);
+        }
+    }
+}
+
+impl fmt::Debug for Commands {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Commands")
+            .field("queued", &self.queue.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::{Position, Velocity};
+
+    #[test]
+    fn test_spawn_and_insert_apply_at_sync_point() {
+        let mut world = World::new();
+        let existing = world.spawn();
+        let mut commands = Commands::new();
+
+        let spawned = commands.spawn(&world);
+        commands.insert(spawned, Position::new(1.0, 2.0));
+        commands.insert(existing, Velocity::new(3.0, 0.0));
+        assert_ne!(spawned, existing);
+        assert!(!world.contains(spawned));
+        assert_eq!(commands.len(), 3);
+
+        commands.apply(&mut world);
+        assert!(commands.is_empty());
+        assert!(world.contains(spawned));
+        let position = world.get_component::<Position>(spawned).unwrap();
+        assert!((position.x - 1.0).abs() < f32::EPSILON);
+        assert!(world.has_component::<Velocity>(existing));
+
+        // Handles keep counting up past reserved ones
+        let next = world.spawn();
+        assert!(next.id() > spawned.id());
+    }
+
+    #[test]
+    fn test_despawn_during_query() {
+        let mut world = World::new();
+        for x in 0..4_u8 {
+            let e = world.spawn();
+            world.add_component(e, Position::new(f32::from(x), 0.0));
+        }
+        let mut commands = Commands::new();
+        for (entity, position) in world.query::<Position>() {
+            if position.x >= 2.0 {
+                commands.despawn(entity);
+                // Queued twice, as two systems might
+                commands.despawn(entity);
+            }
+        }
+        commands.apply(&mut world);
+        assert_eq!(world.entity_count(), 2);
+        assert!(world.query::<Position>().all(|(_, p)| p.x < 2.0));
+    }
+
+    #[test]
+    fn test_changes_to_despawned_entities_are_skipped() {
+        let mut world = World::new();
+        let e = world.spawn();
+        world.add_component(e, Position::new(0.0, 0.0));
+        let mut commands = Commands::new();
+        commands.remove::<Position>(e);
+        commands.despawn(e);
+        commands.insert(e, Velocity::new(1.0, 1.0));
+        commands.apply(&mut world);
+
+        assert!(!world.contains(e));
+        assert!(!world.has_component::<Velocity>(e));
+        assert_eq!(world.query::<Velocity>().count(), 0);
+    }
+}
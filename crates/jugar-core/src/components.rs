@@ -3,6 +3,7 @@
 //! These components support both mobile (6") and ultrawide (49") displays
 //! through responsive anchoring and scaling systems.
 
+use alloc::collections::BTreeMap;
 use core::fmt;
 
 use glam::Vec2;
@@ -85,6 +86,18 @@ impl Velocity {
         Self { x: 0.0, y: 0.0 }
     }
 
+    /// Converts to a glam Vec2
+    #[must_use]
+    pub const fn as_vec2(self) -> Vec2 {
+        Vec2::new(self.x, self.y)
+    }
+
+    /// Creates from a glam Vec2
+    #[must_use]
+    pub const fn from_vec2(v: Vec2) -> Self {
+        Self { x: v.x, y: v.y }
+    }
+
     /// Returns the speed (magnitude)
     #[must_use]
     pub fn speed(self) -> f32 {
@@ -202,6 +215,8 @@ pub enum ScaleMode {
     PixelPerfect,
     /// Fixed size in pixels, no scaling
     Fixed,
+    /// Whole-number multiples of the 1080p scale, so pixel-art UI stays crisp
+    PixelArt,
 }
 
 /// UI Element component for responsive layout
@@ -390,6 +405,79 @@ impl Default for Sprite {
     }
 }
 
+/// Items carried by an entity, with optional per-item carry limits
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Inventory {
+    items: BTreeMap<String, u32>,
+    limits: BTreeMap<String, u32>,
+}
+
+impl Inventory {
+    /// Creates an empty inventory
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            items: BTreeMap::new(),
+            limits: BTreeMap::new(),
+        }
+    }
+
+    /// Limits how many of an item can be carried
+    #[must_use]
+    pub fn with_limit(mut self, item: impl Into<String>, max: u32) -> Self {
+        let _ = self.limits.insert(item.into(), max);
+        self
+    }
+
+    /// Adds items up to the carry limit, returning how many were added
+    pub fn add(&mut self, item: &str, amount: u32) -> u32 {
+        let held = self.count(item);
+        let max = self.limits.get(item).copied().unwrap_or(u32::MAX);
+        let added = amount.min(max.saturating_sub(held));
+        if added > 0 {
+            let _ = self.items.insert(item.to_string(), held + added);
+        }
+        added
+    }
+
+    /// Removes up to `amount` items, returning how many were removed
+    pub fn remove(&mut self, item: &str, amount: u32) -> u32 {
+        let held = self.count(item);
+        let removed = amount.min(held);
+        if removed == held {
+            let _ = self.items.remove(item);
+        } else {
+            let _ = self.items.insert(item.to_string(), held - removed);
+        }
+        removed
+    }
+
+    /// How many of an item are carried
+    #[must_use]
+    pub fn count(&self, item: &str) -> u32 {
+        self.items.get(item).copied().unwrap_or(0)
+    }
+
+    /// Returns true if at least `amount` of an item are carried
+    #[must_use]
+    pub fn has(&self, item: &str, amount: u32) -> bool {
+        self.count(item) >= amount.max(1)
+    }
+
+    /// Carried items and their counts, sorted by name
+    pub fn items(&self) -> impl Iterator<Item = (&str, u32)> {
+        self.items
+            .iter()
+            .map(|(item, &count)| (item.as_str(), count))
+    }
+
+    /// Returns true if nothing is carried
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
 /// Rectangle for collision and rendering
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct Rect {
@@ -477,6 +565,22 @@ mod tests {
         assert!((pos.y).abs() < f32::EPSILON);
     }
 
+    #[test]
+    fn test_inventory_limits_and_removal() {
+        let mut bag = Inventory::new().with_limit("key", 1);
+        assert_eq!(bag.add("key", 3), 1);
+        assert_eq!(bag.add("gem", 5), 5);
+        assert!(bag.has("key", 1));
+        assert!(!bag.has("gem", 6));
+
+        assert_eq!(bag.remove("gem", 2), 2);
+        assert_eq!(bag.remove("key", 4), 1);
+        assert_eq!(bag.count("key"), 0);
+        assert_eq!(bag.items().collect::<Vec<_>>(), vec![("gem", 3)]);
+        assert_eq!(bag.remove("gem", 3), 3);
+        assert!(bag.is_empty());
+    }
+
     #[test]
     fn test_position_as_vec2() {
         let pos = Position::new(10.0, 20.0);
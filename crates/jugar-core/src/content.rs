@@ -0,0 +1,110 @@
+//! Word filter for kid-facing text
+//!
+//! Lives in the core crate so anything that produces text a child will
+//! see, from the YAML sandbox to procedurally generated names, can share
+//! one blocklist without pulling in the YAML parser.
+
+/// Content filter for inappropriate words
+#[derive(Debug, Clone)]
+pub struct ContentFilter {
+    /// Blocked words (case-insensitive matching)
+    blocked_words: Vec<String>,
+}
+
+impl Default for ContentFilter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ContentFilter {
+    /// Create a new content filter with default blocklist
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            blocked_words: default_blocklist(),
+        }
+    }
+
+    /// Check text for content violations
+    #[must_use]
+    pub fn check(&self, text: &str) -> Option<ContentViolation> {
+        let text_lower = text.to_lowercase();
+
+        for blocked in &self.blocked_words {
+            if text_lower.contains(blocked) {
+                return Some(ContentViolation {
+                    word: blocked.clone(),
+                    reason: "This word isn't allowed in games for kids".to_string(),
+                });
+            }
+        }
+
+        None
+    }
+
+    /// Add a word to the blocklist
+    pub fn block_word(&mut self, word: impl Into<String>) {
+        self.blocked_words.push(word.into().to_lowercase());
+    }
+}
+
+/// A content violation detected by the filter
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentViolation {
+    /// The word that triggered the violation
+    pub word: String,
+    /// Reason for blocking
+    pub reason: String,
+}
+
+/// Default blocklist for content filtering
+/// This is a minimal list focused on child safety
+fn default_blocklist() -> Vec<String> {
+    // Minimal blocklist - in production this would be more comprehensive
+    // but loaded from an external source for easy updates
+    vec![
+        "kill".to_string(),
+        "death".to_string(),
+        "blood".to_string(),
+        "gore".to_string(),
+        "violent".to_string(),
+        "weapon".to_string(),
+    ]
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter_blocks_violent_words() {
+        let filter = ContentFilter::new();
+        let result = filter.check("I want to kill the monster");
+        assert!(result.is_some());
+        assert_eq!(result.unwrap().word, "kill");
+    }
+
+    #[test]
+    fn test_filter_case_insensitive() {
+        let filter = ContentFilter::new();
+        let result = filter.check("BLOOD and gore");
+        assert!(result.is_some());
+    }
+
+    #[test]
+    fn test_filter_allows_friendly_content() {
+        let filter = ContentFilter::new();
+        let result = filter.check("bunny catches stars in the sky");
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn test_custom_blocked_word() {
+        let mut filter = ContentFilter::new();
+        filter.block_word("badword");
+        let result = filter.check("this has a badword");
+        assert!(result.is_some());
+    }
+}
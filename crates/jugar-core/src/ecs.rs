@@ -2,20 +2,52 @@
 //!
 //! A lightweight, cache-friendly ECS designed for WASM targets.
 //! Follows Data-Oriented Design principles for optimal performance.
+//!
+//! ## Change detection
+//!
+//! Every component remembers the [`Tick`] it was added and last changed at.
+//! A system keeps the tick of its previous run and queries with an
+//! [`Added`] or [`Changed`] filter, so "update the UI when `Score` changed"
+//! only touches the entities whose score actually moved:
+//!
+//! ```
+//! use jugar_core::{Changed, Position, Tick, World};
+//!
+//! let mut world = World::new();
+//! let e = world.spawn();
+//! world.add_component(e, Position::new(0.0, 0.0));
+//!
+//! // First run sees everything added so far
+//! let mut last_run = Tick::default();
+//! assert_eq!(world.query_filtered::<Position, Changed<Position>>(last_run).count(), 1);
+//! last_run = world.increment_change_tick();
+//!
+//! // Nothing changed since
+//! assert_eq!(world.query_filtered::<Position, Changed<Position>>(last_run).count(), 0);
+//!
+//! if let Some(pos) = world.get_component_mut::<Position>(e) {
+//!     pos.x = 5.0;
+//! }
+//! assert_eq!(world.query_filtered::<Position, Changed<Position>>(last_run).count(), 1);
+//! ```
 
 use core::any::{Any, TypeId};
 use core::fmt;
+use core::marker::PhantomData;
+use core::sync::atomic::{AtomicU64, Ordering};
 use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
-use crate::{CoreError, Result};
+use crate::names::NameIndex;
+use crate::observers::Observers;
+use crate::{Commands, CoreError, Name, Result, Tags};
 
 /// Unique identifier for an entity in the world.
 ///
 /// Entities are lightweight handles - just a generation-tagged index.
 /// Components are stored separately in contiguous arrays.
-#[derive(Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct Entity(pub u64);
 
 impl Entity {
@@ -44,9 +76,45 @@ impl fmt::Display for Entity {
     }
 }
 
+/// Point in a world's change history
+///
+/// The world's tick only moves forward (see [`World::increment_change_tick`]);
+/// a component changed "since" a tick was written after it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Tick(pub u64);
+
+/// When a component was added and last changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ComponentTicks {
+    /// Tick the component was added at
+    pub added: Tick,
+    /// Tick the component was last added, replaced or mutably borrowed at
+    pub changed: Tick,
+}
+
+impl ComponentTicks {
+    /// Returns true if the component was added after `since`
+    #[must_use]
+    pub fn is_added(self, since: Tick) -> bool {
+        self.added > since
+    }
+
+    /// Returns true if the component was added or changed after `since`
+    #[must_use]
+    pub fn is_changed(self, since: Tick) -> bool {
+        self.changed > since
+    }
+}
+
+/// A component and its change ticks
+struct Stored {
+    value: Box<dyn Any + Send + Sync>,
+    ticks: ComponentTicks,
+}
+
 /// Storage for a single component type
 struct ComponentStorage {
-    data: HashMap<Entity, Box<dyn Any + Send + Sync>>,
+    data: HashMap<Entity, Stored>,
 }
 
 impl ComponentStorage {
@@ -56,20 +124,57 @@ impl ComponentStorage {
         }
     }
 
-    fn insert<T: Any + Send + Sync>(&mut self, entity: Entity, component: T) {
-        let _ = self.data.insert(entity, Box::new(component));
+    /// Inserts a component, returning the one it replaced
+    fn insert<T: Any + Send + Sync>(
+        &mut self,
+        entity: Entity,
+        component: T,
+        tick: Tick,
+    ) -> Option<Box<dyn Any + Send + Sync>> {
+        let value: Box<dyn Any + Send + Sync> = Box::new(component);
+        // Replacing counts as a change, not an addition
+        if let Some(stored) = self.data.get_mut(&entity) {
+            stored.ticks.changed = tick;
+            return Some(core::mem::replace(&mut stored.value, value));
+        }
+        let ticks = ComponentTicks {
+            added: tick,
+            changed: tick,
+        };
+        let _ = self.data.insert(entity, Stored { value, ticks });
+        None
+    }
+
+    fn get_any(&self, entity: Entity) -> Option<&(dyn Any + Send + Sync)> {
+        self.data.get(&entity).map(|c| &*c.value)
     }
 
     fn get<T: Any>(&self, entity: Entity) -> Option<&T> {
-        self.data.get(&entity).and_then(|c| c.downcast_ref())
+        self.data.get(&entity).and_then(|c| c.value.downcast_ref())
+    }
+
+    fn get_mut<T: Any>(&mut self, entity: Entity, tick: Tick) -> Option<&mut T> {
+        let stored = self.data.get_mut(&entity)?;
+        let value = stored.value.downcast_mut()?;
+        stored.ticks.changed = tick;
+        Some(value)
     }
 
-    fn get_mut<T: Any>(&mut self, entity: Entity) -> Option<&mut T> {
-        self.data.get_mut(&entity).and_then(|c| c.downcast_mut())
+    fn ticks(&self, entity: Entity) -> Option<ComponentTicks> {
+        self.data.get(&entity).map(|c| c.ticks)
     }
 
-    fn remove(&mut self, entity: Entity) -> bool {
-        self.data.remove(&entity).is_some()
+    fn iter<T: Any>(&self) -> impl Iterator<Item = (Entity, &T, ComponentTicks)> {
+        self.data.iter().filter_map(|(&entity, stored)| {
+            stored
+                .value
+                .downcast_ref()
+                .map(|value| (entity, value, stored.ticks))
+        })
+    }
+
+    fn remove(&mut self, entity: Entity) -> Option<Box<dyn Any + Send + Sync>> {
+        self.data.remove(&entity).map(|c| c.value)
     }
 
     fn contains(&self, entity: Entity) -> bool {
@@ -77,6 +182,63 @@ impl ComponentStorage {
     }
 }
 
+/// Decides which entities a filtered query returns
+///
+/// Implemented by [`Added`], [`Changed`], tuples of filters (all must
+/// match) and `()` (everything matches).
+pub trait QueryFilter {
+    /// Returns true if `entity` passes the filter for a system that last ran
+    /// at `since`
+    fn matches(world: &World, entity: Entity, since: Tick) -> bool;
+}
+
+/// Filter for entities whose `T` was added after the system last ran
+pub struct Added<T>(PhantomData<T>);
+
+/// Filter for entities whose `T` was added or changed after the system
+/// last ran
+pub struct Changed<T>(PhantomData<T>);
+
+impl<T> fmt::Debug for Added<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Added<{}>", core::any::type_name::<T>())
+    }
+}
+
+impl<T> fmt::Debug for Changed<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Changed<{}>", core::any::type_name::<T>())
+    }
+}
+
+impl QueryFilter for () {
+    fn matches(_world: &World, _entity: Entity, _since: Tick) -> bool {
+        true
+    }
+}
+
+impl<T: Any> QueryFilter for Added<T> {
+    fn matches(world: &World, entity: Entity, since: Tick) -> bool {
+        world
+            .component_ticks::<T>(entity)
+            .is_some_and(|ticks| ticks.is_added(since))
+    }
+}
+
+impl<T: Any> QueryFilter for Changed<T> {
+    fn matches(world: &World, entity: Entity, since: Tick) -> bool {
+        world
+            .component_ticks::<T>(entity)
+            .is_some_and(|ticks| ticks.is_changed(since))
+    }
+}
+
+impl<A: QueryFilter, B: QueryFilter> QueryFilter for (A, B) {
+    fn matches(world: &World, entity: Entity, since: Tick) -> bool {
+        A::matches(world, entity, since) && B::matches(world, entity, since)
+    }
+}
+
 /// The game world containing all entities and their components.
 ///
 /// # Example
@@ -96,9 +258,21 @@ impl ComponentStorage {
 /// }
 /// ```
 pub struct World {
-    next_entity_id: u64,
+    next_entity_id: AtomicU64,
     entities: Vec<Entity>,
     components: HashMap<TypeId, ComponentStorage>,
+    change_tick: Tick,
+    names: NameIndex,
+    /// Entity whose `Name` or `Tags` was last borrowed mutably; reindexed
+    /// by the next `&mut self` call, once the borrow has ended
+    renaming: Option<Entity>,
+    observers: Observers,
+    /// Commands queued by observers, applied before the triggering call
+    /// returns
+    deferred: Commands,
+    /// Set while `deferred` is applied, so nested calls leave it to the
+    /// outer loop
+    flushing: bool,
 }
 
 impl Default for World {
@@ -112,20 +286,46 @@ impl World {
     #[must_use]
     pub fn new() -> Self {
         Self {
-            next_entity_id: 0,
+            next_entity_id: AtomicU64::new(0),
             entities: Vec::new(),
             components: HashMap::new(),
+            // Systems start at the default tick, so the first run sees everything
+            change_tick: Tick(1),
+            names: NameIndex::default(),
+            renaming: None,
+            observers: Observers::default(),
+            deferred: Commands::new(),
+            flushing: false,
         }
     }
 
     /// Spawns a new entity and returns its handle
     pub fn spawn(&mut self) -> Entity {
-        let entity = Entity::new(self.next_entity_id);
-        self.next_entity_id += 1;
+        let id = self.next_entity_id.get_mut();
+        let entity = Entity::new(*id);
+        *id += 1;
         self.entities.push(entity);
         entity
     }
 
+    /// Reserves an entity handle without spawning it
+    ///
+    /// Takes `&self` so a [`Commands`](crate::Commands) buffer can hand out
+    /// handles while queries borrow the world; the entity exists once
+    /// [`spawn_reserved`](Self::spawn_reserved) runs.
+    pub fn reserve_entity(&self) -> Entity {
+        Entity::new(self.next_entity_id.fetch_add(1, Ordering::Relaxed))
+    }
+
+    /// Spawns an entity from [`reserve_entity`](Self::reserve_entity)
+    ///
+    /// Does nothing if it already exists or was never handed out.
+    pub fn spawn_reserved(&mut self, entity: Entity) {
+        if entity.0 < *self.next_entity_id.get_mut() && !self.contains(entity) {
+            self.entities.push(entity);
+        }
+    }
+
     /// Despawns an entity and removes all its components
     ///
     /// # Errors
@@ -141,22 +341,43 @@ impl World {
         let _ = self.entities.swap_remove(idx);
 
         // Remove all components for this entity
-        for storage in self.components.values_mut() {
-            let _ = storage.remove(entity);
+        for (&type_id, storage) in &mut self.components {
+            if let Some(component) = storage.remove(entity) {
+                self.observers
+                    .removed(type_id, entity, &*component, &mut self.deferred);
+            }
         }
+        self.observers.despawned(entity, &mut self.deferred);
+        self.reindex_names();
+        self.names.remove(entity);
+        self.flush_observers();
 
         Ok(())
     }
 
     /// Adds a component to an entity
     ///
-    /// If the entity already has this component type, it is replaced.
+    /// If the entity already has this component type, it is replaced; remove
+    /// observers see the old value and add observers the new one.
     pub fn add_component<T: Any + Send + Sync>(&mut self, entity: Entity, component: T) {
         let type_id = TypeId::of::<T>();
-        self.components
+        let storage = self
+            .components
             .entry(type_id)
-            .or_insert_with(ComponentStorage::new)
-            .insert(entity, component);
+            .or_insert_with(ComponentStorage::new);
+        if let Some(replaced) = storage.insert(entity, component, self.change_tick) {
+            self.observers
+                .removed(type_id, entity, &*replaced, &mut self.deferred);
+        }
+        if let Some(added) = storage.get_any(entity) {
+            self.observers
+                .added(type_id, entity, added, &mut self.deferred);
+        }
+        self.reindex_names();
+        if is_lookup_component(type_id) {
+            self.index_names(entity);
+        }
+        self.flush_observers();
     }
 
     /// Gets a reference to a component on an entity
@@ -167,11 +388,64 @@ impl World {
     }
 
     /// Gets a mutable reference to a component on an entity
+    ///
+    /// Borrowing mutably marks the component as changed, whether or not it
+    /// is written to.
     pub fn get_component_mut<T: Any>(&mut self, entity: Entity) -> Option<&mut T> {
         let type_id = TypeId::of::<T>();
+        self.reindex_names();
+        if is_lookup_component(type_id) && self.components.contains_key(&type_id) {
+            self.renaming = Some(entity);
+        }
+        let tick = self.change_tick;
         self.components
             .get_mut(&type_id)
-            .and_then(|s| s.get_mut(entity))
+            .and_then(|s| s.get_mut(entity, tick))
+    }
+
+    /// When an entity's `T` was added and last changed
+    #[must_use]
+    pub fn component_ticks<T: Any>(&self, entity: Entity) -> Option<ComponentTicks> {
+        let type_id = TypeId::of::<T>();
+        self.components.get(&type_id).and_then(|s| s.ticks(entity))
+    }
+
+    /// Every entity with a `T`, in no particular order
+    pub fn query<T: Any>(&self) -> impl Iterator<Item = (Entity, &T)> {
+        self.query_filtered::<T, ()>(Tick::default())
+    }
+
+    /// Entities with a `T` that pass filter `F` for a system that last ran
+    /// at `since`, in no particular order
+    ///
+    /// Only entities holding a `T` are visited, so a filter on `T` itself
+    /// costs one tick comparison per entity.
+    pub fn query_filtered<T: Any, F: QueryFilter>(
+        &self,
+        since: Tick,
+    ) -> impl Iterator<Item = (Entity, &T)> {
+        self.components
+            .get(&TypeId::of::<T>())
+            .into_iter()
+            .flat_map(ComponentStorage::iter::<T>)
+            .filter(move |&(entity, _, _)| F::matches(self, entity, since))
+            .map(|(entity, value, _)| (entity, value))
+    }
+
+    /// Current change tick; components added or changed now get this tick
+    #[must_use]
+    pub const fn change_tick(&self) -> Tick {
+        self.change_tick
+    }
+
+    /// Advances the change tick and returns the previous one
+    ///
+    /// A system stores the returned tick as its last run and passes it to
+    /// its next filtered query; changes made after this call are newer.
+    pub fn increment_change_tick(&mut self) -> Tick {
+        let previous = self.change_tick;
+        self.change_tick = Tick(previous.0 + 1);
+        previous
     }
 
     /// Checks if an entity has a specific component
@@ -188,9 +462,109 @@ impl World {
     /// Returns true if the component was removed, false if it didn't exist.
     pub fn remove_component<T: Any>(&mut self, entity: Entity) -> bool {
         let type_id = TypeId::of::<T>();
-        self.components
+        let Some(component) = self
+            .components
             .get_mut(&type_id)
-            .is_some_and(|s| s.remove(entity))
+            .and_then(|s| s.remove(entity))
+        else {
+            return false;
+        };
+        self.observers
+            .removed(type_id, entity, &*component, &mut self.deferred);
+        self.reindex_names();
+        if is_lookup_component(type_id) {
+            self.index_names(entity);
+        }
+        self.flush_observers();
+        true
+    }
+
+    /// Calls `observer` whenever a `T` is added to an entity
+    pub fn on_add<T: Any>(
+        &mut self,
+        observer: impl FnMut(Entity, &T, &mut Commands) + Send + Sync + 'static,
+    ) {
+        self.observers.on_add::<T, _>(observer);
+    }
+
+    /// Calls `observer` whenever a `T` is removed, replaced or despawned
+    /// with its entity, before the component is dropped
+    pub fn on_remove<T: Any>(
+        &mut self,
+        observer: impl FnMut(Entity, &T, &mut Commands) + Send + Sync + 'static,
+    ) {
+        self.observers.on_remove::<T, _>(observer);
+    }
+
+    /// Calls `observer` whenever an entity is despawned, after its
+    /// components' remove observers
+    pub fn on_despawn<F>(&mut self, observer: F)
+    where
+        F: FnMut(Entity, &mut Commands) + Send + Sync + 'static,
+    {
+        self.observers.on_despawn(observer);
+    }
+
+    /// Applies commands queued by observers, including the ones those
+    /// commands trigger in turn
+    fn flush_observers(&mut self) {
+        if self.flushing {
+            return;
+        }
+        self.flushing = true;
+        while !self.deferred.is_empty() {
+            let mut commands = core::mem::take(&mut self.deferred);
+            commands.apply(self);
+        }
+        self.flushing = false;
+    }
+
+    /// First-spawned entity with this [`Name`]
+    #[must_use]
+    pub fn find_by_name(&self, name: &str) -> Option<Entity> {
+        self.names
+            .named(name)
+            .chain(self.renaming)
+            .filter(|&entity| {
+                self.get_component::<Name>(entity)
+                    .is_some_and(|n| n.as_str() == name)
+            })
+            .min()
+    }
+
+    /// Entities whose [`Tags`] include `tag`, oldest first
+    #[must_use]
+    pub fn with_tag(&self, tag: &str) -> Vec<Entity> {
+        let mut entities: Vec<Entity> = self
+            .names
+            .tagged(tag)
+            .filter(|&entity| Some(entity) != self.renaming)
+            .collect();
+        if let Some(entity) = self.renaming {
+            if self
+                .get_component::<Tags>(entity)
+                .is_some_and(|tags| tags.contains(tag))
+            {
+                let at = entities.partition_point(|&e| e < entity);
+                entities.insert(at, entity);
+            }
+        }
+        entities
+    }
+
+    /// Indexes the entity whose name or tags were last borrowed mutably
+    fn reindex_names(&mut self) {
+        if let Some(entity) = self.renaming.take() {
+            self.index_names(entity);
+        }
+    }
+
+    /// Indexes `entity` under its current name and tags
+    fn index_names(&mut self, entity: Entity) {
+        let storage = |type_id: TypeId| self.components.get(&type_id);
+        let name = storage(TypeId::of::<Name>()).and_then(|s| s.get::<Name>(entity));
+        let tags = storage(TypeId::of::<Tags>()).and_then(|s| s.get::<Tags>(entity));
+        self.names.update(entity, name, tags);
     }
 
     /// Returns the number of entities in the world
@@ -241,6 +615,11 @@ impl World {
     }
 }
 
+/// Returns true for the components the name index covers
+fn is_lookup_component(type_id: TypeId) -> bool {
+    type_id == TypeId::of::<Name>() || type_id == TypeId::of::<Tags>()
+}
+
 impl fmt::Debug for World {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("World")
@@ -465,6 +844,93 @@ mod tests {
         assert!(entities.contains(&e3));
     }
 
+    // ==================== CHANGE DETECTION TESTS ====================
+
+    #[derive(Debug, PartialEq)]
+    struct Score(u32);
+
+    fn changed_scores(world: &World, since: Tick) -> Vec<Entity> {
+        let mut entities: Vec<_> = world
+            .query_filtered::<Score, Changed<Score>>(since)
+            .map(|(entity, _)| entity)
+            .collect();
+        entities.sort_by_key(|e| e.id());
+        entities
+    }
+
+    #[test]
+    fn test_added_and_changed_filters() {
+        let mut world = World::new();
+        let a = world.spawn();
+        let b = world.spawn();
+        world.add_component(a, Score(0));
+        world.add_component(b, Score(0));
+
+        let last_run = world.increment_change_tick();
+        let c = world.spawn();
+        world.add_component(c, Score(0));
+        if let Some(score) = world.get_component_mut::<Score>(a) {
+            score.0 += 1;
+        }
+
+        assert_eq!(changed_scores(&world, Tick::default()), [a, b, c]);
+        assert_eq!(changed_scores(&world, last_run), [a, c]);
+        let added: Vec<_> = world
+            .query_filtered::<Score, Added<Score>>(last_run)
+            .map(|(entity, score)| (entity, score.0))
+            .collect();
+        assert_eq!(added, [(c, 0)]);
+
+        // Replacing is a change but not an addition
+        let last_run = world.increment_change_tick();
+        world.add_component(b, Score(7));
+        assert_eq!(changed_scores(&world, last_run), [b]);
+        assert_eq!(
+            world
+                .query_filtered::<Score, Added<Score>>(last_run)
+                .count(),
+            0
+        );
+        let ticks = world.component_ticks::<Score>(b).unwrap();
+        assert!(ticks.added < ticks.changed);
+    }
+
+    #[test]
+    fn test_filter_on_other_component() {
+        let mut world = World::new();
+        let moving = world.spawn();
+        let still = world.spawn();
+        for e in [moving, still] {
+            world.add_component(e, Position::new(0.0, 0.0));
+            world.add_component(e, Velocity::new(1.0, 0.0));
+        }
+        let last_run = world.increment_change_tick();
+        let _ = world.get_component_mut::<Velocity>(moving);
+
+        let hits: Vec<_> = world
+            .query_filtered::<Position, Changed<Velocity>>(last_run)
+            .map(|(entity, _)| entity)
+            .collect();
+        assert_eq!(hits, [moving]);
+        assert_eq!(
+            world
+                .query_filtered::<Position, (Changed<Velocity>, Added<Position>)>(last_run)
+                .count(),
+            0
+        );
+        assert_eq!(world.query::<Position>().count(), 2);
+        assert_eq!(world.query::<Score>().count(), 0);
+
+        world.despawn(moving).unwrap();
+        assert!(world.component_ticks::<Velocity>(moving).is_none());
+        assert_eq!(
+            world
+                .query_filtered::<Position, Changed<Velocity>>(last_run)
+                .count(),
+            0
+        );
+    }
+
     // ==================== BEHAVIORAL TESTS (MUTATION-RESISTANT) ====================
 
     #[test]
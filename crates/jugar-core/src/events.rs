@@ -0,0 +1,121 @@
+//! Double-buffered event queues (event bus)
+//!
+//! Systems publish events with [`Events::send`] and consumers read them with
+//! [`Events::iter`] or take them with [`Events::drain`]. Calling
+//! [`Events::update`] once per frame keeps each event readable for the frame
+//! it was sent in and the next one, so systems that run before the sender
+//! still see it exactly once.
+
+use core::fmt;
+
+/// Double-buffered queue of events of one type
+#[derive(Clone, PartialEq, Eq)]
+pub struct Events<T> {
+    previous: Vec<T>,
+    current: Vec<T>,
+}
+
+impl<T> Events<T> {
+    /// Creates an empty queue
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            previous: Vec::new(),
+            current: Vec::new(),
+        }
+    }
+
+    /// Publishes an event
+    pub fn send(&mut self, event: T) {
+        self.current.push(event);
+    }
+
+    /// Publishes several events
+    pub fn extend(&mut self, events: impl IntoIterator<Item = T>) {
+        self.current.extend(events);
+    }
+
+    /// Iterates events from the previous and current frame, oldest first
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.previous.iter().chain(self.current.iter())
+    }
+
+    /// Removes and returns all pending events, oldest first
+    pub fn drain(&mut self) -> impl Iterator<Item = T> + '_ {
+        self.previous.drain(..).chain(self.current.drain(..))
+    }
+
+    /// Advances one frame, dropping events older than one frame
+    pub fn update(&mut self) {
+        core::mem::swap(&mut self.previous, &mut self.current);
+        self.current.clear();
+    }
+
+    /// Number of readable events
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.previous.len() + self.current.len()
+    }
+
+    /// Returns true if there are no readable events
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.previous.is_empty() && self.current.is_empty()
+    }
+
+    /// Drops all events
+    pub fn clear(&mut self) {
+        self.previous.clear();
+        self.current.clear();
+    }
+}
+
+impl<T> Default for Events<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> fmt::Debug for Events<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Events")
+            .field("previous", &self.previous.len())
+            .field("current", &self.current.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_send_and_iter() {
+        let mut events = Events::new();
+        events.send(1);
+        events.send(2);
+        assert_eq!(events.iter().copied().collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(events.len(), 2);
+    }
+
+    #[test]
+    fn test_events_live_two_frames() {
+        let mut events = Events::new();
+        events.send("hit");
+        events.update();
+        assert_eq!(events.len(), 1);
+        events.update();
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn test_drain_takes_all() {
+        let mut events = Events::new();
+        events.send(1);
+        events.update();
+        events.send(2);
+        assert_eq!(events.drain().collect::<Vec<_>>(), vec![1, 2]);
+        assert!(events.is_empty());
+    }
+}
@@ -0,0 +1,566 @@
+//! Parallel system execution.
+//!
+//! Each frame, independent systems (audio update, AI planning, particle
+//! update) are added to a [`JobGraph`] as [`Job`]s that declare which
+//! resources they read and write. The graph sorts them into stages whose
+//! jobs never touch the same resource mutably, then runs each stage on
+//! scoped threads, or one job at a time in declaration order under
+//! [`ExecutionMode::Sequential`]. Either way the result is the same, which
+//! keeps replays deterministic on single-threaded WASM.
+//!
+//! Planning fails with [`ScheduleError::Conflict`] before any job runs if
+//! a stage would pair conflicting access sets, and the planned [`Schedule`]
+//! is kept so Probar can check the same.
+
+use core::any::{type_name, TypeId};
+use core::fmt;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// How a [`JobGraph`] runs its stages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ExecutionMode {
+    /// One job at a time, in declaration order (deterministic fallback)
+    #[default]
+    Sequential,
+    /// Each stage spread over up to `threads` threads
+    Parallel {
+        /// Worker threads per stage, including the calling thread
+        threads: usize,
+    },
+}
+
+impl ExecutionMode {
+    /// Parallel on native targets and threads-enabled WASM, sequential otherwise
+    ///
+    /// Parallel stages run on scoped threads spawned for each stage, so
+    /// this suits one-off heavy work (such as baking) rather than
+    /// per-frame systems.
+    #[must_use]
+    pub fn detect() -> Self {
+        if cfg!(all(target_arch = "wasm32", not(target_feature = "atomics"))) {
+            return Self::Sequential;
+        }
+        match std::thread::available_parallelism() {
+            Ok(threads) if threads.get() > 1 => Self::Parallel {
+                threads: threads.get(),
+            },
+            _ => Self::Sequential,
+        }
+    }
+
+    /// Threads a stage may use
+    #[must_use]
+    pub fn threads(self) -> usize {
+        match self {
+            Self::Sequential => 1,
+            Self::Parallel { threads } => threads.max(1),
+        }
+    }
+}
+
+/// A resource a job touches, identified by type
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ResourceId {
+    type_id: TypeId,
+    name: &'static str,
+}
+
+impl ResourceId {
+    /// Identifies resource type `T`
+    #[must_use]
+    pub fn of<T: 'static>() -> Self {
+        Self {
+            type_id: TypeId::of::<T>(),
+            name: type_name::<T>(),
+        }
+    }
+
+    /// Type name, for reports
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+impl fmt::Debug for ResourceId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name)
+    }
+}
+
+/// Resources a job reads and writes
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Access {
+    reads: Vec<ResourceId>,
+    writes: Vec<ResourceId>,
+}
+
+impl Access {
+    /// Creates an empty access set
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            reads: Vec::new(),
+            writes: Vec::new(),
+        }
+    }
+
+    /// Adds a read of `T`
+    #[must_use]
+    pub fn read<T: 'static>(mut self) -> Self {
+        let id = ResourceId::of::<T>();
+        if !self.reads.contains(&id) {
+            self.reads.push(id);
+        }
+        self
+    }
+
+    /// Adds a write of `T`
+    #[must_use]
+    pub fn write<T: 'static>(mut self) -> Self {
+        let id = ResourceId::of::<T>();
+        if !self.writes.contains(&id) {
+            self.writes.push(id);
+        }
+        self
+    }
+
+    /// Resources read
+    #[must_use]
+    pub fn reads(&self) -> &[ResourceId] {
+        &self.reads
+    }
+
+    /// Resources written
+    #[must_use]
+    pub fn writes(&self) -> &[ResourceId] {
+        &self.writes
+    }
+
+    /// Returns true if running alongside `other` could race
+    ///
+    /// Two reads never conflict; a write conflicts with any other access
+    /// to the same resource.
+    #[must_use]
+    pub fn conflicts_with(&self, other: &Self) -> bool {
+        self.writes
+            .iter()
+            .any(|id| other.writes.contains(id) || other.reads.contains(id))
+            || self.reads.iter().any(|id| other.writes.contains(id))
+    }
+}
+
+/// Errors from planning a [`JobGraph`]
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ScheduleError {
+    /// A job runs after a job that wasn't added before it
+    #[error("Job '{job}' runs after unknown job '{dependency}'")]
+    UnknownDependency {
+        /// Job declaring the dependency
+        job: String,
+        /// Name that didn't match an earlier job
+        dependency: String,
+    },
+    /// Two jobs in one stage declare conflicting access
+    #[error("Jobs '{first}' and '{second}' share a stage but conflict")]
+    Conflict {
+        /// Earlier job
+        first: String,
+        /// Later job
+        second: String,
+    },
+}
+
+/// A unit of work for one frame
+pub struct Job<'a> {
+    name: String,
+    access: Access,
+    after: Vec<String>,
+    run: Box<dyn FnOnce() + Send + 'a>,
+}
+
+impl<'a> Job<'a> {
+    /// Creates a job that touches no declared resources
+    #[must_use]
+    pub fn new(name: impl Into<String>, run: impl FnOnce() + Send + 'a) -> Self {
+        Self {
+            name: name.into(),
+            access: Access::new(),
+            after: Vec::new(),
+            run: Box::new(run),
+        }
+    }
+
+    /// Declares a read of `T`
+    #[must_use]
+    pub fn reads<T: 'static>(mut self) -> Self {
+        self.access = self.access.read::<T>();
+        self
+    }
+
+    /// Declares a write of `T`
+    #[must_use]
+    pub fn writes<T: 'static>(mut self) -> Self {
+        self.access = self.access.write::<T>();
+        self
+    }
+
+    /// Runs only once the named earlier job has finished
+    #[must_use]
+    pub fn after(mut self, job: impl Into<String>) -> Self {
+        self.after.push(job.into());
+        self
+    }
+
+    /// Job name
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Declared access
+    #[must_use]
+    pub const fn access(&self) -> &Access {
+        &self.access
+    }
+}
+
+impl fmt::Debug for Job<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Job")
+            .field("name", &self.name)
+            .field("access", &self.access)
+            .field("after", &self.after)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A planned job, without its work
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduledJob {
+    /// Job name
+    pub name: String,
+    /// Declared access
+    pub access: Access,
+}
+
+/// Jobs grouped into stages that may each run in parallel
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Schedule {
+    stages: Vec<Vec<ScheduledJob>>,
+}
+
+impl Schedule {
+    /// Stages in run order
+    #[must_use]
+    pub fn stages(&self) -> &[Vec<ScheduledJob>] {
+        &self.stages
+    }
+
+    /// Number of stages
+    #[must_use]
+    pub fn stage_count(&self) -> usize {
+        self.stages.len()
+    }
+
+    /// Stage a job runs in
+    #[must_use]
+    pub fn stage_of(&self, job: &str) -> Option<usize> {
+        self.stages
+            .iter()
+            .position(|stage| stage.iter().any(|scheduled| scheduled.name == job))
+    }
+
+    /// Pairs of jobs sharing a stage despite conflicting access
+    ///
+    /// Always empty for a schedule planned by [`JobGraph`]; Probar asserts
+    /// this to verify the frame is race-free.
+    #[must_use]
+    pub fn conflicts(&self) -> Vec<(String, String)> {
+        let mut conflicts = Vec::new();
+        for stage in &self.stages {
+            for (i, first) in stage.iter().enumerate() {
+                for second in &stage[i + 1..] {
+                    if first.access.conflicts_with(&second.access) {
+                        conflicts.push((first.name.clone(), second.name.clone()));
+                    }
+                }
+            }
+        }
+        conflicts
+    }
+}
+
+/// A frame's jobs and the order constraints between them
+#[derive(Debug, Default)]
+pub struct JobGraph<'a> {
+    jobs: Vec<Job<'a>>,
+}
+
+impl<'a> JobGraph<'a> {
+    /// Creates an empty graph
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { jobs: Vec::new() }
+    }
+
+    /// Adds a job; declaration order breaks ties between conflicting jobs
+    pub fn add(&mut self, job: Job<'a>) {
+        self.jobs.push(job);
+    }
+
+    /// Adds a job (builder form)
+    #[must_use]
+    pub fn with(mut self, job: Job<'a>) -> Self {
+        self.add(job);
+        self
+    }
+
+    /// Number of jobs
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.jobs.len()
+    }
+
+    /// Returns true if no jobs were added
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.jobs.is_empty()
+    }
+
+    /// Assigns each job the earliest stage after every earlier job it
+    /// conflicts with or runs after
+    fn stage_indices(&self) -> Result<Vec<usize>, ScheduleError> {
+        let mut stages: Vec<usize> = Vec::with_capacity(self.jobs.len());
+        for (index, job) in self.jobs.iter().enumerate() {
+            let earlier = &self.jobs[..index];
+            for dependency in &job.after {
+                if !earlier.iter().any(|other| &other.name == dependency) {
+                    return Err(ScheduleError::UnknownDependency {
+                        job: job.name.clone(),
+                        dependency: dependency.clone(),
+                    });
+                }
+            }
+            let stage = earlier
+                .iter()
+                .zip(&stages)
+                .filter(|(other, _)| {
+                    job.after.contains(&other.name) || job.access.conflicts_with(&other.access)
+                })
+                .map(|(_, &stage)| stage + 1)
+                .max()
+                .unwrap_or(0);
+            stages.push(stage);
+        }
+        Ok(stages)
+    }
+
+    /// Plans the stages without running anything
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ScheduleError::UnknownDependency`] if a job runs after a
+    /// name that no earlier job has, or [`ScheduleError::Conflict`] if a
+    /// stage would pair conflicting access.
+    pub fn plan(&self) -> Result<Schedule, ScheduleError> {
+        let indices = self.stage_indices()?;
+        self.schedule(&indices)
+    }
+
+    /// Groups the jobs into stages and checks that no stage pairs
+    /// conflicting access
+    fn schedule(&self, indices: &[usize]) -> Result<Schedule, ScheduleError> {
+        let mut schedule = Schedule {
+            stages: vec![Vec::new(); indices.iter().max().map_or(0, |last| last + 1)],
+        };
+        for (job, &stage) in self.jobs.iter().zip(indices) {
+            schedule.stages[stage].push(ScheduledJob {
+                name: job.name.clone(),
+                access: job.access.clone(),
+            });
+        }
+        match schedule.conflicts().into_iter().next() {
+            Some((first, second)) => Err(ScheduleError::Conflict { first, second }),
+            None => Ok(schedule),
+        }
+    }
+
+    /// Runs every job, stage by stage, and returns the schedule used
+    ///
+    /// A panicking job propagates its panic once its stage has finished.
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`plan`](Self::plan) error without running any job if
+    /// the graph can't be planned.
+    pub fn run(self, mode: ExecutionMode) -> Result<Schedule, ScheduleError> {
+        let indices = self.stage_indices()?;
+        let schedule = self.schedule(&indices)?;
+        let mut stages: Vec<Vec<Box<dyn FnOnce() + Send + 'a>>> =
+            (0..schedule.stage_count()).map(|_| Vec::new()).collect();
+        for (job, stage) in self.jobs.into_iter().zip(indices) {
+            stages[stage].push(job.run);
+        }
+        for stage in stages {
+            run_stage(stage, mode.threads());
+        }
+        Ok(schedule)
+    }
+}
+
+/// Runs one stage's jobs over up to `threads` threads
+fn run_stage(jobs: Vec<Box<dyn FnOnce() + Send + '_>>, threads: usize) {
+    if threads <= 1 || jobs.len() <= 1 {
+        for job in jobs {
+            job();
+        }
+        return;
+    }
+
+    // Deal jobs round-robin; the calling thread takes the first share
+    let workers = threads.min(jobs.len());
+    let mut shares: Vec<Vec<Box<dyn FnOnce() + Send + '_>>> =
+        (0..workers).map(|_| Vec::new()).collect();
+    for (index, job) in jobs.into_iter().enumerate() {
+        shares[index % workers].push(job);
+    }
+    let mut shares = shares.into_iter();
+    let local = shares.next().unwrap_or_default();
+    std::thread::scope(|scope| {
+        for share in shares {
+            let _ = scope.spawn(move || {
+                for job in share {
+                    job();
+                }
+            });
+        }
+        for job in local {
+            job();
+        }
+    });
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    struct Audio;
+    struct Particles;
+    struct Ai;
+
+    #[test]
+    fn test_independent_jobs_share_a_stage() {
+        let graph = JobGraph::new()
+            .with(Job::new("audio", || {}).writes::<Audio>())
+            .with(Job::new("particles", || {}).writes::<Particles>())
+            .with(Job::new("ai", || {}).reads::<Particles>().writes::<Ai>());
+        let schedule = graph.plan().unwrap();
+        assert_eq!(schedule.stage_count(), 2);
+        assert_eq!(schedule.stage_of("audio"), Some(0));
+        assert_eq!(schedule.stage_of("particles"), Some(0));
+        // Reads what particles writes, so waits for it
+        assert_eq!(schedule.stage_of("ai"), Some(1));
+        assert!(schedule.conflicts().is_empty());
+    }
+
+    #[test]
+    fn test_after_orders_and_rejects_unknown() {
+        let graph = JobGraph::new()
+            .with(Job::new("input", || {}))
+            .with(Job::new("ai", || {}).after("input"));
+        assert_eq!(graph.plan().unwrap().stage_of("ai"), Some(1));
+
+        let graph = JobGraph::new().with(Job::new("ai", || {}).after("input"));
+        assert_eq!(
+            graph.plan().unwrap_err(),
+            ScheduleError::UnknownDependency {
+                job: "ai".to_string(),
+                dependency: "input".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_modes_give_same_result() {
+        let run = |mode| {
+            let mut audio = 0_u32;
+            let mut particles = vec![1.0_f32; 64];
+            let log = Mutex::new(Vec::new());
+            let schedule = JobGraph::new()
+                .with(
+                    Job::new("audio", || {
+                        audio += 1;
+                        log.lock().unwrap().push("audio");
+                    })
+                    .writes::<Audio>(),
+                )
+                .with(
+                    Job::new("particles", || {
+                        for p in &mut particles {
+                            *p *= 2.0;
+                        }
+                    })
+                    .writes::<Particles>(),
+                )
+                .with(
+                    Job::new("ai", || log.lock().unwrap().push("ai"))
+                        .reads::<Particles>()
+                        .after("audio"),
+                )
+                .run(mode)
+                .unwrap();
+            let log = log.into_inner().unwrap();
+            (audio, particles, log, schedule)
+        };
+
+        let sequential = run(ExecutionMode::Sequential);
+        let parallel = run(ExecutionMode::Parallel { threads: 4 });
+        assert_eq!(sequential.0, 1);
+        assert_eq!(sequential, parallel);
+        assert_eq!(sequential.2, ["audio", "ai"]);
+    }
+
+    #[test]
+    fn test_conflicts_reported() {
+        let access = Access::new().write::<Audio>();
+        let schedule = Schedule {
+            stages: vec![vec![
+                ScheduledJob {
+                    name: "a".to_string(),
+                    access: access.clone(),
+                },
+                ScheduledJob {
+                    name: "b".to_string(),
+                    access: Access::new().read::<Audio>(),
+                },
+            ]],
+        };
+        assert_eq!(
+            schedule.conflicts(),
+            vec![("a".to_string(), "b".to_string())]
+        );
+        assert!(!access.conflicts_with(&Access::new().read::<Particles>()));
+        assert_eq!(ExecutionMode::Sequential.threads(), 1);
+    }
+
+    #[test]
+    fn test_conflicting_stage_is_rejected() {
+        let graph = JobGraph::new()
+            .with(Job::new("audio", || {}).writes::<Audio>())
+            .with(Job::new("mixer", || {}).reads::<Audio>());
+        assert_eq!(graph.plan().unwrap().stage_of("mixer"), Some(1));
+        // Forcing both into one stage must not produce a schedule
+        assert_eq!(
+            graph.schedule(&[0, 0]).unwrap_err(),
+            ScheduleError::Conflict {
+                first: "audio".to_string(),
+                second: "mixer".to_string(),
+            }
+        );
+    }
+}
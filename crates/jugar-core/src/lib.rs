@@ -20,19 +20,54 @@
 #![warn(clippy::pedantic)]
 #![allow(clippy::module_name_repetitions)]
 
+extern crate alloc;
+
 use thiserror::Error;
 
+pub mod accessibility;
+pub mod achievements;
+pub mod commands;
 pub mod components;
+pub mod content;
 pub mod ecs;
+pub mod events;
 pub mod game_loop;
+pub mod jobs;
+pub mod names;
+pub mod observers;
+pub mod perf;
+pub mod pool;
+pub mod senses;
+pub mod soa;
+pub mod spatial;
+pub mod storage;
+pub mod tilemap;
+pub mod timer;
+pub mod versioning;
 
 /// Probar introspection hooks (only compiled with `probar` feature)
 #[cfg(feature = "jugar-probar")]
 pub mod introspection;
 
+pub use accessibility::*;
+pub use achievements::*;
+pub use commands::*;
 pub use components::*;
+pub use content::*;
 pub use ecs::*;
+pub use events::*;
 pub use game_loop::*;
+pub use jobs::*;
+pub use names::*;
+pub use perf::*;
+pub use pool::*;
+pub use senses::*;
+pub use soa::*;
+pub use spatial::*;
+pub use storage::*;
+pub use tilemap::*;
+pub use timer::*;
+pub use versioning::*;
 
 #[cfg(feature = "jugar-probar")]
 pub use introspection::*;
@@ -56,6 +91,13 @@ pub enum CoreError {
         /// Attempted target state
         to: String,
     },
+
+    /// Storage quota exceeded
+    #[error("Storage full, couldn't save '{key}'")]
+    StorageFull {
+        /// Key being written
+        key: String,
+    },
 }
 
 /// Result type for jugar-core operations
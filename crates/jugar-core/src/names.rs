@@ -0,0 +1,234 @@
+//! Entity names and tags with lookup by string
+//!
+//! The YAML compiler and test assertions refer to entities as `"star"` or
+//! `"enemy"`, while the ECS hands out opaque [`Entity`] handles. Adding a
+//! [`Name`] or [`Tags`] component indexes the entity, so
+//! [`World::find_by_name`](crate::World::find_by_name) and
+//! [`World::with_tag`](crate::World::with_tag) answer with a hash lookup
+//! instead of scanning every entity.
+//!
+//! ```
+//! use jugar_core::{Name, Tags, World};
+//!
+//! let mut world = World::new();
+//! let star = world.spawn();
+//! world.add_component(star, Name::new("star"));
+//! let bat = world.spawn();
+//! world.add_component(bat, Tags::new(["enemy", "flying"]));
+//!
+//! assert_eq!(world.find_by_name("star"), Some(star));
+//! assert_eq!(world.with_tag("enemy"), [bat]);
+//! ```
+
+use alloc::collections::BTreeSet;
+use core::fmt;
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::Entity;
+
+/// Name an entity can be looked up by
+///
+/// Names need not be unique: several spawned stars can all be `"star"`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Name(pub String);
+
+impl Name {
+    /// Creates a name
+    #[must_use]
+    pub fn new(name: impl Into<String>) -> Self {
+        Self(name.into())
+    }
+
+    /// The name as a string slice
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Name {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Labels shared by groups of entities (e.g. `"enemy"`, `"collectible"`)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Tags(BTreeSet<String>);
+
+impl Tags {
+    /// Creates a tag set
+    #[must_use]
+    pub fn new<I, S>(tags: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self(tags.into_iter().map(Into::into).collect())
+    }
+
+    /// Adds a tag; returns false if it was already there
+    pub fn insert(&mut self, tag: impl Into<String>) -> bool {
+        self.0.insert(tag.into())
+    }
+
+    /// Removes a tag; returns true if it was there
+    pub fn remove(&mut self, tag: &str) -> bool {
+        self.0.remove(tag)
+    }
+
+    /// Returns true if the tag is present
+    #[must_use]
+    pub fn contains(&self, tag: &str) -> bool {
+        self.0.contains(tag)
+    }
+
+    /// Tags in alphabetical order
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(String::as_str)
+    }
+
+    /// Number of tags
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns true if there are no tags
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// Entities by name and by tag, kept up to date by the world
+#[derive(Debug, Default)]
+pub(crate) struct NameIndex {
+    by_name: HashMap<String, BTreeSet<Entity>>,
+    by_tag: HashMap<String, BTreeSet<Entity>>,
+    /// What each entity is indexed under, for unindexing
+    keys: HashMap<Entity, (Option<String>, Vec<String>)>,
+}
+
+impl NameIndex {
+    /// Indexes `entity` under its current name and tags, replacing any
+    /// previous entries
+    pub(crate) fn update(&mut self, entity: Entity, name: Option<&Name>, tags: Option<&Tags>) {
+        self.remove(entity);
+        let name = name.map(|name| name.0.clone());
+        let tags: Vec<String> = tags.map_or_else(Vec::new, |tags| tags.0.iter().cloned().collect());
+        if name.is_none() && tags.is_empty() {
+            return;
+        }
+        if let Some(name) = &name {
+            let _ = self.by_name.entry(name.clone()).or_default().insert(entity);
+        }
+        for tag in &tags {
+            let _ = self.by_tag.entry(tag.clone()).or_default().insert(entity);
+        }
+        let _ = self.keys.insert(entity, (name, tags));
+    }
+
+    /// Drops every entry for `entity`
+    pub(crate) fn remove(&mut self, entity: Entity) {
+        let Some((name, tags)) = self.keys.remove(&entity) else {
+            return;
+        };
+        if let Some(name) = name {
+            unindex(&mut self.by_name, &name, entity);
+        }
+        for tag in tags {
+            unindex(&mut self.by_tag, &tag, entity);
+        }
+    }
+
+    /// Entities indexed under `name`, oldest first
+    pub(crate) fn named(&self, name: &str) -> impl Iterator<Item = Entity> + '_ {
+        self.by_name.get(name).into_iter().flatten().copied()
+    }
+
+    /// Entities indexed under `tag`, oldest first
+    pub(crate) fn tagged(&self, tag: &str) -> impl Iterator<Item = Entity> + '_ {
+        self.by_tag.get(tag).into_iter().flatten().copied()
+    }
+}
+
+/// Removes `entity` from `key`'s set, dropping the set once empty
+fn unindex(map: &mut HashMap<String, BTreeSet<Entity>>, key: &str, entity: Entity) {
+    if let Some(entities) = map.get_mut(key) {
+        let _ = entities.remove(&entity);
+        if entities.is_empty() {
+            let _ = map.remove(key);
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use crate::{Name, Tags, World};
+
+    #[test]
+    fn test_find_by_name_prefers_oldest() {
+        let mut world = World::new();
+        let first = world.spawn();
+        let second = world.spawn();
+        world.add_component(second, Name::new("star"));
+        world.add_component(first, Name::new("star"));
+        assert_eq!(world.find_by_name("star"), Some(first));
+        assert_eq!(world.find_by_name("moon"), None);
+
+        world.despawn(first).unwrap();
+        assert_eq!(world.find_by_name("star"), Some(second));
+        assert!(world.remove_component::<Name>(second));
+        assert_eq!(world.find_by_name("star"), None);
+    }
+
+    #[test]
+    fn test_renaming_through_mutable_borrow() {
+        let mut world = World::new();
+        let e = world.spawn();
+        world.add_component(e, Name::new("egg"));
+        world.add_component(e, Tags::new(["small"]));
+
+        *world.get_component_mut::<Name>(e).unwrap() = Name::new("chick");
+        // Visible before the next mutation reindexes it
+        assert_eq!(world.find_by_name("egg"), None);
+        assert_eq!(world.find_by_name("chick"), Some(e));
+
+        let other = world.spawn();
+        world.add_component(other, Tags::new(["small"]));
+        let _ = world.get_component_mut::<Tags>(e).unwrap().remove("small");
+        assert_eq!(world.with_tag("small"), [other]);
+        let _ = world.get_component_mut::<Tags>(e).unwrap().insert("small");
+        assert_eq!(world.with_tag("small"), [e, other]);
+
+        let _ = world.spawn();
+        assert_eq!(world.find_by_name("chick"), Some(e));
+        assert_eq!(world.with_tag("small"), [e, other]);
+    }
+
+    #[test]
+    fn test_tags() {
+        let mut world = World::new();
+        let bat = world.spawn();
+        let ghost = world.spawn();
+        world.add_component(bat, Tags::new(["enemy", "flying"]));
+        world.add_component(ghost, Tags::new(["enemy"]));
+
+        assert_eq!(world.with_tag("enemy"), [bat, ghost]);
+        assert_eq!(world.with_tag("flying"), [bat]);
+        assert!(world.with_tag("friend").is_empty());
+
+        // Replacing the component replaces the index entries
+        world.add_component(bat, Tags::new(["friend"]));
+        assert_eq!(world.with_tag("enemy"), [ghost]);
+        assert_eq!(world.with_tag("friend"), [bat]);
+
+        let tags = world.get_component::<Tags>(bat).unwrap();
+        assert_eq!(tags.iter().collect::<Vec<_>>(), ["friend"]);
+        assert_eq!(tags.len(), 1);
+    }
+}
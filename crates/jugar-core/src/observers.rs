@@ -0,0 +1,215 @@
+//! Lifecycle observers: react when components come and go
+//!
+//! Physics bodies, looping sounds and UI widgets live outside the world but
+//! belong to an entity. Registering an observer with
+//! [`World::on_add`](crate::World::on_add),
+//! [`World::on_remove`](crate::World::on_remove) or
+//! [`World::on_despawn`](crate::World::on_despawn) lets the owning subsystem
+//! release them when the entity goes away, however it was removed.
+//!
+//! Observers can't touch the world directly; they get a [`Commands`] buffer
+//! instead, which the world applies as soon as the triggering call finishes.
+//!
+//! ```
+//! use std::sync::{Arc, Mutex};
+//! use jugar_core::{Position, World};
+//!
+//! let freed = Arc::new(Mutex::new(Vec::new()));
+//! let mut world = World::new();
+//! let log = Arc::clone(&freed);
+//! world.on_remove::<Position>(move |entity, _position, _commands| {
+//!     log.lock().unwrap().push(entity);
+//! });
+//!
+//! let e = world.spawn();
+//! world.add_component(e, Position::new(0.0, 0.0));
+//! world.despawn(e).unwrap();
+//! assert_eq!(*freed.lock().unwrap(), [e]);
+//! ```
+
+use core::any::{Any, TypeId};
+use core::fmt;
+use std::collections::HashMap;
+
+use crate::{Commands, Entity};
+
+/// Observer of one component type, type-erased
+type ComponentObserver = Box<dyn FnMut(Entity, &dyn Any, &mut Commands) + Send + Sync>;
+
+/// Observer of despawns
+type DespawnObserver = Box<dyn FnMut(Entity, &mut Commands) + Send + Sync>;
+
+/// Registered observers, called by the world
+#[derive(Default)]
+pub(crate) struct Observers {
+    added: HashMap<TypeId, Vec<ComponentObserver>>,
+    removed: HashMap<TypeId, Vec<ComponentObserver>>,
+    despawned: Vec<DespawnObserver>,
+}
+
+impl Observers {
+    /// Registers an observer of `T` being added
+    pub(crate) fn on_add<T, F>(&mut self, observer: F)
+    where
+        T: Any,
+        F: FnMut(Entity, &T, &mut Commands) + Send + Sync + 'static,
+    {
+        self.added
+            .entry(TypeId::of::<T>())
+            .or_default()
+            .push(erase(observer));
+    }
+
+    /// Registers an observer of `T` being removed
+    pub(crate) fn on_remove<T, F>(&mut self, observer: F)
+    where
+        T: Any,
+        F: FnMut(Entity, &T, &mut Commands) + Send + Sync + 'static,
+    {
+        self.removed
+            .entry(TypeId::of::<T>())
+            .or_default()
+            .push(erase(observer));
+    }
+
+    /// Registers an observer of despawns
+    pub(crate) fn on_despawn<F>(&mut self, observer: F)
+    where
+        F: FnMut(Entity, &mut Commands) + Send + Sync + 'static,
+    {
+        self.despawned.push(Box::new(observer));
+    }
+
+    /// Calls the observers of a component being added
+    pub(crate) fn added(
+        &mut self,
+        type_id: TypeId,
+        entity: Entity,
+        component: &dyn Any,
+        commands: &mut Commands,
+    ) {
+        for observer in self.added.get_mut(&type_id).into_iter().flatten() {
+            observer(entity, component, commands);
+        }
+    }
+
+    /// Calls the observers of a component being removed
+    pub(crate) fn removed(
+        &mut self,
+        type_id: TypeId,
+        entity: Entity,
+        component: &dyn Any,
+        commands: &mut Commands,
+    ) {
+        for observer in self.removed.get_mut(&type_id).into_iter().flatten() {
+            observer(entity, component, commands);
+        }
+    }
+
+    /// Calls the observers of an entity being despawned
+    pub(crate) fn despawned(&mut self, entity: Entity, commands: &mut Commands) {
+        for observer in &mut self.despawned {
+            observer(entity, commands);
+        }
+    }
+}
+
+impl fmt::Debug for Observers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let count = |map: &HashMap<TypeId, Vec<ComponentObserver>>| -> usize {
+            map.values().map(Vec::len).sum()
+        };
+        f.debug_struct("Observers")
+            .field("added", &count(&self.added))
+            .field("removed", &count(&self.removed))
+            .field("despawned", &self.despawned.len())
+            .finish()
+    }
+}
+
+/// Wraps a typed observer so it can be stored by [`TypeId`]
+fn erase<T, F>(mut observer: F) -> ComponentObserver
+where
+    T: Any,
+    F: FnMut(Entity, &T, &mut Commands) + Send + Sync + 'static,
+{
+    Box::new(move |entity, component, commands| {
+        if let Some(component) = component.downcast_ref::<T>() {
+            observer(entity, component, commands);
+        }
+    })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use alloc::sync::Arc;
+    use std::sync::Mutex;
+
+    use crate::{Name, Position, Velocity, World};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct HealthBar(crate::Entity);
+
+    #[test]
+    fn test_add_replace_and_remove_are_observed() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut world = World::new();
+        let added = Arc::clone(&log);
+        world.on_add::<Position>(move |_, position, _| {
+            added.lock().unwrap().push(format!("add {}", position.x));
+        });
+        let removed = Arc::clone(&log);
+        world.on_remove::<Position>(move |_, position, _| {
+            removed
+                .lock()
+                .unwrap()
+                .push(format!("remove {}", position.x));
+        });
+
+        let e = world.spawn();
+        world.add_component(e, Position::new(1.0, 0.0));
+        world.add_component(e, Position::new(2.0, 0.0));
+        world.add_component(e, Velocity::new(0.0, 0.0));
+        assert!(world.remove_component::<Position>(e));
+        assert!(!world.remove_component::<Position>(e));
+
+        assert_eq!(
+            *log.lock().unwrap(),
+            ["add 1", "remove 1", "add 2", "remove 2"]
+        );
+    }
+
+    #[test]
+    fn test_despawn_cleans_up_through_commands() {
+        let despawned = Arc::new(Mutex::new(Vec::new()));
+        let mut world = World::new();
+        // Removing a health bar despawns the widget entity it points to
+        world.on_remove::<HealthBar>(|_, bar, commands| commands.despawn(bar.0));
+        let log = Arc::clone(&despawned);
+        world.on_despawn(move |entity, _| log.lock().unwrap().push(entity));
+
+        let player = world.spawn();
+        let widget = world.spawn();
+        world.add_component(widget, Name::new("health bar"));
+        world.add_component(player, HealthBar(widget));
+
+        world.despawn(player).unwrap();
+        assert!(!world.contains(widget));
+        assert_eq!(world.find_by_name("health bar"), None);
+        assert_eq!(*despawned.lock().unwrap(), [player, widget]);
+    }
+
+    #[test]
+    fn test_observer_commands_chain_without_recursion() {
+        let mut world = World::new();
+        let chain: Vec<_> = (0..50).map(|_| world.spawn()).collect();
+        for pair in chain.windows(2) {
+            world.add_component(pair[0], HealthBar(pair[1]));
+        }
+        world.on_remove::<HealthBar>(|_, next, commands| commands.despawn(next.0));
+
+        world.despawn(chain[0]).unwrap();
+        assert_eq!(world.entity_count(), 0);
+    }
+}
@@ -0,0 +1,402 @@
+//! Benchmark regression checks.
+//!
+//! Criterion keeps each run's estimates under `target/criterion`. Save a
+//! baseline from the main branch, run the benches again on a change, and
+//! [`perf_report`] compares the two runs benchmark by benchmark. Probar
+//! (or the `perf_check` example) fails CI when the report has a
+//! regression past its threshold.
+//!
+//! ```text
+//! cargo bench --workspace -- --save-baseline main   # on main
+//! cargo bench --workspace                           # on the change
+//! cargo run -p jugar-core --example perf_check -- target/criterion main
+//! ```
+
+use core::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Slowdown allowed before a benchmark counts as regressed (percent)
+pub const DEFAULT_THRESHOLD_PERCENT: f64 = 10.0;
+
+/// Criterion's name for the most recent run
+pub const LATEST_RUN: &str = "new";
+
+/// Errors loading benchmark results
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum PerfError {
+    /// A results file or directory couldn't be read
+    #[error("Couldn't read {path}: {message}")]
+    Io {
+        /// Path that failed
+        path: PathBuf,
+        /// Underlying error
+        message: String,
+    },
+
+    /// An estimates file wasn't valid criterion output
+    #[error("Couldn't parse {path}: {message}")]
+    Parse {
+        /// Path that failed
+        path: PathBuf,
+        /// Underlying error
+        message: String,
+    },
+
+    /// No benchmark had results for the run
+    #[error("No '{run}' results under {dir}; run `cargo bench` first")]
+    NoResults {
+        /// Criterion output directory
+        dir: PathBuf,
+        /// Run (baseline) name
+        run: String,
+    },
+}
+
+/// Mean time of one benchmark in one run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchmarkEstimate {
+    /// Benchmark id, e.g. `physics_step/bodies/1000`
+    pub id: String,
+    /// Mean time per iteration in nanoseconds
+    pub mean_ns: f64,
+}
+
+/// Allowed slowdown per benchmark
+///
+/// Ids match overrides by prefix, longest prefix first, so a noisy group
+/// can get a looser limit without loosening the rest.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PerfThresholds {
+    default_percent: f64,
+    overrides: Vec<(String, f64)>,
+}
+
+impl PerfThresholds {
+    /// Thresholds with the same limit for every benchmark
+    #[must_use]
+    pub const fn new(default_percent: f64) -> Self {
+        Self {
+            default_percent,
+            overrides: Vec::new(),
+        }
+    }
+
+    /// Sets the limit for benchmarks whose id starts with `prefix`
+    #[must_use]
+    pub fn with(mut self, prefix: impl Into<String>, percent: f64) -> Self {
+        self.overrides.push((prefix.into(), percent));
+        self
+    }
+
+    /// Limit for one benchmark (percent)
+    #[must_use]
+    pub fn for_benchmark(&self, id: &str) -> f64 {
+        self.overrides
+            .iter()
+            .filter(|(prefix, _)| id.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map_or(self.default_percent, |(_, percent)| *percent)
+    }
+}
+
+impl Default for PerfThresholds {
+    fn default() -> Self {
+        Self::new(DEFAULT_THRESHOLD_PERCENT)
+    }
+}
+
+/// One benchmark in both runs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchmarkChange {
+    /// Benchmark id
+    pub id: String,
+    /// Mean time in the baseline (ns)
+    pub baseline_ns: f64,
+    /// Mean time in the latest run (ns)
+    pub current_ns: f64,
+    /// Allowed slowdown (percent)
+    pub threshold_percent: f64,
+}
+
+impl BenchmarkChange {
+    /// Change from the baseline in percent; positive is slower
+    #[must_use]
+    pub fn change_percent(&self) -> f64 {
+        if self.baseline_ns <= 0.0 {
+            return 0.0;
+        }
+        (self.current_ns - self.baseline_ns) / self.baseline_ns * 100.0
+    }
+
+    /// Returns true if the benchmark slowed down past its threshold
+    #[must_use]
+    pub fn is_regression(&self) -> bool {
+        self.change_percent() > self.threshold_percent
+    }
+}
+
+/// Comparison of a run against a baseline
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PerfReport {
+    /// Benchmarks in both runs, sorted by id
+    pub changes: Vec<BenchmarkChange>,
+    /// Benchmarks only in the latest run
+    pub added: Vec<String>,
+    /// Benchmarks only in the baseline
+    pub removed: Vec<String>,
+}
+
+impl PerfReport {
+    /// Compares two sets of estimates
+    #[must_use]
+    pub fn compare(
+        baseline: &[BenchmarkEstimate],
+        current: &[BenchmarkEstimate],
+        thresholds: &PerfThresholds,
+    ) -> Self {
+        let find = |set: &[BenchmarkEstimate], id: &str| {
+            set.iter()
+                .find(|estimate| estimate.id == id)
+                .map(|estimate| estimate.mean_ns)
+        };
+        let mut report = Self::default();
+        for estimate in current {
+            match find(baseline, &estimate.id) {
+                Some(baseline_ns) => report.changes.push(BenchmarkChange {
+                    id: estimate.id.clone(),
+                    baseline_ns,
+                    current_ns: estimate.mean_ns,
+                    threshold_percent: thresholds.for_benchmark(&estimate.id),
+                }),
+                None => report.added.push(estimate.id.clone()),
+            }
+        }
+        report.removed = baseline
+            .iter()
+            .filter(|estimate| find(current, &estimate.id).is_none())
+            .map(|estimate| estimate.id.clone())
+            .collect();
+        report.changes.sort_by(|a, b| a.id.cmp(&b.id));
+        report.added.sort();
+        report.removed.sort();
+        report
+    }
+
+    /// Benchmarks that slowed down past their threshold
+    pub fn regressions(&self) -> impl Iterator<Item = &BenchmarkChange> {
+        self.changes.iter().filter(|change| change.is_regression())
+    }
+
+    /// Returns true if nothing regressed
+    #[must_use]
+    pub fn is_ok(&self) -> bool {
+        self.regressions().next().is_none()
+    }
+}
+
+impl fmt::Display for PerfReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            writeln!(
+                f,
+                "{} {:<48} {:>12.1} ns -> {:>12.1} ns  {:+6.1}% (limit {:.0}%)",
+                if change.is_regression() {
+                    "FAIL"
+                } else {
+                    "ok  "
+                },
+                change.id,
+                change.baseline_ns,
+                change.current_ns,
+                change.change_percent(),
+                change.threshold_percent
+            )?;
+        }
+        for id in &self.added {
+            writeln!(f, "new  {id}")?;
+        }
+        for id in &self.removed {
+            writeln!(f, "gone {id}")?;
+        }
+        let regressed = self.regressions().count();
+        write!(
+            f,
+            "{} benchmarks, {regressed} regressed",
+            self.changes.len()
+        )
+    }
+}
+
+/// Criterion's `estimates.json`, reduced to what the report needs
+#[derive(Deserialize)]
+struct Estimates {
+    mean: PointEstimate,
+}
+
+#[derive(Deserialize)]
+struct PointEstimate {
+    point_estimate: f64,
+}
+
+/// Reads every benchmark's estimate for one run of criterion output
+///
+/// `run` is a baseline name from `--save-baseline`, or [`LATEST_RUN`].
+///
+/// # Errors
+///
+/// Returns [`PerfError`] if a file can't be read or parsed, or no
+/// benchmark has results for `run`.
+pub fn load_criterion(dir: &Path, run: &str) -> Result<Vec<BenchmarkEstimate>, PerfError> {
+    let mut estimates = Vec::new();
+    collect_estimates(dir, dir, run, &mut estimates)?;
+    if estimates.is_empty() {
+        return Err(PerfError::NoResults {
+            dir: dir.to_path_buf(),
+            run: run.to_string(),
+        });
+    }
+    estimates.sort_by(|a, b| a.id.cmp(&b.id));
+    Ok(estimates)
+}
+
+fn collect_estimates(
+    root: &Path,
+    dir: &Path,
+    run: &str,
+    out: &mut Vec<BenchmarkEstimate>,
+) -> Result<(), PerfError> {
+    let io = |path: &Path, e: std::io::Error| PerfError::Io {
+        path: path.to_path_buf(),
+        message: e.to_string(),
+    };
+    let file = dir.join(run).join("estimates.json");
+    if file.is_file() {
+        let text = fs::read_to_string(&file).map_err(|e| io(&file, e))?;
+        let estimates: Estimates = serde_json::from_str(&text).map_err(|e| PerfError::Parse {
+            path: file.clone(),
+            message: e.to_string(),
+        })?;
+        let id = dir
+            .strip_prefix(root)
+            .unwrap_or(dir)
+            .components()
+            .map(|part| part.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        out.push(BenchmarkEstimate {
+            id,
+            mean_ns: estimates.mean.point_estimate,
+        });
+        return Ok(());
+    }
+    for entry in fs::read_dir(dir).map_err(|e| io(dir, e))? {
+        let path = entry.map_err(|e| io(dir, e))?.path();
+        // Criterion's HTML reports sit next to the benchmarks
+        if path.is_dir() && path.file_name().is_some_and(|name| name != "report") {
+            collect_estimates(root, &path, run, out)?;
+        }
+    }
+    Ok(())
+}
+
+/// Compares criterion's latest run against a saved baseline
+///
+/// # Errors
+///
+/// Returns [`PerfError`] if either run's results can't be loaded.
+pub fn perf_report(
+    criterion_dir: &Path,
+    baseline: &str,
+    thresholds: &PerfThresholds,
+) -> Result<PerfReport, PerfError> {
+    let before = load_criterion(criterion_dir, baseline)?;
+    let after = load_criterion(criterion_dir, LATEST_RUN)?;
+    Ok(PerfReport::compare(&before, &after, thresholds))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn estimate(id: &str, mean_ns: f64) -> BenchmarkEstimate {
+        BenchmarkEstimate {
+            id: id.to_string(),
+            mean_ns,
+        }
+    }
+
+    #[test]
+    fn test_thresholds_use_longest_prefix() {
+        let thresholds = PerfThresholds::new(10.0)
+            .with("physics", 20.0)
+            .with("physics_step/bodies/10000", 30.0);
+        assert!((thresholds.for_benchmark("wfc/collapse") - 10.0).abs() < f64::EPSILON);
+        assert!((thresholds.for_benchmark("physics_step/bodies/100") - 20.0).abs() < f64::EPSILON);
+        assert!(
+            (thresholds.for_benchmark("physics_step/bodies/10000") - 30.0).abs() < f64::EPSILON
+        );
+    }
+
+    #[test]
+    fn test_compare_flags_only_slowdowns_past_threshold() {
+        let baseline = [
+            estimate("a", 100.0),
+            estimate("b", 100.0),
+            estimate("c", 100.0),
+            estimate("old", 5.0),
+        ];
+        let current = [
+            estimate("c", 50.0),
+            estimate("b", 111.0),
+            estimate("a", 109.0),
+            estimate("fresh", 5.0),
+        ];
+        let report = PerfReport::compare(&baseline, &current, &PerfThresholds::default());
+
+        let ids: Vec<&str> = report.changes.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, vec!["a", "b", "c"]);
+        let regressed: Vec<&str> = report.regressions().map(|c| c.id.as_str()).collect();
+        assert_eq!(regressed, vec!["b"]);
+        assert!(!report.is_ok());
+        assert_eq!(report.added, vec!["fresh"]);
+        assert_eq!(report.removed, vec!["old"]);
+
+        let text = report.to_string();
+        assert!(text.contains("FAIL b"));
+        assert!(text.ends_with("3 benchmarks, 1 regressed"));
+    }
+
+    #[test]
+    fn test_perf_report_reads_criterion_layout() {
+        let dir = std::env::temp_dir().join(format!("jugar-perf-{}", std::process::id()));
+        let write = |id: &str, run: &str, mean: f64| {
+            let path = dir.join(id).join(run);
+            fs::create_dir_all(&path).unwrap();
+            let json = format!(
+                r#"{{"mean":{{"confidence_interval":{{"confidence_level":0.95,"lower_bound":0.0,"upper_bound":0.0}},"point_estimate":{mean},"standard_error":1.0}}}}"#
+            );
+            fs::write(path.join("estimates.json"), json).unwrap();
+        };
+        write("physics_step/bodies/100", "main", 1000.0);
+        write("physics_step/bodies/100", "new", 1300.0);
+        write("wfc_collapse/32x32", "main", 500.0);
+        write("wfc_collapse/32x32", "new", 490.0);
+        fs::create_dir_all(dir.join("report")).unwrap();
+
+        let report = perf_report(&dir, "main", &PerfThresholds::default()).unwrap();
+        assert_eq!(report.changes.len(), 2);
+        let regressed: Vec<&str> = report.regressions().map(|c| c.id.as_str()).collect();
+        assert_eq!(regressed, vec!["physics_step/bodies/100"]);
+
+        assert!(matches!(
+            perf_report(&dir, "missing", &PerfThresholds::default()),
+            Err(PerfError::NoResults { .. })
+        ));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,219 @@
+//! Reusable per-frame buffers.
+//!
+//! Render commands and audio/caption events are produced fresh every frame.
+//! Building them in a new `Vec` each time costs an allocation (and several
+//! regrowths) per frame. A [`FramePool`] keeps its buffer between frames:
+//! [`reset`](FramePool::reset) empties it without freeing, and a buffer handed
+//! out with [`take`](FramePool::take) can be given back with
+//! [`recycle`](FramePool::recycle). [`PoolStats`] counts the allocations
+//! that still happen so the debug overlay can show they settle at zero.
+
+use serde::{Deserialize, Serialize};
+
+/// Allocation counters for a [`FramePool`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PoolStats {
+    /// Frames completed
+    pub frames: u64,
+    /// Times the buffer had to allocate or grow
+    pub allocations: u64,
+    /// Most items held in a single frame
+    pub peak: usize,
+}
+
+impl PoolStats {
+    /// Average allocations per completed frame
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn allocations_per_frame(&self) -> f32 {
+        if self.frames == 0 {
+            0.0
+        } else {
+            self.allocations as f32 / self.frames as f32
+        }
+    }
+}
+
+/// A buffer of per-frame items that keeps its allocation between frames
+#[derive(Debug, Clone)]
+pub struct FramePool<T> {
+    items: Vec<T>,
+    spare: Vec<T>,
+    stats: PoolStats,
+}
+
+impl<T> Default for FramePool<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> FramePool<T> {
+    /// Creates an empty pool
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            items: Vec::new(),
+            spare: Vec::new(),
+            stats: PoolStats {
+                frames: 0,
+                allocations: 0,
+                peak: 0,
+            },
+        }
+    }
+
+    /// Creates a pool with room for `capacity` items per frame
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            items: Vec::with_capacity(capacity),
+            ..Self::new()
+        }
+    }
+
+    /// Adds an item to this frame
+    pub fn push(&mut self, item: T) {
+        if self.items.len() == self.items.capacity() {
+            self.stats.allocations += 1;
+        }
+        self.items.push(item);
+    }
+
+    /// Adds several items to this frame
+    pub fn extend(&mut self, items: impl IntoIterator<Item = T>) {
+        for item in items {
+            self.push(item);
+        }
+    }
+
+    /// Items added this frame
+    #[must_use]
+    pub fn as_slice(&self) -> &[T] {
+        &self.items
+    }
+
+    /// Iterates items added this frame
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.items.iter()
+    }
+
+    /// Iterates items added this frame mutably
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.items.iter_mut()
+    }
+
+    /// Number of items this frame
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns true if nothing was added this frame
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Items the buffer holds without allocating
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.items.capacity()
+    }
+
+    /// Drops this frame's items without ending the frame
+    pub fn clear(&mut self) {
+        self.items.clear();
+    }
+
+    /// Ends the frame, emptying the buffer but keeping its allocation
+    pub fn reset(&mut self) {
+        self.end_frame();
+        self.items.clear();
+    }
+
+    /// Ends the frame, handing out its items
+    ///
+    /// The next frame fills the spare buffer, so pass the returned `Vec`
+    /// back to [`recycle`](Self::recycle) once it's consumed.
+    pub fn take(&mut self) -> Vec<T> {
+        self.end_frame();
+        let spare = core::mem::take(&mut self.spare);
+        core::mem::replace(&mut self.items, spare)
+    }
+
+    /// Returns a buffer from [`take`](Self::take) for reuse
+    pub fn recycle(&mut self, mut spent: Vec<T>) {
+        spent.clear();
+        if spent.capacity() > self.spare.capacity() {
+            self.spare = spent;
+        }
+    }
+
+    /// Allocation counters
+    #[must_use]
+    pub const fn stats(&self) -> PoolStats {
+        self.stats
+    }
+
+    fn end_frame(&mut self) {
+        self.stats.frames += 1;
+        self.stats.peak = self.stats.peak.max(self.items.len());
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::float_cmp)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reset_reuses_allocation() {
+        let mut pool = FramePool::new();
+        for frame in 0..100 {
+            for i in 0..50 {
+                pool.push(frame * i);
+            }
+            assert_eq!(pool.len(), 50);
+            pool.reset();
+        }
+        let stats = pool.stats();
+        assert_eq!(stats.frames, 100);
+        assert_eq!(stats.peak, 50);
+        // Only the first frame grows the buffer
+        assert!(stats.allocations <= 7);
+        assert!(stats.allocations_per_frame() < 0.1);
+    }
+
+    #[test]
+    fn test_take_and_recycle_ping_pong() {
+        let mut pool = FramePool::with_capacity(8);
+        let mut warmed_up = 0;
+        for frame in 0..10 {
+            pool.extend(0..8);
+            let taken = pool.take();
+            assert_eq!(taken.len(), 8);
+            assert!(pool.is_empty());
+            pool.recycle(taken);
+            if frame == 1 {
+                warmed_up = pool.stats().allocations;
+            }
+        }
+        // Once the spare buffer has grown, both buffers are reused
+        assert_eq!(pool.stats().allocations, warmed_up);
+        assert_eq!(pool.stats().frames, 10);
+
+        let mut leaky = FramePool::with_capacity(8);
+        for _ in 0..10 {
+            leaky.extend(0..8);
+            drop(leaky.take());
+        }
+        assert!(leaky.stats().allocations >= 9);
+    }
+
+    #[test]
+    fn test_empty_stats() {
+        let pool: FramePool<u8> = FramePool::default();
+        assert_eq!(pool.stats().allocations_per_frame(), 0.0);
+    }
+}
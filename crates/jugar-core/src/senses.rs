@@ -0,0 +1,58 @@
+//! Hooks that feed AI perception from other engine systems.
+//!
+//! Perception itself lives in `jugar-ai`, but what an agent can see depends
+//! on physics and what it can hear depends on audio. The shared types live
+//! here so those crates can feed perception without depending on the AI
+//! crate: physics implements [`LineOfSight`] with a raycast and audio emits a
+//! [`SoundStimulus`] for every sound it plays.
+
+use glam::Vec2;
+use serde::{Deserialize, Serialize};
+
+/// Obstacle query used to decide whether a sight line is blocked.
+pub trait LineOfSight {
+    /// Returns true if nothing blocks the segment from `from` to `to`.
+    fn is_clear(&self, from: Vec2, to: Vec2) -> bool;
+}
+
+impl<F> LineOfSight for F
+where
+    F: Fn(Vec2, Vec2) -> bool,
+{
+    fn is_clear(&self, from: Vec2, to: Vec2) -> bool {
+        self(from, to)
+    }
+}
+
+/// Line of sight with no obstacles.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OpenSight;
+
+impl LineOfSight for OpenSight {
+    fn is_clear(&self, _from: Vec2, _to: Vec2) -> bool {
+        true
+    }
+}
+
+/// A sound heard by agents, usually emitted when a sound is played.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SoundStimulus {
+    /// Identifier of the entity that made the sound
+    pub source: String,
+    /// World position of the sound
+    pub position: Vec2,
+    /// Loudness at the source (0.0 - 1.0 for normal sounds)
+    pub loudness: f32,
+}
+
+impl SoundStimulus {
+    /// Creates a new sound stimulus
+    #[must_use]
+    pub fn new(source: impl Into<String>, position: Vec2, loudness: f32) -> Self {
+        Self {
+            source: source.into(),
+            position,
+            loudness,
+        }
+    }
+}
@@ -0,0 +1,258 @@
+//! Struct-of-arrays storage for hot transform components.
+//!
+//! Moving thousands of entities one `Position`/`Velocity` lookup at a time
+//! leaves most of each SIMD register empty. [`TransformSoa`] copies them
+//! into flat `x`/`y`/`vx`/`vy` columns once per frame, runs the batch
+//! routines over whole columns, and writes the results back.
+//!
+//! The `batch_*` routines run on trueno vectors, so they pick up
+//! SSE/AVX/NEON natively and SIMD128 on `wasm32` without any hand-written
+//! lane loops.
+
+use trueno::Vector;
+
+use crate::{Entity, Position, Velocity, World};
+
+/// Moves every position by its velocity: `positions[i] += velocities[i] * dt`
+///
+/// Only the common prefix of the two slices is touched.
+pub fn batch_integrate(positions: &mut [f32], velocities: &[f32], dt: f32) {
+    let len = positions.len().min(velocities.len());
+    let positions = &mut positions[..len];
+    let moved = Vector::from_slice(&velocities[..len])
+        .scale(dt)
+        .and_then(|step| Vector::from_slice(positions).add(&step));
+    if let Ok(moved) = moved {
+        positions.copy_from_slice(moved.as_slice());
+    }
+}
+
+/// Adds a constant acceleration to every velocity: `velocities[i] += acceleration * dt`
+pub fn batch_accelerate(velocities: &mut [f32], acceleration: f32, dt: f32) {
+    let delta = Vector::from_slice(&vec![acceleration * dt; velocities.len()]);
+    if let Ok(accelerated) = Vector::from_slice(velocities).add(&delta) {
+        velocities.copy_from_slice(accelerated.as_slice());
+    }
+}
+
+/// Applies `out[i] = values[i] * scale + offset`
+///
+/// Only the common prefix of the two slices is written. This is the shape
+/// of a world-to-screen conversion along one axis.
+pub fn batch_affine(values: &[f32], scale: f32, offset: f32, out: &mut [f32]) {
+    let len = values.len().min(out.len());
+    let offsets = Vector::from_slice(&vec![offset; len]);
+    let mapped = Vector::from_slice(&values[..len])
+        .scale(scale)
+        .and_then(|scaled| scaled.add(&offsets));
+    if let Ok(mapped) = mapped {
+        out[..len].copy_from_slice(mapped.as_slice());
+    }
+}
+
+/// Positions and velocities of moving entities, one column per axis
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TransformSoa {
+    entities: Vec<Entity>,
+    x: Vec<f32>,
+    y: Vec<f32>,
+    vx: Vec<f32>,
+    vy: Vec<f32>,
+}
+
+impl TransformSoa {
+    /// Creates empty storage
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates empty storage with room for `capacity` entities
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            entities: Vec::with_capacity(capacity),
+            x: Vec::with_capacity(capacity),
+            y: Vec::with_capacity(capacity),
+            vx: Vec::with_capacity(capacity),
+            vy: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Appends an entity's transform
+    pub fn push(&mut self, entity: Entity, position: Position, velocity: Velocity) {
+        self.entities.push(entity);
+        self.x.push(position.x);
+        self.y.push(position.y);
+        self.vx.push(velocity.x);
+        self.vy.push(velocity.y);
+    }
+
+    /// Number of stored entities
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Returns true if nothing is stored
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    /// Removes every entity, keeping the allocations
+    pub fn clear(&mut self) {
+        self.entities.clear();
+        self.x.clear();
+        self.y.clear();
+        self.vx.clear();
+        self.vy.clear();
+    }
+
+    /// Stored entities, in column order
+    #[must_use]
+    pub fn entities(&self) -> &[Entity] {
+        &self.entities
+    }
+
+    /// X position column
+    #[must_use]
+    pub fn xs(&self) -> &[f32] {
+        &self.x
+    }
+
+    /// Y position column
+    #[must_use]
+    pub fn ys(&self) -> &[f32] {
+        &self.y
+    }
+
+    /// X velocity column
+    #[must_use]
+    pub fn vxs(&self) -> &[f32] {
+        &self.vx
+    }
+
+    /// Y velocity column
+    #[must_use]
+    pub fn vys(&self) -> &[f32] {
+        &self.vy
+    }
+
+    /// Position of the entity in row `index`
+    #[must_use]
+    pub fn position(&self, index: usize) -> Option<Position> {
+        Some(Position::new(*self.x.get(index)?, *self.y.get(index)?))
+    }
+
+    /// Velocity of the entity in row `index`
+    #[must_use]
+    pub fn velocity(&self, index: usize) -> Option<Velocity> {
+        Some(Velocity::new(*self.vx.get(index)?, *self.vy.get(index)?))
+    }
+
+    /// Replaces the contents with every world entity that has both a
+    /// [`Position`] and a [`Velocity`]
+    pub fn gather(&mut self, world: &World) {
+        self.clear();
+        for entity in world.entities() {
+            if let (Some(position), Some(velocity)) = (
+                world.get_component::<Position>(entity),
+                world.get_component::<Velocity>(entity),
+            ) {
+                self.push(entity, *position, *velocity);
+            }
+        }
+    }
+
+    /// Writes positions and velocities back to the world
+    ///
+    /// Entities despawned or stripped of a component since [`gather`](Self::gather)
+    /// are skipped.
+    pub fn scatter(&self, world: &mut World) {
+        for (index, &entity) in self.entities.iter().enumerate() {
+            if let Some(position) = world.get_component_mut::<Position>(entity) {
+                position.x = self.x[index];
+                position.y = self.y[index];
+            }
+            if let Some(velocity) = world.get_component_mut::<Velocity>(entity) {
+                velocity.x = self.vx[index];
+                velocity.y = self.vy[index];
+            }
+        }
+    }
+
+    /// Moves every entity by its velocity over `dt` seconds
+    pub fn integrate(&mut self, dt: f32) {
+        batch_integrate(&mut self.x, &self.vx, dt);
+        batch_integrate(&mut self.y, &self.vy, dt);
+    }
+
+    /// Accelerates every entity by `(ax, ay)` over `dt` seconds
+    pub fn accelerate(&mut self, ax: f32, ay: f32, dt: f32) {
+        batch_accelerate(&mut self.vx, ax, dt);
+        batch_accelerate(&mut self.vy, ay, dt);
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::cast_precision_loss)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_batch_routines_cover_remainder() {
+        let mut positions: Vec<f32> = (0..19).map(|i| i as f32).collect();
+        let velocities = vec![2.0; 19];
+        batch_integrate(&mut positions, &velocities, 0.5);
+        for (i, p) in positions.iter().enumerate() {
+            assert!((p - (i as f32 + 1.0)).abs() < 1e-6);
+        }
+
+        let mut velocities = vec![1.0; 11];
+        batch_accelerate(&mut velocities, -10.0, 0.1);
+        assert!(velocities.iter().all(|v| v.abs() < 1e-6));
+
+        let mut out = vec![0.0; 9];
+        batch_affine(&positions, 2.0, -1.0, &mut out);
+        assert!((out[8] - 17.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_integrate_uses_shorter_slice() {
+        let mut positions = vec![1.0, 1.0, 1.0];
+        batch_integrate(&mut positions, &[1.0], 1.0);
+        assert!((positions[0] - 2.0).abs() < 1e-6);
+        assert!((positions[2] - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_gather_integrate_scatter() {
+        let mut world = World::new();
+        let mut movers = Vec::new();
+        for i in 0..10_000 {
+            let entity = world.spawn();
+            world.add_component(entity, Position::new(i as f32, 0.0));
+            world.add_component(entity, Velocity::new(1.0, -2.0));
+            movers.push(entity);
+        }
+        let still = world.spawn();
+        world.add_component(still, Position::new(5.0, 5.0));
+
+        let mut soa = TransformSoa::with_capacity(world.entity_count());
+        soa.gather(&world);
+        assert_eq!(soa.len(), 10_000);
+
+        soa.accelerate(0.0, 20.0, 0.1);
+        soa.integrate(0.5);
+        soa.scatter(&mut world);
+
+        let last = world.get_component::<Position>(movers[9_999]).unwrap();
+        assert!((last.x - 9_999.5).abs() < 1e-3);
+        assert!(last.y.abs() < 1e-6);
+        let velocity = world.get_component::<Velocity>(movers[0]).unwrap();
+        assert!(velocity.y.abs() < 1e-6);
+        let unmoved = world.get_component::<Position>(still).unwrap();
+        assert!((unmoved.x - 5.0).abs() < f32::EPSILON);
+    }
+}
@@ -0,0 +1,433 @@
+//! Uniform spatial hash grid for fast proximity queries
+//!
+//! [`SpatialGrid`] buckets axis-aligned bounds into square cells so that
+//! range and nearest-neighbor queries only touch nearby items. It is shared by
+//! the physics broadphase, AI perception, UI hit-testing and particles.
+//! Query results are sorted by key, so they are deterministic regardless of
+//! hash map ordering.
+//!
+//! Items spanning more than [`MAX_ITEM_CELLS`] cells are not bucketed; they
+//! are kept in a separate list and checked by every query, so one huge body
+//! (a level floor, a world bounds sensor) costs one entry instead of
+//! thousands of cells.
+
+use std::collections::HashMap;
+
+use glam::Vec2;
+
+/// Most cells an item may cover before it is kept in the large list
+pub const MAX_ITEM_CELLS: i64 = 64;
+
+/// Key of an item stored in a [`SpatialGrid`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GridKey(pub u32);
+
+#[derive(Debug, Clone)]
+struct GridEntry<T> {
+    value: T,
+    min: Vec2,
+    max: Vec2,
+}
+
+/// Spatial hash grid storing values with axis-aligned bounds
+#[derive(Debug, Clone)]
+pub struct SpatialGrid<T> {
+    cell_size: f32,
+    cells: HashMap<(i32, i32), Vec<GridKey>>,
+    entries: Vec<Option<GridEntry<T>>>,
+    large: Vec<GridKey>,
+    free: Vec<u32>,
+    len: usize,
+}
+
+impl<T> SpatialGrid<T> {
+    /// Creates an empty grid with the given cell size
+    #[must_use]
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size: cell_size.max(f32::EPSILON),
+            cells: HashMap::new(),
+            entries: Vec::new(),
+            large: Vec::new(),
+            free: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// Returns the cell size
+    #[must_use]
+    pub const fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    /// Number of stored items
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the grid is empty
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Removes all items
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.entries.clear();
+        self.large.clear();
+        self.free.clear();
+        self.len = 0;
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn cell_of(&self, point: Vec2) -> (i32, i32) {
+        (
+            (point.x / self.cell_size).floor() as i32,
+            (point.y / self.cell_size).floor() as i32,
+        )
+    }
+
+    fn cell_range(&self, min: Vec2, max: Vec2) -> impl Iterator<Item = (i32, i32)> {
+        let (x0, y0) = self.cell_of(min);
+        let (x1, y1) = self.cell_of(max);
+        (x0..=x1).flat_map(move |x| (y0..=y1).map(move |y| (x, y)))
+    }
+
+    /// Number of cells covered by the bounds `min..max`
+    fn cell_count(&self, min: Vec2, max: Vec2) -> i64 {
+        let (x0, y0) = self.cell_of(min);
+        let (x1, y1) = self.cell_of(max);
+        (i64::from(x1) - i64::from(x0) + 1).saturating_mul(i64::from(y1) - i64::from(y0) + 1)
+    }
+
+    /// Inserts a value covering the bounds `min..max`
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn insert(&mut self, value: T, min: Vec2, max: Vec2) -> GridKey {
+        let (min, max) = (min.min(max), min.max(max));
+        let entry = Some(GridEntry { value, min, max });
+        let key = if let Some(slot) = self.free.pop() {
+            self.entries[slot as usize] = entry;
+            GridKey(slot)
+        } else {
+            self.entries.push(entry);
+            GridKey((self.entries.len() - 1) as u32)
+        };
+        self.link(key, min, max);
+        self.len += 1;
+        key
+    }
+
+    /// Inserts a value at a single point
+    pub fn insert_point(&mut self, value: T, point: Vec2) -> GridKey {
+        self.insert(value, point, point)
+    }
+
+    fn link(&mut self, key: GridKey, min: Vec2, max: Vec2) {
+        if self.cell_count(min, max) > MAX_ITEM_CELLS {
+            self.large.push(key);
+            return;
+        }
+        for cell in self.cell_range(min, max) {
+            self.cells.entry(cell).or_default().push(key);
+        }
+    }
+
+    fn unlink(&mut self, key: GridKey, min: Vec2, max: Vec2) {
+        if self.cell_count(min, max) > MAX_ITEM_CELLS {
+            self.large.retain(|k| *k != key);
+            return;
+        }
+        for cell in self.cell_range(min, max) {
+            if let Some(keys) = self.cells.get_mut(&cell) {
+                keys.retain(|k| *k != key);
+                if keys.is_empty() {
+                    let _ = self.cells.remove(&cell);
+                }
+            }
+        }
+    }
+
+    /// Removes an item, returning its value
+    pub fn remove(&mut self, key: GridKey) -> Option<T> {
+        let entry = self.entries.get_mut(key.0 as usize)?.take()?;
+        self.unlink(key, entry.min, entry.max);
+        self.free.push(key.0);
+        self.len -= 1;
+        Some(entry.value)
+    }
+
+    /// Moves an item to new bounds; returns false if the key is unknown
+    pub fn update(&mut self, key: GridKey, min: Vec2, max: Vec2) -> bool {
+        let (min, max) = (min.min(max), min.max(max));
+        let Some(Some(entry)) = self.entries.get_mut(key.0 as usize) else {
+            return false;
+        };
+        let (old_min, old_max) = (entry.min, entry.max);
+        entry.min = min;
+        entry.max = max;
+        if self.cell_of(old_min) != self.cell_of(min) || self.cell_of(old_max) != self.cell_of(max)
+        {
+            self.unlink(key, old_min, old_max);
+            self.link(key, min, max);
+        }
+        true
+    }
+
+    /// Gets a stored value
+    #[must_use]
+    pub fn get(&self, key: GridKey) -> Option<&T> {
+        self.entries
+            .get(key.0 as usize)
+            .and_then(Option::as_ref)
+            .map(|e| &e.value)
+    }
+
+    /// Gets the (min, max) bounds of a stored item
+    #[must_use]
+    pub fn bounds(&self, key: GridKey) -> Option<(Vec2, Vec2)> {
+        self.entries
+            .get(key.0 as usize)
+            .and_then(Option::as_ref)
+            .map(|e| (e.min, e.max))
+    }
+
+    fn candidates(&self, min: Vec2, max: Vec2) -> Vec<GridKey> {
+        let mut keys = self.large.clone();
+        // A query wider than the occupied cells walks those instead
+        if self.cell_count(min, max) > i64::try_from(self.cells.len()).unwrap_or(i64::MAX) {
+            let ((x0, y0), (x1, y1)) = (self.cell_of(min), self.cell_of(max));
+            keys.extend(
+                self.cells
+                    .iter()
+                    .filter(|(&(x, y), _)| (x0..=x1).contains(&x) && (y0..=y1).contains(&y))
+                    .flat_map(|(_, cell)| cell.iter().copied()),
+            );
+        } else {
+            keys.extend(
+                self.cell_range(min, max)
+                    .filter_map(|cell| self.cells.get(&cell))
+                    .flatten()
+                    .copied(),
+            );
+        }
+        keys.sort_unstable();
+        keys.dedup();
+        keys
+    }
+
+    fn entry(&self, key: GridKey) -> Option<&GridEntry<T>> {
+        self.entries.get(key.0 as usize).and_then(Option::as_ref)
+    }
+
+    /// Items whose bounds overlap `min..max`, sorted by key
+    #[must_use]
+    pub fn query_aabb(&self, min: Vec2, max: Vec2) -> Vec<(GridKey, &T)> {
+        let (min, max) = (min.min(max), min.max(max));
+        self.candidates(min, max)
+            .into_iter()
+            .filter_map(|key| self.entry(key).map(|e| (key, e)))
+            .filter(|(_, e)| e.min.cmple(max).all() && e.max.cmpge(min).all())
+            .map(|(key, e)| (key, &e.value))
+            .collect()
+    }
+
+    /// Items whose bounds intersect a circle, sorted by key
+    #[must_use]
+    pub fn query_circle(&self, center: Vec2, radius: f32) -> Vec<(GridKey, &T)> {
+        let extent = Vec2::splat(radius.abs());
+        let radius_sq = radius * radius;
+        self.candidates(center - extent, center + extent)
+            .into_iter()
+            .filter_map(|key| self.entry(key).map(|e| (key, e)))
+            .filter(|(_, e)| distance_sq_to_bounds(center, e.min, e.max) <= radius_sq)
+            .map(|(key, e)| (key, &e.value))
+            .collect()
+    }
+
+    /// Nearest item to `point` (by distance to its bounds) within
+    /// `max_distance`
+    #[must_use]
+    pub fn nearest(&self, point: Vec2, max_distance: f32) -> Option<(GridKey, &T)> {
+        if self.is_empty() {
+            return None;
+        }
+        let max_sq = max_distance * max_distance;
+        let center = self.cell_of(point);
+        let max_ring = self.max_ring(center, max_distance);
+        let mut best: Option<(f32, GridKey)> = None;
+        let consider = |best: &mut Option<(f32, GridKey)>, key: GridKey| {
+            let Some(entry) = self.entry(key) else {
+                return;
+            };
+            let d = distance_sq_to_bounds(point, entry.min, entry.max);
+            let better = match *best {
+                None => true,
+                Some((bd, bk)) => d < bd || (d <= bd && key < bk),
+            };
+            if d <= max_sq && better {
+                *best = Some((d, key));
+            }
+        };
+
+        for &key in &self.large {
+            consider(&mut best, key);
+        }
+        for ring in 0..=max_ring {
+            for cell in ring_cells(center, ring) {
+                for &key in self.cells.get(&cell).into_iter().flatten() {
+                    consider(&mut best, key);
+                }
+            }
+            // Anything in further rings is at least `ring` cells away
+            if let Some((d, _)) = best {
+                let reach = f64::from(ring) * f64::from(self.cell_size);
+                if f64::from(d) <= reach * reach {
+                    break;
+                }
+            }
+        }
+
+        best.and_then(|(_, key)| self.get(key).map(|v| (key, v)))
+    }
+
+    /// Number of rings that must be searched around `center`
+    #[allow(clippy::cast_possible_truncation)]
+    fn max_ring(&self, center: (i32, i32), max_distance: f32) -> i32 {
+        let by_distance = if max_distance.is_finite() {
+            (max_distance / self.cell_size).ceil() as i32 + 1
+        } else {
+            i32::MAX
+        };
+        let by_occupancy = self
+            .cells
+            .keys()
+            .map(|&(x, y)| (x - center.0).abs().max((y - center.1).abs()))
+            .max()
+            .unwrap_or(0);
+        by_distance.min(by_occupancy)
+    }
+}
+
+impl<T> Default for SpatialGrid<T> {
+    fn default() -> Self {
+        Self::new(64.0)
+    }
+}
+
+/// Cells on the square ring `ring` cells away from `center`
+fn ring_cells(center: (i32, i32), ring: i32) -> Vec<(i32, i32)> {
+    if ring == 0 {
+        return vec![center];
+    }
+    let (cx, cy) = center;
+    let mut cells = Vec::with_capacity(usize::try_from(ring).unwrap_or(0).saturating_mul(8));
+    for x in (cx - ring)..=(cx + ring) {
+        cells.push((x, cy - ring));
+        cells.push((x, cy + ring));
+    }
+    for y in (cy - ring + 1)..(cy + ring) {
+        cells.push((cx - ring, y));
+        cells.push((cx + ring, y));
+    }
+    cells
+}
+
+/// Squared distance from a point to an axis-aligned box (0 if inside)
+fn distance_sq_to_bounds(point: Vec2, min: Vec2, max: Vec2) -> f32 {
+    point.distance_squared(point.clamp(min, max))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn values<T: Copy>(results: &[(GridKey, &T)]) -> Vec<T> {
+        results.iter().map(|(_, v)| **v).collect()
+    }
+
+    #[test]
+    fn test_insert_and_query_aabb() {
+        let mut grid = SpatialGrid::new(10.0);
+        let _ = grid.insert_point('a', Vec2::new(5.0, 5.0));
+        let _ = grid.insert_point('b', Vec2::new(25.0, 5.0));
+        let _ = grid.insert('c', Vec2::new(-5.0, -5.0), Vec2::new(15.0, 15.0));
+        let hits = grid.query_aabb(Vec2::ZERO, Vec2::new(10.0, 10.0));
+        assert_eq!(values(&hits), vec!['a', 'c']);
+        assert_eq!(grid.len(), 3);
+    }
+
+    #[test]
+    fn test_remove_and_reuse_key() {
+        let mut grid = SpatialGrid::new(10.0);
+        let a = grid.insert_point(1, Vec2::ZERO);
+        assert_eq!(grid.remove(a), Some(1));
+        assert_eq!(grid.remove(a), None);
+        assert!(grid
+            .query_aabb(Vec2::splat(-1.0), Vec2::splat(1.0))
+            .is_empty());
+        let b = grid.insert_point(2, Vec2::ZERO);
+        assert_eq!(a, b);
+        assert_eq!(grid.get(b), Some(&2));
+    }
+
+    #[test]
+    fn test_update_moves_item() {
+        let mut grid = SpatialGrid::new(10.0);
+        let key = grid.insert_point("bee", Vec2::ZERO);
+        assert!(grid.update(key, Vec2::new(50.0, 50.0), Vec2::new(51.0, 51.0)));
+        assert!(grid.query_circle(Vec2::ZERO, 5.0).is_empty());
+        assert_eq!(grid.query_circle(Vec2::new(50.0, 50.0), 1.0).len(), 1);
+    }
+
+    #[test]
+    fn test_query_circle_excludes_corners() {
+        let mut grid = SpatialGrid::new(10.0);
+        let _ = grid.insert_point(1, Vec2::new(7.0, 7.0));
+        let _ = grid.insert_point(2, Vec2::new(3.0, 0.0));
+        let hits = grid.query_circle(Vec2::ZERO, 8.0);
+        assert_eq!(values(&hits), vec![2]);
+    }
+
+    #[test]
+    fn test_nearest() {
+        let mut grid = SpatialGrid::new(4.0);
+        let _ = grid.insert_point("far", Vec2::new(100.0, 0.0));
+        let _ = grid.insert_point("near", Vec2::new(9.0, 0.0));
+        let _ = grid.insert_point("nearer", Vec2::new(0.0, -6.0));
+        let (_, value) = grid.nearest(Vec2::ZERO, f32::INFINITY).unwrap();
+        assert_eq!(*value, "nearer");
+        assert!(grid.nearest(Vec2::ZERO, 5.0).is_none());
+        let (_, value) = grid.nearest(Vec2::new(99.0, 0.0), 10.0).unwrap();
+        assert_eq!(*value, "far");
+    }
+
+    #[test]
+    fn test_huge_items_skip_the_cells() {
+        let mut grid = SpatialGrid::new(4.0);
+        let floor = grid.insert("floor", Vec2::new(-1.0e6, -1.0), Vec2::new(1.0e6, 0.0));
+        let _ = grid.insert_point("crate", Vec2::new(2.0, 1.0));
+        assert_eq!(grid.cells.len(), 1);
+        assert_eq!(grid.large, vec![floor]);
+
+        let hits = grid.query_aabb(Vec2::new(0.0, -0.5), Vec2::new(3.0, 1.5));
+        assert_eq!(values(&hits), vec!["floor", "crate"]);
+        let everything = grid.query_aabb(Vec2::splat(-1.0e9), Vec2::splat(1.0e9));
+        assert_eq!(everything.len(), 2);
+        let (_, value) = grid.nearest(Vec2::new(500.0, -3.0), 10.0).unwrap();
+        assert_eq!(*value, "floor");
+
+        assert!(grid.update(floor, Vec2::ZERO, Vec2::ONE));
+        assert!(grid.large.is_empty());
+        assert_eq!(grid.remove(floor), Some("floor"));
+        assert_eq!(grid.cells.len(), 1);
+    }
+
+    #[test]
+    fn test_nearest_empty() {
+        let grid: SpatialGrid<u8> = SpatialGrid::new(1.0);
+        assert!(grid.nearest(Vec2::ZERO, 10.0).is_none());
+    }
+}
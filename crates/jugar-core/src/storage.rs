@@ -0,0 +1,145 @@
+//! Key-value persistence for small save data.
+//!
+//! Games and engine services persist settings, progress and ledgers through
+//! the [`Storage`] trait instead of touching the platform directly. The
+//! browser host backs it with the Web Storage API; tests and native tools
+//! use [`MemoryStorage`]. Values are strings (usually JSON) so every
+//! backend can hold them.
+
+use alloc::collections::BTreeMap;
+use core::fmt;
+
+use crate::{CoreError, Result};
+
+/// Persistent string key-value store
+pub trait Storage: fmt::Debug {
+    /// Reads a value
+    fn get(&self, key: &str) -> Option<String>;
+
+    /// Writes a value, replacing any previous one
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CoreError::StorageFull`] if the backend's quota would be
+    /// exceeded.
+    fn set(&mut self, key: &str, value: &str) -> Result<()>;
+
+    /// Deletes a value; returns true if it existed
+    fn remove(&mut self, key: &str) -> bool;
+
+    /// All keys, sorted
+    fn keys(&self) -> Vec<String>;
+
+    /// Keys starting with a prefix (e.g. one game's namespace)
+    fn keys_with_prefix(&self, prefix: &str) -> Vec<String> {
+        self.keys()
+            .into_iter()
+            .filter(|key| key.starts_with(prefix))
+            .collect()
+    }
+}
+
+/// In-memory storage with an optional byte quota
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MemoryStorage {
+    entries: BTreeMap<String, String>,
+    quota: Option<usize>,
+}
+
+impl MemoryStorage {
+    /// Creates empty, unlimited storage
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limits total key + value bytes (Web Storage allows about 5 MB)
+    #[must_use]
+    pub const fn with_quota(mut self, bytes: usize) -> Self {
+        self.quota = Some(bytes);
+        self
+    }
+
+    /// Bytes used by keys and values
+    #[must_use]
+    pub fn used_bytes(&self) -> usize {
+        self.entries
+            .iter()
+            .map(|(key, value)| key.len() + value.len())
+            .sum()
+    }
+
+    /// Number of stored values
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if nothing is stored
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl Storage for MemoryStorage {
+    fn get(&self, key: &str) -> Option<String> {
+        self.entries.get(key).cloned()
+    }
+
+    fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        if let Some(quota) = self.quota {
+            let replaced = self.entries.get(key).map_or(0, |old| key.len() + old.len());
+            if self.used_bytes() - replaced + key.len() + value.len() > quota {
+                return Err(CoreError::StorageFull {
+                    key: key.to_string(),
+                });
+            }
+        }
+        let _ = self.entries.insert(key.to_string(), value.to_string());
+        Ok(())
+    }
+
+    fn remove(&mut self, key: &str) -> bool {
+        self.entries.remove(key).is_some()
+    }
+
+    fn keys(&self) -> Vec<String> {
+        self.entries.keys().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_get_remove() {
+        let mut storage = MemoryStorage::new();
+        storage.set("game.volume", "0.8").unwrap();
+        storage.set("game.level", "3").unwrap();
+        storage.set("other", "x").unwrap();
+        assert_eq!(storage.get("game.volume").as_deref(), Some("0.8"));
+        assert_eq!(
+            storage.keys_with_prefix("game."),
+            vec!["game.level", "game.volume"]
+        );
+        assert!(storage.remove("other"));
+        assert!(!storage.remove("other"));
+        assert_eq!(storage.len(), 2);
+    }
+
+    #[test]
+    fn test_quota() {
+        let mut storage = MemoryStorage::new().with_quota(10);
+        storage.set("k", "12345").unwrap();
+        // Replacing a value only counts the difference
+        storage.set("k", "123456789").unwrap();
+        assert!(matches!(
+            storage.set("k2", "1"),
+            Err(CoreError::StorageFull { .. })
+        ));
+        assert_eq!(storage.used_bytes(), 10);
+    }
+}
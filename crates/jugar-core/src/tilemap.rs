@@ -0,0 +1,194 @@
+//! Chunked tile grids.
+//!
+//! A [`TileMap`] stores tile ids in fixed-size square [`TileChunk`]s so
+//! renderers can cull and rebuild one chunk at a time and empty areas cost
+//! nothing. Id 0 means "no tile".
+
+use alloc::collections::BTreeMap;
+
+use glam::Vec2;
+use serde::{Deserialize, Serialize};
+
+use crate::Rect;
+
+/// Tiles per chunk side
+pub const TILE_CHUNK_SIZE: u32 = 16;
+
+/// Tile id; 0 is empty
+pub type TileGid = u32;
+
+/// A square block of tiles
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TileChunk {
+    /// Chunk column and row
+    pub coord: (u32, u32),
+    tiles: Vec<TileGid>,
+}
+
+impl TileChunk {
+    fn new(coord: (u32, u32)) -> Self {
+        Self {
+            coord,
+            tiles: vec![0; (TILE_CHUNK_SIZE * TILE_CHUNK_SIZE) as usize],
+        }
+    }
+
+    /// Tile at a position inside the chunk
+    #[must_use]
+    pub fn get(&self, x: u32, y: u32) -> TileGid {
+        if x >= TILE_CHUNK_SIZE || y >= TILE_CHUNK_SIZE {
+            return 0;
+        }
+        self.tiles[(y * TILE_CHUNK_SIZE + x) as usize]
+    }
+
+    /// Tiles row by row
+    #[must_use]
+    pub fn tiles(&self) -> &[TileGid] {
+        &self.tiles
+    }
+
+    /// Returns true if every tile is empty
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.tiles.iter().all(|&gid| gid == 0)
+    }
+}
+
+/// A grid of tiles stored in chunks
+#[derive(Debug, Clone, PartialEq)]
+pub struct TileMap {
+    /// Width in tiles
+    pub width: u32,
+    /// Height in tiles
+    pub height: u32,
+    /// Size of one tile in world units
+    pub tile_size: Vec2,
+    /// Chunks keyed by (row, column)
+    chunks: BTreeMap<(u32, u32), TileChunk>,
+}
+
+impl TileMap {
+    /// Creates an empty map
+    #[must_use]
+    pub const fn new(width: u32, height: u32, tile_size: Vec2) -> Self {
+        Self {
+            width,
+            height,
+            tile_size,
+            chunks: BTreeMap::new(),
+        }
+    }
+
+    /// Tile at a grid position (0 if empty or outside)
+    #[must_use]
+    pub fn get(&self, x: u32, y: u32) -> TileGid {
+        self.chunks
+            .get(&(y / TILE_CHUNK_SIZE, x / TILE_CHUNK_SIZE))
+            .map_or(0, |chunk| {
+                chunk.get(x % TILE_CHUNK_SIZE, y % TILE_CHUNK_SIZE)
+            })
+    }
+
+    /// Sets a tile; positions outside the map are ignored
+    pub fn set(&mut self, x: u32, y: u32, gid: TileGid) {
+        if x >= self.width || y >= self.height {
+            return;
+        }
+        let coord = (x / TILE_CHUNK_SIZE, y / TILE_CHUNK_SIZE);
+        let key = (coord.1, coord.0);
+        if gid == 0 && !self.chunks.contains_key(&key) {
+            return;
+        }
+        let chunk = self
+            .chunks
+            .entry(key)
+            .or_insert_with(|| TileChunk::new(coord));
+        let local = (y % TILE_CHUNK_SIZE) * TILE_CHUNK_SIZE + x % TILE_CHUNK_SIZE;
+        chunk.tiles[local as usize] = gid;
+    }
+
+    /// Allocated chunks in row-major chunk order
+    pub fn chunks(&self) -> impl Iterator<Item = &TileChunk> {
+        self.chunks.values()
+    }
+
+    /// Number of allocated chunks
+    #[must_use]
+    pub fn chunk_count(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// Every non-empty tile as `(x, y, gid)`
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn tiles(&self) -> impl Iterator<Item = (u32, u32, TileGid)> + '_ {
+        self.chunks.values().flat_map(|chunk| {
+            let (cx, cy) = chunk.coord;
+            chunk
+                .tiles
+                .iter()
+                .enumerate()
+                .filter(|(_, &gid)| gid != 0)
+                .map(move |(i, &gid)| {
+                    let i = i as u32;
+                    (
+                        cx * TILE_CHUNK_SIZE + i % TILE_CHUNK_SIZE,
+                        cy * TILE_CHUNK_SIZE + i / TILE_CHUNK_SIZE,
+                        gid,
+                    )
+                })
+        })
+    }
+
+    /// World-space rectangle of a tile
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn tile_rect(&self, x: u32, y: u32) -> Rect {
+        Rect::new(
+            x as f32 * self.tile_size.x,
+            y as f32 * self.tile_size.y,
+            self.tile_size.x,
+            self.tile_size.y,
+        )
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_get_across_chunks() {
+        let mut map = TileMap::new(40, 20, Vec2::splat(16.0));
+        map.set(0, 0, 1);
+        map.set(17, 3, 2);
+        map.set(39, 19, 3);
+        map.set(40, 0, 9);
+
+        assert_eq!(map.get(17, 3), 2);
+        assert_eq!(map.get(5, 5), 0);
+        assert_eq!(map.get(40, 0), 0);
+        assert_eq!(map.chunk_count(), 3);
+        assert_eq!(
+            map.tiles().collect::<Vec<_>>(),
+            vec![(0, 0, 1), (17, 3, 2), (39, 19, 3)]
+        );
+    }
+
+    #[test]
+    fn test_empty_tiles_do_not_allocate() {
+        let mut map = TileMap::new(64, 64, Vec2::ONE);
+        map.set(50, 50, 0);
+        assert_eq!(map.chunk_count(), 0);
+        map.set(1, 1, 4);
+        map.set(1, 1, 0);
+        assert!(map.chunks().all(TileChunk::is_empty));
+    }
+
+    #[test]
+    fn test_tile_rect() {
+        let map = TileMap::new(4, 4, Vec2::new(16.0, 8.0));
+        assert_eq!(map.tile_rect(2, 3), Rect::new(32.0, 24.0, 16.0, 8.0));
+    }
+}
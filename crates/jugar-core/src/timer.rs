@@ -0,0 +1,272 @@
+//! Stopwatches and countdowns.
+//!
+//! [`GameTimer`] replaces the hand-written `time_left -= dt` found in most
+//! kid games ("survive 60 seconds", "collect 10 stars before time runs
+//! out"). It counts up or down, pauses, records laps, and publishes a
+//! [`TimerEvent`] on the event bus when a countdown reaches zero. A timer
+//! can live in a game struct or be attached to an entity as a component.
+
+use serde::{Deserialize, Serialize};
+
+use crate::Events;
+
+/// Which way a timer counts
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum TimerMode {
+    /// Counts up from zero with no end
+    Stopwatch,
+    /// Counts down from a duration (seconds) and finishes at zero
+    Countdown(f32),
+}
+
+/// Something a timer announces on the event bus
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TimerEvent {
+    /// A countdown reached zero
+    Finished {
+        /// Timer name
+        timer: String,
+    },
+    /// A lap was recorded
+    Lap {
+        /// Timer name
+        timer: String,
+        /// Lap number, starting at 1
+        number: u32,
+        /// Seconds since the previous lap (or the start)
+        split: f32,
+    },
+}
+
+/// A stopwatch or countdown driven by frame time
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameTimer {
+    name: String,
+    mode: TimerMode,
+    elapsed: f32,
+    running: bool,
+    finished: bool,
+    laps: Vec<f32>,
+}
+
+impl GameTimer {
+    /// Creates a running stopwatch
+    #[must_use]
+    pub fn stopwatch(name: impl Into<String>) -> Self {
+        Self::new(name, TimerMode::Stopwatch)
+    }
+
+    /// Creates a running countdown; negative or non-finite durations are zero
+    #[must_use]
+    pub fn countdown(name: impl Into<String>, seconds: f32) -> Self {
+        let seconds = if seconds.is_finite() {
+            seconds.max(0.0)
+        } else {
+            0.0
+        };
+        Self::new(name, TimerMode::Countdown(seconds))
+    }
+
+    fn new(name: impl Into<String>, mode: TimerMode) -> Self {
+        Self {
+            name: name.into(),
+            mode,
+            elapsed: 0.0,
+            running: true,
+            finished: false,
+            laps: Vec::new(),
+        }
+    }
+
+    /// Creates the timer paused
+    #[must_use]
+    pub const fn paused(mut self) -> Self {
+        self.running = false;
+        self
+    }
+
+    /// Timer name, used in its events
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Which way the timer counts
+    #[must_use]
+    pub const fn mode(&self) -> TimerMode {
+        self.mode
+    }
+
+    /// Advances the timer, sending [`TimerEvent::Finished`] when a
+    /// countdown reaches zero
+    pub fn update(&mut self, dt: f32, events: &mut Events<TimerEvent>) {
+        if !self.running || self.finished || !dt.is_finite() || dt <= 0.0 {
+            return;
+        }
+        self.elapsed += dt;
+        if let TimerMode::Countdown(duration) = self.mode {
+            if self.elapsed >= duration {
+                self.elapsed = duration;
+                self.finished = true;
+                self.running = false;
+                events.send(TimerEvent::Finished {
+                    timer: self.name.clone(),
+                });
+            }
+        }
+    }
+
+    /// Stops counting until [`GameTimer::resume`]
+    pub fn pause(&mut self) {
+        self.running = false;
+    }
+
+    /// Continues counting (does nothing once finished)
+    pub fn resume(&mut self) {
+        self.running = !self.finished;
+    }
+
+    /// Back to the start, running, with no laps
+    pub fn reset(&mut self) {
+        self.elapsed = 0.0;
+        self.running = true;
+        self.finished = false;
+        self.laps.clear();
+    }
+
+    /// Records a lap and sends [`TimerEvent::Lap`]; returns the split
+    pub fn lap(&mut self, events: &mut Events<TimerEvent>) -> f32 {
+        let previous = self.laps.last().copied().unwrap_or(0.0);
+        let split = self.elapsed - previous;
+        self.laps.push(self.elapsed);
+        events.send(TimerEvent::Lap {
+            timer: self.name.clone(),
+            number: u32::try_from(self.laps.len()).unwrap_or(u32::MAX),
+            split,
+        });
+        split
+    }
+
+    /// Elapsed time at each lap, oldest first
+    #[must_use]
+    pub fn laps(&self) -> &[f32] {
+        &self.laps
+    }
+
+    /// Returns true while counting
+    #[must_use]
+    pub const fn is_running(&self) -> bool {
+        self.running
+    }
+
+    /// Returns true once a countdown has reached zero
+    #[must_use]
+    pub const fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Seconds counted since the start, excluding pauses
+    #[must_use]
+    pub const fn elapsed(&self) -> f32 {
+        self.elapsed
+    }
+
+    /// Seconds left on a countdown; `None` for a stopwatch
+    #[must_use]
+    pub fn remaining(&self) -> Option<f32> {
+        match self.mode {
+            TimerMode::Stopwatch => None,
+            TimerMode::Countdown(duration) => Some((duration - self.elapsed).max(0.0)),
+        }
+    }
+
+    /// The number a player sees: time left on a countdown, elapsed otherwise
+    #[must_use]
+    pub fn seconds(&self) -> f32 {
+        self.remaining().unwrap_or(self.elapsed)
+    }
+
+    /// Clock text such as `"1:05"`
+    ///
+    /// Countdowns round up, so "0:00" only shows once time is up.
+    #[must_use]
+    pub fn clock_text(&self) -> String {
+        let seconds = match self.mode {
+            TimerMode::Stopwatch => self.seconds().floor(),
+            TimerMode::Countdown(_) => self.seconds().ceil(),
+        };
+        // Float casts saturate, so negative and huge times stay in range
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let whole = seconds as u32;
+        format!("{}:{:02}", whole / 60, whole % 60)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_countdown_finishes_once() {
+        let mut events = Events::new();
+        let mut timer = GameTimer::countdown("survive", 1.0);
+        timer.update(0.6, &mut events);
+        assert!(events.is_empty());
+        assert!((timer.remaining().unwrap() - 0.4).abs() < 1e-5);
+
+        timer.update(0.6, &mut events);
+        timer.update(0.6, &mut events);
+        assert!(timer.is_finished() && !timer.is_running());
+        assert_eq!(timer.remaining(), Some(0.0));
+        assert_eq!(
+            events.drain().collect::<Vec<_>>(),
+            vec![TimerEvent::Finished {
+                timer: "survive".to_string()
+            }]
+        );
+
+        timer.resume();
+        assert!(!timer.is_running());
+        timer.reset();
+        assert!(timer.is_running() && !timer.is_finished());
+    }
+
+    #[test]
+    fn test_pause_and_laps() {
+        let mut events = Events::new();
+        let mut timer = GameTimer::stopwatch("race").paused();
+        timer.update(1.0, &mut events);
+        assert!(timer.elapsed().abs() < f32::EPSILON);
+
+        timer.resume();
+        timer.update(2.0, &mut events);
+        assert!((timer.lap(&mut events) - 2.0).abs() < 1e-5);
+        timer.pause();
+        timer.update(5.0, &mut events);
+        timer.resume();
+        timer.update(1.5, &mut events);
+        assert!((timer.lap(&mut events) - 1.5).abs() < 1e-5);
+
+        assert_eq!(timer.laps().len(), 2);
+        assert!(matches!(
+            events.iter().last(),
+            Some(TimerEvent::Lap { number: 2, .. })
+        ));
+        assert_eq!(timer.remaining(), None);
+    }
+
+    #[test]
+    fn test_clock_text() {
+        let mut events = Events::new();
+        let mut countdown = GameTimer::countdown("t", 65.0);
+        assert_eq!(countdown.clock_text(), "1:05");
+        countdown.update(64.5, &mut events);
+        assert_eq!(countdown.clock_text(), "0:01");
+
+        let mut stopwatch = GameTimer::stopwatch("t");
+        stopwatch.update(59.9, &mut events);
+        assert_eq!(stopwatch.clock_text(), "0:59");
+        assert_eq!(GameTimer::countdown("t", f32::NAN).remaining(), Some(0.0));
+    }
+}
@@ -0,0 +1,281 @@
+//! Versioned save formats and migrations.
+//!
+//! Bundles, snapshots and other saved files outlive the build that wrote
+//! them. Each format carries a schema version tag, and a [`Migrations`]
+//! chain upgrades an old document one version at a time until it matches
+//! what the current build reads. The chain is generic over the document
+//! representation (usually a `serde_json::Value`), so each crate migrates
+//! its own formats without jugar-core knowing about them.
+//!
+//! ```
+//! use jugar_core::Migrations;
+//!
+//! let migrations = Migrations::<Vec<String>>::new("notes", 1)
+//!     .step("split the title line", |mut doc| {
+//!         doc.insert(0, "untitled".to_string());
+//!         Ok(doc)
+//!     });
+//! assert_eq!(migrations.latest(), 2);
+//!
+//! let report = migrations.dry_run(&vec!["hello".to_string()], 1).unwrap();
+//! assert_eq!(report.steps, vec!["split the title line"]);
+//! ```
+
+use thiserror::Error;
+
+/// Schema version tag stored in a saved document
+pub type SchemaVersion = u32;
+
+/// Why a document couldn't be brought up to date
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum VersionError {
+    /// Written by a newer build
+    #[error("This {format} was made with a newer Jugar (version {found}, this one reads up to {latest})")]
+    TooNew {
+        /// Format name
+        format: &'static str,
+        /// Version in the document
+        found: SchemaVersion,
+        /// Newest version this build understands
+        latest: SchemaVersion,
+    },
+
+    /// Older than any version with a migration path
+    #[error("This {format} is too old to open (version {found}, oldest supported is {oldest})")]
+    TooOld {
+        /// Format name
+        format: &'static str,
+        /// Version in the document
+        found: SchemaVersion,
+        /// Oldest version that can be migrated
+        oldest: SchemaVersion,
+    },
+
+    /// A migration step rejected the document
+    #[error("Couldn't upgrade {format} from version {from}: {reason}")]
+    Failed {
+        /// Format name
+        format: &'static str,
+        /// Version the failing step upgrades from
+        from: SchemaVersion,
+        /// What was wrong
+        reason: String,
+    },
+}
+
+/// Upgrades a document by one version
+pub type MigrationFn<D> = fn(D) -> core::result::Result<D, String>;
+
+/// One step of a [`Migrations`] chain
+#[derive(Debug, Clone, Copy)]
+pub struct Migration<D> {
+    /// Version this step upgrades from (to `from + 1`)
+    pub from: SchemaVersion,
+    /// What the step changes
+    pub description: &'static str,
+    apply: MigrationFn<D>,
+}
+
+/// What migrating a document would do
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationReport {
+    /// Format name
+    pub format: &'static str,
+    /// Version in the document
+    pub from: SchemaVersion,
+    /// Version after migrating
+    pub to: SchemaVersion,
+    /// Descriptions of the steps applied, in order
+    pub steps: Vec<&'static str>,
+}
+
+impl MigrationReport {
+    /// Returns true if the document was already current
+    #[must_use]
+    pub fn is_current(&self) -> bool {
+        self.steps.is_empty()
+    }
+}
+
+/// Ordered migrations from the oldest supported version to the latest
+#[derive(Debug, Clone)]
+pub struct Migrations<D> {
+    format: &'static str,
+    oldest: SchemaVersion,
+    steps: Vec<Migration<D>>,
+}
+
+impl<D> Migrations<D> {
+    /// Starts a chain for `format` whose oldest readable version is `oldest`
+    #[must_use]
+    pub const fn new(format: &'static str, oldest: SchemaVersion) -> Self {
+        Self {
+            format,
+            oldest,
+            steps: Vec::new(),
+        }
+    }
+
+    /// Adds the step from the current latest version to the next one
+    #[must_use]
+    pub fn step(mut self, description: &'static str, apply: MigrationFn<D>) -> Self {
+        let from = self.latest();
+        self.steps.push(Migration {
+            from,
+            description,
+            apply,
+        });
+        self
+    }
+
+    /// Format name used in errors
+    #[must_use]
+    pub const fn format(&self) -> &'static str {
+        self.format
+    }
+
+    /// Oldest version that can be migrated
+    #[must_use]
+    pub const fn oldest(&self) -> SchemaVersion {
+        self.oldest
+    }
+
+    /// Version documents are migrated to
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn latest(&self) -> SchemaVersion {
+        self.oldest + self.steps.len() as SchemaVersion
+    }
+
+    /// Steps a document at `version` needs, in order
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VersionError::TooNew`] or [`VersionError::TooOld`] if
+    /// `version` is outside the chain.
+    pub fn pending(&self, version: SchemaVersion) -> Result<&[Migration<D>], VersionError> {
+        if version > self.latest() {
+            return Err(VersionError::TooNew {
+                format: self.format,
+                found: version,
+                latest: self.latest(),
+            });
+        }
+        if version < self.oldest {
+            return Err(VersionError::TooOld {
+                format: self.format,
+                found: version,
+                oldest: self.oldest,
+            });
+        }
+        Ok(&self.steps[(version - self.oldest) as usize..])
+    }
+
+    /// Upgrades a document written at `version` to [`latest`](Self::latest)
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`VersionError`] if the version is unsupported or a step
+    /// rejects the document.
+    pub fn migrate(&self, document: D, version: SchemaVersion) -> Result<D, VersionError> {
+        self.pending(version)?
+            .iter()
+            .try_fold(document, |document, step| {
+                (step.apply)(document).map_err(|reason| VersionError::Failed {
+                    format: self.format,
+                    from: step.from,
+                    reason,
+                })
+            })
+    }
+
+    /// Runs the migration on a copy, reporting what it would do
+    ///
+    /// # Errors
+    ///
+    /// Returns the error [`migrate`](Self::migrate) would.
+    pub fn dry_run(
+        &self,
+        document: &D,
+        version: SchemaVersion,
+    ) -> Result<MigrationReport, VersionError>
+    where
+        D: Clone,
+    {
+        let _ = self.migrate(document.clone(), version)?;
+        Ok(MigrationReport {
+            format: self.format,
+            from: version,
+            to: self.latest(),
+            steps: self
+                .pending(version)?
+                .iter()
+                .map(|step| step.description)
+                .collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn chain() -> Migrations<Vec<u32>> {
+        Migrations::<Vec<u32>>::new("save", 1)
+            .step("add a slot", |mut doc| {
+                doc.push(0);
+                Ok(doc)
+            })
+            .step("double everything", |doc| {
+                if doc.contains(&13) {
+                    return Err("unlucky".to_string());
+                }
+                Ok(doc.into_iter().map(|v| v * 2).collect())
+            })
+    }
+
+    #[test]
+    fn test_migrates_in_order_from_any_version() {
+        let migrations = chain();
+        assert_eq!(migrations.latest(), 3);
+        assert_eq!(migrations.migrate(vec![1], 1).unwrap(), vec![2, 0]);
+        assert_eq!(migrations.migrate(vec![1], 2).unwrap(), vec![2]);
+        assert_eq!(migrations.migrate(vec![1], 3).unwrap(), vec![1]);
+    }
+
+    #[test]
+    fn test_rejects_unknown_versions() {
+        let migrations = chain();
+        assert!(matches!(
+            migrations.migrate(vec![], 4),
+            Err(VersionError::TooNew {
+                found: 4,
+                latest: 3,
+                ..
+            })
+        ));
+        let err = migrations.migrate(vec![], 0).unwrap_err();
+        assert!(matches!(err, VersionError::TooOld { oldest: 1, .. }));
+        assert!(err.to_string().contains("too old"));
+    }
+
+    #[test]
+    fn test_dry_run_reports_steps_and_failures() {
+        let migrations = chain();
+        let report = migrations.dry_run(&vec![5], 1).unwrap();
+        assert_eq!((report.from, report.to), (1, 3));
+        assert_eq!(report.steps, vec!["add a slot", "double everything"]);
+        assert!(migrations.dry_run(&vec![5], 3).unwrap().is_current());
+
+        let err = migrations.dry_run(&vec![13], 1).unwrap_err();
+        assert_eq!(
+            err,
+            VersionError::Failed {
+                format: "save",
+                from: 2,
+                reason: "unlucky".to_string(),
+            }
+        );
+    }
+}
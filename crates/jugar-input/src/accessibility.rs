@@ -0,0 +1,331 @@
+//! Accessibility filtering for actions: hold-to-toggle, tremor tolerance and
+//! slowed key repeat.
+//!
+//! [`AccessibleInput`] sits between raw [`InputState`] and gameplay code. It is
+//! driven by a serializable [`InputAccessibilityConfig`] so settings from YAML
+//! or a player profile can be applied without game changes. The player's
+//! [`AccessibilitySettings`], the ones a compiled YAML game is adjusted for,
+//! map onto a config with [`InputAccessibilityConfig::for_settings`].
+
+use std::collections::HashMap;
+
+use jugar_core::AccessibilitySettings;
+use serde::{Deserialize, Serialize};
+
+use crate::{InputAction, InputState};
+
+/// Input accessibility settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InputAccessibilityConfig {
+    /// Actions that toggle on press instead of requiring a hold (sticky keys)
+    pub toggle_actions: Vec<String>,
+    /// Presses shorter than this (seconds) are ignored (tremor tolerance)
+    pub min_press_duration: f32,
+    /// Delay before a held action starts repeating (seconds)
+    pub repeat_delay: f32,
+    /// Time between repeats while held (seconds)
+    pub repeat_interval: f32,
+    /// Multiplier applied to repeat timing (2.0 = half as fast)
+    pub repeat_slowdown: f32,
+}
+
+impl InputAccessibilityConfig {
+    /// Creates a config with no adjustments
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            toggle_actions: Vec::new(),
+            min_press_duration: 0.0,
+            repeat_delay: 0.5,
+            repeat_interval: 0.1,
+            repeat_slowdown: 1.0,
+        }
+    }
+
+    /// Preset for players with tremors or limited motor control
+    #[must_use]
+    pub const fn motor_assist() -> Self {
+        Self {
+            toggle_actions: Vec::new(),
+            min_press_duration: 0.08,
+            repeat_delay: 0.5,
+            repeat_interval: 0.1,
+            repeat_slowdown: 2.5,
+        }
+    }
+
+    /// Config for the player's accessibility settings
+    ///
+    /// Motor assist picks [`Self::motor_assist`]; otherwise nothing is
+    /// adjusted. Toggles are per action, so add them with
+    /// [`Self::with_toggle`].
+    #[must_use]
+    pub const fn for_settings(settings: &AccessibilitySettings) -> Self {
+        if settings.motor_assist {
+            Self::motor_assist()
+        } else {
+            Self::new()
+        }
+    }
+
+    /// Makes an action toggle on press instead of requiring a hold
+    #[must_use]
+    pub fn with_toggle(mut self, action: impl Into<String>) -> Self {
+        self.toggle_actions.push(action.into());
+        self
+    }
+
+    /// Sets the minimum press duration
+    #[must_use]
+    pub const fn with_min_press_duration(mut self, seconds: f32) -> Self {
+        self.min_press_duration = seconds;
+        self
+    }
+
+    /// Sets the repeat slowdown multiplier
+    #[must_use]
+    pub const fn with_repeat_slowdown(mut self, slowdown: f32) -> Self {
+        self.repeat_slowdown = slowdown;
+        self
+    }
+
+    /// Checks whether an action is configured as a toggle
+    #[must_use]
+    pub fn is_toggle(&self, action: &str) -> bool {
+        self.toggle_actions.iter().any(|a| a == action)
+    }
+}
+
+impl Default for InputAccessibilityConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Where a filtered action is in its press
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum Hold {
+    #[default]
+    Up,
+    /// Down, waiting for the repeat delay
+    Down,
+    /// Down and repeating every interval
+    Repeating,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct FilterState {
+    held: f32,
+    hold: Hold,
+    toggled: bool,
+    triggered: bool,
+    repeat_timer: f32,
+}
+
+/// Applies an [`InputAccessibilityConfig`] to actions each frame
+#[derive(Debug, Clone, Default)]
+pub struct AccessibleInput {
+    config: InputAccessibilityConfig,
+    states: HashMap<String, FilterState>,
+}
+
+impl AccessibleInput {
+    /// Creates a filter with the given config
+    #[must_use]
+    pub fn new(config: InputAccessibilityConfig) -> Self {
+        Self {
+            config,
+            states: HashMap::new(),
+        }
+    }
+
+    /// Returns the config
+    #[must_use]
+    pub const fn config(&self) -> &InputAccessibilityConfig {
+        &self.config
+    }
+
+    /// Replaces the config and resets action state
+    pub fn set_config(&mut self, config: InputAccessibilityConfig) {
+        self.config = config;
+        self.states.clear();
+    }
+
+    /// Updates all actions from the current input
+    pub fn update(&mut self, actions: &[InputAction], input: &InputState, dt: f32) {
+        for action in actions {
+            self.update_raw(&action.name, action.is_active(input), dt);
+        }
+    }
+
+    /// Updates a single action from its raw (unfiltered) down state
+    pub fn update_raw(&mut self, name: &str, raw_down: bool, dt: f32) {
+        let toggle = self.config.is_toggle(name);
+        let min_press = self.config.min_press_duration;
+        let slowdown = self.config.repeat_slowdown.max(f32::EPSILON);
+        let delay = self.config.repeat_delay * slowdown;
+        let interval = self.config.repeat_interval * slowdown;
+
+        let state = self.states.entry(name.to_string()).or_default();
+        state.held = if raw_down { state.held + dt } else { 0.0 };
+        let down = raw_down && state.held >= min_press;
+        let pressed = down && state.hold == Hold::Up;
+        state.triggered = false;
+
+        if !down {
+            state.hold = Hold::Up;
+        } else if pressed {
+            state.hold = Hold::Down;
+            state.repeat_timer = 0.0;
+            if toggle {
+                state.toggled = !state.toggled;
+                state.triggered = state.toggled;
+            } else {
+                state.triggered = true;
+            }
+        } else if !toggle {
+            state.repeat_timer += dt;
+            let threshold = if state.hold == Hold::Repeating {
+                interval
+            } else {
+                delay
+            };
+            if state.repeat_timer >= threshold {
+                state.repeat_timer -= threshold;
+                state.hold = Hold::Repeating;
+                state.triggered = true;
+            }
+        }
+    }
+
+    /// Returns true while the filtered action is active
+    #[must_use]
+    pub fn is_active(&self, name: &str) -> bool {
+        let toggle = self.config.is_toggle(name);
+        self.states.get(name).is_some_and(|s| {
+            if toggle {
+                s.toggled
+            } else {
+                s.hold != Hold::Up
+            }
+        })
+    }
+
+    /// Returns true on the frame the action activates or repeats
+    #[must_use]
+    pub fn triggered(&self, name: &str) -> bool {
+        self.states.get(name).is_some_and(|s| s.triggered)
+    }
+
+    /// Clears all toggles and timers
+    pub fn reset(&mut self) {
+        self.states.clear();
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::{ButtonState, KeyCode};
+
+    #[test]
+    fn test_default_passthrough() {
+        let mut filter = AccessibleInput::default();
+        filter.update_raw("jump", true, 0.016);
+        assert!(filter.is_active("jump"));
+        assert!(filter.triggered("jump"));
+        filter.update_raw("jump", false, 0.016);
+        assert!(!filter.is_active("jump"));
+    }
+
+    #[test]
+    fn test_min_press_duration_filters_tremor() {
+        let config = InputAccessibilityConfig::new().with_min_press_duration(0.1);
+        let mut filter = AccessibleInput::new(config);
+        filter.update_raw("fire", true, 0.05);
+        assert!(!filter.is_active("fire"));
+        filter.update_raw("fire", false, 0.05);
+        filter.update_raw("fire", true, 0.05);
+        assert!(!filter.is_active("fire"));
+        filter.update_raw("fire", true, 0.06);
+        assert!(filter.is_active("fire"));
+        assert!(filter.triggered("fire"));
+    }
+
+    #[test]
+    fn test_hold_to_toggle() {
+        let config = InputAccessibilityConfig::new().with_toggle("run");
+        let mut filter = AccessibleInput::new(config);
+        filter.update_raw("run", true, 0.016);
+        filter.update_raw("run", false, 0.016);
+        assert!(filter.is_active("run"));
+        filter.update_raw("run", true, 0.016);
+        assert!(!filter.is_active("run"));
+        filter.update_raw("run", false, 0.016);
+        assert!(!filter.is_active("run"));
+    }
+
+    #[test]
+    fn test_repeat_slowdown() {
+        let normal = InputAccessibilityConfig::new();
+        let slow = InputAccessibilityConfig::new().with_repeat_slowdown(2.0);
+        let count = |config: InputAccessibilityConfig| {
+            let mut filter = AccessibleInput::new(config);
+            let mut triggers = 0;
+            for _ in 0..100 {
+                filter.update_raw("move", true, 0.01);
+                if filter.triggered("move") {
+                    triggers += 1;
+                }
+            }
+            triggers
+        };
+        let normal_count = count(normal);
+        let slow_count = count(slow);
+        assert!(normal_count > slow_count);
+        assert!(slow_count >= 1);
+    }
+
+    #[test]
+    fn test_update_from_actions() {
+        let mut input = InputState::new();
+        input.set_key(KeyCode::Space, ButtonState::JustPressed);
+        let actions = [InputAction::new("jump").with_key(KeyCode::Space)];
+        let mut filter = AccessibleInput::new(InputAccessibilityConfig::default());
+        filter.update(&actions, &input, 0.016);
+        assert!(filter.is_active("jump"));
+    }
+
+    #[test]
+    fn test_reset_clears_toggles() {
+        let config = InputAccessibilityConfig::new().with_toggle("crouch");
+        let mut filter = AccessibleInput::new(config);
+        filter.update_raw("crouch", true, 0.016);
+        assert!(filter.is_active("crouch"));
+        filter.reset();
+        assert!(!filter.is_active("crouch"));
+    }
+
+    #[test]
+    fn test_motor_assist_preset() {
+        let config = InputAccessibilityConfig::motor_assist();
+        assert!(config.min_press_duration > 0.0);
+        assert!(config.repeat_slowdown > 1.0);
+    }
+
+    #[test]
+    fn test_config_for_player_settings() {
+        let settings = AccessibilitySettings::new().with_motor_assist(true);
+        assert_eq!(
+            InputAccessibilityConfig::for_settings(&settings),
+            InputAccessibilityConfig::motor_assist()
+        );
+        let settings = AccessibilitySettings::new().with_reduced_motion(true);
+        assert_eq!(
+            InputAccessibilityConfig::for_settings(&settings),
+            InputAccessibilityConfig::new()
+        );
+    }
+}
@@ -0,0 +1,396 @@
+//! Input buffering and combo detection.
+//!
+//! [`InputBuffer`] keeps recent action presses with timestamps so games can
+//! honor slightly early inputs (jump buffering) and match sequences such as
+//! "down, forward, punch" with [`Combo`].
+
+use alloc::collections::VecDeque;
+use core::cmp::Reverse;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{InputAction, InputState};
+
+/// A recorded action press
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BufferedInput {
+    /// Action name
+    pub action: String,
+    /// Time of the press in seconds
+    pub time: f64,
+}
+
+/// Ring buffer of recent action presses
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InputBuffer {
+    entries: VecDeque<BufferedInput>,
+    capacity: usize,
+    max_age: f64,
+}
+
+impl InputBuffer {
+    /// Creates a buffer holding up to `capacity` presses for `max_age` seconds
+    #[must_use]
+    pub fn new(capacity: usize, max_age: f64) -> Self {
+        Self {
+            entries: VecDeque::with_capacity(capacity),
+            capacity: capacity.max(1),
+            max_age,
+        }
+    }
+
+    /// Records a press of `action` at `time`
+    pub fn record(&mut self, action: impl Into<String>, time: f64) {
+        if self.entries.len() >= self.capacity {
+            let _ = self.entries.pop_front();
+        }
+        self.entries.push_back(BufferedInput {
+            action: action.into(),
+            time,
+        });
+    }
+
+    /// Records every action that was just pressed this frame
+    pub fn record_actions(&mut self, actions: &[InputAction], input: &InputState, time: f64) {
+        for action in actions {
+            if action.just_pressed(input) {
+                self.record(action.name.clone(), time);
+            }
+        }
+        self.prune(time);
+    }
+
+    /// Drops presses older than the buffer's maximum age
+    pub fn prune(&mut self, now: f64) {
+        while self
+            .entries
+            .front()
+            .is_some_and(|e| now - e.time > self.max_age)
+        {
+            let _ = self.entries.pop_front();
+        }
+    }
+
+    /// Checks whether `action` was pressed within the last `window` seconds
+    #[must_use]
+    pub fn pressed_within(&self, action: &str, window: f64, now: f64) -> bool {
+        self.entries
+            .iter()
+            .rev()
+            .take_while(|e| now - e.time <= window)
+            .any(|e| e.action == action)
+    }
+
+    /// Like [`pressed_within`](Self::pressed_within) but removes the press so
+    /// it only triggers once
+    pub fn consume(&mut self, action: &str, window: f64, now: f64) -> bool {
+        let found = self
+            .entries
+            .iter()
+            .enumerate()
+            .rev()
+            .take_while(|(_, e)| now - e.time <= window)
+            .find(|(_, e)| e.action == action)
+            .map(|(i, _)| i);
+        found.is_some_and(|i| self.entries.remove(i).is_some())
+    }
+
+    /// Checks whether the combo was just completed
+    #[must_use]
+    pub fn matches(&self, combo: &Combo, now: f64) -> bool {
+        self.match_start(combo, now).is_some()
+    }
+
+    /// Finds the index of the first entry of a completed combo
+    fn match_start(&self, combo: &Combo, now: f64) -> Option<usize> {
+        let last = self.entries.back()?;
+        let mut steps = combo.steps.iter().rev();
+        let final_step = steps.next()?;
+        if last.action != *final_step || now - last.time > combo.step_window {
+            return None;
+        }
+
+        let mut next_time = last.time;
+        let end_time = last.time;
+        let mut index = self.entries.len() - 1;
+        for step in steps {
+            loop {
+                index = index.checked_sub(1)?;
+                let entry = &self.entries[index];
+                if next_time - entry.time > combo.step_window
+                    || end_time - entry.time > combo.window
+                {
+                    return None;
+                }
+                if entry.action == *step {
+                    next_time = entry.time;
+                    break;
+                }
+                if combo.strict {
+                    return None;
+                }
+            }
+        }
+        Some(index)
+    }
+
+    /// Removes a completed combo's presses so it does not fire twice.
+    ///
+    /// Returns true if the combo matched.
+    pub fn consume_combo(&mut self, combo: &Combo, now: f64) -> bool {
+        match self.match_start(combo, now) {
+            Some(start) => {
+                self.entries.truncate(start);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Number of buffered presses
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if no presses are buffered
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Clears all buffered presses
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Iterates buffered presses from oldest to newest
+    pub fn entries(&self) -> impl Iterator<Item = &BufferedInput> {
+        self.entries.iter()
+    }
+}
+
+impl Default for InputBuffer {
+    fn default() -> Self {
+        Self::new(32, 1.0)
+    }
+}
+
+/// A sequence of action presses that must happen within time windows
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Combo {
+    /// Combo name
+    pub name: String,
+    /// Actions in order
+    pub steps: Vec<String>,
+    /// Maximum time from first to last step in seconds
+    pub window: f64,
+    /// Maximum time between consecutive steps in seconds
+    pub step_window: f64,
+    /// If true, no other presses may occur between steps
+    pub strict: bool,
+}
+
+impl Combo {
+    /// Creates a combo that must complete within `window` seconds
+    #[must_use]
+    pub fn new<S: Into<String>>(
+        name: impl Into<String>,
+        steps: impl IntoIterator<Item = S>,
+        window: f64,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            steps: steps.into_iter().map(Into::into).collect(),
+            window,
+            step_window: window,
+            strict: false,
+        }
+    }
+
+    /// Creates a double-tap combo (e.g. tap right twice to dash)
+    #[must_use]
+    pub fn double_tap(name: impl Into<String>, action: &str, window: f64) -> Self {
+        Self::new(name, [action, action], window).strict()
+    }
+
+    /// Sets the maximum time between consecutive steps
+    #[must_use]
+    pub const fn with_step_window(mut self, step_window: f64) -> Self {
+        self.step_window = step_window;
+        self
+    }
+
+    /// Requires steps to be pressed without other presses in between
+    #[must_use]
+    pub const fn strict(mut self) -> Self {
+        self.strict = true;
+        self
+    }
+}
+
+/// Detects the first matching combo from a list, longest combos first
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ComboDetector {
+    combos: Vec<Combo>,
+}
+
+impl ComboDetector {
+    /// Creates an empty detector
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { combos: Vec::new() }
+    }
+
+    /// Adds a combo
+    #[must_use]
+    pub fn with_combo(mut self, combo: Combo) -> Self {
+        self.combos.push(combo);
+        self.combos.sort_by_key(|c| Reverse(c.steps.len()));
+        self
+    }
+
+    /// Returns the registered combos
+    #[must_use]
+    pub fn combos(&self) -> &[Combo] {
+        &self.combos
+    }
+
+    /// Detects and consumes a completed combo, returning its name
+    pub fn detect(&self, buffer: &mut InputBuffer, now: f64) -> Option<String> {
+        self.combos
+            .iter()
+            .find(|combo| buffer.consume_combo(combo, now))
+            .map(|combo| combo.name.clone())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::{ButtonState, KeyCode};
+
+    fn hadouken() -> Combo {
+        Combo::new("hadouken", ["down", "down_forward", "punch"], 0.4)
+    }
+
+    // ==================== BUFFER TESTS ====================
+
+    #[test]
+    fn test_buffer_pressed_within() {
+        let mut buffer = InputBuffer::default();
+        buffer.record("jump", 1.0);
+        assert!(buffer.pressed_within("jump", 0.1, 1.05));
+        assert!(!buffer.pressed_within("jump", 0.1, 1.2));
+        assert!(!buffer.pressed_within("attack", 0.1, 1.05));
+    }
+
+    #[test]
+    fn test_buffer_consume_once() {
+        let mut buffer = InputBuffer::default();
+        buffer.record("jump", 1.0);
+        assert!(buffer.consume("jump", 0.2, 1.1));
+        assert!(!buffer.consume("jump", 0.2, 1.1));
+    }
+
+    #[test]
+    fn test_buffer_capacity() {
+        let mut buffer = InputBuffer::new(2, 10.0);
+        buffer.record("a", 0.0);
+        buffer.record("b", 0.1);
+        buffer.record("c", 0.2);
+        assert_eq!(buffer.len(), 2);
+        assert_eq!(buffer.entries().next().unwrap().action, "b");
+    }
+
+    #[test]
+    fn test_buffer_prune() {
+        let mut buffer = InputBuffer::new(8, 0.5);
+        buffer.record("a", 0.0);
+        buffer.record("b", 0.8);
+        buffer.prune(1.0);
+        assert_eq!(buffer.len(), 1);
+    }
+
+    #[test]
+    fn test_buffer_record_actions() {
+        let mut input = InputState::new();
+        input.set_key(KeyCode::Space, ButtonState::JustPressed);
+        let actions = [
+            InputAction::new("jump").with_key(KeyCode::Space),
+            InputAction::new("fire").with_key(KeyCode::Enter),
+        ];
+        let mut buffer = InputBuffer::default();
+        buffer.record_actions(&actions, &input, 2.0);
+        assert_eq!(buffer.len(), 1);
+        assert!(buffer.pressed_within("jump", 0.1, 2.0));
+    }
+
+    // ==================== COMBO TESTS ====================
+
+    #[test]
+    fn test_combo_matches_in_window() {
+        let mut buffer = InputBuffer::default();
+        buffer.record("down", 0.0);
+        buffer.record("down_forward", 0.1);
+        buffer.record("punch", 0.3);
+        assert!(buffer.matches(&hadouken(), 0.3));
+    }
+
+    #[test]
+    fn test_combo_too_slow() {
+        let mut buffer = InputBuffer::default();
+        buffer.record("down", 0.0);
+        buffer.record("down_forward", 0.2);
+        buffer.record("punch", 0.5);
+        assert!(!buffer.matches(&hadouken(), 0.5));
+    }
+
+    #[test]
+    fn test_combo_wrong_order() {
+        let mut buffer = InputBuffer::default();
+        buffer.record("down_forward", 0.0);
+        buffer.record("down", 0.1);
+        buffer.record("punch", 0.2);
+        assert!(!buffer.matches(&hadouken(), 0.2));
+    }
+
+    #[test]
+    fn test_combo_lenient_allows_extra_presses() {
+        let mut buffer = InputBuffer::default();
+        buffer.record("down", 0.0);
+        buffer.record("kick", 0.05);
+        buffer.record("down_forward", 0.1);
+        buffer.record("punch", 0.2);
+        assert!(buffer.matches(&hadouken(), 0.2));
+        assert!(!buffer.matches(&hadouken().strict(), 0.2));
+    }
+
+    #[test]
+    fn test_combo_double_tap() {
+        let dash = Combo::double_tap("dash", "right", 0.25);
+        let mut buffer = InputBuffer::default();
+        buffer.record("right", 0.0);
+        assert!(!buffer.matches(&dash, 0.0));
+        buffer.record("right", 0.2);
+        assert!(buffer.matches(&dash, 0.2));
+    }
+
+    #[test]
+    fn test_combo_detector_consumes() {
+        let detector = ComboDetector::new()
+            .with_combo(Combo::new("punch", ["punch"], 0.1))
+            .with_combo(hadouken());
+        let mut buffer = InputBuffer::default();
+        buffer.record("down", 0.0);
+        buffer.record("down_forward", 0.1);
+        buffer.record("punch", 0.2);
+        assert_eq!(
+            detector.detect(&mut buffer, 0.2),
+            Some("hadouken".to_string())
+        );
+        assert!(buffer.is_empty());
+        assert_eq!(detector.detect(&mut buffer, 0.2), None);
+    }
+}
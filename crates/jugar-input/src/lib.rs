@@ -5,10 +5,24 @@
 #![forbid(unsafe_code)]
 #![warn(missing_docs)]
 
+extern crate alloc;
+
+mod accessibility;
+mod buffer;
+mod motion;
+mod players;
+mod virtual_controls;
+
 use glam::Vec2;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+pub use accessibility::{AccessibleInput, InputAccessibilityConfig};
+pub use buffer::{BufferedInput, Combo, ComboDetector, InputBuffer};
+pub use motion::{MotionPermission, MotionState, SHAKE_JOLTS, SHAKE_THRESHOLD, SHAKE_WINDOW};
+pub use players::{KeyboardBinding, PlayerBinding, PlayerInput, PlayerInputMap};
+pub use virtual_controls::{JoystickMode, VirtualButton, VirtualJoystick};
+
 /// Input errors
 #[derive(Error, Debug, Clone, PartialEq, Eq)]
 pub enum InputError {
@@ -274,6 +288,9 @@ impl GamepadState {
     }
 }
 
+/// Device tilt (degrees) that counts as full deflection
+pub const TILT_RANGE_DEGREES: f32 = 45.0;
+
 /// Unified input state manager
 #[derive(Debug, Default)]
 pub struct InputState {
@@ -289,6 +306,14 @@ pub struct InputState {
     keys: std::collections::HashMap<KeyCode, ButtonState>,
     /// Gamepad states (up to 4)
     pub gamepads: [GamepadState; 4],
+    /// On-screen virtual button states by name
+    virtual_buttons: std::collections::HashMap<String, ButtonState>,
+    /// Device-to-player assignment for local multiplayer
+    player_map: PlayerInputMap,
+    /// Tilt, acceleration and shake from motion sensors
+    motion: MotionState,
+    /// Pointer locked to the game (relative mouse mode)
+    pointer_locked: bool,
 }
 
 impl InputState {
@@ -328,6 +353,37 @@ impl InputState {
         let _ = self.keys.insert(key, state);
     }
 
+    /// Gets the state of a named virtual (on-screen) button
+    #[must_use]
+    pub fn virtual_button(&self, name: &str) -> ButtonState {
+        self.virtual_buttons
+            .get(name)
+            .copied()
+            .unwrap_or(ButtonState::Released)
+    }
+
+    /// Sets the state of a named virtual (on-screen) button
+    pub fn set_virtual_button(&mut self, name: impl Into<String>, state: ButtonState) {
+        let _ = self.virtual_buttons.insert(name.into(), state);
+    }
+
+    /// Sets the device-to-player assignment
+    pub fn set_player_map(&mut self, map: PlayerInputMap) {
+        self.player_map = map;
+    }
+
+    /// Gets the device-to-player assignment
+    #[must_use]
+    pub const fn player_map(&self) -> &PlayerInputMap {
+        &self.player_map
+    }
+
+    /// Returns a view of the input restricted to one player's devices
+    #[must_use]
+    pub fn for_player(&self, player: usize) -> PlayerInput<'_> {
+        PlayerInput::new(self, self.player_map.binding(player), player)
+    }
+
     /// Gets primary touch (or mouse as touch)
     #[must_use]
     pub fn primary_pointer(&self) -> Option<Vec2> {
@@ -360,12 +416,77 @@ impl InputState {
         for state in self.keys.values_mut() {
             *state = state.advance();
         }
+        for state in self.virtual_buttons.values_mut() {
+            *state = state.advance();
+        }
         for gamepad in &mut self.gamepads {
             for button in &mut gamepad.buttons {
                 *button = button.advance();
             }
         }
         self.mouse_delta = Vec2::ZERO;
+        self.motion.advance_frame();
+    }
+
+    /// Records a device orientation reading (degrees, as reported by the
+    /// browser's `deviceorientation` event)
+    ///
+    /// See [`MotionState::set_orientation`].
+    pub fn set_orientation(&mut self, beta: f32, gamma: f32) {
+        self.motion.set_orientation(beta, gamma);
+    }
+
+    /// Device tilt with each axis in -1.0..=1.0
+    ///
+    /// Returns `None` if the device has no orientation sensor (or the player
+    /// has not granted access), so callers can fall back to touch or keys.
+    #[must_use]
+    pub const fn tilt(&self) -> Option<Vec2> {
+        self.motion.tilt()
+    }
+
+    /// Forgets the tilt reading (e.g. the sensor was revoked)
+    pub fn clear_tilt(&mut self) {
+        self.motion.clear();
+    }
+
+    /// Motion sensor readings and shake detection
+    #[must_use]
+    pub const fn motion(&self) -> &MotionState {
+        &self.motion
+    }
+
+    /// Mutable motion state, for feeding sensor readings
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn motion_mut(&mut self) -> &mut MotionState {
+        &mut self.motion
+    }
+
+    /// Records whether the pointer is locked to the game
+    ///
+    /// While locked the cursor is hidden and pinned, so only
+    /// [`mouse_delta`](Self::mouse_delta) carries movement. Changing the
+    /// lock state clears the delta so absolute and relative motion never mix.
+    pub fn set_pointer_locked(&mut self, locked: bool) {
+        if self.pointer_locked != locked {
+            self.pointer_locked = locked;
+            self.mouse_delta = Vec2::ZERO;
+        }
+    }
+
+    /// Returns true while the pointer is locked (relative mouse mode)
+    #[must_use]
+    pub const fn is_pointer_locked(&self) -> bool {
+        self.pointer_locked
+    }
+
+    /// Adds relative mouse movement without moving `mouse_position`
+    ///
+    /// Movements accumulate until the next [`advance_frame`](Self::advance_frame).
+    pub fn add_mouse_motion(&mut self, delta: Vec2) {
+        if delta.is_finite() {
+            self.mouse_delta += delta;
+        }
     }
 
     /// Clears all touches
@@ -411,6 +532,9 @@ pub struct InputAction {
     pub mouse_buttons: Vec<MouseButton>,
     /// Bound gamepad buttons
     pub gamepad_buttons: Vec<GamepadButton>,
+    /// Bound virtual (on-screen) button names
+    #[serde(default)]
+    pub virtual_buttons: Vec<String>,
 }
 
 impl InputAction {
@@ -422,6 +546,7 @@ impl InputAction {
             keys: Vec::new(),
             mouse_buttons: Vec::new(),
             gamepad_buttons: Vec::new(),
+            virtual_buttons: Vec::new(),
         }
     }
 
@@ -446,6 +571,13 @@ impl InputAction {
         self
     }
 
+    /// Adds a virtual (on-screen) button binding
+    #[must_use]
+    pub fn with_virtual_button(mut self, name: impl Into<String>) -> Self {
+        self.virtual_buttons.push(name.into());
+        self
+    }
+
     /// Checks if the action is active
     #[must_use]
     pub fn is_active(&self, input: &InputState) -> bool {
@@ -460,6 +592,30 @@ impl InputAction {
                     .iter()
                     .any(|g| g.connected && g.button(*b).is_down())
             })
+            || self
+                .virtual_buttons
+                .iter()
+                .any(|name| input.virtual_button(name).is_down())
+    }
+
+    /// Checks if any binding of the action was pressed this frame
+    #[must_use]
+    pub fn just_pressed(&self, input: &InputState) -> bool {
+        self.keys.iter().any(|k| input.key(*k).just_pressed())
+            || self
+                .mouse_buttons
+                .iter()
+                .any(|b| input.mouse_button(*b).just_pressed())
+            || self.gamepad_buttons.iter().any(|b| {
+                input
+                    .gamepads
+                    .iter()
+                    .any(|g| g.connected && g.button(*b).just_pressed())
+            })
+            || self
+                .virtual_buttons
+                .iter()
+                .any(|name| input.virtual_button(name).just_pressed())
     }
 }
 
@@ -668,6 +824,43 @@ mod tests {
         assert_eq!(pointer, Some(Vec2::new(50.0, 50.0)));
     }
 
+    #[test]
+    fn test_tilt_scaled_and_clamped() {
+        let mut state = InputState::new();
+        assert_eq!(state.tilt(), None);
+
+        state.set_orientation(-22.5, 90.0);
+        assert_eq!(state.tilt(), Some(Vec2::new(1.0, -0.5)));
+
+        state.set_orientation(f32::NAN, 0.0);
+        assert_eq!(state.tilt(), Some(Vec2::new(1.0, -0.5)));
+
+        state.clear_tilt();
+        assert_eq!(state.tilt(), None);
+    }
+
+    #[test]
+    fn test_relative_mouse_motion_accumulates() {
+        let mut state = InputState::new();
+        assert!(!state.is_pointer_locked());
+        state.set_pointer_locked(true);
+        state.mouse_position = Vec2::new(50.0, 50.0);
+
+        state.add_mouse_motion(Vec2::new(3.0, -1.0));
+        state.add_mouse_motion(Vec2::new(2.0, 4.0));
+        state.add_mouse_motion(Vec2::new(f32::INFINITY, 0.0));
+        assert_eq!(state.mouse_delta, Vec2::new(5.0, 3.0));
+        assert_eq!(state.mouse_position, Vec2::new(50.0, 50.0));
+
+        state.advance_frame();
+        assert_eq!(state.mouse_delta, Vec2::ZERO);
+        assert!(state.is_pointer_locked());
+
+        state.add_mouse_motion(Vec2::new(1.0, 1.0));
+        state.set_pointer_locked(false);
+        assert_eq!(state.mouse_delta, Vec2::ZERO);
+    }
+
     // ==================== GAMEPAD STATE TESTS ====================
 
     #[test]
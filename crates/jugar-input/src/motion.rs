@@ -0,0 +1,222 @@
+//! Device motion: tilt and shake.
+//!
+//! Tablets report orientation (`deviceorientation`, in degrees) and motion
+//! (`devicemotion`: accelerometer in m/s² and gyroscope in degrees per
+//! second). [`MotionState`] keeps the latest readings, normalizes
+//! orientation into a tilt vector for `move: tilt` games, and turns sharp
+//! back-and-forth jolts into a shake gesture.
+
+use alloc::collections::VecDeque;
+
+use glam::{Vec2, Vec3};
+use serde::{Deserialize, Serialize};
+
+use crate::TILT_RANGE_DEGREES;
+
+/// Acceleration (m/s², gravity excluded) that counts as a jolt
+pub const SHAKE_THRESHOLD: f32 = 12.0;
+
+/// Jolts in alternating directions that make a shake
+pub const SHAKE_JOLTS: usize = 3;
+
+/// Seconds within which the jolts must land
+pub const SHAKE_WINDOW: f64 = 0.8;
+
+/// Whether the player allowed access to motion sensors
+///
+/// iOS asks once per page after a tap; other browsers grant access silently.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MotionPermission {
+    /// Not asked yet (or the browser doesn't need to ask)
+    #[default]
+    Unknown,
+    /// Sensors are available
+    Granted,
+    /// The player declined
+    Denied,
+}
+
+/// Latest tilt, acceleration and rotation readings plus shake detection
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MotionState {
+    tilt: Option<Vec2>,
+    acceleration: Option<Vec3>,
+    rotation_rate: Option<Vec3>,
+    permission: MotionPermission,
+    /// Recent jolts as (time, direction), oldest first
+    jolts: VecDeque<(f64, Vec3)>,
+    shaken: bool,
+}
+
+impl MotionState {
+    /// Creates a state with no sensor readings
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a device orientation reading (degrees, as reported by the
+    /// browser's `deviceorientation` event)
+    ///
+    /// `gamma` is the left/right tilt and `beta` the front/back tilt. Both
+    /// are scaled so [`TILT_RANGE_DEGREES`] maps to full deflection.
+    pub fn set_orientation(&mut self, beta: f32, gamma: f32) {
+        if !beta.is_finite() || !gamma.is_finite() || self.permission == MotionPermission::Denied {
+            return;
+        }
+        self.tilt =
+            Some((Vec2::new(gamma, beta) / TILT_RANGE_DEGREES).clamp(-Vec2::ONE, Vec2::ONE));
+    }
+
+    /// Records a `devicemotion` reading at `time` seconds
+    ///
+    /// `acceleration` excludes gravity (m/s²); `rotation_rate` is the
+    /// gyroscope's alpha, beta and gamma rates in degrees per second.
+    pub fn record_motion(&mut self, acceleration: Vec3, rotation_rate: Vec3, time: f64) {
+        if self.permission == MotionPermission::Denied {
+            return;
+        }
+        if rotation_rate.is_finite() {
+            self.rotation_rate = Some(rotation_rate);
+        }
+        if !acceleration.is_finite() {
+            return;
+        }
+        self.acceleration = Some(acceleration);
+
+        while self
+            .jolts
+            .front()
+            .is_some_and(|&(at, _)| time - at > SHAKE_WINDOW)
+        {
+            let _ = self.jolts.pop_front();
+        }
+        if acceleration.length() < SHAKE_THRESHOLD {
+            return;
+        }
+        let direction = acceleration.normalize();
+        // Consecutive readings in the same direction are one jolt
+        if self
+            .jolts
+            .back()
+            .is_some_and(|&(_, last)| last.dot(direction) > 0.0)
+        {
+            return;
+        }
+        self.jolts.push_back((time, direction));
+        if self.jolts.len() >= SHAKE_JOLTS {
+            self.shaken = true;
+            self.jolts.clear();
+        }
+    }
+
+    /// Device tilt with each axis in -1.0..=1.0
+    ///
+    /// Returns `None` if the device has no orientation sensor (or the player
+    /// has not granted access), so callers can fall back to touch or keys.
+    #[must_use]
+    pub const fn tilt(&self) -> Option<Vec2> {
+        self.tilt
+    }
+
+    /// Latest acceleration without gravity (m/s²)
+    #[must_use]
+    pub const fn acceleration(&self) -> Option<Vec3> {
+        self.acceleration
+    }
+
+    /// Latest gyroscope rates (degrees per second)
+    #[must_use]
+    pub const fn rotation_rate(&self) -> Option<Vec3> {
+        self.rotation_rate
+    }
+
+    /// Returns true on the frame a shake completed
+    #[must_use]
+    pub const fn just_shaken(&self) -> bool {
+        self.shaken
+    }
+
+    /// Whether the player allowed motion sensors
+    #[must_use]
+    pub const fn permission(&self) -> MotionPermission {
+        self.permission
+    }
+
+    /// Records the answer to a permission prompt
+    ///
+    /// A refusal forgets every reading so games fall back to touch.
+    pub fn set_permission(&mut self, permission: MotionPermission) {
+        self.permission = permission;
+        if permission == MotionPermission::Denied {
+            self.clear();
+        }
+    }
+
+    /// Forgets all readings (e.g. the sensor was revoked)
+    pub fn clear(&mut self) {
+        self.tilt = None;
+        self.acceleration = None;
+        self.rotation_rate = None;
+        self.jolts.clear();
+        self.shaken = false;
+    }
+
+    /// Ends the frame, clearing the one-frame shake flag
+    pub fn advance_frame(&mut self) {
+        self.shaken = false;
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shake_needs_alternating_jolts() {
+        let mut motion = MotionState::new();
+        let left = Vec3::new(-20.0, 0.0, 0.0);
+        let right = Vec3::new(20.0, 0.0, 0.0);
+
+        // Holding one direction is a single jolt
+        motion.record_motion(left, Vec3::ZERO, 0.0);
+        motion.record_motion(left, Vec3::ZERO, 0.05);
+        motion.record_motion(Vec3::new(1.0, 0.0, 0.0), Vec3::ZERO, 0.1);
+        motion.record_motion(right, Vec3::ZERO, 0.2);
+        assert!(!motion.just_shaken());
+
+        motion.record_motion(left, Vec3::ZERO, 0.4);
+        assert!(motion.just_shaken());
+        assert_eq!(motion.acceleration(), Some(left));
+        motion.advance_frame();
+        assert!(!motion.just_shaken());
+    }
+
+    #[test]
+    fn test_slow_jolts_are_not_a_shake() {
+        let mut motion = MotionState::new();
+        for (i, x) in [20.0, -20.0, 20.0].into_iter().enumerate() {
+            motion.record_motion(Vec3::new(x, 0.0, 0.0), Vec3::ZERO, i as f64 * 0.5);
+        }
+        assert!(!motion.just_shaken());
+    }
+
+    #[test]
+    fn test_denied_permission_clears_readings() {
+        let mut motion = MotionState::new();
+        motion.set_orientation(10.0, 45.0);
+        motion.record_motion(Vec3::ZERO, Vec3::new(0.0, 90.0, 0.0), 0.0);
+        assert_eq!(
+            motion.tilt(),
+            Some(Vec2::new(1.0, 10.0 / TILT_RANGE_DEGREES))
+        );
+        assert_eq!(motion.rotation_rate(), Some(Vec3::new(0.0, 90.0, 0.0)));
+
+        motion.set_permission(MotionPermission::Denied);
+        assert_eq!(motion.tilt(), None);
+        motion.set_orientation(10.0, 45.0);
+        assert_eq!(motion.tilt(), None);
+        assert_eq!(motion.acceleration(), None);
+    }
+}
@@ -0,0 +1,444 @@
+//! Per-player device assignment for local multiplayer.
+//!
+//! A [`PlayerInputMap`] binds gamepads, keyboard key subsets, the mouse and
+//! touch to player slots. [`InputState::for_player`] returns a
+//! [`PlayerInput`] view that only sees the devices bound to that player.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ButtonState, GamepadState, InputAction, InputDevice, InputState, KeyCode, MouseButton,
+    TouchEvent,
+};
+
+/// Which keyboard keys a player may use
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum KeyboardBinding {
+    /// No keyboard access
+    #[default]
+    None,
+    /// The whole keyboard
+    All,
+    /// Only these keys
+    Keys(Vec<KeyCode>),
+}
+
+impl KeyboardBinding {
+    /// Checks whether the binding includes a key
+    #[must_use]
+    pub fn contains(&self, key: KeyCode) -> bool {
+        match self {
+            Self::None => false,
+            Self::All => true,
+            Self::Keys(keys) => keys.contains(&key),
+        }
+    }
+}
+
+/// Devices bound to one player
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlayerBinding {
+    /// Bound gamepad indices
+    pub gamepads: Vec<u32>,
+    /// Keyboard keys this player may use
+    pub keyboard: KeyboardBinding,
+    /// Whether this player uses the mouse
+    pub mouse: bool,
+    /// Whether this player uses touch
+    pub touch: bool,
+}
+
+impl PlayerBinding {
+    /// Creates a binding with no devices
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            gamepads: Vec::new(),
+            keyboard: KeyboardBinding::None,
+            mouse: false,
+            touch: false,
+        }
+    }
+
+    /// Checks whether a device belongs to this binding
+    #[must_use]
+    pub fn owns(&self, device: InputDevice) -> bool {
+        match device {
+            InputDevice::Gamepad(index) => self.gamepads.contains(&index),
+            InputDevice::Keyboard => self.keyboard != KeyboardBinding::None,
+            InputDevice::Mouse => self.mouse,
+            InputDevice::Touch => self.touch,
+        }
+    }
+}
+
+static UNBOUND: PlayerBinding = PlayerBinding::new();
+
+/// Maps devices to player slots
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlayerInputMap {
+    players: Vec<PlayerBinding>,
+}
+
+impl PlayerInputMap {
+    /// Creates a map with `count` unbound players
+    #[must_use]
+    pub fn new(count: usize) -> Self {
+        Self {
+            players: vec![PlayerBinding::new(); count],
+        }
+    }
+
+    /// Single player owning every device
+    #[must_use]
+    pub fn single_player() -> Self {
+        let mut map = Self::new(1);
+        for gamepad in 0..4 {
+            map.bind_gamepad(0, gamepad);
+        }
+        map.bind_keyboard(0);
+        map.bind_mouse(0);
+        map.bind_touch(0);
+        map
+    }
+
+    /// Two players sharing a keyboard: WASD + Space and arrows + Enter,
+    /// with gamepads 0 and 1
+    #[must_use]
+    pub fn shared_keyboard() -> Self {
+        let mut map = Self::new(2);
+        map.bind_keys(
+            0,
+            [
+                KeyCode::Letter('W'),
+                KeyCode::Letter('A'),
+                KeyCode::Letter('S'),
+                KeyCode::Letter('D'),
+                KeyCode::Space,
+            ],
+        );
+        map.bind_keys(
+            1,
+            [
+                KeyCode::Up,
+                KeyCode::Down,
+                KeyCode::Left,
+                KeyCode::Right,
+                KeyCode::Enter,
+            ],
+        );
+        map.bind_gamepad(0, 0);
+        map.bind_gamepad(1, 1);
+        map
+    }
+
+    fn slot(&mut self, player: usize) -> &mut PlayerBinding {
+        if player >= self.players.len() {
+            self.players.resize(player + 1, PlayerBinding::new());
+        }
+        &mut self.players[player]
+    }
+
+    /// Binds a gamepad to a player, removing it from any other player
+    pub fn bind_gamepad(&mut self, player: usize, gamepad: u32) {
+        for binding in &mut self.players {
+            binding.gamepads.retain(|&g| g != gamepad);
+        }
+        self.slot(player).gamepads.push(gamepad);
+    }
+
+    /// Gives the whole keyboard to a player
+    pub fn bind_keyboard(&mut self, player: usize) {
+        for binding in &mut self.players {
+            binding.keyboard = KeyboardBinding::None;
+        }
+        self.slot(player).keyboard = KeyboardBinding::All;
+    }
+
+    /// Binds keyboard keys to a player, removing them from any other player
+    pub fn bind_keys(&mut self, player: usize, keys: impl IntoIterator<Item = KeyCode>) {
+        let keys: Vec<KeyCode> = keys.into_iter().collect();
+        for binding in &mut self.players {
+            if binding.keyboard == KeyboardBinding::All {
+                binding.keyboard = KeyboardBinding::None;
+            } else if let KeyboardBinding::Keys(owned) = &mut binding.keyboard {
+                owned.retain(|k| !keys.contains(k));
+            }
+        }
+        let slot = self.slot(player);
+        let mut owned = match core::mem::take(&mut slot.keyboard) {
+            KeyboardBinding::Keys(owned) => owned,
+            KeyboardBinding::None | KeyboardBinding::All => Vec::new(),
+        };
+        for key in keys {
+            if !owned.contains(&key) {
+                owned.push(key);
+            }
+        }
+        slot.keyboard = KeyboardBinding::Keys(owned);
+    }
+
+    /// Gives the mouse to a player
+    pub fn bind_mouse(&mut self, player: usize) {
+        for binding in &mut self.players {
+            binding.mouse = false;
+        }
+        self.slot(player).mouse = true;
+    }
+
+    /// Gives touch input to a player
+    pub fn bind_touch(&mut self, player: usize) {
+        for binding in &mut self.players {
+            binding.touch = false;
+        }
+        self.slot(player).touch = true;
+    }
+
+    /// Returns the binding for a player
+    #[must_use]
+    pub fn binding(&self, player: usize) -> Option<&PlayerBinding> {
+        self.players.get(player)
+    }
+
+    /// Returns the player that owns a device
+    #[must_use]
+    pub fn player_for_device(&self, device: InputDevice) -> Option<usize> {
+        self.players.iter().position(|b| b.owns(device))
+    }
+
+    /// Returns the player that owns a key
+    #[must_use]
+    pub fn player_for_key(&self, key: KeyCode) -> Option<usize> {
+        self.players.iter().position(|b| b.keyboard.contains(key))
+    }
+
+    /// Number of player slots
+    #[must_use]
+    pub fn player_count(&self) -> usize {
+        self.players.len()
+    }
+}
+
+impl Default for PlayerInputMap {
+    fn default() -> Self {
+        Self::single_player()
+    }
+}
+
+/// Read-only view of input restricted to one player's devices
+#[derive(Debug, Clone, Copy)]
+pub struct PlayerInput<'a> {
+    input: &'a InputState,
+    binding: &'a PlayerBinding,
+    player: usize,
+}
+
+impl<'a> PlayerInput<'a> {
+    pub(crate) fn new(
+        input: &'a InputState,
+        binding: Option<&'a PlayerBinding>,
+        player: usize,
+    ) -> Self {
+        Self {
+            input,
+            binding: binding.unwrap_or(&UNBOUND),
+            player,
+        }
+    }
+
+    /// Player slot index
+    #[must_use]
+    pub const fn player(&self) -> usize {
+        self.player
+    }
+
+    /// Devices bound to this player
+    #[must_use]
+    pub const fn binding(&self) -> &'a PlayerBinding {
+        self.binding
+    }
+
+    /// Key state, or `Released` if the key belongs to another player
+    #[must_use]
+    pub fn key(&self, key: KeyCode) -> ButtonState {
+        if self.binding.keyboard.contains(key) {
+            self.input.key(key)
+        } else {
+            ButtonState::Released
+        }
+    }
+
+    /// Mouse button state, or `Released` if the mouse is not bound
+    #[must_use]
+    pub const fn mouse_button(&self, button: MouseButton) -> ButtonState {
+        if self.binding.mouse {
+            self.input.mouse_button(button)
+        } else {
+            ButtonState::Released
+        }
+    }
+
+    /// Touches, or an empty slice if touch is not bound
+    #[must_use]
+    pub fn touches(&self) -> &'a [TouchEvent] {
+        if self.binding.touch {
+            &self.input.touches
+        } else {
+            &[]
+        }
+    }
+
+    /// Connected gamepads bound to this player
+    pub fn gamepads(&self) -> impl Iterator<Item = &'a GamepadState> + 'a {
+        let input = self.input;
+        self.binding
+            .gamepads
+            .iter()
+            .filter_map(move |&i| input.gamepads.get(i as usize))
+            .filter(|g| g.connected)
+    }
+
+    /// First connected gamepad bound to this player
+    #[must_use]
+    pub fn gamepad(&self) -> Option<&'a GamepadState> {
+        self.gamepads().next()
+    }
+
+    /// Virtual button state (on-screen controls follow touch ownership)
+    #[must_use]
+    pub fn virtual_button(&self, name: &str) -> ButtonState {
+        if self.binding.touch {
+            self.input.virtual_button(name)
+        } else {
+            ButtonState::Released
+        }
+    }
+
+    /// Checks if an action is active using only this player's devices
+    #[must_use]
+    pub fn is_action_active(&self, action: &InputAction) -> bool {
+        action.keys.iter().any(|k| self.key(*k).is_down())
+            || action
+                .mouse_buttons
+                .iter()
+                .any(|b| self.mouse_button(*b).is_down())
+            || action
+                .gamepad_buttons
+                .iter()
+                .any(|b| self.gamepads().any(|g| g.button(*b).is_down()))
+            || action
+                .virtual_buttons
+                .iter()
+                .any(|name| self.virtual_button(name).is_down())
+    }
+
+    /// Checks if an action was just pressed using only this player's devices
+    #[must_use]
+    pub fn action_just_pressed(&self, action: &InputAction) -> bool {
+        action.keys.iter().any(|k| self.key(*k).just_pressed())
+            || action
+                .mouse_buttons
+                .iter()
+                .any(|b| self.mouse_button(*b).just_pressed())
+            || action
+                .gamepad_buttons
+                .iter()
+                .any(|b| self.gamepads().any(|g| g.button(*b).just_pressed()))
+            || action
+                .virtual_buttons
+                .iter()
+                .any(|name| self.virtual_button(name).just_pressed())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::GamepadButton;
+
+    fn jump() -> InputAction {
+        InputAction::new("jump")
+            .with_key(KeyCode::Space)
+            .with_key(KeyCode::Enter)
+            .with_gamepad_button(GamepadButton::South)
+    }
+
+    #[test]
+    fn test_shared_keyboard_split() {
+        let mut input = InputState::new();
+        input.set_player_map(PlayerInputMap::shared_keyboard());
+        input.set_key(KeyCode::Enter, ButtonState::JustPressed);
+
+        assert!(!input.for_player(0).is_action_active(&jump()));
+        assert!(input.for_player(1).is_action_active(&jump()));
+        assert!(input.for_player(1).action_just_pressed(&jump()));
+    }
+
+    #[test]
+    fn test_gamepad_assignment() {
+        let mut input = InputState::new();
+        input.set_player_map(PlayerInputMap::shared_keyboard());
+        input.gamepads[1].connected = true;
+        input.gamepads[1].buttons[GamepadButton::South as usize] = ButtonState::Pressed;
+
+        assert!(!input.for_player(0).is_action_active(&jump()));
+        assert!(input.for_player(1).is_action_active(&jump()));
+        assert!(input.for_player(0).gamepad().is_none());
+    }
+
+    #[test]
+    fn test_rebinding_moves_device() {
+        let mut map = PlayerInputMap::new(2);
+        map.bind_gamepad(0, 2);
+        map.bind_gamepad(1, 2);
+        assert_eq!(map.player_for_device(InputDevice::Gamepad(2)), Some(1));
+        assert!(map.binding(0).unwrap().gamepads.is_empty());
+    }
+
+    #[test]
+    fn test_bind_keys_moves_keys() {
+        let mut map = PlayerInputMap::new(2);
+        map.bind_keys(0, [KeyCode::Space]);
+        map.bind_keys(1, [KeyCode::Space]);
+        assert_eq!(map.player_for_key(KeyCode::Space), Some(1));
+    }
+
+    #[test]
+    fn test_bind_grows_slots() {
+        let mut map = PlayerInputMap::new(1);
+        map.bind_mouse(3);
+        assert_eq!(map.player_count(), 4);
+        assert_eq!(map.player_for_device(InputDevice::Mouse), Some(3));
+    }
+
+    #[test]
+    fn test_unknown_player_sees_nothing() {
+        let mut input = InputState::new();
+        input.set_key(KeyCode::Space, ButtonState::Pressed);
+        let view = input.for_player(7);
+        assert_eq!(view.key(KeyCode::Space), ButtonState::Released);
+        assert!(!view.is_action_active(&jump()));
+    }
+
+    #[test]
+    fn test_default_map_single_player() {
+        let mut input = InputState::new();
+        input.set_key(KeyCode::Space, ButtonState::Pressed);
+        input.touches.push(TouchEvent::new(glam::Vec2::ZERO));
+        let view = input.for_player(0);
+        assert!(view.is_action_active(&jump()));
+        assert_eq!(view.touches().len(), 1);
+    }
+
+    #[test]
+    fn test_touch_ownership() {
+        let mut input = InputState::new();
+        let mut map = PlayerInputMap::new(2);
+        map.bind_touch(1);
+        input.set_player_map(map);
+        input.touches.push(TouchEvent::new(glam::Vec2::ZERO));
+        assert!(input.for_player(0).touches().is_empty());
+        assert_eq!(input.for_player(1).touches().len(), 1);
+    }
+}
@@ -0,0 +1,407 @@
+//! On-screen touch controls: virtual joystick and buttons.
+//!
+//! Controls consume the active [`TouchEvent`]s from [`InputState`]. Virtual
+//! buttons publish their state by name so an [`InputAction`](crate::InputAction)
+//! bound with `with_virtual_button` reacts to them like any other device.
+
+use glam::Vec2;
+use jugar_core::Rect;
+use jugar_render::RenderCommand;
+use serde::{Deserialize, Serialize};
+
+use crate::{ButtonState, InputState, TouchEvent, TouchPhase};
+
+/// How the joystick origin is chosen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum JoystickMode {
+    /// Origin is fixed at the center of the region
+    Fixed,
+    /// Origin is wherever the touch starts inside the region
+    #[default]
+    Dynamic,
+}
+
+/// Touch joystick producing a 2D axis
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VirtualJoystick {
+    /// Screen region that accepts new touches
+    pub region: Rect,
+    /// Maximum knob travel in pixels
+    pub radius: f32,
+    /// Dead zone as a fraction of the radius (0.0 - 1.0)
+    pub dead_zone: f32,
+    /// Origin behavior
+    pub mode: JoystickMode,
+    /// Base color (RGBA)
+    pub base_color: [f32; 4],
+    /// Knob color (RGBA)
+    pub knob_color: [f32; 4],
+    origin: Vec2,
+    knob: Vec2,
+    touch_id: Option<u32>,
+    axis: Vec2,
+}
+
+impl VirtualJoystick {
+    /// Creates a joystick covering `region`
+    #[must_use]
+    pub fn new(region: Rect) -> Self {
+        let (cx, cy) = region.center();
+        let origin = Vec2::new(cx, cy);
+        Self {
+            region,
+            radius: 60.0,
+            dead_zone: 0.15,
+            mode: JoystickMode::default(),
+            base_color: [1.0, 1.0, 1.0, 0.25],
+            knob_color: [1.0, 1.0, 1.0, 0.6],
+            origin,
+            knob: origin,
+            touch_id: None,
+            axis: Vec2::ZERO,
+        }
+    }
+
+    /// Sets the knob travel radius
+    #[must_use]
+    pub const fn with_radius(mut self, radius: f32) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Sets the dead zone fraction
+    #[must_use]
+    pub const fn with_dead_zone(mut self, dead_zone: f32) -> Self {
+        self.dead_zone = dead_zone;
+        self
+    }
+
+    /// Sets the origin mode
+    #[must_use]
+    pub const fn with_mode(mut self, mode: JoystickMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Updates from the current touches and returns the axis
+    pub fn update(&mut self, input: &InputState) -> Vec2 {
+        self.update_touches(&input.touches)
+    }
+
+    /// Updates from a list of touches and returns the axis
+    pub fn update_touches(&mut self, touches: &[TouchEvent]) -> Vec2 {
+        if let Some(id) = self.touch_id {
+            match touches.iter().find(|t| t.id == id) {
+                Some(touch) if !is_finished(touch.phase) => self.knob = touch.position,
+                _ => self.release(),
+            }
+        } else {
+            let started = touches.iter().find(|t| {
+                t.phase == TouchPhase::Started
+                    && self.region.contains_point(t.position.x, t.position.y)
+            });
+            if let Some(touch) = started {
+                self.touch_id = Some(touch.id);
+                if self.mode == JoystickMode::Dynamic {
+                    self.origin = touch.position;
+                }
+                self.knob = touch.position;
+            }
+        }
+
+        self.axis = self.compute_axis();
+        self.axis
+    }
+
+    fn release(&mut self) {
+        self.touch_id = None;
+        let (cx, cy) = self.region.center();
+        self.origin = Vec2::new(cx, cy);
+        self.knob = self.origin;
+    }
+
+    fn compute_axis(&self) -> Vec2 {
+        if self.touch_id.is_none() || self.radius <= 0.0 {
+            return Vec2::ZERO;
+        }
+        let offset = (self.knob - self.origin).clamp_length_max(self.radius);
+        let magnitude = offset.length() / self.radius;
+        if magnitude <= self.dead_zone {
+            return Vec2::ZERO;
+        }
+        let scaled = (magnitude - self.dead_zone) / (1.0 - self.dead_zone).max(f32::EPSILON);
+        offset.normalize_or_zero() * scaled.min(1.0)
+    }
+
+    /// Returns the current axis (-1.0 to 1.0 per component, screen space)
+    #[must_use]
+    pub const fn axis(&self) -> Vec2 {
+        self.axis
+    }
+
+    /// Returns true while a touch is controlling the joystick
+    #[must_use]
+    pub const fn is_active(&self) -> bool {
+        self.touch_id.is_some()
+    }
+
+    /// Returns the current joystick origin
+    #[must_use]
+    pub const fn origin(&self) -> Vec2 {
+        self.origin
+    }
+
+    /// Returns the knob center, clamped to the travel radius
+    #[must_use]
+    pub fn knob_position(&self) -> Vec2 {
+        self.origin + (self.knob - self.origin).clamp_length_max(self.radius)
+    }
+
+    /// Generates render commands for the base and knob
+    #[must_use]
+    pub fn render_commands(&self) -> Vec<RenderCommand> {
+        let knob_size = self.radius * 0.5;
+        vec![
+            square_command(self.origin, self.radius, self.base_color),
+            square_command(self.knob_position(), knob_size, self.knob_color),
+        ]
+    }
+}
+
+/// Round on-screen button bound to a named virtual input
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VirtualButton {
+    /// Name used by `InputAction::with_virtual_button`
+    pub name: String,
+    /// Button center in screen coordinates
+    pub center: Vec2,
+    /// Touch radius
+    pub radius: f32,
+    /// Color when released (RGBA)
+    pub color: [f32; 4],
+    /// Color when pressed (RGBA)
+    pub pressed_color: [f32; 4],
+    touch_id: Option<u32>,
+    state: ButtonState,
+}
+
+impl VirtualButton {
+    /// Creates a new virtual button
+    #[must_use]
+    pub fn new(name: impl Into<String>, center: Vec2, radius: f32) -> Self {
+        Self {
+            name: name.into(),
+            center,
+            radius,
+            color: [1.0, 1.0, 1.0, 0.3],
+            pressed_color: [1.0, 1.0, 1.0, 0.7],
+            touch_id: None,
+            state: ButtonState::Released,
+        }
+    }
+
+    /// Sets the released and pressed colors
+    #[must_use]
+    pub const fn with_colors(mut self, color: [f32; 4], pressed_color: [f32; 4]) -> Self {
+        self.color = color;
+        self.pressed_color = pressed_color;
+        self
+    }
+
+    /// Checks if a point lies on the button
+    #[must_use]
+    pub fn contains(&self, point: Vec2) -> bool {
+        point.distance_squared(self.center) <= self.radius * self.radius
+    }
+
+    /// Updates from the current touches and publishes the state to `input`
+    pub fn update(&mut self, input: &mut InputState) -> ButtonState {
+        let state = self.update_touches(&input.touches);
+        input.set_virtual_button(self.name.clone(), state);
+        state
+    }
+
+    /// Updates from a list of touches and returns the button state
+    pub fn update_touches(&mut self, touches: &[TouchEvent]) -> ButtonState {
+        let held = if let Some(id) = self.touch_id {
+            touches
+                .iter()
+                .any(|t| t.id == id && !is_finished(t.phase) && self.contains(t.position))
+        } else {
+            let started = touches
+                .iter()
+                .find(|t| t.phase == TouchPhase::Started && self.contains(t.position));
+            self.touch_id = started.map(|t| t.id);
+            started.is_some()
+        };
+        if !held {
+            self.touch_id = None;
+        }
+
+        self.state = match (held, self.state.is_down()) {
+            (true, false) => ButtonState::JustPressed,
+            (true, true) => ButtonState::Pressed,
+            (false, true) => ButtonState::JustReleased,
+            (false, false) => ButtonState::Released,
+        };
+        self.state
+    }
+
+    /// Returns the current state
+    #[must_use]
+    pub const fn state(&self) -> ButtonState {
+        self.state
+    }
+
+    /// Generates render commands for the button
+    #[must_use]
+    pub fn render_commands(&self) -> Vec<RenderCommand> {
+        let color = if self.state.is_down() {
+            self.pressed_color
+        } else {
+            self.color
+        };
+        vec![RenderCommand::DrawCircle {
+            center: self.center,
+            radius: self.radius,
+            color,
+            outline: None,
+        }]
+    }
+}
+
+const fn is_finished(phase: TouchPhase) -> bool {
+    matches!(phase, TouchPhase::Ended | TouchPhase::Cancelled)
+}
+
+fn square_command(center: Vec2, half_extent: f32, color: [f32; 4]) -> RenderCommand {
+    let size = half_extent * 2.0;
+    RenderCommand::DrawRect {
+        rect: Rect::new(center.x - half_extent, center.y - half_extent, size, size),
+        color,
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::InputAction;
+
+    fn touch(id: u32, x: f32, y: f32, phase: TouchPhase) -> TouchEvent {
+        TouchEvent::new(Vec2::new(x, y))
+            .with_id(id)
+            .with_phase(phase)
+    }
+
+    // ==================== JOYSTICK TESTS ====================
+
+    #[test]
+    fn test_joystick_idle() {
+        let mut stick = VirtualJoystick::new(Rect::new(0.0, 0.0, 200.0, 200.0));
+        assert_eq!(stick.update_touches(&[]), Vec2::ZERO);
+        assert!(!stick.is_active());
+    }
+
+    #[test]
+    fn test_joystick_dynamic_origin() {
+        let mut stick = VirtualJoystick::new(Rect::new(0.0, 0.0, 200.0, 200.0));
+        let _ = stick.update_touches(&[touch(1, 50.0, 50.0, TouchPhase::Started)]);
+        assert_eq!(stick.origin(), Vec2::new(50.0, 50.0));
+        let axis = stick.update_touches(&[touch(1, 110.0, 50.0, TouchPhase::Moved)]);
+        assert!((axis.x - 1.0).abs() < 0.001);
+        assert!(axis.y.abs() < 0.001);
+    }
+
+    #[test]
+    fn test_joystick_fixed_origin() {
+        let mut stick = VirtualJoystick::new(Rect::new(0.0, 0.0, 200.0, 200.0))
+            .with_mode(JoystickMode::Fixed)
+            .with_dead_zone(0.0);
+        let axis = stick.update_touches(&[touch(1, 100.0, 130.0, TouchPhase::Started)]);
+        assert_eq!(stick.origin(), Vec2::new(100.0, 100.0));
+        assert!((axis.y - 0.5).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_joystick_dead_zone() {
+        let mut stick = VirtualJoystick::new(Rect::new(0.0, 0.0, 200.0, 200.0))
+            .with_mode(JoystickMode::Fixed)
+            .with_dead_zone(0.2);
+        let axis = stick.update_touches(&[touch(1, 105.0, 100.0, TouchPhase::Started)]);
+        assert_eq!(axis, Vec2::ZERO);
+        assert!(stick.is_active());
+    }
+
+    #[test]
+    fn test_joystick_ignores_touch_outside_region() {
+        let mut stick = VirtualJoystick::new(Rect::new(0.0, 0.0, 100.0, 100.0));
+        let _ = stick.update_touches(&[touch(1, 300.0, 300.0, TouchPhase::Started)]);
+        assert!(!stick.is_active());
+    }
+
+    #[test]
+    fn test_joystick_release() {
+        let mut stick = VirtualJoystick::new(Rect::new(0.0, 0.0, 200.0, 200.0));
+        let _ = stick.update_touches(&[touch(1, 50.0, 50.0, TouchPhase::Started)]);
+        let _ = stick.update_touches(&[touch(1, 100.0, 50.0, TouchPhase::Moved)]);
+        let axis = stick.update_touches(&[touch(1, 100.0, 50.0, TouchPhase::Ended)]);
+        assert_eq!(axis, Vec2::ZERO);
+        assert!(!stick.is_active());
+    }
+
+    #[test]
+    fn test_joystick_render_commands() {
+        let stick = VirtualJoystick::new(Rect::new(0.0, 0.0, 200.0, 200.0));
+        assert_eq!(stick.render_commands().len(), 2);
+    }
+
+    // ==================== BUTTON TESTS ====================
+
+    #[test]
+    fn test_virtual_button_press_cycle() {
+        let mut button = VirtualButton::new("jump", Vec2::new(300.0, 300.0), 40.0);
+        let started = [touch(2, 310.0, 300.0, TouchPhase::Started)];
+        let moved = [touch(2, 310.0, 300.0, TouchPhase::Moved)];
+        assert_eq!(button.update_touches(&started), ButtonState::JustPressed);
+        assert_eq!(button.update_touches(&moved), ButtonState::Pressed);
+        assert_eq!(button.update_touches(&[]), ButtonState::JustReleased);
+        assert_eq!(button.update_touches(&[]), ButtonState::Released);
+    }
+
+    #[test]
+    fn test_virtual_button_miss() {
+        let mut button = VirtualButton::new("jump", Vec2::new(300.0, 300.0), 40.0);
+        let state = button.update_touches(&[touch(1, 10.0, 10.0, TouchPhase::Started)]);
+        assert_eq!(state, ButtonState::Released);
+    }
+
+    #[test]
+    fn test_virtual_button_drives_action() {
+        let mut input = InputState::new();
+        input
+            .touches
+            .push(touch(3, 300.0, 300.0, TouchPhase::Started));
+        let mut button = VirtualButton::new("jump", Vec2::new(300.0, 300.0), 40.0);
+        let _ = button.update(&mut input);
+
+        let action = InputAction::new("jump").with_virtual_button("jump");
+        assert!(action.is_active(&input));
+        assert!(input.virtual_button("jump").just_pressed());
+    }
+
+    #[test]
+    fn test_virtual_button_render_color() {
+        let mut button = VirtualButton::new("a", Vec2::ZERO, 10.0)
+            .with_colors([0.0, 0.0, 0.0, 1.0], [1.0, 1.0, 1.0, 1.0]);
+        let _ = button.update_touches(&[touch(1, 0.0, 0.0, TouchPhase::Started)]);
+        let commands = button.render_commands();
+        assert!(matches!(
+            commands[0],
+            RenderCommand::DrawCircle { center, radius, color, outline: None }
+                if center == Vec2::ZERO
+                    && (radius - 10.0).abs() < f32::EPSILON
+                    && color.iter().all(|c| (c - 1.0).abs() < f32::EPSILON)
+        ));
+    }
+}
@@ -0,0 +1,42 @@
+//! Benchmarks for the physics step at increasing body counts.
+
+#![allow(missing_docs, unused_results)]
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use jugar_core::{Position, Velocity};
+use jugar_physics::{PhysicsWorld, RigidBody, Shape};
+
+/// A grid of small circles falling onto a floor, so the broadphase and
+/// contact solver both have work
+fn world_with_bodies(count: usize) -> PhysicsWorld {
+    let mut world = PhysicsWorld::new();
+    let _ = world.add_body(
+        RigidBody::new_static(Position::new(0.0, -1.0)).with_shape(Shape::rect(10_000.0, 2.0)),
+    );
+    let columns = (count as f32).sqrt().ceil() as usize;
+    for i in 0..count {
+        let x = (i % columns) as f32 * 1.5;
+        let y = ((i / columns) as f32).mul_add(1.5, 1.0);
+        let body = RigidBody::new(Position::new(x, y))
+            .with_velocity(Velocity::new(0.5, 0.0))
+            .with_shape(Shape::circle(0.5));
+        let _ = world.add_body(body);
+    }
+    world
+}
+
+fn bench_physics_step(c: &mut Criterion) {
+    let mut group = c.benchmark_group("physics_step");
+
+    for count in &[100, 1000, 10000] {
+        let mut world = world_with_bodies(*count);
+        group.bench_with_input(BenchmarkId::new("bodies", count), count, |b, _| {
+            b.iter(|| world.step(black_box(1.0 / 60.0)));
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_physics_step);
+criterion_main!(benches);
@@ -0,0 +1,313 @@
+//! Collision shapes, layer filtering and narrowphase contact generation.
+
+use glam::Vec2;
+use serde::{Deserialize, Serialize};
+
+use crate::BodyHandle;
+
+/// Collision shape centered on the body position
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Shape {
+    /// Circle with a radius
+    Circle {
+        /// Radius
+        radius: f32,
+    },
+    /// Axis-aligned rectangle
+    Rect {
+        /// Half width and half height
+        half_extents: Vec2,
+    },
+}
+
+impl Shape {
+    /// Creates a circle shape
+    #[must_use]
+    pub const fn circle(radius: f32) -> Self {
+        Self::Circle { radius }
+    }
+
+    /// Creates a rectangle shape from its full size
+    #[must_use]
+    pub fn rect(width: f32, height: f32) -> Self {
+        Self::Rect {
+            half_extents: Vec2::new(width * 0.5, height * 0.5),
+        }
+    }
+
+    /// Returns the (min, max) bounds of the shape at `center`
+    #[must_use]
+    pub fn bounds(&self, center: Vec2) -> (Vec2, Vec2) {
+        let half = match *self {
+            Self::Circle { radius } => Vec2::splat(radius),
+            Self::Rect { half_extents } => half_extents,
+        };
+        (center - half, center + half)
+    }
+
+    /// Area of the shape
+    #[must_use]
+    pub fn area(&self) -> f32 {
+        match *self {
+            Self::Circle { radius } => core::f32::consts::PI * radius * radius,
+            Self::Rect { half_extents } => 4.0 * half_extents.x * half_extents.y,
+        }
+    }
+
+    /// Returns true if `point` lies inside the shape at `center`
+    #[must_use]
+    pub fn contains(&self, center: Vec2, point: Vec2) -> bool {
+        match *self {
+            Self::Circle { radius } => center.distance_squared(point) <= radius * radius,
+            Self::Rect { half_extents } => {
+                let offset = (point - center).abs();
+                offset.x <= half_extents.x && offset.y <= half_extents.y
+            }
+        }
+    }
+
+    /// Fraction along the segment `from`-`to` where it enters the shape
+    ///
+    /// Returns `None` if the segment misses the shape or starts inside it.
+    #[must_use]
+    pub fn ray_entry(&self, center: Vec2, from: Vec2, to: Vec2) -> Option<f32> {
+        if self.contains(center, from) {
+            return None;
+        }
+        let direction = to - from;
+        let entry = match *self {
+            Self::Circle { radius } => {
+                let a = direction.length_squared();
+                let offset = from - center;
+                let b = offset.dot(direction);
+                let c = radius.mul_add(-radius, offset.length_squared());
+                let discriminant = b.mul_add(b, -a * c);
+                if a <= f32::EPSILON || discriminant < 0.0 {
+                    return None;
+                }
+                (-b - discriminant.sqrt()) / a
+            }
+            Self::Rect { half_extents } => {
+                let (min, max) = (center - half_extents, center + half_extents);
+                let mut near = f32::NEG_INFINITY;
+                let mut far = f32::INFINITY;
+                for axis in 0..2 {
+                    if direction[axis].abs() <= f32::EPSILON {
+                        if from[axis] < min[axis] || from[axis] > max[axis] {
+                            return None;
+                        }
+                        continue;
+                    }
+                    let t1 = (min[axis] - from[axis]) / direction[axis];
+                    let t2 = (max[axis] - from[axis]) / direction[axis];
+                    near = near.max(t1.min(t2));
+                    far = far.min(t1.max(t2));
+                }
+                if near > far {
+                    return None;
+                }
+                near
+            }
+        };
+        (0.0..=1.0).contains(&entry).then_some(entry)
+    }
+}
+
+/// Collision layer membership and mask bits
+///
+/// Two bodies collide only if each one's membership overlaps the other's
+/// mask, e.g. players on bit 0 and enemies on bit 1 with enemy masks that
+/// exclude bit 1 lets enemies pass through each other but not the player.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CollisionLayers {
+    /// Layers this body belongs to
+    pub membership: u32,
+    /// Layers this body collides with
+    pub mask: u32,
+}
+
+impl CollisionLayers {
+    /// Belongs to and collides with everything
+    pub const ALL: Self = Self {
+        membership: u32::MAX,
+        mask: u32::MAX,
+    };
+
+    /// Collides with nothing
+    pub const NONE: Self = Self {
+        membership: 0,
+        mask: 0,
+    };
+
+    /// Creates layers from membership and mask bits
+    #[must_use]
+    pub const fn new(membership: u32, mask: u32) -> Self {
+        Self { membership, mask }
+    }
+
+    /// Returns true if the two bodies may collide
+    #[must_use]
+    pub const fn interacts(&self, other: &Self) -> bool {
+        self.membership & other.mask != 0 && other.membership & self.mask != 0
+    }
+}
+
+impl Default for CollisionLayers {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+/// A contact between two bodies found during a step
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Contact {
+    /// First body
+    pub a: BodyHandle,
+    /// Second body
+    pub b: BodyHandle,
+    /// Unit normal pointing from `a` to `b`
+    pub normal: Vec2,
+    /// Penetration depth
+    pub depth: f32,
+}
+
+/// The first body crossed by a raycast
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RaycastHit {
+    /// Body that was hit
+    pub body: BodyHandle,
+    /// World position where the ray entered the body
+    pub point: Vec2,
+    /// Fraction of the way from the start to the end of the ray (0.0 - 1.0)
+    pub fraction: f32,
+}
+
+/// Events published by the physics world each step
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PhysicsEvent {
+    /// A body started overlapping a sensor
+    TriggerEnter {
+        /// Sensor body
+        sensor: BodyHandle,
+        /// Body that entered
+        other: BodyHandle,
+    },
+    /// A body stopped overlapping a sensor
+    TriggerExit {
+        /// Sensor body
+        sensor: BodyHandle,
+        /// Body that left
+        other: BodyHandle,
+    },
+}
+
+/// Tests two shapes for overlap, returning (normal from a to b, depth)
+#[must_use]
+pub fn collide(shape_a: &Shape, pos_a: Vec2, shape_b: &Shape, pos_b: Vec2) -> Option<(Vec2, f32)> {
+    match (*shape_a, *shape_b) {
+        (Shape::Circle { radius: ra }, Shape::Circle { radius: rb }) => {
+            let delta = pos_b - pos_a;
+            let distance = delta.length();
+            let depth = ra + rb - distance;
+            if depth <= 0.0 {
+                return None;
+            }
+            let normal = if distance > f32::EPSILON {
+                delta / distance
+            } else {
+                Vec2::Y
+            };
+            Some((normal, depth))
+        }
+        (Shape::Rect { half_extents: ha }, Shape::Rect { half_extents: hb }) => {
+            let delta = pos_b - pos_a;
+            let overlap = ha + hb - delta.abs();
+            if overlap.x <= 0.0 || overlap.y <= 0.0 {
+                return None;
+            }
+            if overlap.x < overlap.y {
+                Some((Vec2::new(sign(delta.x), 0.0), overlap.x))
+            } else {
+                Some((Vec2::new(0.0, sign(delta.y)), overlap.y))
+            }
+        }
+        (Shape::Circle { radius }, Shape::Rect { half_extents }) => {
+            circle_rect(pos_a, radius, pos_b, half_extents)
+        }
+        (Shape::Rect { half_extents }, Shape::Circle { radius }) => {
+            circle_rect(pos_b, radius, pos_a, half_extents).map(|(n, d)| (-n, d))
+        }
+    }
+}
+
+fn sign(value: f32) -> f32 {
+    if value < 0.0 {
+        -1.0
+    } else {
+        1.0
+    }
+}
+
+/// Circle vs rectangle, normal from circle to rectangle
+fn circle_rect(circle: Vec2, radius: f32, rect: Vec2, half: Vec2) -> Option<(Vec2, f32)> {
+    let local = circle - rect;
+    let clamped = local.clamp(-half, half);
+    if clamped == local {
+        // Circle center inside the rectangle: push out along the nearest face
+        let gap = half - local.abs();
+        return if gap.x < gap.y {
+            Some((Vec2::new(-sign(local.x), 0.0), gap.x + radius))
+        } else {
+            Some((Vec2::new(0.0, -sign(local.y)), gap.y + radius))
+        };
+    }
+    let delta = clamped - local;
+    let distance = delta.length();
+    if distance >= radius {
+        return None;
+    }
+    Some((delta / distance, radius - distance))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_circle_circle() {
+        let a = Shape::circle(1.0);
+        let (normal, depth) = collide(&a, Vec2::ZERO, &a, Vec2::new(1.5, 0.0)).unwrap();
+        assert_eq!(normal, Vec2::X);
+        assert!((depth - 0.5).abs() < 0.001);
+        assert!(collide(&a, Vec2::ZERO, &a, Vec2::new(3.0, 0.0)).is_none());
+    }
+
+    #[test]
+    fn test_rect_rect_min_axis() {
+        let a = Shape::rect(2.0, 2.0);
+        let (normal, depth) = collide(&a, Vec2::ZERO, &a, Vec2::new(0.5, 1.8)).unwrap();
+        assert_eq!(normal, Vec2::Y);
+        assert!((depth - 0.2).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_circle_rect_both_orders() {
+        let circle = Shape::circle(1.0);
+        let rect = Shape::rect(4.0, 2.0);
+        let (normal, depth) = collide(&circle, Vec2::new(0.0, 1.5), &rect, Vec2::ZERO).unwrap();
+        assert_eq!(normal, Vec2::new(0.0, -1.0));
+        assert!((depth - 0.5).abs() < 0.001);
+        let (normal, _) = collide(&rect, Vec2::ZERO, &circle, Vec2::new(0.0, 1.5)).unwrap();
+        assert_eq!(normal, Vec2::Y);
+    }
+
+    #[test]
+    fn test_layers_interact() {
+        let player = CollisionLayers::new(0b01, 0b11);
+        let enemy = CollisionLayers::new(0b10, 0b01);
+        assert!(player.interacts(&enemy));
+        assert!(!enemy.interacts(&enemy));
+        assert!(!CollisionLayers::NONE.interacts(&CollisionLayers::ALL));
+    }
+}
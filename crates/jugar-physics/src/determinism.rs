@@ -0,0 +1,217 @@
+//! Determinism checks: stable state hashing and canned scenarios.
+//!
+//! [`PhysicsWorld::state_hash`] folds every body, rope and soft body
+//! particle into a 64-bit FNV-1a hash of the raw `f32` bits, so two runs
+//! that agree bit-for-bit produce the same hash on every platform. The
+//! [`Scenario`]s below are fixed worlds that tests step for N frames and
+//! compare, which catches regressions like iteration-order changes in the
+//! broadphase.
+
+use glam::Vec2;
+use jugar_core::{Position, Velocity};
+
+use crate::{FluidZone, PhysicsBackend, PhysicsWorld, RigidBody, Rope, Shape, SoftBody};
+
+/// Fixed timestep used by all scenarios
+pub const SCENARIO_DT: f32 = 1.0 / 60.0;
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+/// FNV-1a hasher with a fixed byte order
+#[derive(Debug, Clone, Copy)]
+pub struct StateHasher(u64);
+
+impl StateHasher {
+    pub const fn new() -> Self {
+        Self(FNV_OFFSET)
+    }
+
+    pub fn write_u32(&mut self, value: u32) {
+        for byte in value.to_le_bytes() {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    /// Hashes the bits of a float, treating -0.0 as 0.0
+    pub fn write_f32(&mut self, value: f32) {
+        self.write_u32((value + 0.0).to_bits());
+    }
+
+    pub fn write_vec2(&mut self, value: Vec2) {
+        self.write_f32(value.x);
+        self.write_f32(value.y);
+    }
+
+    pub const fn finish(self) -> u64 {
+        self.0
+    }
+}
+
+/// Canned worlds for determinism tests
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Scenario {
+    /// Stack of boxes settling on a floor
+    Pyramid,
+    /// Circles colliding on a frictionless table
+    Billiards,
+    /// Pinned rope bridge with a soft ball dropped on it
+    RopeBridge,
+    /// Boxes of varying mass dropped into water
+    FloatingCrates,
+}
+
+impl Scenario {
+    /// All scenarios
+    pub const ALL: [Self; 4] = [
+        Self::Pyramid,
+        Self::Billiards,
+        Self::RopeBridge,
+        Self::FloatingCrates,
+    ];
+
+    /// Short name for reports
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Pyramid => "pyramid",
+            Self::Billiards => "billiards",
+            Self::RopeBridge => "rope_bridge",
+            Self::FloatingCrates => "floating_crates",
+        }
+    }
+
+    /// Builds the scenario's initial world
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn build(self) -> PhysicsWorld {
+        let mut world = PhysicsWorld::with_backend(PhysicsBackend::Scalar);
+        match self {
+            Self::Pyramid => {
+                let _ = world.add_body(
+                    RigidBody::new_static(Position::new(0.0, -0.5))
+                        .with_shape(Shape::rect(20.0, 1.0)),
+                );
+                for row in 0..4 {
+                    for col in 0..(4 - row) {
+                        let x = (col as f32).mul_add(1.05, row as f32 * 0.525) - 1.6;
+                        let y = (row as f32).mul_add(1.05, 0.6);
+                        let _ = world.add_body(
+                            RigidBody::new(Position::new(x, y)).with_shape(Shape::rect(1.0, 1.0)),
+                        );
+                    }
+                }
+            }
+            Self::Billiards => {
+                world.set_gravity(Vec2::ZERO);
+                let _ = world.add_body(
+                    RigidBody::new(Position::new(-6.0, 0.1))
+                        .with_velocity(Velocity::new(12.0, 0.0))
+                        .with_shape(Shape::circle(0.5)),
+                );
+                for row in 0..3 {
+                    for i in 0..=row {
+                        let x = (row as f32).mul_add(0.9, 2.0);
+                        // Exact either way: halves of small integers
+                        let y = (row as f32).mul_add(-0.5, i as f32) * 1.02;
+                        let _ = world.add_body(
+                            RigidBody::new(Position::new(x, y)).with_shape(Shape::circle(0.5)),
+                        );
+                    }
+                }
+            }
+            Self::RopeBridge => {
+                if let Ok(rope) = Rope::new(Vec2::new(-5.0, 0.0), Vec2::new(5.0, 0.0), 20) {
+                    let _ = world.add_rope(rope.pinned_ends());
+                }
+                if let Ok(ball) = SoftBody::circle(Vec2::new(0.0, 3.0), 0.8, 12) {
+                    let _ = world.add_soft_body(ball);
+                }
+                let _ = world.add_body(
+                    RigidBody::new_static(Position::new(0.0, -4.0))
+                        .with_shape(Shape::rect(20.0, 1.0)),
+                );
+            }
+            Self::FloatingCrates => {
+                let _ = world.add_fluid(FluidZone::water(
+                    Vec2::new(-10.0, -10.0),
+                    Vec2::new(10.0, 0.0),
+                ));
+                for i in 0..5 {
+                    let mass = (i as f32).mul_add(0.4, 0.2);
+                    let _ = world.add_body(
+                        RigidBody::new(Position::new((i as f32).mul_add(2.0, -4.0), 3.0))
+                            .with_mass(mass)
+                            .with_shape(Shape::rect(1.0, 1.0)),
+                    );
+                }
+            }
+        }
+        world
+    }
+
+    /// Steps the scenario `frames` times and returns the final state hash
+    #[must_use]
+    pub fn run(self, frames: u32) -> u64 {
+        let mut world = self.build();
+        for _ in 0..frames {
+            let _ = world.step(SCENARIO_DT);
+        }
+        world.state_hash()
+    }
+
+    /// Hashes after every `interval` frames (for locating a divergence)
+    #[must_use]
+    pub fn trace(self, frames: u32, interval: u32) -> Vec<u64> {
+        let interval = interval.max(1);
+        let mut world = self.build();
+        let mut hashes = Vec::new();
+        for frame in 1..=frames {
+            let _ = world.step(SCENARIO_DT);
+            if frame % interval == 0 {
+                hashes.push(world.state_hash());
+            }
+        }
+        hashes
+    }
+}
+
+/// Index of the first differing hash between two traces
+#[must_use]
+pub fn first_divergence(a: &[u64], b: &[u64]) -> Option<usize> {
+    a.iter()
+        .zip(b)
+        .position(|(x, y)| x != y)
+        .or_else(|| (a.len() != b.len()).then_some(a.len().min(b.len())))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hasher_normalizes_negative_zero() {
+        let mut a = StateHasher::new();
+        a.write_f32(0.0);
+        let mut b = StateHasher::new();
+        b.write_f32(-0.0);
+        assert_eq!(a.finish(), b.finish());
+    }
+
+    #[test]
+    fn test_first_divergence() {
+        assert_eq!(first_divergence(&[1, 2, 3], &[1, 2, 3]), None);
+        assert_eq!(first_divergence(&[1, 2, 3], &[1, 5, 3]), Some(1));
+        assert_eq!(first_divergence(&[1, 2], &[1, 2, 3]), Some(2));
+    }
+
+    #[test]
+    fn test_scenario_names_unique() {
+        let mut names: Vec<_> = Scenario::ALL.iter().map(|s| s.name()).collect();
+        names.sort_unstable();
+        names.dedup();
+        assert_eq!(names.len(), Scenario::ALL.len());
+    }
+}
@@ -0,0 +1,220 @@
+//! Fluid volumes that apply buoyancy and drag.
+//!
+//! A [`FluidZone`] is a region of water, air current or similar. Dynamic
+//! bodies overlapping it are pushed up in proportion to the fluid they
+//! displace (Archimedes) and dragged toward the zone's flow velocity, so a
+//! density of zero with a flow makes a wind tunnel.
+
+use glam::Vec2;
+use serde::{Deserialize, Serialize};
+
+/// Samples per axis when estimating overlap with a polygon region
+const POLYGON_SAMPLES: u32 = 4;
+
+/// Handle to a fluid zone in the physics world
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FluidHandle(pub u32);
+
+/// Area covered by a fluid zone
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum FluidRegion {
+    /// Axis-aligned rectangle
+    Rect {
+        /// Lower-left corner
+        min: Vec2,
+        /// Upper-right corner
+        max: Vec2,
+    },
+    /// Simple polygon (vertices in order)
+    Polygon(Vec<Vec2>),
+}
+
+impl FluidRegion {
+    /// Returns true if the point is inside the region
+    #[must_use]
+    pub fn contains(&self, point: Vec2) -> bool {
+        match self {
+            Self::Rect { min, max } => point.cmpge(*min).all() && point.cmple(*max).all(),
+            Self::Polygon(vertices) => {
+                // Even-odd ray casting
+                let mut inside = false;
+                let mut j = vertices.len().wrapping_sub(1);
+                for (i, a) in vertices.iter().enumerate() {
+                    let b = vertices[j];
+                    if (a.y > point.y) != (b.y > point.y)
+                        && point.x < (b.x - a.x) * (point.y - a.y) / (b.y - a.y) + a.x
+                    {
+                        inside = !inside;
+                    }
+                    j = i;
+                }
+                inside
+            }
+        }
+    }
+
+    /// Fraction (0-1) of the box `min..max` inside the region
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn coverage(&self, min: Vec2, max: Vec2) -> f32 {
+        let size = max - min;
+        if size.x <= 0.0 || size.y <= 0.0 {
+            return if self.contains(min) { 1.0 } else { 0.0 };
+        }
+        match self {
+            Self::Rect {
+                min: rmin,
+                max: rmax,
+            } => {
+                let overlap = (max.min(*rmax) - min.max(*rmin)).max(Vec2::ZERO);
+                (overlap.x * overlap.y) / (size.x * size.y)
+            }
+            Self::Polygon(_) => {
+                let step = size / POLYGON_SAMPLES as f32;
+                let mut inside = 0;
+                for y in 0..POLYGON_SAMPLES {
+                    for x in 0..POLYGON_SAMPLES {
+                        let offset = Vec2::new(x as f32 + 0.5, y as f32 + 0.5) * step;
+                        if self.contains(min + offset) {
+                            inside += 1;
+                        }
+                    }
+                }
+                inside as f32 / (POLYGON_SAMPLES * POLYGON_SAMPLES) as f32
+            }
+        }
+    }
+}
+
+/// A volume of fluid with density and flow
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FluidZone {
+    /// Region covered by the fluid
+    pub region: FluidRegion,
+    /// Mass per unit area (water = 1.0, zero for pure currents)
+    pub density: f32,
+    /// Velocity of the fluid itself
+    pub flow: Vec2,
+    /// How quickly bodies match the flow velocity (per second)
+    pub drag: f32,
+}
+
+impl FluidZone {
+    /// Creates a still fluid in a rectangle
+    #[must_use]
+    pub const fn rect(min: Vec2, max: Vec2, density: f32) -> Self {
+        Self {
+            region: FluidRegion::Rect { min, max },
+            density,
+            flow: Vec2::ZERO,
+            drag: 1.0,
+        }
+    }
+
+    /// Creates a still fluid in a polygon
+    #[must_use]
+    pub const fn polygon(vertices: Vec<Vec2>, density: f32) -> Self {
+        Self {
+            region: FluidRegion::Polygon(vertices),
+            density,
+            flow: Vec2::ZERO,
+            drag: 1.0,
+        }
+    }
+
+    /// Water filling a rectangle
+    #[must_use]
+    pub const fn water(min: Vec2, max: Vec2) -> Self {
+        Self::rect(min, max, 1.0).with_drag(2.0)
+    }
+
+    /// Weightless current (wind tunnel, conveyor of air)
+    #[must_use]
+    pub const fn wind(min: Vec2, max: Vec2, flow: Vec2) -> Self {
+        Self::rect(min, max, 0.0).with_flow(flow)
+    }
+
+    /// Sets the flow velocity
+    #[must_use]
+    pub const fn with_flow(mut self, flow: Vec2) -> Self {
+        self.flow = flow;
+        self
+    }
+
+    /// Sets the drag rate
+    #[must_use]
+    pub const fn with_drag(mut self, drag: f32) -> Self {
+        self.drag = drag;
+        self
+    }
+
+    /// Velocity change for a body over `dt`
+    ///
+    /// `bounds` and `area` describe the body's shape, `gravity` is the
+    /// world gravity. Returns zero when the body is outside the fluid.
+    #[must_use]
+    pub fn velocity_change(
+        &self,
+        bounds: (Vec2, Vec2),
+        area: f32,
+        mass: f32,
+        velocity: Vec2,
+        gravity: Vec2,
+        dt: f32,
+    ) -> Vec2 {
+        let submerged = self.region.coverage(bounds.0, bounds.1);
+        if submerged <= 0.0 || mass <= 0.0 || !mass.is_finite() {
+            return Vec2::ZERO;
+        }
+        let buoyancy = -gravity * (self.density * area * submerged / mass);
+        let drag = (self.flow - velocity) * (self.drag * submerged * dt).min(1.0);
+        buoyancy * dt + drag
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rect_coverage() {
+        let region = FluidRegion::Rect {
+            min: Vec2::new(-10.0, -10.0),
+            max: Vec2::new(10.0, 0.0),
+        };
+        let half = region.coverage(Vec2::new(0.0, -1.0), Vec2::new(2.0, 1.0));
+        assert!((half - 0.5).abs() < 0.001);
+        assert!(region.coverage(Vec2::new(0.0, 1.0), Vec2::new(1.0, 2.0)) <= 0.0);
+    }
+
+    #[test]
+    fn test_polygon_contains_and_coverage() {
+        let triangle = FluidRegion::Polygon(vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(4.0, 0.0),
+            Vec2::new(0.0, 4.0),
+        ]);
+        assert!(triangle.contains(Vec2::new(1.0, 1.0)));
+        assert!(!triangle.contains(Vec2::new(3.0, 3.0)));
+        assert!(triangle.coverage(Vec2::new(0.0, 0.0), Vec2::new(1.0, 1.0)) >= 1.0);
+    }
+
+    #[test]
+    fn test_buoyancy_opposes_gravity() {
+        let water = FluidZone::water(Vec2::new(-5.0, -5.0), Vec2::new(5.0, 0.0));
+        let bounds = (Vec2::new(-0.5, -2.0), Vec2::new(0.5, -1.0));
+        let dv = water.velocity_change(bounds, 1.0, 0.5, Vec2::ZERO, Vec2::new(0.0, -10.0), 0.1);
+        assert!(dv.y > 0.0);
+        assert!(dv.x.abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_wind_pushes_toward_flow() {
+        let wind = FluidZone::wind(Vec2::ZERO, Vec2::splat(10.0), Vec2::new(5.0, 0.0));
+        let bounds = (Vec2::splat(1.0), Vec2::splat(2.0));
+        let dv = wind.velocity_change(bounds, 1.0, 1.0, Vec2::ZERO, Vec2::new(0.0, -10.0), 0.1);
+        assert!(dv.x > 0.0);
+        assert!(dv.y.abs() < f32::EPSILON);
+    }
+}
@@ -10,6 +10,16 @@
 #![forbid(unsafe_code)]
 #![warn(missing_docs)]
 
+extern crate alloc;
+
+mod collision;
+mod determinism;
+mod fluid;
+mod material;
+mod soft;
+mod sync;
+
+use alloc::collections::BTreeSet;
 use core::fmt;
 use core::time::Duration;
 
@@ -17,7 +27,14 @@ use glam::Vec2;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use jugar_core::{Position, Velocity};
+use jugar_core::{Events, LineOfSight, Position, SpatialGrid, Velocity};
+
+pub use collision::{collide, CollisionLayers, Contact, PhysicsEvent, RaycastHit, Shape};
+pub use determinism::{first_divergence, Scenario, SCENARIO_DT};
+pub use fluid::{FluidHandle, FluidRegion, FluidZone};
+pub use material::{CombineRule, MaterialId, MaterialRegistry, PhysicsMaterial};
+pub use soft::{Rope, RopeHandle, SoftBody, SoftBodyHandle, VerletParticle, MAX_SOFT_PARTICLES};
+pub use sync::{PhysicsBodyRef, PhysicsSync};
 
 /// Physics backend selection
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
@@ -47,6 +64,16 @@ pub enum PhysicsError {
     /// Backend not available
     #[error("Physics backend {0} not available")]
     BackendNotAvailable(PhysicsBackend),
+    /// Rope or soft body particle count out of range
+    #[error("Particle count {requested} must be between {min} and {max}")]
+    InvalidParticleCount {
+        /// Requested particle count
+        requested: usize,
+        /// Minimum allowed
+        min: usize,
+        /// Maximum allowed
+        max: usize,
+    },
 }
 
 /// Result type for physics operations
@@ -67,6 +94,21 @@ pub struct RigidBody {
     pub friction: f32,
     /// Whether the body is static (immovable)
     pub is_static: bool,
+    /// Collision shape (`None` = no collisions)
+    #[serde(default)]
+    pub shape: Option<Shape>,
+    /// Collision layer membership and mask
+    #[serde(default)]
+    pub layers: CollisionLayers,
+    /// Shared material (overrides `friction` and `restitution`)
+    #[serde(default)]
+    pub material: Option<MaterialId>,
+    /// One-way platform: only blocks bodies approaching from this side
+    #[serde(default)]
+    pub one_way: Option<Vec2>,
+    /// Sensors detect overlaps (trigger events) but are not solid
+    #[serde(default)]
+    pub is_sensor: bool,
 }
 
 impl RigidBody {
@@ -80,6 +122,11 @@ impl RigidBody {
             restitution: 0.5,
             friction: 0.3,
             is_static: false,
+            shape: None,
+            layers: CollisionLayers::ALL,
+            material: None,
+            one_way: None,
+            is_sensor: false,
         }
     }
 
@@ -93,6 +140,11 @@ impl RigidBody {
             restitution: 0.5,
             friction: 0.3,
             is_static: true,
+            shape: None,
+            layers: CollisionLayers::ALL,
+            material: None,
+            one_way: None,
+            is_sensor: false,
         }
     }
 
@@ -109,6 +161,59 @@ impl RigidBody {
         self.mass = mass;
         self
     }
+
+    /// Sets the collision shape
+    #[must_use]
+    pub const fn with_shape(mut self, shape: Shape) -> Self {
+        self.shape = Some(shape);
+        self
+    }
+
+    /// Sets the collision layers
+    #[must_use]
+    pub const fn with_layers(mut self, layers: CollisionLayers) -> Self {
+        self.layers = layers;
+        self
+    }
+
+    /// Sets the shared material
+    #[must_use]
+    pub const fn with_material(mut self, material: MaterialId) -> Self {
+        self.material = Some(material);
+        self
+    }
+
+    /// Makes the body a one-way platform that blocks from the `up` side only
+    #[must_use]
+    pub const fn with_one_way(mut self, up: Vec2) -> Self {
+        self.one_way = Some(up);
+        self
+    }
+
+    /// Makes the body a sensor (trigger volume)
+    #[must_use]
+    pub const fn with_sensor(mut self, sensor: bool) -> Self {
+        self.is_sensor = sensor;
+        self
+    }
+
+    /// Inverse mass (0 for static or infinite-mass bodies)
+    #[must_use]
+    pub fn inverse_mass(&self) -> f32 {
+        if self.is_static || !self.mass.is_finite() || self.mass <= 0.0 {
+            0.0
+        } else {
+            1.0 / self.mass
+        }
+    }
+
+    /// Resolves the body's material from a registry
+    #[must_use]
+    pub fn resolved_material(&self, registry: &MaterialRegistry) -> PhysicsMaterial {
+        self.material
+            .and_then(|id| registry.get(id).copied())
+            .unwrap_or_else(|| PhysicsMaterial::new(self.friction, self.restitution))
+    }
 }
 
 impl Default for RigidBody {
@@ -125,7 +230,17 @@ pub struct BodyHandle(pub u32);
 pub struct PhysicsWorld {
     backend: PhysicsBackend,
     bodies: Vec<RigidBody>,
+    /// Slots of removed bodies, reused by `add_body`
+    vacant: BTreeSet<u32>,
     gravity: Vec2,
+    materials: MaterialRegistry,
+    contacts: Vec<Contact>,
+    overlaps: BTreeSet<(u32, u32)>,
+    events: Events<PhysicsEvent>,
+    broadphase_cell_size: f32,
+    ropes: Vec<Rope>,
+    soft_bodies: Vec<SoftBody>,
+    fluids: Vec<FluidZone>,
 }
 
 impl PhysicsWorld {
@@ -133,11 +248,7 @@ impl PhysicsWorld {
     #[must_use]
     pub const fn new() -> Self {
         let backend = detect_best_backend();
-        Self {
-            backend,
-            bodies: Vec::new(),
-            gravity: Vec2::new(0.0, -9.81),
-        }
+        Self::with_backend(backend)
     }
 
     /// Creates a physics world with a specific backend
@@ -146,7 +257,16 @@ impl PhysicsWorld {
         Self {
             backend,
             bodies: Vec::new(),
+            vacant: BTreeSet::new(),
             gravity: Vec2::new(0.0, -9.81),
+            materials: MaterialRegistry::new(),
+            contacts: Vec::new(),
+            overlaps: BTreeSet::new(),
+            events: Events::new(),
+            broadphase_cell_size: 4.0,
+            ropes: Vec::new(),
+            soft_bodies: Vec::new(),
+            fluids: Vec::new(),
         }
     }
 
@@ -162,28 +282,211 @@ impl PhysicsWorld {
     }
 
     /// Adds a body to the world
+    ///
+    /// Reuses the slot of a removed body if there is one.
     #[allow(clippy::cast_possible_truncation)]
     pub fn add_body(&mut self, body: RigidBody) -> BodyHandle {
+        if let Some(index) = self.vacant.pop_first() {
+            self.bodies[index as usize] = body;
+            return BodyHandle(index);
+        }
         let handle = BodyHandle(self.bodies.len() as u32);
         self.bodies.push(body);
         handle
     }
 
+    /// Removes a body, returning it
+    ///
+    /// The handle becomes invalid and may be handed out again by
+    /// [`add_body`](Self::add_body). Sensor overlaps with the body end
+    /// without exit events.
+    pub fn remove_body(&mut self, handle: BodyHandle) -> Option<RigidBody> {
+        if self.vacant.contains(&handle.0) {
+            return None;
+        }
+        // An inert placeholder keeps the other handles' indices stable
+        let placeholder = RigidBody::new_static(Position::zero());
+        let body = core::mem::replace(self.bodies.get_mut(handle.0 as usize)?, placeholder);
+        let _ = self.vacant.insert(handle.0);
+        self.overlaps
+            .retain(|&(a, b)| a != handle.0 && b != handle.0);
+        Some(body)
+    }
+
     /// Gets a reference to a body
     #[must_use]
     pub fn get_body(&self, handle: BodyHandle) -> Option<&RigidBody> {
+        if self.vacant.contains(&handle.0) {
+            return None;
+        }
         self.bodies.get(handle.0 as usize)
     }
 
     /// Gets a mutable reference to a body
     pub fn get_body_mut(&mut self, handle: BodyHandle) -> Option<&mut RigidBody> {
+        if self.vacant.contains(&handle.0) {
+            return None;
+        }
         self.bodies.get_mut(handle.0 as usize)
     }
 
+    /// Sets the broadphase grid cell size (roughly the size of a typical body)
+    pub fn set_broadphase_cell_size(&mut self, cell_size: f32) {
+        self.broadphase_cell_size = cell_size.max(f32::EPSILON);
+    }
+
+    /// Gets the material registry
+    #[must_use]
+    pub const fn materials(&self) -> &MaterialRegistry {
+        &self.materials
+    }
+
+    /// Gets the material registry mutably
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn materials_mut(&mut self) -> &mut MaterialRegistry {
+        &mut self.materials
+    }
+
+    /// Contacts resolved during the last step
+    #[must_use]
+    pub fn contacts(&self) -> &[Contact] {
+        &self.contacts
+    }
+
+    /// Trigger events from the last two steps
+    #[must_use]
+    pub const fn events(&self) -> &Events<PhysicsEvent> {
+        &self.events
+    }
+
+    /// Trigger events, mutably (e.g. to drain them)
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn events_mut(&mut self) -> &mut Events<PhysicsEvent> {
+        &mut self.events
+    }
+
+    /// Nearest solid body crossed by the segment from `from` to `to`
+    ///
+    /// Sensors and bodies that already contain `from` (such as the caster's
+    /// own body) are ignored.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn raycast(&self, from: Vec2, to: Vec2) -> Option<RaycastHit> {
+        self.bodies
+            .iter()
+            .enumerate()
+            .filter(|(_, body)| !body.is_sensor)
+            .filter_map(|(index, body)| {
+                let fraction = body.shape?.ray_entry(body.position.as_vec2(), from, to)?;
+                Some(RaycastHit {
+                    body: BodyHandle(index as u32),
+                    point: from.lerp(to, fraction),
+                    fraction,
+                })
+            })
+            .min_by(|a, b| a.fraction.total_cmp(&b.fraction))
+    }
+
+    /// Bodies currently overlapping a sensor
+    pub fn overlapping(&self, sensor: BodyHandle) -> impl Iterator<Item = BodyHandle> + '_ {
+        self.overlaps.iter().filter_map(move |&(a, b)| {
+            if a == sensor.0 {
+                Some(BodyHandle(b))
+            } else if b == sensor.0 {
+                Some(BodyHandle(a))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Adds a rope to the world
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn add_rope(&mut self, rope: Rope) -> RopeHandle {
+        let handle = RopeHandle(self.ropes.len() as u32);
+        self.ropes.push(rope);
+        handle
+    }
+
+    /// Gets a reference to a rope
+    #[must_use]
+    pub fn get_rope(&self, handle: RopeHandle) -> Option<&Rope> {
+        self.ropes.get(handle.0 as usize)
+    }
+
+    /// Gets a mutable reference to a rope
+    pub fn get_rope_mut(&mut self, handle: RopeHandle) -> Option<&mut Rope> {
+        self.ropes.get_mut(handle.0 as usize)
+    }
+
+    /// Adds a soft body to the world
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn add_soft_body(&mut self, body: SoftBody) -> SoftBodyHandle {
+        let handle = SoftBodyHandle(self.soft_bodies.len() as u32);
+        self.soft_bodies.push(body);
+        handle
+    }
+
+    /// Gets a reference to a soft body
+    #[must_use]
+    pub fn get_soft_body(&self, handle: SoftBodyHandle) -> Option<&SoftBody> {
+        self.soft_bodies.get(handle.0 as usize)
+    }
+
+    /// Gets a mutable reference to a soft body
+    pub fn get_soft_body_mut(&mut self, handle: SoftBodyHandle) -> Option<&mut SoftBody> {
+        self.soft_bodies.get_mut(handle.0 as usize)
+    }
+
+    /// Adds a fluid zone to the world
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn add_fluid(&mut self, fluid: FluidZone) -> FluidHandle {
+        let handle = FluidHandle(self.fluids.len() as u32);
+        self.fluids.push(fluid);
+        handle
+    }
+
+    /// Gets a reference to a fluid zone
+    #[must_use]
+    pub fn get_fluid(&self, handle: FluidHandle) -> Option<&FluidZone> {
+        self.fluids.get(handle.0 as usize)
+    }
+
+    /// Gets a mutable reference to a fluid zone
+    pub fn get_fluid_mut(&mut self, handle: FluidHandle) -> Option<&mut FluidZone> {
+        self.fluids.get_mut(handle.0 as usize)
+    }
+
+    /// Stable hash of all body and particle positions and velocities
+    ///
+    /// Equal hashes after the same inputs mean the runs matched
+    /// bit-for-bit, so this can be compared across machines for replays
+    /// and lockstep netcode.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn state_hash(&self) -> u64 {
+        let mut hasher = determinism::StateHasher::new();
+        hasher.write_u32(self.bodies.len() as u32);
+        for body in &self.bodies {
+            hasher.write_vec2(body.position.as_vec2());
+            hasher.write_vec2(body.velocity.as_vec2());
+        }
+        let ropes = self.ropes.iter().map(Rope::particles);
+        let soft_bodies = self.soft_bodies.iter().map(SoftBody::particles);
+        for particles in ropes.chain(soft_bodies) {
+            hasher.write_u32(particles.len() as u32);
+            for particle in particles {
+                hasher.write_vec2(particle.position);
+                hasher.write_vec2(particle.previous);
+            }
+        }
+        hasher.finish()
+    }
+
     /// Returns the number of bodies
     #[must_use]
     pub fn body_count(&self) -> usize {
-        self.bodies.len()
+        self.bodies.len() - self.vacant.len()
     }
 
     /// Steps the physics simulation
@@ -191,6 +494,7 @@ impl PhysicsWorld {
     /// Returns the time taken for the step.
     pub fn step(&mut self, dt: f32) -> Duration {
         let start = std::time::Instant::now();
+        self.events.update();
 
         // Apply gravity and integrate
         for body in &mut self.bodies {
@@ -202,13 +506,148 @@ impl PhysicsWorld {
             body.velocity.x += self.gravity.x * dt;
             body.velocity.y += self.gravity.y * dt;
 
+            // Buoyancy and drag from overlapping fluids
+            if let Some(shape) = body.shape {
+                let bounds = shape.bounds(body.position.as_vec2());
+                for fluid in &self.fluids {
+                    let dv = fluid.velocity_change(
+                        bounds,
+                        shape.area(),
+                        body.mass,
+                        body.velocity.as_vec2(),
+                        self.gravity,
+                        dt,
+                    );
+                    body.velocity.x += dv.x;
+                    body.velocity.y += dv.y;
+                }
+            }
+
             // Integrate position
             body.position.x += body.velocity.x * dt;
             body.position.y += body.velocity.y * dt;
         }
 
+        self.resolve_collisions();
+
+        // Ropes and soft bodies collide with static bodies only
+        for rope in &mut self.ropes {
+            rope.step(dt, self.gravity, &self.bodies);
+        }
+        for soft in &mut self.soft_bodies {
+            soft.step(dt, self.gravity, &self.bodies);
+        }
+
         start.elapsed()
     }
+
+    /// Finds overlapping body pairs and pushes them apart
+    #[allow(clippy::cast_possible_truncation)]
+    fn resolve_collisions(&mut self) {
+        self.contacts.clear();
+        let mut overlaps = BTreeSet::new();
+        for (i, j) in self.candidate_pairs() {
+            let (before, after) = self.bodies.split_at_mut(j);
+            let (a, b) = (&mut before[i], &mut after[0]);
+            let (Some(shape_a), Some(shape_b)) = (a.shape, b.shape) else {
+                continue;
+            };
+            if (a.is_static && b.is_static) || !a.layers.interacts(&b.layers) {
+                continue;
+            }
+            let Some((normal, depth)) = collide(
+                &shape_a,
+                a.position.as_vec2(),
+                &shape_b,
+                b.position.as_vec2(),
+            ) else {
+                continue;
+            };
+            if a.is_sensor || b.is_sensor {
+                let _ = overlaps.insert((i as u32, j as u32));
+                continue;
+            }
+            if !one_way_allows(a, b, normal) {
+                continue;
+            }
+            let (friction, restitution) = a
+                .resolved_material(&self.materials)
+                .combine(&b.resolved_material(&self.materials));
+            resolve_contact(a, b, normal, depth, friction, restitution);
+            self.contacts.push(Contact {
+                a: BodyHandle(i as u32),
+                b: BodyHandle(j as u32),
+                normal,
+                depth,
+            });
+        }
+        self.publish_trigger_events(overlaps);
+    }
+
+    /// Broadphase: index pairs `(i, j)` with `i < j` whose bounds overlap,
+    /// in ascending order
+    fn candidate_pairs(&self) -> Vec<(usize, usize)> {
+        let mut grid = SpatialGrid::new(self.broadphase_cell_size);
+        for (index, body) in self.bodies.iter().enumerate() {
+            if let Some(shape) = body.shape {
+                let (min, max) = shape.bounds(body.position.as_vec2());
+                let _ = grid.insert(index, min, max);
+            }
+        }
+
+        let mut pairs = Vec::new();
+        for (index, body) in self.bodies.iter().enumerate() {
+            let Some(shape) = body.shape else {
+                continue;
+            };
+            let (min, max) = shape.bounds(body.position.as_vec2());
+            pairs.extend(
+                grid.query_aabb(min, max)
+                    .into_iter()
+                    .map(|(_, &other)| other)
+                    .filter(|&other| other > index)
+                    .map(|other| (index, other)),
+            );
+        }
+        pairs.sort_unstable();
+        pairs
+    }
+
+    /// Compares sensor overlaps with the previous step and sends enter/exit
+    /// events
+    fn publish_trigger_events(&mut self, overlaps: BTreeSet<(u32, u32)>) {
+        let pair_event = |bodies: &[RigidBody], (a, b): (u32, u32)| {
+            if bodies[a as usize].is_sensor {
+                (BodyHandle(a), BodyHandle(b))
+            } else {
+                (BodyHandle(b), BodyHandle(a))
+            }
+        };
+        for &pair in overlaps.difference(&self.overlaps) {
+            let (sensor, other) = pair_event(&self.bodies, pair);
+            self.events
+                .send(PhysicsEvent::TriggerEnter { sensor, other });
+        }
+        for &pair in self.overlaps.difference(&overlaps) {
+            let (sensor, other) = pair_event(&self.bodies, pair);
+            self.events
+                .send(PhysicsEvent::TriggerExit { sensor, other });
+        }
+        self.overlaps = overlaps;
+    }
+}
+
+/// Sight is blocked by the nearest solid body on the segment, unless that
+/// body is the target itself (it contains `to`).
+impl LineOfSight for PhysicsWorld {
+    fn is_clear(&self, from: Vec2, to: Vec2) -> bool {
+        self.raycast(from, to).map_or(true, |hit| {
+            self.get_body(hit.body).is_some_and(|body| {
+                body.shape
+                    .is_some_and(|shape| shape.contains(body.position.as_vec2(), to))
+            })
+        })
+    }
 }
 
 impl Default for PhysicsWorld {
@@ -221,11 +660,73 @@ impl fmt::Debug for PhysicsWorld {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("PhysicsWorld")
             .field("backend", &self.backend)
-            .field("body_count", &self.bodies.len())
+            .field("body_count", &self.body_count())
             .finish_non_exhaustive()
     }
 }
 
+/// Checks one-way platforms: the other body must be on the platform's `up`
+/// side and not moving away through it
+fn one_way_allows(a: &RigidBody, b: &RigidBody, normal: Vec2) -> bool {
+    let relative = b.velocity.as_vec2() - a.velocity.as_vec2();
+    if let Some(up) = b.one_way {
+        // `a` must be above the platform `b` and moving down into it
+        if (-normal).dot(up) < 0.5 || relative.dot(up) < 0.0 {
+            return false;
+        }
+    }
+    if let Some(up) = a.one_way {
+        if normal.dot(up) < 0.5 || relative.dot(up) > 0.0 {
+            return false;
+        }
+    }
+    true
+}
+
+/// Separates two overlapping bodies and applies bounce and friction impulses
+fn resolve_contact(
+    a: &mut RigidBody,
+    b: &mut RigidBody,
+    normal: Vec2,
+    depth: f32,
+    friction: f32,
+    restitution: f32,
+) {
+    const SLOP: f32 = 0.01;
+    const CORRECTION: f32 = 0.8;
+
+    let inv_a = a.inverse_mass();
+    let inv_b = b.inverse_mass();
+    let inv_total = inv_a + inv_b;
+    if inv_total <= 0.0 {
+        return;
+    }
+
+    // Positional correction
+    let correction = normal * ((depth - SLOP).max(0.0) / inv_total * CORRECTION);
+    a.position = Position::from_vec2(a.position.as_vec2() - correction * inv_a);
+    b.position = Position::from_vec2(b.position.as_vec2() + correction * inv_b);
+
+    // Normal impulse
+    let va = a.velocity.as_vec2();
+    let vb = b.velocity.as_vec2();
+    let relative = vb - va;
+    let normal_speed = relative.dot(normal);
+    if normal_speed > 0.0 {
+        return;
+    }
+    let j = -(1.0 + restitution) * normal_speed / inv_total;
+    let mut impulse = normal * j;
+
+    // Friction impulse (Coulomb-clamped)
+    let tangent = (relative - normal * normal_speed).normalize_or_zero();
+    let jt = (-relative.dot(tangent) / inv_total).clamp(-j * friction, j * friction);
+    impulse += tangent * jt;
+
+    a.velocity = Velocity::from_vec2(va - impulse * inv_a);
+    b.velocity = Velocity::from_vec2(vb + impulse * inv_b);
+}
+
 /// Detects the best available physics backend
 #[must_use]
 pub const fn detect_best_backend() -> PhysicsBackend {
@@ -323,6 +824,27 @@ mod tests {
         assert!((body.position.x - 100.0).abs() < f32::EPSILON);
     }
 
+    #[test]
+    fn test_remove_body_frees_slot_for_reuse() {
+        let mut world = PhysicsWorld::new();
+        let a = world.add_body(RigidBody::new(Position::new(1.0, 0.0)));
+        let b = world.add_body(RigidBody::new(Position::new(2.0, 0.0)));
+
+        let removed = world.remove_body(a).unwrap();
+        assert!((removed.position.x - 1.0).abs() < f32::EPSILON);
+        assert!(world.get_body(a).is_none());
+        assert!(world.remove_body(a).is_none());
+        assert_eq!(world.body_count(), 1);
+
+        // The removed slot neither moves nor collides
+        let _ = world.step(1.0 / 60.0);
+        assert!((world.get_body(b).unwrap().position.x - 2.0).abs() < f32::EPSILON);
+
+        let c = world.add_body(RigidBody::new(Position::new(3.0, 0.0)));
+        assert_eq!(c, a);
+        assert_eq!(world.body_count(), 2);
+    }
+
     #[test]
     fn test_physics_world_get_invalid_handle() {
         let world = PhysicsWorld::new();
@@ -443,4 +965,272 @@ mod tests {
         // Duration should be valid
         assert!(duration.as_secs_f32() < 1.0);
     }
+
+    // ==================== COLLISION TESTS ====================
+
+    fn zero_gravity_world() -> PhysicsWorld {
+        let mut world = PhysicsWorld::new();
+        world.set_gravity(Vec2::ZERO);
+        world
+    }
+
+    #[test]
+    fn test_circles_collide_and_separate() {
+        let mut world = zero_gravity_world();
+        let a = world.add_body(
+            RigidBody::new(Position::new(0.0, 0.0))
+                .with_velocity(Velocity::new(1.0, 0.0))
+                .with_shape(Shape::circle(1.0)),
+        );
+        let b = world.add_body(
+            RigidBody::new(Position::new(1.9, 0.0))
+                .with_velocity(Velocity::new(-1.0, 0.0))
+                .with_shape(Shape::circle(1.0)),
+        );
+        let _ = world.step(0.016);
+        assert_eq!(world.contacts().len(), 1);
+        assert!(world.get_body(a).unwrap().velocity.x < 0.0);
+        assert!(world.get_body(b).unwrap().velocity.x > 0.0);
+    }
+
+    #[test]
+    fn test_layers_filter_pairs() {
+        let mut world = zero_gravity_world();
+        let enemy = CollisionLayers::new(0b10, 0b01);
+        let _ = world.add_body(
+            RigidBody::new(Position::zero())
+                .with_shape(Shape::circle(1.0))
+                .with_layers(enemy),
+        );
+        let _ = world.add_body(
+            RigidBody::new(Position::new(0.5, 0.0))
+                .with_shape(Shape::circle(1.0))
+                .with_layers(enemy),
+        );
+        let _ = world.step(0.016);
+        assert!(world.contacts().is_empty());
+    }
+
+    #[test]
+    fn test_static_floor_stops_fall() {
+        let mut world = PhysicsWorld::new();
+        let _ = world.add_body(
+            RigidBody::new_static(Position::new(0.0, 0.0)).with_shape(Shape::rect(10.0, 1.0)),
+        );
+        let ball = world.add_body(
+            RigidBody::new(Position::new(0.0, 1.0))
+                .with_velocity(Velocity::new(0.0, -5.0))
+                .with_shape(Shape::circle(0.6)),
+        );
+        let _ = world.step(0.016);
+        let body = world.get_body(ball).unwrap();
+        assert!(body.velocity.y > 0.0, "ball should bounce up");
+    }
+
+    #[test]
+    fn test_one_way_platform() {
+        let mut world = zero_gravity_world();
+        let _ = world.add_body(
+            RigidBody::new_static(Position::zero())
+                .with_shape(Shape::rect(4.0, 0.5))
+                .with_one_way(Vec2::Y),
+        );
+        let rising = world.add_body(
+            RigidBody::new(Position::new(0.0, -0.5))
+                .with_velocity(Velocity::new(0.0, 3.0))
+                .with_shape(Shape::circle(0.5)),
+        );
+        let _ = world.step(0.016);
+        assert!(world.contacts().is_empty());
+        assert!(world.get_body(rising).unwrap().velocity.y > 0.0);
+
+        let mut world = zero_gravity_world();
+        let _ = world.add_body(
+            RigidBody::new_static(Position::zero())
+                .with_shape(Shape::rect(4.0, 0.5))
+                .with_one_way(Vec2::Y),
+        );
+        let falling = world.add_body(
+            RigidBody::new(Position::new(0.0, 0.6))
+                .with_velocity(Velocity::new(0.0, -3.0))
+                .with_shape(Shape::circle(0.5)),
+        );
+        let _ = world.step(0.016);
+        assert_eq!(world.contacts().len(), 1);
+        assert!(world.get_body(falling).unwrap().velocity.y >= 0.0);
+    }
+
+    #[test]
+    fn test_material_registry_overrides_body_coefficients() {
+        let mut world = zero_gravity_world();
+        let bouncy = world.materials_mut().register(
+            "bouncy",
+            PhysicsMaterial::new(0.0, 1.0).with_restitution_combine(CombineRule::Max),
+        );
+        let _ = world.add_body(
+            RigidBody::new_static(Position::zero())
+                .with_shape(Shape::rect(10.0, 1.0))
+                .with_material(bouncy),
+        );
+        let ball = world.add_body(
+            RigidBody::new(Position::new(0.0, 0.9))
+                .with_velocity(Velocity::new(0.0, -4.0))
+                .with_shape(Shape::circle(0.5)),
+        );
+        let _ = world.step(0.016);
+        let body = world.get_body(ball).unwrap();
+        assert!((body.velocity.y - 4.0).abs() < 0.01);
+    }
+
+    // ==================== TRIGGER TESTS ====================
+
+    #[test]
+    fn test_sensor_enter_and_exit_events() {
+        let mut world = zero_gravity_world();
+        let bucket = world.add_body(
+            RigidBody::new_static(Position::zero())
+                .with_shape(Shape::rect(2.0, 2.0))
+                .with_sensor(true),
+        );
+        let ball = world.add_body(
+            RigidBody::new(Position::new(-4.0, 0.0))
+                .with_velocity(Velocity::new(60.0, 0.0))
+                .with_shape(Shape::circle(0.5)),
+        );
+
+        let _ = world.step(1.0 / 30.0);
+        assert!(world.events().is_empty());
+
+        let _ = world.step(1.0 / 30.0);
+        assert!(world.events().iter().any(|e| *e
+            == PhysicsEvent::TriggerEnter {
+                sensor: bucket,
+                other: ball
+            }));
+        assert_eq!(world.overlapping(bucket).collect::<Vec<_>>(), vec![ball]);
+        // Sensors are not solid
+        assert!((world.get_body(ball).unwrap().velocity.x - 60.0).abs() < f32::EPSILON);
+        assert!(world.contacts().is_empty());
+
+        let _ = world.step(1.0 / 30.0);
+        let _ = world.step(1.0 / 30.0);
+        let exits = world
+            .events_mut()
+            .drain()
+            .filter(|e| matches!(e, PhysicsEvent::TriggerExit { .. }))
+            .count();
+        assert_eq!(exits, 1);
+        assert_eq!(world.overlapping(bucket).count(), 0);
+    }
+
+    #[test]
+    fn test_raycast_hits_nearest_solid_body() {
+        let mut world = zero_gravity_world();
+        let guard =
+            world.add_body(RigidBody::new(Position::new(-5.0, 0.0)).with_shape(Shape::circle(0.5)));
+        let _ = world.add_body(
+            RigidBody::new_static(Position::new(4.0, 0.0))
+                .with_shape(Shape::rect(2.0, 2.0))
+                .with_sensor(true),
+        );
+        let wall = world
+            .add_body(RigidBody::new_static(Position::zero()).with_shape(Shape::rect(1.0, 4.0)));
+        let player =
+            world.add_body(RigidBody::new(Position::new(5.0, 0.0)).with_shape(Shape::circle(0.5)));
+
+        // Cast from inside the guard: its own body and the sensor are skipped
+        let hit = world
+            .raycast(Vec2::new(-5.0, 0.0), Vec2::new(5.0, 0.0))
+            .unwrap();
+        assert_eq!(hit.body, wall);
+        assert!((hit.point.x + 0.5).abs() < 1e-5);
+        assert!((hit.fraction - 0.45).abs() < 1e-5);
+        assert!(world
+            .raycast(Vec2::new(-5.0, 3.0), Vec2::new(5.0, 3.0))
+            .is_none());
+
+        assert!(!world.is_clear(Vec2::new(-5.0, 0.0), Vec2::new(5.0, 0.0)));
+        let _ = world.remove_body(wall);
+        let hit = world
+            .raycast(Vec2::new(-5.0, 0.0), Vec2::new(5.0, 0.0))
+            .unwrap();
+        assert_eq!(hit.body, player);
+        // The target's own body does not block the view of it
+        assert!(world.is_clear(Vec2::new(-5.0, 0.0), Vec2::new(5.0, 0.0)));
+        assert_ne!(hit.body, guard);
+    }
+
+    #[test]
+    fn test_broadphase_many_bodies() {
+        let mut world = zero_gravity_world();
+        world.set_broadphase_cell_size(2.0);
+        for i in 0..50 {
+            let _ = world.add_body(
+                RigidBody::new(Position::new(i as f32 * 10.0, 0.0)).with_shape(Shape::circle(1.0)),
+            );
+        }
+        let _ = world
+            .add_body(RigidBody::new(Position::new(101.5, 0.0)).with_shape(Shape::circle(1.0)));
+        let _ = world.step(0.016);
+        assert_eq!(world.contacts().len(), 1);
+        assert_eq!(world.contacts()[0].a, BodyHandle(10));
+        assert_eq!(world.contacts()[0].b, BodyHandle(50));
+    }
+
+    #[test]
+    fn test_world_steps_ropes_and_soft_bodies() {
+        let mut world = PhysicsWorld::new();
+        let _ = world.add_body(
+            RigidBody::new_static(Position::new(0.0, -1.0)).with_shape(Shape::rect(20.0, 1.0)),
+        );
+        let rope = world.add_rope(
+            Rope::new(Vec2::new(0.0, 5.0), Vec2::new(4.0, 5.0), 8)
+                .unwrap()
+                .pinned_start(),
+        );
+        let jelly = world.add_soft_body(SoftBody::circle(Vec2::new(3.0, 2.0), 1.0, 12).unwrap());
+        for _ in 0..120 {
+            let _ = world.step(1.0 / 60.0);
+        }
+        let rope = world.get_rope(rope).unwrap();
+        assert!(rope.particles()[8].position.y < 5.0);
+        let jelly = world.get_soft_body(jelly).unwrap();
+        assert!(jelly.center().y > -0.5);
+    }
+
+    #[test]
+    fn test_fluid_floats_light_body_and_sinks_heavy_one() {
+        let mut world = PhysicsWorld::new();
+        let _ = world.add_fluid(FluidZone::water(
+            Vec2::new(-10.0, -10.0),
+            Vec2::new(10.0, 0.0),
+        ));
+        let cork = world.add_body(
+            RigidBody::new(Position::new(-3.0, -2.0))
+                .with_mass(0.2)
+                .with_shape(Shape::rect(1.0, 1.0)),
+        );
+        let rock = world.add_body(
+            RigidBody::new(Position::new(3.0, -2.0))
+                .with_mass(5.0)
+                .with_shape(Shape::rect(1.0, 1.0)),
+        );
+        for _ in 0..120 {
+            let _ = world.step(1.0 / 60.0);
+        }
+        let cork = world.get_body(cork).unwrap().position.y;
+        let rock = world.get_body(rock).unwrap().position.y;
+        assert!(cork > -1.0, "cork should float up, got {cork}");
+        assert!(rock < -2.0, "rock should sink, got {rock}");
+    }
+
+    #[test]
+    fn test_state_hash_tracks_motion() {
+        let mut world = PhysicsWorld::new();
+        let _ = world.add_body(RigidBody::new(Position::new(0.0, 10.0)));
+        let before = world.state_hash();
+        assert_eq!(before, world.state_hash());
+        let _ = world.step(1.0 / 60.0);
+        assert_ne!(before, world.state_hash());
+    }
 }
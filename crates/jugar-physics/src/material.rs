@@ -0,0 +1,213 @@
+//! Surface materials and how two materials combine at a contact.
+
+use serde::{Deserialize, Serialize};
+
+/// How two materials' coefficients are combined at a contact
+///
+/// When the two sides disagree, the rule with the higher priority wins
+/// (`Average` < `Min` < `Multiply` < `Max`), so an ice surface set to `Min`
+/// stays slippery no matter what touches it.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Default,
+)]
+pub enum CombineRule {
+    /// Mean of both values
+    #[default]
+    Average,
+    /// Smaller of both values
+    Min,
+    /// Product of both values
+    Multiply,
+    /// Larger of both values
+    Max,
+}
+
+impl CombineRule {
+    /// Combines two coefficients
+    #[must_use]
+    pub fn combine(self, a: f32, b: f32) -> f32 {
+        match self {
+            Self::Average => (a + b) * 0.5,
+            Self::Min => a.min(b),
+            Self::Multiply => a * b,
+            Self::Max => a.max(b),
+        }
+    }
+}
+
+/// Surface properties used when resolving contacts
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PhysicsMaterial {
+    /// Friction coefficient
+    pub friction: f32,
+    /// Restitution (bounciness, 0-1)
+    pub restitution: f32,
+    /// Rule for combining friction
+    pub friction_combine: CombineRule,
+    /// Rule for combining restitution
+    pub restitution_combine: CombineRule,
+}
+
+impl PhysicsMaterial {
+    /// Creates a material with average combine rules
+    #[must_use]
+    pub const fn new(friction: f32, restitution: f32) -> Self {
+        Self {
+            friction,
+            restitution,
+            friction_combine: CombineRule::Average,
+            restitution_combine: CombineRule::Average,
+        }
+    }
+
+    /// Sets the friction combine rule
+    #[must_use]
+    pub const fn with_friction_combine(mut self, rule: CombineRule) -> Self {
+        self.friction_combine = rule;
+        self
+    }
+
+    /// Sets the restitution combine rule
+    #[must_use]
+    pub const fn with_restitution_combine(mut self, rule: CombineRule) -> Self {
+        self.restitution_combine = rule;
+        self
+    }
+
+    /// Combined (friction, restitution) for a contact between two materials
+    #[must_use]
+    pub fn combine(&self, other: &Self) -> (f32, f32) {
+        let friction_rule = self.friction_combine.max(other.friction_combine);
+        let restitution_rule = self.restitution_combine.max(other.restitution_combine);
+        (
+            friction_rule.combine(self.friction, other.friction),
+            restitution_rule
+                .combine(self.restitution, other.restitution)
+                .clamp(0.0, 1.0),
+        )
+    }
+}
+
+impl Default for PhysicsMaterial {
+    fn default() -> Self {
+        Self::new(0.3, 0.5)
+    }
+}
+
+/// Handle to a material in a [`MaterialRegistry`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct MaterialId(pub u32);
+
+/// Named set of materials shared by a physics world
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MaterialRegistry {
+    materials: Vec<(String, PhysicsMaterial)>,
+}
+
+impl MaterialRegistry {
+    /// Creates an empty registry
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            materials: Vec::new(),
+        }
+    }
+
+    /// Creates a registry with common presets (ice, rubber, wood, metal)
+    #[must_use]
+    pub fn with_presets() -> Self {
+        let mut registry = Self::new();
+        let _ = registry.register(
+            "ice",
+            PhysicsMaterial::new(0.02, 0.1).with_friction_combine(CombineRule::Min),
+        );
+        let _ = registry.register(
+            "rubber",
+            PhysicsMaterial::new(0.9, 0.85).with_restitution_combine(CombineRule::Max),
+        );
+        let _ = registry.register("wood", PhysicsMaterial::new(0.5, 0.3));
+        let _ = registry.register("metal", PhysicsMaterial::new(0.4, 0.2));
+        registry
+    }
+
+    /// Registers (or replaces) a named material
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn register(&mut self, name: impl Into<String>, material: PhysicsMaterial) -> MaterialId {
+        let name = name.into();
+        if let Some(index) = self.materials.iter().position(|(n, _)| *n == name) {
+            self.materials[index].1 = material;
+            return MaterialId(index as u32);
+        }
+        self.materials.push((name, material));
+        MaterialId((self.materials.len() - 1) as u32)
+    }
+
+    /// Looks up a material by handle
+    #[must_use]
+    pub fn get(&self, id: MaterialId) -> Option<&PhysicsMaterial> {
+        self.materials.get(id.0 as usize).map(|(_, m)| m)
+    }
+
+    /// Finds a material handle by name
+    #[allow(clippy::cast_possible_truncation)]
+    #[must_use]
+    pub fn find(&self, name: &str) -> Option<MaterialId> {
+        self.materials
+            .iter()
+            .position(|(n, _)| n == name)
+            .map(|i| MaterialId(i as u32))
+    }
+
+    /// Number of registered materials
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.materials.len()
+    }
+
+    /// Returns true if no materials are registered
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.materials.is_empty()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_combine_rules() {
+        assert!((CombineRule::Average.combine(0.2, 0.4) - 0.3).abs() < 0.001);
+        assert!((CombineRule::Min.combine(0.2, 0.4) - 0.2).abs() < f32::EPSILON);
+        assert!((CombineRule::Multiply.combine(0.5, 0.4) - 0.2).abs() < 0.001);
+        assert!((CombineRule::Max.combine(0.2, 0.4) - 0.4).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_higher_priority_rule_wins() {
+        let ice = PhysicsMaterial::new(0.0, 0.1).with_friction_combine(CombineRule::Min);
+        let rubber = PhysicsMaterial::new(1.0, 0.9).with_restitution_combine(CombineRule::Max);
+        let (friction, restitution) = ice.combine(&rubber);
+        assert!(friction.abs() < f32::EPSILON);
+        assert!((restitution - 0.9).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_registry_register_and_find() {
+        let mut registry = MaterialRegistry::new();
+        let id = registry.register("slime", PhysicsMaterial::new(0.8, 0.0));
+        assert_eq!(registry.find("slime"), Some(id));
+        let again = registry.register("slime", PhysicsMaterial::new(0.9, 0.0));
+        assert_eq!(id, again);
+        assert_eq!(registry.len(), 1);
+        assert!((registry.get(id).unwrap().friction - 0.9).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_presets() {
+        let registry = MaterialRegistry::with_presets();
+        assert!(registry.find("ice").is_some());
+        assert!(registry.find("rubber").is_some());
+    }
+}
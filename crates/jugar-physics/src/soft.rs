@@ -0,0 +1,439 @@
+//! Verlet ropes and pressure soft bodies.
+//!
+//! Both are chains of [`VerletParticle`]s held together by distance
+//! constraints. Ropes can be pinned at any particle (swinging bridges,
+//! pendulums); soft bodies are closed rings that push outward to keep their
+//! area (jelly balls). Particle counts are bounded by
+//! [`MAX_SOFT_PARTICLES`] so contraptions stay cheap.
+
+use glam::Vec2;
+use serde::{Deserialize, Serialize};
+
+use crate::{collide, PhysicsError, Result, RigidBody, Shape};
+
+/// Maximum particles in a single rope or soft body
+pub const MAX_SOFT_PARTICLES: usize = 128;
+
+/// Handle to a rope in the physics world
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RopeHandle(pub u32);
+
+/// Handle to a soft body in the physics world
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SoftBodyHandle(pub u32);
+
+/// A point mass integrated with Verlet integration
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct VerletParticle {
+    /// Current position
+    pub position: Vec2,
+    /// Position on the previous step (velocity is implied)
+    pub previous: Vec2,
+    /// Pinned particles do not move
+    pub pinned: bool,
+}
+
+impl VerletParticle {
+    /// Creates a particle at rest
+    #[must_use]
+    pub const fn new(position: Vec2) -> Self {
+        Self {
+            position,
+            previous: position,
+            pinned: false,
+        }
+    }
+
+    /// Implied velocity per step
+    #[must_use]
+    pub fn displacement(&self) -> Vec2 {
+        self.position - self.previous
+    }
+
+    fn integrate(&mut self, acceleration: Vec2, damping: f32, dt: f32) {
+        if self.pinned {
+            self.previous = self.position;
+            return;
+        }
+        let velocity = self.displacement() * damping;
+        self.previous = self.position;
+        self.position += velocity + acceleration * (dt * dt);
+    }
+}
+
+fn check_count(requested: usize, min: usize) -> Result<()> {
+    if (min..=MAX_SOFT_PARTICLES).contains(&requested) {
+        Ok(())
+    } else {
+        Err(PhysicsError::InvalidParticleCount {
+            requested,
+            min,
+            max: MAX_SOFT_PARTICLES,
+        })
+    }
+}
+
+/// Moves two particles toward `rest` distance apart
+fn satisfy_distance(
+    particles: &mut [VerletParticle],
+    i: usize,
+    j: usize,
+    rest: f32,
+    stiffness: f32,
+) {
+    let (a, b) = (particles[i], particles[j]);
+    let delta = b.position - a.position;
+    let distance = delta.length();
+    if distance <= f32::EPSILON {
+        return;
+    }
+    let correction = delta * ((distance - rest) / distance * stiffness);
+    match (a.pinned, b.pinned) {
+        (true, true) => {}
+        (true, false) => particles[j].position -= correction,
+        (false, true) => particles[i].position += correction,
+        (false, false) => {
+            particles[i].position += correction * 0.5;
+            particles[j].position -= correction * 0.5;
+        }
+    }
+}
+
+/// Pushes particles out of solid static bodies
+fn collide_particles(particles: &mut [VerletParticle], radius: f32, obstacles: &[RigidBody]) {
+    let probe = Shape::circle(radius);
+    for particle in particles.iter_mut().filter(|p| !p.pinned) {
+        for body in obstacles.iter().filter(|b| b.is_static && !b.is_sensor) {
+            let Some(shape) = body.shape else {
+                continue;
+            };
+            if let Some((normal, depth)) =
+                collide(&probe, particle.position, &shape, body.position.as_vec2())
+            {
+                particle.position -= normal * depth;
+            }
+        }
+    }
+}
+
+/// A chain of particles with fixed spacing
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Rope {
+    particles: Vec<VerletParticle>,
+    segment_length: f32,
+    /// Constraint stiffness (0-1)
+    pub stiffness: f32,
+    /// Constraint iterations per step (more = less stretchy)
+    pub iterations: u32,
+    /// Velocity retained per step (0-1)
+    pub damping: f32,
+    /// Collision radius of each particle
+    pub particle_radius: f32,
+}
+
+impl Rope {
+    /// Creates a straight rope from `start` to `end` with `segments` links
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the particle count is outside the allowed range
+    #[allow(clippy::cast_precision_loss)]
+    pub fn new(start: Vec2, end: Vec2, segments: usize) -> Result<Self> {
+        check_count(segments + 1, 2)?;
+        let particles = (0..=segments)
+            .map(|i| VerletParticle::new(start.lerp(end, i as f32 / segments as f32)))
+            .collect();
+        Ok(Self {
+            particles,
+            segment_length: start.distance(end) / segments as f32,
+            stiffness: 1.0,
+            iterations: 8,
+            damping: 0.99,
+            particle_radius: 0.1,
+        })
+    }
+
+    /// Pins the first particle (hanging rope)
+    #[must_use]
+    pub fn pinned_start(mut self) -> Self {
+        self.pin(0);
+        self
+    }
+
+    /// Pins both ends (bridge)
+    #[must_use]
+    pub fn pinned_ends(mut self) -> Self {
+        self.pin(0);
+        self.pin(self.particles.len() - 1);
+        self
+    }
+
+    /// Sets the number of constraint iterations
+    #[must_use]
+    pub const fn with_iterations(mut self, iterations: u32) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Pins a particle in place
+    pub fn pin(&mut self, index: usize) {
+        if let Some(p) = self.particles.get_mut(index) {
+            p.pinned = true;
+        }
+    }
+
+    /// Releases a pinned particle
+    pub fn unpin(&mut self, index: usize) {
+        if let Some(p) = self.particles.get_mut(index) {
+            p.pinned = false;
+        }
+    }
+
+    /// Moves a particle (e.g. to drag a pinned end)
+    pub fn move_particle(&mut self, index: usize, position: Vec2) {
+        if let Some(p) = self.particles.get_mut(index) {
+            p.position = position;
+            p.previous = position;
+        }
+    }
+
+    /// The rope's particles
+    #[must_use]
+    pub fn particles(&self) -> &[VerletParticle] {
+        &self.particles
+    }
+
+    /// Rest length of one link
+    #[must_use]
+    pub const fn segment_length(&self) -> f32 {
+        self.segment_length
+    }
+
+    /// Line segments for rendering
+    pub fn segments(&self) -> impl Iterator<Item = (Vec2, Vec2)> + '_ {
+        self.particles
+            .windows(2)
+            .map(|w| (w[0].position, w[1].position))
+    }
+
+    /// Advances the simulation
+    pub fn step(&mut self, dt: f32, gravity: Vec2, obstacles: &[RigidBody]) {
+        for particle in &mut self.particles {
+            particle.integrate(gravity, self.damping, dt);
+        }
+        for _ in 0..self.iterations {
+            for i in 0..self.particles.len() - 1 {
+                satisfy_distance(
+                    &mut self.particles,
+                    i,
+                    i + 1,
+                    self.segment_length,
+                    self.stiffness,
+                );
+            }
+            collide_particles(&mut self.particles, self.particle_radius, obstacles);
+        }
+    }
+}
+
+/// A closed ring of particles inflated by internal pressure
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SoftBody {
+    particles: Vec<VerletParticle>,
+    edge_length: f32,
+    rest_area: f32,
+    /// How strongly the body restores its area (0-1)
+    pub pressure: f32,
+    /// Edge constraint stiffness (0-1)
+    pub stiffness: f32,
+    /// Constraint iterations per step
+    pub iterations: u32,
+    /// Velocity retained per step (0-1)
+    pub damping: f32,
+}
+
+impl SoftBody {
+    /// Creates a soft circle from `points` particles
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the particle count is outside the allowed range
+    #[allow(clippy::cast_precision_loss)]
+    pub fn circle(center: Vec2, radius: f32, points: usize) -> Result<Self> {
+        check_count(points, 3)?;
+        let particles: Vec<_> = (0..points)
+            .map(|i| {
+                let angle = core::f32::consts::TAU * i as f32 / points as f32;
+                VerletParticle::new(center + Vec2::from_angle(angle) * radius)
+            })
+            .collect();
+        let edge_length = particles[0].position.distance(particles[1].position);
+        let mut body = Self {
+            particles,
+            edge_length,
+            rest_area: 0.0,
+            pressure: 0.5,
+            stiffness: 0.8,
+            iterations: 6,
+            damping: 0.99,
+        };
+        body.rest_area = body.area();
+        Ok(body)
+    }
+
+    /// Sets the pressure
+    #[must_use]
+    pub const fn with_pressure(mut self, pressure: f32) -> Self {
+        self.pressure = pressure;
+        self
+    }
+
+    /// The body's particles in ring order
+    #[must_use]
+    pub fn particles(&self) -> &[VerletParticle] {
+        &self.particles
+    }
+
+    /// Outline polygon for rendering
+    #[must_use]
+    pub fn outline(&self) -> Vec<Vec2> {
+        self.particles.iter().map(|p| p.position).collect()
+    }
+
+    /// Center of mass
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn center(&self) -> Vec2 {
+        self.particles.iter().map(|p| p.position).sum::<Vec2>() / self.particles.len() as f32
+    }
+
+    /// Current enclosed area (shoelace formula)
+    #[must_use]
+    pub fn area(&self) -> f32 {
+        let n = self.particles.len();
+        let twice: f32 = (0..n)
+            .map(|i| {
+                let a = self.particles[i].position;
+                let b = self.particles[(i + 1) % n].position;
+                a.perp_dot(b)
+            })
+            .sum();
+        twice.abs() * 0.5
+    }
+
+    /// Area the body tries to keep
+    #[must_use]
+    pub const fn rest_area(&self) -> f32 {
+        self.rest_area
+    }
+
+    /// Pushes every particle along its outward normal to restore the area
+    fn apply_pressure(&mut self) {
+        let n = self.particles.len();
+        let perimeter = self.edge_length * n as f32;
+        if perimeter <= f32::EPSILON {
+            return;
+        }
+        let push = (self.rest_area - self.area()) / perimeter * self.pressure;
+        let center = self.center();
+        for particle in self.particles.iter_mut().filter(|p| !p.pinned) {
+            let outward = (particle.position - center).normalize_or_zero();
+            particle.position += outward * push;
+        }
+    }
+
+    /// Advances the simulation
+    #[allow(clippy::cast_precision_loss)]
+    pub fn step(&mut self, dt: f32, gravity: Vec2, obstacles: &[RigidBody]) {
+        for particle in &mut self.particles {
+            particle.integrate(gravity, self.damping, dt);
+        }
+        let n = self.particles.len();
+        let radius = self.edge_length * 0.5;
+        for _ in 0..self.iterations {
+            for i in 0..n {
+                satisfy_distance(
+                    &mut self.particles,
+                    i,
+                    (i + 1) % n,
+                    self.edge_length,
+                    self.stiffness,
+                );
+            }
+            self.apply_pressure();
+            collide_particles(&mut self.particles, radius, obstacles);
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use jugar_core::Position;
+
+    #[test]
+    fn test_rope_particle_bounds() {
+        assert!(Rope::new(Vec2::ZERO, Vec2::X, 0).is_err());
+        assert!(Rope::new(Vec2::ZERO, Vec2::X, MAX_SOFT_PARTICLES).is_err());
+        let rope = Rope::new(Vec2::ZERO, Vec2::new(10.0, 0.0), 10).unwrap();
+        assert_eq!(rope.particles().len(), 11);
+        assert!((rope.segment_length() - 1.0).abs() < 0.001);
+        assert_eq!(rope.segments().count(), 10);
+    }
+
+    #[test]
+    fn test_hanging_rope_swings_down_but_keeps_length() {
+        let mut rope = Rope::new(Vec2::ZERO, Vec2::new(5.0, 0.0), 5)
+            .unwrap()
+            .pinned_start()
+            .with_iterations(20);
+        for _ in 0..120 {
+            rope.step(1.0 / 60.0, Vec2::new(0.0, -9.81), &[]);
+        }
+        let first = rope.particles()[0].position;
+        let last = rope.particles()[5].position;
+        assert_eq!(first, Vec2::ZERO);
+        assert!(last.y < -1.0, "free end should fall");
+        assert!(first.distance(last) <= 5.0 * 1.05);
+    }
+
+    #[test]
+    fn test_bridge_rests_on_pins() {
+        let mut bridge = Rope::new(Vec2::ZERO, Vec2::new(10.0, 0.0), 10)
+            .unwrap()
+            .pinned_ends();
+        for _ in 0..60 {
+            bridge.step(1.0 / 60.0, Vec2::new(0.0, -9.81), &[]);
+        }
+        assert_eq!(bridge.particles()[10].position, Vec2::new(10.0, 0.0));
+    }
+
+    #[test]
+    fn test_soft_body_keeps_area_on_floor() {
+        let floor =
+            RigidBody::new_static(Position::new(0.0, -1.0)).with_shape(Shape::rect(20.0, 1.0));
+        let mut jelly = SoftBody::circle(Vec2::new(0.0, 2.0), 1.0, 16)
+            .unwrap()
+            .with_pressure(0.8);
+        let rest = jelly.rest_area();
+        for _ in 0..180 {
+            jelly.step(
+                1.0 / 60.0,
+                Vec2::new(0.0, -9.81),
+                core::slice::from_ref(&floor),
+            );
+        }
+        assert!(jelly.center().y > -0.5, "jelly should rest on the floor");
+        assert!(jelly.area() > rest * 0.5, "jelly should not collapse");
+    }
+
+    #[test]
+    fn test_soft_body_particle_bounds() {
+        assert!(SoftBody::circle(Vec2::ZERO, 1.0, 2).is_err());
+        assert!(SoftBody::circle(Vec2::ZERO, 1.0, MAX_SOFT_PARTICLES + 1).is_err());
+        let body = SoftBody::circle(Vec2::ZERO, 1.0, 32).unwrap();
+        assert!((body.area() - core::f32::consts::PI).abs() < 0.05);
+        assert_eq!(body.outline().len(), 32);
+    }
+}
@@ -0,0 +1,235 @@
+//! Keeps ECS entities and physics bodies in step
+//!
+//! Gameplay code works with entities while [`PhysicsWorld`] owns the bodies.
+//! [`PhysicsSync`] bridges the two each frame:
+//!
+//! - an entity given a [`RigidBody`] component gets a body, and the
+//!   component is swapped for a [`PhysicsBodyRef`] to it
+//! - `Position` and `Velocity` edits made by gameplay code are pushed to the
+//!   body before the step
+//! - after the step, body positions and velocities are written back
+//! - removing the [`PhysicsBodyRef`] or despawning the entity removes the
+//!   body
+//!
+//! Bodies have no rotation yet, so only position and velocity are synced.
+//!
+//! ```
+//! use jugar_core::{Position, World};
+//! use jugar_physics::{PhysicsSync, PhysicsWorld, RigidBody};
+//!
+//! let mut world = World::new();
+//! let mut physics = PhysicsWorld::new();
+//! let mut sync = PhysicsSync::new(&mut world);
+//!
+//! let ball = world.spawn();
+//! world.add_component(ball, RigidBody::new(Position::new(0.0, 100.0)));
+//! sync.step(&mut world, &mut physics, 1.0 / 60.0);
+//!
+//! let y = world.get_component::<Position>(ball).unwrap().y;
+//! assert!(y < 100.0);
+//!
+//! world.despawn(ball).unwrap();
+//! sync.pre_step(&mut world, &mut physics);
+//! assert_eq!(physics.body_count(), 0);
+//! ```
+
+use alloc::sync::Arc;
+use std::sync::{Mutex, PoisonError};
+
+use jugar_core::{Changed, Entity, Position, Tick, Velocity, World};
+
+use crate::{BodyHandle, PhysicsWorld, RigidBody};
+
+/// Component linking an entity to its body in the [`PhysicsWorld`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PhysicsBodyRef(pub BodyHandle);
+
+/// Synchronizes a [`World`] with a [`PhysicsWorld`]
+#[derive(Debug)]
+pub struct PhysicsSync {
+    /// Bodies whose [`PhysicsBodyRef`] was removed since the last sync
+    removed: Arc<Mutex<Vec<BodyHandle>>>,
+    /// Change tick of the last write-back
+    last_sync: Tick,
+}
+
+impl PhysicsSync {
+    /// Creates a sync layer, registering its observers on `world`
+    #[must_use]
+    pub fn new(world: &mut World) -> Self {
+        let removed = Arc::new(Mutex::new(Vec::new()));
+        let queue = Arc::clone(&removed);
+        world.on_remove::<PhysicsBodyRef>(move |_, body, _| {
+            queue
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .push(body.0);
+        });
+        Self {
+            removed,
+            last_sync: world.change_tick(),
+        }
+    }
+
+    /// Applies ECS changes to the physics world
+    ///
+    /// Creates bodies for new [`RigidBody`] components, removes the bodies
+    /// of despawned entities, and pushes `Position` and `Velocity` changes
+    /// made since the last [`post_step`](Self::post_step) to the bodies.
+    pub fn pre_step(&mut self, world: &mut World, physics: &mut PhysicsWorld) {
+        let removed: Vec<BodyHandle> = self
+            .removed
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .drain(..)
+            .collect();
+        for handle in removed {
+            let _ = physics.remove_body(handle);
+        }
+
+        let spawned: Vec<(Entity, RigidBody)> = world
+            .query::<RigidBody>()
+            .map(|(entity, body)| (entity, body.clone()))
+            .collect();
+        for (entity, mut body) in spawned {
+            let _ = world.remove_component::<RigidBody>(entity);
+            if let Some(position) = world.get_component::<Position>(entity) {
+                body.position = *position;
+            }
+            if let Some(velocity) = world.get_component::<Velocity>(entity) {
+                body.velocity = *velocity;
+            }
+            let handle = physics.add_body(body);
+            world.add_component(entity, PhysicsBodyRef(handle));
+        }
+
+        let moved: Vec<(BodyHandle, Position)> = world
+            .query_filtered::<PhysicsBodyRef, Changed<Position>>(self.last_sync)
+            .filter_map(|(entity, body)| {
+                world
+                    .get_component::<Position>(entity)
+                    .map(|position| (body.0, *position))
+            })
+            .collect();
+        for (handle, position) in moved {
+            if let Some(body) = physics.get_body_mut(handle) {
+                body.position = position;
+            }
+        }
+
+        let pushed: Vec<(BodyHandle, Velocity)> = world
+            .query_filtered::<PhysicsBodyRef, Changed<Velocity>>(self.last_sync)
+            .filter_map(|(entity, body)| {
+                world
+                    .get_component::<Velocity>(entity)
+                    .map(|velocity| (body.0, *velocity))
+            })
+            .collect();
+        for (handle, velocity) in pushed {
+            if let Some(body) = physics.get_body_mut(handle) {
+                body.velocity = velocity;
+            }
+        }
+    }
+
+    /// Writes body positions and velocities back into the ECS
+    ///
+    /// Entities missing a `Position` or `Velocity` component get one.
+    pub fn post_step(&mut self, world: &mut World, physics: &PhysicsWorld) {
+        let bodies: Vec<(Entity, Position, Velocity)> = world
+            .query::<PhysicsBodyRef>()
+            .filter_map(|(entity, body)| {
+                physics
+                    .get_body(body.0)
+                    .map(|body| (entity, body.position, body.velocity))
+            })
+            .collect();
+        for (entity, position, velocity) in bodies {
+            match world.get_component_mut::<Position>(entity) {
+                Some(current) => *current = position,
+                None => world.add_component(entity, position),
+            }
+            match world.get_component_mut::<Velocity>(entity) {
+                Some(current) => *current = velocity,
+                None => world.add_component(entity, velocity),
+            }
+        }
+        // Our own write-back is older than anything gameplay does next
+        self.last_sync = world.increment_change_tick();
+    }
+
+    /// Runs [`pre_step`](Self::pre_step), a physics step and
+    /// [`post_step`](Self::post_step)
+    pub fn step(&mut self, world: &mut World, physics: &mut PhysicsWorld, dt: f32) {
+        self.pre_step(world, physics);
+        let _ = physics.step(dt);
+        self.post_step(world, physics);
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    const DT: f32 = 1.0 / 60.0;
+
+    fn setup() -> (World, PhysicsWorld, PhysicsSync) {
+        let mut world = World::new();
+        let physics = PhysicsWorld::new();
+        let sync = PhysicsSync::new(&mut world);
+        (world, physics, sync)
+    }
+
+    #[test]
+    fn test_spawn_creates_body_from_components() {
+        let (mut world, mut physics, mut sync) = setup();
+        let e = world.spawn();
+        world.add_component(e, Position::new(5.0, 6.0));
+        world.add_component(e, RigidBody::new(Position::zero()));
+
+        sync.pre_step(&mut world, &mut physics);
+        assert!(!world.has_component::<RigidBody>(e));
+        let handle = world.get_component::<PhysicsBodyRef>(e).unwrap().0;
+        let body = physics.get_body(handle).unwrap();
+        assert!((body.position.x - 5.0).abs() < f32::EPSILON);
+        assert!((body.position.y - 6.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_step_writes_back_and_gameplay_edits_win() {
+        let (mut world, mut physics, mut sync) = setup();
+        physics.set_gravity(glam::Vec2::ZERO);
+        let e = world.spawn();
+        let mut body = RigidBody::new(Position::zero());
+        body.velocity = Velocity::new(60.0, 0.0);
+        world.add_component(e, body);
+
+        sync.step(&mut world, &mut physics, DT);
+        let position = *world.get_component::<Position>(e).unwrap();
+        assert!((position.x - 1.0).abs() < 1e-4);
+        assert!(world.has_component::<Velocity>(e));
+
+        // A teleport by gameplay code overrides the body
+        *world.get_component_mut::<Position>(e).unwrap() = Position::new(100.0, 0.0);
+        sync.step(&mut world, &mut physics, DT);
+        let position = *world.get_component::<Position>(e).unwrap();
+        assert!((position.x - 101.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_despawn_and_remove_drop_bodies() {
+        let (mut world, mut physics, mut sync) = setup();
+        let a = world.spawn();
+        let b = world.spawn();
+        world.add_component(a, RigidBody::new(Position::zero()));
+        world.add_component(b, RigidBody::new(Position::zero()));
+        sync.step(&mut world, &mut physics, DT);
+        assert_eq!(physics.body_count(), 2);
+
+        world.despawn(a).unwrap();
+        assert!(world.remove_component::<PhysicsBodyRef>(b));
+        sync.step(&mut world, &mut physics, DT);
+        assert_eq!(physics.body_count(), 0);
+    }
+}
@@ -0,0 +1,65 @@
+//! Benchmarks for wave function collapse and dungeon generation.
+
+#![allow(missing_docs, unused_results)]
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use jugar_procgen::{Direction, DungeonGenerator, Wfc};
+
+/// Terrain-like rules: water next to sand, sand next to grass, grass next
+/// to forest, so collapse has to propagate constraints
+fn terrain_wfc(size: usize, seed: u64) -> Wfc {
+    let mut wfc = Wfc::new(size, size, 4, seed);
+    for tile in 0..4u16 {
+        for dir in Direction::ALL {
+            wfc.rules_mut().add(tile, dir, tile);
+            if tile > 0 {
+                wfc.rules_mut().add(tile, dir, tile - 1);
+            }
+            if tile < 3 {
+                wfc.rules_mut().add(tile, dir, tile + 1);
+            }
+        }
+    }
+    wfc
+}
+
+fn bench_wfc_collapse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("wfc_collapse");
+
+    for size in &[16, 32, 64] {
+        group.bench_with_input(BenchmarkId::new("grid", size), size, |b, &size| {
+            let mut seed = 0;
+            b.iter(|| {
+                seed += 1;
+                let mut wfc = terrain_wfc(size, seed);
+                black_box(wfc.collapse())
+            });
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_dungeon_generate(c: &mut Criterion) {
+    let mut group = c.benchmark_group("dungeon_generate");
+
+    for (width, height, rooms) in &[(40, 30, 8), (80, 60, 16), (160, 120, 32)] {
+        let generator = DungeonGenerator::new(*width, *height).with_room_count(*rooms);
+        group.bench_with_input(
+            BenchmarkId::new("size", format!("{width}x{height}")),
+            &generator,
+            |b, generator| {
+                let mut seed = 0;
+                b.iter(|| {
+                    seed += 1;
+                    black_box(generator.generate(seed))
+                });
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_wfc_collapse, bench_dungeon_generate);
+criterion_main!(benches);
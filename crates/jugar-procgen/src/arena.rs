@@ -0,0 +1,337 @@
+//! Fair arenas for two-player games.
+//!
+//! An [`ArenaGenerator`] scatters walls over one half of the map and copies
+//! them onto the other half by a [`Symmetry`], then places one spawn in each
+//! half with the objective at the center. Every generated [`Arena`] is
+//! checked before it is returned: the tiles really are symmetric and both
+//! spawns walk the same distance to the objective. Unfair attempts are
+//! thrown away and generation tries again.
+//!
+//! ```
+//! use jugar_procgen::{ArenaGenerator, Symmetry};
+//!
+//! let arena = ArenaGenerator::new(21, 11)
+//!     .with_symmetry(Symmetry::Rotational)
+//!     .generate(7)
+//!     .unwrap();
+//! assert!(arena.is_fair());
+//! let (a, b) = arena.spawn_distances().unwrap();
+//! assert_eq!(a, b);
+//! ```
+
+use alloc::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Dungeon, DungeonTile, ProcgenError, Result, Rng};
+
+/// How one half of an arena maps onto the other
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum Symmetry {
+    /// Left half mirrored onto the right (Pong-style, needs an odd width)
+    #[default]
+    Mirror,
+    /// Top half mirrored onto the bottom (needs an odd height)
+    MirrorVertical,
+    /// Turned half a circle about the center (needs odd width and height)
+    Rotational,
+}
+
+impl Symmetry {
+    /// The tile matching `(x, y)` on a `width` x `height` map
+    #[must_use]
+    pub const fn map(self, x: usize, y: usize, width: usize, height: usize) -> (usize, usize) {
+        match self {
+            Self::Mirror => (width - 1 - x, y),
+            Self::MirrorVertical => (x, height - 1 - y),
+            Self::Rotational => (width - 1 - x, height - 1 - y),
+        }
+    }
+
+    /// Whether the center tile maps onto itself, so it can hold the objective
+    const fn has_center(self, width: usize, height: usize) -> bool {
+        match self {
+            Self::Mirror => width % 2 == 1,
+            Self::MirrorVertical => height % 2 == 1,
+            Self::Rotational => width % 2 == 1 && height % 2 == 1,
+        }
+    }
+}
+
+/// A generated arena
+#[derive(Debug, Clone)]
+pub struct Arena {
+    /// Floor and wall tiles
+    pub layout: Dungeon,
+    /// How the halves match
+    pub symmetry: Symmetry,
+    /// One spawn per player, each the other's match
+    pub spawns: [(usize, usize); 2],
+    /// The center tile both players race for
+    pub objective: (usize, usize),
+}
+
+impl Arena {
+    /// Walking distance from each spawn to the objective, if both reach it
+    #[must_use]
+    pub fn spawn_distances(&self) -> Option<(usize, usize)> {
+        let distances = walk_distances(&self.layout, self.objective);
+        let at = |(x, y): (usize, usize)| distances[y * self.layout.width + x];
+        Some((at(self.spawns[0])?, at(self.spawns[1])?))
+    }
+
+    /// Checks the arena is fair to both players
+    ///
+    /// Every tile matches its partner, the spawns match each other, the
+    /// objective is its own partner, and both spawns are the same walk from
+    /// the objective.
+    #[must_use]
+    pub fn is_fair(&self) -> bool {
+        let (width, height) = (self.layout.width, self.layout.height);
+        let map = |(x, y): (usize, usize)| self.symmetry.map(x, y, width, height);
+        let symmetric = (0..height).all(|y| {
+            (0..width).all(|x| {
+                let (mx, my) = map((x, y));
+                self.layout.get(x, y) == self.layout.get(mx, my)
+            })
+        });
+        symmetric
+            && map(self.spawns[0]) == self.spawns[1]
+            && map(self.objective) == self.objective
+            && self.spawn_distances().is_some_and(|(a, b)| a == b)
+    }
+}
+
+/// Generator for symmetric two-player arenas
+#[derive(Debug, Clone)]
+pub struct ArenaGenerator {
+    /// Arena width
+    pub width: usize,
+    /// Arena height
+    pub height: usize,
+    /// How the halves match
+    pub symmetry: Symmetry,
+    /// Chance each tile becomes a wall (0.0 to 1.0)
+    pub wall_density: f32,
+    /// Shortest walk from a spawn to the objective
+    pub min_spawn_distance: usize,
+    /// Attempts before giving up
+    pub max_attempts: usize,
+}
+
+impl ArenaGenerator {
+    /// Creates a mirrored arena generator
+    #[must_use]
+    pub const fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            symmetry: Symmetry::Mirror,
+            wall_density: 0.2,
+            min_spawn_distance: 4,
+            max_attempts: 50,
+        }
+    }
+
+    /// Sets the symmetry
+    #[must_use]
+    pub const fn with_symmetry(mut self, symmetry: Symmetry) -> Self {
+        self.symmetry = symmetry;
+        self
+    }
+
+    /// Sets the chance each tile becomes a wall
+    #[must_use]
+    pub const fn with_wall_density(mut self, density: f32) -> Self {
+        self.wall_density = density;
+        self
+    }
+
+    /// Sets the shortest walk from a spawn to the objective
+    #[must_use]
+    pub const fn with_min_spawn_distance(mut self, distance: usize) -> Self {
+        self.min_spawn_distance = distance;
+        self
+    }
+
+    /// Generates a fair arena with the given seed
+    ///
+    /// # Errors
+    ///
+    /// Returns `ProcgenError::InvalidParameters` if the size has no center
+    /// tile for the symmetry, or `ProcgenError::GenerationFailed` if no fair
+    /// arena was found in `max_attempts` tries.
+    pub fn generate(&self, seed: u64) -> Result<Arena> {
+        let (width, height) = (self.width, self.height);
+        if width.min(height) < 5 || !self.symmetry.has_center(width, height) {
+            return Err(ProcgenError::InvalidParameters(format!(
+                "A {:?} arena needs a center tile, so {width}x{height} won't do",
+                self.symmetry
+            )));
+        }
+
+        let mut rng = Rng::new(seed);
+        for _ in 0..self.max_attempts {
+            if let Some(arena) = self.attempt(&mut rng) {
+                if arena.is_fair() {
+                    return Ok(arena);
+                }
+            }
+        }
+        Err(ProcgenError::GenerationFailed(format!(
+            "No fair arena in {} attempts",
+            self.max_attempts
+        )))
+    }
+
+    fn attempt(&self, rng: &mut Rng) -> Option<Arena> {
+        let (width, height) = (self.width, self.height);
+        let objective = (width / 2, height / 2);
+        let map = |x: usize, y: usize| self.symmetry.map(x, y, width, height);
+        // The first tile of each matching pair, in row-major order
+        let leads = |x: usize, y: usize| {
+            let (mx, my) = map(x, y);
+            y * width + x <= my * width + mx
+        };
+
+        let mut layout = Dungeon::new(width, height);
+        for y in 1..height - 1 {
+            for x in 1..width - 1 {
+                layout.set(x, y, DungeonTile::Floor);
+            }
+        }
+        for y in 1..height - 1 {
+            for x in 1..width - 1 {
+                // Keep the objective reachable from every side
+                let near_objective = x.abs_diff(objective.0) <= 1 && y.abs_diff(objective.1) <= 1;
+                if !leads(x, y) || near_objective || rng.next_f32() >= self.wall_density {
+                    continue;
+                }
+                let (mx, my) = map(x, y);
+                layout.set(x, y, DungeonTile::Wall);
+                layout.set(mx, my, DungeonTile::Wall);
+            }
+        }
+
+        let distances = walk_distances(&layout, objective);
+        let spawns: Vec<(usize, usize)> = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .filter(|&(x, y)| map(x, y) != (x, y) && leads(x, y))
+            .filter(|&(x, y)| {
+                distances[y * width + x].is_some_and(|d| d >= self.min_spawn_distance)
+            })
+            .collect();
+        if spawns.is_empty() {
+            return None;
+        }
+        let spawn = spawns[rng.next_usize(spawns.len())];
+        Some(Arena {
+            layout,
+            symmetry: self.symmetry,
+            spawns: [spawn, map(spawn.0, spawn.1)],
+            objective,
+        })
+    }
+}
+
+/// Steps from `from` to every tile, walking four ways over walkable tiles
+fn walk_distances(layout: &Dungeon, from: (usize, usize)) -> Vec<Option<usize>> {
+    let mut distances = vec![None; layout.tiles.len()];
+    if !layout
+        .get(from.0, from.1)
+        .is_some_and(DungeonTile::is_walkable)
+    {
+        return distances;
+    }
+    distances[from.1 * layout.width + from.0] = Some(0);
+    let mut queue = VecDeque::from([(from, 0)]);
+    while let Some(((x, y), steps)) = queue.pop_front() {
+        let neighbors = [
+            (x.checked_sub(1), Some(y)),
+            (Some(x + 1), Some(y)),
+            (Some(x), y.checked_sub(1)),
+            (Some(x), Some(y + 1)),
+        ];
+        for (nx, ny) in neighbors {
+            let (Some(nx), Some(ny)) = (nx, ny) else {
+                continue;
+            };
+            if !layout.get(nx, ny).is_some_and(DungeonTile::is_walkable) {
+                continue;
+            }
+            let seen = &mut distances[ny * layout.width + nx];
+            if seen.is_none() {
+                *seen = Some(steps + 1);
+                queue.push_back(((nx, ny), steps + 1));
+            }
+        }
+    }
+    distances
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_symmetry_is_fair() {
+        let symmetries = [
+            Symmetry::Mirror,
+            Symmetry::MirrorVertical,
+            Symmetry::Rotational,
+        ];
+        for symmetry in symmetries {
+            for seed in 1..10 {
+                let arena = ArenaGenerator::new(21, 15)
+                    .with_symmetry(symmetry)
+                    .with_wall_density(0.3)
+                    .generate(seed)
+                    .unwrap();
+                assert!(arena.is_fair(), "{symmetry:?} seed {seed}");
+                let (a, b) = arena.spawn_distances().unwrap();
+                assert_eq!(a, b);
+                assert!(a >= 4);
+                assert_ne!(arena.spawns[0], arena.spawns[1]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_unfair_arenas_are_caught() {
+        let mut arena = ArenaGenerator::new(15, 9).generate(3).unwrap();
+        let (x, y) = arena.spawns[0];
+        let moved = if arena.layout.get(x, y + 1) == Some(DungeonTile::Floor) {
+            (x, y + 1)
+        } else {
+            (x, y - 1)
+        };
+        arena.spawns[0] = moved;
+        assert!(!arena.is_fair());
+
+        let mut arena = ArenaGenerator::new(15, 9).generate(3).unwrap();
+        arena.layout.set(1, 1, DungeonTile::Wall);
+        arena.layout.set(13, 1, DungeonTile::Floor);
+        assert!(!arena.is_fair());
+    }
+
+    #[test]
+    fn test_sizes_without_a_center_are_rejected() {
+        assert!(ArenaGenerator::new(20, 15).generate(1).is_err());
+        assert!(ArenaGenerator::new(20, 15)
+            .with_symmetry(Symmetry::MirrorVertical)
+            .generate(1)
+            .is_ok());
+        assert!(ArenaGenerator::new(21, 14)
+            .with_symmetry(Symmetry::Rotational)
+            .generate(1)
+            .is_err());
+        assert!(matches!(
+            ArenaGenerator::new(21, 15)
+                .with_wall_density(1.0)
+                .with_min_spawn_distance(100)
+                .generate(1),
+            Err(ProcgenError::GenerationFailed(_))
+        ));
+    }
+}
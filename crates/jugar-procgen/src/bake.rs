@@ -0,0 +1,207 @@
+//! Noise baked into image buffers.
+//!
+//! Sampling [`ValueNoise`] for every pixel of every frame is wasted work
+//! for a background or heightmap that never changes. [`ValueNoise::bake`]
+//! fills a buffer once, spread over threads on native targets, and
+//! [`ValueNoise::bake_rgba8`] turns it into pixels ready to upload or
+//! snapshot. Every pixel is sampled on its own, so the result is the same
+//! however many threads fill it.
+//!
+//! ```
+//! use jugar_procgen::{BakeOptions, ValueNoise};
+//!
+//! let noise = ValueNoise::new(7).with_scale(16.0);
+//! let heights = noise.bake(64, 32);
+//! assert_eq!(heights.len(), 64 * 32);
+//!
+//! let sky = noise.bake_rgba8(64, 32, &BakeOptions::new().with_seamless(true));
+//! assert_eq!(sky.len(), 64 * 32 * 4);
+//! ```
+
+use jugar_core::ExecutionMode;
+
+use crate::{lerp, ValueNoise};
+
+/// How to bake noise into a buffer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BakeOptions {
+    seamless: bool,
+    mode: ExecutionMode,
+    low: [u8; 4],
+    high: [u8; 4],
+}
+
+impl Default for BakeOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BakeOptions {
+    /// Not seamless, black to white, on every available thread
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            seamless: false,
+            mode: ExecutionMode::detect(),
+            low: [0, 0, 0, 255],
+            high: [255, 255, 255, 255],
+        }
+    }
+
+    /// Makes the texture tile: its right edge continues into its left and
+    /// its bottom into its top
+    ///
+    /// Blends four offset samples per pixel, which softens contrast a little.
+    #[must_use]
+    pub const fn with_seamless(mut self, seamless: bool) -> Self {
+        self.seamless = seamless;
+        self
+    }
+
+    /// Sets how the buffer is filled
+    #[must_use]
+    pub const fn with_mode(mut self, mode: ExecutionMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Sets the RGBA colors of noise 0 and noise 1 for [`ValueNoise::bake_rgba8`]
+    #[must_use]
+    pub const fn with_colors(mut self, low: [u8; 4], high: [u8; 4]) -> Self {
+        self.low = low;
+        self.high = high;
+        self
+    }
+}
+
+impl ValueNoise {
+    /// Samples every pixel of a `width` x `height` image, row by row
+    #[must_use]
+    pub fn bake(&self, width: usize, height: usize) -> Vec<f32> {
+        self.bake_with(width, height, &BakeOptions::new())
+    }
+
+    /// Like [`Self::bake`], with options
+    #[must_use]
+    pub fn bake_with(&self, width: usize, height: usize, options: &BakeOptions) -> Vec<f32> {
+        let mut pixels = vec![0.0; width * height];
+        if pixels.is_empty() {
+            return pixels;
+        }
+        let seamless = options.seamless;
+        let fill = |first_row: usize, band: &mut [f32]| {
+            for (row, row_pixels) in band.chunks_mut(width).enumerate() {
+                for (x, pixel) in row_pixels.iter_mut().enumerate() {
+                    *pixel = self.baked_pixel(x, first_row + row, width, height, seamless);
+                }
+            }
+        };
+
+        let threads = options.mode.threads().min(height);
+        if threads <= 1 {
+            fill(0, &mut pixels);
+            return pixels;
+        }
+        // Bands of whole rows; the calling thread takes the first
+        let rows = height.div_ceil(threads);
+        let fill = &fill;
+        std::thread::scope(|scope| {
+            let mut bands = pixels.chunks_mut(rows * width).enumerate();
+            let first = bands.next();
+            for (index, band) in bands {
+                let _ = scope.spawn(move || fill(index * rows, band));
+            }
+            if let Some((_, band)) = first {
+                fill(0, band);
+            }
+        });
+        pixels
+    }
+
+    /// Bakes RGBA8 pixels, shading each from the low to the high color
+    #[must_use]
+    pub fn bake_rgba8(&self, width: usize, height: usize, options: &BakeOptions) -> Vec<u8> {
+        let (low, high) = (options.low, options.high);
+        self.bake_with(width, height, options)
+            .into_iter()
+            .flat_map(|value| {
+                let t = value.clamp(0.0, 1.0);
+                core::array::from_fn::<u8, 4, _>(|channel| {
+                    lerp(f32::from(low[channel]), f32::from(high[channel]), t).round() as u8
+                })
+            })
+            .collect()
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn baked_pixel(&self, x: usize, y: usize, width: usize, height: usize, seamless: bool) -> f32 {
+        let (px, py) = (x as f32, y as f32);
+        if !seamless {
+            return self.sample(px, py);
+        }
+        // Fade towards the samples one tile up and left, so the far edges
+        // meet the near ones
+        let (tile_w, tile_h) = (width as f32, height as f32);
+        let (fade_x, fade_y) = (px / tile_w, py / tile_h);
+        let top = lerp(self.sample(px, py), self.sample(px - tile_w, py), fade_x);
+        let bottom = lerp(
+            self.sample(px, py - tile_h),
+            self.sample(px - tile_w, py - tile_h),
+            fade_x,
+        );
+        lerp(top, bottom, fade_y)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bake_is_the_same_on_any_thread_count() {
+        let noise = ValueNoise::new(3).with_scale(8.0);
+        let sequential = BakeOptions::new().with_mode(ExecutionMode::Sequential);
+        let baked = noise.bake_with(37, 23, &sequential);
+        assert_eq!(baked.len(), 37 * 23);
+        assert_eq!(
+            baked[5 * 37 + 11].to_bits(),
+            noise.sample(11.0, 5.0).to_bits()
+        );
+
+        for threads in [2, 4, 64] {
+            let parallel = sequential.with_mode(ExecutionMode::Parallel { threads });
+            assert_eq!(noise.bake_with(37, 23, &parallel), baked);
+        }
+        assert!(noise.bake(0, 10).is_empty());
+    }
+
+    #[test]
+    fn test_seamless_edges_meet() {
+        let noise = ValueNoise::new(11).with_scale(5.0);
+        let (width, height) = (32, 24);
+        for y in 0..height {
+            let past_right = noise.baked_pixel(width, y, width, height, true);
+            let left = noise.baked_pixel(0, y, width, height, true);
+            assert!((past_right - left).abs() < 1e-5);
+        }
+        for x in 0..width {
+            let past_bottom = noise.baked_pixel(x, height, width, height, true);
+            let top = noise.baked_pixel(x, 0, width, height, true);
+            assert!((past_bottom - top).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_rgba8_shades_between_colors() {
+        let noise = ValueNoise::new(5).with_scale(4.0);
+        let options = BakeOptions::new().with_colors([0, 0, 40, 255], [0, 0, 240, 255]);
+        let pixels = noise.bake_rgba8(16, 8, &options);
+        assert_eq!(pixels.len(), 16 * 8 * 4);
+        for pixel in pixels.chunks(4) {
+            assert_eq!((pixel[0], pixel[1], pixel[3]), (0, 0, 255));
+            assert!((40..=240).contains(&pixel[2]));
+        }
+    }
+}
@@ -0,0 +1,437 @@
+//! Endless worlds streamed in chunks.
+//!
+//! A [`ChunkedWorld`] splits an unbounded map into square chunks and keeps
+//! only those near the camera in memory. Each chunk is generated on demand
+//! by a [`ChunkGenerator`] from the world seed and its [`ChunkCoord`], so
+//! walking away and coming back rebuilds exactly the same ground. Entities
+//! a game attaches to a chunk (dropped loot, moved crates) are set aside
+//! when it unloads and handed back when it loads again.
+//!
+//! Chunks load within [`ChunkedWorld::load_radius`] of the camera's chunk
+//! but only unload once a further [`ChunkedWorld::hysteresis`] chunks away,
+//! so pacing along a chunk border doesn't regenerate the same chunk every
+//! frame.
+
+use alloc::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{splitmix64, Dungeon, DungeonGenerator, Result, Terrain, TerrainGenerator};
+
+/// Default chunks loaded in each direction around the camera
+pub const DEFAULT_LOAD_RADIUS: u32 = 2;
+
+/// Default extra chunks kept before unloading
+pub const DEFAULT_HYSTERESIS: u32 = 1;
+
+/// Position of a chunk in the chunk grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct ChunkCoord {
+    /// Column
+    pub x: i32,
+    /// Row
+    pub y: i32,
+}
+
+impl ChunkCoord {
+    /// Creates a chunk coordinate
+    #[must_use]
+    pub const fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+
+    /// The chunk containing a world position
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn containing(world_x: f32, world_y: f32, chunk_world_size: f32) -> Self {
+        Self {
+            x: (world_x / chunk_world_size).floor() as i32,
+            y: (world_y / chunk_world_size).floor() as i32,
+        }
+    }
+
+    /// Chunks between this one and `other`, counting diagonals as one step
+    #[must_use]
+    pub const fn distance(self, other: Self) -> u32 {
+        let dx = self.x.abs_diff(other.x);
+        let dy = self.y.abs_diff(other.y);
+        if dx > dy {
+            dx
+        } else {
+            dy
+        }
+    }
+
+    /// Seed for generating this chunk, mixed from the world seed
+    ///
+    /// Neighbouring chunks get unrelated seeds (splitmix64).
+    #[must_use]
+    #[allow(clippy::cast_sign_loss)]
+    pub const fn seed(self, world_seed: u64) -> u64 {
+        let coords = ((self.x as u32 as u64) << 32) | self.y as u32 as u64;
+        splitmix64(world_seed ^ coords)
+    }
+}
+
+/// Builds the content of one chunk
+///
+/// Must be deterministic: the same coordinate and world seed always give
+/// the same chunk. Closures `Fn(ChunkCoord, u64) -> Result<T>` implement
+/// it, which is the easy way to stream WFC chunks.
+pub trait ChunkGenerator {
+    /// Generated chunk content
+    type Chunk;
+
+    /// Generates the chunk at `coord`
+    ///
+    /// # Errors
+    ///
+    /// Returns the underlying generator's error.
+    fn generate(&self, coord: ChunkCoord, world_seed: u64) -> Result<Self::Chunk>;
+}
+
+impl<T, F> ChunkGenerator for F
+where
+    F: Fn(ChunkCoord, u64) -> Result<T>,
+{
+    type Chunk = T;
+
+    fn generate(&self, coord: ChunkCoord, world_seed: u64) -> Result<T> {
+        self(coord, world_seed)
+    }
+}
+
+/// Seamless terrain: every chunk samples the same world-wide noise
+impl ChunkGenerator for TerrainGenerator {
+    type Chunk = Terrain;
+
+    #[allow(clippy::cast_possible_wrap)]
+    fn generate(&self, coord: ChunkCoord, world_seed: u64) -> Result<Terrain> {
+        self.generate_at(
+            world_seed,
+            i64::from(coord.x) * self.width as i64,
+            i64::from(coord.y) * self.height as i64,
+        )
+    }
+}
+
+/// A separate dungeon per chunk, seeded by its coordinate
+impl ChunkGenerator for DungeonGenerator {
+    type Chunk = Dungeon;
+
+    fn generate(&self, coord: ChunkCoord, world_seed: u64) -> Result<Dungeon> {
+        // The inherent method, not this one
+        Self::generate(self, coord.seed(world_seed))
+    }
+}
+
+/// A chunk in memory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoadedChunk<C, E> {
+    /// Where it is
+    pub coord: ChunkCoord,
+    /// Generated content
+    pub content: C,
+    /// Entities living in it
+    pub entities: Vec<E>,
+}
+
+/// Chunks loaded and unloaded by one [`ChunkedWorld::update`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChunkChanges {
+    /// Newly generated chunks, nearest first
+    pub loaded: Vec<ChunkCoord>,
+    /// Chunks dropped from memory
+    pub unloaded: Vec<ChunkCoord>,
+}
+
+impl ChunkChanges {
+    /// Returns true if nothing changed
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.loaded.is_empty() && self.unloaded.is_empty()
+    }
+}
+
+/// Keeps the chunks around the camera loaded
+#[derive(Debug, Clone)]
+pub struct ChunkedWorld<G: ChunkGenerator, E> {
+    generator: G,
+    seed: u64,
+    chunk_world_size: f32,
+    load_radius: u32,
+    hysteresis: u32,
+    load_budget: usize,
+    loaded: BTreeMap<ChunkCoord, LoadedChunk<G::Chunk, E>>,
+    stored: BTreeMap<ChunkCoord, Vec<E>>,
+}
+
+impl<G: ChunkGenerator, E> ChunkedWorld<G, E> {
+    /// Creates an empty world whose chunks span `chunk_world_size` world units
+    #[must_use]
+    pub fn new(generator: G, seed: u64, chunk_world_size: f32) -> Self {
+        Self {
+            generator,
+            seed,
+            chunk_world_size: chunk_world_size.max(f32::EPSILON),
+            load_radius: DEFAULT_LOAD_RADIUS,
+            hysteresis: DEFAULT_HYSTERESIS,
+            load_budget: usize::MAX,
+            loaded: BTreeMap::new(),
+            stored: BTreeMap::new(),
+        }
+    }
+
+    /// Sets how many chunks load in each direction around the camera
+    #[must_use]
+    pub const fn with_load_radius(mut self, radius: u32) -> Self {
+        self.load_radius = radius;
+        self
+    }
+
+    /// Sets how many extra chunks stay loaded before unloading
+    #[must_use]
+    pub const fn with_hysteresis(mut self, chunks: u32) -> Self {
+        self.hysteresis = chunks;
+        self
+    }
+
+    /// Limits chunks generated per [`update`](Self::update), spreading a
+    /// fast camera's loading over several frames
+    #[must_use]
+    pub fn with_load_budget(mut self, chunks: usize) -> Self {
+        self.load_budget = chunks.max(1);
+        self
+    }
+
+    /// World seed
+    #[must_use]
+    pub const fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Chunks loaded in each direction around the camera
+    #[must_use]
+    pub const fn load_radius(&self) -> u32 {
+        self.load_radius
+    }
+
+    /// Extra chunks kept before unloading
+    #[must_use]
+    pub const fn hysteresis(&self) -> u32 {
+        self.hysteresis
+    }
+
+    /// The chunk containing a world position
+    #[must_use]
+    pub fn chunk_coord_at(&self, world_x: f32, world_y: f32) -> ChunkCoord {
+        ChunkCoord::containing(world_x, world_y, self.chunk_world_size)
+    }
+
+    /// Loads chunks near the camera and unloads far ones
+    ///
+    /// # Errors
+    ///
+    /// Returns the generator's error; chunks loaded before it stay loaded.
+    pub fn update(&mut self, camera_x: f32, camera_y: f32) -> Result<ChunkChanges> {
+        let center = self.chunk_coord_at(camera_x, camera_y);
+        let mut changes = ChunkChanges::default();
+
+        let keep = self.load_radius.saturating_add(self.hysteresis);
+        let far: Vec<ChunkCoord> = self
+            .loaded
+            .keys()
+            .filter(|coord| coord.distance(center) > keep)
+            .copied()
+            .collect();
+        for coord in far {
+            if let Some(chunk) = self.loaded.remove(&coord) {
+                if !chunk.entities.is_empty() {
+                    let _ = self.stored.insert(coord, chunk.entities);
+                }
+                changes.unloaded.push(coord);
+            }
+        }
+
+        let radius = i32::try_from(self.load_radius).unwrap_or(i32::MAX);
+        let mut wanted = Vec::new();
+        for y in center.y.saturating_sub(radius)..=center.y.saturating_add(radius) {
+            for x in center.x.saturating_sub(radius)..=center.x.saturating_add(radius) {
+                let coord = ChunkCoord::new(x, y);
+                if !self.loaded.contains_key(&coord) {
+                    wanted.push(coord);
+                }
+            }
+        }
+        wanted.sort_by_key(|coord| (coord.distance(center), *coord));
+        for coord in wanted.into_iter().take(self.load_budget) {
+            let content = self.generator.generate(coord, self.seed)?;
+            let entities = self.stored.remove(&coord).unwrap_or_default();
+            let _ = self.loaded.insert(
+                coord,
+                LoadedChunk {
+                    coord,
+                    content,
+                    entities,
+                },
+            );
+            changes.loaded.push(coord);
+        }
+        Ok(changes)
+    }
+
+    /// A loaded chunk
+    #[must_use]
+    pub fn chunk(&self, coord: ChunkCoord) -> Option<&LoadedChunk<G::Chunk, E>> {
+        self.loaded.get(&coord)
+    }
+
+    /// A loaded chunk, mutably
+    pub fn chunk_mut(&mut self, coord: ChunkCoord) -> Option<&mut LoadedChunk<G::Chunk, E>> {
+        self.loaded.get_mut(&coord)
+    }
+
+    /// The loaded chunk containing a world position
+    #[must_use]
+    pub fn chunk_at(&self, world_x: f32, world_y: f32) -> Option<&LoadedChunk<G::Chunk, E>> {
+        self.chunk(self.chunk_coord_at(world_x, world_y))
+    }
+
+    /// Loaded chunks, in coordinate order
+    pub fn loaded(&self) -> impl Iterator<Item = &LoadedChunk<G::Chunk, E>> {
+        self.loaded.values()
+    }
+
+    /// Number of loaded chunks
+    #[must_use]
+    pub fn loaded_count(&self) -> usize {
+        self.loaded.len()
+    }
+
+    /// Returns true if a chunk is in memory
+    #[must_use]
+    pub fn is_loaded(&self, coord: ChunkCoord) -> bool {
+        self.loaded.contains_key(&coord)
+    }
+
+    /// Adds an entity to the chunk containing a world position, loaded or not
+    pub fn spawn(&mut self, world_x: f32, world_y: f32, entity: E) {
+        let coord = self.chunk_coord_at(world_x, world_y);
+        if let Some(chunk) = self.loaded.get_mut(&coord) {
+            chunk.entities.push(entity);
+        } else {
+            self.stored.entry(coord).or_default().push(entity);
+        }
+    }
+
+    /// Entities set aside with unloaded chunks, for saving the game
+    pub fn stored(&self) -> impl Iterator<Item = (ChunkCoord, &[E])> {
+        self.stored
+            .iter()
+            .map(|(coord, entities)| (*coord, entities.as_slice()))
+    }
+
+    /// Restores saved entities for a chunk, appending if it's loaded
+    pub fn restore(&mut self, coord: ChunkCoord, entities: Vec<E>) {
+        if let Some(chunk) = self.loaded.get_mut(&coord) {
+            chunk.entities.extend(entities);
+        } else if !entities.is_empty() {
+            self.stored.entry(coord).or_default().extend(entities);
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::{Direction, ProcgenError, Wfc};
+
+    fn terrain_world() -> ChunkedWorld<TerrainGenerator, &'static str> {
+        ChunkedWorld::new(TerrainGenerator::new(8, 8), 11, 64.0).with_load_radius(1)
+    }
+
+    #[test]
+    fn test_chunk_coords() {
+        assert_eq!(
+            ChunkCoord::containing(-0.5, 70.0, 64.0),
+            ChunkCoord::new(-1, 1)
+        );
+        assert_eq!(ChunkCoord::new(0, 0).distance(ChunkCoord::new(-2, 1)), 2);
+        let seed = ChunkCoord::new(3, -4).seed(9);
+        assert_eq!(seed, ChunkCoord::new(3, -4).seed(9));
+        assert_ne!(seed, ChunkCoord::new(-4, 3).seed(9));
+    }
+
+    #[test]
+    fn test_loads_around_camera_with_hysteresis() {
+        let mut world = terrain_world();
+        let changes = world.update(0.0, 0.0).unwrap();
+        assert_eq!(changes.loaded.len(), 9);
+        assert_eq!(changes.loaded[0], ChunkCoord::new(0, 0));
+        assert!(world.update(10.0, 10.0).unwrap().is_empty());
+
+        // One chunk right: new column loads, nothing unloads yet
+        let changes = world.update(64.0 + 1.0, 0.0).unwrap();
+        assert_eq!(changes.loaded.len(), 3);
+        assert!(changes.unloaded.is_empty());
+        // Back again: still nothing to do
+        assert!(world.update(0.0, 0.0).unwrap().is_empty());
+
+        let changes = world.update(4.0 * 64.0, 0.0).unwrap();
+        assert!(changes.unloaded.contains(&ChunkCoord::new(-1, 0)));
+        assert!(!world.is_loaded(ChunkCoord::new(0, 0)));
+    }
+
+    #[test]
+    fn test_regenerated_chunk_and_entities_survive() {
+        let mut world = terrain_world();
+        let _ = world.update(0.0, 0.0).unwrap();
+        let original = world.chunk(ChunkCoord::new(1, 1)).unwrap().content.clone();
+        world.spawn(70.0, 70.0, "crate");
+        world.spawn(-1000.0, 0.0, "far chest");
+        assert_eq!(world.stored().count(), 1);
+
+        let _ = world.update(1000.0, 1000.0).unwrap();
+        assert!(!world.is_loaded(ChunkCoord::new(1, 1)));
+        assert_eq!(world.stored().count(), 2);
+
+        let _ = world.update(0.0, 0.0).unwrap();
+        let chunk = world.chunk_at(70.0, 70.0).unwrap();
+        assert_eq!(chunk.content.tiles, original.tiles);
+        assert_eq!(chunk.entities, ["crate"]);
+    }
+
+    #[test]
+    fn test_load_budget_spreads_work() {
+        let mut world = terrain_world().with_load_budget(4);
+        assert_eq!(world.update(0.0, 0.0).unwrap().loaded.len(), 4);
+        assert_eq!(world.update(0.0, 0.0).unwrap().loaded.len(), 4);
+        assert_eq!(world.update(0.0, 0.0).unwrap().loaded.len(), 1);
+        assert_eq!(world.loaded_count(), 9);
+    }
+
+    #[test]
+    fn test_closure_generator_for_wfc() {
+        let generator = |coord: ChunkCoord, seed: u64| -> Result<Vec<Option<u16>>> {
+            let mut wfc = Wfc::new(4, 4, 2, coord.seed(seed));
+            for dir in Direction::ALL {
+                wfc.rules_mut().add(0, dir, 1);
+                wfc.rules_mut().add(1, dir, 0);
+            }
+            wfc.collapse()?;
+            Ok(wfc.result())
+        };
+        let mut world: ChunkedWorld<_, ()> =
+            ChunkedWorld::new(generator, 1, 4.0).with_load_radius(0);
+        let _ = world.update(0.0, 0.0).unwrap();
+        let chunk = world.chunk(ChunkCoord::new(0, 0)).unwrap();
+        assert!(chunk.content.iter().all(Option::is_some));
+
+        let failing = |_: ChunkCoord, _: u64| -> Result<()> {
+            Err(ProcgenError::GenerationFailed("no".to_string()))
+        };
+        let mut broken: ChunkedWorld<_, ()> = ChunkedWorld::new(failing, 1, 4.0);
+        assert!(broken.update(0.0, 0.0).is_err());
+    }
+}
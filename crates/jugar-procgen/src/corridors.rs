@@ -0,0 +1,292 @@
+//! Corridor styles and doors for [`DungeonGenerator`](crate::DungeonGenerator).
+//!
+//! A hard L between every pair of rooms makes every level look alike.
+//! [`CorridorStyle`] picks how corridors wander between room centers, and
+//! doors can be placed where a corridor passes through a room's wall.
+//!
+//! ```
+//! use jugar_procgen::{CorridorStyle, DungeonGenerator, DungeonTile};
+//!
+//! let dungeon = DungeonGenerator::new(40, 30)
+//!     .with_corridor_style(CorridorStyle::Winding)
+//!     .with_doors(true)
+//!     .generate(7)
+//!     .unwrap();
+//! assert!(dungeon.tiles.contains(&DungeonTile::Corridor));
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Direction, Dungeon, DungeonTile, Rng};
+
+/// Chance a jittered corridor sidesteps instead of heading on
+const JITTER_CHANCE: f32 = 0.3;
+
+/// Chance a winding corridor heads for its target instead of wandering
+const WINDING_FOCUS: f32 = 0.5;
+
+/// A walk gives up and finishes as an L after this many steps per tile of
+/// distance
+const WALK_STEPS_PER_TILE: usize = 20;
+
+/// How corridors between rooms are carved
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum CorridorStyle {
+    /// One horizontal and one vertical run
+    #[default]
+    LShaped,
+    /// Heads for the target, sidestepping now and then
+    Jittered,
+    /// Wanders towards the target (drunkard's walk)
+    Winding,
+    /// L-shaped, two tiles wide
+    Wide,
+}
+
+/// Carves a corridor from one room center to another
+pub fn carve(
+    dungeon: &mut Dungeon,
+    rng: &mut Rng,
+    style: CorridorStyle,
+    from: (i32, i32),
+    to: (i32, i32),
+) {
+    let path = match style {
+        CorridorStyle::LShaped | CorridorStyle::Wide => l_path(rng, from, to),
+        CorridorStyle::Jittered | CorridorStyle::Winding => {
+            walk_path(dungeon, rng, style, from, to)
+        }
+    };
+    for (x, y) in path {
+        dig(dungeon, x, y);
+        if style == CorridorStyle::Wide {
+            dig(dungeon, x + 1, y);
+            dig(dungeon, x, y + 1);
+            dig(dungeon, x + 1, y + 1);
+        }
+    }
+}
+
+/// Turns wall inside the outer border into corridor
+fn dig(dungeon: &mut Dungeon, x: i32, y: i32) {
+    let inside = x > 0 && y > 0 && dungeon.in_bounds(x + 1, y + 1);
+    if inside && dungeon.get(x as usize, y as usize) == Some(DungeonTile::Wall) {
+        dungeon.set(x as usize, y as usize, DungeonTile::Corridor);
+    }
+}
+
+/// Tiles of a horizontal or vertical line, both ends included
+fn line(from: (i32, i32), to: (i32, i32)) -> Vec<(i32, i32)> {
+    let (dx, dy) = ((to.0 - from.0).signum(), (to.1 - from.1).signum());
+    let mut tiles = vec![from];
+    let mut at = from;
+    while at != to {
+        at = (at.0 + dx, at.1 + dy);
+        tiles.push(at);
+    }
+    tiles
+}
+
+fn l_path(rng: &mut Rng, from: (i32, i32), to: (i32, i32)) -> Vec<(i32, i32)> {
+    let corner = if rng.next_f32() < 0.5 {
+        (to.0, from.1)
+    } else {
+        (from.0, to.1)
+    };
+    let mut path = line(from, corner);
+    path.extend(line(corner, to));
+    path
+}
+
+/// Towards the target on `remaining`'s axis, or a random way if on target
+fn sidestep(rng: &mut Rng, remaining: i32) -> i32 {
+    if remaining != 0 {
+        remaining.signum()
+    } else if rng.next_u64() & 1 == 0 {
+        1
+    } else {
+        -1
+    }
+}
+
+fn walk_path(
+    dungeon: &Dungeon,
+    rng: &mut Rng,
+    style: CorridorStyle,
+    from: (i32, i32),
+    to: (i32, i32),
+) -> Vec<(i32, i32)> {
+    let max_x = (dungeon.width as i32 - 2).max(1);
+    let max_y = (dungeon.height as i32 - 2).max(1);
+    let distance = (to.0 - from.0).unsigned_abs() + (to.1 - from.1).unsigned_abs();
+    let limit = WALK_STEPS_PER_TILE * (distance as usize + 1);
+
+    let mut at = from;
+    let mut path = vec![from];
+    while at != to && path.len() < limit {
+        let (rx, ry) = (to.0 - at.0, to.1 - at.1);
+        let (dx, dy) = match style {
+            CorridorStyle::Winding if rng.next_f32() >= WINDING_FOCUS => {
+                Direction::ALL[rng.next_usize(Direction::ALL.len())].delta()
+            }
+            CorridorStyle::Winding => {
+                if rx != 0 && (ry == 0 || rng.next_f32() < 0.5) {
+                    (rx.signum(), 0)
+                } else {
+                    (0, ry.signum())
+                }
+            }
+            _ => {
+                let horizontal = rx.abs() >= ry.abs();
+                match (rng.next_f32() < JITTER_CHANCE, horizontal) {
+                    (true, true) => (0, sidestep(rng, ry)),
+                    (true, false) => (sidestep(rng, rx), 0),
+                    (false, true) => (rx.signum(), 0),
+                    (false, false) => (0, ry.signum()),
+                }
+            }
+        };
+        at = ((at.0 + dx).clamp(1, max_x), (at.1 + dy).clamp(1, max_y));
+        path.push(at);
+    }
+    if at != to {
+        let corner = (to.0, at.1);
+        path.extend(line(at, corner));
+        path.extend(line(corner, to));
+    }
+    path
+}
+
+/// Puts doors in one-tile gaps where corridors leave rooms
+pub fn place_doors(dungeon: &mut Dungeon) {
+    for room in dungeon.rooms.clone() {
+        for x in room.x..room.x + room.width {
+            try_door(dungeon, (x, room.y - 1), (0, -1));
+            try_door(dungeon, (x, room.y + room.height), (0, 1));
+        }
+        for y in room.y..room.y + room.height {
+            try_door(dungeon, (room.x - 1, y), (-1, 0));
+            try_door(dungeon, (room.x + room.width, y), (1, 0));
+        }
+    }
+}
+
+/// Makes `(x, y)` a door if a corridor leaves the room through it heading
+/// `(dx, dy)`, with wall on both sides
+fn try_door(dungeon: &mut Dungeon, (x, y): (i32, i32), (dx, dy): (i32, i32)) {
+    let tile = |x: i32, y: i32| {
+        dungeon
+            .in_bounds(x, y)
+            .then(|| dungeon.get(x as usize, y as usize))
+            .flatten()
+    };
+    let open = |x: i32, y: i32| tile(x, y).is_some_and(DungeonTile::is_walkable);
+    let doorway = tile(x, y) == Some(DungeonTile::Corridor)
+        && tile(x - dx, y - dy) == Some(DungeonTile::Floor)
+        && open(x + dx, y + dy)
+        && !open(x + dy, y + dx)
+        && !open(x - dy, y - dx);
+    if doorway {
+        dungeon.set(x as usize, y as usize, DungeonTile::Door);
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::DungeonGenerator;
+
+    fn reachable(dungeon: &Dungeon) -> usize {
+        let open = dungeon.walkable_positions();
+        let mut seen = vec![false; dungeon.tiles.len()];
+        let mut stack = vec![open[0]];
+        let mut count = 0;
+        while let Some((x, y)) = stack.pop() {
+            if !dungeon.get(x, y).is_some_and(DungeonTile::is_walkable) {
+                continue;
+            }
+            if core::mem::replace(&mut seen[y * dungeon.width + x], true) {
+                continue;
+            }
+            count += 1;
+            stack.extend([(x + 1, y), (x, y + 1), (x - 1, y), (x, y - 1)]);
+        }
+        count
+    }
+
+    #[test]
+    fn test_every_style_connects_all_rooms() {
+        let styles = [
+            CorridorStyle::LShaped,
+            CorridorStyle::Jittered,
+            CorridorStyle::Winding,
+            CorridorStyle::Wide,
+        ];
+        for style in styles {
+            for seed in 1..8 {
+                let dungeon = DungeonGenerator::new(48, 32)
+                    .with_corridor_style(style)
+                    .with_doors(true)
+                    .generate(seed)
+                    .unwrap();
+                assert_eq!(
+                    reachable(&dungeon),
+                    dungeon.walkable_positions().len(),
+                    "{style:?} seed {seed}"
+                );
+                // The outer border stays solid
+                for x in 0..dungeon.width {
+                    assert_eq!(dungeon.get(x, 0), Some(DungeonTile::Wall));
+                    assert_eq!(dungeon.get(x, dungeon.height - 1), Some(DungeonTile::Wall));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_styles_differ_and_default_is_unchanged() {
+        let generator = DungeonGenerator::new(48, 32);
+        let plain = generator.generate(3).unwrap();
+        let explicit = generator
+            .clone()
+            .with_corridor_style(CorridorStyle::LShaped)
+            .generate(3)
+            .unwrap();
+        assert_eq!(plain.tiles, explicit.tiles);
+        assert!(!plain.tiles.contains(&DungeonTile::Door));
+
+        let winding = generator
+            .with_corridor_style(CorridorStyle::Winding)
+            .generate(3)
+            .unwrap();
+        assert_eq!(winding.rooms, plain.rooms);
+        assert_ne!(winding.tiles, plain.tiles);
+    }
+
+    #[test]
+    fn test_doors_sit_in_wall_gaps() {
+        let mut doors = 0;
+        for seed in 1..8 {
+            let dungeon = DungeonGenerator::new(48, 32)
+                .with_doors(true)
+                .generate(seed)
+                .unwrap();
+            for y in 1..dungeon.height - 1 {
+                for x in 1..dungeon.width - 1 {
+                    if dungeon.get(x, y) != Some(DungeonTile::Door) {
+                        continue;
+                    }
+                    doors += 1;
+                    let walkable = |x: usize, y: usize| {
+                        dungeon.get(x, y).is_some_and(DungeonTile::is_walkable)
+                    };
+                    let across = walkable(x - 1, y) && walkable(x + 1, y);
+                    let along = walkable(x, y - 1) && walkable(x, y + 1);
+                    assert!(across != along, "door at ({x}, {y}) is not in a gap");
+                }
+            }
+        }
+        assert!(doors > 0);
+    }
+}
@@ -0,0 +1,183 @@
+//! Tile layouts for YAML `world:` sections.
+//!
+//! Turns a compiled `world:` into a [`Dungeon`] grid the runtime can place
+//! characters on. `algorithm: maze` carves a perfect maze (every open tile
+//! reachable, one path between any two), `dungeon` and `rooms` use the
+//! [`DungeonGenerator`], and `arena` builds a mirrored two-player
+//! [`Arena`](crate::Arena). Hand-placed worlds have no layout.
+//!
+//! ```
+//! use jugar_procgen::generate_layout;
+//!
+//! let game = jugar_yaml::compile_game(
+//!     "world:\n  type: procedural\n  algorithm: maze\n  seed: 7\n  size: [11, 9]\n",
+//! )
+//! .unwrap();
+//! let maze = generate_layout(game.world.as_ref().unwrap(), 1).unwrap().unwrap();
+//! assert_eq!((maze.width, maze.height), (11, 9));
+//! ```
+
+use jugar_yaml::CompiledWorld;
+
+use crate::{ArenaGenerator, Dungeon, DungeonGenerator, DungeonTile, ProcgenError, Result, Rng};
+
+/// Size of a procedural world without a `size:`
+pub const DEFAULT_LAYOUT_SIZE: (u32, u32) = (21, 15);
+
+/// Smallest side a layout can be generated for
+const MIN_LAYOUT_SIDE: usize = 5;
+
+/// Generates the tile layout of a compiled world
+///
+/// Worlds without a seed use `fallback_seed`. Returns `None` for worlds
+/// that are not procedural or use a generator without a tile layout
+/// (`noise`, `wfc`).
+///
+/// # Errors
+///
+/// Returns `ProcgenError::InvalidParameters` if the world is too small, or
+/// an arena world has an even width.
+pub fn generate_layout(world: &CompiledWorld, fallback_seed: u64) -> Result<Option<Dungeon>> {
+    if !world.is_procedural() {
+        return Ok(None);
+    }
+    let (width, height) = world.size.unwrap_or(DEFAULT_LAYOUT_SIZE);
+    let (width, height) = (width as usize, height as usize);
+    if width.min(height) < MIN_LAYOUT_SIDE {
+        return Err(ProcgenError::InvalidParameters(format!(
+            "World size {width}x{height} is smaller than {MIN_LAYOUT_SIDE}x{MIN_LAYOUT_SIDE}"
+        )));
+    }
+    let seed = world.seed.unwrap_or(fallback_seed);
+
+    match world.algorithm.as_deref().unwrap_or("dungeon") {
+        "maze" => Ok(Some(generate_maze(width, height, seed))),
+        "dungeon" | "rooms" => {
+            // Rooms plus padding must fit on the shorter side
+            let max_room = (width.min(height) / 3).clamp(2, 10) as i32;
+            DungeonGenerator::new(width, height)
+                .with_room_size(2, max_room)
+                .with_room_count((width * height / 80).clamp(2, 12))
+                .generate(seed)
+                .map(Some)
+        }
+        "arena" => ArenaGenerator::new(width, height)
+            .generate(seed)
+            .map(|arena| Some(arena.layout)),
+        _ => Ok(None),
+    }
+}
+
+/// Carves a maze with a randomized depth-first search
+///
+/// Walls sit on even rows and columns, so an even side loses its last
+/// column or row. Sides under 3 are raised to 3.
+#[must_use]
+pub fn generate_maze(width: usize, height: usize, seed: u64) -> Dungeon {
+    let width = width.max(3) - (1 - width.max(3) % 2);
+    let height = height.max(3) - (1 - height.max(3) % 2);
+    let mut rng = Rng::new(seed);
+    let mut maze = Dungeon::new(width, height);
+
+    maze.set(1, 1, DungeonTile::Corridor);
+    let mut stack = vec![(1_usize, 1_usize)];
+    while let Some(&(x, y)) = stack.last() {
+        let unvisited: Vec<(usize, usize)> = [(0, -2), (2, 0), (0, 2), (-2, 0)]
+            .into_iter()
+            .filter_map(|(dx, dy)| {
+                let nx = x.checked_add_signed(dx)?;
+                let ny = y.checked_add_signed(dy)?;
+                (nx < width - 1 && ny < height - 1 && maze.get(nx, ny) == Some(DungeonTile::Wall))
+                    .then_some((nx, ny))
+            })
+            .collect();
+        if unvisited.is_empty() {
+            let _ = stack.pop();
+            continue;
+        }
+        let (nx, ny) = unvisited[rng.next_usize(unvisited.len())];
+        maze.set((x + nx) / 2, (y + ny) / 2, DungeonTile::Corridor);
+        maze.set(nx, ny, DungeonTile::Corridor);
+        stack.push((nx, ny));
+    }
+    maze
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use jugar_yaml::TemplateCatalog;
+
+    /// Open tiles reachable from the first open tile
+    fn reachable(dungeon: &Dungeon) -> usize {
+        let open = dungeon.walkable_positions();
+        let mut seen = vec![false; dungeon.tiles.len()];
+        let mut stack = vec![open[0]];
+        let mut count = 0;
+        while let Some((x, y)) = stack.pop() {
+            if !dungeon.get(x, y).is_some_and(DungeonTile::is_walkable) {
+                continue;
+            }
+            let index = y * dungeon.width + x;
+            if seen[index] {
+                continue;
+            }
+            seen[index] = true;
+            count += 1;
+            stack.extend([(x + 1, y), (x, y + 1)]);
+            stack.extend(x.checked_sub(1).map(|x| (x, y)));
+            stack.extend(y.checked_sub(1).map(|y| (x, y)));
+        }
+        count
+    }
+
+    fn world(yaml: &str) -> CompiledWorld {
+        jugar_yaml::compile_game(yaml).unwrap().world.unwrap()
+    }
+
+    #[test]
+    fn test_maze_template_layout_is_connected() {
+        let catalog = TemplateCatalog::with_defaults();
+        let template = catalog.get("maze-explorer").unwrap();
+        let world = jugar_yaml::compile_game(&template.yaml)
+            .unwrap()
+            .world
+            .unwrap();
+
+        for seed in 1..20 {
+            let maze = generate_layout(&world, seed).unwrap().unwrap();
+            assert_eq!((maze.width, maze.height), (21, 15));
+            // 10 x 7 cells joined by 69 passages
+            assert_eq!(maze.walkable_positions().len(), 10 * 7 + 69);
+            assert_eq!(reachable(&maze), maze.walkable_positions().len());
+        }
+        let a = generate_layout(&world, 3).unwrap().unwrap();
+        let b = generate_layout(&world, 3).unwrap().unwrap();
+        assert_eq!(a.tiles, b.tiles);
+    }
+
+    #[test]
+    fn test_dungeon_and_unsupported_worlds() {
+        let rooms = world(
+            "world:\n  type: procedural\n  algorithm: dungeon\n  seed: 9\n  size: [30, 20]\n",
+        );
+        let dungeon = generate_layout(&rooms, 1).unwrap().unwrap();
+        assert!(dungeon.rooms.len() >= 2);
+        assert_eq!(reachable(&dungeon), dungeon.walkable_positions().len());
+
+        let side = world("world:\n  type: sidescroll\n  gravity: 20\n");
+        assert!(generate_layout(&side, 1).unwrap().is_none());
+        let arena = world("world:\n  type: procedural\n  algorithm: arena\n  seed: 2\n");
+        let arena = generate_layout(&arena, 1).unwrap().unwrap();
+        assert_eq!((arena.width, arena.height), (21, 15));
+        let noise = world("world:\n  type: procedural\n  algorithm: noise\n");
+        assert!(generate_layout(&noise, 1).unwrap().is_none());
+
+        let tiny = CompiledWorld {
+            size: Some((4, 30)),
+            ..rooms
+        };
+        assert!(generate_layout(&tiny, 1).is_err());
+    }
+}
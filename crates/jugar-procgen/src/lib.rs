@@ -1,16 +1,52 @@
 //! # jugar-procgen
 //!
-//! Procedural generation for Jugar including noise, dungeon generation, and WFC.
+//! Procedural generation for Jugar including noise, dungeon generation, WFC,
+//! endless chunked worlds, fair two-player arenas, layouts for YAML
+//! `world:` sections (with the `yaml` feature) and independent random
+//! streams.
 
 #![forbid(unsafe_code)]
 #![warn(missing_docs)]
 
+extern crate alloc;
+
+mod arena;
+mod bake;
+mod chunks;
+mod corridors;
+#[cfg(feature = "yaml")]
+mod layout;
+mod loot;
+mod mission;
+mod names;
+mod rng;
+mod streaming;
+mod terrain;
+mod topology;
+
 use core::fmt;
 use std::collections::HashSet;
 
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+pub use arena::{Arena, ArenaGenerator, Symmetry};
+pub use bake::BakeOptions;
+pub use chunks::{
+    ChunkChanges, ChunkCoord, ChunkGenerator, ChunkedWorld, LoadedChunk, DEFAULT_HYSTERESIS,
+    DEFAULT_LOAD_RADIUS,
+};
+pub use corridors::CorridorStyle;
+#[cfg(feature = "yaml")]
+pub use layout::{generate_layout, generate_maze, DEFAULT_LAYOUT_SIZE};
+pub use loot::{LootDrop, LootEntry, LootItem, LootTable, PityRule};
+pub use mission::{MissionDungeon, MissionGenerator, MissionGraph, MissionRoom, RoomRole};
+pub use names::{NameGenerator, NameTheme};
+pub use rng::{splitmix64, RngStreams};
+pub use streaming::{WfcRegion, WfcStream, DEFAULT_REGION_SIZE};
+pub use terrain::{Biome, Terrain, TerrainGenerator};
+pub use topology::{HexDirection, HexGrid, SquareGrid, Topology};
+
 /// Procedural generation errors
 #[derive(Error, Debug, Clone, PartialEq, Eq)]
 pub enum ProcgenError {
@@ -83,6 +119,13 @@ impl Rng {
         min + (self.next_usize((max - min) as usize) as i32)
     }
 
+    /// Advances the generator as if `draws` numbers had been drawn
+    ///
+    /// A jump of a billion costs about as much as a jump of a thousand.
+    pub fn jump(&mut self, draws: u64) {
+        self.state = rng::jump_state(self.state, draws);
+    }
+
     /// Shuffles a slice in place
     pub fn shuffle<T>(&mut self, slice: &mut [T]) {
         for i in (1..slice.len()).rev() {
@@ -361,6 +404,10 @@ pub struct DungeonGenerator {
     pub room_count: usize,
     /// Room padding (space between rooms)
     pub padding: i32,
+    /// How corridors are carved
+    pub corridor_style: CorridorStyle,
+    /// Place doors where corridors leave rooms
+    pub doors: bool,
 }
 
 impl DungeonGenerator {
@@ -374,6 +421,8 @@ impl DungeonGenerator {
             max_room_size: 10,
             room_count: 10,
             padding: 1,
+            corridor_style: CorridorStyle::LShaped,
+            doors: false,
         }
     }
 
@@ -392,6 +441,23 @@ impl DungeonGenerator {
         self
     }
 
+    /// Sets how corridors are carved
+    #[must_use]
+    pub const fn with_corridor_style(mut self, style: CorridorStyle) -> Self {
+        self.corridor_style = style;
+        self
+    }
+
+    /// Sets whether doors are placed where corridors leave rooms
+    ///
+    /// Doors only go in one-tile gaps, so wide corridors open straight
+    /// into rooms.
+    #[must_use]
+    pub const fn with_doors(mut self, doors: bool) -> Self {
+        self.doors = doors;
+        self
+    }
+
     /// Generates a dungeon with the given seed
     ///
     /// # Errors
@@ -444,48 +510,15 @@ impl DungeonGenerator {
 
         // Connect rooms with corridors
         for i in 1..dungeon.rooms.len() {
-            let (x1, y1) = dungeon.rooms[i - 1].center();
-            let (x2, y2) = dungeon.rooms[i].center();
-
-            // Randomly choose horizontal-first or vertical-first
-            if rng.next_f32() < 0.5 {
-                Self::carve_h_corridor(&mut dungeon, x1, x2, y1);
-                Self::carve_v_corridor(&mut dungeon, y1, y2, x2);
-            } else {
-                Self::carve_v_corridor(&mut dungeon, y1, y2, x1);
-                Self::carve_h_corridor(&mut dungeon, x1, x2, y2);
-            }
+            let from = dungeon.rooms[i - 1].center();
+            let to = dungeon.rooms[i].center();
+            corridors::carve(&mut dungeon, &mut rng, self.corridor_style, from, to);
         }
-
-        Ok(dungeon)
-    }
-
-    fn carve_h_corridor(dungeon: &mut Dungeon, x1: i32, x2: i32, y: i32) {
-        let (start, end) = if x1 < x2 { (x1, x2) } else { (x2, x1) };
-        for x in start..=end {
-            if dungeon.in_bounds(x, y) {
-                let tile = dungeon
-                    .get(x as usize, y as usize)
-                    .unwrap_or(DungeonTile::Wall);
-                if tile == DungeonTile::Wall {
-                    dungeon.set(x as usize, y as usize, DungeonTile::Corridor);
-                }
-            }
+        if self.doors {
+            corridors::place_doors(&mut dungeon);
         }
-    }
 
-    fn carve_v_corridor(dungeon: &mut Dungeon, y1: i32, y2: i32, x: i32) {
-        let (start, end) = if y1 < y2 { (y1, y2) } else { (y2, y1) };
-        for y in start..=end {
-            if dungeon.in_bounds(x, y) {
-                let tile = dungeon
-                    .get(x as usize, y as usize)
-                    .unwrap_or(DungeonTile::Wall);
-                if tile == DungeonTile::Wall {
-                    dungeon.set(x as usize, y as usize, DungeonTile::Corridor);
-                }
-            }
-        }
+        Ok(dungeon)
     }
 }
 
@@ -542,11 +575,20 @@ impl Direction {
     pub const ALL: [Self; 4] = [Self::Up, Self::Down, Self::Left, Self::Right];
 }
 
+impl From<Direction> for usize {
+    fn from(direction: Direction) -> Self {
+        direction as Self
+    }
+}
+
 /// Adjacency rules for WFC
+///
+/// Directions are a [`Direction`], a [`HexDirection`] or any direction
+/// number of the grid's [`Topology`].
 #[derive(Debug, Clone, Default)]
 pub struct AdjacencyRules {
     /// Allowed neighbors per tile per direction
-    rules: Vec<[HashSet<TileId>; 4]>,
+    rules: Vec<Vec<HashSet<TileId>>>,
 }
 
 impl AdjacencyRules {
@@ -554,23 +596,25 @@ impl AdjacencyRules {
     #[must_use]
     pub fn new(tile_count: usize) -> Self {
         Self {
-            rules: vec![Default::default(); tile_count],
+            rules: vec![vec![HashSet::new(); Direction::ALL.len()]; tile_count],
         }
     }
 
     /// Adds an adjacency rule
-    pub fn add(&mut self, tile: TileId, direction: Direction, neighbor: TileId) {
-        if (tile as usize) < self.rules.len() {
-            let _ = self.rules[tile as usize][direction as usize].insert(neighbor);
+    pub fn add(&mut self, tile: TileId, direction: impl Into<usize>, neighbor: TileId) {
+        let direction = direction.into();
+        if let Some(directions) = self.rules.get_mut(tile as usize) {
+            if directions.len() <= direction {
+                directions.resize(direction + 1, HashSet::new());
+            }
+            let _ = directions[direction].insert(neighbor);
         }
     }
 
     /// Gets allowed neighbors for a tile in a direction
     #[must_use]
-    pub fn allowed(&self, tile: TileId, direction: Direction) -> Option<&HashSet<TileId>> {
-        self.rules
-            .get(tile as usize)
-            .map(|r| &r[direction as usize])
+    pub fn allowed(&self, tile: TileId, direction: impl Into<usize>) -> Option<&HashSet<TileId>> {
+        self.rules.get(tile as usize)?.get(direction.into())
     }
 }
 
@@ -606,6 +650,34 @@ impl WfcCell {
     }
 }
 
+/// How far a [`Wfc`] run has got
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WfcProgress {
+    /// Cells collapsed so far
+    pub collapsed: usize,
+    /// Cells in the grid
+    pub total: usize,
+}
+
+impl WfcProgress {
+    /// Returns true once every cell is collapsed
+    #[must_use]
+    pub const fn is_done(&self) -> bool {
+        self.collapsed >= self.total
+    }
+
+    /// Completed fraction (0.0 to 1.0), for loading bars
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn fraction(&self) -> f32 {
+        if self.total == 0 {
+            1.0
+        } else {
+            self.collapsed as f32 / self.total as f32
+        }
+    }
+}
+
 /// Wave Function Collapse generator
 pub struct Wfc {
     width: usize,
@@ -614,6 +686,8 @@ pub struct Wfc {
     rules: AdjacencyRules,
     all_tiles: Vec<TileId>,
     rng: Rng,
+    collapsed: usize,
+    topology: Box<dyn Topology>,
 }
 
 impl Wfc {
@@ -630,9 +704,30 @@ impl Wfc {
             rules: AdjacencyRules::new(tile_count),
             all_tiles,
             rng: Rng::new(seed),
+            collapsed: 0,
+            topology: Box::new(SquareGrid::new()),
         }
     }
 
+    /// Sets which cells neighbor which (a [`SquareGrid`] by default)
+    #[must_use]
+    pub fn with_topology(mut self, topology: impl Topology + 'static) -> Self {
+        self.topology = Box::new(topology);
+        self
+    }
+
+    /// Grid width in cells
+    #[must_use]
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Grid height in cells
+    #[must_use]
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
     /// Gets the adjacency rules for modification
     #[allow(clippy::missing_const_for_fn)]
     pub fn rules_mut(&mut self) -> &mut AdjacencyRules {
@@ -655,13 +750,29 @@ impl Wfc {
     ///
     /// Returns `ProcgenError::GenerationFailed` if the WFC algorithm encounters a contradiction.
     pub fn collapse(&mut self) -> Result<()> {
-        loop {
-            // Find cell with lowest entropy (not collapsed)
-            let min_cell = self.find_min_entropy_cell();
+        let _ = self.step(usize::MAX)?;
+        Ok(())
+    }
 
-            let Some((x, y)) = min_cell else {
+    /// Collapses at most `budget_cells` cells, then returns
+    ///
+    /// Large maps can be spread across frames this way: call it once per
+    /// frame with a budget that fits the frame, until the returned progress
+    /// is done. The result matches a single [`collapse`](Self::collapse)
+    /// with the same seed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ProcgenError::WfcContradiction` if no tile fits a cell, or
+    /// `ProcgenError::InvalidParameters` if the grid size does not suit the
+    /// topology.
+    pub fn step(&mut self, budget_cells: usize) -> Result<WfcProgress> {
+        self.topology.validate(self.width, self.height)?;
+        for _ in 0..budget_cells {
+            // Find cell with lowest entropy (not collapsed)
+            let Some((x, y)) = self.find_min_entropy_cell() else {
                 // All cells collapsed
-                return Ok(());
+                break;
             };
 
             // Collapse this cell
@@ -670,6 +781,16 @@ impl Wfc {
             // Propagate constraints
             self.propagate(x, y)?;
         }
+        Ok(self.progress())
+    }
+
+    /// Current progress
+    #[must_use]
+    pub fn progress(&self) -> WfcProgress {
+        WfcProgress {
+            collapsed: self.collapsed,
+            total: self.cells.len(),
+        }
     }
 
     /// Returns the collapsed grid
@@ -715,12 +836,14 @@ impl Wfc {
             return Err(ProcgenError::WfcContradiction { x, y });
         }
 
-        // Pick a random possibility
-        let possibilities: Vec<_> = cell.possibilities.iter().copied().collect();
+        // Pick a random possibility (sorted, as set order varies per run)
+        let mut possibilities: Vec<_> = cell.possibilities.iter().copied().collect();
+        possibilities.sort_unstable();
         let idx = self.rng.next_usize(possibilities.len());
         let chosen = possibilities[idx];
 
         cell.collapsed = Some(chosen);
+        self.collapsed += 1;
         cell.possibilities.clear();
         let _ = cell.possibilities.insert(chosen);
 
@@ -734,17 +857,11 @@ impl Wfc {
             let cell = &self.cells[y * self.width + x];
             let current_possibilities = cell.possibilities.clone();
 
-            for dir in Direction::ALL {
-                let (dx, dy) = dir.delta();
-                let nx = x as i32 + dx;
-                let ny = y as i32 + dy;
-
-                if nx < 0 || ny < 0 || nx >= self.width as i32 || ny >= self.height as i32 {
+            for dir in 0..self.topology.directions() {
+                let Some((nx, ny)) = self.topology.neighbor(x, y, dir, self.width, self.height)
+                else {
                     continue;
-                }
-
-                let nx = nx as usize;
-                let ny = ny as usize;
+                };
                 let neighbor = &mut self.cells[ny * self.width + nx];
 
                 if neighbor.is_collapsed() {
@@ -783,6 +900,7 @@ impl fmt::Debug for Wfc {
             .field("width", &self.width)
             .field("height", &self.height)
             .field("tiles", &self.all_tiles.len())
+            .field("topology", &self.topology)
             .finish_non_exhaustive()
     }
 }
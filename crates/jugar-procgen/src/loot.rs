@@ -0,0 +1,390 @@
+//! Weighted loot tables with nested tables, guaranteed drops and pity counters.
+
+use serde::{Deserialize, Serialize};
+
+use crate::Rng;
+
+/// A single dropped item stack
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LootItem {
+    /// Item identifier
+    pub id: String,
+    /// Stack size
+    pub count: u32,
+}
+
+impl LootItem {
+    /// Creates a new item stack
+    #[must_use]
+    pub fn new(id: impl Into<String>, count: u32) -> Self {
+        Self {
+            id: id.into(),
+            count,
+        }
+    }
+}
+
+/// What a loot entry produces when selected
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum LootDrop {
+    /// Nothing drops
+    Nothing,
+    /// An item with a count range (inclusive)
+    Item {
+        /// Item identifier
+        id: String,
+        /// Minimum count
+        min: u32,
+        /// Maximum count
+        max: u32,
+    },
+    /// Roll a nested table
+    Table(Box<LootTable>),
+}
+
+/// A weighted entry in a loot table
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LootEntry {
+    /// What this entry drops
+    pub drop: LootDrop,
+    /// Relative weight
+    pub weight: u32,
+}
+
+/// Pity rule: guarantees an item after a number of rolls without it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PityRule {
+    /// Item that is guaranteed
+    pub item: String,
+    /// Number of consecutive misses before the item is forced
+    pub threshold: u32,
+    misses: u32,
+}
+
+impl PityRule {
+    /// Rolls since the item last dropped
+    #[must_use]
+    pub const fn misses(&self) -> u32 {
+        self.misses
+    }
+}
+
+/// Weighted loot table.
+///
+/// All randomness comes from the crate [`Rng`], so the same seed always
+/// yields the same drops.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LootTable {
+    entries: Vec<LootEntry>,
+    guaranteed: Vec<LootItem>,
+    rolls: u32,
+    pity: Vec<PityRule>,
+}
+
+impl LootTable {
+    /// Creates an empty loot table that rolls once
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            guaranteed: Vec::new(),
+            rolls: 1,
+            pity: Vec::new(),
+        }
+    }
+
+    /// Adds an item entry with a fixed count of one
+    #[must_use]
+    pub fn with_item(self, id: impl Into<String>, weight: u32) -> Self {
+        self.with_item_range(id, 1, 1, weight)
+    }
+
+    /// Adds an item entry with an inclusive count range
+    #[must_use]
+    pub fn with_item_range(
+        mut self,
+        id: impl Into<String>,
+        min: u32,
+        max: u32,
+        weight: u32,
+    ) -> Self {
+        self.entries.push(LootEntry {
+            drop: LootDrop::Item {
+                id: id.into(),
+                min: min.min(max),
+                max: max.max(min),
+            },
+            weight,
+        });
+        self
+    }
+
+    /// Adds a weighted chance of dropping nothing
+    #[must_use]
+    pub fn with_nothing(mut self, weight: u32) -> Self {
+        self.entries.push(LootEntry {
+            drop: LootDrop::Nothing,
+            weight,
+        });
+        self
+    }
+
+    /// Adds a nested table that is rolled when selected
+    #[must_use]
+    pub fn with_table(mut self, table: Self, weight: u32) -> Self {
+        self.entries.push(LootEntry {
+            drop: LootDrop::Table(Box::new(table)),
+            weight,
+        });
+        self
+    }
+
+    /// Adds an item that always drops
+    #[must_use]
+    pub fn with_guaranteed(mut self, id: impl Into<String>, count: u32) -> Self {
+        self.guaranteed.push(LootItem::new(id, count));
+        self
+    }
+
+    /// Sets how many weighted rolls happen per drop
+    #[must_use]
+    pub const fn with_rolls(mut self, rolls: u32) -> Self {
+        self.rolls = rolls;
+        self
+    }
+
+    /// Forces `item` to drop after `threshold` drops without it
+    #[must_use]
+    pub fn with_pity(mut self, item: impl Into<String>, threshold: u32) -> Self {
+        self.pity.push(PityRule {
+            item: item.into(),
+            threshold,
+            misses: 0,
+        });
+        self
+    }
+
+    /// Returns the weighted entries
+    #[must_use]
+    pub fn entries(&self) -> &[LootEntry] {
+        &self.entries
+    }
+
+    /// Returns the pity rules and their counters
+    #[must_use]
+    pub fn pity_rules(&self) -> &[PityRule] {
+        &self.pity
+    }
+
+    /// Sum of all entry weights
+    #[must_use]
+    pub fn total_weight(&self) -> u64 {
+        self.entries.iter().map(|e| u64::from(e.weight)).sum()
+    }
+
+    /// Rolls the table and returns the dropped items.
+    ///
+    /// Guaranteed items are always included. Pity counters are updated and,
+    /// once a threshold is reached, the pity item is added to the drop.
+    pub fn roll(&mut self, rng: &mut Rng) -> Vec<LootItem> {
+        let mut drops = self.guaranteed.clone();
+        for _ in 0..self.rolls {
+            self.roll_once(rng, &mut drops);
+        }
+
+        for rule in &mut self.pity {
+            if drops.iter().any(|d| d.id == rule.item) {
+                rule.misses = 0;
+                continue;
+            }
+            rule.misses += 1;
+            if rule.threshold > 0 && rule.misses >= rule.threshold {
+                drops.push(LootItem::new(rule.item.clone(), 1));
+                rule.misses = 0;
+            }
+        }
+
+        merge_stacks(drops)
+    }
+
+    /// Resets all pity counters
+    pub fn reset_pity(&mut self) {
+        for rule in &mut self.pity {
+            rule.misses = 0;
+        }
+    }
+
+    fn roll_once(&mut self, rng: &mut Rng, drops: &mut Vec<LootItem>) {
+        let total = self.total_weight();
+        if total == 0 {
+            return;
+        }
+        let mut pick = rng.next_u64() % total;
+        for entry in &mut self.entries {
+            let weight = u64::from(entry.weight);
+            if pick >= weight {
+                pick -= weight;
+                continue;
+            }
+            match &mut entry.drop {
+                LootDrop::Nothing => {}
+                LootDrop::Item { id, min, max } => {
+                    let span = u64::from(*max - *min) + 1;
+                    let count = *min + (rng.next_u64() % span) as u32;
+                    drops.push(LootItem::new(id.clone(), count));
+                }
+                LootDrop::Table(table) => drops.extend(table.roll(rng)),
+            }
+            return;
+        }
+    }
+}
+
+impl Default for LootTable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Merges stacks of the same item; a full stack spills into a new one
+fn merge_stacks(drops: Vec<LootItem>) -> Vec<LootItem> {
+    let mut merged: Vec<LootItem> = Vec::with_capacity(drops.len());
+    for mut item in drops {
+        if let Some(existing) = merged
+            .iter_mut()
+            .find(|m| m.id == item.id && m.count < u32::MAX)
+        {
+            let moved = item.count.min(u32::MAX - existing.count);
+            existing.count += moved;
+            item.count -= moved;
+        }
+        if item.count > 0 {
+            merged.push(item);
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn chest() -> LootTable {
+        LootTable::new()
+            .with_item_range("coin", 1, 5, 70)
+            .with_item("potion", 25)
+            .with_item("sword", 5)
+    }
+
+    #[test]
+    fn test_loot_deterministic() {
+        let mut a = chest();
+        let mut b = chest();
+        let mut rng_a = Rng::new(7);
+        let mut rng_b = Rng::new(7);
+        for _ in 0..50 {
+            assert_eq!(a.roll(&mut rng_a), b.roll(&mut rng_b));
+        }
+    }
+
+    #[test]
+    fn test_loot_empty_table() {
+        let mut table = LootTable::new();
+        let mut rng = Rng::new(1);
+        assert!(table.roll(&mut rng).is_empty());
+    }
+
+    #[test]
+    fn test_loot_guaranteed_always_drops() {
+        let mut table = LootTable::new().with_nothing(1).with_guaranteed("key", 1);
+        let mut rng = Rng::new(3);
+        for _ in 0..20 {
+            assert_eq!(table.roll(&mut rng), vec![LootItem::new("key", 1)]);
+        }
+    }
+
+    #[test]
+    fn test_loot_count_range() {
+        let mut table = LootTable::new().with_item_range("coin", 2, 4, 1);
+        let mut rng = Rng::new(11);
+        for _ in 0..100 {
+            let drops = table.roll(&mut rng);
+            assert_eq!(drops.len(), 1);
+            assert!((2..=4).contains(&drops[0].count));
+        }
+    }
+
+    #[test]
+    fn test_loot_weights_distribution() {
+        let mut table = chest();
+        let mut rng = Rng::new(99);
+        let mut coins = 0;
+        for _ in 0..1000 {
+            if table.roll(&mut rng).iter().any(|i| i.id == "coin") {
+                coins += 1;
+            }
+        }
+        assert!((600..800).contains(&coins), "coins = {coins}");
+    }
+
+    #[test]
+    fn test_loot_nested_table() {
+        let gems = LootTable::new()
+            .with_item("ruby", 1)
+            .with_item("emerald", 1);
+        let mut table = LootTable::new().with_table(gems, 1);
+        let mut rng = Rng::new(5);
+        for _ in 0..20 {
+            let drops = table.roll(&mut rng);
+            assert_eq!(drops.len(), 1);
+            assert!(drops[0].id == "ruby" || drops[0].id == "emerald");
+        }
+    }
+
+    #[test]
+    fn test_loot_multiple_rolls_merge() {
+        let mut table = LootTable::new().with_item("coin", 1).with_rolls(3);
+        let mut rng = Rng::new(5);
+        assert_eq!(table.roll(&mut rng), vec![LootItem::new("coin", 3)]);
+    }
+
+    #[test]
+    fn test_loot_full_stacks_spill_over() {
+        let drops = vec![
+            LootItem::new("coin", u32::MAX - 1),
+            LootItem::new("gem", 0),
+            LootItem::new("coin", 5),
+            LootItem::new("coin", 2),
+        ];
+        assert_eq!(
+            merge_stacks(drops),
+            vec![LootItem::new("coin", u32::MAX), LootItem::new("coin", 6)]
+        );
+    }
+
+    #[test]
+    fn test_loot_pity_counter() {
+        let mut table = LootTable::new().with_nothing(1).with_pity("sword", 3);
+        let mut rng = Rng::new(8);
+        assert!(table.roll(&mut rng).is_empty());
+        assert!(table.roll(&mut rng).is_empty());
+        assert_eq!(table.pity_rules()[0].misses(), 2);
+        assert_eq!(table.roll(&mut rng), vec![LootItem::new("sword", 1)]);
+        assert_eq!(table.pity_rules()[0].misses(), 0);
+    }
+
+    #[test]
+    fn test_loot_reset_pity() {
+        let mut table = LootTable::new().with_nothing(1).with_pity("sword", 10);
+        let mut rng = Rng::new(8);
+        let _ = table.roll(&mut rng);
+        table.reset_pity();
+        assert_eq!(table.pity_rules()[0].misses(), 0);
+    }
+
+    #[test]
+    fn test_loot_total_weight() {
+        assert_eq!(chest().total_weight(), 100);
+    }
+}
@@ -0,0 +1,331 @@
+//! Lock-and-key mission graphs mapped onto generated dungeons.
+//!
+//! A [`MissionGenerator`] first builds a spatial [`Dungeon`] and then assigns
+//! roles along its room chain (start → key → locked door → … → boss) so the
+//! resulting progression is always solvable.
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Dungeon, DungeonGenerator, DungeonTile, ProcgenError, Result, Rng, Room};
+
+/// Role a room plays in the mission
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum RoomRole {
+    /// Player spawn
+    Start,
+    /// Ordinary room
+    #[default]
+    Normal,
+    /// Holds a key
+    Key,
+    /// Entered through a locked door
+    Locked,
+    /// Final locked room with the boss
+    Boss,
+}
+
+/// Per-room mission metadata
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MissionRoom {
+    /// Spatial room in the dungeon
+    pub room: Room,
+    /// Role in the mission
+    pub role: RoomRole,
+    /// Difficulty from 1 (easy) upward
+    pub difficulty: u32,
+    /// Item needed to enter the room
+    pub requires: Option<String>,
+    /// Item found in the room
+    pub grants: Option<String>,
+}
+
+/// Graph of mission rooms and their connections
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MissionGraph {
+    /// Rooms (index 0 is the start)
+    pub rooms: Vec<MissionRoom>,
+    /// Undirected connections between room indices
+    pub edges: Vec<(usize, usize)>,
+}
+
+impl MissionGraph {
+    /// Returns the indices of rooms connected to `index`
+    #[must_use]
+    pub fn neighbors(&self, index: usize) -> Vec<usize> {
+        self.edges
+            .iter()
+            .filter_map(|&(a, b)| {
+                if a == index {
+                    Some(b)
+                } else if b == index {
+                    Some(a)
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the index of the first room with the given role
+    #[must_use]
+    pub fn find_role(&self, role: RoomRole) -> Option<usize> {
+        self.rooms.iter().position(|r| r.role == role)
+    }
+
+    /// Checks that the boss can be reached from the start by collecting items
+    #[must_use]
+    pub fn is_solvable(&self) -> bool {
+        let (Some(start), Some(boss)) = (
+            self.find_role(RoomRole::Start),
+            self.find_role(RoomRole::Boss),
+        ) else {
+            return false;
+        };
+
+        let mut visited = HashSet::new();
+        let mut items: HashSet<&str> = HashSet::new();
+        let _ = visited.insert(start);
+        if let Some(item) = &self.rooms[start].grants {
+            let _ = items.insert(item.as_str());
+        }
+
+        loop {
+            let mut progressed = false;
+            for index in visited.clone() {
+                for next in self.neighbors(index) {
+                    if visited.contains(&next) {
+                        continue;
+                    }
+                    let room = &self.rooms[next];
+                    let open = room
+                        .requires
+                        .as_deref()
+                        .map_or(true, |item| items.contains(item));
+                    if open {
+                        let _ = visited.insert(next);
+                        if let Some(item) = &room.grants {
+                            let _ = items.insert(item.as_str());
+                        }
+                        progressed = true;
+                    }
+                }
+            }
+            if !progressed {
+                return visited.contains(&boss);
+            }
+        }
+    }
+}
+
+/// A dungeon together with its mission graph
+#[derive(Debug, Clone)]
+pub struct MissionDungeon {
+    /// Spatial layout (locked rooms have `Door` tiles at their entrances)
+    pub dungeon: Dungeon,
+    /// Mission metadata
+    pub graph: MissionGraph,
+}
+
+/// Generates solvable lock-and-key dungeons
+#[derive(Debug, Clone)]
+pub struct MissionGenerator {
+    /// Spatial dungeon generator
+    pub layout: DungeonGenerator,
+    /// Number of key/lock pairs
+    pub key_count: usize,
+}
+
+impl MissionGenerator {
+    /// Creates a mission generator with one key
+    #[must_use]
+    pub const fn new(layout: DungeonGenerator) -> Self {
+        Self {
+            layout,
+            key_count: 1,
+        }
+    }
+
+    /// Sets the number of key/lock pairs (the last lock guards the boss)
+    #[must_use]
+    pub const fn with_key_count(mut self, count: usize) -> Self {
+        self.key_count = count;
+        self
+    }
+
+    /// Generates a dungeon and assigns mission roles to its rooms
+    ///
+    /// # Errors
+    ///
+    /// Returns `ProcgenError::InvalidParameters` if `key_count` is zero and
+    /// `ProcgenError::GenerationFailed` if too few rooms were placed.
+    pub fn generate(&self, seed: u64) -> Result<MissionDungeon> {
+        if self.key_count == 0 {
+            return Err(ProcgenError::InvalidParameters(
+                "Mission needs at least one key".to_string(),
+            ));
+        }
+        let mut dungeon = self.layout.generate(seed)?;
+        let needed = self.key_count * 2 + 1;
+        let count = dungeon.rooms.len();
+        if count < needed {
+            return Err(ProcgenError::GenerationFailed(format!(
+                "Mission with {} keys needs {needed} rooms, only {count} placed",
+                self.key_count
+            )));
+        }
+
+        let mut rng = Rng::new(seed ^ 0x9E37_79B9_7F4A_7C15);
+        let mut rooms: Vec<MissionRoom> = dungeon
+            .rooms
+            .iter()
+            .enumerate()
+            .map(|(i, room)| MissionRoom {
+                room: room.clone(),
+                role: RoomRole::Normal,
+                difficulty: 1 + (i * 9 / count.saturating_sub(1).max(1)) as u32,
+                requires: None,
+                grants: None,
+            })
+            .collect();
+        rooms[0].role = RoomRole::Start;
+
+        // Spread locks evenly along the chain, ending at the boss room.
+        let mut previous_lock = 0;
+        for key in 0..self.key_count {
+            let lock = if key + 1 == self.key_count {
+                count - 1
+            } else {
+                (count - 1) * (key + 1) / self.key_count
+            };
+            let lock = lock.max(previous_lock + 2);
+            let key_room = previous_lock + 1 + rng.next_usize(lock - previous_lock - 1);
+            let item = format!("key_{}", key + 1);
+
+            rooms[key_room].role = RoomRole::Key;
+            rooms[key_room].grants = Some(item.clone());
+            rooms[lock].role = if lock == count - 1 {
+                RoomRole::Boss
+            } else {
+                RoomRole::Locked
+            };
+            rooms[lock].requires = Some(item);
+            rooms[lock].difficulty += 1;
+            previous_lock = lock;
+        }
+
+        for room in rooms.iter().filter(|r| r.requires.is_some()) {
+            place_doors(&mut dungeon, &room.room);
+        }
+
+        let edges = (1..count).map(|i| (i - 1, i)).collect();
+        Ok(MissionDungeon {
+            dungeon,
+            graph: MissionGraph { rooms, edges },
+        })
+    }
+}
+
+impl Default for MissionGenerator {
+    fn default() -> Self {
+        Self::new(DungeonGenerator::default())
+    }
+}
+
+/// Turns corridor tiles touching a room's outline into doors
+fn place_doors(dungeon: &mut Dungeon, room: &Room) {
+    for y in (room.y - 1)..=(room.y + room.height) {
+        for x in (room.x - 1)..=(room.x + room.width) {
+            let on_edge = x == room.x - 1
+                || x == room.x + room.width
+                || y == room.y - 1
+                || y == room.y + room.height;
+            if on_edge
+                && dungeon.in_bounds(x, y)
+                && dungeon.get(x as usize, y as usize) == Some(DungeonTile::Corridor)
+            {
+                dungeon.set(x as usize, y as usize, DungeonTile::Door);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn generator() -> MissionGenerator {
+        MissionGenerator::new(DungeonGenerator::new(80, 80).with_room_count(8))
+    }
+
+    #[test]
+    fn test_mission_roles_assigned() {
+        let mission = generator().generate(42).unwrap();
+        let graph = &mission.graph;
+        assert_eq!(graph.find_role(RoomRole::Start), Some(0));
+        assert_eq!(graph.find_role(RoomRole::Boss), Some(graph.rooms.len() - 1));
+        assert!(graph.find_role(RoomRole::Key).is_some());
+    }
+
+    #[test]
+    fn test_mission_solvable() {
+        for seed in 1..=20 {
+            let mission = generator().with_key_count(2).generate(seed).unwrap();
+            assert!(mission.graph.is_solvable(), "seed {seed}");
+        }
+    }
+
+    #[test]
+    fn test_mission_key_before_lock() {
+        let mission = generator().with_key_count(3).generate(7).unwrap();
+        let rooms = &mission.graph.rooms;
+        for (lock_index, room) in rooms.iter().enumerate() {
+            if let Some(item) = &room.requires {
+                let key_index = rooms
+                    .iter()
+                    .position(|r| r.grants.as_ref() == Some(item))
+                    .unwrap();
+                assert!(key_index < lock_index);
+            }
+        }
+    }
+
+    #[test]
+    fn test_mission_unsolvable_without_key() {
+        let mut mission = generator().generate(3).unwrap();
+        for room in &mut mission.graph.rooms {
+            room.grants = None;
+        }
+        assert!(!mission.graph.is_solvable());
+    }
+
+    #[test]
+    fn test_mission_difficulty_increases() {
+        let mission = generator().generate(11).unwrap();
+        let rooms = &mission.graph.rooms;
+        assert!(rooms[rooms.len() - 1].difficulty > rooms[0].difficulty);
+    }
+
+    #[test]
+    fn test_mission_too_few_rooms() {
+        let result = MissionGenerator::new(DungeonGenerator::new(80, 80).with_room_count(2))
+            .with_key_count(2)
+            .generate(1);
+        assert!(matches!(result, Err(ProcgenError::GenerationFailed(_))));
+    }
+
+    #[test]
+    fn test_mission_zero_keys() {
+        let result = generator().with_key_count(0).generate(1);
+        assert!(matches!(result, Err(ProcgenError::InvalidParameters(_))));
+    }
+
+    #[test]
+    fn test_mission_neighbors() {
+        let mission = generator().generate(5).unwrap();
+        assert_eq!(mission.graph.neighbors(0), vec![1]);
+        assert_eq!(mission.graph.neighbors(1), vec![0, 2]);
+    }
+}
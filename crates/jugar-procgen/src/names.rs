@@ -0,0 +1,267 @@
+//! Syllable-template name generation for kid-safe procedural content.
+//!
+//! Templates use `C` for a consonant, `V` for a vowel and `S` for a themed
+//! syllable; any other character is copied literally. Every generated name is
+//! run through the shared [`ContentFilter`] before it is returned.
+
+use jugar_core::ContentFilter;
+use serde::{Deserialize, Serialize};
+
+use crate::{ProcgenError, Result, Rng};
+
+/// Themed sound sets for names
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum NameTheme {
+    /// Soft, bouncy names for pets (e.g. "Mimo", "Pufi")
+    #[default]
+    CuteAnimals,
+    /// Techy names for planets and ships (e.g. "Zorax", "Kelvo")
+    Space,
+    /// Storybook names for heroes and places (e.g. "Elvarin")
+    Fantasy,
+}
+
+impl NameTheme {
+    /// Consonants used by `C`
+    #[must_use]
+    pub const fn consonants(self) -> &'static [&'static str] {
+        match self {
+            Self::CuteAnimals => &["b", "m", "p", "l", "n", "f", "w", "t", "d", "s"],
+            Self::Space => &["z", "x", "k", "v", "r", "t", "q", "n", "s", "l"],
+            Self::Fantasy => &["l", "r", "th", "v", "d", "g", "m", "n", "s", "f"],
+        }
+    }
+
+    /// Vowels used by `V`
+    #[must_use]
+    pub const fn vowels(self) -> &'static [&'static str] {
+        match self {
+            Self::CuteAnimals => &["a", "i", "o", "u", "ee", "oo"],
+            Self::Space => &["a", "e", "i", "o", "y"],
+            Self::Fantasy => &["a", "e", "i", "o", "ae", "ia"],
+        }
+    }
+
+    /// Whole syllables used by `S`
+    #[must_use]
+    pub const fn syllables(self) -> &'static [&'static str] {
+        match self {
+            Self::CuteAnimals => &["mo", "pip", "bun", "fluf", "wig", "nib", "tot"],
+            Self::Space => &["zor", "ax", "nova", "tron", "kel", "vex", "ion"],
+            Self::Fantasy => &["el", "var", "in", "dor", "wyn", "mir", "eth"],
+        }
+    }
+
+    /// Default template for the theme
+    #[must_use]
+    pub const fn default_template(self) -> &'static str {
+        match self {
+            Self::CuteAnimals => "CVCV",
+            Self::Space => "SCV",
+            Self::Fantasy => "VSS",
+        }
+    }
+}
+
+/// Generates pronounceable names from syllable templates
+#[derive(Debug, Clone)]
+pub struct NameGenerator {
+    theme: NameTheme,
+    template: String,
+    filter: ContentFilter,
+    max_attempts: u32,
+}
+
+impl NameGenerator {
+    /// Creates a generator using the theme's default template
+    #[must_use]
+    pub fn new(theme: NameTheme) -> Self {
+        Self {
+            theme,
+            template: theme.default_template().to_string(),
+            filter: ContentFilter::new(),
+            max_attempts: 32,
+        }
+    }
+
+    /// Sets the syllable template
+    #[must_use]
+    pub fn with_template(mut self, template: impl Into<String>) -> Self {
+        self.template = template.into();
+        self
+    }
+
+    /// Sets the content filter used to reject names
+    #[must_use]
+    pub fn with_filter(mut self, filter: ContentFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Sets how many candidates are tried before giving up
+    #[must_use]
+    pub const fn with_max_attempts(mut self, attempts: u32) -> Self {
+        self.max_attempts = attempts;
+        self
+    }
+
+    /// Returns the theme
+    #[must_use]
+    pub const fn theme(&self) -> NameTheme {
+        self.theme
+    }
+
+    /// Returns the template
+    #[must_use]
+    pub fn template(&self) -> &str {
+        &self.template
+    }
+
+    /// Generates a single filtered name
+    ///
+    /// # Errors
+    ///
+    /// Returns `ProcgenError::InvalidParameters` for an empty template and
+    /// `ProcgenError::GenerationFailed` if every candidate was rejected.
+    pub fn generate(&self, rng: &mut Rng) -> Result<String> {
+        if self.template.is_empty() {
+            return Err(ProcgenError::InvalidParameters(
+                "Name template is empty".to_string(),
+            ));
+        }
+        for _ in 0..self.max_attempts {
+            let name = self.candidate(rng);
+            if self.filter.check(&name).is_none() {
+                return Ok(name);
+            }
+        }
+        Err(ProcgenError::GenerationFailed(format!(
+            "No safe name found for template '{}'",
+            self.template
+        )))
+    }
+
+    /// Generates up to `count` distinct filtered names
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a name cannot be generated.
+    pub fn generate_unique(&self, rng: &mut Rng, count: usize) -> Result<Vec<String>> {
+        let mut names: Vec<String> = Vec::with_capacity(count);
+        let budget = count.saturating_mul(self.max_attempts as usize);
+        for _ in 0..budget {
+            if names.len() >= count {
+                break;
+            }
+            let name = self.generate(rng)?;
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+        Ok(names)
+    }
+
+    fn candidate(&self, rng: &mut Rng) -> String {
+        let mut name = String::new();
+        for symbol in self.template.chars() {
+            let pool = match symbol {
+                'C' => self.theme.consonants(),
+                'V' => self.theme.vowels(),
+                'S' => self.theme.syllables(),
+                literal => {
+                    name.push(literal);
+                    continue;
+                }
+            };
+            name.push_str(pool[rng.next_usize(pool.len())]);
+        }
+        capitalize(&name)
+    }
+}
+
+impl Default for NameGenerator {
+    fn default() -> Self {
+        Self::new(NameTheme::default())
+    }
+}
+
+fn capitalize(name: &str) -> String {
+    let mut chars = name.chars();
+    chars.next().map_or_else(String::new, |first| {
+        first.to_uppercase().chain(chars).collect()
+    })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_name_deterministic() {
+        let generator = NameGenerator::new(NameTheme::Space);
+        let a = generator.generate(&mut Rng::new(42)).unwrap();
+        let b = generator.generate(&mut Rng::new(42)).unwrap();
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_name_capitalized() {
+        let generator = NameGenerator::default();
+        let name = generator.generate(&mut Rng::new(1)).unwrap();
+        assert!(name.chars().next().unwrap().is_uppercase());
+    }
+
+    #[test]
+    fn test_name_template_literals() {
+        let generator = NameGenerator::new(NameTheme::CuteAnimals).with_template("CV-bot");
+        let name = generator.generate(&mut Rng::new(3)).unwrap();
+        assert!(name.ends_with("-bot"));
+    }
+
+    #[test]
+    fn test_name_all_themes() {
+        let mut rng = Rng::new(9);
+        for theme in [NameTheme::CuteAnimals, NameTheme::Space, NameTheme::Fantasy] {
+            let name = NameGenerator::new(theme).generate(&mut rng).unwrap();
+            assert!(name.len() >= 2);
+        }
+    }
+
+    #[test]
+    fn test_name_filtered() {
+        let generator = NameGenerator::default().with_template("kill");
+        let result = generator.generate(&mut Rng::new(1));
+        assert!(matches!(result, Err(ProcgenError::GenerationFailed(_))));
+    }
+
+    #[test]
+    fn test_name_custom_filter() {
+        let mut filter = ContentFilter::new();
+        filter.block_word("mo");
+        let generator = NameGenerator::new(NameTheme::CuteAnimals)
+            .with_template("S")
+            .with_filter(filter);
+        let mut rng = Rng::new(5);
+        for _ in 0..50 {
+            let name = generator.generate(&mut rng).unwrap();
+            assert!(!name.to_lowercase().contains("mo"));
+        }
+    }
+
+    #[test]
+    fn test_name_empty_template() {
+        let generator = NameGenerator::default().with_template("");
+        assert!(generator.generate(&mut Rng::new(1)).is_err());
+    }
+
+    #[test]
+    fn test_name_unique() {
+        let generator = NameGenerator::new(NameTheme::Fantasy);
+        let names = generator.generate_unique(&mut Rng::new(77), 10).unwrap();
+        assert_eq!(names.len(), 10);
+        for (i, name) in names.iter().enumerate() {
+            assert!(!names[i + 1..].contains(name));
+        }
+    }
+}
@@ -0,0 +1,185 @@
+//! Independent random streams from one seed.
+//!
+//! Sharing one [`Rng`] couples every system that draws from it: if the loot
+//! roller takes one extra number, every tree the terrain places afterwards
+//! moves. [`RngStreams`] gives each system a generator of its own, derived
+//! from the master seed and the stream's name with splitmix64, so a
+//! stream's numbers depend only on the seed and on what that stream drew.
+//! [`Rng::jump`] skips ahead without drawing, for replays and chunks that
+//! resume a stream part way through.
+//!
+//! ```
+//! use jugar_procgen::RngStreams;
+//!
+//! let streams = RngStreams::new(42);
+//! let mut terrain = streams.stream("terrain");
+//! let first = terrain.next_u64();
+//!
+//! // Drawing loot does not move the terrain stream
+//! let mut loot = streams.stream("loot");
+//! let _ = loot.next_u64();
+//! assert_eq!(streams.stream("terrain").next_u64(), first);
+//! ```
+
+use crate::Rng;
+
+/// Mixes a value into a well-spread seed (splitmix64)
+///
+/// Neighbouring inputs give unrelated outputs.
+#[must_use]
+pub const fn splitmix64(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// FNV-1a hash of a stream name
+const fn name_hash(name: &str) -> u64 {
+    let bytes = name.as_bytes();
+    let mut hash = 0xCBF2_9CE4_8422_2325_u64;
+    let mut i = 0;
+    while i < bytes.len() {
+        hash ^= bytes[i] as u64;
+        hash = hash.wrapping_mul(0x0100_0000_01B3);
+        i += 1;
+    }
+    hash
+}
+
+/// Named, independent generators derived from one master seed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RngStreams {
+    seed: u64,
+}
+
+impl RngStreams {
+    /// Creates streams for a master seed
+    #[must_use]
+    pub const fn new(seed: u64) -> Self {
+        Self { seed }
+    }
+
+    /// Master seed
+    #[must_use]
+    pub const fn seed(self) -> u64 {
+        self.seed
+    }
+
+    /// Seed of the stream called `name`
+    #[must_use]
+    pub const fn stream_seed(self, name: &str) -> u64 {
+        splitmix64(self.seed ^ name_hash(name))
+    }
+
+    /// Generator for the stream called `name`, from its first number
+    #[must_use]
+    pub const fn stream(self, name: &str) -> Rng {
+        Rng::new(self.stream_seed(name))
+    }
+
+    /// Generator for entry `index` of the stream called `name`
+    ///
+    /// For things numbered at runtime (enemy 3, wave 12), each of which
+    /// should draw without disturbing the others.
+    #[must_use]
+    pub const fn substream(self, name: &str, index: u64) -> Rng {
+        Rng::new(splitmix64(self.stream_seed(name) ^ splitmix64(index)))
+    }
+}
+
+/// Linear map over 64 bits: entry `i` is the image of bit `i`
+type BitMatrix = [u64; 64];
+
+/// One xorshift step (see [`Rng::next_u64`])
+const fn step(mut state: u64) -> u64 {
+    state ^= state << 13;
+    state ^= state >> 7;
+    state ^= state << 17;
+    state
+}
+
+fn apply(matrix: &BitMatrix, value: u64) -> u64 {
+    matrix
+        .iter()
+        .enumerate()
+        .filter(|(bit, _)| (value >> bit) & 1 == 1)
+        .fold(0, |acc, (_, image)| acc ^ image)
+}
+
+/// `outer` after `inner`
+fn compose(outer: &BitMatrix, inner: &BitMatrix) -> BitMatrix {
+    core::array::from_fn(|bit| apply(outer, inner[bit]))
+}
+
+/// State after `draws` xorshift steps
+///
+/// Each step is linear over GF(2), so `draws` steps are one bit matrix
+/// raised to the power `draws`: 64 squarings at most.
+pub fn jump_state(mut state: u64, mut draws: u64) -> u64 {
+    let mut power: BitMatrix = core::array::from_fn(|bit| step(1 << bit));
+    while draws > 0 {
+        if draws & 1 == 1 {
+            state = apply(&power, state);
+        }
+        draws >>= 1;
+        if draws > 0 {
+            power = compose(&power, &power);
+        }
+    }
+    state
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::ChunkCoord;
+
+    #[test]
+    fn test_jump_matches_drawing() {
+        for draws in [0, 1, 2, 63, 64, 1000] {
+            let mut jumped = Rng::new(7);
+            jumped.jump(draws);
+            let mut drawn = Rng::new(7);
+            for _ in 0..draws {
+                let _ = drawn.next_u64();
+            }
+            assert_eq!(jumped.next_u64(), drawn.next_u64(), "after {draws}");
+        }
+
+        // Jumps add up
+        let mut once = Rng::new(99);
+        once.jump(1 << 40);
+        let mut twice = Rng::new(99);
+        twice.jump(1 << 39);
+        twice.jump(1 << 39);
+        assert_eq!(once.next_u64(), twice.next_u64());
+    }
+
+    #[test]
+    fn test_streams_are_independent_and_deterministic() {
+        let streams = RngStreams::new(42);
+        assert_eq!(
+            streams.stream_seed("ai"),
+            RngStreams::new(42).stream_seed("ai")
+        );
+        assert_ne!(streams.stream_seed("ai"), streams.stream_seed("loot"));
+        assert_ne!(
+            streams.stream_seed("ai"),
+            RngStreams::new(43).stream_seed("ai")
+        );
+
+        let mut enemy_1 = streams.substream("ai", 1);
+        let mut enemy_2 = streams.substream("ai", 2);
+        assert_ne!(enemy_1.next_u64(), enemy_2.next_u64());
+        assert_eq!(
+            streams.substream("ai", 1).next_u64(),
+            RngStreams::new(42).substream("ai", 1).next_u64()
+        );
+
+        // Chunk seeds are splitmix64 of the world seed and coordinates
+        let coord = ChunkCoord::new(0, 0);
+        assert_eq!(coord.seed(9), splitmix64(9));
+    }
+}
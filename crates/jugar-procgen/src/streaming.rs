@@ -0,0 +1,187 @@
+//! Wave Function Collapse streamed across frames.
+//!
+//! A 100x100 [`Wfc`] takes far longer than one frame to collapse. A
+//! [`WfcStream`] runs it a few cells per frame with [`Wfc::step`] and hands
+//! back each square region as soon as all of its cells are settled, so a
+//! loading screen can draw the world appearing piece by piece while the
+//! main loop keeps hitting vsync.
+
+use crate::{Result, TileId, Wfc, WfcProgress};
+
+/// Default region edge (cells)
+pub const DEFAULT_REGION_SIZE: usize = 16;
+
+/// A fully collapsed rectangle of the map
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WfcRegion {
+    /// Left edge (cells)
+    pub x: usize,
+    /// Top edge (cells)
+    pub y: usize,
+    /// Width (cells)
+    pub width: usize,
+    /// Height (cells)
+    pub height: usize,
+    /// Tiles in row-major order
+    pub tiles: Vec<TileId>,
+}
+
+impl WfcRegion {
+    /// Tile at map coordinates, if inside this region
+    #[must_use]
+    pub fn get(&self, x: usize, y: usize) -> Option<TileId> {
+        if x < self.x || y < self.y || x >= self.x + self.width || y >= self.y + self.height {
+            return None;
+        }
+        self.tiles
+            .get((y - self.y) * self.width + (x - self.x))
+            .copied()
+    }
+}
+
+/// Runs a [`Wfc`] incrementally, yielding regions as they complete
+#[derive(Debug)]
+pub struct WfcStream {
+    wfc: Wfc,
+    region_size: usize,
+    columns: usize,
+    emitted: Vec<bool>,
+}
+
+impl WfcStream {
+    /// Streams `wfc` in regions of `region_size` x `region_size` cells
+    #[must_use]
+    pub fn new(wfc: Wfc, region_size: usize) -> Self {
+        let region_size = region_size.max(1);
+        let columns = wfc.width().div_ceil(region_size);
+        let rows = wfc.height().div_ceil(region_size);
+        Self {
+            wfc,
+            region_size,
+            columns,
+            emitted: vec![false; columns * rows],
+        }
+    }
+
+    /// Collapses up to `budget_cells` cells and returns regions that
+    /// completed during this call
+    ///
+    /// # Errors
+    ///
+    /// Returns `ProcgenError::WfcContradiction` if no tile fits a cell.
+    pub fn step(&mut self, budget_cells: usize) -> Result<Vec<WfcRegion>> {
+        let _ = self.wfc.step(budget_cells)?;
+        let mut completed = Vec::new();
+        for index in 0..self.emitted.len() {
+            if self.emitted[index] {
+                continue;
+            }
+            if let Some(region) = self.region(index) {
+                self.emitted[index] = true;
+                completed.push(region);
+            }
+        }
+        Ok(completed)
+    }
+
+    /// Current progress
+    #[must_use]
+    pub fn progress(&self) -> WfcProgress {
+        self.wfc.progress()
+    }
+
+    /// Returns true once every region has been yielded
+    #[must_use]
+    pub fn is_done(&self) -> bool {
+        self.emitted.iter().all(|&emitted| emitted)
+    }
+
+    /// The generator being streamed
+    #[must_use]
+    pub const fn wfc(&self) -> &Wfc {
+        &self.wfc
+    }
+
+    /// Finishes streaming, returning the generator
+    #[must_use]
+    pub fn into_inner(self) -> Wfc {
+        self.wfc
+    }
+
+    /// Region `index` if every cell in it is collapsed
+    fn region(&self, index: usize) -> Option<WfcRegion> {
+        let x = (index % self.columns) * self.region_size;
+        let y = (index / self.columns) * self.region_size;
+        let width = self.region_size.min(self.wfc.width() - x);
+        let height = self.region_size.min(self.wfc.height() - y);
+        let mut tiles = Vec::with_capacity(width * height);
+        for cy in y..y + height {
+            for cx in x..x + width {
+                tiles.push(self.wfc.get(cx, cy)?.collapsed?);
+            }
+        }
+        Some(WfcRegion {
+            x,
+            y,
+            width,
+            height,
+            tiles,
+        })
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::Direction;
+
+    fn checkerboard(width: usize, height: usize, seed: u64) -> Wfc {
+        let mut wfc = Wfc::new(width, height, 2, seed);
+        for dir in Direction::ALL {
+            wfc.rules_mut().add(0, dir, 1);
+            wfc.rules_mut().add(1, dir, 0);
+        }
+        wfc
+    }
+
+    #[test]
+    fn test_step_respects_budget_and_matches_collapse() {
+        let mut stepped = checkerboard(12, 10, 7);
+        let progress = stepped.step(5).unwrap();
+        assert_eq!(progress.collapsed, 5);
+        assert_eq!(progress.total, 120);
+        assert!(!progress.is_done());
+        while !stepped.step(5).unwrap().is_done() {}
+        assert!((stepped.progress().fraction() - 1.0).abs() < f32::EPSILON);
+
+        let mut whole = checkerboard(12, 10, 7);
+        whole.collapse().unwrap();
+        assert_eq!(stepped.result(), whole.result());
+    }
+
+    #[test]
+    fn test_stream_yields_every_region_once() {
+        let mut stream = WfcStream::new(checkerboard(20, 9, 3), 8);
+        let mut regions = Vec::new();
+        let mut frames = 0;
+        while !stream.is_done() {
+            regions.extend(stream.step(10).unwrap());
+            frames += 1;
+        }
+        assert!(frames > 1);
+        // 3 columns x 2 rows, edge regions clipped to the map
+        assert_eq!(regions.len(), 6);
+        let corner = regions.iter().find(|r| r.x == 16 && r.y == 8).unwrap();
+        assert_eq!((corner.width, corner.height), (4, 1));
+
+        let wfc = stream.into_inner();
+        for region in &regions {
+            for y in region.y..region.y + region.height {
+                for x in region.x..region.x + region.width {
+                    assert_eq!(region.get(x, y), wfc.get(x, y).unwrap().collapsed);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,677 @@
+//! Heightmap terrain generation with biome classification.
+//!
+//! Height, moisture and temperature are sampled from independent
+//! [`ValueNoise`] layers and combined into a [`Biome`] per tile. Biomes map
+//! onto the Level 1 background words so generated worlds match what kids can
+//! write in YAML.
+//!
+//! Rivers and roads are carved over a finished [`Terrain`] with
+//! [`Terrain::carve_river`] and [`Terrain::carve_road`].
+
+use alloc::collections::BinaryHeap;
+use core::cmp::Reverse;
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{ProcgenError, Result, TileId, ValueNoise};
+
+/// Terrain tile classification
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum Biome {
+    /// Deep or shallow water
+    #[default]
+    Water,
+    /// Sand next to water
+    Beach,
+    /// Open grassland
+    Grass,
+    /// Dense trees
+    Forest,
+    /// Rocky highlands
+    Mountain,
+    /// Cold peaks and tundra
+    Snow,
+    /// River carved downhill from a peak
+    River,
+    /// Road carved between points of interest
+    Road,
+    /// Road crossing water or a river
+    Bridge,
+}
+
+impl Biome {
+    /// Stable tile id for use in tile maps
+    #[must_use]
+    pub const fn tile_id(self) -> TileId {
+        self as TileId
+    }
+
+    /// Returns the Level 1 background word for this biome, if any
+    #[must_use]
+    pub const fn background_word(self) -> Option<&'static str> {
+        match self {
+            Self::Water | Self::River => Some("water"),
+            Self::Beach => Some("beach"),
+            Self::Grass => Some("grass"),
+            Self::Forest => Some("forest"),
+            Self::Snow => Some("snow"),
+            Self::Mountain | Self::Road | Self::Bridge => None,
+        }
+    }
+
+    /// Returns true if characters can walk on this tile
+    #[must_use]
+    pub const fn is_walkable(self) -> bool {
+        !matches!(self, Self::Water | Self::Mountain | Self::River)
+    }
+
+    /// Returns true for water and river tiles
+    #[must_use]
+    pub const fn is_water(self) -> bool {
+        matches!(self, Self::Water | Self::River)
+    }
+
+    /// Extra cost for building a road over this tile
+    const fn road_cost(self) -> u32 {
+        match self {
+            Self::Road | Self::Bridge => 0,
+            Self::Grass | Self::Beach => 10,
+            Self::Forest => 25,
+            Self::Snow => 30,
+            Self::River => 40,
+            Self::Water => 80,
+            Self::Mountain => 120,
+        }
+    }
+}
+
+/// Generated terrain grid
+#[derive(Debug, Clone)]
+pub struct Terrain {
+    /// Width in tiles
+    pub width: usize,
+    /// Height in tiles
+    pub height: usize,
+    /// Elevation per tile in [0, 1] (row-major)
+    pub heights: Vec<f32>,
+    /// Moisture per tile in [0, 1] (row-major)
+    pub moisture: Vec<f32>,
+    /// Temperature per tile in [0, 1] (row-major)
+    pub temperature: Vec<f32>,
+    /// Biome per tile (row-major)
+    pub tiles: Vec<Biome>,
+}
+
+impl Terrain {
+    /// Checks if a position is in bounds
+    #[must_use]
+    pub const fn in_bounds(&self, x: i32, y: i32) -> bool {
+        x >= 0 && (x as usize) < self.width && y >= 0 && (y as usize) < self.height
+    }
+
+    /// Gets the biome at a position
+    #[must_use]
+    pub fn get(&self, x: usize, y: usize) -> Option<Biome> {
+        if x < self.width && y < self.height {
+            Some(self.tiles[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    /// Sets the biome at a position
+    pub fn set(&mut self, x: usize, y: usize, tile: Biome) {
+        if x < self.width && y < self.height {
+            self.tiles[y * self.width + x] = tile;
+        }
+    }
+
+    /// Gets the elevation at a position
+    #[must_use]
+    pub fn height_at(&self, x: usize, y: usize) -> Option<f32> {
+        if x < self.width && y < self.height {
+            Some(self.heights[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    /// Returns the tile grid as tile ids (row-major)
+    #[must_use]
+    pub fn tile_ids(&self) -> Vec<TileId> {
+        self.tiles.iter().map(|b| b.tile_id()).collect()
+    }
+
+    /// Counts tiles of a biome
+    #[must_use]
+    pub fn count(&self, biome: Biome) -> usize {
+        self.tiles.iter().filter(|&&b| b == biome).count()
+    }
+
+    /// Returns up to `count` of the highest land tiles, at least `spacing` apart
+    #[must_use]
+    pub fn peaks(&self, count: usize, spacing: usize) -> Vec<(usize, usize)> {
+        let mut order: Vec<usize> = (0..self.heights.len())
+            .filter(|&i| !self.tiles[i].is_water())
+            .collect();
+        order.sort_by(|&a, &b| self.heights[b].total_cmp(&self.heights[a]));
+
+        let mut peaks: Vec<(usize, usize)> = Vec::new();
+        for index in order {
+            if peaks.len() >= count {
+                break;
+            }
+            let (x, y) = (index % self.width, index / self.width);
+            let far_enough = peaks
+                .iter()
+                .all(|&(px, py)| px.abs_diff(x).max(py.abs_diff(y)) >= spacing);
+            if far_enough {
+                peaks.push((x, y));
+            }
+        }
+        peaks
+    }
+
+    /// Traces a river downhill from `start` until it reaches water, the map
+    /// edge or a basin, marking the tiles as [`Biome::River`].
+    ///
+    /// Returns the river's tiles in flow order.
+    pub fn carve_river(&mut self, start: (usize, usize)) -> Vec<(usize, usize)> {
+        let mut path = Vec::new();
+        if start.0 >= self.width || start.1 >= self.height {
+            return path;
+        }
+
+        let mut visited = HashSet::new();
+        let mut current = start;
+        loop {
+            let _ = visited.insert(current);
+            let index = current.1 * self.width + current.0;
+            if self.tiles[index] == Biome::Water {
+                break;
+            }
+            path.push(current);
+
+            let next = self
+                .neighbors(current)
+                .into_iter()
+                .filter(|p| !visited.contains(p))
+                .min_by(|a, b| {
+                    let ha = self.heights[a.1 * self.width + a.0];
+                    let hb = self.heights[b.1 * self.width + b.0];
+                    ha.total_cmp(&hb)
+                });
+            let Some(next) = next else { break };
+            // Allow flowing across flat ground but stop in a basin
+            if self.heights[next.1 * self.width + next.0] > self.heights[index] + 0.02 {
+                break;
+            }
+            current = next;
+        }
+
+        for &(x, y) in &path {
+            self.set(x, y, Biome::River);
+        }
+        path
+    }
+
+    /// Carves a road between two points using weighted pathfinding.
+    ///
+    /// Steep slopes, forests, mountains and water are expensive. Road tiles
+    /// over water or rivers become [`Biome::Bridge`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `ProcgenError::InvalidParameters` if either point is out of bounds
+    /// and `ProcgenError::GenerationFailed` if no path exists.
+    pub fn carve_road(
+        &mut self,
+        from: (usize, usize),
+        to: (usize, usize),
+    ) -> Result<Vec<(usize, usize)>> {
+        let path = self.find_road_path(from, to)?;
+        for &(x, y) in &path {
+            let index = y * self.width + x;
+            let tile = self.tiles[index];
+            self.tiles[index] = if tile.is_water() || tile == Biome::Bridge {
+                Biome::Bridge
+            } else {
+                Biome::Road
+            };
+        }
+        Ok(path)
+    }
+
+    /// Finds the cheapest road path without modifying the terrain
+    ///
+    /// # Errors
+    ///
+    /// Returns `ProcgenError::InvalidParameters` if either point is out of bounds
+    /// and `ProcgenError::GenerationFailed` if no path exists.
+    pub fn find_road_path(
+        &self,
+        from: (usize, usize),
+        to: (usize, usize),
+    ) -> Result<Vec<(usize, usize)>> {
+        for (x, y) in [from, to] {
+            if x >= self.width || y >= self.height {
+                return Err(ProcgenError::InvalidParameters(format!(
+                    "Road point ({x}, {y}) is outside the terrain"
+                )));
+            }
+        }
+
+        let size = self.width * self.height;
+        let mut cost = vec![u32::MAX; size];
+        let mut came_from = vec![usize::MAX; size];
+        let mut open = BinaryHeap::new();
+        let start = from.1 * self.width + from.0;
+        let goal = to.1 * self.width + to.0;
+        cost[start] = 0;
+        open.push(Reverse((0_u32, start)));
+
+        while let Some(Reverse((current_cost, index))) = open.pop() {
+            if index == goal {
+                break;
+            }
+            if current_cost > cost[index] {
+                continue;
+            }
+            let current = (index % self.width, index / self.width);
+            for (nx, ny) in self.neighbors(current) {
+                let next = ny * self.width + nx;
+                let slope = (self.heights[next] - self.heights[index]).abs();
+                let step = self.tiles[next].road_cost() + (slope * 400.0) as u32;
+                let next_cost = current_cost.saturating_add(step);
+                if next_cost < cost[next] {
+                    cost[next] = next_cost;
+                    came_from[next] = index;
+                    open.push(Reverse((next_cost, next)));
+                }
+            }
+        }
+
+        let mut path = vec![to];
+        let mut index = goal;
+        while index != start {
+            index = came_from[index];
+            if index == usize::MAX {
+                return Err(ProcgenError::GenerationFailed(
+                    "No road path found".to_string(),
+                ));
+            }
+            path.push((index % self.width, index / self.width));
+        }
+        path.reverse();
+        Ok(path)
+    }
+
+    fn neighbors(&self, (x, y): (usize, usize)) -> Vec<(usize, usize)> {
+        let mut result = Vec::with_capacity(4);
+        if x > 0 {
+            result.push((x - 1, y));
+        }
+        if x + 1 < self.width {
+            result.push((x + 1, y));
+        }
+        if y > 0 {
+            result.push((x, y - 1));
+        }
+        if y + 1 < self.height {
+            result.push((x, y + 1));
+        }
+        result
+    }
+}
+
+/// Terrain generator combining height, moisture and temperature noise
+#[derive(Debug, Clone)]
+pub struct TerrainGenerator {
+    /// Width in tiles
+    pub width: usize,
+    /// Height in tiles
+    pub height: usize,
+    /// Noise scale (larger means bigger features)
+    pub scale: f32,
+    /// Elevation below which tiles are water
+    pub water_level: f32,
+    /// Elevation below which tiles are beach
+    pub beach_level: f32,
+    /// Elevation above which tiles are mountain
+    pub mountain_level: f32,
+    /// Elevation above which tiles are snow
+    pub snow_level: f32,
+}
+
+impl TerrainGenerator {
+    /// Creates a terrain generator with default thresholds
+    #[must_use]
+    pub const fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            scale: 16.0,
+            water_level: 0.35,
+            beach_level: 0.4,
+            mountain_level: 0.7,
+            snow_level: 0.82,
+        }
+    }
+
+    /// Sets the noise scale
+    #[must_use]
+    pub const fn with_scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Sets the water and beach elevation thresholds
+    #[must_use]
+    pub const fn with_sea_level(mut self, water: f32, beach: f32) -> Self {
+        self.water_level = water;
+        self.beach_level = beach;
+        self
+    }
+
+    /// Sets the mountain and snow elevation thresholds
+    #[must_use]
+    pub const fn with_peaks(mut self, mountain: f32, snow: f32) -> Self {
+        self.mountain_level = mountain;
+        self.snow_level = snow;
+        self
+    }
+
+    /// Generates terrain with the given seed
+    ///
+    /// # Errors
+    ///
+    /// Returns `ProcgenError::InvalidParameters` for an empty map or a
+    /// non-positive scale.
+    pub fn generate(&self, seed: u64) -> Result<Terrain> {
+        self.generate_at(seed, 0, 0)
+    }
+
+    /// Generates the terrain whose top-left tile is at `(origin_x, origin_y)`
+    /// of an endless map
+    ///
+    /// Neighbouring areas generated with the same seed line up seamlessly,
+    /// which is what chunked worlds are built from.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ProcgenError::InvalidParameters` for an empty map or a
+    /// non-positive scale.
+    pub fn generate_at(&self, seed: u64, origin_x: i64, origin_y: i64) -> Result<Terrain> {
+        if self.width == 0 || self.height == 0 {
+            return Err(ProcgenError::InvalidParameters(
+                "Terrain size must be non-zero".to_string(),
+            ));
+        }
+        if self.scale <= 0.0 {
+            return Err(ProcgenError::InvalidParameters(
+                "Terrain scale must be positive".to_string(),
+            ));
+        }
+
+        let base = ValueNoise::new(seed).with_scale(self.scale).with_octaves(5);
+        let detail = ValueNoise::new(seed.wrapping_add(1))
+            .with_scale(self.scale * 0.25)
+            .with_octaves(2);
+        let moisture_noise = ValueNoise::new(seed.wrapping_add(2)).with_scale(self.scale * 1.5);
+        let temperature_noise = ValueNoise::new(seed.wrapping_add(3)).with_scale(self.scale * 2.0);
+
+        let size = self.width * self.height;
+        let mut heights = Vec::with_capacity(size);
+        let mut moisture = Vec::with_capacity(size);
+        let mut temperature = Vec::with_capacity(size);
+        let mut tiles = Vec::with_capacity(size);
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let (fx, fy) = ((origin_x + x as i64) as f32, (origin_y + y as i64) as f32);
+                let h = base
+                    .sample(fx, fy)
+                    .mul_add(0.75, detail.sample(fx, fy) * 0.25)
+                    .clamp(0.0, 1.0);
+                let m = moisture_noise.sample(fx, fy).clamp(0.0, 1.0);
+                // Higher ground is colder
+                let t = h
+                    .mul_add(-0.3, temperature_noise.sample(fx, fy))
+                    .clamp(0.0, 1.0);
+
+                heights.push(h);
+                moisture.push(m);
+                temperature.push(t);
+                tiles.push(self.classify(h, m, t));
+            }
+        }
+
+        Ok(Terrain {
+            width: self.width,
+            height: self.height,
+            heights,
+            moisture,
+            temperature,
+            tiles,
+        })
+    }
+
+    /// Classifies a tile from its elevation, moisture and temperature
+    #[must_use]
+    pub fn classify(&self, height: f32, moisture: f32, temperature: f32) -> Biome {
+        if height < self.water_level {
+            Biome::Water
+        } else if height < self.beach_level {
+            Biome::Beach
+        } else if height >= self.snow_level {
+            Biome::Snow
+        } else if height >= self.mountain_level {
+            if temperature < 0.2 {
+                Biome::Snow
+            } else {
+                Biome::Mountain
+            }
+        } else if temperature < 0.1 {
+            Biome::Snow
+        } else if moisture > 0.55 {
+            Biome::Forest
+        } else {
+            Biome::Grass
+        }
+    }
+}
+
+impl Default for TerrainGenerator {
+    fn default() -> Self {
+        Self::new(64, 64)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_terrain_dimensions() {
+        let terrain = TerrainGenerator::new(32, 24).generate(1).unwrap();
+        assert_eq!(terrain.tiles.len(), 32 * 24);
+        assert_eq!(terrain.heights.len(), 32 * 24);
+        assert_eq!(terrain.tile_ids().len(), 32 * 24);
+    }
+
+    #[test]
+    fn test_terrain_deterministic() {
+        let a = TerrainGenerator::default().generate(42).unwrap();
+        let b = TerrainGenerator::default().generate(42).unwrap();
+        assert_eq!(a.tiles, b.tiles);
+    }
+
+    #[test]
+    fn test_terrain_generate_at_is_seamless() {
+        let generator = TerrainGenerator::new(16, 8);
+        let wide = TerrainGenerator::new(32, 8)
+            .generate_at(5, -16, 40)
+            .unwrap();
+        let left = generator.generate_at(5, -16, 40).unwrap();
+        let right = generator.generate_at(5, 0, 40).unwrap();
+        for y in 0..8 {
+            for x in 0..16 {
+                assert_eq!(left.get(x, y), wide.get(x, y));
+                assert_eq!(right.get(x, y), wide.get(x + 16, y));
+            }
+        }
+    }
+
+    #[test]
+    fn test_terrain_seed_varies() {
+        let a = TerrainGenerator::default().generate(1).unwrap();
+        let b = TerrainGenerator::default().generate(2).unwrap();
+        assert_ne!(a.tiles, b.tiles);
+    }
+
+    #[test]
+    fn test_terrain_heights_normalized() {
+        let terrain = TerrainGenerator::default().generate(7).unwrap();
+        assert!(terrain.heights.iter().all(|h| (0.0..=1.0).contains(h)));
+        assert!(terrain.temperature.iter().all(|t| (0.0..=1.0).contains(t)));
+    }
+
+    #[test]
+    fn test_terrain_has_multiple_biomes() {
+        let terrain = TerrainGenerator::new(96, 96).generate(3).unwrap();
+        let distinct = [
+            Biome::Water,
+            Biome::Beach,
+            Biome::Grass,
+            Biome::Forest,
+            Biome::Mountain,
+            Biome::Snow,
+        ]
+        .iter()
+        .filter(|&&b| terrain.count(b) > 0)
+        .count();
+        assert!(distinct >= 3);
+    }
+
+    #[test]
+    fn test_terrain_classify_thresholds() {
+        let generator = TerrainGenerator::default();
+        assert_eq!(generator.classify(0.1, 0.5, 0.5), Biome::Water);
+        assert_eq!(generator.classify(0.37, 0.5, 0.5), Biome::Beach);
+        assert_eq!(generator.classify(0.5, 0.2, 0.5), Biome::Grass);
+        assert_eq!(generator.classify(0.5, 0.8, 0.5), Biome::Forest);
+        assert_eq!(generator.classify(0.75, 0.5, 0.5), Biome::Mountain);
+        assert_eq!(generator.classify(0.9, 0.5, 0.5), Biome::Snow);
+    }
+
+    #[test]
+    fn test_terrain_invalid_params() {
+        assert!(TerrainGenerator::new(0, 10).generate(1).is_err());
+        assert!(TerrainGenerator::default()
+            .with_scale(0.0)
+            .generate(1)
+            .is_err());
+    }
+
+    #[test]
+    fn test_biome_background_words() {
+        assert_eq!(Biome::Forest.background_word(), Some("forest"));
+        assert_eq!(Biome::Mountain.background_word(), None);
+        assert!(!Biome::Water.is_walkable());
+        assert!(Biome::Grass.is_walkable());
+    }
+
+    #[test]
+    fn test_terrain_get_set() {
+        let mut terrain = TerrainGenerator::new(8, 8).generate(1).unwrap();
+        terrain.set(2, 3, Biome::Snow);
+        assert_eq!(terrain.get(2, 3), Some(Biome::Snow));
+        assert_eq!(terrain.get(8, 0), None);
+        assert!(terrain.height_at(0, 0).is_some());
+    }
+
+    #[test]
+    fn test_peaks_spaced() {
+        let terrain = TerrainGenerator::default().generate(5).unwrap();
+        let peaks = terrain.peaks(3, 10);
+        assert!(!peaks.is_empty());
+        for (i, a) in peaks.iter().enumerate() {
+            for b in &peaks[i + 1..] {
+                assert!(a.0.abs_diff(b.0).max(a.1.abs_diff(b.1)) >= 10);
+            }
+        }
+    }
+
+    #[test]
+    fn test_river_flows_downhill() {
+        let mut terrain = TerrainGenerator::default().generate(5).unwrap();
+        let peak = terrain.peaks(1, 0)[0];
+        let river = terrain.carve_river(peak);
+        assert_eq!(river.first(), Some(&peak));
+        let start_height = terrain.height_at(peak.0, peak.1).unwrap();
+        let (ex, ey) = *river.last().unwrap();
+        assert!(terrain.height_at(ex, ey).unwrap() <= start_height);
+        assert!(river
+            .iter()
+            .all(|&(x, y)| terrain.get(x, y) == Some(Biome::River)));
+    }
+
+    #[test]
+    fn test_river_stops_at_water() {
+        let mut terrain = TerrainGenerator::new(4, 1).generate(1).unwrap();
+        terrain.heights = vec![0.9, 0.6, 0.3, 0.1];
+        terrain.tiles = vec![Biome::Snow, Biome::Grass, Biome::Water, Biome::Water];
+        let river = terrain.carve_river((0, 0));
+        assert_eq!(river, vec![(0, 0), (1, 0)]);
+        assert_eq!(terrain.get(2, 0), Some(Biome::Water));
+    }
+
+    #[test]
+    fn test_road_connects_points() {
+        let mut terrain = TerrainGenerator::default().generate(9).unwrap();
+        let road = terrain.carve_road((2, 2), (50, 40)).unwrap();
+        assert_eq!(road.first(), Some(&(2, 2)));
+        assert_eq!(road.last(), Some(&(50, 40)));
+        for pair in road.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            assert_eq!(a.0.abs_diff(b.0) + a.1.abs_diff(b.1), 1);
+        }
+        assert!(road
+            .iter()
+            .all(|&(x, y)| matches!(terrain.get(x, y), Some(Biome::Road | Biome::Bridge))));
+    }
+
+    #[test]
+    fn test_road_bridges_water() {
+        let mut terrain = TerrainGenerator::new(5, 1).generate(1).unwrap();
+        terrain.heights = vec![0.5; 5];
+        terrain.tiles = vec![
+            Biome::Grass,
+            Biome::Grass,
+            Biome::River,
+            Biome::Grass,
+            Biome::Grass,
+        ];
+        let _ = terrain.carve_road((0, 0), (4, 0)).unwrap();
+        assert_eq!(terrain.get(2, 0), Some(Biome::Bridge));
+        assert_eq!(terrain.get(0, 0), Some(Biome::Road));
+    }
+
+    #[test]
+    fn test_road_avoids_mountains() {
+        let mut terrain = TerrainGenerator::new(3, 3).generate(1).unwrap();
+        terrain.heights = vec![0.5; 9];
+        terrain.tiles = vec![Biome::Grass; 9];
+        terrain.set(1, 1, Biome::Mountain);
+        let path = terrain.find_road_path((0, 1), (2, 1)).unwrap();
+        assert!(!path.contains(&(1, 1)));
+    }
+
+    #[test]
+    fn test_road_out_of_bounds() {
+        let mut terrain = TerrainGenerator::new(8, 8).generate(1).unwrap();
+        assert!(terrain.carve_road((0, 0), (8, 8)).is_err());
+    }
+}
@@ -0,0 +1,314 @@
+//! Neighborhoods for [`Wfc`](crate::Wfc) grids.
+//!
+//! A [`Topology`] decides which cells are next to which. [`SquareGrid`] is
+//! the classic four-way grid; [`HexGrid`] gives every cell six neighbors for
+//! board and strategy games. Either can wrap around its edges, so a map
+//! scrolls forever without a seam: leaving the right edge enters the left,
+//! leaving the top enters the bottom.
+//!
+//! ```
+//! use jugar_procgen::{HexDirection, HexGrid, Wfc};
+//!
+//! let mut wfc = Wfc::new(8, 6, 2, 42).with_topology(HexGrid::wrapping());
+//! for dir in HexDirection::ALL {
+//!     wfc.rules_mut().add(0, dir, 0);
+//!     wfc.rules_mut().add(0, dir, 1);
+//!     wfc.rules_mut().add(1, dir, 0);
+//! }
+//! wfc.collapse().unwrap();
+//! ```
+
+use core::fmt;
+
+use crate::{Direction, ProcgenError, Result};
+
+/// Which cells of a grid neighbor which
+///
+/// Directions are numbered `0..directions()`, and adjacency rules are kept
+/// per direction number.
+pub trait Topology: fmt::Debug + Send + Sync {
+    /// Number of directions a cell has neighbors in
+    fn directions(&self) -> usize;
+
+    /// The cell one step from `(x, y)` in `direction` on a `width` x
+    /// `height` grid, if there is one
+    fn neighbor(
+        &self,
+        x: usize,
+        y: usize,
+        direction: usize,
+        width: usize,
+        height: usize,
+    ) -> Option<(usize, usize)>;
+
+    /// Checks a grid size suits this topology
+    ///
+    /// # Errors
+    ///
+    /// Returns `ProcgenError::InvalidParameters` if it does not.
+    fn validate(&self, width: usize, height: usize) -> Result<()> {
+        let _ = (width, height);
+        Ok(())
+    }
+}
+
+/// Moves `delta` along an axis of length `len`
+#[allow(clippy::cast_possible_wrap)]
+fn step_axis(position: usize, delta: i32, len: usize, wrap: bool) -> Option<usize> {
+    let moved = position as i64 + i64::from(delta);
+    if wrap {
+        Some(moved.rem_euclid(len as i64) as usize)
+    } else {
+        usize::try_from(moved).ok().filter(|&p| p < len)
+    }
+}
+
+/// Four-way square grid, numbered as [`Direction`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SquareGrid {
+    wrap: bool,
+}
+
+impl SquareGrid {
+    /// Square grid with hard edges
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { wrap: false }
+    }
+
+    /// Square grid whose edges wrap around (a torus)
+    #[must_use]
+    pub const fn wrapping() -> Self {
+        Self { wrap: true }
+    }
+}
+
+impl Topology for SquareGrid {
+    fn directions(&self) -> usize {
+        Direction::ALL.len()
+    }
+
+    fn neighbor(
+        &self,
+        x: usize,
+        y: usize,
+        direction: usize,
+        width: usize,
+        height: usize,
+    ) -> Option<(usize, usize)> {
+        let (dx, dy) = Direction::ALL.get(direction)?.delta();
+        Some((
+            step_axis(x, dx, width, self.wrap)?,
+            step_axis(y, dy, height, self.wrap)?,
+        ))
+    }
+}
+
+/// Direction on a [`HexGrid`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HexDirection {
+    /// Right
+    East,
+    /// Left
+    West,
+    /// Up and right
+    NorthEast,
+    /// Up and left
+    NorthWest,
+    /// Down and right
+    SouthEast,
+    /// Down and left
+    SouthWest,
+}
+
+impl HexDirection {
+    /// All directions
+    pub const ALL: [Self; 6] = [
+        Self::East,
+        Self::West,
+        Self::NorthEast,
+        Self::NorthWest,
+        Self::SouthEast,
+        Self::SouthWest,
+    ];
+
+    /// Returns the opposite direction
+    #[must_use]
+    pub const fn opposite(self) -> Self {
+        match self {
+            Self::East => Self::West,
+            Self::West => Self::East,
+            Self::NorthEast => Self::SouthWest,
+            Self::NorthWest => Self::SouthEast,
+            Self::SouthEast => Self::NorthWest,
+            Self::SouthWest => Self::NorthEast,
+        }
+    }
+
+    /// Returns the delta for this direction from a cell in an even or odd row
+    #[must_use]
+    pub const fn delta(self, odd_row: bool) -> (i32, i32) {
+        match (self, odd_row) {
+            (Self::East, _) => (1, 0),
+            (Self::West, _) => (-1, 0),
+            (Self::NorthEast, false) | (Self::NorthWest, true) => (0, -1),
+            (Self::SouthEast, false) | (Self::SouthWest, true) => (0, 1),
+            (Self::NorthWest, false) => (-1, -1),
+            (Self::SouthWest, false) => (-1, 1),
+            (Self::NorthEast, true) => (1, -1),
+            (Self::SouthEast, true) => (1, 1),
+        }
+    }
+}
+
+impl From<HexDirection> for usize {
+    fn from(direction: HexDirection) -> Self {
+        direction as Self
+    }
+}
+
+/// Six-way grid of pointy-topped hexes, numbered as [`HexDirection`]
+///
+/// Cells are stored in rows like a square grid, with odd rows shifted half a
+/// cell to the right.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HexGrid {
+    wrap: bool,
+}
+
+impl HexGrid {
+    /// Hex grid with hard edges
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { wrap: false }
+    }
+
+    /// Hex grid whose edges wrap around
+    ///
+    /// Needs an even height, so the shifted rows line up across the seam.
+    #[must_use]
+    pub const fn wrapping() -> Self {
+        Self { wrap: true }
+    }
+}
+
+impl Topology for HexGrid {
+    fn directions(&self) -> usize {
+        HexDirection::ALL.len()
+    }
+
+    fn neighbor(
+        &self,
+        x: usize,
+        y: usize,
+        direction: usize,
+        width: usize,
+        height: usize,
+    ) -> Option<(usize, usize)> {
+        let (dx, dy) = HexDirection::ALL.get(direction)?.delta(y % 2 == 1);
+        Some((
+            step_axis(x, dx, width, self.wrap)?,
+            step_axis(y, dy, height, self.wrap)?,
+        ))
+    }
+
+    fn validate(&self, width: usize, height: usize) -> Result<()> {
+        if self.wrap && height % 2 == 1 {
+            return Err(ProcgenError::InvalidParameters(format!(
+                "Wrapping hex grid {width}x{height} needs an even height"
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::{TileId, Wfc};
+
+    /// Water (0) and land (1) never touch; sand (2) goes anywhere
+    fn coastline(wfc: &mut Wfc, directions: usize) {
+        for dir in 0..directions {
+            for (tile, neighbors) in [(0, [0, 2]), (1, [1, 2])] {
+                for neighbor in neighbors {
+                    wfc.rules_mut().add(tile, dir, neighbor);
+                }
+            }
+            for neighbor in 0..3 {
+                wfc.rules_mut().add(2, dir, neighbor);
+            }
+        }
+    }
+
+    fn assert_coastline(grid: &[Option<TileId>], topology: &dyn Topology, width: usize) {
+        let height = grid.len() / width;
+        for y in 0..height {
+            for x in 0..width {
+                let tile = grid[y * width + x].unwrap();
+                for dir in 0..topology.directions() {
+                    let (nx, ny) = topology
+                        .neighbor(x, y, dir, width, height)
+                        .unwrap_or((x, y));
+                    let neighbor = grid[ny * width + nx].unwrap();
+                    assert!(tile + neighbor != 1, "water next to land at ({x}, {y})");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_neighbors_are_mutual() {
+        for topology in [HexGrid::new(), HexGrid::wrapping()] {
+            for y in 0..6 {
+                for x in 0..5 {
+                    for dir in HexDirection::ALL {
+                        let Some((nx, ny)) = topology.neighbor(x, y, dir.into(), 5, 6) else {
+                            continue;
+                        };
+                        let back = topology.neighbor(nx, ny, dir.opposite().into(), 5, 6);
+                        assert_eq!(back, Some((x, y)), "{dir:?} from ({x}, {y})");
+                    }
+                }
+            }
+        }
+
+        let torus = SquareGrid::wrapping();
+        assert_eq!(
+            torus.neighbor(0, 0, Direction::Left.into(), 4, 3),
+            Some((3, 0))
+        );
+        assert_eq!(
+            torus.neighbor(2, 0, Direction::Up.into(), 4, 3),
+            Some((2, 2))
+        );
+        assert_eq!(
+            SquareGrid::new().neighbor(0, 0, Direction::Up.into(), 4, 3),
+            None
+        );
+        assert!(HexGrid::wrapping().validate(4, 5).is_err());
+    }
+
+    fn check_wfc<T: Topology + Copy + 'static>(topology: T, height: usize) {
+        for seed in 1..5 {
+            let mut wfc = Wfc::new(9, height, 3, seed).with_topology(topology);
+            coastline(&mut wfc, topology.directions());
+            wfc.collapse().unwrap();
+            assert_coastline(&wfc.result(), &topology, 9);
+        }
+    }
+
+    #[test]
+    fn test_wfc_respects_topology() {
+        check_wfc(HexGrid::new(), 7);
+        check_wfc(HexGrid::wrapping(), 8);
+        check_wfc(SquareGrid::wrapping(), 8);
+
+        let mut odd = Wfc::new(4, 5, 3, 1).with_topology(HexGrid::wrapping());
+        assert!(matches!(
+            odd.collapse(),
+            Err(ProcgenError::InvalidParameters(_))
+        ));
+    }
+}
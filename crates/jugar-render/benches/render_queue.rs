@@ -0,0 +1,61 @@
+//! Benchmarks for building a frame's render queue.
+
+#![allow(missing_docs, unused_results)]
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use glam::Vec2;
+use jugar_core::{Camera, Position};
+use jugar_render::{RenderCommand, RenderQueue, SpriteParams, TextureFilter, Viewport};
+
+/// Sprites spread over twice the screen, so about a quarter are culled
+fn sprite_positions(count: usize) -> Vec<Vec2> {
+    (0..count)
+        .map(|i| {
+            let t = i as f32 * 0.618_034;
+            Vec2::new(
+                (t.fract() - 0.5) * 3840.0,
+                ((t * 7.0).fract() - 0.5) * 2160.0,
+            )
+        })
+        .collect()
+}
+
+fn bench_build_queue(c: &mut Criterion) {
+    let mut group = c.benchmark_group("render_queue");
+    let viewport = Viewport::new(1920, 1080);
+    let camera = Camera::new();
+
+    for count in &[100, 1000, 10000] {
+        let positions = sprite_positions(*count);
+        let mut queue = RenderQueue::new();
+        group.bench_with_input(BenchmarkId::new("sprites", count), count, |b, _| {
+            b.iter(|| {
+                queue.clear();
+                queue.push(RenderCommand::Clear {
+                    color: [0.1, 0.1, 0.2, 1.0],
+                });
+                for (i, pos) in positions.iter().enumerate() {
+                    if !viewport.is_visible(*pos, &camera) {
+                        continue;
+                    }
+                    queue.push(RenderCommand::DrawSprite {
+                        texture_id: (i % 16) as u32,
+                        position: Position::new(pos.x, pos.y),
+                        size: Vec2::splat(32.0),
+                        source: None,
+                        color: [1.0; 4],
+                        filter: TextureFilter::Nearest,
+                        params: SpriteParams::new(),
+                    });
+                }
+                queue.text("Score: 42", Vec2::new(16.0, 16.0), 24.0, [1.0; 4]);
+                black_box(queue.len())
+            });
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_build_queue);
+criterion_main!(benches);
@@ -0,0 +1,540 @@
+//! Sprite sheet import and frame animation.
+//!
+//! [`SpriteSheet::from_aseprite_json`] reads the JSON that Aseprite writes
+//! next to an exported sheet (array or hash layout): every frame becomes an
+//! atlas region and every tag becomes an [`AnimationClip`] with Aseprite's
+//! per-frame durations and play direction. Plain horizontal strips with
+//! equal frames use [`SpriteSheet::from_strip`]. Clips are sampled by
+//! elapsed time and drawn as `DrawSprite` commands with a source rect.
+
+use alloc::collections::BTreeMap;
+
+use glam::Vec2;
+use jugar_core::{Position, Rect};
+use serde::{Deserialize, Serialize};
+
+use crate::{RenderCommand, RenderError, Result, SpriteParams, TextureFilter};
+
+/// Clip name used when a sheet has no tags
+pub const DEFAULT_CLIP: &str = "default";
+
+/// Frame duration used when the export doesn't give one (Aseprite's default)
+const DEFAULT_FRAME_MS: u32 = 100;
+
+/// Order in which a clip's frames play
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PlayDirection {
+    /// First to last
+    #[default]
+    Forward,
+    /// Last to first
+    Reverse,
+    /// First to last and back, without repeating the end frames
+    PingPong,
+}
+
+impl PlayDirection {
+    /// Direction from an Aseprite tag (`forward`, `reverse`, `pingpong`)
+    fn from_aseprite(direction: &str) -> Self {
+        match direction {
+            "reverse" => Self::Reverse,
+            "pingpong" | "pingpong_reverse" => Self::PingPong,
+            _ => Self::Forward,
+        }
+    }
+}
+
+/// One frame of a clip
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AnimationFrame {
+    /// Source rectangle in the sheet image, in pixels
+    pub region: Rect,
+    /// How long the frame shows
+    pub duration_ms: u32,
+}
+
+/// A named sequence of sheet frames
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnimationClip {
+    /// Clip name (the Aseprite tag)
+    pub name: String,
+    /// Frames in sheet order
+    pub frames: Vec<AnimationFrame>,
+    /// Play order
+    pub direction: PlayDirection,
+    /// Whether the clip repeats; otherwise it holds its last frame
+    pub looping: bool,
+}
+
+impl AnimationClip {
+    /// Creates a looping, forward clip
+    #[must_use]
+    pub fn new(name: impl Into<String>, frames: Vec<AnimationFrame>) -> Self {
+        Self {
+            name: name.into(),
+            frames,
+            direction: PlayDirection::Forward,
+            looping: true,
+        }
+    }
+
+    /// Sets the play direction
+    #[must_use]
+    pub const fn with_direction(mut self, direction: PlayDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Plays once and holds the last frame
+    #[must_use]
+    pub const fn once(mut self) -> Self {
+        self.looping = false;
+        self
+    }
+
+    /// Frame indices of one cycle in play order
+    fn sequence(&self) -> Vec<usize> {
+        let count = self.frames.len();
+        match self.direction {
+            PlayDirection::Forward => (0..count).collect(),
+            PlayDirection::Reverse => (0..count).rev().collect(),
+            PlayDirection::PingPong => (0..count)
+                .chain((1..count.saturating_sub(1)).rev())
+                .collect(),
+        }
+    }
+
+    /// Length of one cycle
+    #[must_use]
+    pub fn cycle_ms(&self) -> u64 {
+        self.sequence()
+            .into_iter()
+            .map(|i| u64::from(self.frames[i].duration_ms))
+            .sum()
+    }
+
+    /// Returns true once a non-looping clip has played through
+    #[must_use]
+    pub fn is_finished(&self, elapsed_ms: u64) -> bool {
+        !self.looping && elapsed_ms >= self.cycle_ms()
+    }
+
+    /// Index into [`frames`](Self::frames) showing after `elapsed_ms`
+    #[must_use]
+    pub fn frame_index_at(&self, elapsed_ms: u64) -> Option<usize> {
+        let sequence = self.sequence();
+        let cycle = self.cycle_ms();
+        if cycle == 0 {
+            return sequence.first().copied();
+        }
+        let mut time = if self.looping {
+            elapsed_ms % cycle
+        } else {
+            elapsed_ms.min(cycle - 1)
+        };
+        for index in sequence {
+            let duration = u64::from(self.frames[index].duration_ms);
+            if time < duration {
+                return Some(index);
+            }
+            time -= duration;
+        }
+        None
+    }
+
+    /// Frame showing after `elapsed_ms`
+    #[must_use]
+    pub fn frame_at(&self, elapsed_ms: u64) -> Option<&AnimationFrame> {
+        self.frame_index_at(elapsed_ms).map(|i| &self.frames[i])
+    }
+}
+
+/// Frame regions of one sheet image
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TextureAtlas {
+    /// Sheet image file name
+    pub image: String,
+    /// Sheet size in pixels
+    pub size: Vec2,
+    /// Frame rectangles in sheet order
+    pub regions: Vec<Rect>,
+}
+
+/// An imported sheet: atlas regions plus clips
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpriteSheet {
+    /// Frame regions
+    pub atlas: TextureAtlas,
+    clips: BTreeMap<String, AnimationClip>,
+}
+
+impl SpriteSheet {
+    /// Imports an Aseprite JSON export
+    ///
+    /// Each tag becomes a clip; without tags all frames form
+    /// [`DEFAULT_CLIP`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RenderError::InvalidSpriteSheet`] if the JSON is malformed,
+    /// has no frames, uses rotated frames, or a tag points past the last
+    /// frame.
+    pub fn from_aseprite_json(json: &str) -> Result<Self> {
+        let export: AsepriteExport =
+            serde_json::from_str(json).map_err(|e| invalid(&e.to_string()))?;
+        let frames = match export.frames {
+            AsepriteFrames::List(frames) => frames,
+            AsepriteFrames::Map(frames) => {
+                let mut named: Vec<(String, AsepriteFrame)> = frames.into_iter().collect();
+                named.sort_by_key(|(name, _)| (frame_number(name), name.clone()));
+                named.into_iter().map(|(_, frame)| frame).collect()
+            }
+        };
+        if frames.is_empty() {
+            return Err(invalid("sheet has no frames"));
+        }
+        if frames.iter().any(|frame| frame.rotated) {
+            return Err(invalid("rotated frames are not supported"));
+        }
+
+        let frames: Vec<AnimationFrame> = frames
+            .into_iter()
+            .map(|frame| AnimationFrame {
+                region: Rect::new(frame.frame.x, frame.frame.y, frame.frame.w, frame.frame.h),
+                duration_ms: frame.duration,
+            })
+            .collect();
+        let size = export.meta.size.map_or_else(
+            || {
+                frames.iter().fold(Vec2::ZERO, |size, frame| {
+                    size.max(Vec2::new(
+                        frame.region.x + frame.region.width,
+                        frame.region.y + frame.region.height,
+                    ))
+                })
+            },
+            |size| Vec2::new(size.w, size.h),
+        );
+
+        let mut clips = BTreeMap::new();
+        for tag in &export.meta.frame_tags {
+            if tag.from > tag.to || tag.to >= frames.len() {
+                return Err(invalid(&format!("tag '{}' is out of range", tag.name)));
+            }
+            let clip = AnimationClip::new(tag.name.clone(), frames[tag.from..=tag.to].to_vec())
+                .with_direction(PlayDirection::from_aseprite(&tag.direction));
+            let _ = clips.insert(tag.name.clone(), clip);
+        }
+        if clips.is_empty() {
+            let _ = clips.insert(
+                DEFAULT_CLIP.to_string(),
+                AnimationClip::new(DEFAULT_CLIP, frames.clone()),
+            );
+        }
+
+        Ok(Self {
+            atlas: TextureAtlas {
+                image: export.meta.image,
+                size,
+                regions: frames.iter().map(|frame| frame.region).collect(),
+            },
+            clips,
+        })
+    }
+
+    /// Imports a horizontal strip of equal frames as [`DEFAULT_CLIP`]
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RenderError::InvalidSpriteSheet`] if `frame_count` is zero
+    /// or doesn't divide the image width.
+    pub fn from_strip(
+        image: impl Into<String>,
+        width: u32,
+        height: u32,
+        frame_count: u32,
+        duration_ms: u32,
+    ) -> Result<Self> {
+        if frame_count == 0 || width % frame_count != 0 {
+            return Err(invalid("strip width must split into equal frames"));
+        }
+        let frame_width = (width / frame_count) as f32;
+        let frames: Vec<AnimationFrame> = (0..frame_count)
+            .map(|i| AnimationFrame {
+                region: Rect::new(i as f32 * frame_width, 0.0, frame_width, height as f32),
+                duration_ms,
+            })
+            .collect();
+
+        let mut clips = BTreeMap::new();
+        let _ = clips.insert(
+            DEFAULT_CLIP.to_string(),
+            AnimationClip::new(DEFAULT_CLIP, frames.clone()),
+        );
+        Ok(Self {
+            atlas: TextureAtlas {
+                image: image.into(),
+                size: Vec2::new(width as f32, height as f32),
+                regions: frames.iter().map(|frame| frame.region).collect(),
+            },
+            clips,
+        })
+    }
+
+    /// Looks up a clip
+    #[must_use]
+    pub fn clip(&self, name: &str) -> Option<&AnimationClip> {
+        self.clips.get(name)
+    }
+
+    /// Looks up a clip for editing (e.g. to make it play once)
+    pub fn clip_mut(&mut self, name: &str) -> Option<&mut AnimationClip> {
+        self.clips.get_mut(name)
+    }
+
+    /// Clip names, sorted
+    pub fn clip_names(&self) -> impl Iterator<Item = &str> {
+        self.clips.keys().map(String::as_str)
+    }
+
+    /// Draws the frame of `clip` showing after `elapsed_ms`
+    ///
+    /// Uses nearest-neighbor sampling so pixel art stays crisp.
+    #[must_use]
+    pub fn sprite_command(
+        &self,
+        clip: &str,
+        elapsed_ms: u64,
+        texture_id: u32,
+        position: Position,
+        size: Vec2,
+    ) -> Option<RenderCommand> {
+        let frame = self.clip(clip)?.frame_at(elapsed_ms)?;
+        Some(RenderCommand::DrawSprite {
+            texture_id,
+            position,
+            size,
+            source: Some(frame.region),
+            color: [1.0, 1.0, 1.0, 1.0],
+            filter: TextureFilter::Nearest,
+            params: SpriteParams::new(),
+        })
+    }
+}
+
+fn invalid(message: &str) -> RenderError {
+    RenderError::InvalidSpriteSheet(message.to_string())
+}
+
+/// Trailing frame number of a hash key like `hero 12.aseprite`
+fn frame_number(name: &str) -> Option<u32> {
+    let stem = name.rsplit_once('.').map_or(name, |(stem, _)| stem);
+    let digits = stem.len() - stem.trim_end_matches(|c: char| c.is_ascii_digit()).len();
+    stem[stem.len() - digits..].parse().ok()
+}
+
+#[derive(Debug, Deserialize)]
+struct AsepriteExport {
+    frames: AsepriteFrames,
+    #[serde(default)]
+    meta: AsepriteMeta,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum AsepriteFrames {
+    List(Vec<AsepriteFrame>),
+    Map(BTreeMap<String, AsepriteFrame>),
+}
+
+#[derive(Debug, Deserialize)]
+struct AsepriteFrame {
+    frame: AsepriteRect,
+    #[serde(default)]
+    rotated: bool,
+    #[serde(default = "default_duration")]
+    duration: u32,
+}
+
+const fn default_duration() -> u32 {
+    DEFAULT_FRAME_MS
+}
+
+#[derive(Debug, Deserialize)]
+struct AsepriteRect {
+    x: f32,
+    y: f32,
+    w: f32,
+    h: f32,
+}
+
+#[derive(Debug, Deserialize)]
+struct AsepriteSize {
+    w: f32,
+    h: f32,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct AsepriteMeta {
+    #[serde(default)]
+    image: String,
+    #[serde(default)]
+    size: Option<AsepriteSize>,
+    #[serde(default, rename = "frameTags")]
+    frame_tags: Vec<AsepriteTag>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AsepriteTag {
+    name: String,
+    from: usize,
+    to: usize,
+    #[serde(default)]
+    direction: String,
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::float_cmp)]
+mod tests {
+    use super::*;
+
+    const ARRAY_EXPORT: &str = r#"{
+      "frames": [
+        { "filename": "hero 0.aseprite", "frame": { "x": 0, "y": 0, "w": 16, "h": 16 }, "rotated": false, "duration": 100 },
+        { "filename": "hero 1.aseprite", "frame": { "x": 16, "y": 0, "w": 16, "h": 16 }, "rotated": false, "duration": 200 },
+        { "filename": "hero 2.aseprite", "frame": { "x": 32, "y": 0, "w": 16, "h": 16 }, "rotated": false, "duration": 100 },
+        { "filename": "hero 3.aseprite", "frame": { "x": 48, "y": 0, "w": 16, "h": 16 }, "rotated": false, "duration": 50 }
+      ],
+      "meta": {
+        "app": "https://www.aseprite.org/",
+        "image": "hero.png",
+        "size": { "w": 64, "h": 16 },
+        "frameTags": [
+          { "name": "idle", "from": 0, "to": 0, "direction": "forward" },
+          { "name": "walk", "from": 1, "to": 3, "direction": "pingpong" }
+        ]
+      }
+    }"#;
+
+    fn frames(durations: &[u32]) -> Vec<AnimationFrame> {
+        durations
+            .iter()
+            .enumerate()
+            .map(|(i, &duration_ms)| AnimationFrame {
+                region: Rect::new(i as f32, 0.0, 1.0, 1.0),
+                duration_ms,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_aseprite_tags_become_clips() {
+        let sheet = SpriteSheet::from_aseprite_json(ARRAY_EXPORT).unwrap();
+        assert_eq!(sheet.atlas.image, "hero.png");
+        assert_eq!(sheet.atlas.size, Vec2::new(64.0, 16.0));
+        assert_eq!(sheet.atlas.regions.len(), 4);
+        assert_eq!(sheet.clip_names().collect::<Vec<_>>(), vec!["idle", "walk"]);
+
+        let walk = sheet.clip("walk").unwrap();
+        assert_eq!(walk.direction, PlayDirection::PingPong);
+        assert_eq!(walk.frames[0].region.x, 16.0);
+        assert_eq!(walk.frames[0].duration_ms, 200);
+        // 200 + 100 + 50, then back through the middle frame (100)
+        assert_eq!(walk.cycle_ms(), 450);
+    }
+
+    #[test]
+    fn test_hash_layout_orders_frames_numerically() {
+        let entries: Vec<String> = (0..12)
+            .map(|i| {
+                format!(
+                    r#""hero {i}.aseprite": {{ "frame": {{ "x": {}, "y": 0, "w": 8, "h": 8 }} }}"#,
+                    i * 8
+                )
+            })
+            .collect();
+        let json = format!(r#"{{ "frames": {{ {} }} }}"#, entries.join(","));
+        let sheet = SpriteSheet::from_aseprite_json(&json).unwrap();
+
+        let xs: Vec<f32> = sheet.atlas.regions.iter().map(|r| r.x).collect();
+        assert_eq!(xs[2], 16.0);
+        assert_eq!(xs[11], 88.0);
+        assert_eq!(sheet.atlas.size, Vec2::new(96.0, 8.0));
+        let clip = sheet.clip(DEFAULT_CLIP).unwrap();
+        assert_eq!(clip.frames[0].duration_ms, DEFAULT_FRAME_MS);
+    }
+
+    #[test]
+    fn test_frame_durations_are_respected() {
+        let clip = AnimationClip::new("walk", frames(&[100, 200, 100]));
+        assert_eq!(clip.frame_index_at(0), Some(0));
+        assert_eq!(clip.frame_index_at(99), Some(0));
+        assert_eq!(clip.frame_index_at(100), Some(1));
+        assert_eq!(clip.frame_index_at(299), Some(1));
+        assert_eq!(clip.frame_index_at(300), Some(2));
+        assert_eq!(clip.frame_index_at(400), Some(0));
+    }
+
+    #[test]
+    fn test_directions_and_once() {
+        let pingpong =
+            AnimationClip::new("p", frames(&[10, 10, 10])).with_direction(PlayDirection::PingPong);
+        let order: Vec<_> = (0..5)
+            .map(|i| pingpong.frame_index_at(i * 10).unwrap())
+            .collect();
+        assert_eq!(order, vec![0, 1, 2, 1, 0]);
+
+        let reverse =
+            AnimationClip::new("r", frames(&[10, 10])).with_direction(PlayDirection::Reverse);
+        assert_eq!(reverse.frame_index_at(0), Some(1));
+
+        let once = AnimationClip::new("o", frames(&[10, 10])).once();
+        assert_eq!(once.frame_index_at(1000), Some(1));
+        assert!(once.is_finished(20));
+        assert!(!once.is_finished(19));
+    }
+
+    #[test]
+    fn test_strip_import() {
+        let sheet = SpriteSheet::from_strip("coin.png", 64, 16, 4, 80).unwrap();
+        let clip = sheet.clip(DEFAULT_CLIP).unwrap();
+        assert_eq!(clip.frames.len(), 4);
+        assert_eq!(clip.frames[3].region, Rect::new(48.0, 0.0, 16.0, 16.0));
+        assert!(SpriteSheet::from_strip("coin.png", 64, 16, 3, 80).is_err());
+        assert!(SpriteSheet::from_strip("coin.png", 64, 16, 0, 80).is_err());
+    }
+
+    #[test]
+    fn test_invalid_exports() {
+        let bad_tag = ARRAY_EXPORT.replace(r#""to": 3"#, r#""to": 9"#);
+        assert!(matches!(
+            SpriteSheet::from_aseprite_json(&bad_tag),
+            Err(RenderError::InvalidSpriteSheet(_))
+        ));
+        let rotated = ARRAY_EXPORT.replacen(r#""rotated": false"#, r#""rotated": true"#, 1);
+        assert!(SpriteSheet::from_aseprite_json(&rotated).is_err());
+        assert!(SpriteSheet::from_aseprite_json(r#"{ "frames": [] }"#).is_err());
+        assert!(SpriteSheet::from_aseprite_json("not json").is_err());
+    }
+
+    #[test]
+    fn test_sprite_command_uses_current_frame() {
+        let sheet = SpriteSheet::from_aseprite_json(ARRAY_EXPORT).unwrap();
+        let command = sheet
+            .sprite_command("walk", 250, 7, Position::new(0.0, 0.0), Vec2::splat(32.0))
+            .unwrap();
+        assert!(matches!(
+            command,
+            RenderCommand::DrawSprite {
+                texture_id: 7,
+                source: Some(region),
+                filter: TextureFilter::Nearest,
+                ..
+            } if region.x == 32.0
+        ));
+        assert!(sheet
+            .sprite_command("jump", 0, 7, Position::new(0.0, 0.0), Vec2::ONE)
+            .is_none());
+    }
+}
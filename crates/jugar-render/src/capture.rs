@@ -0,0 +1,272 @@
+//! Rolling clip recording.
+//!
+//! [`ClipRecorder`] keeps the last few seconds of gameplay as small frames
+//! so a kid can press "save clip" after something fun already happened.
+//! Frames come from the [`SoftwareRasterizer`](crate::SoftwareRasterizer)
+//! headlessly or from a canvas readback on the web, are downscaled on entry
+//! to keep memory low, and are encoded as an animated PNG that every
+//! browser plays.
+
+use alloc::collections::VecDeque;
+
+use crate::raster::png_chunk;
+use crate::Image;
+
+/// Default clip length (seconds)
+pub const DEFAULT_CLIP_SECONDS: f32 = 5.0;
+
+/// Default frames recorded per second
+pub const DEFAULT_CLIP_FPS: u32 = 10;
+
+/// Default widest stored frame (pixels)
+pub const DEFAULT_CLIP_WIDTH: u32 = 320;
+
+/// Keeps the most recent frames of gameplay for sharing
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClipRecorder {
+    fps: u32,
+    capacity: usize,
+    max_width: u32,
+    recording: bool,
+    since_capture: f32,
+    frames: VecDeque<Image>,
+}
+
+impl Default for ClipRecorder {
+    fn default() -> Self {
+        Self::new(DEFAULT_CLIP_SECONDS, DEFAULT_CLIP_FPS)
+    }
+}
+
+impl ClipRecorder {
+    /// Creates a stopped recorder keeping `seconds` of frames at `fps`
+    #[must_use]
+    pub fn new(seconds: f32, fps: u32) -> Self {
+        let fps = fps.clamp(1, 60);
+        let seconds = if seconds.is_finite() {
+            seconds.max(0.0)
+        } else {
+            DEFAULT_CLIP_SECONDS
+        };
+        Self {
+            fps,
+            capacity: ((seconds * fps as f32).ceil() as usize).max(1),
+            max_width: DEFAULT_CLIP_WIDTH,
+            recording: false,
+            since_capture: 0.0,
+            frames: VecDeque::new(),
+        }
+    }
+
+    /// Sets the widest stored frame; larger frames are downscaled
+    #[must_use]
+    pub const fn with_max_width(mut self, max_width: u32) -> Self {
+        self.max_width = max_width;
+        self
+    }
+
+    /// Widest stored frame (pixels)
+    #[must_use]
+    pub const fn max_width(&self) -> u32 {
+        self.max_width
+    }
+
+    /// Starts recording, keeping any frames already held
+    pub fn start(&mut self) {
+        self.recording = true;
+        self.since_capture = f32::INFINITY;
+    }
+
+    /// Stops recording; held frames can still be exported
+    pub fn stop(&mut self) {
+        self.recording = false;
+    }
+
+    /// Returns true while recording
+    #[must_use]
+    pub const fn is_recording(&self) -> bool {
+        self.recording
+    }
+
+    /// Advances time; returns true if a frame should be captured now
+    ///
+    /// Lets the host skip rasterizing or reading back frames the clip
+    /// won't keep.
+    pub fn tick(&mut self, dt: f32) -> bool {
+        if !self.recording {
+            return false;
+        }
+        if dt.is_finite() && dt > 0.0 {
+            self.since_capture += dt;
+        }
+        if self.since_capture >= self.frame_seconds() {
+            self.since_capture = 0.0;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Adds a frame, dropping the oldest once the clip is full
+    ///
+    /// A frame of a different size than the held ones (after a resize)
+    /// starts a new clip.
+    pub fn push(&mut self, frame: &Image) {
+        let frame = frame.downscaled(self.max_width);
+        if self
+            .frames
+            .front()
+            .is_some_and(|first| first.width() != frame.width() || first.height() != frame.height())
+        {
+            self.frames.clear();
+        }
+        if self.frames.len() >= self.capacity {
+            let _ = self.frames.pop_front();
+        }
+        self.frames.push_back(frame);
+    }
+
+    /// Number of held frames
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Returns true if no frames are held
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Length of the held clip (seconds)
+    #[must_use]
+    pub fn duration(&self) -> f32 {
+        self.frames.len() as f32 * self.frame_seconds()
+    }
+
+    /// Most recent frame
+    #[must_use]
+    pub fn latest(&self) -> Option<&Image> {
+        self.frames.back()
+    }
+
+    /// Drops every held frame
+    pub fn clear(&mut self) {
+        self.frames.clear();
+    }
+
+    fn frame_seconds(&self) -> f32 {
+        1.0 / self.fps as f32
+    }
+
+    /// Encodes the held frames as a looping animated PNG
+    ///
+    /// Returns `None` if no frames are held.
+    #[must_use]
+    pub fn to_apng(&self) -> Option<Vec<u8>> {
+        let first = self.frames.front()?;
+        let mut png = first.png_start();
+
+        let mut control = Vec::with_capacity(8);
+        control.extend_from_slice(&(self.frames.len() as u32).to_be_bytes());
+        control.extend_from_slice(&0_u32.to_be_bytes()); // loop forever
+        png_chunk(&mut png, *b"acTL", &control);
+
+        let delay = u16::try_from(self.fps).unwrap_or(u16::MAX);
+        let mut sequence = 0_u32;
+        for (index, frame) in self.frames.iter().enumerate() {
+            let mut frame_control = Vec::with_capacity(26);
+            frame_control.extend_from_slice(&sequence.to_be_bytes());
+            frame_control.extend_from_slice(&frame.width().to_be_bytes());
+            frame_control.extend_from_slice(&frame.height().to_be_bytes());
+            frame_control.extend_from_slice(&[0; 8]); // x and y offsets
+            frame_control.extend_from_slice(&1_u16.to_be_bytes());
+            frame_control.extend_from_slice(&delay.to_be_bytes()); // 1/fps seconds
+            frame_control.extend_from_slice(&[0, 0]); // no dispose, replace
+            png_chunk(&mut png, *b"fcTL", &frame_control);
+            sequence += 1;
+
+            if index == 0 {
+                png_chunk(&mut png, *b"IDAT", &frame.zlib());
+            } else {
+                let mut data = sequence.to_be_bytes().to_vec();
+                data.extend(frame.zlib());
+                png_chunk(&mut png, *b"fdAT", &data);
+                sequence += 1;
+            }
+        }
+        png_chunk(&mut png, *b"IEND", &[]);
+        Some(png)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn solid(width: u32, height: u32, value: u8) -> Image {
+        Image::from_rgba(
+            width,
+            height,
+            vec![value; width as usize * height as usize * 4],
+        )
+        .unwrap()
+    }
+
+    /// Chunk types in file order
+    fn chunks(png: &[u8]) -> Vec<String> {
+        let mut kinds = Vec::new();
+        let mut i = 8;
+        while i + 8 <= png.len() {
+            let length = u32::from_be_bytes(png[i..i + 4].try_into().unwrap()) as usize;
+            kinds.push(String::from_utf8_lossy(&png[i + 4..i + 8]).into_owned());
+            i += 12 + length;
+        }
+        kinds
+    }
+
+    #[test]
+    fn test_tick_paces_captures() {
+        let mut recorder = ClipRecorder::new(1.0, 10);
+        assert!(!recorder.tick(1.0));
+        recorder.start();
+        assert!(recorder.tick(0.016));
+        let captured = (0..60).filter(|_| recorder.tick(1.0 / 60.0)).count();
+        assert!((9..=11).contains(&captured));
+    }
+
+    #[test]
+    fn test_keeps_last_seconds_downscaled() {
+        let mut recorder = ClipRecorder::new(0.5, 4).with_max_width(8);
+        for value in 0..5 {
+            recorder.push(&solid(16, 8, value));
+        }
+        assert_eq!(recorder.len(), 2);
+        assert!((recorder.duration() - 0.5).abs() < f32::EPSILON);
+        let latest = recorder.latest().unwrap();
+        assert_eq!((latest.width(), latest.height()), (8, 4));
+        assert_eq!(latest.pixel(0, 0), Some([4; 4]));
+
+        recorder.push(&solid(4, 4, 9));
+        assert_eq!(recorder.len(), 1);
+    }
+
+    #[test]
+    fn test_apng_layout() {
+        let mut recorder = ClipRecorder::default();
+        assert!(recorder.to_apng().is_none());
+        for value in 0..3 {
+            recorder.push(&solid(4, 4, value));
+        }
+        let apng = recorder.to_apng().unwrap();
+        assert_eq!(
+            &apng[..8],
+            &[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A]
+        );
+        assert_eq!(
+            chunks(&apng),
+            ["IHDR", "acTL", "fcTL", "IDAT", "fcTL", "fdAT", "fcTL", "fdAT", "IEND"]
+        );
+    }
+}
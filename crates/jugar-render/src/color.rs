@@ -0,0 +1,699 @@
+//! Color type, conversions and kid-friendly palettes.
+//!
+//! [`Color`] stores straight (non-premultiplied) sRGB components in 0-1 and
+//! converts to and from the `[f32; 4]` arrays used by render commands.
+//! [`Palette`] maps names to colors; the built-in palettes use the same
+//! color and background words as the jugar-yaml vocabulary, so a game's
+//! `color: purple` or `background: sky` can be looked up directly.
+//! [`Palette::colors_for`] and [`Palette::backgrounds_for`] swap in the
+//! high-contrast palettes when [`AccessibilitySettings::high_contrast`] is on,
+//! and [`Palette::colorblind_safe`] when a [`ColorVision`] is selected.
+
+use jugar_core::{AccessibilitySettings, ColorVision};
+use serde::{Deserialize, Serialize};
+
+/// An RGBA color in sRGB space (components 0-1)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Color {
+    /// Red component (0.0 to 1.0)
+    pub r: f32,
+    /// Green component (0.0 to 1.0)
+    pub g: f32,
+    /// Blue component (0.0 to 1.0)
+    pub b: f32,
+    /// Alpha component (0.0 to 1.0)
+    pub a: f32,
+}
+
+impl Color {
+    /// Black
+    pub const BLACK: Self = Self::new(0.0, 0.0, 0.0, 1.0);
+    /// White
+    pub const WHITE: Self = Self::new(1.0, 1.0, 1.0, 1.0);
+    /// Fully transparent black
+    pub const TRANSPARENT: Self = Self::new(0.0, 0.0, 0.0, 0.0);
+    /// Pure red
+    pub const RED: Self = Self::new(1.0, 0.0, 0.0, 1.0);
+    /// Pure green
+    pub const GREEN: Self = Self::new(0.0, 1.0, 0.0, 1.0);
+    /// Pure blue
+    pub const BLUE: Self = Self::new(0.0, 0.0, 1.0, 1.0);
+
+    /// Creates a color from RGBA components
+    #[must_use]
+    pub const fn new(r: f32, g: f32, b: f32, a: f32) -> Self {
+        Self { r, g, b, a }
+    }
+
+    /// Creates an opaque color from RGB components
+    #[must_use]
+    pub const fn rgb(r: f32, g: f32, b: f32) -> Self {
+        Self::new(r, g, b, 1.0)
+    }
+
+    /// Creates a color from an RGBA array
+    #[must_use]
+    pub const fn from_array(rgba: [f32; 4]) -> Self {
+        Self::new(rgba[0], rgba[1], rgba[2], rgba[3])
+    }
+
+    /// Converts to an RGBA array
+    #[must_use]
+    pub const fn to_array(self) -> [f32; 4] {
+        [self.r, self.g, self.b, self.a]
+    }
+
+    /// Returns the same color with a different alpha
+    #[must_use]
+    pub const fn with_alpha(mut self, a: f32) -> Self {
+        self.a = a;
+        self
+    }
+
+    /// Parses `#rgb`, `#rrggbb` or `#rrggbbaa` (the `#` is optional)
+    #[must_use]
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        let hex = hex.strip_prefix('#').unwrap_or(hex);
+        let channel = |i: usize, width: usize| {
+            let value = u8::from_str_radix(hex.get(i * width..(i + 1) * width)?, 16).ok()?;
+            // Short form repeats each digit (#f80 == #ff8800)
+            Some(if width == 1 { value * 17 } else { value })
+        };
+        let (width, count) = match hex.len() {
+            3 => (1, 3),
+            6 => (2, 3),
+            8 => (2, 4),
+            _ => return None,
+        };
+        let mut rgba = [255_u8; 4];
+        for (i, slot) in rgba.iter_mut().take(count).enumerate() {
+            *slot = channel(i, width)?;
+        }
+        Some(Self::from_rgba8(rgba))
+    }
+
+    /// Creates a color from 8-bit RGBA components
+    #[must_use]
+    pub fn from_rgba8(rgba: [u8; 4]) -> Self {
+        let [r, g, b, a] = rgba.map(|c| f32::from(c) / 255.0);
+        Self::new(r, g, b, a)
+    }
+
+    /// Converts to 8-bit RGBA components (rounded, clamped)
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn to_rgba8(self) -> [u8; 4] {
+        self.to_array()
+            .map(|c| c.clamp(0.0, 1.0).mul_add(255.0, 0.5) as u8)
+    }
+
+    /// Formats as `#rrggbbaa`
+    #[must_use]
+    pub fn to_hex(self) -> String {
+        let [r, g, b, a] = self.to_rgba8();
+        format!("#{r:02x}{g:02x}{b:02x}{a:02x}")
+    }
+
+    /// Converts to a CSS `rgba()` string
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn to_css_rgba(self) -> String {
+        format!(
+            "rgba({}, {}, {}, {})",
+            (self.r * 255.0) as u8,
+            (self.g * 255.0) as u8,
+            (self.b * 255.0) as u8,
+            self.a
+        )
+    }
+
+    /// Creates an opaque color from hue (degrees), saturation and value
+    #[must_use]
+    pub fn from_hsv(hue: f32, saturation: f32, value: f32) -> Self {
+        let sector = hue.rem_euclid(360.0) / 60.0;
+        let chroma = value * saturation;
+        let mid = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+        let low = value - chroma;
+        let (r, g, b) = match sector as u32 {
+            0 => (chroma, mid, 0.0),
+            1 => (mid, chroma, 0.0),
+            2 => (0.0, chroma, mid),
+            3 => (0.0, mid, chroma),
+            4 => (mid, 0.0, chroma),
+            _ => (chroma, 0.0, mid),
+        };
+        Self::rgb(r + low, g + low, b + low)
+    }
+
+    /// Converts to (hue degrees, saturation, value)
+    #[must_use]
+    pub fn to_hsv(self) -> (f32, f32, f32) {
+        let max = self.r.max(self.g).max(self.b);
+        let min = self.r.min(self.g).min(self.b);
+        let delta = max - min;
+        let hue = if delta <= f32::EPSILON {
+            0.0
+        } else if max <= self.r {
+            60.0 * ((self.g - self.b) / delta).rem_euclid(6.0)
+        } else if max <= self.g {
+            60.0 * ((self.b - self.r) / delta + 2.0)
+        } else {
+            60.0 * ((self.r - self.g) / delta + 4.0)
+        };
+        let saturation = if max <= 0.0 { 0.0 } else { delta / max };
+        (hue, saturation, max)
+    }
+
+    /// Linear interpolation between two colors (in sRGB space)
+    #[must_use]
+    pub fn lerp(self, other: Self, t: f32) -> Self {
+        let mix = |a: f32, b: f32| (b - a).mul_add(t, a);
+        Self::new(
+            mix(self.r, other.r),
+            mix(self.g, other.g),
+            mix(self.b, other.b),
+            mix(self.a, other.a),
+        )
+    }
+
+    /// Multiplies RGB by alpha (for premultiplied blending)
+    #[must_use]
+    pub fn premultiplied(self) -> Self {
+        Self::new(self.r * self.a, self.g * self.a, self.b * self.a, self.a)
+    }
+
+    /// Converts sRGB components to linear light (alpha unchanged)
+    #[must_use]
+    pub fn to_linear(self) -> Self {
+        Self::new(
+            srgb_to_linear(self.r),
+            srgb_to_linear(self.g),
+            srgb_to_linear(self.b),
+            self.a,
+        )
+    }
+
+    /// Converts linear light components back to sRGB (alpha unchanged)
+    #[must_use]
+    pub fn from_linear(linear: Self) -> Self {
+        Self::new(
+            linear_to_srgb(linear.r),
+            linear_to_srgb(linear.g),
+            linear_to_srgb(linear.b),
+            linear.a,
+        )
+    }
+
+    /// WCAG relative luminance (0 for black, 1 for white)
+    #[must_use]
+    pub fn relative_luminance(self) -> f32 {
+        let linear = self.to_linear();
+        0.0722_f32.mul_add(linear.b, 0.2126_f32.mul_add(linear.r, 0.7152 * linear.g))
+    }
+
+    /// WCAG contrast ratio between two colors (1 to 21)
+    #[must_use]
+    pub fn contrast_ratio(self, other: Self) -> f32 {
+        let a = self.relative_luminance();
+        let b = other.relative_luminance();
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
+
+    /// Approximates how the color looks with a color vision deficiency
+    ///
+    /// Uses the full-severity Machado et al. (2009) matrices in linear RGB.
+    #[must_use]
+    pub fn as_seen_with(self, vision: ColorVision) -> Self {
+        let m: [[f32; 3]; 3] = match vision {
+            ColorVision::Typical => return self,
+            ColorVision::Deuteranopia => [
+                [0.367_322, 0.860_646, -0.227_968],
+                [0.280_085, 0.672_501, 0.047_413],
+                [-0.011_820, 0.042_940, 0.968_881],
+            ],
+            ColorVision::Protanopia => [
+                [0.152_286, 1.052_583, -0.204_868],
+                [0.114_503, 0.786_281, 0.099_216],
+                [-0.003_882, -0.048_116, 1.051_998],
+            ],
+            ColorVision::Tritanopia => [
+                [1.255_528, -0.076_749, -0.178_779],
+                [-0.078_411, 0.930_809, 0.147_602],
+                [0.004_733, 0.691_367, 0.303_900],
+            ],
+        };
+        let linear = self.to_linear();
+        let row = |r: [f32; 3]| {
+            r[2].mul_add(linear.b, r[0].mul_add(linear.r, r[1] * linear.g))
+                .clamp(0.0, 1.0)
+        };
+        Self::from_linear(Self::new(row(m[0]), row(m[1]), row(m[2]), self.a))
+    }
+
+    /// Looks up a vocabulary color name in the default kid palette
+    #[must_use]
+    pub fn named(name: &str) -> Option<Self> {
+        Palette::crayons().get(name)
+    }
+}
+
+impl Default for Color {
+    fn default() -> Self {
+        Self::BLACK
+    }
+}
+
+impl From<[f32; 4]> for Color {
+    fn from(rgba: [f32; 4]) -> Self {
+        Self::from_array(rgba)
+    }
+}
+
+impl From<Color> for [f32; 4] {
+    fn from(color: Color) -> Self {
+        color.to_array()
+    }
+}
+
+/// sRGB transfer function: encoded component to linear
+#[must_use]
+pub fn srgb_to_linear(c: f32) -> f32 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Inverse sRGB transfer function: linear component to encoded
+#[must_use]
+pub fn linear_to_srgb(c: f32) -> f32 {
+    if c <= 0.003_130_8 {
+        c * 12.92
+    } else {
+        1.055_f32.mul_add(c.powf(1.0 / 2.4), -0.055)
+    }
+}
+
+/// A named set of colors
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Palette {
+    /// Palette name
+    pub name: String,
+    colors: Vec<(String, Color)>,
+}
+
+impl Palette {
+    /// Creates an empty palette
+    #[must_use]
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            colors: Vec::new(),
+        }
+    }
+
+    /// Adds (or replaces) a named color
+    #[must_use]
+    pub fn with(mut self, name: &str, color: Color) -> Self {
+        self.set(name, color);
+        self
+    }
+
+    /// Adds (or replaces) a named color
+    pub fn set(&mut self, name: &str, color: Color) {
+        if let Some(index) = self.colors.iter().position(|(n, _)| n == name) {
+            self.colors[index].1 = color;
+            return;
+        }
+        self.colors.push((name.to_string(), color));
+    }
+
+    /// Looks up a color by name (case-insensitive)
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<Color> {
+        self.colors
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|&(_, c)| c)
+    }
+
+    /// Color names in insertion order
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.colors.iter().map(|(n, _)| n.as_str())
+    }
+
+    /// Number of colors
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.colors.len()
+    }
+
+    /// Returns true if the palette has no colors
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.colors.is_empty()
+    }
+
+    /// Bright, high-contrast colors for the vocabulary color words
+    #[must_use]
+    pub fn crayons() -> Self {
+        Self::from_hex_table(
+            "crayons",
+            &[
+                ("red", "#e63946"),
+                ("blue", "#1d7fe0"),
+                ("green", "#2bb24c"),
+                ("yellow", "#ffd23f"),
+                ("orange", "#ff8c1a"),
+                ("purple", "#8e44ad"),
+                ("pink", "#ff6fb5"),
+                ("white", "#ffffff"),
+                ("black", "#1b1b1f"),
+            ],
+        )
+    }
+
+    /// Soft colors for the same vocabulary words
+    #[must_use]
+    pub fn pastel() -> Self {
+        Self::from_hex_table(
+            "pastel",
+            &[
+                ("red", "#ff9aa2"),
+                ("blue", "#a0c4ff"),
+                ("green", "#b9fbc0"),
+                ("yellow", "#fdffb6"),
+                ("orange", "#ffd6a5"),
+                ("purple", "#cdb4db"),
+                ("pink", "#ffc8dd"),
+                ("white", "#fffdf7"),
+                ("black", "#4a4e69"),
+            ],
+        )
+    }
+
+    /// Clear colors for the vocabulary background words
+    #[must_use]
+    pub fn backgrounds() -> Self {
+        Self::from_hex_table(
+            "backgrounds",
+            &[
+                ("sky", "#87ceeb"),
+                ("grass", "#7ec850"),
+                ("water", "#3a8dde"),
+                ("space", "#0b0d2a"),
+                ("forest", "#2e6b3a"),
+                ("beach", "#f4d58d"),
+                ("snow", "#f2f7ff"),
+                ("rainbow", "#ffb3de"),
+            ],
+        )
+    }
+
+    /// Vivid colors that stay readable on every high-contrast background
+    #[must_use]
+    pub fn high_contrast() -> Self {
+        Self::from_hex_table(
+            "high-contrast",
+            &[
+                ("red", "#ff5c5c"),
+                ("blue", "#5cb8ff"),
+                ("green", "#3dff6e"),
+                ("yellow", "#ffff00"),
+                ("orange", "#ffa31a"),
+                ("purple", "#d98cff"),
+                ("pink", "#ff85d6"),
+                ("white", "#ffffff"),
+                ("black", "#000000"),
+            ],
+        )
+    }
+
+    /// Near-black tints of the background words, keeping each one recognizable
+    #[must_use]
+    pub fn high_contrast_backgrounds() -> Self {
+        Self::from_hex_table(
+            "high-contrast-backgrounds",
+            &[
+                ("sky", "#00213d"),
+                ("grass", "#002910"),
+                ("water", "#001a40"),
+                ("space", "#000000"),
+                ("forest", "#001a0a"),
+                ("beach", "#241a00"),
+                ("snow", "#101418"),
+                ("rainbow", "#24001a"),
+            ],
+        )
+    }
+
+    /// Color words that stay distinguishable with a color vision deficiency
+    ///
+    /// Deuteranopia uses the Okabe-Ito set; protanopia brightens its reds
+    /// and oranges, which protans see darker; tritanopia avoids blue-yellow
+    /// pairs. Typical vision gets [`Palette::crayons`].
+    #[must_use]
+    pub fn colorblind_safe(vision: ColorVision) -> Self {
+        match vision {
+            ColorVision::Typical => Self::crayons(),
+            ColorVision::Deuteranopia => Self::from_hex_table(
+                "deuteranopia",
+                &[
+                    ("red", "#d55e00"),
+                    ("blue", "#0072b2"),
+                    ("green", "#009e73"),
+                    ("yellow", "#f0e442"),
+                    ("orange", "#e69f00"),
+                    ("purple", "#882e72"),
+                    ("pink", "#f5c1dc"),
+                    ("white", "#ffffff"),
+                    ("black", "#000000"),
+                ],
+            ),
+            ColorVision::Protanopia => Self::from_hex_table(
+                "protanopia",
+                &[
+                    ("red", "#f0641e"),
+                    ("blue", "#0072b2"),
+                    ("green", "#009e73"),
+                    ("yellow", "#f0e442"),
+                    ("orange", "#ffb000"),
+                    ("purple", "#882e72"),
+                    ("pink", "#f5c1dc"),
+                    ("white", "#ffffff"),
+                    ("black", "#000000"),
+                ],
+            ),
+            ColorVision::Tritanopia => Self::from_hex_table(
+                "tritanopia",
+                &[
+                    ("red", "#d7191c"),
+                    ("blue", "#1f4e9e"),
+                    ("green", "#1a9e8f"),
+                    ("yellow", "#ffe08a"),
+                    ("orange", "#f07d3c"),
+                    ("purple", "#7b3294"),
+                    ("pink", "#f2a1c7"),
+                    ("white", "#ffffff"),
+                    ("black", "#000000"),
+                ],
+            ),
+        }
+    }
+
+    /// Color words palette for the player's settings
+    ///
+    /// A selected color vision takes precedence over high contrast.
+    #[must_use]
+    pub fn colors_for(settings: &AccessibilitySettings) -> Self {
+        if settings.color_vision.needs_safe_palette() {
+            Self::colorblind_safe(settings.color_vision)
+        } else if settings.high_contrast {
+            Self::high_contrast()
+        } else {
+            Self::crayons()
+        }
+    }
+
+    /// Background words palette for the player's settings
+    #[must_use]
+    pub fn backgrounds_for(settings: &AccessibilitySettings) -> Self {
+        if settings.high_contrast {
+            Self::high_contrast_backgrounds()
+        } else {
+            Self::backgrounds()
+        }
+    }
+
+    fn from_hex_table(name: &str, table: &[(&str, &str)]) -> Self {
+        let mut palette = Self::new(name);
+        for &(color_name, hex) in table {
+            if let Some(color) = Color::from_hex(hex) {
+                palette.set(color_name, color);
+            }
+        }
+        palette
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn close(a: Color, b: Color) -> bool {
+        (a.r - b.r).abs() < 0.01
+            && (a.g - b.g).abs() < 0.01
+            && (a.b - b.b).abs() < 0.01
+            && (a.a - b.a).abs() < 0.01
+    }
+
+    #[test]
+    fn test_from_hex_forms() {
+        assert_eq!(Color::from_hex("#ff0000"), Some(Color::RED));
+        assert_eq!(Color::from_hex("00f"), Some(Color::BLUE));
+        let translucent = Color::from_hex("#ffffff80").unwrap();
+        assert!((translucent.a - 128.0 / 255.0).abs() < 0.001);
+        assert!(Color::from_hex("#12345").is_none());
+        assert!(Color::from_hex("#gg0000").is_none());
+    }
+
+    #[test]
+    fn test_hex_roundtrip() {
+        let color = Color::from_hex("#1d7fe0").unwrap();
+        assert_eq!(color.to_hex(), "#1d7fe0ff");
+    }
+
+    #[test]
+    fn test_hsv_roundtrip() {
+        let orange = Color::from_hsv(30.0, 1.0, 1.0);
+        assert!(close(orange, Color::rgb(1.0, 0.5, 0.0)));
+        let (h, s, v) = orange.to_hsv();
+        assert!((h - 30.0).abs() < 0.5);
+        assert!((s - 1.0).abs() < 0.001);
+        assert!((v - 1.0).abs() < 0.001);
+        assert!(close(Color::from_hsv(-120.0, 1.0, 1.0), Color::BLUE));
+    }
+
+    #[test]
+    fn test_linear_roundtrip() {
+        let color = Color::rgb(0.2, 0.5, 0.8);
+        assert!(close(Color::from_linear(color.to_linear()), color));
+        assert!(color.to_linear().r < color.r);
+    }
+
+    #[test]
+    fn test_lerp_and_premultiply() {
+        let mid = Color::BLACK.lerp(Color::WHITE, 0.5);
+        assert!(close(mid, Color::rgb(0.5, 0.5, 0.5)));
+        let pre = Color::new(1.0, 0.5, 0.0, 0.5).premultiplied();
+        assert!(close(pre, Color::new(0.5, 0.25, 0.0, 0.5)));
+    }
+
+    #[test]
+    fn test_vocabulary_palettes() {
+        for word in [
+            "red", "blue", "green", "yellow", "orange", "purple", "pink", "white", "black",
+        ] {
+            assert!(
+                Palette::crayons().get(word).is_some(),
+                "crayons missing {word}"
+            );
+            assert!(
+                Palette::pastel().get(word).is_some(),
+                "pastel missing {word}"
+            );
+        }
+        assert_eq!(Palette::backgrounds().len(), 8);
+        assert_eq!(Color::named("Purple"), Palette::crayons().get("purple"));
+        assert!(Color::named("plaid").is_none());
+    }
+
+    #[test]
+    fn test_contrast_ratio() {
+        assert!((Color::BLACK.contrast_ratio(Color::WHITE) - 21.0).abs() < 0.01);
+        assert!((Color::WHITE.contrast_ratio(Color::WHITE) - 1.0).abs() < 0.001);
+        assert!(Color::BLACK.relative_luminance().abs() < 0.001);
+    }
+
+    #[test]
+    fn test_high_contrast_palettes_meet_wcag_aa() {
+        let colors = Palette::high_contrast();
+        let backgrounds = Palette::high_contrast_backgrounds();
+        assert_eq!(
+            colors.names().collect::<Vec<_>>(),
+            Palette::crayons().names().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            backgrounds.names().collect::<Vec<_>>(),
+            Palette::backgrounds().names().collect::<Vec<_>>()
+        );
+        for name in colors.names().filter(|name| *name != "black") {
+            let color = colors.get(name).unwrap();
+            for background in backgrounds.names() {
+                let ratio = color.contrast_ratio(backgrounds.get(background).unwrap());
+                assert!(ratio >= 4.5, "{name} on {background}: {ratio}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_palettes_follow_settings() {
+        let settings = AccessibilitySettings::new().with_high_contrast(true);
+        assert_eq!(Palette::colors_for(&settings).name, "high-contrast");
+        assert_eq!(
+            Palette::backgrounds_for(&AccessibilitySettings::new()).name,
+            "backgrounds"
+        );
+    }
+
+    fn closest_pair(palette: &Palette, vision: ColorVision) -> f32 {
+        let seen: Vec<Color> = palette
+            .names()
+            .map(|name| palette.get(name).unwrap().as_seen_with(vision))
+            .collect();
+        let mut closest = f32::MAX;
+        for (i, a) in seen.iter().enumerate() {
+            for b in &seen[i + 1..] {
+                let (dr, dg, db) = (a.r - b.r, a.g - b.g, a.b - b.b);
+                closest = closest.min(dr.mul_add(dr, dg.mul_add(dg, db * db)).sqrt());
+            }
+        }
+        closest
+    }
+
+    #[test]
+    fn test_colorblind_palettes_stay_distinguishable() {
+        for vision in ColorVision::ALL {
+            let palette = Palette::colorblind_safe(vision);
+            assert_eq!(
+                palette.names().collect::<Vec<_>>(),
+                Palette::crayons().names().collect::<Vec<_>>()
+            );
+            if vision.needs_safe_palette() {
+                let safe = closest_pair(&palette, vision);
+                assert!(safe > 0.2, "{vision:?}: {safe}");
+                assert!(safe > closest_pair(&Palette::crayons(), vision));
+            }
+        }
+    }
+
+    #[test]
+    fn test_simulation_keeps_grays() {
+        let gray = Color::rgb(0.5, 0.5, 0.5);
+        for vision in ColorVision::ALL {
+            assert!(close(gray.as_seen_with(vision), gray), "{vision:?}");
+        }
+        let seen = Color::RED.as_seen_with(ColorVision::Deuteranopia);
+        assert!((seen.r - seen.g).abs() < 0.3);
+    }
+
+    #[test]
+    fn test_color_vision_beats_high_contrast() {
+        let settings = AccessibilitySettings::new()
+            .with_high_contrast(true)
+            .with_color_vision(ColorVision::Protanopia);
+        assert_eq!(Palette::colors_for(&settings).name, "protanopia");
+    }
+}
@@ -0,0 +1,241 @@
+//! Letterbox/pillarbox compositing.
+//!
+//! The [`Viewport`] safe area is the 16:9 region gameplay is designed for.
+//! [`Compositor`] fills everything outside it with a [`BorderFill`], clips
+//! world commands to the safe area with a scissor, and then draws UI
+//! commands unclipped so HUDs can use the full width of ultrawide screens.
+
+use serde::{Deserialize, Serialize};
+
+use jugar_core::Rect;
+
+use crate::{RenderCommand, Viewport};
+
+/// Smallest stripe or checker cell size, to bound the command count
+const MIN_PATTERN_SIZE: f32 = 8.0;
+
+/// How the area outside the safe area is filled
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum BorderFill {
+    /// Leave the bars alone (whatever the world drew stays visible)
+    None,
+    /// Solid color
+    Color([f32; 4]),
+    /// Alternating stripes across each bar
+    Stripes {
+        /// First stripe color
+        a: [f32; 4],
+        /// Second stripe color
+        b: [f32; 4],
+        /// Stripe width in pixels
+        width: f32,
+    },
+    /// Checkerboard
+    Checker {
+        /// First cell color
+        a: [f32; 4],
+        /// Second cell color
+        b: [f32; 4],
+        /// Cell size in pixels
+        size: f32,
+    },
+}
+
+impl Default for BorderFill {
+    fn default() -> Self {
+        Self::Color([0.0, 0.0, 0.0, 1.0])
+    }
+}
+
+impl BorderFill {
+    /// Rectangles that paint this fill over `bar`
+    fn commands(&self, bar: Rect) -> Vec<RenderCommand> {
+        match *self {
+            Self::None => Vec::new(),
+            Self::Color(color) => vec![RenderCommand::DrawRect { rect: bar, color }],
+            Self::Stripes { a, b, width } => {
+                let width = width.max(MIN_PATTERN_SIZE);
+                // Stripes run across the bar's long axis
+                let vertical = bar.height >= bar.width;
+                let length = if vertical { bar.height } else { bar.width };
+                (0..cells(length, width))
+                    .map(|index| {
+                        let offset = index as f32 * width;
+                        let span = width.min(length - offset);
+                        let rect = if vertical {
+                            Rect::new(bar.x, bar.y + offset, bar.width, span)
+                        } else {
+                            Rect::new(bar.x + offset, bar.y, span, bar.height)
+                        };
+                        let color = if index % 2 == 0 { a } else { b };
+                        RenderCommand::DrawRect { rect, color }
+                    })
+                    .collect()
+            }
+            Self::Checker { a, b, size } => {
+                let size = size.max(MIN_PATTERN_SIZE);
+                let mut commands = vec![RenderCommand::DrawRect {
+                    rect: bar,
+                    color: a,
+                }];
+                for row in 0..cells(bar.height, size) {
+                    for col in (0..cells(bar.width, size)).filter(|col| (row + col) % 2 == 1) {
+                        let (x, y) = (col as f32 * size, row as f32 * size);
+                        commands.push(RenderCommand::DrawRect {
+                            rect: Rect::new(
+                                bar.x + x,
+                                bar.y + y,
+                                size.min(bar.width - x),
+                                size.min(bar.height - y),
+                            ),
+                            color: b,
+                        });
+                    }
+                }
+                commands
+            }
+        }
+    }
+}
+
+/// Number of `size` cells needed to cover `length`
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn cells(length: f32, size: f32) -> usize {
+    (length / size).ceil().max(0.0) as usize
+}
+
+/// Composites world and UI layers around the viewport safe area
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Compositor {
+    /// Fill for the bars outside the safe area
+    pub border: BorderFill,
+    /// Clip world rendering to the safe area
+    pub clip_world: bool,
+}
+
+impl Compositor {
+    /// Creates a compositor with black bars and world clipping
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            border: BorderFill::Color([0.0, 0.0, 0.0, 1.0]),
+            clip_world: true,
+        }
+    }
+
+    /// Sets the bar fill
+    #[must_use]
+    pub const fn with_border(mut self, border: BorderFill) -> Self {
+        self.border = border;
+        self
+    }
+
+    /// Enables or disables clipping the world to the safe area
+    #[must_use]
+    pub const fn with_world_clip(mut self, clip: bool) -> Self {
+        self.clip_world = clip;
+        self
+    }
+
+    /// Builds the final command list: world (clipped), bars, then UI
+    #[must_use]
+    pub fn compose(
+        &self,
+        viewport: &Viewport,
+        world: &[RenderCommand],
+        ui: &[RenderCommand],
+    ) -> Vec<RenderCommand> {
+        let bars = viewport.letterbox_bars();
+        let clip = self.clip_world && !bars.is_empty();
+        let mut commands = Vec::with_capacity(world.len() + ui.len() + 4);
+        if clip {
+            commands.push(RenderCommand::PushScissor {
+                rect: viewport.safe_area,
+            });
+        }
+        commands.extend_from_slice(world);
+        if clip {
+            commands.push(RenderCommand::PopScissor);
+        }
+        for bar in bars {
+            commands.extend(self.border.commands(bar));
+        }
+        commands.extend_from_slice(ui);
+        commands
+    }
+}
+
+impl Default for Compositor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn world() -> Vec<RenderCommand> {
+        vec![RenderCommand::Clear {
+            color: [0.2, 0.4, 0.8, 1.0],
+        }]
+    }
+
+    #[test]
+    fn test_standard_viewport_has_no_bars() {
+        let viewport = Viewport::new(1920, 1080);
+        let commands = Compositor::new().compose(&viewport, &world(), &[]);
+        assert_eq!(commands, world());
+    }
+
+    #[test]
+    fn test_ultrawide_clips_world_and_fills_bars() {
+        let viewport = Viewport::new(5120, 1440);
+        let ui = vec![RenderCommand::DrawRect {
+            rect: Rect::new(0.0, 0.0, 5120.0, 40.0),
+            color: [1.0; 4],
+        }];
+        let commands = Compositor::new().compose(&viewport, &world(), &ui);
+        assert!(
+            matches!(commands[0], RenderCommand::PushScissor { rect } if rect == viewport.safe_area)
+        );
+        assert!(matches!(commands[2], RenderCommand::PopScissor));
+        // Two black bars, then the full-width UI
+        assert_eq!(commands.len(), 6);
+        assert_eq!(commands[5], ui[0]);
+    }
+
+    #[test]
+    fn test_stripes_cover_bar() {
+        let bar = Rect::new(0.0, 0.0, 100.0, 50.0);
+        let fill = BorderFill::Stripes {
+            a: [1.0; 4],
+            b: [0.0, 0.0, 0.0, 1.0],
+            width: 30.0,
+        };
+        let commands = fill.commands(bar);
+        assert_eq!(commands.len(), 4);
+        let covered: f32 = commands
+            .iter()
+            .map(|c| match c {
+                RenderCommand::DrawRect { rect, .. } => rect.width,
+                _ => 0.0,
+            })
+            .sum();
+        assert!((covered - 100.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_checker_and_none() {
+        let bar = Rect::new(0.0, 0.0, 20.0, 20.0);
+        let checker = BorderFill::Checker {
+            a: [1.0; 4],
+            b: [0.0, 0.0, 0.0, 1.0],
+            size: 10.0,
+        };
+        // Base fill plus the two odd cells
+        assert_eq!(checker.commands(bar).len(), 3);
+        assert!(BorderFill::None.commands(bar).is_empty());
+    }
+}
@@ -0,0 +1,288 @@
+//! Debug drawing for colliders, velocities, contacts, AI and UI bounds.
+//!
+//! [`DebugDraw`] records outline primitives into its own command buffer
+//! when enabled. Each primitive belongs to a [`DebugCategory`] that can be
+//! toggled at runtime, so only the overlays being investigated are drawn.
+//! Coordinates are passed through unchanged; callers draw in whichever
+//! space the rest of their frame uses.
+
+use glam::Vec2;
+use serde::{Deserialize, Serialize};
+
+use jugar_core::Rect;
+
+use crate::{RenderCommand, RenderQueue};
+
+/// Kind of debug overlay
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DebugCategory {
+    /// Physics collider outlines
+    Colliders,
+    /// Body velocity vectors
+    Velocities,
+    /// Contact points and normals
+    Contacts,
+    /// AI navigation paths
+    Paths,
+    /// AI steering vectors
+    Steering,
+    /// UI widget bounds
+    UiBounds,
+}
+
+impl DebugCategory {
+    /// All categories
+    pub const ALL: [Self; 6] = [
+        Self::Colliders,
+        Self::Velocities,
+        Self::Contacts,
+        Self::Paths,
+        Self::Steering,
+        Self::UiBounds,
+    ];
+
+    const fn bit(self) -> u32 {
+        1 << self as u32
+    }
+
+    /// Default overlay color for the category
+    #[must_use]
+    pub const fn color(self) -> [f32; 4] {
+        match self {
+            Self::Colliders => [0.2, 1.0, 0.2, 1.0],
+            Self::Velocities => [0.2, 0.6, 1.0, 1.0],
+            Self::Contacts => [1.0, 0.2, 0.2, 1.0],
+            Self::Paths => [1.0, 0.9, 0.2, 1.0],
+            Self::Steering => [1.0, 0.4, 1.0, 1.0],
+            Self::UiBounds => [0.2, 1.0, 1.0, 1.0],
+        }
+    }
+}
+
+/// Runtime-toggleable debug overlay recorder
+#[derive(Debug, Clone, PartialEq)]
+pub struct DebugDraw {
+    enabled: bool,
+    categories: u32,
+    line_width: f32,
+    commands: Vec<RenderCommand>,
+}
+
+impl DebugDraw {
+    /// Creates a disabled recorder with every category selected
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            enabled: false,
+            categories: u32::MAX,
+            line_width: 1.0,
+            commands: Vec::new(),
+        }
+    }
+
+    /// Sets the outline width
+    #[must_use]
+    pub const fn with_line_width(mut self, width: f32) -> Self {
+        self.line_width = width;
+        self
+    }
+
+    /// Returns true if debug drawing is on
+    #[must_use]
+    pub const fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Turns debug drawing on or off
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        if !enabled {
+            self.commands.clear();
+        }
+    }
+
+    /// Turns a category on or off
+    pub const fn set_category(&mut self, category: DebugCategory, on: bool) {
+        if on {
+            self.categories |= category.bit();
+        } else {
+            self.categories &= !category.bit();
+        }
+    }
+
+    /// Flips a category
+    pub const fn toggle_category(&mut self, category: DebugCategory) {
+        self.categories ^= category.bit();
+    }
+
+    /// Returns true if primitives of this category will be recorded
+    #[must_use]
+    pub const fn is_active(&self, category: DebugCategory) -> bool {
+        self.enabled && self.categories & category.bit() != 0
+    }
+
+    fn record(
+        &mut self,
+        category: DebugCategory,
+        command: impl FnOnce(f32, [f32; 4]) -> RenderCommand,
+    ) {
+        if self.is_active(category) {
+            self.commands
+                .push(command(self.line_width, category.color()));
+        }
+    }
+
+    /// Circle outline
+    pub fn circle(&mut self, category: DebugCategory, center: Vec2, radius: f32) {
+        self.record(category, |width, color| RenderCommand::DrawCircle {
+            center,
+            radius,
+            color,
+            outline: Some(width),
+        });
+    }
+
+    /// Rectangle outline
+    pub fn rect(&mut self, category: DebugCategory, rect: Rect) {
+        let min = Vec2::new(rect.x, rect.y);
+        let max = min + Vec2::new(rect.width, rect.height);
+        self.polygon(
+            category,
+            vec![min, Vec2::new(max.x, min.y), max, Vec2::new(min.x, max.y)],
+        );
+    }
+
+    /// Closed polygon outline
+    pub fn polygon(&mut self, category: DebugCategory, points: Vec<Vec2>) {
+        self.record(category, |width, color| RenderCommand::DrawPolygon {
+            points,
+            color,
+            outline: Some(width),
+        });
+    }
+
+    /// Line segment
+    pub fn line(&mut self, category: DebugCategory, start: Vec2, end: Vec2) {
+        self.record(category, |width, color| RenderCommand::DrawLine {
+            start,
+            end,
+            width,
+            color,
+        });
+    }
+
+    /// Arrow from `origin` along `vector` (velocities, normals, steering)
+    pub fn arrow(&mut self, category: DebugCategory, origin: Vec2, vector: Vec2) {
+        if !self.is_active(category) || vector.length_squared() <= f32::EPSILON {
+            return;
+        }
+        let tip = origin + vector;
+        let head = vector.normalize() * (vector.length() * 0.25).min(8.0);
+        let left = tip - head + head.perp() * 0.5;
+        let right = tip - head - head.perp() * 0.5;
+        self.record(category, |width, color| RenderCommand::DrawPolyline {
+            points: vec![origin, tip, left, tip, right],
+            width,
+            color,
+            closed: false,
+        });
+    }
+
+    /// Small filled marker
+    pub fn point(&mut self, category: DebugCategory, position: Vec2, radius: f32) {
+        self.record(category, |_, color| RenderCommand::DrawCircle {
+            center: position,
+            radius,
+            color,
+            outline: None,
+        });
+    }
+
+    /// Open path through waypoints
+    pub fn path(&mut self, category: DebugCategory, points: &[Vec2]) {
+        if points.len() < 2 {
+            return;
+        }
+        self.record(category, |width, color| RenderCommand::DrawPolyline {
+            points: points.to_vec(),
+            width,
+            color,
+            closed: false,
+        });
+    }
+
+    /// Commands recorded since the last flush
+    #[must_use]
+    pub fn commands(&self) -> &[RenderCommand] {
+        &self.commands
+    }
+
+    /// Drops recorded commands
+    pub fn clear(&mut self) {
+        self.commands.clear();
+    }
+
+    /// Moves recorded commands onto a render queue
+    pub fn flush(&mut self, queue: &mut RenderQueue) {
+        for command in self.commands.drain(..) {
+            queue.push(command);
+        }
+    }
+}
+
+impl Default for DebugDraw {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disabled_records_nothing() {
+        let mut debug = DebugDraw::new();
+        debug.circle(DebugCategory::Colliders, Vec2::ZERO, 1.0);
+        assert!(debug.commands().is_empty());
+    }
+
+    #[test]
+    fn test_category_toggle() {
+        let mut debug = DebugDraw::new();
+        debug.set_enabled(true);
+        debug.set_category(DebugCategory::Velocities, false);
+        debug.arrow(DebugCategory::Velocities, Vec2::ZERO, Vec2::X);
+        debug.rect(DebugCategory::UiBounds, Rect::new(0.0, 0.0, 10.0, 10.0));
+        assert_eq!(debug.commands().len(), 1);
+        debug.toggle_category(DebugCategory::Velocities);
+        debug.arrow(DebugCategory::Velocities, Vec2::ZERO, Vec2::X);
+        assert_eq!(debug.commands().len(), 2);
+    }
+
+    #[test]
+    fn test_arrow_has_head() {
+        let mut debug = DebugDraw::new();
+        debug.set_enabled(true);
+        debug.arrow(DebugCategory::Steering, Vec2::ZERO, Vec2::new(10.0, 0.0));
+        debug.arrow(DebugCategory::Steering, Vec2::ZERO, Vec2::ZERO);
+        assert_eq!(debug.commands().len(), 1);
+        assert!(matches!(
+            &debug.commands()[0],
+            RenderCommand::DrawPolyline { points, .. } if points.len() == 5 && points[1] == Vec2::new(10.0, 0.0)
+        ));
+    }
+
+    #[test]
+    fn test_flush_moves_commands() {
+        let mut debug = DebugDraw::new();
+        debug.set_enabled(true);
+        debug.path(DebugCategory::Paths, &[Vec2::ZERO, Vec2::X, Vec2::Y]);
+        debug.point(DebugCategory::Contacts, Vec2::ZERO, 2.0);
+        let mut queue = RenderQueue::new();
+        debug.flush(&mut queue);
+        assert_eq!(queue.len(), 2);
+        assert!(debug.commands().is_empty());
+    }
+}
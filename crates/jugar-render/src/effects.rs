@@ -0,0 +1,357 @@
+//! Full-screen post effects: fades, flashes, vignette and color filters.
+//!
+//! [`ScreenEffects`] animates the effects over time and emits them as
+//! [`RenderCommand`]s after the world has been drawn. Flashes go through a
+//! [`FlashLimiter`] that applies the same WCAG 2.1 limits as jugar-yaml's
+//! `PhotosensitivityGuard` (at most 3 flashes per second, capped red
+//! intensity, none at all with reduced motion), so a game cannot strobe the
+//! screen no matter how often it asks.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{RenderCommand, RenderQueue};
+
+/// Maximum flashes per second (WCAG 2.1)
+pub const MAX_FLASHES_PER_SECOND: usize = 3;
+
+/// Maximum opacity of a saturated red flash
+pub const MAX_RED_FLASH_ALPHA: f32 = 0.8;
+
+/// Maximum opacity of any other flash
+pub const MAX_FLASH_ALPHA: f32 = 0.9;
+
+/// Full-screen color filter
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ScreenFilter {
+    /// Remove all color
+    Grayscale,
+    /// Warm old-photo look
+    Sepia,
+    /// Dark blue night time
+    Night,
+    /// Multiply by a color
+    Tint([f32; 4]),
+}
+
+/// Rate and intensity limits for screen flashes
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlashLimiter {
+    /// Maximum flashes in any one-second window
+    pub max_per_second: usize,
+    /// Whether the player prefers reduced motion (disables flashes)
+    pub reduced_motion: bool,
+    recent: Vec<f32>,
+}
+
+impl FlashLimiter {
+    /// Creates a limiter with WCAG defaults
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            max_per_second: MAX_FLASHES_PER_SECOND,
+            reduced_motion: false,
+            recent: Vec::new(),
+        }
+    }
+
+    /// Disables flashes entirely
+    #[must_use]
+    pub const fn with_reduced_motion(mut self) -> Self {
+        self.reduced_motion = true;
+        self
+    }
+
+    /// Asks to flash at `time` (seconds); returns the allowed color
+    ///
+    /// Returns `None` if the flash would exceed the rate limit or reduced
+    /// motion is on. Otherwise the alpha is capped, more strictly for red.
+    pub fn request(&mut self, time: f32, color: [f32; 4]) -> Option<[f32; 4]> {
+        if self.reduced_motion {
+            return None;
+        }
+        self.recent.retain(|&t| time - t < 1.0);
+        if self.recent.len() >= self.max_per_second {
+            return None;
+        }
+        self.recent.push(time);
+        let [r, g, b, a] = color;
+        let is_red = r > 0.8 && g < 0.5 && b < 0.5;
+        let cap = if is_red {
+            MAX_RED_FLASH_ALPHA
+        } else {
+            MAX_FLASH_ALPHA
+        };
+        Some([r, g, b, a.min(cap)])
+    }
+
+    /// Forgets flash history
+    pub fn reset(&mut self) {
+        self.recent.clear();
+    }
+}
+
+impl Default for FlashLimiter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A color overlay animating from one alpha to another
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Overlay {
+    color: [f32; 3],
+    from: f32,
+    to: f32,
+    duration: f32,
+    elapsed: f32,
+}
+
+impl Overlay {
+    fn alpha(&self) -> f32 {
+        if self.duration <= 0.0 {
+            return self.to;
+        }
+        let t = (self.elapsed / self.duration).clamp(0.0, 1.0);
+        (self.to - self.from).mul_add(t, self.from)
+    }
+
+    fn is_finished(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+
+    fn command(&self) -> Option<RenderCommand> {
+        let alpha = self.alpha();
+        (alpha > 0.0).then_some(RenderCommand::ScreenOverlay {
+            color: [self.color[0], self.color[1], self.color[2], alpha],
+        })
+    }
+}
+
+/// Controller for full-screen post effects
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScreenEffects {
+    time: f32,
+    fade: Option<Overlay>,
+    flash: Option<Overlay>,
+    vignette: Option<(f32, [f32; 4])>,
+    filter: Option<(ScreenFilter, f32)>,
+    limiter: FlashLimiter,
+}
+
+impl ScreenEffects {
+    /// Creates a controller with no active effects
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            time: 0.0,
+            fade: None,
+            flash: None,
+            vignette: None,
+            filter: None,
+            limiter: FlashLimiter::new(),
+        }
+    }
+
+    /// Uses a custom flash limiter
+    #[must_use]
+    pub fn with_limiter(mut self, limiter: FlashLimiter) -> Self {
+        self.limiter = limiter;
+        self
+    }
+
+    /// Gets the flash limiter
+    #[must_use]
+    pub const fn limiter(&self) -> &FlashLimiter {
+        &self.limiter
+    }
+
+    /// Gets the flash limiter mutably (e.g. to toggle reduced motion)
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn limiter_mut(&mut self) -> &mut FlashLimiter {
+        &mut self.limiter
+    }
+
+    /// Fades the screen out to `color` over `duration` seconds
+    pub fn fade_to(&mut self, color: [f32; 4], duration: f32) {
+        let from = self.fade.as_ref().map_or(0.0, Overlay::alpha);
+        self.fade = Some(Overlay {
+            color: [color[0], color[1], color[2]],
+            from,
+            to: color[3],
+            duration: duration.max(0.0),
+            elapsed: 0.0,
+        });
+    }
+
+    /// Fades the screen back in from the current fade color
+    pub fn fade_in(&mut self, duration: f32) {
+        if let Some(fade) = self.fade.as_mut() {
+            *fade = Overlay {
+                from: fade.alpha(),
+                to: 0.0,
+                duration: duration.max(0.0),
+                elapsed: 0.0,
+                ..*fade
+            };
+        }
+    }
+
+    /// Returns true while a fade is animating
+    #[must_use]
+    pub fn is_fading(&self) -> bool {
+        self.fade.as_ref().is_some_and(|f| !f.is_finished())
+    }
+
+    /// Current fade opacity (1.0 = fully covered)
+    #[must_use]
+    pub fn fade_alpha(&self) -> f32 {
+        self.fade.as_ref().map_or(0.0, Overlay::alpha)
+    }
+
+    /// Flashes the screen, returning false if the limiter refused
+    pub fn flash(&mut self, color: [f32; 4], duration: f32) -> bool {
+        let Some([r, g, b, a]) = self.limiter.request(self.time, color) else {
+            return false;
+        };
+        self.flash = Some(Overlay {
+            color: [r, g, b],
+            from: a,
+            to: 0.0,
+            duration: duration.max(0.0),
+            elapsed: 0.0,
+        });
+        true
+    }
+
+    /// Sets (or clears with 0.0) the vignette intensity
+    pub fn set_vignette(&mut self, intensity: f32, color: [f32; 4]) {
+        self.vignette = (intensity > 0.0).then_some((intensity.min(1.0), color));
+    }
+
+    /// Sets (or clears) the full-screen filter
+    pub fn set_filter(&mut self, filter: Option<ScreenFilter>, strength: f32) {
+        self.filter = filter.map(|f| (f, strength.clamp(0.0, 1.0)));
+    }
+
+    /// Advances effect animations
+    pub fn update(&mut self, dt: f32) {
+        self.time += dt;
+        if let Some(fade) = self.fade.as_mut() {
+            fade.elapsed += dt;
+        }
+        if let Some(flash) = self.flash.as_mut() {
+            flash.elapsed += dt;
+            if flash.is_finished() {
+                self.flash = None;
+            }
+        }
+    }
+
+    /// Removes all effects
+    pub fn clear(&mut self) {
+        self.fade = None;
+        self.flash = None;
+        self.vignette = None;
+        self.filter = None;
+    }
+
+    /// Render commands for the active effects, in compositing order
+    #[must_use]
+    pub fn commands(&self) -> Vec<RenderCommand> {
+        let mut commands = Vec::new();
+        if let Some((filter, strength)) = self.filter {
+            commands.push(RenderCommand::Filter { filter, strength });
+        }
+        if let Some((intensity, color)) = self.vignette {
+            commands.push(RenderCommand::Vignette { color, intensity });
+        }
+        commands.extend(self.fade.as_ref().and_then(Overlay::command));
+        commands.extend(self.flash.as_ref().and_then(Overlay::command));
+        commands
+    }
+
+    /// Pushes the active effects onto a render queue
+    pub fn emit(&self, queue: &mut RenderQueue) {
+        for command in self.commands() {
+            queue.push(command);
+        }
+    }
+}
+
+impl Default for ScreenEffects {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    const WHITE: [f32; 4] = [1.0, 1.0, 1.0, 1.0];
+    const BLACK: [f32; 4] = [0.0, 0.0, 0.0, 1.0];
+
+    #[test]
+    fn test_fade_to_and_back() {
+        let mut effects = ScreenEffects::new();
+        effects.fade_to(BLACK, 1.0);
+        effects.update(0.5);
+        assert!((effects.fade_alpha() - 0.5).abs() < 0.001);
+        effects.update(0.5);
+        assert!(!effects.is_fading());
+        assert!(matches!(
+            effects.commands()[0],
+            RenderCommand::ScreenOverlay { color } if color[3] >= 1.0
+        ));
+        effects.fade_in(0.5);
+        effects.update(0.5);
+        assert!(effects.fade_alpha() <= 0.0);
+        assert!(effects.commands().is_empty());
+    }
+
+    #[test]
+    fn test_flash_rate_limited() {
+        let mut effects = ScreenEffects::new();
+        let allowed = (0..10).filter(|_| effects.flash(WHITE, 0.1)).count();
+        assert_eq!(allowed, MAX_FLASHES_PER_SECOND);
+        effects.update(1.0);
+        assert!(effects.flash(WHITE, 0.1));
+    }
+
+    #[test]
+    fn test_red_flash_capped() {
+        let mut limiter = FlashLimiter::new();
+        let color = limiter.request(0.0, [1.0, 0.0, 0.0, 1.0]).unwrap();
+        assert!(color[3] <= MAX_RED_FLASH_ALPHA);
+    }
+
+    #[test]
+    fn test_reduced_motion_blocks_flashes() {
+        let mut effects =
+            ScreenEffects::new().with_limiter(FlashLimiter::new().with_reduced_motion());
+        assert!(!effects.flash(WHITE, 0.2));
+        assert!(effects.commands().is_empty());
+    }
+
+    #[test]
+    fn test_flash_expires() {
+        let mut effects = ScreenEffects::new();
+        assert!(effects.flash(WHITE, 0.2));
+        assert_eq!(effects.commands().len(), 1);
+        effects.update(0.3);
+        assert!(effects.commands().is_empty());
+    }
+
+    #[test]
+    fn test_filter_and_vignette_order() {
+        let mut effects = ScreenEffects::new();
+        effects.set_vignette(0.5, BLACK);
+        effects.set_filter(Some(ScreenFilter::Night), 0.7);
+        let commands = effects.commands();
+        assert!(matches!(commands[0], RenderCommand::Filter { .. }));
+        assert!(matches!(commands[1], RenderCommand::Vignette { .. }));
+        effects.clear();
+        assert!(effects.commands().is_empty());
+    }
+}
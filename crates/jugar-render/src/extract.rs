@@ -0,0 +1,227 @@
+//! Builds a frame's sprite commands from the ECS world
+//!
+//! Every entity with a [`Sprite`] and a `Position` (the sprite's center in
+//! world space) is drawn at the size given by its [`SpriteSize`], or its
+//! source rectangle's size if it has none. [`SpriteExtractor::extract`]
+//! culls sprites outside the camera's view, sorts the rest by [`ZIndex`]
+//! (ties in spawn order) and pushes them to the queue in screen space.
+//!
+//! The camera is the oldest entity with a `Camera` component, or a default
+//! camera at the origin if there is none. Pixel-art cameras (those with a
+//! target resolution) sample textures with nearest-neighbor filtering.
+//!
+//! ```
+//! use glam::Vec2;
+//! use jugar_core::{Position, Sprite, World};
+//! use jugar_render::{RenderQueue, SpriteExtractor, SpriteSize, Viewport};
+//!
+//! let mut world = World::new();
+//! let player = world.spawn();
+//! world.add_component(player, Position::new(0.0, 0.0));
+//! world.add_component(player, Sprite::new(1));
+//! world.add_component(player, SpriteSize(Vec2::new(64.0, 64.0)));
+//!
+//! let mut queue = RenderQueue::new();
+//! let stats = SpriteExtractor::new().extract(&world, &Viewport::new(800, 600), &mut queue);
+//! assert_eq!(stats.drawn, 1);
+//! assert_eq!(queue.len(), 1);
+//! ```
+
+use glam::Vec2;
+use serde::{Deserialize, Serialize};
+
+use jugar_core::{Camera, Entity, Position, Rect, Sprite, World};
+
+use crate::{RenderCommand, RenderQueue, SpriteParams, TextureFilter, Viewport};
+
+/// Draw order of an entity's sprite; higher values draw on top
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+pub struct ZIndex(pub i32);
+
+/// Size of an entity's sprite in world units
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SpriteSize(pub Vec2);
+
+/// Counts from one extraction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ExtractStats {
+    /// Sprites pushed to the queue
+    pub drawn: usize,
+    /// Sprites skipped for being off screen
+    pub culled: usize,
+}
+
+/// Turns sprites in the world into render commands
+///
+/// Keeps its sort buffer between frames, so extraction stops allocating
+/// once it has warmed up.
+#[derive(Debug, Default)]
+pub struct SpriteExtractor {
+    visible: Vec<(ZIndex, Entity, RenderCommand)>,
+}
+
+impl SpriteExtractor {
+    /// Creates an extractor
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends the world's visible sprites to `queue`, back to front
+    ///
+    /// The queue is not cleared, so a clear or background pushed first
+    /// stays underneath.
+    pub fn extract(
+        &mut self,
+        world: &World,
+        viewport: &Viewport,
+        queue: &mut RenderQueue,
+    ) -> ExtractStats {
+        let camera = world
+            .query::<Camera>()
+            .min_by_key(|&(entity, _)| entity)
+            .map_or_else(Camera::new, |(_, camera)| camera.clone());
+        let view = viewport.visible_world_rect(&camera);
+        let filter = if camera.target_resolution.is_some() {
+            TextureFilter::Nearest
+        } else {
+            TextureFilter::Linear
+        };
+
+        self.visible.clear();
+        let mut culled = 0;
+        for (entity, sprite) in world.query::<Sprite>() {
+            let Some(position) = world.get_component::<Position>(entity) else {
+                continue;
+            };
+            let size = world.get_component::<SpriteSize>(entity).map_or_else(
+                || sprite.source.map(|rect| Vec2::new(rect.width, rect.height)),
+                |size| Some(size.0),
+            );
+            let Some(size) = size else {
+                continue;
+            };
+            let bounds = Rect::new(
+                position.x - size.x / 2.0,
+                position.y - size.y / 2.0,
+                size.x,
+                size.y,
+            );
+            if !bounds.overlaps(&view) {
+                culled += 1;
+                continue;
+            }
+
+            // World y points up, so the top-left corner is the maximum y
+            let top_left =
+                viewport.world_to_screen(Vec2::new(bounds.x, bounds.y + size.y), &camera);
+            let z = world
+                .get_component::<ZIndex>(entity)
+                .copied()
+                .unwrap_or_default();
+            self.visible.push((
+                z,
+                entity,
+                RenderCommand::DrawSprite {
+                    texture_id: sprite.texture_id,
+                    position: Position::from_vec2(top_left),
+                    size: size * camera.zoom,
+                    source: sprite.source,
+                    color: sprite.color,
+                    filter,
+                    params: SpriteParams::new().flipped(sprite.flip_x, sprite.flip_y),
+                },
+            ));
+        }
+
+        self.visible.sort_by_key(|&(z, entity, _)| (z, entity));
+        let drawn = self.visible.len();
+        for (_, _, command) in self.visible.drain(..) {
+            queue.push(command);
+        }
+        ExtractStats { drawn, culled }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn spawn_sprite(world: &mut World, texture_id: u32, x: f32, z: i32) -> Entity {
+        let entity = world.spawn();
+        world.add_component(entity, Position::new(x, 0.0));
+        world.add_component(entity, Sprite::new(texture_id));
+        world.add_component(entity, SpriteSize(Vec2::new(10.0, 20.0)));
+        world.add_component(entity, ZIndex(z));
+        entity
+    }
+
+    fn drawn(queue: &RenderQueue) -> Vec<(u32, Vec2, Vec2, TextureFilter)> {
+        queue
+            .commands()
+            .iter()
+            .filter_map(|command| match command {
+                RenderCommand::DrawSprite {
+                    texture_id,
+                    position,
+                    size,
+                    filter,
+                    ..
+                } => Some((*texture_id, position.as_vec2(), *size, *filter)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_sorts_by_z_then_spawn_order() {
+        let mut world = World::new();
+        let _ = spawn_sprite(&mut world, 1, 0.0, 2);
+        let _ = spawn_sprite(&mut world, 2, 0.0, -1);
+        let _ = spawn_sprite(&mut world, 3, 0.0, 2);
+        let _ = spawn_sprite(&mut world, 4, 0.0, 0);
+
+        let mut queue = RenderQueue::new();
+        let _ = SpriteExtractor::new().extract(&world, &Viewport::new(800, 600), &mut queue);
+        let order: Vec<u32> = drawn(&queue).iter().map(|d| d.0).collect();
+        assert_eq!(order, [2, 4, 1, 3]);
+    }
+
+    #[test]
+    fn test_culls_off_screen_and_applies_camera() {
+        let mut world = World::new();
+        let _ = spawn_sprite(&mut world, 1, 100.0, 0);
+        let _ = spawn_sprite(&mut world, 2, 5000.0, 0);
+        // No size and no source rectangle: nothing to draw
+        let bare = world.spawn();
+        world.add_component(bare, Position::new(0.0, 0.0));
+        world.add_component(bare, Sprite::new(3));
+        let camera = world.spawn();
+        world.add_component(
+            camera,
+            Camera::pixel_art(400.0, 300.0)
+                .with_zoom(2.0)
+                .with_position(Position::new(100.0, 0.0)),
+        );
+
+        let mut queue = RenderQueue::new();
+        let stats = SpriteExtractor::new().extract(&world, &Viewport::new(800, 600), &mut queue);
+        assert_eq!(
+            stats,
+            ExtractStats {
+                drawn: 1,
+                culled: 1
+            }
+        );
+
+        let (texture_id, top_left, size, filter) = drawn(&queue)[0];
+        assert_eq!(texture_id, 1);
+        // Centered on screen at twice the size
+        assert!((top_left - Vec2::new(390.0, 280.0)).length() < 1e-3);
+        assert!((size - Vec2::new(20.0, 40.0)).length() < 1e-3);
+        assert_eq!(filter, TextureFilter::Nearest);
+    }
+}
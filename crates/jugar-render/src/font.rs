@@ -0,0 +1,742 @@
+//! Bitmap fonts, glyph atlases and text layout.
+//!
+//! Text is laid out once in Rust with [`BitmapFont::layout`] (kerning and
+//! word wrapping included) and then emitted either as one
+//! [`RenderCommand::DrawText`] per line for Canvas 2D, or as one textured
+//! quad per glyph for GPU and headless renderers. Both paths share the same
+//! line breaks and glyph positions, so text wraps identically everywhere.
+//!
+//! Fonts come from pre-baked `BMFont` descriptors
+//! ([`BitmapFont::from_bmfont`]) or are generated at a chosen pixel size by
+//! packing glyphs from any [`GlyphRasterizer`] into a [`GlyphAtlas`].
+
+use std::collections::HashMap;
+
+use glam::Vec2;
+use serde::{Deserialize, Serialize};
+
+use jugar_core::{Position, Rect};
+
+use crate::{RenderCommand, RenderError, Result, SpriteParams, TextureFilter};
+
+/// Padding between packed glyphs, to stop bilinear filtering bleeding
+const ATLAS_PADDING: u32 = 1;
+
+/// Metrics and atlas location of one glyph
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Glyph {
+    /// Horizontal pen advance after this glyph
+    pub advance: f32,
+    /// Offset of the glyph bitmap from the pen position (line top)
+    pub offset: Vec2,
+    /// Bitmap size
+    pub size: Vec2,
+    /// Source rectangle in the atlas texture (`None` for blank glyphs)
+    pub atlas: Option<Rect>,
+}
+
+/// A font rasterized at one pixel size
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BitmapFont {
+    /// Face name
+    pub name: String,
+    /// Pixel size the glyphs were rasterized at
+    pub size: f32,
+    /// Distance between successive lines
+    pub line_height: f32,
+    /// Distance from the line top to the baseline
+    pub ascent: f32,
+    /// Texture holding the atlas
+    pub texture_id: u32,
+    /// Sampling used when drawing glyph quads
+    pub filter: TextureFilter,
+    glyphs: HashMap<char, Glyph>,
+    kerning: HashMap<(char, char), f32>,
+}
+
+/// A glyph placed by layout, relative to the text origin
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PositionedGlyph {
+    /// Character
+    pub ch: char,
+    /// Pen position (top of the line)
+    pub position: Vec2,
+    /// Line index
+    pub line: usize,
+}
+
+/// One laid-out line
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextLine {
+    /// Line text without the break
+    pub text: String,
+    /// Offset of the line top from the text origin
+    pub y: f32,
+    /// Advance width of the line
+    pub width: f32,
+}
+
+/// Result of laying out a string
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextLayout {
+    /// Lines after wrapping
+    pub lines: Vec<TextLine>,
+    /// Every visible glyph
+    pub glyphs: Vec<PositionedGlyph>,
+    /// Bounding size of the block
+    pub size: Vec2,
+    /// Font size used
+    pub font_size: f32,
+}
+
+impl TextLayout {
+    /// One `DrawText` per line (Canvas 2D path)
+    #[must_use]
+    pub fn text_commands(&self, origin: Vec2, color: [f32; 4]) -> Vec<RenderCommand> {
+        self.lines
+            .iter()
+            .filter(|line| !line.text.is_empty())
+            .map(|line| RenderCommand::DrawText {
+                text: line.text.clone(),
+                position: origin + Vec2::new(0.0, line.y),
+                size: self.font_size,
+                color,
+            })
+            .collect()
+    }
+
+    /// One atlas quad per glyph (GPU and headless path)
+    #[must_use]
+    pub fn glyph_commands(
+        &self,
+        font: &BitmapFont,
+        origin: Vec2,
+        color: [f32; 4],
+    ) -> Vec<RenderCommand> {
+        self.glyphs
+            .iter()
+            .filter_map(|placed| {
+                let glyph = font.glyph(placed.ch)?;
+                let source = glyph.atlas?;
+                let top_left = origin + placed.position + glyph.offset;
+                Some(RenderCommand::DrawSprite {
+                    texture_id: font.texture_id,
+                    position: Position::new(top_left.x, top_left.y),
+                    size: glyph.size,
+                    source: Some(source),
+                    color,
+                    filter: font.filter,
+                    params: SpriteParams::new(),
+                })
+            })
+            .collect()
+    }
+}
+
+impl BitmapFont {
+    /// Creates a font with no glyphs
+    #[must_use]
+    pub fn new(name: impl Into<String>, size: f32, line_height: f32, ascent: f32) -> Self {
+        Self {
+            name: name.into(),
+            size,
+            line_height,
+            ascent,
+            texture_id: 0,
+            filter: TextureFilter::Linear,
+            glyphs: HashMap::new(),
+            kerning: HashMap::new(),
+        }
+    }
+
+    /// Fixed-advance metrics for printable ASCII without an atlas
+    ///
+    /// Matches the `monospace` CSS font closely enough for layout when only
+    /// the Canvas 2D path is used.
+    #[must_use]
+    pub fn monospace(size: f32) -> Self {
+        let mut font = Self::new("monospace", size, size * 1.2, size * 0.8);
+        for ch in ' '..='~' {
+            font.add_glyph(
+                ch,
+                Glyph {
+                    advance: size * 0.6,
+                    offset: Vec2::ZERO,
+                    size: Vec2::new(size * 0.6, size),
+                    atlas: None,
+                },
+            );
+        }
+        font
+    }
+
+    /// Sets the atlas texture
+    #[must_use]
+    pub const fn with_texture(mut self, texture_id: u32) -> Self {
+        self.texture_id = texture_id;
+        self
+    }
+
+    /// Adds or replaces a glyph
+    pub fn add_glyph(&mut self, ch: char, glyph: Glyph) {
+        let _ = self.glyphs.insert(ch, glyph);
+    }
+
+    /// Adds a kerning adjustment between a pair
+    pub fn add_kerning(&mut self, first: char, second: char, amount: f32) {
+        let _ = self.kerning.insert((first, second), amount);
+    }
+
+    /// Gets a glyph
+    #[must_use]
+    pub fn glyph(&self, ch: char) -> Option<&Glyph> {
+        self.glyphs.get(&ch)
+    }
+
+    /// Number of glyphs
+    #[must_use]
+    pub fn glyph_count(&self) -> usize {
+        self.glyphs.len()
+    }
+
+    /// Kerning adjustment between two characters
+    #[must_use]
+    pub fn kerning(&self, first: char, second: char) -> f32 {
+        self.kerning.get(&(first, second)).copied().unwrap_or(0.0)
+    }
+
+    /// Glyph used for a character (falls back to `?`)
+    fn resolve(&self, ch: char) -> Option<(char, &Glyph)> {
+        self.glyphs
+            .get(&ch)
+            .map(|glyph| (ch, glyph))
+            .or_else(|| self.glyphs.get(&'?').map(|glyph| ('?', glyph)))
+    }
+
+    /// Advance width of a single line, including kerning
+    #[must_use]
+    pub fn line_width(&self, text: &str) -> f32 {
+        let mut width = 0.0;
+        let mut previous = None;
+        for ch in text.chars() {
+            let Some((ch, glyph)) = self.resolve(ch) else {
+                continue;
+            };
+            if let Some(prev) = previous {
+                width += self.kerning(prev, ch);
+            }
+            width += glyph.advance;
+            previous = Some(ch);
+        }
+        width
+    }
+
+    /// Size of the text without wrapping
+    #[must_use]
+    pub fn measure(&self, text: &str) -> Vec2 {
+        self.layout(text, None).size
+    }
+
+    /// Breaks text into lines no wider than `max_width`
+    ///
+    /// Explicit `\n` always breaks. Words longer than a line are split
+    /// between characters.
+    #[must_use]
+    pub fn wrap(&self, text: &str, max_width: Option<f32>) -> Vec<String> {
+        let Some(max_width) = max_width else {
+            return text.split('\n').map(str::to_string).collect();
+        };
+        let mut lines = Vec::new();
+        for paragraph in text.split('\n') {
+            let mut line = String::new();
+            for word in paragraph.split(' ') {
+                let candidate = if line.is_empty() {
+                    word.to_string()
+                } else {
+                    format!("{line} {word}")
+                };
+                if self.line_width(&candidate) <= max_width {
+                    line = candidate;
+                    continue;
+                }
+                if !line.is_empty() {
+                    lines.push(core::mem::take(&mut line));
+                }
+                // Split words that cannot fit on a line of their own
+                for ch in word.chars() {
+                    line.push(ch);
+                    if self.line_width(&line) > max_width && line.chars().count() > 1 {
+                        let _ = line.pop();
+                        lines.push(core::mem::take(&mut line));
+                        line.push(ch);
+                    }
+                }
+            }
+            lines.push(line);
+        }
+        lines
+    }
+
+    /// Lays out text, optionally wrapping at `max_width`
+    #[must_use]
+    pub fn layout(&self, text: &str, max_width: Option<f32>) -> TextLayout {
+        let mut lines = Vec::new();
+        let mut glyphs = Vec::new();
+        let mut width: f32 = 0.0;
+        for (index, text) in self.wrap(text, max_width).into_iter().enumerate() {
+            let y = index as f32 * self.line_height;
+            let mut x = 0.0;
+            let mut previous = None;
+            for ch in text.chars() {
+                let Some((ch, glyph)) = self.resolve(ch) else {
+                    continue;
+                };
+                if let Some(prev) = previous {
+                    x += self.kerning(prev, ch);
+                }
+                if glyph.atlas.is_some() || !ch.is_whitespace() {
+                    glyphs.push(PositionedGlyph {
+                        ch,
+                        position: Vec2::new(x, y),
+                        line: index,
+                    });
+                }
+                x += glyph.advance;
+                previous = Some(ch);
+            }
+            width = width.max(x);
+            lines.push(TextLine { text, y, width: x });
+        }
+        let size = Vec2::new(width, lines.len() as f32 * self.line_height);
+        TextLayout {
+            lines,
+            glyphs,
+            size,
+            font_size: self.size,
+        }
+    }
+
+    /// Parses a `BMFont` text descriptor (`.fnt`)
+    ///
+    /// Only single-page fonts are supported.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RenderError::InvalidFont`] if the `common` line is missing,
+    /// a number does not parse, or the font uses more than one page.
+    pub fn from_bmfont(descriptor: &str) -> Result<Self> {
+        let mut font = Self::new("", 0.0, 0.0, 0.0);
+        let mut has_common = false;
+        for (number, line) in descriptor.lines().enumerate() {
+            let mut tokens = tokenize(line).into_iter();
+            let Some(tag) = tokens.next() else {
+                continue;
+            };
+            let fields: HashMap<&str, String> = tokens
+                .filter_map(|token| {
+                    let (key, value) = token.split_once('=')?;
+                    Some((key, value.trim_matches('"').to_string()))
+                })
+                .collect();
+            let number = number + 1;
+            let get = |key: &str| -> Result<f32> {
+                fields
+                    .get(key)
+                    .and_then(|value| value.parse().ok())
+                    .ok_or_else(|| {
+                        RenderError::InvalidFont(format!("line {number}: bad or missing '{key}'"))
+                    })
+            };
+            match tag {
+                "info" => {
+                    font.name = fields.get("face").cloned().unwrap_or_default();
+                    font.size = get("size")?.abs();
+                }
+                "common" => {
+                    font.line_height = get("lineHeight")?;
+                    font.ascent = get("base")?;
+                    if get("pages").unwrap_or(1.0) > 1.0 {
+                        return Err(RenderError::InvalidFont(
+                            "multi-page fonts are not supported".to_string(),
+                        ));
+                    }
+                    has_common = true;
+                }
+                "char" => {
+                    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                    let Some(ch) = char::from_u32(get("id")? as u32) else {
+                        continue;
+                    };
+                    let size = Vec2::new(get("width")?, get("height")?);
+                    let atlas = (size.x > 0.0 && size.y > 0.0)
+                        .then(|| {
+                            Ok::<_, RenderError>(Rect::new(get("x")?, get("y")?, size.x, size.y))
+                        })
+                        .transpose()?;
+                    font.add_glyph(
+                        ch,
+                        Glyph {
+                            advance: get("xadvance")?,
+                            offset: Vec2::new(get("xoffset")?, get("yoffset")?),
+                            size,
+                            atlas,
+                        },
+                    );
+                }
+                "kerning" => {
+                    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                    let pair = (
+                        char::from_u32(get("first")? as u32),
+                        char::from_u32(get("second")? as u32),
+                    );
+                    if let (Some(first), Some(second)) = pair {
+                        font.add_kerning(first, second, get("amount")?);
+                    }
+                }
+                _ => {}
+            }
+        }
+        if !has_common {
+            return Err(RenderError::InvalidFont(
+                "missing 'common' line".to_string(),
+            ));
+        }
+        if font.size <= 0.0 {
+            font.size = font.line_height;
+        }
+        Ok(font)
+    }
+}
+
+/// Splits a `BMFont` line on whitespace outside quotes
+fn tokenize(line: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+    let mut quoted = false;
+    for (index, c) in line.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            c if c.is_whitespace() && !quoted => {
+                if let Some(begin) = start.take() {
+                    tokens.push(&line[begin..index]);
+                }
+                continue;
+            }
+            _ => {}
+        }
+        if start.is_none() {
+            start = Some(index);
+        }
+    }
+    if let Some(begin) = start {
+        tokens.push(&line[begin..]);
+    }
+    tokens
+}
+
+/// Coverage bitmap of one rasterized glyph
+#[derive(Debug, Clone, PartialEq)]
+pub struct GlyphBitmap {
+    /// Width in pixels
+    pub width: u32,
+    /// Height in pixels
+    pub height: u32,
+    /// Row-major coverage, one byte per pixel
+    pub coverage: Vec<u8>,
+    /// Offset from the pen position (line top)
+    pub offset: Vec2,
+    /// Pen advance
+    pub advance: f32,
+}
+
+/// Source of glyph bitmaps (a TTF rasterizer, a procedural font, ...)
+pub trait GlyphRasterizer {
+    /// Face name
+    fn name(&self) -> &str;
+
+    /// Rasterizes a character at a pixel size, `None` if unsupported
+    fn rasterize(&self, ch: char, size: f32) -> Option<GlyphBitmap>;
+
+    /// Line height and ascent at a pixel size
+    fn line_metrics(&self, size: f32) -> (f32, f32) {
+        (size * 1.2, size * 0.8)
+    }
+
+    /// Kerning between two characters at a pixel size
+    fn kerning(&self, _first: char, _second: char, _size: f32) -> f32 {
+        0.0
+    }
+}
+
+/// Single-channel texture that glyphs are shelf-packed into
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GlyphAtlas {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+    cursor_x: u32,
+    cursor_y: u32,
+    shelf_height: u32,
+}
+
+impl GlyphAtlas {
+    /// Creates an empty atlas
+    #[must_use]
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![0; width as usize * height as usize],
+            cursor_x: ATLAS_PADDING,
+            cursor_y: ATLAS_PADDING,
+            shelf_height: 0,
+        }
+    }
+
+    /// Atlas dimensions
+    #[must_use]
+    pub const fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    /// Row-major coverage pixels
+    #[must_use]
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// Copies a bitmap in, returning where it went (`None` when full)
+    pub fn insert(&mut self, bitmap: &GlyphBitmap) -> Option<Rect> {
+        let (w, h) = (bitmap.width, bitmap.height);
+        if self.cursor_x + w + ATLAS_PADDING > self.width {
+            self.cursor_x = ATLAS_PADDING;
+            self.cursor_y += self.shelf_height + ATLAS_PADDING;
+            self.shelf_height = 0;
+        }
+        if self.cursor_x + w + ATLAS_PADDING > self.width
+            || self.cursor_y + h + ATLAS_PADDING > self.height
+        {
+            return None;
+        }
+        let (x, y) = (self.cursor_x, self.cursor_y);
+        for row in 0..h {
+            let src = (row * w) as usize;
+            let dst = ((y + row) * self.width + x) as usize;
+            let (Some(from), Some(to)) = (
+                bitmap.coverage.get(src..src + w as usize),
+                self.pixels.get_mut(dst..dst + w as usize),
+            ) else {
+                break;
+            };
+            to.copy_from_slice(from);
+        }
+        self.cursor_x += w + ATLAS_PADDING;
+        self.shelf_height = self.shelf_height.max(h);
+        Some(Rect::new(x as f32, y as f32, w as f32, h as f32))
+    }
+
+    /// Rasterizes `charset` at `size` and packs it into a new atlas
+    ///
+    /// Characters the rasterizer does not support are skipped. Kerning is
+    /// sampled for every pair in the charset.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RenderError::AtlasFull`] if the glyphs do not fit.
+    pub fn build(
+        rasterizer: &dyn GlyphRasterizer,
+        size: f32,
+        charset: &str,
+        width: u32,
+        height: u32,
+    ) -> Result<(BitmapFont, Self)> {
+        let (line_height, ascent) = rasterizer.line_metrics(size);
+        let mut font = BitmapFont::new(rasterizer.name(), size, line_height, ascent);
+        let mut atlas = Self::new(width, height);
+        for ch in charset.chars() {
+            let Some(bitmap) = rasterizer.rasterize(ch, size) else {
+                continue;
+            };
+            let blank = bitmap.width == 0 || bitmap.height == 0;
+            let atlas_rect = if blank {
+                None
+            } else {
+                Some(
+                    atlas
+                        .insert(&bitmap)
+                        .ok_or(RenderError::AtlasFull { glyph: ch })?,
+                )
+            };
+            font.add_glyph(
+                ch,
+                Glyph {
+                    advance: bitmap.advance,
+                    offset: bitmap.offset,
+                    size: Vec2::new(bitmap.width as f32, bitmap.height as f32),
+                    atlas: atlas_rect,
+                },
+            );
+        }
+        for first in charset.chars() {
+            for second in charset.chars() {
+                let amount = rasterizer.kerning(first, second, size);
+                if amount.abs() > f32::EPSILON {
+                    font.add_kerning(first, second, amount);
+                }
+            }
+        }
+        Ok((font, atlas))
+    }
+}
+
+/// The same face rasterized at several sizes
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FontSet {
+    fonts: Vec<BitmapFont>,
+}
+
+impl FontSet {
+    /// Creates an empty set
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { fonts: Vec::new() }
+    }
+
+    /// Adds a size
+    pub fn add(&mut self, font: BitmapFont) {
+        let index = self
+            .fonts
+            .iter()
+            .position(|existing| existing.size > font.size)
+            .unwrap_or(self.fonts.len());
+        self.fonts.insert(index, font);
+    }
+
+    /// Smallest font at least `size` pixels, else the largest available
+    ///
+    /// Downscaling a larger bake keeps text crisp; upscaling blurs it.
+    #[must_use]
+    pub fn best_for(&self, size: f32) -> Option<&BitmapFont> {
+        self.fonts
+            .iter()
+            .find(|font| font.size >= size)
+            .or_else(|| self.fonts.last())
+    }
+
+    /// Number of sizes
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.fonts.len()
+    }
+
+    /// Returns true if no sizes are loaded
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.fonts.is_empty()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    const FNT: &str = r#"info face="Kid Sans" size=-16 bold=0
+common lineHeight=20 base=16 scaleW=64 scaleH=64 pages=1
+page id=0 file="kid.png"
+chars count=3
+char id=32 x=0 y=0 width=0 height=0 xoffset=0 yoffset=0 xadvance=4 page=0 chnl=15
+char id=65 x=0 y=0 width=10 height=12 xoffset=0 yoffset=4 xadvance=10 page=0 chnl=15
+char id=86 x=11 y=0 width=10 height=12 xoffset=0 yoffset=4 xadvance=10 page=0 chnl=15
+kerning first=65 second=86 amount=-2
+"#;
+
+    struct Blocks;
+
+    impl GlyphRasterizer for Blocks {
+        fn name(&self) -> &'static str {
+            "blocks"
+        }
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        fn rasterize(&self, ch: char, size: f32) -> Option<GlyphBitmap> {
+            let side = if ch == ' ' { 0 } else { (size * 0.5) as u32 };
+            Some(GlyphBitmap {
+                width: side,
+                height: side,
+                coverage: vec![255; (side * side) as usize],
+                offset: Vec2::ZERO,
+                advance: size * 0.5,
+            })
+        }
+    }
+
+    #[test]
+    fn test_bmfont_parse() {
+        let font = BitmapFont::from_bmfont(FNT).unwrap();
+        assert_eq!(font.name, "Kid Sans");
+        assert!((font.size - 16.0).abs() < f32::EPSILON);
+        assert_eq!(font.glyph_count(), 3);
+        assert!(font.glyph(' ').unwrap().atlas.is_none());
+        assert!((font.kerning('A', 'V') + 2.0).abs() < f32::EPSILON);
+        assert!((font.line_width("AV") - 18.0).abs() < f32::EPSILON);
+        assert!(BitmapFont::from_bmfont("info size=16").is_err());
+    }
+
+    #[test]
+    fn test_wrap_words_and_long_words() {
+        let font = BitmapFont::monospace(10.0);
+        // 6px per character
+        assert_eq!(font.wrap("the cat sat", Some(42.0)), vec!["the cat", "sat"]);
+        assert_eq!(font.wrap("abcdefgh", Some(30.0)), vec!["abcde", "fgh"]);
+        assert_eq!(font.wrap("a\nb", None), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_layout_positions_and_commands() {
+        let font = BitmapFont::from_bmfont(FNT).unwrap().with_texture(7);
+        let layout = font.layout("AV A", Some(20.0));
+        assert_eq!(layout.lines.len(), 2);
+        assert!((layout.glyphs[1].position.x - 8.0).abs() < f32::EPSILON);
+        assert_eq!(layout.glyphs[2].line, 1);
+        assert!((layout.size.y - 40.0).abs() < f32::EPSILON);
+
+        let text = layout.text_commands(Vec2::new(5.0, 5.0), [1.0; 4]);
+        assert_eq!(text.len(), 2);
+        let quads = layout.glyph_commands(&font, Vec2::ZERO, [1.0; 4]);
+        assert_eq!(quads.len(), 3);
+        assert!(matches!(
+            quads[0],
+            RenderCommand::DrawSprite {
+                texture_id: 7,
+                source: Some(_),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_atlas_build() {
+        let (font, atlas) = GlyphAtlas::build(&Blocks, 16.0, "AB C", 32, 32).unwrap();
+        assert_eq!(font.glyph_count(), 4);
+        let a = font.glyph('A').unwrap().atlas.unwrap();
+        let b = font.glyph('B').unwrap().atlas.unwrap();
+        assert!(!a.overlaps(&b));
+        assert!(font.glyph(' ').unwrap().atlas.is_none());
+        let opaque: usize = atlas.pixels().iter().map(|&p| usize::from(p == 255)).sum();
+        assert_eq!(opaque, 3 * 64);
+
+        let err = GlyphAtlas::build(&Blocks, 16.0, "ABCDEFGHIJ", 16, 16).unwrap_err();
+        assert!(matches!(err, RenderError::AtlasFull { .. }));
+    }
+
+    #[test]
+    fn test_font_set_picks_size() {
+        let mut set = FontSet::new();
+        set.add(BitmapFont::monospace(32.0));
+        set.add(BitmapFont::monospace(16.0));
+        assert!((set.best_for(20.0).unwrap().size - 32.0).abs() < f32::EPSILON);
+        assert!((set.best_for(12.0).unwrap().size - 16.0).abs() < f32::EPSILON);
+        assert!((set.best_for(64.0).unwrap().size - 32.0).abs() < f32::EPSILON);
+    }
+}
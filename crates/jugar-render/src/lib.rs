@@ -5,11 +5,47 @@
 #![forbid(unsafe_code)]
 #![warn(missing_docs)]
 
+extern crate alloc;
+
+mod animation;
+mod capture;
+mod color;
+mod compositor;
+mod debug;
+mod effects;
+mod extract;
+mod font;
+mod pixel;
+mod raster;
+mod remap;
+mod svg;
+
 use glam::Vec2;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use jugar_core::{Anchor, Camera, Position, Rect, ScaleMode};
+use jugar_core::{batch_affine, Anchor, Camera, FramePool, PoolStats, Position, Rect, ScaleMode};
+
+pub use animation::{
+    AnimationClip, AnimationFrame, PlayDirection, SpriteSheet, TextureAtlas, DEFAULT_CLIP,
+};
+pub use capture::{ClipRecorder, DEFAULT_CLIP_FPS, DEFAULT_CLIP_SECONDS, DEFAULT_CLIP_WIDTH};
+pub use color::{linear_to_srgb, srgb_to_linear, Color, Palette};
+pub use compositor::{BorderFill, Compositor};
+pub use debug::{DebugCategory, DebugDraw};
+pub use effects::{
+    FlashLimiter, ScreenEffects, ScreenFilter, MAX_FLASHES_PER_SECOND, MAX_FLASH_ALPHA,
+    MAX_RED_FLASH_ALPHA,
+};
+pub use extract::{ExtractStats, SpriteExtractor, SpriteSize, ZIndex};
+pub use font::{
+    BitmapFont, FontSet, Glyph, GlyphAtlas, GlyphBitmap, GlyphRasterizer, PositionedGlyph,
+    TextLayout, TextLine,
+};
+pub use pixel::PixelArtView;
+pub use raster::{rasterize, Image, SoftwareRasterizer};
+pub use remap::ColorRemap;
+pub use svg::{parse_svg, SpriteLibrary, VectorDrawing, VectorMesh, VectorShape, MAX_SVG_POINTS};
 
 /// Rendering errors
 #[derive(Error, Debug, Clone, PartialEq, Eq)]
@@ -22,6 +58,21 @@ pub enum RenderError {
         /// Height
         height: u32,
     },
+    /// Font descriptor could not be parsed
+    #[error("Invalid font: {0}")]
+    InvalidFont(String),
+    /// Glyph atlas has no room left
+    #[error("Glyph atlas full while packing '{glyph}'")]
+    AtlasFull {
+        /// Glyph that did not fit
+        glyph: char,
+    },
+    /// SVG drawing could not be imported
+    #[error("Invalid SVG: {0}")]
+    InvalidSvg(String),
+    /// Sprite sheet export could not be imported
+    #[error("Invalid sprite sheet: {0}")]
+    InvalidSpriteSheet(String),
 }
 
 /// Result type for render operations
@@ -111,6 +162,112 @@ impl Viewport {
         self.safe_area = calculate_safe_area(width, height);
     }
 
+    /// The full drawable area, including any letterbox/pillarbox bars
+    #[must_use]
+    pub const fn full_area(&self) -> Rect {
+        Rect::new(0.0, 0.0, self.width as f32, self.height as f32)
+    }
+
+    /// Regions outside the safe area (empty for exactly 16:9)
+    #[must_use]
+    pub fn letterbox_bars(&self) -> Vec<Rect> {
+        self.bars_around(self.safe_area)
+    }
+
+    /// Camera that shows all of `rect` as large as possible
+    ///
+    /// The world region is centered and scaled to fit the whole viewport, so
+    /// it is fully visible at any aspect ratio; the leftover strips (see
+    /// [`world_letterbox_bars`](Self::world_letterbox_bars)) show whatever
+    /// lies beyond it.
+    #[must_use]
+    pub fn fit_world_rect(&self, rect: Rect) -> Camera {
+        let zoom = (self.width as f32 / rect.width).min(self.height as f32 / rect.height);
+        let zoom = if zoom.is_finite() && zoom > 0.0 {
+            zoom
+        } else {
+            1.0
+        };
+        let (x, y) = rect.center();
+        Camera::new()
+            .with_position(Position::new(x, y))
+            .with_zoom(zoom)
+    }
+
+    /// Screen rectangle covered by a world rectangle
+    #[must_use]
+    pub fn world_rect_to_screen(&self, rect: Rect, camera: &Camera) -> Rect {
+        // World y points up, so the top-left corner is the world (x, y + height)
+        let top_left = self.world_to_screen(Vec2::new(rect.x, rect.y + rect.height), camera);
+        Rect::new(
+            top_left.x,
+            top_left.y,
+            rect.width * camera.zoom,
+            rect.height * camera.zoom,
+        )
+    }
+
+    /// Screen regions outside a framed world rectangle
+    ///
+    /// With a camera from [`fit_world_rect`](Self::fit_world_rect), these are
+    /// the bars to fill when nothing should be drawn beyond the world bounds.
+    #[must_use]
+    pub fn world_letterbox_bars(&self, rect: Rect, camera: &Camera) -> Vec<Rect> {
+        self.bars_around(self.world_rect_to_screen(rect, camera))
+    }
+
+    /// World region visible on screen
+    #[must_use]
+    pub fn visible_world_rect(&self, camera: &Camera) -> Rect {
+        self.screen_rect_to_world(self.full_area(), camera)
+    }
+
+    /// World region covered by the safe area
+    ///
+    /// Gameplay kept inside this region is on screen at every aspect ratio.
+    #[must_use]
+    pub fn safe_area_to_world(&self, camera: &Camera) -> Rect {
+        self.screen_rect_to_world(self.safe_area, camera)
+    }
+
+    /// Converts coordinates relative to the safe area's top-left corner to
+    /// world coordinates
+    #[must_use]
+    pub fn safe_to_world(&self, safe_pos: Vec2, camera: &Camera) -> Vec2 {
+        let origin = Vec2::new(self.safe_area.x, self.safe_area.y);
+        self.screen_to_world(origin + safe_pos, camera)
+    }
+
+    fn screen_rect_to_world(&self, rect: Rect, camera: &Camera) -> Rect {
+        // The screen's bottom-left corner is the world minimum
+        let min = self.screen_to_world(Vec2::new(rect.x, rect.y + rect.height), camera);
+        Rect::new(
+            min.x,
+            min.y,
+            rect.width / camera.zoom,
+            rect.height / camera.zoom,
+        )
+    }
+
+    /// Parts of the screen outside `inner` (a screen rectangle)
+    fn bars_around(&self, inner: Rect) -> Vec<Rect> {
+        let (w, h) = (self.width as f32, self.height as f32);
+        let left = inner.x.clamp(0.0, w);
+        let right = (inner.x + inner.width).clamp(left, w);
+        let top = inner.y.clamp(0.0, h);
+        let bottom = (inner.y + inner.height).clamp(top, h);
+        let candidates = [
+            Rect::new(0.0, 0.0, left, h),
+            Rect::new(right, 0.0, w - right, h),
+            Rect::new(0.0, 0.0, w, top),
+            Rect::new(0.0, bottom, w, h - bottom),
+        ];
+        candidates
+            .into_iter()
+            .filter(|bar| bar.width >= 0.5 && bar.height >= 0.5)
+            .collect()
+    }
+
     /// Converts screen coordinates to world coordinates
     #[must_use]
     pub fn screen_to_world(&self, screen_pos: Vec2, camera: &Camera) -> Vec2 {
@@ -132,6 +289,35 @@ impl Viewport {
         )
     }
 
+    /// Converts many world positions to screen coordinates at once
+    ///
+    /// Takes struct-of-arrays columns (see [`jugar_core::TransformSoa`]) and
+    /// writes the common prefix of all four slices.
+    pub fn world_to_screen_batch(
+        &self,
+        xs: &[f32],
+        ys: &[f32],
+        camera: &Camera,
+        screen_xs: &mut [f32],
+        screen_ys: &mut [f32],
+    ) {
+        let center = Vec2::new(self.width as f32 / 2.0, self.height as f32 / 2.0);
+        let len = xs.len().min(ys.len());
+        let (xs, ys) = (&xs[..len], &ys[..len]);
+        batch_affine(
+            xs,
+            camera.zoom,
+            camera.position.x.mul_add(-camera.zoom, center.x),
+            screen_xs,
+        );
+        batch_affine(
+            ys,
+            -camera.zoom,
+            camera.position.y.mul_add(camera.zoom, center.y),
+            screen_ys,
+        );
+    }
+
     /// Checks if a world position is visible
     #[must_use]
     pub fn is_visible(&self, world_pos: Vec2, camera: &Camera) -> bool {
@@ -167,6 +353,114 @@ fn calculate_safe_area(width: u32, height: u32) -> Rect {
     }
 }
 
+/// Texture sampling filter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum TextureFilter {
+    /// Bilinear filtering (smooth)
+    #[default]
+    Linear,
+    /// Nearest-neighbor (crisp pixel art)
+    Nearest,
+}
+
+/// Per-draw sprite modulation beyond the tint color
+///
+/// Lets one texture serve hit flashes, mirrored walk cycles and spinning
+/// pickups without duplicate assets.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SpriteParams {
+    /// Blend toward white (0 = none, 1 = solid white silhouette)
+    pub flash: f32,
+    /// Mirror horizontally around the pivot
+    pub flip_x: bool,
+    /// Mirror vertically around the pivot
+    pub flip_y: bool,
+    /// Rotation around the pivot in radians (clockwise on screen)
+    pub rotation: f32,
+    /// Pivot as a fraction of the sprite size ((0.5, 0.5) = center)
+    pub pivot: Vec2,
+}
+
+impl SpriteParams {
+    /// No flash, flip or rotation, pivoting on the center
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            flash: 0.0,
+            flip_x: false,
+            flip_y: false,
+            rotation: 0.0,
+            pivot: Vec2::new(0.5, 0.5),
+        }
+    }
+
+    /// Sets the flash-to-white amount (clamped to 0-1)
+    #[must_use]
+    pub fn with_flash(mut self, amount: f32) -> Self {
+        self.flash = amount.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Sets horizontal and vertical mirroring
+    #[must_use]
+    pub const fn flipped(mut self, flip_x: bool, flip_y: bool) -> Self {
+        self.flip_x = flip_x;
+        self.flip_y = flip_y;
+        self
+    }
+
+    /// Sets the rotation in radians
+    #[must_use]
+    pub const fn with_rotation(mut self, radians: f32) -> Self {
+        self.rotation = radians;
+        self
+    }
+
+    /// Sets the pivot as a fraction of the sprite size
+    #[must_use]
+    pub const fn with_pivot(mut self, pivot: Vec2) -> Self {
+        self.pivot = pivot;
+        self
+    }
+
+    /// Returns true if drawing with these parameters changes nothing
+    #[must_use]
+    pub fn is_identity(&self) -> bool {
+        self.flash <= 0.0 && !self.flip_x && !self.flip_y && self.rotation.abs() < f32::EPSILON
+    }
+
+    /// Applies the flash to a tint color, keeping its alpha
+    #[must_use]
+    pub fn flashed(&self, color: [f32; 4]) -> [f32; 4] {
+        let t = self.flash.clamp(0.0, 1.0);
+        let lift = |c: f32| (1.0 - c).mul_add(t, c);
+        [lift(color[0]), lift(color[1]), lift(color[2]), color[3]]
+    }
+
+    /// Screen corners of a sprite drawn at `position` (top-left) with `size`
+    ///
+    /// Returned clockwise from the unrotated top-left corner.
+    #[must_use]
+    pub fn corners(&self, position: Vec2, size: Vec2) -> [Vec2; 4] {
+        let pivot = position + size * self.pivot;
+        let rotate = Vec2::from_angle(self.rotation);
+        [
+            position,
+            position + Vec2::new(size.x, 0.0),
+            position + size,
+            position + Vec2::new(0.0, size.y),
+        ]
+        .map(|corner| pivot + rotate.rotate(corner - pivot))
+    }
+}
+
+impl Default for SpriteParams {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Render command for batched rendering
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum RenderCommand {
@@ -187,6 +481,12 @@ pub enum RenderCommand {
         source: Option<Rect>,
         /// Tint color
         color: [f32; 4],
+        /// Texture sampling
+        #[serde(default)]
+        filter: TextureFilter,
+        /// Flash, flips, rotation and pivot
+        #[serde(default)]
+        params: SpriteParams,
     },
     /// Draw a rectangle
     DrawRect {
@@ -195,12 +495,104 @@ pub enum RenderCommand {
         /// Fill color
         color: [f32; 4],
     },
+    /// Draw a circle
+    DrawCircle {
+        /// Center position
+        center: Vec2,
+        /// Radius
+        radius: f32,
+        /// Color
+        color: [f32; 4],
+        /// Outline width, or `None` to fill
+        outline: Option<f32>,
+    },
+    /// Draw a line segment
+    DrawLine {
+        /// Start point
+        start: Vec2,
+        /// End point
+        end: Vec2,
+        /// Line width
+        width: f32,
+        /// Color
+        color: [f32; 4],
+    },
+    /// Draw a closed polygon
+    DrawPolygon {
+        /// Vertices in order
+        points: Vec<Vec2>,
+        /// Color
+        color: [f32; 4],
+        /// Outline width, or `None` to fill
+        outline: Option<f32>,
+    },
+    /// Draw connected line segments
+    DrawPolyline {
+        /// Points in order
+        points: Vec<Vec2>,
+        /// Line width
+        width: f32,
+        /// Color
+        color: [f32; 4],
+        /// Connect the last point back to the first
+        closed: bool,
+    },
+    /// Draw a single line of text
+    DrawText {
+        /// Text content
+        text: String,
+        /// Top-left of the line
+        position: Vec2,
+        /// Font size in pixels
+        size: f32,
+        /// Color
+        color: [f32; 4],
+    },
+    /// Clip subsequent drawing to a rectangle until the matching pop
+    PushScissor {
+        /// Visible region in screen pixels
+        rect: Rect,
+    },
+    /// Remove the most recent scissor
+    PopScissor,
+    /// Multiply the opacity of subsequent drawing until the matching pop
+    ///
+    /// Groups nest, so fading a whole character (body, hat, shadow) is one
+    /// push rather than an alpha change on every sprite.
+    PushOpacity {
+        /// Opacity multiplier (0-1)
+        alpha: f32,
+    },
+    /// Remove the most recent opacity group
+    PopOpacity,
+    /// Cover the whole screen with a translucent color (fades, flashes)
+    ScreenOverlay {
+        /// RGBA color (alpha is the coverage)
+        color: [f32; 4],
+    },
+    /// Darken the screen edges
+    Vignette {
+        /// Edge color
+        color: [f32; 4],
+        /// Strength (0-1)
+        intensity: f32,
+    },
+    /// Apply a full-screen color filter
+    Filter {
+        /// Filter to apply
+        filter: ScreenFilter,
+        /// Blend strength (0-1)
+        strength: f32,
+    },
 }
 
 /// Render queue for batched rendering
+///
+/// The command buffer is kept between frames, so a queue that is cleared
+/// and refilled every frame stops allocating once it has warmed up.
 #[derive(Debug, Default)]
 pub struct RenderQueue {
-    commands: Vec<RenderCommand>,
+    commands: FramePool<RenderCommand>,
 }
 
 impl RenderQueue {
@@ -210,9 +602,9 @@ impl RenderQueue {
         Self::default()
     }
 
-    /// Clears the queue
+    /// Clears the queue for the next frame, keeping its buffer
     pub fn clear(&mut self) {
-        self.commands.clear();
+        self.commands.reset();
     }
 
     /// Adds a command to the queue
@@ -220,10 +612,96 @@ impl RenderQueue {
         self.commands.push(cmd);
     }
 
+    /// Starts an opacity group; end it with [`pop_opacity`](Self::pop_opacity)
+    pub fn push_opacity(&mut self, alpha: f32) {
+        self.push(RenderCommand::PushOpacity {
+            alpha: alpha.clamp(0.0, 1.0),
+        });
+    }
+
+    /// Ends the most recent opacity group
+    pub fn pop_opacity(&mut self) {
+        self.push(RenderCommand::PopOpacity);
+    }
+
+    /// Queues a filled circle
+    pub fn fill_circle(&mut self, center: Vec2, radius: f32, color: [f32; 4]) {
+        self.push(RenderCommand::DrawCircle {
+            center,
+            radius,
+            color,
+            outline: None,
+        });
+    }
+
+    /// Queues a circle outline
+    pub fn stroke_circle(&mut self, center: Vec2, radius: f32, width: f32, color: [f32; 4]) {
+        self.push(RenderCommand::DrawCircle {
+            center,
+            radius,
+            color,
+            outline: Some(width),
+        });
+    }
+
+    /// Queues a line of text at its top-left corner
+    pub fn text(&mut self, text: impl Into<String>, position: Vec2, size: f32, color: [f32; 4]) {
+        self.push(RenderCommand::DrawText {
+            text: text.into(),
+            position,
+            size,
+            color,
+        });
+    }
+
+    /// Queues a line segment
+    pub fn line(&mut self, start: Vec2, end: Vec2, width: f32, color: [f32; 4]) {
+        self.push(RenderCommand::DrawLine {
+            start,
+            end,
+            width,
+            color,
+        });
+    }
+
+    /// Queues a filled polygon (ignored with fewer than 3 points)
+    pub fn fill_polygon(&mut self, points: Vec<Vec2>, color: [f32; 4]) {
+        if points.len() >= 3 {
+            self.push(RenderCommand::DrawPolygon {
+                points,
+                color,
+                outline: None,
+            });
+        }
+    }
+
+    /// Queues a polyline (ignored with fewer than 2 points)
+    pub fn polyline(&mut self, points: Vec<Vec2>, width: f32, color: [f32; 4], closed: bool) {
+        if points.len() >= 2 {
+            self.push(RenderCommand::DrawPolyline {
+                points,
+                width,
+                color,
+                closed,
+            });
+        }
+    }
+
+    /// Rewrites the color of every command that has one
+    pub fn map_colors(&mut self, mut f: impl FnMut([f32; 4]) -> [f32; 4]) {
+        for color in self
+            .commands
+            .iter_mut()
+            .filter_map(RenderCommand::color_mut)
+        {
+            *color = f(*color);
+        }
+    }
+
     /// Returns the commands
     #[must_use]
     pub fn commands(&self) -> &[RenderCommand] {
-        &self.commands
+        self.commands.as_slice()
     }
 
     /// Returns the number of commands
@@ -237,6 +715,12 @@ impl RenderQueue {
     pub fn is_empty(&self) -> bool {
         self.commands.is_empty()
     }
+
+    /// Allocation counters of the command buffer
+    #[must_use]
+    pub const fn pool_stats(&self) -> PoolStats {
+        self.commands.stats()
+    }
 }
 
 /// Calculates UI element position based on anchor and viewport
@@ -260,6 +744,7 @@ pub fn calculate_anchored_position(
     let scale = match scale_mode {
         ScaleMode::Adaptive => vh.min(vw) / 1080.0, // Scale based on shortest dimension
         ScaleMode::PixelPerfect | ScaleMode::Fixed => 1.0,
+        ScaleMode::PixelArt => (vh.min(vw) / 1080.0).floor().max(1.0),
     };
 
     // Calculate final position (centered on anchor point)
@@ -326,6 +811,44 @@ mod tests {
         assert!(world.y.abs() < 0.1);
     }
 
+    #[test]
+    fn test_render_queue_reuses_buffer() {
+        let mut queue = RenderQueue::new();
+        for _ in 0..60 {
+            for i in 0..100 {
+                queue.fill_circle(Vec2::new(i as f32, 0.0), 1.0, [1.0; 4]);
+            }
+            queue.clear();
+        }
+        let stats = queue.pool_stats();
+        assert_eq!(stats.frames, 60);
+        assert_eq!(stats.peak, 100);
+        assert!(stats.allocations_per_frame() < 0.2);
+    }
+
+    #[test]
+    fn test_world_to_screen_batch_matches_scalar() {
+        let viewport = Viewport::new(800, 600);
+        let mut camera = Camera::new();
+        camera.position = Position::new(30.0, -12.0);
+        camera.zoom = 2.5;
+        let xs: Vec<f32> = (0..21_u8)
+            .map(|i| f32::from(i).mul_add(7.0, -50.0))
+            .collect();
+        let ys: Vec<f32> = (0..21_u8)
+            .map(|i| f32::from(i).mul_add(-3.0, 40.0))
+            .collect();
+        let mut screen_xs = vec![0.0; 21];
+        let mut screen_ys = vec![0.0; 21];
+        viewport.world_to_screen_batch(&xs, &ys, &camera, &mut screen_xs, &mut screen_ys);
+
+        for i in 0..xs.len() {
+            let expected = viewport.world_to_screen(Vec2::new(xs[i], ys[i]), &camera);
+            assert!((screen_xs[i] - expected.x).abs() < 1e-3);
+            assert!((screen_ys[i] - expected.y).abs() < 1e-3);
+        }
+    }
+
     #[test]
     fn test_world_to_screen_roundtrip() {
         let viewport = Viewport::new(800, 600);
@@ -383,4 +906,103 @@ mod tests {
         assert!((pos.x - (960.0 - 50.0)).abs() < 1.0);
         assert!((pos.y - (540.0 - 25.0)).abs() < 1.0);
     }
+
+    #[test]
+    fn test_render_queue_primitives() {
+        let mut queue = RenderQueue::new();
+        queue.fill_circle(Vec2::ZERO, 5.0, [1.0; 4]);
+        queue.stroke_circle(Vec2::ZERO, 5.0, 2.0, [1.0; 4]);
+        queue.line(Vec2::ZERO, Vec2::X, 1.0, [1.0; 4]);
+        queue.fill_polygon(vec![Vec2::ZERO, Vec2::X], [1.0; 4]);
+        queue.fill_polygon(vec![Vec2::ZERO, Vec2::X, Vec2::Y], [1.0; 4]);
+        queue.polyline(vec![Vec2::ZERO], 1.0, [1.0; 4], false);
+        queue.polyline(vec![Vec2::ZERO, Vec2::X], 1.0, [1.0; 4], false);
+        assert_eq!(queue.len(), 5);
+        assert!(matches!(
+            queue.commands()[1],
+            RenderCommand::DrawCircle {
+                outline: Some(_),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_letterbox_bars() {
+        assert!(Viewport::new(1920, 1080).letterbox_bars().is_empty());
+        let pillarbox = Viewport::new(5120, 1440).letterbox_bars();
+        assert_eq!(pillarbox.len(), 2);
+        assert!((pillarbox[0].width - pillarbox[1].width).abs() < 1.0);
+        let letterbox = Viewport::new(1080, 1920).letterbox_bars();
+        assert_eq!(letterbox.len(), 2);
+        assert!((letterbox[0].width - 1080.0).abs() < 1.0);
+    }
+
+    fn contains(outer: Rect, inner: Rect) -> bool {
+        inner.x >= outer.x - 0.01
+            && inner.y >= outer.y - 0.01
+            && inner.x + inner.width <= outer.x + outer.width + 0.01
+            && inner.y + inner.height <= outer.y + outer.height + 0.01
+    }
+
+    #[test]
+    fn test_fit_world_rect_any_aspect() {
+        let arena = Rect::new(-960.0, -540.0, 1920.0, 1080.0);
+        for (width, height) in [
+            (1920, 1080),
+            (3840, 1080),
+            (2560, 1080),
+            (1080, 1920),
+            (800, 600),
+        ] {
+            let viewport = Viewport::new(width, height);
+            let camera = viewport.fit_world_rect(arena);
+            assert!(contains(viewport.visible_world_rect(&camera), arena));
+
+            // The arena touches two opposite screen edges
+            let screen = viewport.world_rect_to_screen(arena, &camera);
+            let fills_width = (screen.width - width as f32).abs() < 0.5;
+            let fills_height = (screen.height - height as f32).abs() < 0.5;
+            assert!(fills_width || fills_height, "{width}x{height}");
+        }
+    }
+
+    #[test]
+    fn test_fit_world_rect_centers_off_origin_region() {
+        let viewport = Viewport::new(1920, 1080);
+        let camera = viewport.fit_world_rect(Rect::new(100.0, 200.0, 960.0, 540.0));
+        assert!((camera.zoom - 2.0).abs() < f32::EPSILON);
+        assert!((camera.position.x - 580.0).abs() < f32::EPSILON);
+        assert!((camera.position.y - 470.0).abs() < f32::EPSILON);
+
+        let degenerate = viewport.fit_world_rect(Rect::new(0.0, 0.0, 0.0, 0.0));
+        assert!((degenerate.zoom - 1.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_world_letterbox_bars_on_ultrawide() {
+        let viewport = Viewport::new(3840, 1080);
+        let arena = Rect::new(-960.0, -540.0, 1920.0, 1080.0);
+        let camera = viewport.fit_world_rect(arena);
+        let bars = viewport.world_letterbox_bars(arena, &camera);
+        assert_eq!(bars.len(), 2);
+        assert!((bars[0].width - 960.0).abs() < 0.5);
+        assert!((bars[1].x - 2880.0).abs() < 0.5);
+    }
+
+    #[test]
+    fn test_safe_area_to_world() {
+        let viewport = Viewport::new(3840, 1080);
+        let camera = Camera::new();
+        let safe = viewport.safe_area_to_world(&camera);
+        assert!((safe.x + 960.0).abs() < 0.01);
+        assert!((safe.y + 540.0).abs() < 0.01);
+        assert!((safe.width - 1920.0).abs() < 0.01);
+
+        // Safe-area top-left is the world's top-left of that region
+        let corner = viewport.safe_to_world(Vec2::ZERO, &camera);
+        assert!((corner - Vec2::new(-960.0, 540.0)).length() < 0.01);
+        let center = viewport.safe_to_world(Vec2::new(960.0, 540.0), &camera);
+        assert!(center.length() < 0.01);
+    }
 }
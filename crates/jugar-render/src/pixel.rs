@@ -0,0 +1,257 @@
+//! Virtual resolution rendering for pixel-art games.
+//!
+//! Games using [`PixelArtView`] draw into a small virtual canvas (e.g.
+//! 320x180) where one unit is one art pixel. [`PixelArtView::present`] snaps
+//! every command to the virtual pixel grid, scales it by the largest whole
+//! number that fits the screen, centers the result and switches sprites to
+//! nearest-neighbor sampling. Art pixels therefore stay square and never
+//! shimmer as the camera moves, from phones to ultrawide monitors.
+
+use glam::Vec2;
+use serde::{Deserialize, Serialize};
+
+use jugar_core::{Camera, Position, Rect};
+
+use crate::{RenderCommand, TextureFilter, Viewport};
+
+/// Integer-scaled virtual canvas
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PixelArtView {
+    /// Virtual width in art pixels
+    pub width: u32,
+    /// Virtual height in art pixels
+    pub height: u32,
+}
+
+impl PixelArtView {
+    /// Creates a virtual canvas
+    #[must_use]
+    pub const fn new(width: u32, height: u32) -> Self {
+        Self { width, height }
+    }
+
+    /// Virtual size as a vector
+    #[must_use]
+    pub const fn size(&self) -> Vec2 {
+        Vec2::new(self.width as f32, self.height as f32)
+    }
+
+    /// Largest whole-number scale that fits the viewport (at least 1)
+    #[must_use]
+    pub fn scale(&self, viewport: &Viewport) -> u32 {
+        let x = viewport.width / self.width.max(1);
+        let y = viewport.height / self.height.max(1);
+        x.min(y).max(1)
+    }
+
+    /// Screen rectangle the scaled canvas occupies, centered
+    #[must_use]
+    pub fn output_rect(&self, viewport: &Viewport) -> Rect {
+        let scale = self.scale(viewport) as f32;
+        let size = self.size() * scale;
+        // Whole-pixel origin so art pixels align with screen pixels
+        let x = ((viewport.width as f32 - size.x) * 0.5).floor();
+        let y = ((viewport.height as f32 - size.y) * 0.5).floor();
+        Rect::new(x, y, size.x, size.y)
+    }
+
+    /// Rounds a virtual position to the nearest art pixel
+    #[must_use]
+    pub fn snap(position: Vec2) -> Vec2 {
+        position.round()
+    }
+
+    /// Camera moved onto the art-pixel grid at its zoom level
+    #[must_use]
+    pub fn snap_camera(camera: &Camera) -> Camera {
+        let zoom = if camera.zoom > 0.0 { camera.zoom } else { 1.0 };
+        let snapped = (camera.position.as_vec2() * zoom).round() / zoom;
+        let mut camera = camera.clone();
+        camera.position = Position::new(snapped.x, snapped.y);
+        camera
+    }
+
+    /// Converts a virtual point to screen pixels (snapping first)
+    #[must_use]
+    pub fn to_screen(&self, point: Vec2, viewport: &Viewport) -> Vec2 {
+        let output = self.output_rect(viewport);
+        Vec2::new(output.x, output.y) + Self::snap(point) * self.scale(viewport) as f32
+    }
+
+    /// Converts a screen point (e.g. a touch) to virtual coordinates
+    #[must_use]
+    pub fn to_virtual(&self, point: Vec2, viewport: &Viewport) -> Vec2 {
+        let output = self.output_rect(viewport);
+        (point - Vec2::new(output.x, output.y)) / self.scale(viewport) as f32
+    }
+
+    /// Maps virtual-space commands to screen space
+    ///
+    /// The output is clipped to the canvas so nothing draws into the
+    /// letterbox bars; fill those with a [`crate::Compositor`] if needed.
+    #[must_use]
+    pub fn present(&self, commands: &[RenderCommand], viewport: &Viewport) -> Vec<RenderCommand> {
+        let output = self.output_rect(viewport);
+        let origin = Vec2::new(output.x, output.y);
+        let scale = self.scale(viewport) as f32;
+        let point = |p: Vec2| origin + Self::snap(p) * scale;
+        let rect = |r: Rect| {
+            let min = point(Vec2::new(r.x, r.y));
+            let size = Self::snap(Vec2::new(r.width, r.height)) * scale;
+            Rect::new(min.x, min.y, size.x, size.y)
+        };
+        let mut out = Vec::with_capacity(commands.len() + 2);
+        out.push(RenderCommand::PushScissor { rect: output });
+        out.extend(commands.iter().map(|command| match command {
+            RenderCommand::DrawSprite {
+                texture_id,
+                position,
+                size,
+                source,
+                color,
+                params,
+                ..
+            } => {
+                let at = point(position.as_vec2());
+                RenderCommand::DrawSprite {
+                    texture_id: *texture_id,
+                    position: Position::new(at.x, at.y),
+                    size: Self::snap(*size) * scale,
+                    source: *source,
+                    color: *color,
+                    filter: TextureFilter::Nearest,
+                    params: *params,
+                }
+            }
+            RenderCommand::DrawRect { rect: r, color } => RenderCommand::DrawRect {
+                rect: rect(*r),
+                color: *color,
+            },
+            RenderCommand::DrawCircle {
+                center,
+                radius,
+                color,
+                outline,
+            } => RenderCommand::DrawCircle {
+                center: point(*center),
+                radius: radius.round() * scale,
+                color: *color,
+                outline: outline.map(|width| width * scale),
+            },
+            RenderCommand::DrawLine {
+                start,
+                end,
+                width,
+                color,
+            } => RenderCommand::DrawLine {
+                start: point(*start),
+                end: point(*end),
+                width: width * scale,
+                color: *color,
+            },
+            RenderCommand::DrawPolygon {
+                points,
+                color,
+                outline,
+            } => RenderCommand::DrawPolygon {
+                points: points.iter().map(|p| point(*p)).collect(),
+                color: *color,
+                outline: outline.map(|width| width * scale),
+            },
+            RenderCommand::DrawPolyline {
+                points,
+                width,
+                color,
+                closed,
+            } => RenderCommand::DrawPolyline {
+                points: points.iter().map(|p| point(*p)).collect(),
+                width: width * scale,
+                color: *color,
+                closed: *closed,
+            },
+            RenderCommand::DrawText {
+                text,
+                position,
+                size,
+                color,
+            } => RenderCommand::DrawText {
+                text: text.clone(),
+                position: point(*position),
+                size: size * scale,
+                color: *color,
+            },
+            RenderCommand::PushScissor { rect: r } => RenderCommand::PushScissor { rect: rect(*r) },
+            other => other.clone(),
+        }));
+        out.push(RenderCommand::PopScissor);
+        out
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::SpriteParams;
+
+    #[test]
+    fn test_integer_scale_across_screens() {
+        let view = PixelArtView::new(320, 180);
+        assert_eq!(view.scale(&Viewport::new(1920, 1080)), 6);
+        assert_eq!(view.scale(&Viewport::new(1280, 720)), 4);
+        assert_eq!(view.scale(&Viewport::new(5120, 1440)), 8);
+        // Portrait phone: width limits the scale
+        assert_eq!(view.scale(&Viewport::new(1080, 1920)), 3);
+        assert_eq!(view.scale(&Viewport::new(200, 100)), 1);
+    }
+
+    #[test]
+    fn test_output_rect_centered() {
+        let view = PixelArtView::new(320, 180);
+        let output = view.output_rect(&Viewport::new(1366, 768));
+        // 4x -> 1280x720, centered with whole-pixel margins
+        assert!((output.width - 1280.0).abs() < f32::EPSILON);
+        assert!((output.x - 43.0).abs() < f32::EPSILON);
+        assert!((output.y - 24.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_present_snaps_and_uses_nearest() {
+        let view = PixelArtView::new(320, 180);
+        let viewport = Viewport::new(1920, 1080);
+        let commands = vec![RenderCommand::DrawSprite {
+            texture_id: 1,
+            position: Position::new(10.4, 20.6),
+            size: Vec2::new(16.0, 16.0),
+            source: None,
+            color: [1.0; 4],
+            filter: TextureFilter::Linear,
+            params: SpriteParams::new().flipped(true, false),
+        }];
+        let out = view.present(&commands, &viewport);
+        assert_eq!(out.len(), 3);
+        assert!(matches!(out[0], RenderCommand::PushScissor { .. }));
+        assert!(matches!(
+            &out[1],
+            RenderCommand::DrawSprite { position, size, filter: TextureFilter::Nearest, params, .. }
+                if params.flip_x
+                    && (position.x - 60.0).abs() < f32::EPSILON
+                    && (position.y - 126.0).abs() < f32::EPSILON
+                    && (size.x - 96.0).abs() < f32::EPSILON
+        ));
+    }
+
+    #[test]
+    fn test_snap_camera_and_round_trip() {
+        let mut moving = Camera::new().with_zoom(2.0);
+        moving.position = Position::new(3.3, 1.8);
+        let snapped = PixelArtView::snap_camera(&moving);
+        assert!((snapped.position.x - 3.5).abs() < f32::EPSILON);
+        assert!((snapped.position.y - 2.0).abs() < f32::EPSILON);
+
+        let view = PixelArtView::new(320, 180);
+        let viewport = Viewport::new(1920, 1080);
+        let screen = view.to_screen(Vec2::new(100.0, 50.0), &viewport);
+        assert!((view.to_virtual(screen, &viewport) - Vec2::new(100.0, 50.0)).length() < 0.001);
+    }
+}
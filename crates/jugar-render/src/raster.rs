@@ -0,0 +1,711 @@
+//! Software rasterizer for headless rendering.
+//!
+//! [`SoftwareRasterizer`] draws [`RenderCommand`]s into an RGBA8 [`Image`] on
+//! the CPU, so previews and thumbnails can be made without a GPU or browser.
+//! Shapes are filled by per-pixel coverage tests without antialiasing.
+//! Sprites are drawn as tinted rectangles and text is greeked (one block per
+//! character), which reads fine at thumbnail sizes. [`Image::to_png`]
+//! compresses with a small fixed-Huffman deflate that finds the long runs of
+//! flat color in rendered scenes, so no compression library is needed.
+
+use glam::Vec2;
+
+use jugar_core::Rect;
+
+use crate::RenderCommand;
+
+/// Width of a greeked character relative to the text size
+const GREEK_ADVANCE: f32 = 0.55;
+
+/// RGBA8 image, rows top to bottom
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl Image {
+    /// Creates a transparent image
+    #[must_use]
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![0; width as usize * height as usize * 4],
+        }
+    }
+
+    /// Wraps RGBA8 pixels, e.g. read back from a canvas
+    ///
+    /// Returns `None` if the buffer isn't `width * height * 4` bytes.
+    #[must_use]
+    pub fn from_rgba(width: u32, height: u32, pixels: Vec<u8>) -> Option<Self> {
+        (pixels.len() == width as usize * height as usize * 4).then_some(Self {
+            width,
+            height,
+            pixels,
+        })
+    }
+
+    /// Nearest-neighbor copy no wider than `max_width`, keeping the aspect ratio
+    #[must_use]
+    pub fn downscaled(&self, max_width: u32) -> Self {
+        if self.width <= max_width || max_width == 0 {
+            return self.clone();
+        }
+        let height =
+            (u64::from(self.height) * u64::from(max_width) / u64::from(self.width)).max(1) as u32;
+        let mut image = Self::new(max_width, height);
+        for y in 0..height {
+            let source_y = (u64::from(y) * u64::from(self.height) / u64::from(height)) as usize;
+            for x in 0..max_width {
+                let source_x =
+                    (u64::from(x) * u64::from(self.width) / u64::from(max_width)) as usize;
+                let from = (source_y * self.width as usize + source_x) * 4;
+                let to = (y as usize * max_width as usize + x as usize) * 4;
+                image.pixels[to..to + 4].copy_from_slice(&self.pixels[from..from + 4]);
+            }
+        }
+        image
+    }
+
+    /// Width in pixels
+    #[must_use]
+    pub const fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Height in pixels
+    #[must_use]
+    pub const fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Raw RGBA8 pixels
+    #[must_use]
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// Color of one pixel
+    #[must_use]
+    pub fn pixel(&self, x: u32, y: u32) -> Option<[u8; 4]> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let i = (y as usize * self.width as usize + x as usize) * 4;
+        Some([
+            self.pixels[i],
+            self.pixels[i + 1],
+            self.pixels[i + 2],
+            self.pixels[i + 3],
+        ])
+    }
+
+    /// Blends a color over one pixel (source-over)
+    fn blend(&mut self, x: u32, y: u32, color: [f32; 4]) {
+        let alpha = color[3].clamp(0.0, 1.0);
+        if alpha <= 0.0 {
+            return;
+        }
+        let i = (y as usize * self.width as usize + x as usize) * 4;
+        for (channel, &source) in color.iter().take(3).enumerate() {
+            let dst = f32::from(self.pixels[i + channel]) / 255.0;
+            let out = source.clamp(0.0, 1.0).mul_add(alpha, dst * (1.0 - alpha));
+            self.pixels[i + channel] = (out * 255.0).round() as u8;
+        }
+        let dst_alpha = f32::from(self.pixels[i + 3]) / 255.0;
+        let out_alpha = dst_alpha.mul_add(1.0 - alpha, alpha);
+        self.pixels[i + 3] = (out_alpha * 255.0).round() as u8;
+    }
+
+    /// Encodes the image as PNG
+    #[must_use]
+    pub fn to_png(&self) -> Vec<u8> {
+        let mut png = self.png_start();
+        png_chunk(&mut png, *b"IDAT", &self.zlib());
+        png_chunk(&mut png, *b"IEND", &[]);
+        png
+    }
+
+    /// PNG signature and header chunk for an image of this size
+    pub(crate) fn png_start(&self) -> Vec<u8> {
+        let mut header = Vec::with_capacity(13);
+        header.extend_from_slice(&self.width.to_be_bytes());
+        header.extend_from_slice(&self.height.to_be_bytes());
+        header.extend_from_slice(&[8, 6, 0, 0, 0]);
+
+        let mut png = vec![0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+        png_chunk(&mut png, *b"IHDR", &header);
+        png
+    }
+
+    /// Pixel rows as a zlib stream (the contents of a PNG `IDAT` chunk)
+    pub(crate) fn zlib(&self) -> Vec<u8> {
+        // Each row starts with filter type 0 (none)
+        let row_len = self.width as usize * 4;
+        let mut raw = Vec::with_capacity((row_len + 1) * self.height as usize);
+        for row in self.pixels.chunks(row_len.max(1)) {
+            raw.push(0);
+            raw.extend_from_slice(row);
+        }
+
+        let mut zlib = vec![0x78, 0x01];
+        zlib.extend(deflate(&raw, &[4, row_len + 1]));
+        zlib.extend_from_slice(&adler32(&raw).to_be_bytes());
+        zlib
+    }
+}
+
+pub fn png_chunk(png: &mut Vec<u8>, kind: [u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend_from_slice(&kind);
+    png.extend_from_slice(data);
+    let crc = crc32(&png[start..]);
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFF_u32;
+    for &byte in bytes {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+/// Base match lengths of deflate length codes 257-285
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+
+/// Extra bits of deflate length codes 257-285
+const LENGTH_EXTRA: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+
+/// Base distances of deflate distance codes 0-29
+const DISTANCE_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+
+/// Extra bits of deflate distance codes 0-29
+const DISTANCE_EXTRA: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+
+/// Longest deflate match
+const MAX_MATCH: usize = 258;
+
+/// Farthest deflate match distance
+const MAX_DISTANCE: usize = 32_768;
+
+/// Least-significant-bit-first bit writer
+#[derive(Debug, Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    count: u32,
+}
+
+impl BitWriter {
+    fn bits(&mut self, value: u32, count: u32) {
+        self.buffer |= value << self.count;
+        self.count += count;
+        while self.count >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.count -= 8;
+        }
+    }
+
+    /// Writes a Huffman code, which deflate stores most significant bit first
+    fn code(&mut self, code: u32, length: u32) {
+        self.bits(code.reverse_bits() >> (32 - length), length);
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.count > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+        self.bytes
+    }
+
+    /// Writes a literal/length symbol with the fixed Huffman code
+    fn symbol(&mut self, symbol: u16) {
+        let symbol = u32::from(symbol);
+        match symbol {
+            0..=143 => self.code(0x30 + symbol, 8),
+            144..=255 => self.code(0x190 + symbol - 144, 9),
+            256..=279 => self.code(symbol - 256, 7),
+            _ => self.code(0xC0 + symbol - 280, 8),
+        }
+    }
+
+    fn matched(&mut self, length: usize, distance: usize) {
+        let l = LENGTH_BASE
+            .iter()
+            .rposition(|&base| usize::from(base) <= length)
+            .unwrap_or(0);
+        self.symbol(257 + l as u16);
+        self.bits(
+            (length - usize::from(LENGTH_BASE[l])) as u32,
+            u32::from(LENGTH_EXTRA[l]),
+        );
+        let d = DISTANCE_BASE
+            .iter()
+            .rposition(|&base| usize::from(base) <= distance)
+            .unwrap_or(0);
+        self.code(d as u32, 5);
+        self.bits(
+            (distance - usize::from(DISTANCE_BASE[d])) as u32,
+            u32::from(DISTANCE_EXTRA[d]),
+        );
+    }
+}
+
+/// Compresses with one fixed-Huffman deflate block
+///
+/// Matches are only searched at the given distances (one pixel back and one
+/// row up for images), which finds the long runs of flat color in rendered
+/// scenes without a hash table.
+fn deflate(data: &[u8], distances: &[usize]) -> Vec<u8> {
+    let mut writer = BitWriter::default();
+    writer.bits(1, 1); // final block
+    writer.bits(1, 2); // fixed Huffman codes
+    let mut i = 0;
+    while i < data.len() {
+        let best = distances
+            .iter()
+            .filter(|&&distance| distance <= i && distance <= MAX_DISTANCE)
+            .map(|&distance| {
+                let limit = MAX_MATCH.min(data.len() - i);
+                let length = (0..limit)
+                    .take_while(|&k| data[i + k] == data[i + k - distance])
+                    .count();
+                (length, distance)
+            })
+            .max_by_key(|&(length, _)| length);
+        match best {
+            Some((length, distance)) if length >= 3 => {
+                writer.matched(length, distance);
+                i += length;
+            }
+            _ => {
+                writer.symbol(u16::from(data[i]));
+                i += 1;
+            }
+        }
+    }
+    writer.symbol(256);
+    writer.finish()
+}
+
+fn adler32(bytes: &[u8]) -> u32 {
+    let (mut a, mut b) = (1_u32, 0_u32);
+    for &byte in bytes {
+        a = (a + u32::from(byte)) % 65_521;
+        b = (b + a) % 65_521;
+    }
+    (b << 16) | a
+}
+
+/// Distance from a point to a line segment
+fn segment_distance(point: Vec2, start: Vec2, end: Vec2) -> f32 {
+    let along = end - start;
+    let length_squared = along.length_squared();
+    if length_squared <= f32::EPSILON {
+        return point.distance(start);
+    }
+    let t = ((point - start).dot(along) / length_squared).clamp(0.0, 1.0);
+    point.distance(start + along * t)
+}
+
+/// Even-odd point-in-polygon test
+fn inside_polygon(point: Vec2, points: &[Vec2]) -> bool {
+    let mut inside = false;
+    let mut previous = points.len().saturating_sub(1);
+    for (i, a) in points.iter().enumerate() {
+        let b = points[previous];
+        if (a.y > point.y) != (b.y > point.y)
+            && point.x < (b.x - a.x) * (point.y - a.y) / (b.y - a.y) + a.x
+        {
+            inside = !inside;
+        }
+        previous = i;
+    }
+    inside
+}
+
+/// CPU renderer for [`RenderCommand`]s
+#[derive(Debug, Clone)]
+pub struct SoftwareRasterizer {
+    image: Image,
+    scale: Vec2,
+    scissors: Vec<Rect>,
+    /// Open opacity groups, innermost last
+    opacity: Vec<f32>,
+}
+
+impl SoftwareRasterizer {
+    /// Creates a rasterizer drawing in image pixels
+    #[must_use]
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            image: Image::new(width, height),
+            scale: Vec2::ONE,
+            scissors: Vec::new(),
+            opacity: Vec::new(),
+        }
+    }
+
+    /// Scales a scene of the given size to fill the image
+    #[must_use]
+    pub fn with_scene_size(mut self, scene: Vec2) -> Self {
+        self.scale = Vec2::new(
+            self.image.width as f32 / scene.x.max(1.0),
+            self.image.height as f32 / scene.y.max(1.0),
+        );
+        self
+    }
+
+    /// The image drawn so far
+    #[must_use]
+    pub const fn image(&self) -> &Image {
+        &self.image
+    }
+
+    /// Consumes the rasterizer, returning the image
+    #[must_use]
+    pub fn into_image(self) -> Image {
+        self.image
+    }
+
+    /// Draws commands in order
+    ///
+    /// Vignettes and screen filters are ignored.
+    pub fn draw(&mut self, commands: &[RenderCommand]) {
+        for command in commands {
+            self.draw_command(command);
+        }
+    }
+
+    fn draw_command(&mut self, command: &RenderCommand) {
+        let scene = Vec2::new(self.image.width as f32, self.image.height as f32) / self.scale;
+        match command {
+            RenderCommand::Clear { color } => {
+                for pixel in self.image.pixels.chunks_mut(4) {
+                    for (channel, value) in color.iter().enumerate() {
+                        pixel[channel] = (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+                    }
+                }
+            }
+            RenderCommand::ScreenOverlay { color } => {
+                self.fill_rect(Rect::new(0.0, 0.0, scene.x, scene.y), *color);
+            }
+            RenderCommand::DrawRect { rect, color } => self.fill_rect(*rect, *color),
+            RenderCommand::DrawSprite {
+                position,
+                size,
+                color,
+                params,
+                ..
+            } => {
+                let corners = params.corners(position.as_vec2(), *size);
+                if let Some(bounds) = bounds_of(&corners, 0.0) {
+                    self.fill_where(bounds, params.flashed(*color), |p| {
+                        inside_polygon(p, &corners)
+                    });
+                }
+            }
+            RenderCommand::DrawCircle {
+                center,
+                radius,
+                color,
+                outline,
+            } => {
+                let (center, radius) = (*center, *radius);
+                let inner = outline.map_or(0.0, |width| (radius - width).max(0.0));
+                let bounds = Rect::new(
+                    center.x - radius,
+                    center.y - radius,
+                    radius * 2.0,
+                    radius * 2.0,
+                );
+                self.fill_where(bounds, *color, |p| {
+                    let d = p.distance(center);
+                    d <= radius && (outline.is_none() || d >= inner)
+                });
+            }
+            RenderCommand::DrawLine {
+                start,
+                end,
+                width,
+                color,
+            } => self.stroke(&[*start, *end], *width, *color, false),
+            RenderCommand::DrawPolyline {
+                points,
+                width,
+                color,
+                closed,
+            } => self.stroke(points, *width, *color, *closed),
+            RenderCommand::DrawPolygon {
+                points,
+                color,
+                outline,
+            } => {
+                if let Some(width) = outline {
+                    self.stroke(points, *width, *color, true);
+                } else if let Some(bounds) = bounds_of(points, 0.0) {
+                    self.fill_where(bounds, *color, |p| inside_polygon(p, points));
+                }
+            }
+            RenderCommand::DrawText {
+                text,
+                position,
+                size,
+                color,
+            } => {
+                let advance = size * GREEK_ADVANCE;
+                for (i, ch) in text.chars().enumerate() {
+                    if !ch.is_whitespace() {
+                        let x = (i as f32).mul_add(advance, position.x);
+                        let y = size.mul_add(0.2, position.y);
+                        let block = Rect::new(x, y, advance * 0.8, size * 0.7);
+                        self.fill_rect(block, *color);
+                    }
+                }
+            }
+            RenderCommand::PushScissor { rect } => self.scissors.push(*rect),
+            RenderCommand::PopScissor => {
+                let _ = self.scissors.pop();
+            }
+            RenderCommand::PushOpacity { alpha } => self.opacity.push(*alpha),
+            RenderCommand::PopOpacity => {
+                let _ = self.opacity.pop();
+            }
+            RenderCommand::Vignette { .. } | RenderCommand::Filter { .. } => {}
+        }
+    }
+
+    fn fill_rect(&mut self, rect: Rect, color: [f32; 4]) {
+        self.fill_where(rect, color, |_| true);
+    }
+
+    fn stroke(&mut self, points: &[Vec2], width: f32, color: [f32; 4], closed: bool) {
+        let half = (width / 2.0).max(0.5 / self.scale.min_element());
+        let Some(bounds) = bounds_of(points, half) else {
+            return;
+        };
+        let mut segments: Vec<(Vec2, Vec2)> = points.windows(2).map(|w| (w[0], w[1])).collect();
+        if closed && points.len() > 2 {
+            segments.push((points[points.len() - 1], points[0]));
+        }
+        self.fill_where(bounds, color, |p| {
+            segments
+                .iter()
+                .any(|&(a, b)| segment_distance(p, a, b) <= half)
+        });
+    }
+
+    /// Blends `color` into every pixel in `bounds` (scene units) whose
+    /// center passes `covers`
+    fn fill_where(&mut self, bounds: Rect, color: [f32; 4], covers: impl Fn(Vec2) -> bool) {
+        let clip = self.scissors.last().copied();
+        let mut color = color;
+        color[3] *= self.opacity.iter().product::<f32>();
+        let x0 = (bounds.x * self.scale.x).floor().max(0.0) as u32;
+        let y0 = (bounds.y * self.scale.y).floor().max(0.0) as u32;
+        let x1 = ((bounds.x + bounds.width) * self.scale.x)
+            .ceil()
+            .clamp(0.0, self.image.width as f32) as u32;
+        let y1 = ((bounds.y + bounds.height) * self.scale.y)
+            .ceil()
+            .clamp(0.0, self.image.height as f32) as u32;
+        for y in y0..y1 {
+            for x in x0..x1 {
+                let p = Vec2::new(x as f32 + 0.5, y as f32 + 0.5) / self.scale;
+                let in_bounds = p.x >= bounds.x
+                    && p.x < bounds.x + bounds.width
+                    && p.y >= bounds.y
+                    && p.y < bounds.y + bounds.height;
+                let clipped = clip.is_some_and(|c| {
+                    p.x < c.x || p.x >= c.x + c.width || p.y < c.y || p.y >= c.y + c.height
+                });
+                if in_bounds && !clipped && covers(p) {
+                    self.image.blend(x, y, color);
+                }
+            }
+        }
+    }
+}
+
+/// Bounding box of points grown by `margin`
+fn bounds_of(points: &[Vec2], margin: f32) -> Option<Rect> {
+    let first = *points.first()?;
+    let (min, max) = points
+        .iter()
+        .fold((first, first), |(min, max), &p| (min.min(p), max.max(p)));
+    Some(Rect::new(
+        min.x - margin,
+        min.y - margin,
+        margin.mul_add(2.0, max.x - min.x),
+        margin.mul_add(2.0, max.y - min.y),
+    ))
+}
+
+/// Renders commands for a scene of `scene` size into a `width` x `height` image
+#[must_use]
+pub fn rasterize(commands: &[RenderCommand], scene: Vec2, width: u32, height: u32) -> Image {
+    let mut rasterizer = SoftwareRasterizer::new(width, height).with_scene_size(scene);
+    rasterizer.draw(commands);
+    rasterizer.into_image()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::{SpriteParams, TextureFilter};
+    use jugar_core::Position;
+
+    const RED: [f32; 4] = [1.0, 0.0, 0.0, 1.0];
+    const BLUE: [f32; 4] = [0.0, 0.0, 1.0, 1.0];
+
+    #[test]
+    fn test_clear_and_rect() {
+        let commands = [
+            RenderCommand::Clear { color: BLUE },
+            RenderCommand::DrawRect {
+                rect: Rect::new(2.0, 2.0, 4.0, 4.0),
+                color: RED,
+            },
+        ];
+        let image = rasterize(&commands, Vec2::new(8.0, 8.0), 8, 8);
+        assert_eq!(image.pixel(0, 0), Some([0, 0, 255, 255]));
+        assert_eq!(image.pixel(3, 3), Some([255, 0, 0, 255]));
+        assert_eq!(image.pixel(6, 6), Some([0, 0, 255, 255]));
+        assert_eq!(image.pixel(8, 0), None);
+    }
+
+    #[test]
+    fn test_scene_is_scaled_to_image() {
+        let commands = [RenderCommand::DrawCircle {
+            center: Vec2::new(960.0, 540.0),
+            radius: 200.0,
+            color: RED,
+            outline: None,
+        }];
+        let image = rasterize(&commands, Vec2::new(1920.0, 1080.0), 192, 108);
+        assert_eq!(image.pixel(96, 54), Some([255, 0, 0, 255]));
+        assert_eq!(image.pixel(10, 10), Some([0, 0, 0, 0]));
+    }
+
+    #[test]
+    fn test_blending_and_scissor() {
+        let mut rasterizer = SoftwareRasterizer::new(4, 4);
+        rasterizer.draw(&[
+            RenderCommand::Clear {
+                color: [0.0, 0.0, 0.0, 1.0],
+            },
+            RenderCommand::PushScissor {
+                rect: Rect::new(0.0, 0.0, 2.0, 4.0),
+            },
+            RenderCommand::DrawRect {
+                rect: Rect::new(0.0, 0.0, 4.0, 4.0),
+                color: [1.0, 1.0, 1.0, 0.5],
+            },
+            RenderCommand::PopScissor,
+        ]);
+        let image = rasterizer.image();
+        assert_eq!(image.pixel(1, 1), Some([128, 128, 128, 255]));
+        assert_eq!(image.pixel(3, 1), Some([0, 0, 0, 255]));
+    }
+
+    #[test]
+    fn test_polygon_and_line() {
+        let commands = [
+            RenderCommand::DrawPolygon {
+                points: vec![
+                    Vec2::new(0.0, 0.0),
+                    Vec2::new(10.0, 0.0),
+                    Vec2::new(0.0, 10.0),
+                ],
+                color: RED,
+                outline: None,
+            },
+            RenderCommand::DrawLine {
+                start: Vec2::new(0.0, 15.0),
+                end: Vec2::new(20.0, 15.0),
+                width: 2.0,
+                color: BLUE,
+            },
+        ];
+        let image = rasterize(&commands, Vec2::new(20.0, 20.0), 20, 20);
+        assert_eq!(image.pixel(1, 1), Some([255, 0, 0, 255]));
+        assert_eq!(image.pixel(9, 9), Some([0, 0, 0, 0]));
+        assert_eq!(image.pixel(10, 15), Some([0, 0, 255, 255]));
+    }
+
+    #[test]
+    fn test_sprite_flash_rotation_and_opacity_group() {
+        let sprite = |x: f32, params: SpriteParams| RenderCommand::DrawSprite {
+            texture_id: 0,
+            position: Position::new(x, 4.0),
+            size: Vec2::new(8.0, 2.0),
+            source: None,
+            color: [1.0, 0.0, 0.0, 1.0],
+            filter: TextureFilter::Nearest,
+            params,
+        };
+        let commands = [
+            sprite(0.0, SpriteParams::new().with_flash(1.0)),
+            // A quarter turn stands the 8x2 bar upright around its center
+            sprite(
+                12.0,
+                SpriteParams::new().with_rotation(core::f32::consts::FRAC_PI_2),
+            ),
+            RenderCommand::PushOpacity { alpha: 0.5 },
+            RenderCommand::PushOpacity { alpha: 0.5 },
+            RenderCommand::DrawRect {
+                rect: Rect::new(0.0, 10.0, 2.0, 2.0),
+                color: [1.0, 1.0, 1.0, 1.0],
+            },
+            RenderCommand::PopOpacity,
+            RenderCommand::PopOpacity,
+            RenderCommand::DrawRect {
+                rect: Rect::new(4.0, 10.0, 2.0, 2.0),
+                color: [1.0, 1.0, 1.0, 1.0],
+            },
+        ];
+        let image = rasterize(&commands, Vec2::new(24.0, 16.0), 24, 16);
+        assert_eq!(image.pixel(3, 4), Some([255, 255, 255, 255]));
+        assert_eq!(image.pixel(16, 1), Some([255, 0, 0, 255]));
+        assert_eq!(image.pixel(12, 4), Some([0, 0, 0, 0]));
+        assert_eq!(image.pixel(1, 11), Some([64, 64, 64, 64]));
+        assert_eq!(image.pixel(5, 11), Some([255, 255, 255, 255]));
+    }
+
+    #[test]
+    fn test_png_encoding() {
+        let png = Image::new(3, 2).to_png();
+        assert_eq!(&png[..8], &[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A]);
+        assert_eq!(&png[12..16], b"IHDR");
+        assert_eq!(&png[16..20], &3_u32.to_be_bytes());
+        assert_eq!(&png[png.len() - 8..png.len() - 4], b"IEND");
+        // Known check values
+        assert_eq!(crc32(b"IEND"), 0xAE42_6082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11E6_0398);
+    }
+}
@@ -0,0 +1,191 @@
+//! Palette remapping for queued draw commands.
+//!
+//! A game draws with the colors of its own palette (the kid crayons by
+//! default). [`ColorRemap`] remembers that palette and, once an active
+//! palette is chosen (colorblind-safe, high contrast), rewrites every queued
+//! command whose color matches a game color by name. Games need no code of
+//! their own for it; the engine runs the remap just before presenting.
+
+use crate::{Color, Palette, RenderCommand, RenderQueue};
+
+/// Largest per-channel difference still treated as the same color
+///
+/// Allows for colors that went through 8-bit hex strings.
+const MATCH_TOLERANCE: f32 = 1.0 / 255.0;
+
+/// Swaps a game's registered colors for an active palette
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorRemap {
+    game: Palette,
+    swaps: Vec<(Color, Color)>,
+}
+
+impl Default for ColorRemap {
+    fn default() -> Self {
+        Self::new(Palette::crayons())
+    }
+}
+
+impl ColorRemap {
+    /// Creates a remap for a game's palette; nothing changes until
+    /// [`ColorRemap::set_active`] is called
+    #[must_use]
+    pub const fn new(game: Palette) -> Self {
+        Self {
+            game,
+            swaps: Vec::new(),
+        }
+    }
+
+    /// The game's registered colors
+    #[must_use]
+    pub const fn game_palette(&self) -> &Palette {
+        &self.game
+    }
+
+    /// Registers (or replaces) a named game color
+    ///
+    /// Call [`ColorRemap::set_active`] again afterwards.
+    pub fn register(&mut self, name: &str, color: Color) {
+        self.game.set(name, color);
+    }
+
+    /// Maps each game color to the active palette's color of the same name
+    ///
+    /// Names missing from the active palette keep their game color.
+    pub fn set_active(&mut self, active: &Palette) {
+        self.swaps = self
+            .game
+            .names()
+            .filter_map(|name| {
+                let from = self.game.get(name)?;
+                let to = active.get(name)?;
+                if same_rgb(from, to) {
+                    None
+                } else {
+                    Some((from, to))
+                }
+            })
+            .collect();
+    }
+
+    /// Returns true if no color changes
+    #[must_use]
+    pub fn is_identity(&self) -> bool {
+        self.swaps.is_empty()
+    }
+
+    /// Remaps one color, keeping its alpha
+    #[must_use]
+    pub fn map(&self, rgba: [f32; 4]) -> [f32; 4] {
+        let color = Color::from_array(rgba);
+        self.swaps
+            .iter()
+            .find(|(from, _)| same_rgb(*from, color))
+            .map_or(rgba, |(_, to)| to.with_alpha(color.a).to_array())
+    }
+
+    /// Rewrites the colors of every queued command
+    pub fn apply(&self, queue: &mut RenderQueue) {
+        if !self.is_identity() {
+            queue.map_colors(|color| self.map(color));
+        }
+    }
+}
+
+impl RenderCommand {
+    /// The command's color, if it has one
+    pub fn color_mut(&mut self) -> Option<&mut [f32; 4]> {
+        match self {
+            Self::Clear { color }
+            | Self::DrawSprite { color, .. }
+            | Self::DrawRect { color, .. }
+            | Self::DrawCircle { color, .. }
+            | Self::DrawLine { color, .. }
+            | Self::DrawPolygon { color, .. }
+            | Self::DrawPolyline { color, .. }
+            | Self::DrawText { color, .. }
+            | Self::ScreenOverlay { color }
+            | Self::Vignette { color, .. } => Some(color),
+            Self::PushScissor { .. }
+            | Self::PopScissor
+            | Self::PushOpacity { .. }
+            | Self::PopOpacity
+            | Self::Filter { .. } => None,
+        }
+    }
+}
+
+fn same_rgb(a: Color, b: Color) -> bool {
+    (a.r - b.r).abs() <= MATCH_TOLERANCE
+        && (a.g - b.g).abs() <= MATCH_TOLERANCE
+        && (a.b - b.b).abs() <= MATCH_TOLERANCE
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::float_cmp)]
+mod tests {
+    use glam::Vec2;
+    use jugar_core::{AccessibilitySettings, ColorVision, Rect};
+
+    use super::*;
+
+    fn colorblind() -> Palette {
+        Palette::colors_for(
+            &AccessibilitySettings::new().with_color_vision(ColorVision::Deuteranopia),
+        )
+    }
+
+    #[test]
+    fn test_apply_swaps_registered_colors() {
+        let crayons = Palette::crayons();
+        let red = crayons.get("red").unwrap();
+        let mut remap = ColorRemap::default();
+        remap.set_active(&colorblind());
+
+        let mut queue = RenderQueue::new();
+        queue.push(RenderCommand::DrawRect {
+            rect: Rect::new(0.0, 0.0, 10.0, 10.0),
+            color: red.with_alpha(0.5).to_array(),
+        });
+        queue.text("hi", Vec2::ZERO, 16.0, [0.3, 0.3, 0.3, 1.0]);
+        queue.push(RenderCommand::PopScissor);
+        remap.apply(&mut queue);
+
+        let expected = colorblind().get("red").unwrap().with_alpha(0.5).to_array();
+        assert!(matches!(
+            &queue.commands()[0],
+            RenderCommand::DrawRect { color, .. } if *color == expected
+        ));
+        assert!(matches!(
+            &queue.commands()[1],
+            RenderCommand::DrawText { color, .. } if *color == [0.3, 0.3, 0.3, 1.0]
+        ));
+    }
+
+    #[test]
+    fn test_same_palette_is_identity() {
+        let mut remap = ColorRemap::default();
+        remap.set_active(&Palette::crayons());
+        assert!(remap.is_identity());
+
+        remap.set_active(&colorblind());
+        assert!(!remap.is_identity());
+        // White is the same in both palettes
+        assert_eq!(remap.map(Color::WHITE.to_array()), Color::WHITE.to_array());
+    }
+
+    #[test]
+    fn test_registered_game_colors() {
+        let mut remap = ColorRemap::new(Palette::new("game"));
+        remap.register("green", Color::rgb(0.0, 1.0, 0.0));
+        remap.register("lava", Color::rgb(1.0, 0.3, 0.0));
+        remap.set_active(&colorblind());
+
+        let green = colorblind().get("green").unwrap().to_array();
+        assert_eq!(remap.map(Color::GREEN.to_array()), green);
+        let lava = Color::rgb(1.0, 0.3, 0.0).to_array();
+        assert_eq!(remap.map(lava), lava);
+        assert_eq!(remap.game_palette().len(), 2);
+    }
+}
@@ -0,0 +1,805 @@
+//! Minimal SVG import for kid-drawn sprites.
+//!
+//! Simple drawing tools export SVG; [`parse_svg`] reads the subset they
+//! produce (`rect`, `circle`, `ellipse`, `polygon`, `polyline` and `path`
+//! with solid fills) into a [`VectorDrawing`]. Curves are flattened into
+//! polygons, so a drawing can be triangulated into a [`VectorMesh`] or
+//! rasterized into sprite [`Image`]s at several sizes. Strokes, gradients,
+//! text and transforms are ignored, arcs become straight lines, and each
+//! subpath is filled on its own (holes are not cut). A [`SpriteLibrary`]
+//! keeps drawings by name so a Level 3 game's `sprite: dragon` can use them.
+
+use alloc::collections::BTreeMap;
+
+use glam::Vec2;
+
+use crate::raster::{Image, SoftwareRasterizer};
+use crate::{RenderCommand, RenderError, Result};
+
+/// Line segments per flattened curve
+const CURVE_SEGMENTS: usize = 8;
+
+/// Line segments per circle or ellipse
+const ELLIPSE_SEGMENTS: usize = 32;
+
+/// Most outline points accepted from one drawing
+pub const MAX_SVG_POINTS: usize = 20_000;
+
+const BLACK: [f32; 4] = [0.0, 0.0, 0.0, 1.0];
+
+/// One filled outline
+#[derive(Debug, Clone, PartialEq)]
+pub struct VectorShape {
+    /// Outline in drawing units, relative to the drawing's top-left
+    pub points: Vec<Vec2>,
+    /// Fill color
+    pub fill: [f32; 4],
+}
+
+/// Shapes imported from an SVG file, back to front
+#[derive(Debug, Clone, PartialEq)]
+pub struct VectorDrawing {
+    /// Drawing size (from `viewBox`, or `width`/`height`)
+    pub size: Vec2,
+    /// Filled shapes in paint order
+    pub shapes: Vec<VectorShape>,
+}
+
+/// Triangles ready for upload, one color per vertex
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VectorMesh {
+    /// Vertex positions in drawing units
+    pub vertices: Vec<Vec2>,
+    /// Vertex colors
+    pub colors: Vec<[f32; 4]>,
+    /// Three indices per triangle
+    pub indices: Vec<u32>,
+}
+
+impl VectorMesh {
+    /// Number of triangles
+    #[must_use]
+    pub fn triangle_count(&self) -> usize {
+        self.indices.len() / 3
+    }
+}
+
+impl VectorDrawing {
+    /// Draw commands placing the drawing at `origin`, scaled by `scale`
+    #[must_use]
+    pub fn commands(&self, origin: Vec2, scale: f32) -> Vec<RenderCommand> {
+        self.shapes
+            .iter()
+            .map(|shape| RenderCommand::DrawPolygon {
+                points: shape.points.iter().map(|&p| origin + p * scale).collect(),
+                color: shape.fill,
+                outline: None,
+            })
+            .collect()
+    }
+
+    /// Triangulates every shape (ear clipping)
+    #[must_use]
+    pub fn triangulate(&self) -> VectorMesh {
+        let mut mesh = VectorMesh::default();
+        for shape in &self.shapes {
+            let base = mesh.vertices.len() as u32;
+            for [a, b, c] in triangulate_polygon(&shape.points) {
+                mesh.indices
+                    .extend([base + a as u32, base + b as u32, base + c as u32]);
+            }
+            mesh.vertices.extend_from_slice(&shape.points);
+            mesh.colors.extend(shape.points.iter().map(|_| shape.fill));
+        }
+        mesh
+    }
+
+    /// Rasterizes a sprite `width` pixels wide, keeping the aspect ratio
+    #[must_use]
+    pub fn rasterize(&self, width: u32) -> Image {
+        let aspect = self.size.y / self.size.x;
+        let height = (width as f32 * aspect).round().max(1.0) as u32;
+        let mut rasterizer = SoftwareRasterizer::new(width, height).with_scene_size(self.size);
+        rasterizer.draw(&self.commands(Vec2::ZERO, 1.0));
+        rasterizer.into_image()
+    }
+
+    /// Rasterizes one sprite per requested width (e.g. 32, 64, 128)
+    #[must_use]
+    pub fn sprite_set(&self, widths: &[u32]) -> Vec<Image> {
+        widths.iter().map(|&width| self.rasterize(width)).collect()
+    }
+}
+
+/// Imported drawings by name
+#[derive(Debug, Clone, Default)]
+pub struct SpriteLibrary {
+    drawings: BTreeMap<String, VectorDrawing>,
+}
+
+impl SpriteLibrary {
+    /// Creates an empty library
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses an SVG file and stores it under `name`, replacing any previous one
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RenderError::InvalidSvg`] if the file can't be imported
+    pub fn import(&mut self, name: impl Into<String>, svg: &str) -> Result<()> {
+        let drawing = parse_svg(svg)?;
+        let _ = self.drawings.insert(name.into(), drawing);
+        Ok(())
+    }
+
+    /// Looks up a drawing
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&VectorDrawing> {
+        self.drawings.get(name)
+    }
+
+    /// Rasterizes a named drawing
+    #[must_use]
+    pub fn sprite(&self, name: &str, width: u32) -> Option<Image> {
+        self.get(name).map(|drawing| drawing.rasterize(width))
+    }
+
+    /// Names of all drawings, sorted
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.drawings.keys().map(String::as_str)
+    }
+
+    /// Number of drawings
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.drawings.len()
+    }
+
+    /// Returns true if the library is empty
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.drawings.is_empty()
+    }
+}
+
+fn invalid(message: &str) -> RenderError {
+    RenderError::InvalidSvg(message.to_string())
+}
+
+/// Parses the supported SVG subset
+///
+/// # Errors
+///
+/// Returns [`RenderError::InvalidSvg`] if there is no `<svg>` element with a
+/// size, a tag or path is malformed, or the drawing has more than
+/// [`MAX_SVG_POINTS`] points.
+pub fn parse_svg(source: &str) -> Result<VectorDrawing> {
+    let mut size = None;
+    let mut origin = Vec2::ZERO;
+    // Fill of each open <g>; None means `fill="none"`
+    let mut groups: Vec<Option<[f32; 4]>> = Vec::new();
+    let mut shapes = Vec::new();
+    let mut point_count = 0;
+
+    let mut rest = source;
+    while let Some(open) = rest.find('<') {
+        rest = &rest[open + 1..];
+        if let Some(comment) = rest.strip_prefix("!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+        let close = rest.find('>').ok_or_else(|| invalid("unclosed tag"))?;
+        let tag = &rest[..close];
+        rest = &rest[close + 1..];
+        if tag.starts_with('?') || tag.starts_with('!') {
+            continue;
+        }
+        if let Some(name) = tag.strip_prefix('/') {
+            if name.trim() == "g" {
+                let _ = groups.pop();
+            }
+            continue;
+        }
+
+        let self_closing = tag.ends_with('/');
+        let tag = tag.trim_end_matches('/');
+        let (name, attrs) = tag
+            .split_once(|c: char| c.is_ascii_whitespace())
+            .unwrap_or((tag, ""));
+        let attrs = parse_attributes(attrs);
+        let inherited = groups.last().copied().unwrap_or(Some(BLACK));
+        let fill = paint(&attrs, inherited);
+
+        let outlines = match name {
+            "svg" => {
+                let view_box = numbers(attribute(&attrs, "viewBox").unwrap_or_default());
+                if let [x, y, width, height] = view_box[..] {
+                    origin = Vec2::new(x, y);
+                    size = Some(Vec2::new(width, height));
+                } else {
+                    let width = length(&attrs, "width");
+                    let height = length(&attrs, "height");
+                    size = Some(Vec2::new(width, height));
+                }
+                continue;
+            }
+            "g" => {
+                if !self_closing {
+                    groups.push(fill);
+                }
+                continue;
+            }
+            _ => match shape_outlines(name, &attrs)? {
+                Some(outlines) => outlines,
+                None => continue,
+            },
+        };
+
+        let Some(fill) = fill else {
+            continue;
+        };
+        for outline in outlines {
+            if outline.len() < 3 {
+                continue;
+            }
+            point_count += outline.len();
+            if point_count > MAX_SVG_POINTS {
+                return Err(invalid("drawing has too many points"));
+            }
+            shapes.push(VectorShape {
+                points: outline.into_iter().map(|p| p - origin).collect(),
+                fill,
+            });
+        }
+    }
+
+    let size = size.ok_or_else(|| invalid("not an SVG drawing"))?;
+    if size.x <= 0.0 || size.y <= 0.0 {
+        return Err(invalid("drawing has no size"));
+    }
+    Ok(VectorDrawing { size, shapes })
+}
+
+/// Outlines of a shape element, or `None` for elements that draw nothing
+fn shape_outlines(name: &str, attrs: &[(&str, &str)]) -> Result<Option<Vec<Vec<Vec2>>>> {
+    let outlines = match name {
+        "rect" => {
+            let (x, y) = (length(attrs, "x"), length(attrs, "y"));
+            let (w, h) = (length(attrs, "width"), length(attrs, "height"));
+            vec![vec![
+                Vec2::new(x, y),
+                Vec2::new(x + w, y),
+                Vec2::new(x + w, y + h),
+                Vec2::new(x, y + h),
+            ]]
+        }
+        "circle" => {
+            let r = length(attrs, "r");
+            vec![ellipse(
+                Vec2::new(length(attrs, "cx"), length(attrs, "cy")),
+                Vec2::splat(r),
+            )]
+        }
+        "ellipse" => vec![ellipse(
+            Vec2::new(length(attrs, "cx"), length(attrs, "cy")),
+            Vec2::new(length(attrs, "rx"), length(attrs, "ry")),
+        )],
+        "polygon" | "polyline" => {
+            let coords = numbers(attribute(attrs, "points").unwrap_or_default());
+            vec![coords
+                .chunks_exact(2)
+                .map(|pair| Vec2::new(pair[0], pair[1]))
+                .collect()]
+        }
+        "path" => parse_path(attribute(attrs, "d").unwrap_or_default())?,
+        _ => return Ok(None),
+    };
+    Ok(Some(outlines))
+}
+
+/// `name="value"` pairs of a tag
+fn parse_attributes(mut source: &str) -> Vec<(&str, &str)> {
+    let mut attrs = Vec::new();
+    while let Some((name, after)) = source.split_once('=') {
+        let after = after.trim_start();
+        let Some(quote) = after.chars().next().filter(|c| *c == '"' || *c == '\'') else {
+            break;
+        };
+        let Some((value, next)) = after[1..].split_once(quote) else {
+            break;
+        };
+        attrs.push((name.trim(), value));
+        source = next;
+    }
+    attrs
+}
+
+/// An attribute, or the same property inside `style`
+fn attribute<'a>(attrs: &[(&str, &'a str)], name: &str) -> Option<&'a str> {
+    attrs
+        .iter()
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| *value)
+        .or_else(|| {
+            let style = attrs.iter().find(|(key, _)| *key == "style")?.1;
+            style.split(';').find_map(|declaration| {
+                let (key, value) = declaration.split_once(':')?;
+                (key.trim() == name).then_some(value.trim())
+            })
+        })
+}
+
+/// A numeric attribute, ignoring units like `px`
+fn length(attrs: &[(&str, &str)], name: &str) -> f32 {
+    attribute(attrs, name)
+        .and_then(|value| numbers(value).first().copied())
+        .unwrap_or(0.0)
+}
+
+/// Fill color of an element, or `None` for `fill="none"`
+fn paint(attrs: &[(&str, &str)], inherited: Option<[f32; 4]>) -> Option<[f32; 4]> {
+    let mut fill = match attribute(attrs, "fill") {
+        Some("none") => None,
+        Some(value) => parse_color(value).or(inherited),
+        None => inherited,
+    }?;
+    for name in ["opacity", "fill-opacity"] {
+        if let Some(opacity) = attribute(attrs, name).and_then(|v| v.trim().parse::<f32>().ok()) {
+            fill[3] *= opacity.clamp(0.0, 1.0);
+        }
+    }
+    Some(fill)
+}
+
+/// `#rgb`, `#rrggbb` or a basic color name
+fn parse_color(value: &str) -> Option<[f32; 4]> {
+    let value = value.trim();
+    if let Some(hex) = value.strip_prefix('#') {
+        let digits: Vec<u8> = hex
+            .chars()
+            .map(|c| c.to_digit(16).map(|d| d as u8))
+            .collect::<Option<_>>()?;
+        let channel = |high: u8, low: u8| f32::from(high * 16 + low) / 255.0;
+        return match digits[..] {
+            [r, g, b] => Some([channel(r, r), channel(g, g), channel(b, b), 1.0]),
+            [r1, r2, g1, g2, b1, b2] => {
+                Some([channel(r1, r2), channel(g1, g2), channel(b1, b2), 1.0])
+            }
+            _ => None,
+        };
+    }
+    let rgb = match value.to_ascii_lowercase().as_str() {
+        "black" => [0.0, 0.0, 0.0],
+        "white" => [1.0, 1.0, 1.0],
+        "red" => [1.0, 0.0, 0.0],
+        "green" => [0.0, 0.5, 0.0],
+        "blue" => [0.0, 0.0, 1.0],
+        "yellow" => [1.0, 1.0, 0.0],
+        "orange" => [1.0, 0.65, 0.0],
+        "purple" => [0.5, 0.0, 0.5],
+        "pink" => [1.0, 0.75, 0.8],
+        "brown" => [0.65, 0.16, 0.16],
+        "gray" | "grey" => [0.5, 0.5, 0.5],
+        _ => return None,
+    };
+    Some([rgb[0], rgb[1], rgb[2], 1.0])
+}
+
+/// Every number in a list like `0,0 10 20` or `10-5.5.5`
+fn numbers(source: &str) -> Vec<f32> {
+    let mut lexer = Lexer::new(source);
+    let mut values = Vec::new();
+    loop {
+        lexer.skip_separators();
+        if lexer.at_end() {
+            break;
+        }
+        match lexer.number() {
+            Some(value) => values.push(value),
+            None => lexer.pos += 1,
+        }
+    }
+    values
+}
+
+/// Scanner over path data and number lists
+struct Lexer<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Lexer<'a> {
+    const fn new(source: &'a str) -> Self {
+        Self {
+            bytes: source.as_bytes(),
+            pos: 0,
+        }
+    }
+
+    const fn at_end(&self) -> bool {
+        self.pos >= self.bytes.len()
+    }
+
+    fn skip_separators(&mut self) {
+        while self
+            .bytes
+            .get(self.pos)
+            .is_some_and(|b| b.is_ascii_whitespace() || *b == b',')
+        {
+            self.pos += 1;
+        }
+    }
+
+    /// Consumes a path command letter, if one is next
+    fn command(&mut self) -> Option<u8> {
+        self.skip_separators();
+        let byte = *self.bytes.get(self.pos)?;
+        (byte.is_ascii_alphabetic() && byte != b'e' && byte != b'E').then(|| {
+            self.pos += 1;
+            byte
+        })
+    }
+
+    fn number(&mut self) -> Option<f32> {
+        self.skip_separators();
+        let start = self.pos;
+        let digits = |lexer: &mut Self| {
+            let from = lexer.pos;
+            while lexer.bytes.get(lexer.pos).is_some_and(u8::is_ascii_digit) {
+                lexer.pos += 1;
+            }
+            lexer.pos > from
+        };
+        if matches!(self.bytes.get(self.pos), Some(b'+' | b'-')) {
+            self.pos += 1;
+        }
+        let mut any = digits(self);
+        if self.bytes.get(self.pos) == Some(&b'.') {
+            self.pos += 1;
+            any |= digits(self);
+        }
+        if !any {
+            self.pos = start;
+            return None;
+        }
+        if matches!(self.bytes.get(self.pos), Some(b'e' | b'E')) {
+            let mark = self.pos;
+            self.pos += 1;
+            if matches!(self.bytes.get(self.pos), Some(b'+' | b'-')) {
+                self.pos += 1;
+            }
+            if !digits(self) {
+                self.pos = mark;
+            }
+        }
+        core::str::from_utf8(&self.bytes[start..self.pos])
+            .ok()?
+            .parse()
+            .ok()
+    }
+
+    fn required(&mut self) -> Result<f32> {
+        self.number()
+            .ok_or_else(|| invalid("path is missing a number"))
+    }
+
+    fn point(&mut self) -> Result<Vec2> {
+        Ok(Vec2::new(self.required()?, self.required()?))
+    }
+}
+
+/// Flattens path data into one outline per subpath
+fn parse_path(data: &str) -> Result<Vec<Vec<Vec2>>> {
+    let mut lexer = Lexer::new(data);
+    let mut outlines = Vec::new();
+    let mut current: Vec<Vec2> = Vec::new();
+    let mut pen = Vec2::ZERO;
+    let mut start = Vec2::ZERO;
+    let mut command: Option<u8> = None;
+    // Second control point of the previous C/S or control point of Q/T
+    let mut cubic_control: Option<Vec2> = None;
+    let mut quad_control: Option<Vec2> = None;
+
+    loop {
+        if let Some(letter) = lexer.command() {
+            command = Some(letter);
+        } else if lexer.at_end() {
+            break;
+        }
+        let Some(letter) = command else {
+            return Err(invalid("path must start with a command"));
+        };
+        let base = if letter.is_ascii_lowercase() {
+            pen
+        } else {
+            Vec2::ZERO
+        };
+        if current.is_empty() && !matches!(letter, b'M' | b'm') {
+            current.push(pen);
+        }
+        let (mut next_cubic, mut next_quad) = (None, None);
+
+        match letter.to_ascii_uppercase() {
+            b'M' => {
+                pen = base + lexer.point()?;
+                start = pen;
+                end_subpath(&mut outlines, &mut current);
+                current.push(pen);
+                // Extra coordinate pairs after a move are line segments
+                command = Some(if letter == b'm' { b'l' } else { b'L' });
+            }
+            b'L' => {
+                pen = base + lexer.point()?;
+                current.push(pen);
+            }
+            b'H' => {
+                pen.x = base.x + lexer.required()?;
+                current.push(pen);
+            }
+            b'V' => {
+                pen.y = base.y + lexer.required()?;
+                current.push(pen);
+            }
+            b'C' | b'S' => {
+                let first = if letter.eq_ignore_ascii_case(&b'C') {
+                    base + lexer.point()?
+                } else {
+                    cubic_control.map_or(pen, |control| pen * 2.0 - control)
+                };
+                let second = base + lexer.point()?;
+                let end = base + lexer.point()?;
+                current.extend(curve_steps().map(|t| {
+                    let u = 1.0 - t;
+                    pen * (u * u * u)
+                        + first * (3.0 * u * u * t)
+                        + second * (3.0 * u * t * t)
+                        + end * (t * t * t)
+                }));
+                next_cubic = Some(second);
+                pen = end;
+            }
+            b'Q' | b'T' => {
+                let control = if letter.eq_ignore_ascii_case(&b'Q') {
+                    base + lexer.point()?
+                } else {
+                    quad_control.map_or(pen, |control| pen * 2.0 - control)
+                };
+                let end = base + lexer.point()?;
+                current.extend(curve_steps().map(|t| {
+                    let u = 1.0 - t;
+                    pen * (u * u) + control * (2.0 * u * t) + end * (t * t)
+                }));
+                next_quad = Some(control);
+                pen = end;
+            }
+            b'A' => {
+                // Radii, rotation and flags are skipped; the arc becomes a line
+                for _ in 0..5 {
+                    let _ = lexer.required()?;
+                }
+                pen = base + lexer.point()?;
+                current.push(pen);
+            }
+            b'Z' => {
+                end_subpath(&mut outlines, &mut current);
+                pen = start;
+                command = None;
+            }
+            _ => return Err(invalid("unsupported path command")),
+        }
+        cubic_control = next_cubic;
+        quad_control = next_quad;
+    }
+    end_subpath(&mut outlines, &mut current);
+    Ok(outlines)
+}
+
+/// Moves a finished subpath into `outlines`; a lone point is dropped
+fn end_subpath(outlines: &mut Vec<Vec<Vec2>>, current: &mut Vec<Vec2>) {
+    if current.len() > 1 {
+        outlines.push(core::mem::take(current));
+    }
+    current.clear();
+}
+
+/// Curve parameters of the flattened points after the start point
+fn curve_steps() -> impl Iterator<Item = f32> {
+    (1..=CURVE_SEGMENTS).map(|i| i as f32 / CURVE_SEGMENTS as f32)
+}
+
+/// Points around an ellipse
+fn ellipse(center: Vec2, radii: Vec2) -> Vec<Vec2> {
+    (0..ELLIPSE_SEGMENTS)
+        .map(|i| {
+            let angle = i as f32 / ELLIPSE_SEGMENTS as f32 * core::f32::consts::TAU;
+            center + Vec2::new(angle.cos(), angle.sin()) * radii
+        })
+        .collect()
+}
+
+/// Twice the signed area (positive when the winding makes convex turns left)
+fn signed_area(points: &[Vec2]) -> f32 {
+    points
+        .iter()
+        .zip(points.iter().cycle().skip(1))
+        .map(|(a, b)| a.perp_dot(*b))
+        .sum()
+}
+
+fn in_triangle(p: Vec2, a: Vec2, b: Vec2, c: Vec2) -> bool {
+    (b - a).perp_dot(p - a) >= 0.0
+        && (c - b).perp_dot(p - b) >= 0.0
+        && (a - c).perp_dot(p - c) >= 0.0
+}
+
+/// Ear-clips a simple polygon into index triples
+///
+/// Self-intersecting outlines that run out of ears are finished as a fan.
+fn triangulate_polygon(points: &[Vec2]) -> Vec<[usize; 3]> {
+    let area = signed_area(points);
+    if points.len() < 3 || area.abs() <= f32::EPSILON {
+        return Vec::new();
+    }
+    let mut remaining: Vec<usize> = (0..points.len()).collect();
+    if area < 0.0 {
+        remaining.reverse();
+    }
+
+    let mut triangles = Vec::with_capacity(points.len() - 2);
+    while remaining.len() > 3 {
+        let n = remaining.len();
+        let ear = (0..n).find(|&i| {
+            let (prev, cur, next) = (
+                remaining[(i + n - 1) % n],
+                remaining[i],
+                remaining[(i + 1) % n],
+            );
+            let (a, b, c) = (points[prev], points[cur], points[next]);
+            (b - a).perp_dot(c - b) > 0.0
+                && !remaining
+                    .iter()
+                    .any(|&j| j != prev && j != cur && j != next && in_triangle(points[j], a, b, c))
+        });
+        let Some(i) = ear else {
+            break;
+        };
+        triangles.push([
+            remaining[(i + n - 1) % n],
+            remaining[i],
+            remaining[(i + 1) % n],
+        ]);
+        let _ = remaining.remove(i);
+    }
+    for k in 1..remaining.len().saturating_sub(1) {
+        triangles.push([remaining[0], remaining[k], remaining[k + 1]]);
+    }
+    triangles
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::float_cmp)]
+mod tests {
+    use super::*;
+
+    const DRAWING: &str = r##"<?xml version="1.0"?>
+<!-- made with a drawing app -->
+<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 100 50" width="200px">
+  <rect x="0" y="0" width="100" height="50" fill="#0000ff"/>
+  <g fill="yellow">
+    <circle cx="25" cy="25" r="10"/>
+    <path d="M60,10 l20,0 q5,15 -10,30 z" style="fill: #f00"/>
+  </g>
+  <g fill="none"><rect width="5" height="5"/></g>
+</svg>"##;
+
+    fn mesh_area(mesh: &VectorMesh) -> f32 {
+        mesh.indices
+            .chunks_exact(3)
+            .map(|t| {
+                let (a, b, c) = (
+                    mesh.vertices[t[0] as usize],
+                    mesh.vertices[t[1] as usize],
+                    mesh.vertices[t[2] as usize],
+                );
+                (b - a).perp_dot(c - a).abs() / 2.0
+            })
+            .sum()
+    }
+
+    #[test]
+    fn test_parse_subset() {
+        let drawing = parse_svg(DRAWING).unwrap();
+        assert_eq!(drawing.size, Vec2::new(100.0, 50.0));
+        assert_eq!(drawing.shapes.len(), 3);
+        assert_eq!(drawing.shapes[0].fill, [0.0, 0.0, 1.0, 1.0]);
+        // Group fill is inherited, style overrides it
+        assert_eq!(drawing.shapes[1].fill, [1.0, 1.0, 0.0, 1.0]);
+        assert_eq!(drawing.shapes[2].fill, [1.0, 0.0, 0.0, 1.0]);
+        assert_eq!(drawing.shapes[2].points[1], Vec2::new(80.0, 10.0));
+    }
+
+    #[test]
+    fn test_view_box_origin_is_removed() {
+        let svg = r#"<svg viewBox="10 10 20 20"><rect x="10" y="10" width="5" height="5"/></svg>"#;
+        let drawing = parse_svg(svg).unwrap();
+        assert_eq!(drawing.shapes[0].points[0], Vec2::ZERO);
+        assert_eq!(drawing.shapes[0].fill, BLACK);
+    }
+
+    #[test]
+    fn test_path_commands() {
+        let outlines = parse_path("M0 0H10V10h-10Z m20,0 l5-5 5,5 C30 10 25 15 20 10").unwrap();
+        assert_eq!(outlines.len(), 2);
+        assert_eq!(outlines[0].len(), 4);
+        assert_eq!(outlines[1][1], Vec2::new(25.0, -5.0));
+        assert_eq!(outlines[1].last(), Some(&Vec2::new(20.0, 10.0)));
+        assert!(parse_path("10 10").is_err());
+        assert!(parse_path("M0 0 L5").is_err());
+        assert!(parse_path("M0 0 B5 5").is_err());
+    }
+
+    #[test]
+    fn test_numbers_without_separators() {
+        assert_eq!(numbers("10-5.5.5 1e2"), vec![10.0, -5.5, 0.5, 100.0]);
+        assert_eq!(numbers("32px"), vec![32.0]);
+    }
+
+    #[test]
+    fn test_triangulate_concave() {
+        // L-shape: area 300
+        let drawing = VectorDrawing {
+            size: Vec2::new(20.0, 20.0),
+            shapes: vec![VectorShape {
+                points: vec![
+                    Vec2::new(0.0, 0.0),
+                    Vec2::new(10.0, 0.0),
+                    Vec2::new(10.0, 10.0),
+                    Vec2::new(20.0, 10.0),
+                    Vec2::new(20.0, 20.0),
+                    Vec2::new(0.0, 20.0),
+                ],
+                fill: BLACK,
+            }],
+        };
+        let mesh = drawing.triangulate();
+        assert_eq!(mesh.triangle_count(), 4);
+        assert!((mesh_area(&mesh) - 300.0).abs() < 0.01);
+        assert_eq!(mesh.colors.len(), mesh.vertices.len());
+    }
+
+    #[test]
+    fn test_triangulated_drawing_covers_its_shapes() {
+        let mesh = parse_svg(DRAWING).unwrap().triangulate();
+        // Background rect alone is 5000
+        assert!(mesh_area(&mesh) > 5000.0);
+    }
+
+    #[test]
+    fn test_rasterize_sizes() {
+        let drawing = parse_svg(DRAWING).unwrap();
+        let sprites = drawing.sprite_set(&[32, 64, 128]);
+        assert_eq!(sprites[0].height(), 16);
+        assert_eq!(sprites[2].width(), 128);
+        let image = &sprites[2];
+        // Background, the yellow circle and the red path
+        assert_eq!(image.pixel(2, 60), Some([0, 0, 255, 255]));
+        assert_eq!(image.pixel(32, 32), Some([255, 255, 0, 255]));
+        assert_eq!(image.pixel(90, 20), Some([255, 0, 0, 255]));
+    }
+
+    #[test]
+    fn test_library_by_name() {
+        let mut library = SpriteLibrary::new();
+        library.import("dragon", DRAWING).unwrap();
+        assert!(matches!(
+            library.import("blob", "<html></html>"),
+            Err(RenderError::InvalidSvg(_))
+        ));
+        assert_eq!(library.names().collect::<Vec<_>>(), vec!["dragon"]);
+        assert_eq!(library.sprite("dragon", 64).map(|s| s.width()), Some(64));
+        assert!(library.sprite("unicorn", 64).is_none());
+    }
+}
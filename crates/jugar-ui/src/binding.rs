@@ -0,0 +1,460 @@
+//! Data binding from game state to label text.
+//!
+//! A [`Bindings`] set owns labels whose text is produced by a getter over
+//! some source `S` (a [`World`], a resource struct, a score counter) and a
+//! format string. Calling [`Bindings::update`] once per UI pass refreshes
+//! every label, only touching text whose value actually changed.
+//!
+//! Format strings use `{}` for the value, `{:.N}` for N decimal places and
+//! `{{`/`}}` for literal braces: `"Score: {}"`, `"Time {:.1}s"`. Use
+//! [`timer_clock`] to show a [`GameTimer`] as `"1:05"`.
+
+use core::any::Any;
+use core::fmt;
+use core::fmt::Write as _;
+
+use jugar_core::{Entity, GameTimer, World};
+
+use crate::{Label, Result, UiError, WidgetId};
+
+/// A value read from game state
+#[derive(Debug, Clone, PartialEq)]
+pub enum BindingValue {
+    /// Integer (scores, counts, lives)
+    Int(i64),
+    /// Floating point (timers, health)
+    Float(f32),
+    /// Boolean
+    Bool(bool),
+    /// Text
+    Text(String),
+    /// Source had nothing to show (e.g. entity despawned)
+    Missing,
+}
+
+impl From<i64> for BindingValue {
+    fn from(value: i64) -> Self {
+        Self::Int(value)
+    }
+}
+
+impl From<i32> for BindingValue {
+    fn from(value: i32) -> Self {
+        Self::Int(i64::from(value))
+    }
+}
+
+impl From<u32> for BindingValue {
+    fn from(value: u32) -> Self {
+        Self::Int(i64::from(value))
+    }
+}
+
+impl From<f32> for BindingValue {
+    fn from(value: f32) -> Self {
+        Self::Float(value)
+    }
+}
+
+impl From<bool> for BindingValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<String> for BindingValue {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl From<&str> for BindingValue {
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_string())
+    }
+}
+
+impl<T: Into<Self>> From<Option<T>> for BindingValue {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Self::Missing, Into::into)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Literal(String),
+    Value { precision: Option<usize> },
+}
+
+/// Parsed format string
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextFormat {
+    segments: Vec<Segment>,
+}
+
+impl TextFormat {
+    /// Parses a format string
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UiError::InvalidFormat`] for unbalanced braces or an
+    /// unsupported placeholder.
+    pub fn parse(format: &str) -> Result<Self> {
+        let invalid = || UiError::InvalidFormat(format.to_string());
+        let mut segments = Vec::new();
+        let mut literal = String::new();
+        let mut chars = format.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    let _ = chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    let _ = chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let mut spec = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => spec.push(c),
+                            None => return Err(invalid()),
+                        }
+                    }
+                    let precision = match spec.as_str() {
+                        "" => None,
+                        spec => Some(
+                            spec.strip_prefix(":.")
+                                .and_then(|digits| digits.parse().ok())
+                                .ok_or_else(invalid)?,
+                        ),
+                    };
+                    if !literal.is_empty() {
+                        segments.push(Segment::Literal(core::mem::take(&mut literal)));
+                    }
+                    segments.push(Segment::Value { precision });
+                }
+                '}' => return Err(invalid()),
+                c => literal.push(c),
+            }
+        }
+        if !literal.is_empty() {
+            segments.push(Segment::Literal(literal));
+        }
+        Ok(Self { segments })
+    }
+
+    /// Renders the format with a value
+    #[must_use]
+    pub fn render(&self, value: &BindingValue) -> String {
+        let mut out = String::new();
+        for segment in &self.segments {
+            match segment {
+                Segment::Literal(text) => out.push_str(text),
+                Segment::Value { precision } => {
+                    let _ = match (value, *precision) {
+                        (BindingValue::Float(v), Some(p)) => write!(out, "{v:.p$}"),
+                        (BindingValue::Int(v), _) => write!(out, "{v}"),
+                        (BindingValue::Float(v), None) => write!(out, "{v}"),
+                        (BindingValue::Bool(v), _) => write!(out, "{v}"),
+                        (BindingValue::Text(v), _) => write!(out, "{v}"),
+                        (BindingValue::Missing, _) => Ok(()),
+                    };
+                }
+            }
+        }
+        out
+    }
+}
+
+type Getter<S> = Box<dyn Fn(&S) -> BindingValue + Send + Sync>;
+
+/// A label whose text follows a value in `S`
+pub struct BoundLabel<S> {
+    /// The label being driven
+    pub label: Label,
+    format: TextFormat,
+    getter: Getter<S>,
+    last: Option<BindingValue>,
+}
+
+impl<S> BoundLabel<S> {
+    /// Binds a label to a getter with a format string
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UiError::InvalidFormat`] if the format does not parse.
+    pub fn new<V, F>(label: Label, format: &str, getter: F) -> Result<Self>
+    where
+        V: Into<BindingValue>,
+        F: Fn(&S) -> V + Send + Sync + 'static,
+    {
+        Ok(Self {
+            label,
+            format: TextFormat::parse(format)?,
+            getter: Box::new(move |source| getter(source).into()),
+            last: None,
+        })
+    }
+
+    /// Re-reads the value; returns true if the text changed
+    pub fn update(&mut self, source: &S) -> bool {
+        let value = (self.getter)(source);
+        if self.last.as_ref() == Some(&value) {
+            return false;
+        }
+        let text = self.format.render(&value);
+        self.last = Some(value);
+        if text == self.label.text {
+            return false;
+        }
+        self.label.text = text;
+        true
+    }
+}
+
+impl<S> fmt::Debug for BoundLabel<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BoundLabel")
+            .field("label", &self.label)
+            .field("format", &self.format)
+            .field("last", &self.last)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Set of bound labels refreshed together each UI pass
+pub struct Bindings<S> {
+    labels: Vec<(WidgetId, BoundLabel<S>)>,
+}
+
+impl<S> Bindings<S> {
+    /// Creates an empty binding set
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { labels: Vec::new() }
+    }
+
+    /// Binds a label under `id`, replacing any existing binding
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UiError::InvalidFormat`] if the format does not parse.
+    pub fn bind<V, F>(
+        &mut self,
+        id: impl Into<String>,
+        label: Label,
+        format: &str,
+        getter: F,
+    ) -> Result<WidgetId>
+    where
+        V: Into<BindingValue>,
+        F: Fn(&S) -> V + Send + Sync + 'static,
+    {
+        let id = WidgetId::new(id);
+        let bound = BoundLabel::new(label, format, getter)?;
+        let _ = self.unbind(&id);
+        self.labels.push((id.clone(), bound));
+        Ok(id)
+    }
+
+    /// Removes a binding, returning its label
+    pub fn unbind(&mut self, id: &WidgetId) -> Option<Label> {
+        let index = self.labels.iter().position(|(wid, _)| wid == id)?;
+        Some(self.labels.remove(index).1.label)
+    }
+
+    /// Refreshes every label; returns how many changed
+    pub fn update(&mut self, source: &S) -> usize {
+        self.labels
+            .iter_mut()
+            .map(|(_, bound)| bound.update(source))
+            .filter(|changed| *changed)
+            .count()
+    }
+
+    /// Gets a bound label
+    #[must_use]
+    pub fn label(&self, id: &WidgetId) -> Option<&Label> {
+        self.labels
+            .iter()
+            .find(|(wid, _)| wid == id)
+            .map(|(_, bound)| &bound.label)
+    }
+
+    /// Iterates over all bound labels
+    pub fn labels(&self) -> impl Iterator<Item = (&WidgetId, &Label)> {
+        self.labels.iter().map(|(id, bound)| (id, &bound.label))
+    }
+
+    /// Number of bindings
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    /// Returns true if nothing is bound
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+}
+
+impl<S> Default for Bindings<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> fmt::Debug for Bindings<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Bindings")
+            .field("labels", &self.labels)
+            .finish()
+    }
+}
+
+/// Getter that reads a component of one entity from a [`World`]
+///
+/// Yields [`BindingValue::Missing`] once the entity or component is gone.
+pub fn component<T, V, F>(entity: Entity, read: F) -> impl Fn(&World) -> BindingValue
+where
+    T: Any,
+    V: Into<BindingValue>,
+    F: Fn(&T) -> V,
+{
+    move |world| {
+        world
+            .get_component::<T>(entity)
+            .map_or(BindingValue::Missing, |c| read(c).into())
+    }
+}
+
+/// Getter that shows a [`GameTimer`] as clock text (`"Time: {}"` → `"Time: 1:05"`)
+pub fn timer_clock<S, F>(read: F) -> impl Fn(&S) -> BindingValue
+where
+    F: Fn(&S) -> &GameTimer,
+{
+    move |source| BindingValue::Text(read(source).clock_text())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    struct Score {
+        points: u32,
+        time: f32,
+    }
+
+    #[test]
+    fn test_timer_clock_binding() {
+        struct Round {
+            timer: GameTimer,
+        }
+
+        let mut round = Round {
+            timer: GameTimer::countdown("survive", 60.0),
+        };
+        let mut bindings = Bindings::new();
+        let id = bindings
+            .bind(
+                "time",
+                Label::new(""),
+                "Time: {}",
+                timer_clock(|round: &Round| &round.timer),
+            )
+            .unwrap();
+        assert_eq!(bindings.update(&round), 1);
+        assert_eq!(bindings.label(&id).unwrap().text, "Time: 1:00");
+
+        let mut events = jugar_core::Events::new();
+        round.timer.update(0.5, &mut events);
+        assert_eq!(bindings.update(&round), 0);
+        round.timer.update(30.0, &mut events);
+        assert_eq!(bindings.update(&round), 1);
+        assert_eq!(bindings.label(&id).unwrap().text, "Time: 0:30");
+    }
+
+    #[test]
+    fn test_format_parse_and_render() {
+        let format = TextFormat::parse(concat!("Time {", ":.1}s")).unwrap();
+        assert_eq!(format.render(&BindingValue::Float(12.345)), "Time 12.3s");
+        let format = TextFormat::parse("{{{}}}").unwrap();
+        assert_eq!(format.render(&BindingValue::Int(3)), "{3}");
+        let hex = concat!("{", ":x}");
+        assert_eq!(
+            TextFormat::parse(hex),
+            Err(UiError::InvalidFormat(hex.to_string()))
+        );
+        assert!(TextFormat::parse("oops }").is_err());
+        assert!(TextFormat::parse("open {").is_err());
+    }
+
+    #[test]
+    fn test_bindings_update_only_on_change() {
+        let mut bindings = Bindings::new();
+        let id = bindings
+            .bind("score", Label::new(""), "Score: {}", |s: &Score| s.points)
+            .unwrap();
+        let _ = bindings
+            .bind(
+                "time",
+                Label::new(""),
+                concat!("{", ":.0}s"),
+                |s: &Score| s.time,
+            )
+            .unwrap();
+        let mut score = Score {
+            points: 0,
+            time: 1.2,
+        };
+        assert_eq!(bindings.update(&score), 2);
+        assert_eq!(bindings.label(&id).unwrap().text, "Score: 0");
+        assert_eq!(bindings.update(&score), 0);
+        score.points = 10;
+        score.time = 1.3;
+        // Time text still reads "1s"
+        assert_eq!(bindings.update(&score), 1);
+        assert_eq!(bindings.label(&id).unwrap().text, "Score: 10");
+    }
+
+    #[test]
+    fn test_rebind_replaces() {
+        let mut bindings: Bindings<Score> = Bindings::new();
+        let _ = bindings
+            .bind("a", Label::new(""), "{}", |s: &Score| s.points)
+            .unwrap();
+        let _ = bindings
+            .bind("a", Label::new(""), "{}", |s: &Score| s.time)
+            .unwrap();
+        assert_eq!(bindings.len(), 1);
+        assert!(bindings.unbind(&WidgetId::new("a")).is_some());
+        assert!(bindings.is_empty());
+    }
+
+    #[test]
+    fn test_component_getter() {
+        struct Health(i32);
+        let mut world = World::new();
+        let player = world.spawn();
+        world.add_component(player, Health(3));
+        let mut bindings = Bindings::new();
+        let id = bindings
+            .bind(
+                "hp",
+                Label::new(""),
+                "HP {}",
+                component(player, |h: &Health| h.0),
+            )
+            .unwrap();
+        let _ = bindings.update(&world);
+        assert_eq!(bindings.label(&id).unwrap().text, "HP 3");
+        world.despawn(player).unwrap();
+        let _ = bindings.update(&world);
+        assert_eq!(bindings.label(&id).unwrap().text, "HP ");
+    }
+}
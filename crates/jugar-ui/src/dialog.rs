@@ -0,0 +1,367 @@
+//! Modal dialogs with confirm/cancel buttons and focus trapping.
+//!
+//! A [`Dialog`] sits above the rest of the UI. While one is open, input
+//! should be routed to it first: keyboard/gamepad focus cycles only between
+//! its own buttons, and pointer presses outside the panel are swallowed
+//! rather than reaching widgets underneath.
+
+use glam::Vec2;
+use serde::{Deserialize, Serialize};
+
+use jugar_core::Rect;
+
+/// Reference height that dialog sizes are authored against
+const REFERENCE_HEIGHT: f32 = 1080.0;
+
+/// Icon shown next to a dialog or toast message
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Icon {
+    /// Information
+    Info,
+    /// Something went well
+    Success,
+    /// Careful
+    Warning,
+    /// A question for the player
+    Question,
+    /// Reward
+    Star,
+    /// Named image asset
+    Custom(String),
+}
+
+/// Button chosen in a dialog
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DialogChoice {
+    /// The confirm (OK / Yes) button
+    Confirm,
+    /// The cancel (No / Back) button
+    Cancel,
+}
+
+/// Screen rectangles of a laid-out dialog
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DialogLayout {
+    /// Panel background
+    pub panel: Rect,
+    /// Icon area, if the dialog has an icon
+    pub icon: Option<Rect>,
+    /// Confirm button
+    pub confirm: Rect,
+    /// Cancel button, if the dialog has one
+    pub cancel: Option<Rect>,
+}
+
+/// Modal confirm/cancel dialog
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Dialog {
+    /// Title text
+    pub title: String,
+    /// Body text
+    pub message: String,
+    /// Optional icon
+    pub icon: Option<Icon>,
+    /// Confirm button text
+    pub confirm_text: String,
+    /// Cancel button text (`None` for a single-button alert)
+    pub cancel_text: Option<String>,
+    /// Button size at 1080p
+    pub button_size: Vec2,
+    /// Font size at 1080p
+    pub font_size: f32,
+    focus: DialogChoice,
+}
+
+impl Dialog {
+    /// Creates a confirm/cancel dialog with kid-friendly sizing
+    #[must_use]
+    pub fn new(title: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            message: message.into(),
+            icon: None,
+            confirm_text: "OK".to_string(),
+            cancel_text: Some("Cancel".to_string()),
+            button_size: Vec2::new(280.0, 110.0),
+            font_size: 40.0,
+            focus: DialogChoice::Confirm,
+        }
+    }
+
+    /// Creates a single-button alert
+    #[must_use]
+    pub fn alert(message: impl Into<String>) -> Self {
+        let mut dialog = Self::new("", message);
+        dialog.cancel_text = None;
+        dialog
+    }
+
+    /// Sets the icon
+    #[must_use]
+    pub fn with_icon(mut self, icon: Icon) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+
+    /// Sets the button labels
+    #[must_use]
+    pub fn with_buttons(mut self, confirm: impl Into<String>, cancel: Option<String>) -> Self {
+        self.confirm_text = confirm.into();
+        self.cancel_text = cancel;
+        self
+    }
+
+    /// Returns true if the dialog has a cancel button
+    #[must_use]
+    pub const fn has_cancel(&self) -> bool {
+        self.cancel_text.is_some()
+    }
+
+    /// Currently focused button
+    #[must_use]
+    pub const fn focus(&self) -> DialogChoice {
+        self.focus
+    }
+
+    /// Moves focus to the next button, wrapping inside the dialog
+    pub const fn focus_next(&mut self) {
+        if self.has_cancel() {
+            self.focus = match self.focus {
+                DialogChoice::Confirm => DialogChoice::Cancel,
+                DialogChoice::Cancel => DialogChoice::Confirm,
+            };
+        }
+    }
+
+    /// Moves focus to the previous button (two buttons, so same as next)
+    pub const fn focus_previous(&mut self) {
+        self.focus_next();
+    }
+
+    /// Activates the focused button (Enter / gamepad A)
+    #[must_use]
+    pub const fn activate(&self) -> DialogChoice {
+        self.focus
+    }
+
+    /// Result of a back/escape press
+    #[must_use]
+    pub const fn dismiss(&self) -> DialogChoice {
+        if self.has_cancel() {
+            DialogChoice::Cancel
+        } else {
+            DialogChoice::Confirm
+        }
+    }
+
+    /// Lays the dialog out centered in the viewport
+    #[must_use]
+    pub fn layout(&self, viewport: Vec2) -> DialogLayout {
+        let scale = viewport.min_element() / REFERENCE_HEIGHT;
+        let button = self.button_size * scale;
+        let gap = 32.0 * scale;
+        let buttons = if self.has_cancel() { 2.0 } else { 1.0 };
+        let width = button
+            .x
+            .mul_add(buttons, gap * (buttons + 1.0))
+            .max(720.0 * scale)
+            .min(viewport.x);
+        let height = (420.0 * scale).min(viewport.y);
+        let panel = Rect::new(
+            (viewport.x - width) * 0.5,
+            (viewport.y - height) * 0.5,
+            width,
+            height,
+        );
+        let icon_size = 96.0 * scale;
+        let icon = self
+            .icon
+            .as_ref()
+            .map(|_| Rect::new(panel.x + gap, panel.y + gap, icon_size, icon_size));
+        let row_y = panel.y + panel.height - gap - button.y;
+        let center = panel.width.mul_add(0.5, panel.x);
+        let (confirm, cancel) = if self.has_cancel() {
+            (
+                Rect::new(center + gap * 0.5, row_y, button.x, button.y),
+                Some(Rect::new(
+                    center - gap * 0.5 - button.x,
+                    row_y,
+                    button.x,
+                    button.y,
+                )),
+            )
+        } else {
+            (
+                Rect::new(button.x.mul_add(-0.5, center), row_y, button.x, button.y),
+                None,
+            )
+        };
+        DialogLayout {
+            panel,
+            icon,
+            confirm,
+            cancel,
+        }
+    }
+
+    /// Handles a pointer press
+    ///
+    /// Returns the button hit, if any. Presses anywhere else are consumed
+    /// by the modal and must not reach the UI underneath.
+    pub fn press(&mut self, viewport: Vec2, point: Vec2) -> Option<DialogChoice> {
+        let layout = self.layout(viewport);
+        let choice = if layout.confirm.contains_point(point.x, point.y) {
+            DialogChoice::Confirm
+        } else if layout
+            .cancel
+            .is_some_and(|cancel| cancel.contains_point(point.x, point.y))
+        {
+            DialogChoice::Cancel
+        } else {
+            return None;
+        };
+        self.focus = choice;
+        Some(choice)
+    }
+}
+
+/// Stack of open modal dialogs; only the top one receives input
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DialogStack {
+    dialogs: Vec<(u32, Dialog)>,
+    next_id: u32,
+}
+
+impl DialogStack {
+    /// Creates an empty stack
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            dialogs: Vec::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Opens a dialog on top; returns an id to match its result against
+    pub fn open(&mut self, dialog: Dialog) -> u32 {
+        let id = self.next_id;
+        self.next_id = self.next_id.wrapping_add(1);
+        self.dialogs.push((id, dialog));
+        id
+    }
+
+    /// Returns true if a modal is open (input below should be blocked)
+    #[must_use]
+    pub fn is_blocking(&self) -> bool {
+        !self.dialogs.is_empty()
+    }
+
+    /// The dialog receiving input
+    #[must_use]
+    pub fn top(&self) -> Option<&Dialog> {
+        self.dialogs.last().map(|(_, dialog)| dialog)
+    }
+
+    /// Mutable access to the dialog receiving input
+    pub fn top_mut(&mut self) -> Option<&mut Dialog> {
+        self.dialogs.last_mut().map(|(_, dialog)| dialog)
+    }
+
+    /// Closes the top dialog with a choice, returning its id
+    pub fn close(&mut self, choice: DialogChoice) -> Option<(u32, DialogChoice)> {
+        self.dialogs.pop().map(|(id, _)| (id, choice))
+    }
+
+    /// Routes a pointer press to the top dialog, closing it on a button hit
+    pub fn press(&mut self, viewport: Vec2, point: Vec2) -> Option<(u32, DialogChoice)> {
+        let choice = self.top_mut()?.press(viewport, point)?;
+        self.close(choice)
+    }
+
+    /// Dialogs bottom to top, for rendering
+    pub fn iter(&self) -> impl Iterator<Item = &Dialog> {
+        self.dialogs.iter().map(|(_, dialog)| dialog)
+    }
+
+    /// Number of open dialogs
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.dialogs.len()
+    }
+
+    /// Returns true if no dialog is open
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.dialogs.is_empty()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    const VIEWPORT: Vec2 = Vec2::new(1920.0, 1080.0);
+
+    fn center(rect: Rect) -> Vec2 {
+        Vec2::new(
+            rect.width.mul_add(0.5, rect.x),
+            rect.height.mul_add(0.5, rect.y),
+        )
+    }
+
+    #[test]
+    fn test_focus_trapped_between_buttons() {
+        let mut dialog = Dialog::new("Quit?", "Leave the level?");
+        assert_eq!(dialog.focus(), DialogChoice::Confirm);
+        dialog.focus_next();
+        assert_eq!(dialog.activate(), DialogChoice::Cancel);
+        dialog.focus_next();
+        assert_eq!(dialog.activate(), DialogChoice::Confirm);
+
+        let mut alert = Dialog::alert("You win!");
+        alert.focus_next();
+        assert_eq!(alert.activate(), DialogChoice::Confirm);
+        assert_eq!(alert.dismiss(), DialogChoice::Confirm);
+        assert_eq!(dialog.dismiss(), DialogChoice::Cancel);
+    }
+
+    #[test]
+    fn test_layout_is_centered_with_big_buttons() {
+        let dialog = Dialog::new("Quit?", "Leave?").with_icon(Icon::Question);
+        let layout = dialog.layout(VIEWPORT);
+        assert!((center(layout.panel) - VIEWPORT * 0.5).length() < 0.01);
+        assert!(layout.icon.is_some());
+        let cancel = layout.cancel.unwrap();
+        assert!(cancel.x + cancel.width < layout.confirm.x);
+        assert!(layout.confirm.height >= 100.0);
+    }
+
+    #[test]
+    fn test_press_outside_is_swallowed() {
+        let mut stack = DialogStack::new();
+        let id = stack.open(Dialog::new("Quit?", "Leave?"));
+        assert!(stack.is_blocking());
+        assert!(stack.press(VIEWPORT, Vec2::ZERO).is_none());
+        assert_eq!(stack.len(), 1);
+        let cancel = stack.top().unwrap().layout(VIEWPORT).cancel.unwrap();
+        assert_eq!(
+            stack.press(VIEWPORT, center(cancel)),
+            Some((id, DialogChoice::Cancel))
+        );
+        assert!(!stack.is_blocking());
+    }
+
+    #[test]
+    fn test_stack_top_receives_input() {
+        let mut stack = DialogStack::new();
+        let _ = stack.open(Dialog::new("A", "first"));
+        let second = stack.open(Dialog::alert("second"));
+        assert_eq!(stack.top().unwrap().message, "second");
+        assert_eq!(
+            stack.close(DialogChoice::Confirm),
+            Some((second, DialogChoice::Confirm))
+        );
+        assert_eq!(stack.top().unwrap().message, "first");
+    }
+}
@@ -0,0 +1,537 @@
+//! Localization of UI strings.
+//!
+//! [`I18n`] holds one [`Catalog`] per locale and resolves [`TextKey`]s
+//! through a fallback chain: the active locale (`es-MX`), its base language
+//! (`es`), any explicitly configured fallbacks, and finally the default
+//! locale. Plural messages pick a form using the CLDR category rules of the
+//! language being read from.
+//!
+//! Messages interpolate `{name}` arguments; plural lookups also provide
+//! `{count}`. Missing keys render as the key itself so gaps are visible
+//! during testing instead of silently blank.
+
+use alloc::collections::BTreeMap;
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Result, UiError};
+
+/// Key identifying a translatable string
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct TextKey(pub String);
+
+impl TextKey {
+    /// Creates a key
+    #[must_use]
+    pub fn new(key: impl Into<String>) -> Self {
+        Self(key.into())
+    }
+
+    /// Key as a string slice
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&str> for TextKey {
+    fn from(key: &str) -> Self {
+        Self::new(key)
+    }
+}
+
+/// CLDR plural category
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PluralCategory {
+    /// Zero items (Arabic, Latvian)
+    Zero,
+    /// Singular
+    One,
+    /// Dual
+    Two,
+    /// Paucal (Slavic 2-4)
+    Few,
+    /// Large counts (Slavic 5+)
+    Many,
+    /// Everything else; every plural message must have this form
+    Other,
+}
+
+/// Plural rule family for a language
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PluralRule {
+    /// No plural forms (ja, zh, ko, vi)
+    Invariant,
+    /// `one` for 1, `other` otherwise (en, es, de, it, pt-PT, nl)
+    OneOther,
+    /// `one` for 0 and 1 (fr, pt-BR)
+    French,
+    /// `one`/`few`/`many` by last digits (ru, uk)
+    EastSlavic,
+    /// `one` for 1, `few`/`many` by last digits (pl)
+    Polish,
+    /// `zero`/`one`/`two`/`few`/`many`/`other` (ar)
+    Arabic,
+}
+
+impl PluralRule {
+    /// Rule for a locale tag (only the language subtag matters)
+    #[must_use]
+    pub fn for_locale(locale: &str) -> Self {
+        match language(locale) {
+            "ja" | "zh" | "ko" | "vi" | "th" | "id" => Self::Invariant,
+            "fr" => Self::French,
+            "ru" | "uk" | "be" => Self::EastSlavic,
+            "pl" => Self::Polish,
+            "ar" => Self::Arabic,
+            _ if locale.eq_ignore_ascii_case("pt-BR") => Self::French,
+            _ => Self::OneOther,
+        }
+    }
+
+    /// Category for a count
+    #[must_use]
+    pub const fn category(self, n: u64) -> PluralCategory {
+        let (d10, d100) = (n % 10, n % 100);
+        match self {
+            Self::Invariant => PluralCategory::Other,
+            Self::OneOther => {
+                if n == 1 {
+                    PluralCategory::One
+                } else {
+                    PluralCategory::Other
+                }
+            }
+            Self::French => {
+                if n <= 1 {
+                    PluralCategory::One
+                } else {
+                    PluralCategory::Other
+                }
+            }
+            Self::EastSlavic => {
+                if d10 == 1 && d100 != 11 {
+                    PluralCategory::One
+                } else if matches!(d10, 2..=4) && !matches!(d100, 12..=14) {
+                    PluralCategory::Few
+                } else {
+                    PluralCategory::Many
+                }
+            }
+            Self::Polish => {
+                if n == 1 {
+                    PluralCategory::One
+                } else if matches!(d10, 2..=4) && !matches!(d100, 12..=14) {
+                    PluralCategory::Few
+                } else {
+                    PluralCategory::Many
+                }
+            }
+            Self::Arabic => match (n, d100) {
+                (0, _) => PluralCategory::Zero,
+                (1, _) => PluralCategory::One,
+                (2, _) => PluralCategory::Two,
+                (_, 3..=10) => PluralCategory::Few,
+                (_, 11..=99) => PluralCategory::Many,
+                _ => PluralCategory::Other,
+            },
+        }
+    }
+}
+
+/// Language subtag of a locale (`es-MX` -> `es`)
+fn language(locale: &str) -> &str {
+    locale.split(['-', '_']).next().unwrap_or(locale)
+}
+
+/// A translated message
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Message {
+    /// Single form
+    Text(String),
+    /// Forms by plural category
+    Plural(BTreeMap<PluralCategory, String>),
+}
+
+/// Translations for one locale
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Catalog {
+    messages: BTreeMap<String, Message>,
+}
+
+impl Catalog {
+    /// Creates an empty catalog
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            messages: BTreeMap::new(),
+        }
+    }
+
+    /// Adds a single-form message
+    #[must_use]
+    pub fn with(mut self, key: impl Into<String>, text: impl Into<String>) -> Self {
+        let _ = self.messages.insert(key.into(), Message::Text(text.into()));
+        self
+    }
+
+    /// Adds a plural message
+    #[must_use]
+    pub fn with_plural(
+        mut self,
+        key: impl Into<String>,
+        forms: impl IntoIterator<Item = (PluralCategory, String)>,
+    ) -> Self {
+        let _ = self
+            .messages
+            .insert(key.into(), Message::Plural(forms.into_iter().collect()));
+        self
+    }
+
+    /// Looks up a message
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&Message> {
+        self.messages.get(key)
+    }
+
+    /// Number of messages
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    /// Returns true if the catalog has no messages
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    /// Keys present in `reference` but missing here
+    #[must_use]
+    pub fn missing_keys<'a>(&self, reference: &'a Self) -> Vec<&'a str> {
+        reference
+            .messages
+            .keys()
+            .filter(|key| !self.messages.contains_key(*key))
+            .map(String::as_str)
+            .collect()
+    }
+}
+
+/// Localization resource with runtime locale switching
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct I18n {
+    catalogs: HashMap<String, Catalog>,
+    fallbacks: HashMap<String, Vec<String>>,
+    default_locale: String,
+    locale: String,
+    revision: u32,
+}
+
+impl I18n {
+    /// Creates a resource whose default (last-resort) locale is `default_locale`
+    #[must_use]
+    pub fn new(default_locale: impl Into<String>) -> Self {
+        let default_locale = default_locale.into();
+        Self {
+            catalogs: HashMap::new(),
+            fallbacks: HashMap::new(),
+            locale: default_locale.clone(),
+            default_locale,
+            revision: 0,
+        }
+    }
+
+    /// Adds or replaces the catalog for a locale
+    pub fn add_catalog(&mut self, locale: impl Into<String>, catalog: Catalog) {
+        let _ = self.catalogs.insert(locale.into(), catalog);
+        self.revision = self.revision.wrapping_add(1);
+    }
+
+    /// Sets extra locales tried after `locale` and its base language
+    pub fn set_fallbacks(&mut self, locale: impl Into<String>, fallbacks: Vec<String>) {
+        let _ = self.fallbacks.insert(locale.into(), fallbacks);
+        self.revision = self.revision.wrapping_add(1);
+    }
+
+    /// Active locale
+    #[must_use]
+    pub fn locale(&self) -> &str {
+        &self.locale
+    }
+
+    /// Switches the active locale
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UiError::UnknownLocale`] if the locale is not a variant of
+    /// the default language and no catalog along its chain is loaded.
+    pub fn set_locale(&mut self, locale: impl Into<String>) -> Result<()> {
+        let locale = locale.into();
+        let previous = core::mem::replace(&mut self.locale, locale);
+        // Regional variants of the default language need no catalog of their own
+        let known = language(&self.locale) == language(&self.default_locale)
+            || self
+                .chain()
+                .iter()
+                .filter(|tag| **tag != self.default_locale)
+                .any(|tag| self.catalogs.contains_key(*tag));
+        if !known {
+            let rejected = core::mem::replace(&mut self.locale, previous);
+            return Err(UiError::UnknownLocale(rejected));
+        }
+        self.revision = self.revision.wrapping_add(1);
+        Ok(())
+    }
+
+    /// Counter bumped on every locale or catalog change
+    ///
+    /// Widgets compare it against the revision they last localized with to
+    /// know when to refresh.
+    #[must_use]
+    pub const fn revision(&self) -> u32 {
+        self.revision
+    }
+
+    /// Locales searched, most specific first, default last
+    #[must_use]
+    pub fn chain(&self) -> Vec<&str> {
+        let mut chain = vec![self.locale.as_str()];
+        let base = language(&self.locale);
+        let extra = self
+            .fallbacks
+            .get(&self.locale)
+            .or_else(|| self.fallbacks.get(base))
+            .into_iter()
+            .flatten()
+            .map(String::as_str);
+        for tag in core::iter::once(base)
+            .chain(extra)
+            .chain(core::iter::once(self.default_locale.as_str()))
+        {
+            if !chain.contains(&tag) {
+                chain.push(tag);
+            }
+        }
+        // The default always ends the chain
+        if let Some(index) = chain.iter().position(|tag| *tag == self.default_locale) {
+            let default = chain.remove(index);
+            chain.push(default);
+        }
+        chain
+    }
+
+    fn find(&self, key: &str) -> Option<(&str, &Message)> {
+        self.chain().into_iter().find_map(|tag| {
+            self.catalogs
+                .get(tag)
+                .and_then(|catalog| catalog.get(key))
+                .map(|message| (tag, message))
+        })
+    }
+
+    /// Returns true if any locale in the chain has the key
+    #[must_use]
+    pub fn contains(&self, key: &TextKey) -> bool {
+        self.find(key.as_str()).is_some()
+    }
+
+    /// Translates a key (the key itself if missing)
+    #[must_use]
+    pub fn text(&self, key: &TextKey) -> String {
+        self.format(key, &[])
+    }
+
+    /// Translates a key and substitutes `{name}` arguments
+    #[must_use]
+    pub fn format(&self, key: &TextKey, args: &[(&str, &str)]) -> String {
+        match self.find(key.as_str()) {
+            Some((_, Message::Text(text))) => interpolate(text, args),
+            Some((tag, Message::Plural(_))) => {
+                log::warn!("plural message '{}' used without a count", key.as_str());
+                self.plural_in(tag, key, 1, args)
+            }
+            None => key.as_str().to_string(),
+        }
+    }
+
+    /// Translates a plural key for `count`, also substituting `{count}`
+    #[must_use]
+    pub fn plural(&self, key: &TextKey, count: u64, args: &[(&str, &str)]) -> String {
+        match self.find(key.as_str()) {
+            Some((_, Message::Text(text))) => {
+                interpolate(text, &with_count(args, &count.to_string()))
+            }
+            Some((tag, Message::Plural(_))) => self.plural_in(tag, key, count, args),
+            None => key.as_str().to_string(),
+        }
+    }
+
+    fn plural_in(&self, tag: &str, key: &TextKey, count: u64, args: &[(&str, &str)]) -> String {
+        let Some(Message::Plural(forms)) = self
+            .catalogs
+            .get(tag)
+            .and_then(|catalog| catalog.get(key.as_str()))
+        else {
+            return key.as_str().to_string();
+        };
+        let category = PluralRule::for_locale(tag).category(count);
+        forms
+            .get(&category)
+            .or_else(|| forms.get(&PluralCategory::Other))
+            .map_or_else(
+                || key.as_str().to_string(),
+                |text| interpolate(text, &with_count(args, &count.to_string())),
+            )
+    }
+}
+
+fn with_count<'a>(args: &[(&'a str, &'a str)], count: &'a str) -> Vec<(&'a str, &'a str)> {
+    let mut all = args.to_vec();
+    all.push(("count", count));
+    all
+}
+
+/// Replaces `{name}` placeholders; unknown placeholders are left as-is
+fn interpolate(text: &str, args: &[(&str, &str)]) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let Some(end) = after.find('}') else {
+            out.push_str(&rest[start..]);
+            return out;
+        };
+        let name = &after[..end];
+        match args.iter().find(|(arg, _)| *arg == name) {
+            Some((_, value)) => out.push_str(value),
+            None => out.push_str(&rest[start..start + end + 2]),
+        }
+        rest = &after[end + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn i18n() -> I18n {
+        let mut i18n = I18n::new("en");
+        i18n.add_catalog(
+            "en",
+            Catalog::new()
+                .with("menu.play", "Play")
+                .with("menu.quit", "Quit")
+                .with("hello", "Hello, {name}!")
+                .with_plural(
+                    "stars",
+                    [
+                        (PluralCategory::One, "{count} star".to_string()),
+                        (PluralCategory::Other, "{count} stars".to_string()),
+                    ],
+                ),
+        );
+        i18n.add_catalog(
+            "es",
+            Catalog::new()
+                .with("menu.play", "Jugar")
+                .with("hello", "¡Hola, {name}!")
+                .with_plural(
+                    "stars",
+                    [
+                        (PluralCategory::One, "{count} estrella".to_string()),
+                        (PluralCategory::Other, "{count} estrellas".to_string()),
+                    ],
+                ),
+        );
+        i18n.add_catalog("es-MX", Catalog::new().with("menu.quit", "Salir"));
+        i18n
+    }
+
+    #[test]
+    fn test_fallback_chain() {
+        let mut i18n = i18n();
+        i18n.set_locale("es-MX").unwrap();
+        assert_eq!(i18n.chain(), vec!["es-MX", "es", "en"]);
+        assert_eq!(i18n.text(&"menu.quit".into()), "Salir");
+        assert_eq!(i18n.text(&"menu.play".into()), "Jugar");
+        assert_eq!(i18n.text(&"missing".into()), "missing");
+    }
+
+    #[test]
+    fn test_explicit_fallbacks() {
+        let mut i18n = i18n();
+        i18n.add_catalog("ca", Catalog::new().with("menu.quit", "Sortir"));
+        i18n.set_fallbacks("ca", vec!["es".to_string()]);
+        i18n.set_locale("ca").unwrap();
+        assert_eq!(i18n.chain(), vec!["ca", "es", "en"]);
+        assert_eq!(i18n.text(&"menu.play".into()), "Jugar");
+    }
+
+    #[test]
+    fn test_plurals_and_interpolation() {
+        let mut i18n = i18n();
+        assert_eq!(i18n.plural(&"stars".into(), 1, &[]), "1 star");
+        assert_eq!(i18n.plural(&"stars".into(), 3, &[]), "3 stars");
+        i18n.set_locale("es").unwrap();
+        assert_eq!(i18n.plural(&"stars".into(), 0, &[]), "0 estrellas");
+        assert_eq!(
+            i18n.format(&"hello".into(), &[("name", "Ana")]),
+            "¡Hola, Ana!"
+        );
+        assert_eq!(interpolate("{a} {b}", &[("a", "x")]), "x {b}");
+    }
+
+    #[test]
+    fn test_plural_rules() {
+        let ru = PluralRule::for_locale("ru");
+        assert_eq!(ru.category(1), PluralCategory::One);
+        assert_eq!(ru.category(3), PluralCategory::Few);
+        assert_eq!(ru.category(11), PluralCategory::Many);
+        assert_eq!(ru.category(22), PluralCategory::Few);
+        assert_eq!(
+            PluralRule::for_locale("fr").category(0),
+            PluralCategory::One
+        );
+        assert_eq!(
+            PluralRule::for_locale("ja").category(1),
+            PluralCategory::Other
+        );
+        assert_eq!(PluralRule::for_locale("pt-BR"), PluralRule::French);
+        assert_eq!(
+            PluralRule::for_locale("ar").category(2),
+            PluralCategory::Two
+        );
+    }
+
+    #[test]
+    fn test_locale_switching() {
+        let mut i18n = i18n();
+        let before = i18n.revision();
+        assert_eq!(
+            i18n.set_locale("de"),
+            Err(UiError::UnknownLocale("de".to_string()))
+        );
+        assert_eq!(i18n.locale(), "en");
+        i18n.set_locale("en-GB").unwrap();
+        assert_eq!(i18n.text(&"menu.play".into()), "Play");
+        i18n.set_locale("es").unwrap();
+        assert_ne!(i18n.revision(), before);
+        assert_eq!(i18n.locale(), "es");
+    }
+
+    #[test]
+    fn test_missing_keys() {
+        let i18n = i18n();
+        let en = i18n.catalogs.get("en").unwrap();
+        let es = i18n.catalogs.get("es").unwrap();
+        assert_eq!(es.missing_keys(en), vec!["menu.quit"]);
+    }
+}
@@ -1,22 +1,58 @@
 //! # jugar-ui
 //!
 //! Responsive UI system with anchor-based layout for mobile to ultrawide displays.
+//!
+//! The `widgets` feature (on by default) adds dialogs, toasts, speech
+//! bubbles and scroll views; games that only need buttons and labels can
+//! turn it off to keep their WASM small.
 
 #![forbid(unsafe_code)]
 #![warn(missing_docs)]
 
+extern crate alloc;
+
+mod binding;
+#[cfg(feature = "widgets")]
+mod dialog;
+mod i18n;
+#[cfg(feature = "widgets")]
+mod scroll;
+mod semantics;
+#[cfg(feature = "widgets")]
+mod speech;
+#[cfg(feature = "widgets")]
+mod toast;
+
 use glam::Vec2;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use jugar_core::{Anchor, Rect, ScaleMode, UiElement};
 
+pub use binding::{component, timer_clock, BindingValue, Bindings, BoundLabel, TextFormat};
+#[cfg(feature = "widgets")]
+pub use dialog::{Dialog, DialogChoice, DialogLayout, DialogStack, Icon};
+pub use i18n::{Catalog, I18n, Message, PluralCategory, PluralRule, TextKey};
+#[cfg(feature = "widgets")]
+pub use scroll::ScrollView;
+pub use semantics::{Role, SemanticInfo, SemanticNode};
+#[cfg(feature = "widgets")]
+pub use speech::{SpeechBubble, SpeechBubbles, DEFAULT_SPEECH_SECONDS};
+#[cfg(feature = "widgets")]
+pub use toast::{Toast, ToastQueue, DEFAULT_TOAST_SECONDS};
+
 /// UI system errors
 #[derive(Error, Debug, Clone, PartialEq, Eq)]
 pub enum UiError {
     /// Widget not found
     #[error("Widget '{0}' not found")]
     WidgetNotFound(String),
+    /// Malformed binding format string
+    #[error("Invalid format string '{0}'")]
+    InvalidFormat(String),
+    /// No translations loaded for a locale
+    #[error("Unknown locale '{0}'")]
+    UnknownLocale(String),
 }
 
 /// Result type for UI operations
@@ -38,6 +74,7 @@ impl WidgetId {
 #[derive(Debug, Default)]
 pub struct UiContainer {
     widgets: Vec<(WidgetId, UiElement)>,
+    semantics: Vec<(WidgetId, SemanticInfo)>,
     viewport_size: Vec2,
 }
 
@@ -47,6 +84,7 @@ impl UiContainer {
     pub const fn new(viewport_width: f32, viewport_height: f32) -> Self {
         Self {
             widgets: Vec::new(),
+            semantics: Vec::new(),
             viewport_size: Vec2::new(viewport_width, viewport_height),
         }
     }
@@ -85,6 +123,7 @@ impl UiContainer {
     pub fn remove_widget(&mut self, id: &WidgetId) -> bool {
         if let Some(idx) = self.widgets.iter().position(|(wid, _)| wid == id) {
             let _ = self.widgets.remove(idx);
+            self.semantics.retain(|(wid, _)| wid != id);
             true
         } else {
             false
@@ -103,6 +142,9 @@ impl UiContainer {
         let scale = match element.scale_mode {
             ScaleMode::Adaptive => self.viewport_size.y.min(self.viewport_size.x) / 1080.0,
             ScaleMode::PixelPerfect | ScaleMode::Fixed => 1.0,
+            ScaleMode::PixelArt => (self.viewport_size.y.min(self.viewport_size.x) / 1080.0)
+                .floor()
+                .max(1.0),
         };
 
         let (ax, ay) = element.anchor.normalized();
@@ -119,6 +161,9 @@ impl UiContainer {
         let scale = match element.scale_mode {
             ScaleMode::Adaptive => self.viewport_size.y.min(self.viewport_size.x) / 1080.0,
             ScaleMode::PixelPerfect | ScaleMode::Fixed => 1.0,
+            ScaleMode::PixelArt => (self.viewport_size.y.min(self.viewport_size.x) / 1080.0)
+                .floor()
+                .max(1.0),
         };
 
         let scaled_size = element.size * scale;
@@ -148,6 +193,34 @@ impl UiContainer {
         None
     }
 
+    /// Attaches an accessibility description to a widget, replacing any previous one
+    pub fn set_semantics(&mut self, id: &WidgetId, info: SemanticInfo) {
+        if let Some((_, existing)) = self.semantics.iter_mut().find(|(wid, _)| wid == id) {
+            *existing = info;
+        } else {
+            self.semantics.push((id.clone(), info));
+        }
+    }
+
+    /// Accessibility tree: every visible widget with a description, in render order
+    #[must_use]
+    pub fn semantics(&self) -> Vec<SemanticNode> {
+        self.sorted_for_render()
+            .into_iter()
+            .filter_map(|(id, element)| {
+                let (_, info) = self.semantics.iter().find(|(wid, _)| wid == id)?;
+                Some(SemanticNode {
+                    id: id.clone(),
+                    role: info.role,
+                    label: info.label.clone(),
+                    value: info.value.clone(),
+                    disabled: info.disabled,
+                    bounds: self.calculate_widget_bounds(element),
+                })
+            })
+            .collect()
+    }
+
     /// Returns widgets sorted by z-order for rendering
     #[must_use]
     pub fn sorted_for_render(&self) -> Vec<(&WidgetId, &UiElement)> {
@@ -183,6 +256,9 @@ pub struct Button {
     pub element: UiElement,
     /// Button text
     pub text: String,
+    /// Translation key; when set, `text` is filled in by [`Button::localize`]
+    #[serde(default)]
+    pub key: Option<TextKey>,
     /// Current state
     pub state: ButtonState,
 }
@@ -194,10 +270,27 @@ impl Button {
         Self {
             element: UiElement::new(size),
             text: text.into(),
+            key: None,
             state: ButtonState::Normal,
         }
     }
 
+    /// Creates a button whose text comes from a translation key
+    #[must_use]
+    pub fn localized(key: impl Into<TextKey>, size: Vec2) -> Self {
+        let key = key.into();
+        let mut button = Self::new(key.as_str(), size);
+        button.key = Some(key);
+        button
+    }
+
+    /// Refreshes the text from the active locale (no-op for raw text)
+    pub fn localize(&mut self, i18n: &I18n) {
+        if let Some(key) = &self.key {
+            self.text = i18n.text(key);
+        }
+    }
+
     /// Sets the anchor
     #[must_use]
     pub const fn with_anchor(mut self, anchor: Anchor) -> Self {
@@ -232,6 +325,9 @@ pub struct Label {
     pub element: UiElement,
     /// Label text
     pub text: String,
+    /// Translation key; when set, `text` is filled in by [`Label::localize`]
+    #[serde(default)]
+    pub key: Option<TextKey>,
     /// Text color (RGBA)
     pub color: [f32; 4],
     /// Font size
@@ -245,11 +341,28 @@ impl Label {
         Self {
             element: UiElement::new(Vec2::new(200.0, 30.0)),
             text: text.into(),
+            key: None,
             color: [1.0, 1.0, 1.0, 1.0],
             font_size: 16.0,
         }
     }
 
+    /// Creates a label whose text comes from a translation key
+    #[must_use]
+    pub fn localized(key: impl Into<TextKey>) -> Self {
+        let key = key.into();
+        let mut label = Self::new(key.as_str());
+        label.key = Some(key);
+        label
+    }
+
+    /// Refreshes the text from the active locale (no-op for raw text)
+    pub fn localize(&mut self, i18n: &I18n) {
+        if let Some(key) = &self.key {
+            self.text = i18n.text(key);
+        }
+    }
+
     /// Sets the anchor
     #[must_use]
     pub const fn with_anchor(mut self, anchor: Anchor) -> Self {
@@ -369,6 +482,37 @@ mod tests {
         assert!((label.font_size - 24.0).abs() < f32::EPSILON);
     }
 
+    #[test]
+    fn test_pixel_art_scale_is_whole() {
+        let container = UiContainer::new(2560.0, 1440.0);
+        let uhd = UiContainer::new(3840.0, 2160.0);
+        let element = UiElement::new(Vec2::new(100.0, 50.0))
+            .with_anchor(Anchor::TopLeft)
+            .with_scale_mode(ScaleMode::PixelArt);
+        let bounds = container.calculate_widget_bounds(&element);
+        // 1440 / 1080 = 1.33, floored to 1
+        assert!((bounds.width - 100.0).abs() < f32::EPSILON);
+        let bounds = uhd.calculate_widget_bounds(&element);
+        assert!((bounds.width - 200.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_localized_widgets() {
+        let mut i18n = I18n::new("en");
+        i18n.add_catalog("en", Catalog::new().with("menu.play", "Play"));
+        i18n.add_catalog("es", Catalog::new().with("menu.play", "Jugar"));
+        let mut label = Label::localized("menu.play");
+        let mut raw = Label::new("Score");
+        let mut button = Button::localized("menu.play", Vec2::new(200.0, 80.0));
+        i18n.set_locale("es").unwrap();
+        label.localize(&i18n);
+        raw.localize(&i18n);
+        button.localize(&i18n);
+        assert_eq!(label.text, "Jugar");
+        assert_eq!(button.text, "Jugar");
+        assert_eq!(raw.text, "Score");
+    }
+
     #[test]
     fn test_sorted_for_render() {
         let mut container = UiContainer::new(1920.0, 1080.0);
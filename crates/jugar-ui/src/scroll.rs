@@ -0,0 +1,329 @@
+//! Scrollable container with drag, wheel and momentum scrolling.
+//!
+//! A [`ScrollView`] shows a window of `view_size` onto content of
+//! `content_size`. Child rectangles are given in content coordinates and
+//! mapped into the view with [`ScrollView::child_rect`], which clips them to
+//! the view and drops children that are fully scrolled out, so long lists
+//! only draw what is visible.
+
+use glam::Vec2;
+use serde::{Deserialize, Serialize};
+
+use jugar_core::{Anchor, Rect, UiElement};
+
+/// Speed below which momentum stops (units per second)
+const MIN_MOMENTUM: f32 = 5.0;
+
+/// A scrollable viewport onto larger content
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScrollView {
+    /// Visual element (its size is the view size)
+    pub element: UiElement,
+    /// Total size of the scrolled content
+    pub content_size: Vec2,
+    /// Allow horizontal scrolling
+    pub horizontal: bool,
+    /// Allow vertical scrolling
+    pub vertical: bool,
+    /// Distance scrolled per wheel notch
+    pub wheel_step: f32,
+    /// Momentum decay rate (per second)
+    pub friction: f32,
+    /// Scrollbar thickness
+    pub scrollbar_width: f32,
+    offset: Vec2,
+    velocity: Vec2,
+    drag_from: Option<Vec2>,
+}
+
+impl ScrollView {
+    /// Creates a vertical scroll view
+    #[must_use]
+    pub const fn new(view_size: Vec2, content_size: Vec2) -> Self {
+        Self {
+            element: UiElement::new(view_size),
+            content_size,
+            horizontal: false,
+            vertical: true,
+            wheel_step: 40.0,
+            friction: 5.0,
+            scrollbar_width: 6.0,
+            offset: Vec2::ZERO,
+            velocity: Vec2::ZERO,
+            drag_from: None,
+        }
+    }
+
+    /// Sets the anchor
+    #[must_use]
+    pub const fn with_anchor(mut self, anchor: Anchor) -> Self {
+        self.element = self.element.with_anchor(anchor);
+        self
+    }
+
+    /// Sets which axes scroll
+    #[must_use]
+    pub const fn with_axes(mut self, horizontal: bool, vertical: bool) -> Self {
+        self.horizontal = horizontal;
+        self.vertical = vertical;
+        self
+    }
+
+    /// Size of the visible window
+    #[must_use]
+    pub const fn view_size(&self) -> Vec2 {
+        self.element.size
+    }
+
+    /// Current scroll offset (top-left of the view in content space)
+    #[must_use]
+    pub const fn offset(&self) -> Vec2 {
+        self.offset
+    }
+
+    /// Largest valid offset
+    #[must_use]
+    pub fn max_offset(&self) -> Vec2 {
+        let max = (self.content_size - self.view_size()).max(Vec2::ZERO);
+        Vec2::new(
+            if self.horizontal { max.x } else { 0.0 },
+            if self.vertical { max.y } else { 0.0 },
+        )
+    }
+
+    /// Jumps to an offset (clamped)
+    pub fn scroll_to(&mut self, offset: Vec2) {
+        self.offset = offset.clamp(Vec2::ZERO, self.max_offset());
+        self.velocity = Vec2::ZERO;
+    }
+
+    /// Scrolls so a content rectangle is fully visible
+    pub fn scroll_into_view(&mut self, rect: Rect) {
+        let view = self.view_size();
+        let min = Vec2::new(rect.x, rect.y);
+        let max = min + Vec2::new(rect.width, rect.height);
+        let mut offset = self.offset;
+        offset = offset.max(max - view).min(min);
+        self.scroll_to(offset);
+    }
+
+    /// Applies mouse wheel notches (positive y scrolls down)
+    pub fn wheel(&mut self, notches: Vec2) {
+        let delta = if self.vertical || !self.horizontal {
+            notches
+        } else {
+            // Horizontal-only views scroll sideways with a normal wheel
+            Vec2::new(notches.x + notches.y, 0.0)
+        };
+        self.scroll_to(self.offset + delta * self.wheel_step);
+    }
+
+    /// Starts a touch or mouse drag at `position`
+    pub fn begin_drag(&mut self, position: Vec2) {
+        self.drag_from = Some(position);
+        self.velocity = Vec2::ZERO;
+    }
+
+    /// Continues a drag; content follows the pointer
+    pub fn drag_to(&mut self, position: Vec2, dt: f32) {
+        let Some(from) = self.drag_from else {
+            return;
+        };
+        let delta = from - position;
+        let before = self.offset;
+        self.offset = (self.offset + delta).clamp(Vec2::ZERO, self.max_offset());
+        if dt > 0.0 {
+            // Smooth the release velocity over recent moves
+            let instant = (self.offset - before) / dt;
+            self.velocity = self.velocity.lerp(instant, 0.5);
+        }
+        self.drag_from = Some(position);
+    }
+
+    /// Ends a drag, keeping momentum
+    pub fn end_drag(&mut self) {
+        self.drag_from = None;
+    }
+
+    /// Returns true while being dragged
+    #[must_use]
+    pub const fn is_dragging(&self) -> bool {
+        self.drag_from.is_some()
+    }
+
+    /// Returns true while coasting after a fling
+    #[must_use]
+    pub fn is_coasting(&self) -> bool {
+        self.drag_from.is_none() && self.velocity.length() >= MIN_MOMENTUM
+    }
+
+    /// Advances momentum scrolling
+    pub fn update(&mut self, dt: f32) {
+        if self.drag_from.is_some() {
+            return;
+        }
+        if self.velocity.length() < MIN_MOMENTUM {
+            self.velocity = Vec2::ZERO;
+            return;
+        }
+        let max = self.max_offset();
+        let next = self.offset + self.velocity * dt;
+        self.offset = next.clamp(Vec2::ZERO, max);
+        // Stop on the axis that hit an edge
+        if (next.x - self.offset.x).abs() > 0.0 {
+            self.velocity.x = 0.0;
+        }
+        if (next.y - self.offset.y).abs() > 0.0 {
+            self.velocity.y = 0.0;
+        }
+        self.velocity *= (-self.friction * dt).exp();
+    }
+
+    /// Maps a content-space child rectangle into the on-screen `view`
+    ///
+    /// The result is clipped to the view; `None` means the child is
+    /// entirely scrolled out and need not be drawn.
+    #[must_use]
+    pub fn child_rect(&self, view: Rect, child: Rect) -> Option<Rect> {
+        let scale = self.scale(view);
+        let x = (child.x - self.offset.x).mul_add(scale.x, view.x);
+        let y = (child.y - self.offset.y).mul_add(scale.y, view.y);
+        let left = x.max(view.x);
+        let top = y.max(view.y);
+        let right = child.width.mul_add(scale.x, x).min(view.x + view.width);
+        let bottom = child.height.mul_add(scale.y, y).min(view.y + view.height);
+        (right > left && bottom > top).then(|| Rect::new(left, top, right - left, bottom - top))
+    }
+
+    /// Scrollbar thumbs within `view` as (vertical, horizontal)
+    ///
+    /// A thumb is `None` when its axis is disabled or nothing overflows.
+    #[must_use]
+    pub fn scrollbars(&self, view: Rect) -> (Option<Rect>, Option<Rect>) {
+        let size = self.view_size();
+        let max = self.max_offset();
+        let bar = self.scrollbar_width;
+        let vertical = (max.y > 0.0).then(|| {
+            let length = (size.y / self.content_size.y * view.height).max(bar * 2.0);
+            let travel = view.height - length;
+            Rect::new(
+                view.x + view.width - bar,
+                (self.offset.y / max.y).mul_add(travel, view.y),
+                bar,
+                length,
+            )
+        });
+        let horizontal = (max.x > 0.0).then(|| {
+            let length = (size.x / self.content_size.x * view.width).max(bar * 2.0);
+            let travel = view.width - length;
+            Rect::new(
+                (self.offset.x / max.x).mul_add(travel, view.x),
+                view.y + view.height - bar,
+                length,
+                bar,
+            )
+        });
+        (vertical, horizontal)
+    }
+
+    fn scale(&self, view: Rect) -> Vec2 {
+        let size = self.view_size();
+        Vec2::new(
+            if size.x > 0.0 {
+                view.width / size.x
+            } else {
+                1.0
+            },
+            if size.y > 0.0 {
+                view.height / size.y
+            } else {
+                1.0
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn list() -> ScrollView {
+        ScrollView::new(Vec2::new(200.0, 300.0), Vec2::new(200.0, 1000.0))
+    }
+
+    fn view() -> Rect {
+        Rect::new(10.0, 20.0, 200.0, 300.0)
+    }
+
+    #[test]
+    fn test_wheel_clamps() {
+        let mut scroll = list();
+        scroll.wheel(Vec2::new(0.0, 3.0));
+        assert!((scroll.offset().y - 120.0).abs() < 0.001);
+        scroll.wheel(Vec2::new(0.0, 100.0));
+        assert!((scroll.offset().y - 700.0).abs() < 0.001);
+        scroll.wheel(Vec2::new(0.0, -100.0));
+        assert!(scroll.offset().y.abs() < 0.001);
+        assert!(scroll.offset().x.abs() < f32::EPSILON, "horizontal is off");
+    }
+
+    #[test]
+    fn test_drag_and_momentum() {
+        let mut scroll = list();
+        scroll.begin_drag(Vec2::new(100.0, 200.0));
+        scroll.drag_to(Vec2::new(100.0, 150.0), 0.016);
+        scroll.drag_to(Vec2::new(100.0, 100.0), 0.016);
+        assert!((scroll.offset().y - 100.0).abs() < 0.001);
+        scroll.end_drag();
+        assert!(scroll.is_coasting());
+        let released = scroll.offset().y;
+        for _ in 0..120 {
+            scroll.update(1.0 / 60.0);
+        }
+        assert!(scroll.offset().y > released);
+        assert!(!scroll.is_coasting());
+    }
+
+    #[test]
+    fn test_child_rect_clipping() {
+        let mut scroll = list();
+        scroll.scroll_to(Vec2::new(0.0, 100.0));
+        // Half scrolled off the top
+        let row = scroll
+            .child_rect(view(), Rect::new(0.0, 80.0, 200.0, 40.0))
+            .unwrap();
+        assert!((row.y - 20.0).abs() < 0.001);
+        assert!((row.height - 20.0).abs() < 0.001);
+        // Fully above the view
+        assert!(scroll
+            .child_rect(view(), Rect::new(0.0, 0.0, 200.0, 40.0))
+            .is_none());
+        // Below the view
+        assert!(scroll
+            .child_rect(view(), Rect::new(0.0, 500.0, 200.0, 40.0))
+            .is_none());
+    }
+
+    #[test]
+    fn test_scroll_into_view() {
+        let mut scroll = list();
+        scroll.scroll_into_view(Rect::new(0.0, 600.0, 200.0, 50.0));
+        assert!((scroll.offset().y - 350.0).abs() < 0.001);
+        scroll.scroll_into_view(Rect::new(0.0, 10.0, 200.0, 50.0));
+        assert!((scroll.offset().y - 10.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_scrollbar_thumb() {
+        let mut scroll = list();
+        let (vertical, horizontal) = scroll.scrollbars(view());
+        let thumb = vertical.unwrap();
+        assert!(horizontal.is_none());
+        assert!((thumb.height - 90.0).abs() < 0.001);
+        assert!((thumb.y - 20.0).abs() < 0.001);
+        scroll.scroll_to(Vec2::new(0.0, 700.0));
+        let thumb = scroll.scrollbars(view()).0.unwrap();
+        assert!((thumb.y + thumb.height - 320.0).abs() < 0.001);
+    }
+}
@@ -0,0 +1,203 @@
+//! Accessibility tree for screen readers.
+//!
+//! Widgets are drawn on a canvas, which assistive technology can't see. Each
+//! widget can carry a [`SemanticInfo`] (role, label, value) and
+//! [`UiContainer::semantics`](crate::UiContainer::semantics) flattens the
+//! visible ones into [`SemanticNode`]s with their screen bounds, ready for a
+//! platform layer to mirror into native accessibility APIs or DOM overlays.
+
+use serde::{Deserialize, Serialize};
+
+use jugar_core::Rect;
+
+use crate::{Button, ButtonState, Label, WidgetId};
+
+/// What a widget is, in screen reader terms
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum Role {
+    /// Something that can be pressed
+    Button,
+    /// Static text
+    #[default]
+    Label,
+    /// A value picked from a range (volume, difficulty)
+    Slider,
+    /// An on/off toggle
+    Checkbox,
+    /// A picture with alternative text
+    Image,
+    /// A modal message box
+    Dialog,
+    /// Text that changes and should be announced (score, timer)
+    Status,
+    /// A container of related widgets
+    Group,
+}
+
+impl Role {
+    /// WAI-ARIA role name
+    #[must_use]
+    pub const fn aria_role(self) -> &'static str {
+        match self {
+            Self::Button => "button",
+            Self::Label => "note",
+            Self::Slider => "slider",
+            Self::Checkbox => "checkbox",
+            Self::Image => "img",
+            Self::Dialog => "dialog",
+            Self::Status => "status",
+            Self::Group => "group",
+        }
+    }
+
+    /// Returns true if changes should be announced without focus
+    #[must_use]
+    pub const fn is_live(self) -> bool {
+        matches!(self, Self::Status)
+    }
+}
+
+/// Accessibility description attached to a widget
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct SemanticInfo {
+    /// What the widget is
+    pub role: Role,
+    /// Name read out by the screen reader
+    pub label: String,
+    /// Current value ("75%", "On"), if any
+    pub value: Option<String>,
+    /// Whether the widget ignores input
+    pub disabled: bool,
+}
+
+impl SemanticInfo {
+    /// Creates a description
+    #[must_use]
+    pub fn new(role: Role, label: impl Into<String>) -> Self {
+        Self {
+            role,
+            label: label.into(),
+            value: None,
+            disabled: false,
+        }
+    }
+
+    /// Sets the value
+    #[must_use]
+    pub fn with_value(mut self, value: impl Into<String>) -> Self {
+        self.value = Some(value.into());
+        self
+    }
+
+    /// Marks the widget disabled
+    #[must_use]
+    pub const fn with_disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+}
+
+impl Button {
+    /// Accessibility description using the button text
+    #[must_use]
+    pub fn semantic_info(&self) -> SemanticInfo {
+        SemanticInfo::new(Role::Button, self.text.clone())
+            .with_disabled(matches!(self.state, ButtonState::Disabled))
+    }
+}
+
+impl Label {
+    /// Accessibility description using the label text
+    #[must_use]
+    pub fn semantic_info(&self) -> SemanticInfo {
+        SemanticInfo::new(Role::Label, self.text.clone())
+    }
+}
+
+/// A visible widget in the accessibility tree
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SemanticNode {
+    /// Widget the node describes
+    pub id: WidgetId,
+    /// What the widget is
+    pub role: Role,
+    /// Name read out by the screen reader
+    pub label: String,
+    /// Current value, if any
+    pub value: Option<String>,
+    /// Whether the widget ignores input
+    pub disabled: bool,
+    /// Screen bounds in viewport pixels
+    pub bounds: Rect,
+}
+
+impl SemanticNode {
+    /// Text a screen reader would speak ("Volume, 75%")
+    #[must_use]
+    pub fn spoken_text(&self) -> String {
+        self.value.as_ref().map_or_else(
+            || self.label.clone(),
+            |value| format!("{}, {value}", self.label),
+        )
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use glam::Vec2;
+    use jugar_core::{Anchor, UiElement};
+
+    use super::*;
+    use crate::UiContainer;
+
+    #[test]
+    fn test_semantics_lists_visible_described_widgets() {
+        let mut container = UiContainer::new(1920.0, 1080.0);
+        let play = Button::new("Play", Vec2::new(200.0, 80.0)).with_anchor(Anchor::TopLeft);
+        let id = container.add_widget("play", play.element.clone());
+        container.set_semantics(&id, play.semantic_info());
+
+        let hidden = container.add_widget(
+            "hidden",
+            UiElement {
+                visible: false,
+                ..UiElement::default()
+            },
+        );
+        container.set_semantics(&hidden, SemanticInfo::new(Role::Label, "Secret"));
+        let _ = container.add_widget("decoration", UiElement::default());
+
+        let nodes = container.semantics();
+        assert_eq!(nodes.len(), 1);
+        assert_eq!(nodes[0].id, id);
+        assert_eq!(nodes[0].role, Role::Button);
+        assert_eq!(nodes[0].label, "Play");
+        assert!((nodes[0].bounds.width - 200.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_semantics_removed_with_widget() {
+        let mut container = UiContainer::new(1920.0, 1080.0);
+        let id = container.add_widget("score", UiElement::default());
+        container.set_semantics(
+            &id,
+            SemanticInfo::new(Role::Status, "Score").with_value("10"),
+        );
+        assert_eq!(container.semantics()[0].spoken_text(), "Score, 10");
+
+        assert!(container.remove_widget(&id));
+        let _ = container.add_widget("score", UiElement::default());
+        assert!(container.semantics().is_empty());
+    }
+
+    #[test]
+    fn test_disabled_button_and_roles() {
+        let mut button = Button::new("Continue", Vec2::new(100.0, 40.0));
+        button.state = ButtonState::Disabled;
+        assert!(button.semantic_info().disabled);
+        assert_eq!(Role::Image.aria_role(), "img");
+        assert!(Role::Status.is_live());
+        assert!(!Role::Button.is_live());
+    }
+}
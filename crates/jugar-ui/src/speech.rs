@@ -0,0 +1,237 @@
+//! Timed speech bubbles above characters.
+//!
+//! [`SpeechBubbles`] is the presentation path for the YAML `say:` action.
+//! Each speaker shows at most one bubble: a new line from the same speaker
+//! replaces the old one, while different speakers can talk at once. Lines
+//! marked for text-to-speech are handed out once through
+//! [`SpeechBubbles::take_spoken`] so the audio layer can read them aloud.
+
+use glam::Vec2;
+use serde::{Deserialize, Serialize};
+
+use jugar_core::Rect;
+
+/// Default time a bubble stays up (seconds)
+pub const DEFAULT_SPEECH_SECONDS: f32 = 3.0;
+
+/// Bubbles beyond this many on screen are dropped, oldest first
+const MAX_BUBBLES: usize = 4;
+
+/// Widest a bubble gets at 1080p before wrapping
+const MAX_WIDTH: f32 = 560.0;
+
+/// Padding around the text at 1080p
+const PADDING: f32 = 20.0;
+
+/// Gap between the speaker and the bubble tail at 1080p
+const TAIL: f32 = 24.0;
+
+/// A line of dialog
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpeechBubble {
+    /// Text to show
+    pub text: String,
+    /// Entity that is talking (`None` for a narrator bubble)
+    pub speaker: Option<String>,
+    /// Seconds on screen
+    pub duration: f32,
+    /// Also read the line aloud
+    pub speak_aloud: bool,
+    /// Font size at 1080p
+    pub font_size: f32,
+}
+
+impl SpeechBubble {
+    /// Creates a narrator bubble
+    #[must_use]
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            speaker: None,
+            duration: DEFAULT_SPEECH_SECONDS,
+            speak_aloud: false,
+            font_size: 32.0,
+        }
+    }
+
+    /// Sets who is talking
+    #[must_use]
+    pub fn with_speaker(mut self, speaker: impl Into<String>) -> Self {
+        self.speaker = Some(speaker.into());
+        self
+    }
+
+    /// Sets the on-screen time
+    #[must_use]
+    pub const fn with_duration(mut self, seconds: f32) -> Self {
+        self.duration = seconds;
+        self
+    }
+
+    /// Marks the line to be read aloud
+    #[must_use]
+    pub const fn spoken(mut self) -> Self {
+        self.speak_aloud = true;
+        self
+    }
+
+    /// Screen bounds for a bubble whose tail points at `speaker_top`
+    ///
+    /// Text wraps past a maximum width and the bubble is kept inside the
+    /// viewport.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn bounds(&self, speaker_top: Vec2, viewport: Vec2) -> Rect {
+        let scale = viewport.min_element() / 1080.0;
+        let font = self.font_size * scale;
+        let max_width = MAX_WIDTH * scale;
+        let text_width = self.text.chars().count() as f32 * font * 0.55;
+        let lines = (text_width / max_width).ceil().max(1.0);
+        let padding = PADDING * scale;
+        let size = Vec2::new(
+            text_width.min(max_width) + padding * 2.0,
+            (lines * font).mul_add(1.3, padding * 2.0),
+        )
+        .min(viewport);
+        let x = size
+            .x
+            .mul_add(-0.5, speaker_top.x)
+            .clamp(0.0, viewport.x - size.x);
+        let y = (TAIL.mul_add(-scale, speaker_top.y) - size.y).clamp(0.0, viewport.y - size.y);
+        Rect::new(x, y, size.x, size.y)
+    }
+}
+
+/// Speech bubbles currently on screen
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SpeechBubbles {
+    active: Vec<(SpeechBubble, f32)>,
+    to_speak: Vec<String>,
+}
+
+impl SpeechBubbles {
+    /// Creates an empty set
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            active: Vec::new(),
+            to_speak: Vec::new(),
+        }
+    }
+
+    /// Shows a bubble, replacing any line from the same speaker
+    pub fn say(&mut self, bubble: SpeechBubble) {
+        self.active
+            .retain(|(shown, _)| shown.speaker != bubble.speaker);
+        if self.active.len() >= MAX_BUBBLES {
+            let _ = self.active.remove(0);
+        }
+        if bubble.speak_aloud {
+            self.to_speak.push(bubble.text.clone());
+        }
+        self.active.push((bubble, 0.0));
+    }
+
+    /// Advances timers, removing expired bubbles
+    pub fn update(&mut self, dt: f32) {
+        for (_, elapsed) in &mut self.active {
+            *elapsed += dt;
+        }
+        self.active
+            .retain(|(bubble, elapsed)| *elapsed < bubble.duration);
+    }
+
+    /// Bubbles on screen, oldest first
+    pub fn iter(&self) -> impl Iterator<Item = &SpeechBubble> {
+        self.active.iter().map(|(bubble, _)| bubble)
+    }
+
+    /// The bubble a speaker is showing
+    #[must_use]
+    pub fn for_speaker(&self, speaker: &str) -> Option<&SpeechBubble> {
+        self.iter()
+            .find(|bubble| bubble.speaker.as_deref() == Some(speaker))
+    }
+
+    /// Lines waiting to be read aloud, in the order they were said
+    pub fn take_spoken(&mut self) -> Vec<String> {
+        core::mem::take(&mut self.to_speak)
+    }
+
+    /// Removes a speaker's bubble early (e.g. tapped)
+    pub fn dismiss(&mut self, speaker: Option<&str>) {
+        self.active
+            .retain(|(bubble, _)| bubble.speaker.as_deref() != speaker);
+    }
+
+    /// Drops everything
+    pub fn clear(&mut self) {
+        self.active.clear();
+        self.to_speak.clear();
+    }
+
+    /// Number of bubbles on screen
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.active.len()
+    }
+
+    /// Returns true if no one is talking
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.active.is_empty()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_speaker_replaces_line() {
+        let mut speech = SpeechBubbles::new();
+        speech.say(SpeechBubble::new("Hello!").with_speaker("bunny"));
+        speech.say(SpeechBubble::new("Hi bunny").with_speaker("fox"));
+        speech.say(SpeechBubble::new("Let's go!").with_speaker("bunny"));
+        assert_eq!(speech.len(), 2);
+        assert_eq!(speech.for_speaker("bunny").unwrap().text, "Let's go!");
+    }
+
+    #[test]
+    fn test_bubbles_expire() {
+        let mut speech = SpeechBubbles::new();
+        speech.say(SpeechBubble::new("quick").with_duration(1.0));
+        speech.say(SpeechBubble::new("slow").with_speaker("owl"));
+        speech.update(1.5);
+        assert_eq!(
+            speech.iter().map(|b| b.text.as_str()).collect::<Vec<_>>(),
+            ["slow"]
+        );
+        speech.update(DEFAULT_SPEECH_SECONDS);
+        assert!(speech.is_empty());
+    }
+
+    #[test]
+    fn test_spoken_lines_are_taken_once() {
+        let mut speech = SpeechBubbles::new();
+        speech.say(SpeechBubble::new("Read me").spoken());
+        speech.say(SpeechBubble::new("Quiet").with_speaker("cat"));
+        assert_eq!(speech.take_spoken(), vec!["Read me".to_string()]);
+        assert!(speech.take_spoken().is_empty());
+    }
+
+    #[test]
+    fn test_bounds_sit_above_speaker_and_wrap() {
+        let viewport = Vec2::new(1920.0, 1080.0);
+        let short = SpeechBubble::new("Hi").bounds(Vec2::new(960.0, 600.0), viewport);
+        assert!((short.width.mul_add(0.5, short.x) - 960.0).abs() < 0.01);
+        assert!(short.y + short.height <= 600.0);
+
+        let long = SpeechBubble::new("word ".repeat(40));
+        let rect = long.bounds(Vec2::new(10.0, 100.0), viewport);
+        assert!(rect.width <= PADDING.mul_add(2.0, MAX_WIDTH) + 0.01);
+        assert!(rect.height > short.height);
+        assert!(rect.x >= 0.0 && rect.y >= 0.0);
+    }
+}
@@ -0,0 +1,278 @@
+//! Transient toast notifications.
+//!
+//! Toasts are short messages ("Great job!", "+10 points") that appear for a
+//! few seconds without blocking input. [`ToastQueue`] shows one at a time in
+//! the order they were raised, fading each in and out. It is the
+//! presentation path for the YAML `Show(message)` action and for
+//! achievement unlocks.
+
+use alloc::collections::VecDeque;
+
+use glam::Vec2;
+use serde::{Deserialize, Serialize};
+
+use jugar_core::{AchievementUnlocked, Anchor, Events, Rect, DEFAULT_ACHIEVEMENT_ICON};
+
+use crate::Icon;
+
+/// Default time a toast stays on screen (seconds)
+pub const DEFAULT_TOAST_SECONDS: f32 = 3.0;
+
+/// Fade in/out time (seconds)
+const FADE_SECONDS: f32 = 0.25;
+
+/// Toasts beyond this many waiting are dropped, oldest first
+const MAX_QUEUED: usize = 8;
+
+/// A transient message
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Toast {
+    /// Message text
+    pub message: String,
+    /// Optional icon
+    pub icon: Option<Icon>,
+    /// Seconds on screen, including fades
+    pub duration: f32,
+    /// Where the toast appears
+    pub anchor: Anchor,
+    /// Size at 1080p
+    pub size: Vec2,
+}
+
+impl Toast {
+    /// Creates a toast at the bottom center
+    #[must_use]
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            icon: None,
+            duration: DEFAULT_TOAST_SECONDS,
+            anchor: Anchor::BottomCenter,
+            size: Vec2::new(720.0, 120.0),
+        }
+    }
+
+    /// Creates an "Achievement unlocked" toast at the top center
+    #[must_use]
+    pub fn achievement(unlocked: &AchievementUnlocked) -> Self {
+        let icon = if unlocked.icon == DEFAULT_ACHIEVEMENT_ICON {
+            Icon::Star
+        } else {
+            Icon::Custom(unlocked.icon.clone())
+        };
+        Self::new(format!("Achievement unlocked: {}!", unlocked.name))
+            .with_icon(icon)
+            .with_anchor(Anchor::TopCenter)
+    }
+
+    /// Sets the icon
+    #[must_use]
+    pub fn with_icon(mut self, icon: Icon) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+
+    /// Sets the on-screen time
+    #[must_use]
+    pub const fn with_duration(mut self, seconds: f32) -> Self {
+        self.duration = seconds;
+        self
+    }
+
+    /// Sets the anchor
+    #[must_use]
+    pub const fn with_anchor(mut self, anchor: Anchor) -> Self {
+        self.anchor = anchor;
+        self
+    }
+
+    /// Screen bounds inside a viewport, inset from the anchored edge
+    #[must_use]
+    pub fn bounds(&self, viewport: Vec2) -> Rect {
+        let scale = viewport.min_element() / 1080.0;
+        let size = (self.size * scale).min(viewport);
+        let margin = 48.0 * scale;
+        let (ax, ay) = self.anchor.normalized();
+        let x = (viewport.x - size.x).mul_add(ax, margin * 2.0_f32.mul_add(-ax, 1.0));
+        let y = (viewport.y - size.y).mul_add(ay, margin * 2.0_f32.mul_add(-ay, 1.0));
+        Rect::new(x, y, size.x, size.y)
+    }
+}
+
+/// Queue showing one toast at a time
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ToastQueue {
+    pending: VecDeque<Toast>,
+    current: Option<(Toast, f32)>,
+}
+
+impl ToastQueue {
+    /// Creates an empty queue
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            pending: VecDeque::new(),
+            current: None,
+        }
+    }
+
+    /// Queues a plain message (the YAML `Show(message)` action)
+    pub fn show(&mut self, message: impl Into<String>) {
+        self.push(Toast::new(message));
+    }
+
+    /// Queues a toast
+    pub fn push(&mut self, toast: Toast) {
+        if self.pending.len() >= MAX_QUEUED {
+            let _ = self.pending.pop_front();
+        }
+        self.pending.push_back(toast);
+        if self.current.is_none() {
+            self.advance();
+        }
+    }
+
+    fn advance(&mut self) {
+        self.current = self.pending.pop_front().map(|toast| (toast, 0.0));
+    }
+
+    /// Queues a toast for each achievement unlocked this frame
+    pub fn show_achievements(&mut self, events: &Events<AchievementUnlocked>) {
+        for unlocked in events.iter() {
+            self.push(Toast::achievement(unlocked));
+        }
+    }
+
+    /// Advances timers, moving to the next toast when one expires
+    pub fn update(&mut self, dt: f32) {
+        let Some((toast, elapsed)) = &mut self.current else {
+            return;
+        };
+        *elapsed += dt;
+        if *elapsed >= toast.duration {
+            self.advance();
+        }
+    }
+
+    /// The toast on screen
+    #[must_use]
+    pub fn current(&self) -> Option<&Toast> {
+        self.current.as_ref().map(|(toast, _)| toast)
+    }
+
+    /// Opacity of the current toast (0 when none)
+    #[must_use]
+    pub fn alpha(&self) -> f32 {
+        self.current.as_ref().map_or(0.0, |(toast, elapsed)| {
+            let fade_in = elapsed / FADE_SECONDS;
+            let fade_out = (toast.duration - elapsed) / FADE_SECONDS;
+            fade_in.min(fade_out).clamp(0.0, 1.0)
+        })
+    }
+
+    /// Dismisses the current toast early (e.g. tapped)
+    pub fn dismiss(&mut self) {
+        self.advance();
+    }
+
+    /// Drops everything
+    pub fn clear(&mut self) {
+        self.pending.clear();
+        self.current = None;
+    }
+
+    /// Number of toasts waiting behind the current one
+    #[must_use]
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Returns true if nothing is showing or waiting
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.current.is_none()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toasts_show_in_order() {
+        let mut toasts = ToastQueue::new();
+        toasts.show("first");
+        toasts.push(Toast::new("second").with_duration(1.0));
+        assert_eq!(toasts.current().unwrap().message, "first");
+        assert_eq!(toasts.pending(), 1);
+        toasts.update(DEFAULT_TOAST_SECONDS);
+        assert_eq!(toasts.current().unwrap().message, "second");
+        toasts.update(1.0);
+        assert!(toasts.is_empty());
+    }
+
+    #[test]
+    fn test_alpha_fades() {
+        let mut toasts = ToastQueue::new();
+        assert!(toasts.alpha().abs() < f32::EPSILON);
+        toasts.show("hi");
+        toasts.update(FADE_SECONDS * 0.5);
+        assert!((toasts.alpha() - 0.5).abs() < 0.01);
+        toasts.update(1.0);
+        assert!((toasts.alpha() - 1.0).abs() < f32::EPSILON);
+        toasts.update(DEFAULT_TOAST_SECONDS - 1.0 - FADE_SECONDS);
+        assert!((toasts.alpha() - 0.5).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_queue_is_bounded() {
+        let mut toasts = ToastQueue::new();
+        for i in 0..20 {
+            toasts.show(format!("{i}"));
+        }
+        assert_eq!(toasts.pending(), MAX_QUEUED);
+        toasts.dismiss();
+        // Oldest waiting toasts were dropped
+        assert_eq!(toasts.current().unwrap().message, "12");
+    }
+
+    #[test]
+    fn test_achievement_toasts() {
+        let mut events = Events::new();
+        events.send(AchievementUnlocked {
+            id: "first_hop".to_string(),
+            name: "First Hop".to_string(),
+            icon: DEFAULT_ACHIEVEMENT_ICON.to_string(),
+        });
+        events.send(AchievementUnlocked {
+            id: "carrots".to_string(),
+            name: "Carrot Muncher".to_string(),
+            icon: "carrot".to_string(),
+        });
+        let mut toasts = ToastQueue::new();
+        toasts.show_achievements(&events);
+
+        let first = toasts.current().unwrap();
+        assert_eq!(first.message, "Achievement unlocked: First Hop!");
+        assert_eq!(first.icon, Some(Icon::Star));
+        assert_eq!(first.anchor, Anchor::TopCenter);
+        toasts.dismiss();
+        assert_eq!(
+            toasts.current().unwrap().icon,
+            Some(Icon::Custom("carrot".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_bounds_follow_anchor() {
+        let viewport = Vec2::new(1920.0, 1080.0);
+        let bottom = Toast::new("x").bounds(viewport);
+        assert!((bottom.y + bottom.height - (1080.0 - 48.0)).abs() < 0.01);
+        assert!((bottom.width.mul_add(0.5, bottom.x) - 960.0).abs() < 0.01);
+        let top = Toast::new("x")
+            .with_anchor(Anchor::TopLeft)
+            .bounds(viewport);
+        assert!((top.x - 48.0).abs() < 0.01 && (top.y - 48.0).abs() < 0.01);
+    }
+}
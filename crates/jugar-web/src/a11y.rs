@@ -0,0 +1,219 @@
+//! Screen reader overlay for jugar-ui menus.
+//!
+//! The canvas is opaque to assistive technology, so each frame the game's
+//! [`UiContainer::semantics`](jugar::ui::UiContainer::semantics) tree is
+//! turned into an [`AccessibilityUpdate`]: one transparent DOM element per
+//! widget, positioned over the canvas with its ARIA role and label, plus the
+//! text to put in an `aria-live` region for values that changed.
+//! [`WebPlatform::frame`](crate::WebPlatform::frame) sends the update as the
+//! `accessibility` field of its output on frames where it changed, and
+//! JavaScript only mirrors the JSON into the DOM:
+//!
+//! ```javascript
+//! const update = JSON.parse(frameJson).accessibility;
+//! if (update) {
+//!     overlay.replaceChildren(...update.elements.map((el) => {
+//!         const node = document.createElement('div');
+//!         node.id = el.id;
+//!         node.setAttribute('role', el.role);
+//!         node.setAttribute('aria-label', el.label);
+//!         if (el.value !== undefined) node.setAttribute('aria-valuetext', el.value);
+//!         if (el.disabled) node.setAttribute('aria-disabled', 'true');
+//!         Object.assign(node.style, { position: 'absolute', left: `${el.x}px`, top: `${el.y}px`,
+//!             width: `${el.width}px`, height: `${el.height}px` });
+//!         return node;
+//!     }));
+//!     // Every announcement is read, not just the last one
+//!     if (update.announcements) liveRegion.textContent = update.announcements.join('. ');
+//! }
+//! ```
+
+use std::collections::HashMap;
+
+use jugar::ui::{Role, SemanticNode};
+use serde::{Deserialize, Serialize};
+
+/// A DOM element mirroring one widget.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AriaElement {
+    /// Element id (the widget id)
+    pub id: String,
+    /// WAI-ARIA role
+    pub role: String,
+    /// `aria-label`
+    pub label: String,
+    /// `aria-valuetext`, if the widget has a value
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub value: Option<String>,
+    /// `aria-disabled`
+    #[serde(skip_serializing_if = "std::ops::Not::not", default)]
+    pub disabled: bool,
+    /// Left edge in CSS pixels relative to the canvas
+    pub x: f32,
+    /// Top edge in CSS pixels relative to the canvas
+    pub y: f32,
+    /// Width in CSS pixels
+    pub width: f32,
+    /// Height in CSS pixels
+    pub height: f32,
+}
+
+/// Overlay contents and live-region text for one frame.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AccessibilityUpdate {
+    /// Elements to place over the canvas, in render order
+    pub elements: Vec<AriaElement>,
+    /// Text for the `aria-live` region, oldest first
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub announcements: Vec<String>,
+}
+
+impl AccessibilityUpdate {
+    /// Serializes the update for JavaScript.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_else(|_| String::from("{\"elements\":[]}"))
+    }
+}
+
+/// Tracks the semantic tree between frames to decide what to announce.
+///
+/// Status widgets (score, timer) are announced whenever their spoken text
+/// changes, and dialogs when they first appear. Everything else is only read
+/// when the user moves to it.
+#[derive(Debug, Clone)]
+pub struct AccessibilityOverlay {
+    /// CSS pixels per viewport pixel
+    scale: f32,
+    /// Spoken text of each announced widget last frame
+    spoken: HashMap<String, String>,
+}
+
+impl Default for AccessibilityOverlay {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AccessibilityOverlay {
+    /// Creates an overlay for a canvas drawn at CSS size.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            scale: 1.0,
+            spoken: HashMap::new(),
+        }
+    }
+
+    /// Sets the CSS pixels per viewport pixel (canvas CSS width / canvas width).
+    pub fn set_scale(&mut self, scale: f32) {
+        if scale.is_finite() && scale > 0.0 {
+            self.scale = scale;
+        }
+    }
+
+    /// Builds this frame's overlay from the semantic tree.
+    pub fn update(&mut self, nodes: &[SemanticNode]) -> AccessibilityUpdate {
+        let mut announcements = Vec::new();
+        let mut spoken = HashMap::new();
+
+        for node in nodes.iter().filter(|node| announces(node.role)) {
+            let text = node.spoken_text();
+            if self.spoken.get(&node.id.0) != Some(&text) {
+                announcements.push(text.clone());
+            }
+            let _ = spoken.insert(node.id.0.clone(), text);
+        }
+        self.spoken = spoken;
+
+        AccessibilityUpdate {
+            elements: nodes.iter().map(|node| self.element(node)).collect(),
+            announcements,
+        }
+    }
+
+    fn element(&self, node: &SemanticNode) -> AriaElement {
+        AriaElement {
+            id: node.id.0.clone(),
+            role: node.role.aria_role().to_string(),
+            label: node.label.clone(),
+            value: node.value.clone(),
+            disabled: node.disabled,
+            x: node.bounds.x * self.scale,
+            y: node.bounds.y * self.scale,
+            width: node.bounds.width * self.scale,
+            height: node.bounds.height * self.scale,
+        }
+    }
+}
+
+/// Roles whose text goes to the live region
+fn announces(role: Role) -> bool {
+    role.is_live() || role == Role::Dialog
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use glam::Vec2;
+    use jugar::ui::{Button, SemanticInfo, UiContainer};
+    use jugar_core::{Anchor, UiElement};
+
+    fn menu(score: &str) -> UiContainer {
+        let mut ui = UiContainer::new(1920.0, 1080.0);
+        let play = Button::new("Play", Vec2::new(200.0, 80.0)).with_anchor(Anchor::TopLeft);
+        let info = play.semantic_info();
+        let id = ui.add_widget("play", play.element);
+        ui.set_semantics(&id, info);
+        let id = ui.add_widget("score", UiElement::default());
+        ui.set_semantics(
+            &id,
+            SemanticInfo::new(Role::Status, "Score").with_value(score),
+        );
+        ui
+    }
+
+    #[test]
+    fn test_elements_mirror_semantics() {
+        let mut overlay = AccessibilityOverlay::new();
+        overlay.set_scale(0.5);
+        let update = overlay.update(&menu("0").semantics());
+
+        assert_eq!(update.elements.len(), 2);
+        let play = &update.elements[0];
+        assert_eq!(play.role, "button");
+        assert_eq!(play.label, "Play");
+        assert!((play.width - 100.0).abs() < f32::EPSILON);
+
+        let json = update.to_json();
+        assert!(json.contains("\"role\":\"status\""));
+        assert!(!json.contains("disabled"));
+    }
+
+    #[test]
+    fn test_live_region_announces_changes_only() {
+        let mut overlay = AccessibilityOverlay::new();
+        assert_eq!(
+            overlay.update(&menu("0").semantics()).announcements,
+            vec!["Score, 0".to_string()]
+        );
+        assert!(overlay
+            .update(&menu("0").semantics())
+            .announcements
+            .is_empty());
+        assert_eq!(
+            overlay.update(&menu("10").semantics()).announcements,
+            vec!["Score, 10".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_invalid_scale_ignored() {
+        let mut overlay = AccessibilityOverlay::new();
+        overlay.set_scale(f32::NAN);
+        overlay.set_scale(0.0);
+        let update = overlay.update(&menu("0").semantics());
+        assert!((update.elements[0].width - 200.0).abs() < f32::EPSILON);
+    }
+}
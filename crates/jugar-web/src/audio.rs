@@ -16,6 +16,7 @@
 // const fn with mutable references is not yet stable
 #![allow(clippy::missing_const_for_fn)]
 
+use jugar_core::{FramePool, PoolStats};
 use serde::{Deserialize, Serialize};
 
 /// Audio events that JavaScript should play via Web Audio API.
@@ -69,6 +70,24 @@ pub enum AudioEvent {
     },
 }
 
+impl AudioEvent {
+    /// Seconds the page plays this event for
+    ///
+    /// Matches the Web Audio player in the examples: tones with a fixed
+    /// length play 0.15s, the goal arpeggio 0.35s and the start jingle 0.34s.
+    #[must_use]
+    pub const fn duration(&self) -> f32 {
+        match self {
+            Self::PaddleHit { duration, .. } | Self::WallBounce { duration, .. } => *duration,
+            Self::RallyMilestone { .. } => 0.15,
+            Self::Goal { .. } => 0.35,
+            Self::GameStart { .. } => 0.34,
+            Self::SoundToggle { enabled: true, .. } => 0.08,
+            Self::SoundToggle { enabled: false, .. } => 0.0,
+        }
+    }
+}
+
 /// Procedural audio generator for Pong.
 ///
 /// Generates audio events based on game state changes.
@@ -96,7 +115,7 @@ pub struct ProceduralAudio {
     /// Whether audio is enabled
     enabled: bool,
     /// Pending audio events to be sent to JavaScript
-    events: Vec<AudioEvent>,
+    events: FramePool<AudioEvent>,
 }
 
 impl Default for ProceduralAudio {
@@ -112,7 +131,7 @@ impl ProceduralAudio {
         Self {
             master_volume: 0.7,
             enabled: true,
-            events: Vec::with_capacity(4),
+            events: FramePool::with_capacity(4),
         }
     }
 
@@ -275,15 +294,27 @@ impl ProceduralAudio {
     ///
     /// # Returns
     ///
-    /// Vector of audio events to be played by JavaScript.
+    /// Vector of audio events to be played by JavaScript. Pass it back to
+    /// [`Self::recycle_events`] once sent so the next frame reuses it.
     pub fn take_events(&mut self) -> Vec<AudioEvent> {
-        core::mem::take(&mut self.events)
+        self.events.take()
+    }
+
+    /// Returns a buffer from [`Self::take_events`] for reuse.
+    pub fn recycle_events(&mut self, events: Vec<AudioEvent>) {
+        self.events.recycle(events);
+    }
+
+    /// Returns allocation counters of the event buffer.
+    #[must_use]
+    pub const fn pool_stats(&self) -> PoolStats {
+        self.events.stats()
     }
 
     /// Returns pending events without clearing (for inspection).
     #[must_use]
     pub fn peek_events(&self) -> &[AudioEvent] {
-        &self.events
+        self.events.as_slice()
     }
 
     /// Clears all pending events.
@@ -515,6 +546,21 @@ mod tests {
         assert_eq!(audio.event_count(), 0);
     }
 
+    #[test]
+    fn test_recycle_events_reuses_buffer() {
+        let mut audio = ProceduralAudio::new();
+        for _ in 0..10 {
+            audio.on_wall_bounce();
+            let events = audio.take_events();
+            assert_eq!(events.len(), 1);
+            audio.recycle_events(events);
+        }
+        let stats = audio.pool_stats();
+        assert_eq!(stats.frames, 10);
+        // Only the spare buffer's first fill allocates
+        assert_eq!(stats.allocations, 1);
+    }
+
     #[test]
     fn test_multiple_events() {
         let mut audio = ProceduralAudio::new();
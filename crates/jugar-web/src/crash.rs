@@ -0,0 +1,539 @@
+//! Crash reporting with kid-friendly recovery screens.
+//!
+//! A panic hook turns panics into a [`CrashReport`] holding a redacted
+//! message, the frame number and the last few input events. After a crash
+//! the game instance can't be trusted, so JavaScript asks the free functions
+//! in this module for the recovery screen instead of calling into
+//! `WebPlatform` again. The screen offers to restore the last autosaved
+//! snapshot or to start over; a tap goes to
+//! [`WebPlatform::recover`](crate::WebPlatform::recover), which builds the
+//! replacement game.
+
+use std::collections::VecDeque;
+use std::sync::{Mutex, Once, PoisonError};
+
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::input::{BrowserEventData, BrowserInputEvent};
+use crate::platform::FrameOutput;
+use crate::render::{Color, RenderFrame, TextAlign, TextBaseline};
+
+/// Number of recent input events kept for crash reports.
+pub const CRASH_INPUT_HISTORY: usize = 16;
+
+/// Maximum length (in characters) of a redacted crash message.
+pub const MAX_CRASH_MESSAGE_LEN: usize = 160;
+
+/// Title shown on the recovery screen.
+pub const CRASH_TITLE: &str = "Oops! The robot tripped";
+
+/// Placeholder for redacted text.
+const REDACTED: &str = "…";
+
+/// Helper character shown on the recovery screen (the robot from the YAML
+/// editor's error messages).
+const CRASH_HELPER: &str = "🤖";
+
+/// Structured description of a panic.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CrashReport {
+    /// Panic message with quoted text and paths redacted
+    pub message: String,
+    /// Source file name and line, without directories
+    pub location: Option<String>,
+    /// Frame number when the panic happened
+    pub frame: u64,
+    /// Most recent input events, oldest first
+    pub recent_inputs: Vec<String>,
+    /// Last autosaved snapshot JSON, if any
+    pub snapshot: Option<String>,
+}
+
+/// What a tap on the recovery screen asks for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CrashAction {
+    /// Tap missed the buttons
+    None,
+    /// Restore the autosaved snapshot
+    Restore,
+    /// Start a fresh game
+    Restart,
+}
+
+impl CrashAction {
+    /// Name passed to JavaScript (empty for [`CrashAction::None`]).
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::None => "",
+            Self::Restore => "restore",
+            Self::Restart => "restart",
+        }
+    }
+}
+
+/// Button layout of the recovery screen: `(x, y, width, height)`.
+fn button_rects(width: f32, height: f32, can_restore: bool) -> Vec<(CrashAction, [f32; 4])> {
+    let button_w = (width * 0.3).min(320.0);
+    let button_h = 64.0;
+    let y = height * 0.68;
+    if can_restore {
+        let gap = button_w * 0.15;
+        vec![
+            (
+                CrashAction::Restore,
+                [width / 2.0 - gap / 2.0 - button_w, y, button_w, button_h],
+            ),
+            (
+                CrashAction::Restart,
+                [width / 2.0 + gap / 2.0, y, button_w, button_h],
+            ),
+        ]
+    } else {
+        vec![(
+            CrashAction::Restart,
+            [(width - button_w) / 2.0, y, button_w, button_h],
+        )]
+    }
+}
+
+impl CrashReport {
+    /// Returns true if an autosaved snapshot can be restored.
+    #[must_use]
+    pub const fn can_restore(&self) -> bool {
+        self.snapshot.is_some()
+    }
+
+    /// Draws the recovery screen.
+    pub fn render(&self, frame: &mut RenderFrame, width: f32, height: f32) {
+        let text = Color::WHITE;
+        let button = Color::rgb(0.2, 0.45, 0.9);
+
+        frame.clear_screen(Color::rgb(0.1, 0.1, 0.25));
+        frame.fill_text_aligned(
+            CRASH_HELPER,
+            width / 2.0,
+            height * 0.25,
+            "96px sans-serif",
+            text,
+            TextAlign::Center,
+            TextBaseline::Middle,
+        );
+        frame.fill_text_aligned(
+            CRASH_TITLE,
+            width / 2.0,
+            height * 0.42,
+            "bold 48px sans-serif",
+            text,
+            TextAlign::Center,
+            TextBaseline::Middle,
+        );
+        let hint = if self.can_restore() {
+            "Don't worry, we saved your game a moment ago."
+        } else {
+            "Don't worry, let's try again!"
+        };
+        frame.fill_text_aligned(
+            hint,
+            width / 2.0,
+            height * 0.52,
+            "28px sans-serif",
+            text,
+            TextAlign::Center,
+            TextBaseline::Middle,
+        );
+
+        for (action, [x, y, w, h]) in button_rects(width, height, self.can_restore()) {
+            let label = match action {
+                CrashAction::Restore => "Keep playing",
+                CrashAction::Restart | CrashAction::None => "Start over",
+            };
+            frame.fill_rect(x, y, w, h, button);
+            frame.fill_text_aligned(
+                label,
+                x + w / 2.0,
+                y + h / 2.0,
+                "bold 28px sans-serif",
+                text,
+                TextAlign::Center,
+                TextBaseline::Middle,
+            );
+        }
+    }
+
+    /// Returns the action of the button at a point.
+    #[must_use]
+    pub fn action_at(&self, x: f32, y: f32, width: f32, height: f32) -> CrashAction {
+        button_rects(width, height, self.can_restore())
+            .into_iter()
+            .find(|(_, [bx, by, bw, bh])| x >= *bx && x <= bx + bw && y >= *by && y <= by + bh)
+            .map_or(CrashAction::None, |(action, _)| action)
+    }
+}
+
+/// Redacts a panic message for reporting.
+///
+/// Double-quoted text may contain what a child typed, so it is replaced; paths are
+/// cut to the file name; the result is truncated to
+/// [`MAX_CRASH_MESSAGE_LEN`] characters.
+#[must_use]
+pub fn redact_message(message: &str) -> String {
+    let mut unquoted = String::with_capacity(message.len());
+    let mut quote: Option<char> = None;
+    for c in message.chars() {
+        match quote {
+            Some(open) if c == open => {
+                unquoted.push_str(REDACTED);
+                unquoted.push(c);
+                quote = None;
+            }
+            Some(_) => {}
+            None if c == '"' => {
+                unquoted.push(c);
+                quote = Some(c);
+            }
+            None => unquoted.push(c),
+        }
+    }
+    if quote.is_some() {
+        unquoted.push_str(REDACTED);
+    }
+
+    let redacted = unquoted
+        .split(' ')
+        .map(|word| {
+            word.rsplit(['/', '\\'])
+                .next()
+                .filter(|_| word.contains(['/', '\\']))
+                .unwrap_or(word)
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    if redacted.chars().count() > MAX_CRASH_MESSAGE_LEN {
+        let mut truncated: String = redacted.chars().take(MAX_CRASH_MESSAGE_LEN).collect();
+        truncated.push_str(REDACTED);
+        truncated
+    } else {
+        redacted
+    }
+}
+
+/// Describes an input event without pointer coordinates.
+fn describe_input(event: &BrowserInputEvent) -> String {
+    match &event.data {
+        BrowserEventData::Key { key } => format!("{} {key}", event.event_type),
+        _ => event.event_type.clone(),
+    }
+}
+
+/// Keeps the context needed to describe a crash.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CrashRecorder {
+    frame: u64,
+    inputs: VecDeque<String>,
+    snapshot: Option<String>,
+    report: Option<CrashReport>,
+}
+
+impl CrashRecorder {
+    /// Creates an empty recorder.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            frame: 0,
+            inputs: VecDeque::new(),
+            snapshot: None,
+            report: None,
+        }
+    }
+
+    /// Records the start of a frame and its input events JSON.
+    pub fn begin_frame(&mut self, frame: u64, input_events_json: &str) {
+        self.frame = frame;
+        let Ok(events) = serde_json::from_str::<Vec<BrowserInputEvent>>(input_events_json) else {
+            return;
+        };
+        for event in &events {
+            if self.inputs.len() == CRASH_INPUT_HISTORY {
+                let _ = self.inputs.pop_front();
+            }
+            self.inputs.push_back(describe_input(event));
+        }
+    }
+
+    /// Stores the latest autosaved snapshot.
+    pub fn autosave(&mut self, snapshot: String) {
+        self.snapshot = Some(snapshot);
+    }
+
+    /// Builds and stores a report for a panic.
+    pub fn crash(&mut self, message: &str, location: Option<String>) -> CrashReport {
+        let report = CrashReport {
+            message: redact_message(message),
+            location,
+            frame: self.frame,
+            recent_inputs: self.inputs.iter().cloned().collect(),
+            snapshot: self.snapshot.clone(),
+        };
+        self.report = Some(report.clone());
+        report
+    }
+
+    /// The stored crash report, if a panic happened.
+    #[must_use]
+    pub const fn report(&self) -> Option<&CrashReport> {
+        self.report.as_ref()
+    }
+
+    /// Clears the crash after the player chose how to recover.
+    pub fn recover(&mut self) {
+        self.report = None;
+        self.inputs.clear();
+    }
+
+    /// Handles a tap on the recovery screen.
+    ///
+    /// Returns the chosen action and the autosaved snapshot, and clears the
+    /// crash; returns `None` if nothing crashed or the tap missed.
+    pub fn take_recovery(
+        &mut self,
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+    ) -> Option<(CrashAction, Option<String>)> {
+        let report = self.report.as_ref()?;
+        let action = report.action_at(x, y, width, height);
+        if action == CrashAction::None {
+            return None;
+        }
+        let snapshot = report.snapshot.clone();
+        self.recover();
+        Some((action, snapshot))
+    }
+}
+
+static RECORDER: Mutex<CrashRecorder> = Mutex::new(CrashRecorder::new());
+static HOOK: Once = Once::new();
+
+/// Runs `f` with the global recorder, even if a panic poisoned the lock.
+fn with_recorder<T>(f: impl FnOnce(&mut CrashRecorder) -> T) -> T {
+    let mut recorder = RECORDER.lock().unwrap_or_else(PoisonError::into_inner);
+    f(&mut recorder)
+}
+
+/// Installs the panic hook (idempotent).
+///
+/// The previous hook still runs, so panics keep reaching the console.
+pub fn install_panic_hook() {
+    HOOK.call_once(|| {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            let payload = info.payload();
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|s| (*s).to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_default();
+            let location = info.location().map(|loc| {
+                let file = loc.file().rsplit(['/', '\\']).next().unwrap_or("");
+                format!("{file}:{}", loc.line())
+            });
+            note_crash(&message, location);
+            previous(info);
+        }));
+    });
+}
+
+/// Records the start of a frame in the global recorder.
+pub fn note_frame(frame: u64, input_events_json: &str) {
+    with_recorder(|recorder| recorder.begin_frame(frame, input_events_json));
+}
+
+/// Stores an autosaved snapshot in the global recorder.
+pub fn note_autosave(snapshot: String) {
+    with_recorder(|recorder| recorder.autosave(snapshot));
+}
+
+/// Records a crash in the global recorder.
+pub fn note_crash(message: &str, location: Option<String>) {
+    let _ = with_recorder(|recorder| recorder.crash(message, location));
+}
+
+/// Returns the last crash report, if any.
+#[must_use]
+pub fn last_crash() -> Option<CrashReport> {
+    with_recorder(|recorder| recorder.report().cloned())
+}
+
+/// Handles a tap on the recovery screen with the global recorder.
+///
+/// See [`CrashRecorder::take_recovery`].
+#[must_use]
+pub fn take_recovery(
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+) -> Option<(CrashAction, Option<String>)> {
+    with_recorder(|recorder| recorder.take_recovery(x, y, width, height))
+}
+
+/// Installs the crash reporter panic hook.
+#[wasm_bindgen(js_name = "installCrashReporter")]
+pub fn install_crash_reporter() {
+    install_panic_hook();
+}
+
+/// Returns true if the game crashed and is waiting on the recovery screen.
+#[wasm_bindgen(js_name = "isCrashed")]
+#[must_use]
+pub fn is_crashed() -> bool {
+    with_recorder(|recorder| recorder.report().is_some())
+}
+
+/// Returns the last crash report as JSON, or an empty string.
+#[wasm_bindgen(js_name = "crashReport")]
+#[must_use]
+pub fn crash_report_json() -> String {
+    last_crash()
+        .and_then(|report| serde_json::to_string(&report).ok())
+        .unwrap_or_default()
+}
+
+/// Returns the recovery screen as frame output JSON.
+#[wasm_bindgen(js_name = "crashScreen")]
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn crash_screen(width: u32, height: u32) -> String {
+    let mut frame = RenderFrame::new();
+    if let Some(report) = last_crash() {
+        report.render(&mut frame, width as f32, height as f32);
+    }
+    let output = FrameOutput {
+        commands: frame.commands,
+        audio_events: Vec::new(),
+        actions: Vec::new(),
+        accessibility: None,
+        debug_info: None,
+    };
+    serde_json::to_string(&output).unwrap_or_else(|_| r#"{"commands":[]}"#.to_string())
+}
+
+/// Handles a tap on the recovery screen.
+///
+/// Returns `"restore"` or `"restart"` and clears the crash, or an empty
+/// string if the tap missed. Pages that build the replacement game
+/// themselves use this; otherwise call `WebPlatform.recover`.
+#[wasm_bindgen(js_name = "crashScreenAction")]
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn crash_screen_action(x: f32, y: f32, width: u32, height: u32) -> String {
+    take_recovery(x, y, width as f32, height as f32)
+        .map_or(CrashAction::None, |(action, _)| action)
+        .name()
+        .to_string()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::render::Canvas2DCommand;
+
+    #[test]
+    fn test_redact_quoted_text_and_paths() {
+        let redacted =
+            redact_message("unknown word \"my name is Sam\" in /home/sam/games/catch.yaml");
+        assert!(!redacted.contains("Sam"));
+        assert!(!redacted.contains("/home"));
+        assert!(redacted.contains("catch.yaml"));
+    }
+
+    #[test]
+    fn test_redact_truncates() {
+        let redacted = redact_message(&"x".repeat(500));
+        assert_eq!(redacted.chars().count(), MAX_CRASH_MESSAGE_LEN + 1);
+    }
+
+    #[test]
+    fn test_recorder_keeps_last_inputs() {
+        let mut recorder = CrashRecorder::new();
+        for i in 0..20 {
+            let json = format!(
+                r#"[{{"event_type":"KeyDown","timestamp":{i}.0,"data":{{"key":"Key{i}"}}}}]"#
+            );
+            recorder.begin_frame(i, &json);
+        }
+        let report = recorder.crash("index out of bounds", Some("game.rs:10".to_string()));
+
+        assert_eq!(report.frame, 19);
+        assert_eq!(report.recent_inputs.len(), CRASH_INPUT_HISTORY);
+        assert_eq!(report.recent_inputs.last().unwrap(), "KeyDown Key19");
+        assert!(!report.can_restore());
+        assert_eq!(recorder.report(), Some(&report));
+    }
+
+    #[test]
+    fn test_pointer_inputs_drop_coordinates() {
+        let mut recorder = CrashRecorder::new();
+        recorder.begin_frame(
+            1,
+            r#"[{"event_type":"MouseDown","timestamp":1.0,"data":{"button":0,"x":12.0,"y":34.0}}]"#,
+        );
+        let report = recorder.crash("boom", None);
+        assert_eq!(report.recent_inputs, vec!["MouseDown"]);
+    }
+
+    #[test]
+    fn test_recovery_screen_offers_restore() {
+        let mut recorder = CrashRecorder::new();
+        recorder.autosave(r#"{"left_score":3}"#.to_string());
+        let report = recorder.crash("boom", None);
+
+        let mut frame = RenderFrame::new();
+        report.render(&mut frame, 1920.0, 1080.0);
+        let texts: Vec<&str> = frame
+            .commands
+            .iter()
+            .filter_map(|cmd| match cmd {
+                Canvas2DCommand::FillText { text, .. } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert!(texts.contains(&CRASH_TITLE));
+        assert!(texts.contains(&"🤖"));
+        assert!(texts.contains(&"Keep playing"));
+
+        let [x, y, w, h] = button_rects(1920.0, 1080.0, true)[0].1;
+        assert_eq!(
+            report.action_at(x + w / 2.0, y + h / 2.0, 1920.0, 1080.0),
+            CrashAction::Restore
+        );
+        assert_eq!(
+            report.action_at(0.0, 0.0, 1920.0, 1080.0),
+            CrashAction::None
+        );
+
+        assert_eq!(recorder.take_recovery(0.0, 0.0, 1920.0, 1080.0), None);
+        assert_eq!(
+            recorder.take_recovery(x + w / 2.0, y + h / 2.0, 1920.0, 1080.0),
+            Some((
+                CrashAction::Restore,
+                Some(r#"{"left_score":3}"#.to_string())
+            ))
+        );
+        assert!(recorder.report().is_none());
+    }
+
+    #[test]
+    fn test_without_snapshot_only_restart() {
+        let report = CrashRecorder::new().crash("boom", None);
+        let buttons = button_rects(800.0, 600.0, report.can_restore());
+        assert_eq!(buttons.len(), 1);
+        assert_eq!(buttons[0].0, CrashAction::Restart);
+    }
+}
@@ -0,0 +1,582 @@
+//! Responsive device matrix.
+//!
+//! Jugar games promise to play the same from a small phone to a 32:9
+//! monitor. [`DeviceMatrix`] runs one test on a [`WebPlatform`] sized for
+//! each [`DeviceDescriptor`], checks the HUD against layout invariants
+//! (every control inside the device's safe area, no two controls
+//! overlapping) and tiles a snapshot of each device's last frame into one
+//! grid image for review.
+//!
+//! ```ignore
+//! use jugar_web::DeviceMatrix;
+//!
+//! let report = DeviceMatrix::new().run(|platform| {
+//!     let _ = platform.frame(16.0, "[]");
+//! });
+//! assert!(report.passed(), "{:?}", report.violations().collect::<Vec<_>>());
+//! std::fs::write("devices.png", report.grid().to_png())?;
+//! ```
+
+use glam::Vec2;
+use jugar_core::Rect;
+use jugar_render::{rasterize, BitmapFont, Image, RenderCommand, Viewport};
+
+use crate::input::PointerCapabilities;
+use crate::platform::{WebConfig, WebPlatform};
+use crate::render::{Canvas2DCommand, TextAlign, TextBaseline};
+
+/// Width of each device snapshot in the grid
+pub const SNAPSHOT_WIDTH: u32 = 320;
+
+/// Snapshots per row of the grid
+pub const GRID_COLUMNS: usize = 3;
+
+/// Gap between grid cells in pixels
+const GRID_GAP: u32 = 8;
+
+/// Grid background (dark grey, opaque)
+const GRID_BACKGROUND: [u8; 4] = [24, 24, 28, 255];
+
+/// Screen edges covered by notches, rounded corners or system bars (CSS px)
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SafeAreaInsets {
+    /// Top inset
+    pub top: f32,
+    /// Right inset
+    pub right: f32,
+    /// Bottom inset
+    pub bottom: f32,
+    /// Left inset
+    pub left: f32,
+}
+
+impl SafeAreaInsets {
+    /// No insets
+    pub const NONE: Self = Self {
+        top: 0.0,
+        right: 0.0,
+        bottom: 0.0,
+        left: 0.0,
+    };
+}
+
+/// A screen to test on: CSS size, pixel density and input
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DeviceDescriptor {
+    /// Name shown in reports
+    pub name: &'static str,
+    /// Canvas width in CSS pixels
+    pub width: u32,
+    /// Canvas height in CSS pixels
+    pub height: u32,
+    /// Device pixels per CSS pixel
+    pub device_pixel_ratio: f32,
+    /// A touch screen is the main input
+    pub touch: bool,
+    /// Edges the UI must keep clear of
+    pub safe_area_insets: SafeAreaInsets,
+}
+
+impl DeviceDescriptor {
+    /// iPhone SE in landscape (smallest supported phone)
+    pub const IPHONE_SE: Self = Self::new("iPhone SE", 667, 375, 2.0, true);
+
+    /// iPad in landscape (4:3)
+    pub const IPAD: Self = Self::new("iPad", 1024, 768, 2.0, true);
+
+    /// 1080p desktop monitor (16:9)
+    pub const DESKTOP_1080P: Self = Self::new("1080p", 1920, 1080, 1.0, false);
+
+    /// Ultrawide monitor (21:9)
+    pub const ULTRAWIDE: Self = Self::new("21:9", 2560, 1080, 1.0, false);
+
+    /// Super ultrawide monitor (32:9)
+    pub const SUPER_ULTRAWIDE: Self = Self::new("32:9", 3840, 1080, 1.0, false);
+
+    /// The preset matrix, smallest screen first
+    pub const PRESETS: [Self; 5] = [
+        Self::IPHONE_SE,
+        Self::IPAD,
+        Self::DESKTOP_1080P,
+        Self::ULTRAWIDE,
+        Self::SUPER_ULTRAWIDE,
+    ];
+
+    /// Creates a device without safe-area insets
+    #[must_use]
+    pub const fn new(
+        name: &'static str,
+        width: u32,
+        height: u32,
+        device_pixel_ratio: f32,
+        touch: bool,
+    ) -> Self {
+        Self {
+            name,
+            width,
+            height,
+            device_pixel_ratio,
+            touch,
+            safe_area_insets: SafeAreaInsets::NONE,
+        }
+    }
+
+    /// Sets the safe-area insets (e.g. for a notch)
+    #[must_use]
+    pub const fn with_safe_area_insets(mut self, insets: SafeAreaInsets) -> Self {
+        self.safe_area_insets = insets;
+        self
+    }
+
+    /// Platform configuration for this device
+    #[must_use]
+    pub fn web_config(&self) -> WebConfig {
+        WebConfig {
+            width: self.width,
+            height: self.height,
+            pointer: PointerCapabilities {
+                pointer_lock: !self.touch,
+                touch: self.touch,
+            },
+            ..WebConfig::default()
+        }
+    }
+
+    /// Viewport (with the 16:9 gameplay safe area) for this device
+    #[must_use]
+    pub fn viewport(&self) -> Viewport {
+        Viewport::new(self.width, self.height)
+    }
+
+    /// Region the UI must stay inside: the canvas minus the insets
+    #[must_use]
+    pub fn safe_area(&self) -> Rect {
+        let insets = self.safe_area_insets;
+        Rect::new(
+            insets.left,
+            insets.top,
+            self.width as f32 - insets.left - insets.right,
+            self.height as f32 - insets.top - insets.bottom,
+        )
+    }
+}
+
+/// A labelled UI control on screen
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UiRegion {
+    /// What the control is (usually its label)
+    pub label: &'static str,
+    /// Screen rectangle in CSS pixels
+    pub rect: Rect,
+}
+
+/// A broken layout invariant
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LayoutViolation {
+    /// A control reaches outside the device's safe area
+    OutsideSafeArea {
+        /// Device name
+        device: &'static str,
+        /// Control label
+        control: &'static str,
+    },
+    /// Two controls cover each other
+    Overlap {
+        /// Device name
+        device: &'static str,
+        /// First control label
+        first: &'static str,
+        /// Second control label
+        second: &'static str,
+    },
+}
+
+/// Checks `regions` against the layout invariants for `device`
+#[must_use]
+pub fn check_layout(device: &DeviceDescriptor, regions: &[UiRegion]) -> Vec<LayoutViolation> {
+    let safe = device.safe_area();
+    let mut violations = Vec::new();
+    for region in regions {
+        let rect = region.rect;
+        let inside = rect.x >= safe.x
+            && rect.y >= safe.y
+            && rect.x + rect.width <= safe.x + safe.width
+            && rect.y + rect.height <= safe.y + safe.height;
+        if !inside {
+            violations.push(LayoutViolation::OutsideSafeArea {
+                device: device.name,
+                control: region.label,
+            });
+        }
+    }
+    for (i, first) in regions.iter().enumerate() {
+        for second in &regions[i + 1..] {
+            if first.rect.overlaps(&second.rect) {
+                violations.push(LayoutViolation::Overlap {
+                    device: device.name,
+                    first: first.label,
+                    second: second.label,
+                });
+            }
+        }
+    }
+    violations
+}
+
+/// One device's result
+#[derive(Debug, Clone)]
+pub struct DeviceSnapshot {
+    /// The device tested
+    pub device: DeviceDescriptor,
+    /// Last frame, [`SNAPSHOT_WIDTH`] pixels wide
+    pub image: Image,
+    /// HUD controls after the test
+    pub regions: Vec<UiRegion>,
+    /// Broken invariants (empty if the layout holds)
+    pub violations: Vec<LayoutViolation>,
+}
+
+/// Results for every device in a matrix run
+#[derive(Debug, Clone)]
+pub struct MatrixReport {
+    /// One snapshot per device, in matrix order
+    pub snapshots: Vec<DeviceSnapshot>,
+}
+
+impl MatrixReport {
+    /// Returns true if every device kept the layout invariants
+    #[must_use]
+    pub fn passed(&self) -> bool {
+        self.snapshots.iter().all(|s| s.violations.is_empty())
+    }
+
+    /// All violations across devices
+    pub fn violations(&self) -> impl Iterator<Item = &LayoutViolation> {
+        self.snapshots.iter().flat_map(|s| s.violations.iter())
+    }
+
+    /// Snapshots tiled [`GRID_COLUMNS`] to a row, each row as tall as its
+    /// tallest snapshot
+    #[must_use]
+    pub fn grid(&self) -> Image {
+        let rows: Vec<&[DeviceSnapshot]> = self.snapshots.chunks(GRID_COLUMNS).collect();
+        let row_heights: Vec<u32> = rows
+            .iter()
+            .map(|row| row.iter().map(|s| s.image.height()).max().unwrap_or(0))
+            .collect();
+        let columns = self.snapshots.len().min(GRID_COLUMNS);
+        let width = (columns as u32) * (SNAPSHOT_WIDTH + GRID_GAP) + GRID_GAP;
+        let height = row_heights.iter().map(|h| h + GRID_GAP).sum::<u32>() + GRID_GAP;
+
+        let stride = width as usize * 4;
+        let mut pixels = GRID_BACKGROUND.repeat(width as usize * height as usize);
+        let mut top = GRID_GAP;
+        for (row, row_height) in rows.iter().zip(&row_heights) {
+            let mut left = GRID_GAP;
+            for snapshot in *row {
+                let tile = &snapshot.image;
+                let tile_stride = tile.width() as usize * 4;
+                for (y, line) in tile.pixels().chunks_exact(tile_stride).enumerate() {
+                    let start = (top as usize + y) * stride + left as usize * 4;
+                    pixels[start..start + tile_stride].copy_from_slice(line);
+                }
+                left += SNAPSHOT_WIDTH + GRID_GAP;
+            }
+            top += row_height + GRID_GAP;
+        }
+        Image::from_rgba(width, height, pixels).unwrap_or_else(|| Image::new(width, height))
+    }
+}
+
+/// Runs one test across a set of devices
+#[derive(Debug, Clone)]
+pub struct DeviceMatrix {
+    devices: Vec<DeviceDescriptor>,
+}
+
+impl Default for DeviceMatrix {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DeviceMatrix {
+    /// Matrix of [`DeviceDescriptor::PRESETS`]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_devices(DeviceDescriptor::PRESETS.to_vec())
+    }
+
+    /// Matrix of custom devices
+    #[must_use]
+    pub fn with_devices(devices: Vec<DeviceDescriptor>) -> Self {
+        Self { devices }
+    }
+
+    /// Devices in the matrix
+    #[must_use]
+    pub fn devices(&self) -> &[DeviceDescriptor] {
+        &self.devices
+    }
+
+    /// Runs `test` on a fresh platform per device
+    ///
+    /// The test drives the platform (usually by calling
+    /// [`WebPlatform::frame`]); afterwards the last frame is snapshotted and
+    /// the HUD checked with [`check_layout`].
+    pub fn run<F>(&self, mut test: F) -> MatrixReport
+    where
+        F: FnMut(&mut WebPlatform),
+    {
+        let snapshots = self
+            .devices
+            .iter()
+            .map(|device| {
+                let mut platform = WebPlatform::new_for_test(device.web_config());
+                test(&mut platform);
+                let regions = platform.pong().hud_regions();
+                DeviceSnapshot {
+                    device: *device,
+                    image: snapshot(device, &platform.last_frame().commands),
+                    violations: check_layout(device, &regions),
+                    regions,
+                }
+            })
+            .collect();
+        MatrixReport { snapshots }
+    }
+}
+
+/// Rasterizes a frame at [`SNAPSHOT_WIDTH`], keeping the device's aspect ratio
+fn snapshot(device: &DeviceDescriptor, commands: &[Canvas2DCommand]) -> Image {
+    let scene = Vec2::new(device.width as f32, device.height as f32);
+    let height = ((SNAPSHOT_WIDTH as f32 * scene.y / scene.x).round() as u32).max(1);
+    let commands: Vec<RenderCommand> = commands.iter().filter_map(preview_command).collect();
+    rasterize(&commands, scene, SNAPSHOT_WIDTH, height)
+}
+
+/// Shape of a Canvas2D command for the snapshot
+///
+/// Sprites, images, clips and transforms are left out; snapshots are for
+/// spotting layout problems, not pixel comparison.
+fn preview_command(command: &Canvas2DCommand) -> Option<RenderCommand> {
+    let command = match command {
+        Canvas2DCommand::Clear { color } => RenderCommand::Clear {
+            color: (*color).into(),
+        },
+        Canvas2DCommand::FillRect {
+            x,
+            y,
+            width,
+            height,
+            color,
+        } => RenderCommand::DrawRect {
+            rect: Rect::new(*x, *y, *width, *height),
+            color: (*color).into(),
+        },
+        Canvas2DCommand::StrokeRect {
+            x,
+            y,
+            width,
+            height,
+            color,
+            line_width,
+        } => RenderCommand::DrawPolyline {
+            points: vec![
+                Vec2::new(*x, *y),
+                Vec2::new(x + width, *y),
+                Vec2::new(x + width, y + height),
+                Vec2::new(*x, y + height),
+            ],
+            width: *line_width,
+            color: (*color).into(),
+            closed: true,
+        },
+        Canvas2DCommand::FillCircle {
+            x,
+            y,
+            radius,
+            color,
+        } => RenderCommand::DrawCircle {
+            center: Vec2::new(*x, *y),
+            radius: *radius,
+            color: (*color).into(),
+            outline: None,
+        },
+        Canvas2DCommand::StrokeCircle {
+            x,
+            y,
+            radius,
+            color,
+            line_width,
+        } => RenderCommand::DrawCircle {
+            center: Vec2::new(*x, *y),
+            radius: *radius,
+            color: (*color).into(),
+            outline: Some(*line_width),
+        },
+        Canvas2DCommand::Line {
+            x1,
+            y1,
+            x2,
+            y2,
+            color,
+            line_width,
+        } => RenderCommand::DrawLine {
+            start: Vec2::new(*x1, *y1),
+            end: Vec2::new(*x2, *y2),
+            width: *line_width,
+            color: (*color).into(),
+        },
+        Canvas2DCommand::FillPolygon { points, color } => RenderCommand::DrawPolygon {
+            points: points.iter().map(|&[x, y]| Vec2::new(x, y)).collect(),
+            color: (*color).into(),
+            outline: None,
+        },
+        Canvas2DCommand::StrokePolyline {
+            points,
+            color,
+            line_width,
+            closed,
+        } => RenderCommand::DrawPolyline {
+            points: points.iter().map(|&[x, y]| Vec2::new(x, y)).collect(),
+            width: *line_width,
+            color: (*color).into(),
+            closed: *closed,
+        },
+        Canvas2DCommand::FillText {
+            text,
+            x,
+            y,
+            font,
+            color,
+            align,
+            baseline,
+        } => {
+            // "14px monospace" -> 14
+            let size = font
+                .split("px")
+                .next()
+                .and_then(|px| px.trim().parse::<f32>().ok())
+                .unwrap_or(16.0);
+            let width = BitmapFont::monospace(size).line_width(text);
+            let left = match align {
+                TextAlign::Left => *x,
+                TextAlign::Center => x - width / 2.0,
+                TextAlign::Right => x - width,
+            };
+            let top = match baseline {
+                TextBaseline::Top => *y,
+                TextBaseline::Middle => y - size / 2.0,
+                TextBaseline::Bottom | TextBaseline::Alphabetic => y - size,
+            };
+            RenderCommand::DrawText {
+                text: text.clone(),
+                position: Vec2::new(left, top),
+                size,
+                color: (*color).into(),
+            }
+        }
+        _ => return None,
+    };
+    Some(command)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_presets_cover_aspect_ratios() {
+        let ratios: Vec<f64> = DeviceDescriptor::PRESETS
+            .iter()
+            .map(|d| f64::from(d.width) / f64::from(d.height))
+            .collect();
+        assert!((ratios[1] - 4.0 / 3.0).abs() < 0.01);
+        assert!((ratios[2] - 16.0 / 9.0).abs() < 0.01);
+        assert!((ratios[4] - 32.0 / 9.0).abs() < 0.01);
+        assert!(DeviceDescriptor::IPHONE_SE.web_config().pointer.touch);
+        assert!(
+            DeviceDescriptor::DESKTOP_1080P
+                .web_config()
+                .pointer
+                .pointer_lock
+        );
+
+        // The 16:9 gameplay area is pillarboxed on ultrawides
+        let viewport = DeviceDescriptor::SUPER_ULTRAWIDE.viewport();
+        assert!((viewport.safe_area.x - 960.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_check_layout_reports_violations() {
+        let notched = DeviceDescriptor::IPHONE_SE.with_safe_area_insets(SafeAreaInsets {
+            left: 44.0,
+            ..SafeAreaInsets::NONE
+        });
+        let regions = [
+            UiRegion {
+                label: "Pause",
+                rect: Rect::new(10.0, 10.0, 40.0, 40.0),
+            },
+            UiRegion {
+                label: "Score",
+                rect: Rect::new(30.0, 20.0, 60.0, 20.0),
+            },
+            UiRegion {
+                label: "Menu",
+                rect: Rect::new(90.0, 20.0, 40.0, 20.0),
+            },
+        ];
+        let violations = check_layout(&notched, &regions);
+        assert_eq!(
+            violations,
+            [
+                LayoutViolation::OutsideSafeArea {
+                    device: "iPhone SE",
+                    control: "Pause",
+                },
+                LayoutViolation::OutsideSafeArea {
+                    device: "iPhone SE",
+                    control: "Score",
+                },
+                LayoutViolation::Overlap {
+                    device: "iPhone SE",
+                    first: "Pause",
+                    second: "Score",
+                },
+            ]
+        );
+        // Touching edges is not an overlap
+        assert!(check_layout(&DeviceDescriptor::IPHONE_SE, &regions[1..]).is_empty());
+    }
+
+    #[test]
+    fn test_pong_hud_holds_across_matrix() {
+        let matrix = DeviceMatrix::new();
+        let report = matrix.run(|platform| {
+            for i in 0..3 {
+                let _ = platform.frame(f64::from(i) * 16.0, "[]");
+            }
+        });
+        assert_eq!(report.snapshots.len(), 5);
+        assert!(
+            report.passed(),
+            "{:?}",
+            report.violations().collect::<Vec<_>>()
+        );
+
+        let se = &report.snapshots[0];
+        assert_eq!(se.device.name, "iPhone SE");
+        assert_eq!(se.image.width(), SNAPSHOT_WIDTH);
+        assert_eq!(se.image.height(), 180);
+        assert!(!se.regions.is_empty());
+        // The cleared background is drawn
+        assert_ne!(se.image.pixel(0, 0), Some([0, 0, 0, 0]));
+
+        let grid = report.grid();
+        assert_eq!(grid.width(), 3 * (SNAPSHOT_WIDTH + GRID_GAP) + GRID_GAP);
+        assert_eq!(grid.pixel(0, 0), Some(GRID_BACKGROUND));
+    }
+}
@@ -0,0 +1,435 @@
+//! Clipboard and file bridge for the browser editor.
+//!
+//! Copying a share link, pasting YAML and opening or saving files all need
+//! browser APIs that only JavaScript can call, and most of them answer
+//! asynchronously. [`EditorBridge`] queues a [`JsAction`] for each request
+//! and turns JavaScript's [`BridgeResult`] answers into [`EditorEvent`]s.
+//! Everything that arrives from outside passes the [`ContentSandbox`] before
+//! the editor sees it, whether it was pasted as YAML, a share link or a
+//! `.jugar` bundle.
+//!
+//! ```javascript
+//! if (action.type === 'ReadClipboard') navigator.clipboard.readText()
+//!     .then((text) => results.push({ type: 'ClipboardText', request: action.request, text }))
+//!     .catch(() => results.push({ type: 'ClipboardText', request: action.request, text: null }));
+//! ```
+
+use std::path::Path;
+
+use jugar_yaml::sharing::MAX_BUNDLE_SIZE;
+use jugar_yaml::{BundleError, ContentSandbox, GameBundle, ShareLinkGenerator};
+use serde::{Deserialize, Serialize};
+
+use crate::platform::JsAction;
+
+/// Largest pasted or opened text accepted before parsing (a bundle in
+/// base64 is about a third larger than its JSON)
+pub const MAX_IMPORT_SIZE: usize = 2 * MAX_BUNDLE_SIZE;
+
+/// File types offered by the open dialog.
+pub const OPEN_FILE_ACCEPT: &str = ".yaml,.yml,.jugar,.jgb";
+
+/// An answer from JavaScript to a queued [`JsAction`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum BridgeResult {
+    /// `CopyToClipboard` finished
+    ClipboardWritten {
+        /// False if the browser refused
+        ok: bool,
+    },
+    /// `ReadClipboard` finished
+    ClipboardText {
+        /// Request id from the action
+        request: u32,
+        /// Clipboard text, or `None` if the player refused access
+        text: Option<String>,
+    },
+    /// `OpenFile` finished
+    FileOpened {
+        /// Request id from the action
+        request: u32,
+        /// File name chosen by the player, or `None` if they cancelled
+        name: Option<String>,
+        /// File contents as text
+        #[serde(default)]
+        contents: String,
+    },
+    /// `SaveFile` finished
+    FileSaved {
+        /// False if the download was blocked
+        ok: bool,
+    },
+}
+
+/// Where imported YAML came from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ImportSource {
+    /// Pasted YAML text
+    Clipboard,
+    /// Pasted share link
+    ShareLink,
+    /// Opened file (name as chosen by the player)
+    File(String),
+}
+
+/// What the editor should react to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum EditorEvent {
+    /// Game YAML that passed the sandbox
+    Imported {
+        /// Where it came from
+        source: ImportSource,
+        /// The game YAML
+        yaml: String,
+    },
+    /// Imported content was refused
+    Rejected {
+        /// Short headline for the kid
+        headline: String,
+        /// Friendly explanation
+        explanation: String,
+    },
+    /// The share link is on the clipboard
+    LinkCopied,
+    /// The file was handed to the browser's download
+    Saved,
+    /// The browser refused or the player cancelled
+    Cancelled,
+}
+
+/// Clipboard and file requests in flight, and their translation.
+#[derive(Debug, Clone, Default)]
+pub struct EditorBridge {
+    sandbox: ContentSandbox,
+    links: ShareLinkGenerator,
+    actions: Vec<JsAction>,
+    pending: Vec<u32>,
+    next_request: u32,
+}
+
+impl EditorBridge {
+    /// Creates a bridge checking imports with the default sandbox.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a bridge checking imports with `sandbox` (e.g. one for the
+    /// kid's schema level).
+    #[must_use]
+    pub fn with_sandbox(sandbox: ContentSandbox) -> Self {
+        Self {
+            sandbox,
+            ..Self::default()
+        }
+    }
+
+    /// Queues copying a share link for `bundle`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bundle can't be encoded.
+    pub fn copy_share_link(&mut self, bundle: &GameBundle) -> Result<(), BundleError> {
+        let text = self.links.create_link(bundle)?;
+        self.actions.push(JsAction::CopyToClipboard { text });
+        Ok(())
+    }
+
+    /// Queues reading the clipboard; the answer becomes an import.
+    pub fn paste(&mut self) {
+        let request = self.next_request();
+        self.actions.push(JsAction::ReadClipboard { request });
+    }
+
+    /// Queues the open file dialog; the chosen file becomes an import.
+    pub fn open_file(&mut self) {
+        let request = self.next_request();
+        self.actions.push(JsAction::OpenFile {
+            request,
+            accept: OPEN_FILE_ACCEPT.to_string(),
+            max_bytes: MAX_IMPORT_SIZE,
+        });
+    }
+
+    /// Queues downloading `bundle` as `<name>.jugar`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bundle can't be serialized or is larger than
+    /// a shareable bundle may be.
+    pub fn save_bundle(&mut self, bundle: &GameBundle, name: &str) -> Result<(), BundleError> {
+        let contents = bundle.to_json()?;
+        if contents.len() > MAX_BUNDLE_SIZE {
+            return Err(BundleError::BundleTooLarge {
+                size: contents.len(),
+                max: MAX_BUNDLE_SIZE,
+            });
+        }
+        self.actions.push(JsAction::SaveFile {
+            file_name: file_name(name, "jugar"),
+            mime_type: "application/json".to_string(),
+            contents,
+        });
+        Ok(())
+    }
+
+    /// Takes the actions queued since the last call, for the frame output.
+    pub fn take_actions(&mut self) -> Vec<JsAction> {
+        core::mem::take(&mut self.actions)
+    }
+
+    /// Translates an answer from JavaScript.
+    ///
+    /// Answers to requests that aren't pending (stale or forged) are ignored.
+    pub fn handle(&mut self, result: BridgeResult) -> Option<EditorEvent> {
+        match result {
+            BridgeResult::ClipboardWritten { ok } => Some(if ok {
+                EditorEvent::LinkCopied
+            } else {
+                EditorEvent::Cancelled
+            }),
+            BridgeResult::FileSaved { ok } => Some(if ok {
+                EditorEvent::Saved
+            } else {
+                EditorEvent::Cancelled
+            }),
+            BridgeResult::ClipboardText { request, text } => {
+                self.settle(request)?;
+                Some(text.map_or(EditorEvent::Cancelled, |text| self.import_text(&text)))
+            }
+            BridgeResult::FileOpened {
+                request,
+                name,
+                contents,
+            } => {
+                self.settle(request)?;
+                Some(name.map_or(EditorEvent::Cancelled, |name| {
+                    self.import_file(name, &contents)
+                }))
+            }
+        }
+    }
+
+    /// Translates an answer serialized as JSON.
+    ///
+    /// Malformed JSON is ignored like a stale answer.
+    pub fn handle_json(&mut self, json: &str) -> Option<EditorEvent> {
+        serde_json::from_str(json)
+            .ok()
+            .and_then(|result| self.handle(result))
+    }
+
+    fn next_request(&mut self) -> u32 {
+        self.next_request = self.next_request.wrapping_add(1);
+        self.pending.push(self.next_request);
+        self.next_request
+    }
+
+    /// Removes a pending request, returning `None` if it wasn't pending
+    fn settle(&mut self, request: u32) -> Option<()> {
+        let index = self.pending.iter().position(|&id| id == request)?;
+        let _ = self.pending.remove(index);
+        Some(())
+    }
+
+    /// Pasted text may be a share link or plain YAML
+    fn import_text(&self, text: &str) -> EditorEvent {
+        let text = text.trim();
+        if text.len() > MAX_IMPORT_SIZE {
+            return too_large(text.len());
+        }
+        if text.starts_with(&self.links.base_url) {
+            return match self.links.extract_bundle(text) {
+                Ok(bundle) => self.import_yaml(ImportSource::ShareLink, bundle.yaml()),
+                Err(err) => bundle_rejected(&err),
+            };
+        }
+        self.import_yaml(ImportSource::Clipboard, text)
+    }
+
+    /// Files ending in `.jugar` or `.jgb` are bundles; anything else is YAML
+    fn import_file(&self, name: String, contents: &str) -> EditorEvent {
+        if contents.len() > MAX_IMPORT_SIZE {
+            return too_large(contents.len());
+        }
+        let is_bundle = Path::new(&name).extension().is_some_and(|ext| {
+            ext.eq_ignore_ascii_case("jugar") || ext.eq_ignore_ascii_case("jgb")
+        });
+        if is_bundle {
+            return match GameBundle::from_json(contents) {
+                Ok(bundle) => self.import_yaml(ImportSource::File(name), bundle.yaml()),
+                Err(err) => bundle_rejected(&err),
+            };
+        }
+        self.import_yaml(ImportSource::File(name), contents)
+    }
+
+    fn import_yaml(&self, source: ImportSource, yaml: &str) -> EditorEvent {
+        match self.sandbox.validate(yaml) {
+            Ok(()) => EditorEvent::Imported {
+                source,
+                yaml: yaml.to_string(),
+            },
+            Err(err) => {
+                let friendly = err.into_yaml_error().to_kid_friendly();
+                EditorEvent::Rejected {
+                    headline: friendly.headline,
+                    explanation: friendly.explanation,
+                }
+            }
+        }
+    }
+}
+
+/// Keeps a download name to letters, digits, `-` and `_`, plus `extension`
+fn file_name(name: &str, extension: &str) -> String {
+    let stem: String = name
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    let stem = stem.trim_matches('-');
+    let stem = if stem.is_empty() { "my-game" } else { stem };
+    format!("{stem}.{extension}")
+}
+
+fn too_large(size: usize) -> EditorEvent {
+    EditorEvent::Rejected {
+        headline: "That's too big to open!".to_string(),
+        explanation: format!(
+            "It's {} KB, but games can be at most {} KB.",
+            size / 1024,
+            MAX_IMPORT_SIZE / 1024
+        ),
+    }
+}
+
+fn bundle_rejected(err: &BundleError) -> EditorEvent {
+    EditorEvent::Rejected {
+        headline: "I couldn't open that game.".to_string(),
+        explanation: err.to_string(),
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+mod tests {
+    use super::*;
+    use jugar_yaml::BundleMetadata;
+
+    const GAME: &str = "character: bunny\nbackground: sky\n";
+
+    fn bundle() -> GameBundle {
+        GameBundle::from_yaml(GAME, BundleMetadata::new("Bunny Hop")).unwrap()
+    }
+
+    fn request_of(action: &JsAction) -> u32 {
+        match action {
+            JsAction::ReadClipboard { request } | JsAction::OpenFile { request, .. } => *request,
+            other => panic!("unexpected action {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_share_link_round_trip_through_clipboard() {
+        let mut bridge = EditorBridge::new();
+        bridge.copy_share_link(&bundle()).unwrap();
+        let actions = bridge.take_actions();
+        let JsAction::CopyToClipboard { text } = &actions[0] else {
+            panic!("expected CopyToClipboard");
+        };
+        assert_eq!(
+            bridge.handle(BridgeResult::ClipboardWritten { ok: true }),
+            Some(EditorEvent::LinkCopied)
+        );
+
+        bridge.paste();
+        let request = request_of(&bridge.take_actions()[0]);
+        let event = bridge.handle(BridgeResult::ClipboardText {
+            request,
+            text: Some(text.clone()),
+        });
+        assert_eq!(
+            event,
+            Some(EditorEvent::Imported {
+                source: ImportSource::ShareLink,
+                yaml: GAME.to_string(),
+            })
+        );
+        // Answered requests are no longer pending
+        assert_eq!(
+            bridge.handle(BridgeResult::ClipboardText {
+                request,
+                text: Some(GAME.to_string()),
+            }),
+            None
+        );
+    }
+
+    #[test]
+    fn test_open_file_checks_sandbox() {
+        let mut bridge = EditorBridge::new();
+        bridge.open_file();
+        let request = request_of(&bridge.take_actions()[0]);
+        let huge = format!("title: \"{}\"", "a".repeat(70 * 1024));
+        let json = serde_json::json!({
+            "type": "FileOpened", "request": request, "name": "big.yaml", "contents": huge,
+        });
+        assert!(matches!(
+            bridge.handle_json(&json.to_string()),
+            Some(EditorEvent::Rejected { .. })
+        ));
+
+        bridge.open_file();
+        let request = request_of(&bridge.take_actions()[0]);
+        let contents = bundle().to_json().unwrap();
+        assert_eq!(
+            bridge.handle(BridgeResult::FileOpened {
+                request,
+                name: Some("Bunny.jugar".to_string()),
+                contents,
+            }),
+            Some(EditorEvent::Imported {
+                source: ImportSource::File("Bunny.jugar".to_string()),
+                yaml: GAME.to_string(),
+            })
+        );
+
+        bridge.open_file();
+        let request = request_of(&bridge.take_actions()[0]);
+        assert_eq!(
+            bridge.handle(BridgeResult::FileOpened {
+                request,
+                name: None,
+                contents: String::new(),
+            }),
+            Some(EditorEvent::Cancelled)
+        );
+    }
+
+    #[test]
+    fn test_save_bundle_names_file() {
+        let mut bridge = EditorBridge::new();
+        bridge.save_bundle(&bundle(), " Bunny Hop! ").unwrap();
+        let actions = bridge.take_actions();
+        let JsAction::SaveFile {
+            file_name,
+            contents,
+            ..
+        } = &actions[0]
+        else {
+            panic!("expected SaveFile");
+        };
+        assert_eq!(file_name, "Bunny-Hop.jugar");
+        assert!(GameBundle::from_json(contents).is_ok());
+        assert_eq!(super::file_name("../", "yaml"), "my-game.yaml");
+    }
+}
@@ -0,0 +1,343 @@
+//! Flaky test detection and quarantine.
+//!
+//! Timing-dependent game tests (AI reactions, physics at odd frame rates)
+//! sometimes fail for reasons unrelated to the change under test. A red
+//! build for those stops kids and teachers from shipping, while silently
+//! ignoring them hides real bugs. [`FlakeDetector`] reruns a failing test
+//! with varied seeds and scores it over the runs:
+//!
+//! - every run passes: **stable**
+//! - some runs fail: **flaky**, quarantined so it doesn't block CI
+//! - every run fails: **broken**, blocks CI like any failure
+//!
+//! [`QuarantineReport::render`] puts quarantined tests in their own Andon
+//! section so they are still seen on every build.
+//!
+//! ```
+//! use jugar_web::flaky::{FlakeDetector, FlakeVerdict};
+//! use jugar_web::simulation::{GameStateSnapshot, TestResult};
+//!
+//! let mut detector = FlakeDetector::new();
+//! let record = detector.check("ball_bounces", 42, |seed| {
+//!     if seed % 3 == 0 {
+//!         TestResult::fail("bounce", "true", false, GameStateSnapshot::default())
+//!     } else {
+//!         TestResult::Pass
+//!     }
+//! });
+//! assert_eq!(record.verdict, FlakeVerdict::Flaky);
+//! assert!(!detector.report().is_blocking());
+//! ```
+
+use crate::simulation::TestResult;
+
+/// Runs (including the first) before a failing test is scored
+pub const DEFAULT_FLAKE_RUNS: usize = 10;
+
+/// Seed added per retry so each rerun sees different randomness
+pub const DEFAULT_SEED_STRIDE: u64 = 7919;
+
+/// How a test behaved over its runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlakeVerdict {
+    /// Passed the first time
+    Stable,
+    /// Failed some runs but not all (quarantined)
+    Flaky,
+    /// Failed every run
+    Broken,
+}
+
+/// Result of checking one test
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlakeRecord {
+    /// Test name
+    pub name: String,
+    /// Number of runs
+    pub runs: usize,
+    /// Seeds of the failing runs, in run order
+    pub failed_seeds: Vec<u64>,
+    /// First failure's assertion, expected and actual values
+    pub first_failure: Option<String>,
+    /// Stable, flaky or broken
+    pub verdict: FlakeVerdict,
+}
+
+impl FlakeRecord {
+    /// Number of failing runs
+    #[must_use]
+    pub fn failures(&self) -> usize {
+        self.failed_seeds.len()
+    }
+
+    /// Share of runs that failed (0.0 to 1.0)
+    #[must_use]
+    pub fn flake_rate(&self) -> f64 {
+        if self.runs == 0 {
+            0.0
+        } else {
+            self.failures() as f64 / self.runs as f64
+        }
+    }
+
+    /// Wilson score interval for the failure rate at the given z-score
+    ///
+    /// With few runs the observed rate is a rough estimate; the interval
+    /// says how far off it could be (use 1.96 for 95% confidence).
+    #[must_use]
+    pub fn flake_interval(&self, z: f64) -> (f64, f64) {
+        if self.runs == 0 {
+            return (0.0, 1.0);
+        }
+        let n = self.runs as f64;
+        let p = self.flake_rate();
+        let z2 = z * z;
+        let center = (p + z2 / (2.0 * n)) / (1.0 + z2 / n);
+        let margin = z * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt() / (1.0 + z2 / n);
+        ((center - margin).max(0.0), (center + margin).min(1.0))
+    }
+}
+
+/// Reruns failing tests with varied seeds and collects the verdicts
+#[derive(Debug, Clone)]
+pub struct FlakeDetector {
+    runs: usize,
+    seed_stride: u64,
+    records: Vec<FlakeRecord>,
+}
+
+impl Default for FlakeDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FlakeDetector {
+    /// Detector with [`DEFAULT_FLAKE_RUNS`] and [`DEFAULT_SEED_STRIDE`]
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            runs: DEFAULT_FLAKE_RUNS,
+            seed_stride: DEFAULT_SEED_STRIDE,
+            records: Vec::new(),
+        }
+    }
+
+    /// Sets how many runs a failing test gets in total (at least 2)
+    #[must_use]
+    pub fn with_runs(mut self, runs: usize) -> Self {
+        self.runs = runs.max(2);
+        self
+    }
+
+    /// Sets the seed step between reruns
+    #[must_use]
+    pub fn with_seed_stride(mut self, stride: u64) -> Self {
+        self.seed_stride = stride;
+        self
+    }
+
+    /// Runs `test` at `seed`, rerunning with varied seeds if it fails
+    ///
+    /// A passing first run is recorded as stable without retries, so
+    /// healthy suites pay nothing extra.
+    pub fn check<F>(&mut self, name: &str, seed: u64, mut test: F) -> &FlakeRecord
+    where
+        F: FnMut(u64) -> TestResult,
+    {
+        let mut failed_seeds = Vec::new();
+        let mut first_failure = None;
+        let mut runs = 0;
+        for attempt in 0..self.runs {
+            let seed = seed.wrapping_add(self.seed_stride.wrapping_mul(attempt as u64));
+            runs += 1;
+            if let TestResult::Fail {
+                assertion,
+                expected,
+                actual,
+                ..
+            } = test(seed)
+            {
+                failed_seeds.push(seed);
+                if first_failure.is_none() {
+                    first_failure = Some(format!("{assertion}: expected {expected}, got {actual}"));
+                }
+            } else if attempt == 0 {
+                break;
+            }
+        }
+
+        let verdict = match failed_seeds.len() {
+            0 => FlakeVerdict::Stable,
+            n if n == runs => FlakeVerdict::Broken,
+            _ => FlakeVerdict::Flaky,
+        };
+        self.records.push(FlakeRecord {
+            name: name.to_string(),
+            runs,
+            failed_seeds,
+            first_failure,
+            verdict,
+        });
+        let last = self.records.len() - 1;
+        &self.records[last]
+    }
+
+    /// Report over every checked test
+    #[must_use]
+    pub fn report(&self) -> QuarantineReport {
+        QuarantineReport {
+            records: self.records.clone(),
+        }
+    }
+}
+
+/// Verdicts for a suite, with quarantined tests in their own section
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QuarantineReport {
+    /// Every checked test, in check order
+    pub records: Vec<FlakeRecord>,
+}
+
+impl QuarantineReport {
+    /// Tests with the given verdict
+    pub fn with_verdict(&self, verdict: FlakeVerdict) -> impl Iterator<Item = &FlakeRecord> {
+        self.records.iter().filter(move |r| r.verdict == verdict)
+    }
+
+    /// Flaky tests, worst first
+    #[must_use]
+    pub fn quarantined(&self) -> Vec<&FlakeRecord> {
+        let mut flaky: Vec<_> = self.with_verdict(FlakeVerdict::Flaky).collect();
+        flaky.sort_by(|a, b| b.flake_rate().total_cmp(&a.flake_rate()));
+        flaky
+    }
+
+    /// Returns true if a broken test should fail the build
+    ///
+    /// Flaky tests never block; they are reported instead.
+    #[must_use]
+    pub fn is_blocking(&self) -> bool {
+        self.with_verdict(FlakeVerdict::Broken).next().is_some()
+    }
+
+    /// Plain-text report with an Andon section for quarantined tests
+    #[must_use]
+    pub fn render(&self) -> String {
+        let stable = self.with_verdict(FlakeVerdict::Stable).count();
+        let quarantined = self.quarantined();
+        let broken: Vec<_> = self.with_verdict(FlakeVerdict::Broken).collect();
+
+        let mut lines = vec![format!(
+            "{} tests: {stable} stable, {} flaky, {} broken",
+            self.records.len(),
+            quarantined.len(),
+            broken.len()
+        )];
+        for record in &broken {
+            lines.push(format!(
+                "FAIL {} ({} of {} runs): {}",
+                record.name,
+                record.failures(),
+                record.runs,
+                record.first_failure.as_deref().unwrap_or("failed")
+            ));
+        }
+        if !quarantined.is_empty() {
+            lines.push(String::new());
+            lines.push(format!(
+                "=== ANDON: {} quarantined (not blocking, fix soon) ===",
+                quarantined.len()
+            ));
+            for record in quarantined {
+                let seeds: Vec<String> = record.failed_seeds.iter().map(u64::to_string).collect();
+                lines.push(format!(
+                    "FLAKY {} {:.0}% ({} of {} runs) seeds [{}]: {}",
+                    record.name,
+                    record.flake_rate() * 100.0,
+                    record.failures(),
+                    record.runs,
+                    seeds.join(", "),
+                    record.first_failure.as_deref().unwrap_or("failed")
+                ));
+            }
+        }
+        lines.join("\n") + "\n"
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::simulation::GameStateSnapshot;
+
+    fn fail() -> TestResult {
+        TestResult::fail("ball.y", "< 600", 612.5, GameStateSnapshot::default())
+    }
+
+    #[test]
+    fn test_passing_test_runs_once() {
+        let mut detector = FlakeDetector::new();
+        let mut runs = 0;
+        let record = detector.check("stable", 1, |_| {
+            runs += 1;
+            TestResult::Pass
+        });
+        assert_eq!(record.verdict, FlakeVerdict::Stable);
+        assert_eq!(record.runs, 1);
+        assert_eq!(runs, 1);
+    }
+
+    #[test]
+    fn test_retries_vary_seed_and_score_flakes() {
+        let mut detector = FlakeDetector::new().with_runs(4).with_seed_stride(10);
+        let mut seeds = Vec::new();
+        let record = detector.check("timing", 5, |seed| {
+            seeds.push(seed);
+            if seed == 5 || seed == 25 {
+                fail()
+            } else {
+                TestResult::Pass
+            }
+        });
+        assert_eq!(seeds, [5, 15, 25, 35]);
+        assert_eq!(record.verdict, FlakeVerdict::Flaky);
+        assert_eq!(record.failed_seeds, [5, 25]);
+        assert!((record.flake_rate() - 0.5).abs() < f64::EPSILON);
+        assert_eq!(
+            record.first_failure.as_deref(),
+            Some("ball.y: expected < 600, got 612.5")
+        );
+        let (low, high) = record.flake_interval(1.96);
+        assert!(low > 0.1 && low < 0.5);
+        assert!(high > 0.5 && high < 0.9);
+    }
+
+    #[test]
+    fn test_report_quarantines_flaky_and_blocks_on_broken() {
+        let mut detector = FlakeDetector::new().with_runs(3);
+        let _ = detector.check("stable", 0, |_| TestResult::Pass);
+        let mut calls = 0;
+        let _ = detector.check("flaky", 0, |_| {
+            calls += 1;
+            if calls == 1 {
+                fail()
+            } else {
+                TestResult::Pass
+            }
+        });
+        let report = detector.report();
+        assert!(!report.is_blocking());
+        assert_eq!(report.quarantined().len(), 1);
+        let text = report.render();
+        assert!(text.starts_with("2 tests: 1 stable, 1 flaky, 0 broken"));
+        assert!(text.contains("ANDON: 1 quarantined"));
+        assert!(text.contains("FLAKY flaky 33%"));
+
+        let _ = detector.check("broken", 0, |_| fail());
+        let report = detector.report();
+        assert!(report.is_blocking());
+        assert!(report.render().contains("FAIL broken (3 of 3 runs)"));
+    }
+}
@@ -1,12 +1,13 @@
 //! Browser input event translation
 //!
-//! Translates browser events (keyboard, mouse, touch) to Jugar's `InputState`.
+//! Translates browser events (keyboard, mouse, touch, device
+//! orientation) to Jugar's `InputState`.
 //! All computation happens in Rust - JavaScript only forwards raw events.
 
-use glam::Vec2;
+use glam::{Vec2, Vec3};
 use jugar_input::{
-    ButtonState, GamepadAxis, GamepadButton, InputState, KeyCode, MouseButton, TouchEvent,
-    TouchPhase,
+    ButtonState, GamepadAxis, GamepadButton, InputState, KeyCode, MotionPermission, MouseButton,
+    TouchEvent, TouchPhase,
 };
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
@@ -43,6 +44,21 @@ pub struct BrowserInputEvent {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum BrowserEventData {
+    /// Device motion event data (has 6 fields)
+    Motion {
+        /// Acceleration along X without gravity (m/s²)
+        ax: f32,
+        /// Acceleration along Y without gravity (m/s²)
+        ay: f32,
+        /// Acceleration along Z without gravity (m/s²)
+        az: f32,
+        /// Rotation rate around Z (`rotationRate.alpha`, degrees/s)
+        alpha: f32,
+        /// Rotation rate around X (`rotationRate.beta`, degrees/s)
+        beta: f32,
+        /// Rotation rate around Y (`rotationRate.gamma`, degrees/s)
+        gamma: f32,
+    },
     /// Keyboard event data
     Key {
         /// JavaScript key code (e.g., "KeyW", "Space", "ArrowUp")
@@ -75,6 +91,20 @@ pub enum BrowserEventData {
         /// Y position in pixels
         y: f32,
     },
+    /// Device orientation event data (has beta, gamma - 2 fields)
+    Orientation {
+        /// Front/back tilt in degrees
+        beta: f32,
+        /// Left/right tilt in degrees
+        gamma: f32,
+    },
+    /// Relative mouse movement under pointer lock (has dx, dy - 2 fields)
+    MouseMotion {
+        /// Horizontal movement in pixels (`movementX`)
+        dx: f32,
+        /// Vertical movement in pixels (`movementY`)
+        dy: f32,
+    },
     /// Gamepad button event data (has 2 fields)
     GamepadButton {
         /// Gamepad index
@@ -82,6 +112,16 @@ pub enum BrowserEventData {
         /// Button index
         button: u8,
     },
+    /// Motion sensor permission answer (has 1 field)
+    MotionPermission {
+        /// Whether the player allowed motion sensors
+        granted: bool,
+    },
+    /// Pointer lock change event data (has 1 field)
+    PointerLock {
+        /// Whether the pointer is now locked to the canvas
+        locked: bool,
+    },
     /// Mouse move event data (has x, y - 2 fields, least specific)
     MouseMove {
         /// X position in pixels
@@ -91,6 +131,45 @@ pub enum BrowserEventData {
     },
 }
 
+/// Pointer hardware reported by the browser at startup
+///
+/// Lets aiming and drawing games choose between pointer lock on desktop and
+/// touch-drag on tablets and phones.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PointerCapabilities {
+    /// `requestPointerLock` is available with a fine pointer (mouse or trackpad)
+    #[serde(default)]
+    pub pointer_lock: bool,
+    /// A touch screen is present
+    #[serde(default)]
+    pub touch: bool,
+}
+
+/// How a game should read relative aiming movement
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RelativeAim {
+    /// Lock the pointer and read `mouse_delta`
+    PointerLock,
+    /// Read touch deltas while a finger drags
+    TouchDrag,
+    /// Neither is available; derive movement from the cursor position
+    Cursor,
+}
+
+impl PointerCapabilities {
+    /// Best relative aiming scheme for this device
+    #[must_use]
+    pub const fn relative_aim(&self) -> RelativeAim {
+        if self.pointer_lock {
+            RelativeAim::PointerLock
+        } else if self.touch {
+            RelativeAim::TouchDrag
+        } else {
+            RelativeAim::Cursor
+        }
+    }
+}
+
 /// Translates a JavaScript key code to Jugar KeyCode
 #[must_use]
 pub fn translate_key(js_key: &str) -> Option<KeyCode> {
@@ -244,6 +323,10 @@ fn process_single_event(
             }
         }
         "MouseMove" => {
+            // Locked pointers report through MouseMoveRelative instead
+            if state.is_pointer_locked() {
+                return Ok(());
+            }
             if let BrowserEventData::MouseMove { x, y } = &event.data {
                 let old_pos = state.mouse_position;
                 // Apply offset to convert viewport coords to canvas coords
@@ -251,6 +334,16 @@ fn process_single_event(
                 state.mouse_delta = state.mouse_position - old_pos;
             }
         }
+        "MouseMoveRelative" => {
+            if let BrowserEventData::MouseMotion { dx, dy } = &event.data {
+                state.add_mouse_motion(Vec2::new(*dx, *dy));
+            }
+        }
+        "PointerLockChange" => {
+            if let BrowserEventData::PointerLock { locked } = &event.data {
+                state.set_pointer_locked(*locked);
+            }
+        }
         "MouseDown" => {
             if let BrowserEventData::MouseButton { button, x, y } = &event.data {
                 // Apply offset to convert viewport coords to canvas coords
@@ -308,6 +401,37 @@ fn process_single_event(
                 }
             }
         }
+        "DeviceOrientation" => {
+            if let BrowserEventData::Orientation { beta, gamma } = &event.data {
+                state.set_orientation(*beta, *gamma);
+            }
+        }
+        "DeviceMotion" => {
+            if let BrowserEventData::Motion {
+                ax,
+                ay,
+                az,
+                alpha,
+                beta,
+                gamma,
+            } = &event.data
+            {
+                state.motion_mut().record_motion(
+                    Vec3::new(*ax, *ay, *az),
+                    Vec3::new(*alpha, *beta, *gamma),
+                    event.timestamp / 1000.0,
+                );
+            }
+        }
+        "MotionPermission" => {
+            if let BrowserEventData::MotionPermission { granted } = &event.data {
+                state.motion_mut().set_permission(if *granted {
+                    MotionPermission::Granted
+                } else {
+                    MotionPermission::Denied
+                });
+            }
+        }
         "GamepadButtonDown" => {
             if let BrowserEventData::GamepadButton { gamepad, button } = &event.data {
                 let gp_idx = *gamepad as usize;
@@ -443,6 +567,47 @@ mod tests {
         assert_eq!(translate_key("F0"), None); // Out of range
     }
 
+    // ==================== POINTER LOCK TESTS ====================
+
+    #[test]
+    fn test_pointer_lock_reports_deltas_only() {
+        let mut state = InputState::new();
+        let json = r#"[
+            {"event_type": "MouseMove", "timestamp": 0.0, "data": {"x": 100.0, "y": 80.0}},
+            {"event_type": "PointerLockChange", "timestamp": 1.0, "data": {"locked": true}},
+            {"event_type": "MouseMoveRelative", "timestamp": 2.0, "data": {"dx": 4.0, "dy": -2.0}},
+            {"event_type": "MouseMoveRelative", "timestamp": 3.0, "data": {"dx": 1.0, "dy": 1.0}},
+            {"event_type": "MouseMove", "timestamp": 4.0, "data": {"x": 400.0, "y": 300.0}}
+        ]"#;
+        process_input_events(json, &mut state, Vec2::ZERO).unwrap();
+        assert!(state.is_pointer_locked());
+        assert_eq!(state.mouse_position, Vec2::new(100.0, 80.0));
+        assert_eq!(state.mouse_delta, Vec2::new(5.0, -1.0));
+
+        let unlock =
+            r#"[{"event_type": "PointerLockChange", "timestamp": 5.0, "data": {"locked": false}}]"#;
+        process_input_events(unlock, &mut state, Vec2::ZERO).unwrap();
+        assert!(!state.is_pointer_locked());
+    }
+
+    #[test]
+    fn test_relative_aim_fallbacks() {
+        let desktop = PointerCapabilities {
+            pointer_lock: true,
+            touch: true,
+        };
+        let tablet = PointerCapabilities {
+            pointer_lock: false,
+            touch: true,
+        };
+        assert_eq!(desktop.relative_aim(), RelativeAim::PointerLock);
+        assert_eq!(tablet.relative_aim(), RelativeAim::TouchDrag);
+        assert_eq!(
+            PointerCapabilities::default().relative_aim(),
+            RelativeAim::Cursor
+        );
+    }
+
     // ==================== MOUSE BUTTON TESTS ====================
 
     #[test]
@@ -603,6 +768,42 @@ mod tests {
         assert_eq!(state.touches[0].phase, TouchPhase::Ended);
     }
 
+    #[test]
+    fn test_process_device_orientation() {
+        let mut state = InputState::new();
+        let events = r#"[{"event_type":"DeviceOrientation","timestamp":0,"data":{"beta":45.0,"gamma":-9.0}}]"#;
+
+        assert!(process_input_events(events, &mut state, Vec2::ZERO).is_ok());
+        let tilt = state.tilt().unwrap();
+        assert!((tilt.x + 0.2).abs() < 1e-6);
+        assert!((tilt.y - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_process_device_motion_shake_and_permission() {
+        let mut state = InputState::new();
+        let events = r#"[
+            {"event_type":"DeviceMotion","timestamp":0,"data":{"ax":18.0,"ay":0.0,"az":0.0,"alpha":0.0,"beta":0.0,"gamma":0.0}},
+            {"event_type":"DeviceMotion","timestamp":100,"data":{"ax":-18.0,"ay":0.0,"az":0.0,"alpha":0.0,"beta":0.0,"gamma":0.0}},
+            {"event_type":"DeviceMotion","timestamp":200,"data":{"ax":18.0,"ay":0.0,"az":0.0,"alpha":0.0,"beta":30.0,"gamma":0.0}}
+        ]"#;
+
+        assert!(process_input_events(events, &mut state, Vec2::ZERO).is_ok());
+        assert!(state.motion().just_shaken());
+        assert_eq!(
+            state.motion().rotation_rate(),
+            Some(Vec3::new(0.0, 30.0, 0.0))
+        );
+        state.clear_events();
+        assert!(!state.motion().just_shaken());
+
+        let denied =
+            r#"[{"event_type":"MotionPermission","timestamp":0,"data":{"granted":false}}]"#;
+        assert!(process_input_events(denied, &mut state, Vec2::ZERO).is_ok());
+        assert_eq!(state.motion().permission(), MotionPermission::Denied);
+        assert_eq!(state.motion().acceleration(), None);
+    }
+
     #[test]
     fn test_process_gamepad_button() {
         let mut state = InputState::new();
@@ -6,11 +6,15 @@
 //! - Hit confirmation feedback (flash effects)
 //! - Score popup animations
 //!
+//! With reduced motion on (see [`AccessibilitySettings`]) the shake, trail
+//! and particles are skipped; flashes and popups still confirm each hit.
+//!
 //! Reference: Swink, S. (2008). "Game Feel: A Game Designer's Guide to Virtual Sensation"
 
 // const fn with mutable references is not yet stable; mul_add less readable here
 #![allow(clippy::missing_const_for_fn, clippy::suboptimal_flops)]
 
+use jugar_core::AccessibilitySettings;
 use serde::{Deserialize, Serialize};
 
 /// Screen shake effect state.
@@ -639,6 +643,8 @@ pub struct JuiceEffects {
     pub score_popups: Vec<ScorePopup>,
     /// Particle system for collision effects
     pub particles: ParticleSystem,
+    /// Skip screen shake, ball trail and particles
+    pub reduced_motion: bool,
 }
 
 impl Default for JuiceEffects {
@@ -657,6 +663,26 @@ impl JuiceEffects {
             hit_flash: HitFlash::new(),
             score_popups: Vec::new(),
             particles: ParticleSystem::default(),
+            reduced_motion: false,
+        }
+    }
+
+    /// Applies the player's accessibility settings.
+    ///
+    /// Turning reduced motion on also stops any shake, trail or particles
+    /// already in flight.
+    pub fn apply_accessibility(&mut self, settings: &AccessibilitySettings) {
+        self.reduced_motion = settings.reduced_motion;
+        if self.reduced_motion {
+            self.screen_shake.reset();
+            self.ball_trail.clear();
+            self.particles.clear();
+        }
+    }
+
+    fn shake(&mut self, intensity: f32, duration: f32) {
+        if !self.reduced_motion {
+            self.screen_shake.trigger(intensity, duration);
         }
     }
 
@@ -669,7 +695,9 @@ impl JuiceEffects {
     /// * `dt` - Delta time in seconds
     pub fn update(&mut self, ball_x: f32, ball_y: f32, dt: f32) {
         let _ = self.screen_shake.update(dt);
-        self.ball_trail.update(ball_x, ball_y, dt);
+        if !self.reduced_motion {
+            self.ball_trail.update(ball_x, ball_y, dt);
+        }
         let _ = self.hit_flash.update(dt);
         self.particles.update(dt);
 
@@ -686,15 +714,17 @@ impl JuiceEffects {
     /// * `points_text` - Text to show in popup (e.g., "+1")
     pub fn on_goal(&mut self, scorer_x: f32, scorer_y: f32, points_text: &str) {
         // Strong screen shake for goals
-        self.screen_shake.trigger(8.0, 0.3);
+        self.shake(8.0, 0.3);
 
         // Score popup
         self.score_popups
             .push(ScorePopup::new(scorer_x, scorer_y, points_text, 1.0));
 
         // Celebratory particle burst (gold/yellow)
-        self.particles
-            .spawn(scorer_x, scorer_y, 30, 200.0, 0.8, 4.0, 0x00FF_D700);
+        if !self.reduced_motion {
+            self.particles
+                .spawn(scorer_x, scorer_y, 30, 200.0, 0.8, 4.0, 0x00FF_D700);
+        }
 
         // Clear trail on goal (ball resets)
         self.ball_trail.clear();
@@ -707,7 +737,7 @@ impl JuiceEffects {
     /// * `right_paddle` - True if right paddle was hit
     pub fn on_paddle_hit(&mut self, right_paddle: bool) {
         // Light screen shake for hits
-        self.screen_shake.trigger(3.0, 0.1);
+        self.shake(3.0, 0.1);
 
         // Flash the paddle
         self.hit_flash.trigger(right_paddle, 0.8, 0.1);
@@ -722,11 +752,15 @@ impl JuiceEffects {
     /// * `right_paddle` - True if right paddle was hit
     pub fn on_paddle_hit_at(&mut self, ball_x: f32, ball_y: f32, right_paddle: bool) {
         // Light screen shake for hits
-        self.screen_shake.trigger(3.0, 0.1);
+        self.shake(3.0, 0.1);
 
         // Flash the paddle
         self.hit_flash.trigger(right_paddle, 0.8, 0.1);
 
+        if self.reduced_motion {
+            return;
+        }
+
         // Directional particle burst (white/cyan sparks)
         // Direction away from paddle
         let direction_x = if right_paddle { -1.0 } else { 1.0 };
@@ -747,7 +781,7 @@ impl JuiceEffects {
     /// Triggers effects for a wall bounce.
     pub fn on_wall_bounce(&mut self) {
         // Very light shake for wall bounces
-        self.screen_shake.trigger(1.5, 0.05);
+        self.shake(1.5, 0.05);
     }
 
     /// Resets all effects.
@@ -1364,6 +1398,29 @@ mod tests {
         assert!(juice.particles.active_count() > 0);
     }
 
+    #[test]
+    fn test_juice_effects_reduced_motion() {
+        let mut juice = JuiceEffects::new();
+        juice.on_goal(400.0, 300.0, "+1");
+        juice.update(100.0, 200.0, 0.016);
+
+        juice.apply_accessibility(&AccessibilitySettings::new().with_reduced_motion(true));
+        assert!(!juice.screen_shake.is_active());
+        assert_eq!(juice.particles.active_count(), 0);
+        assert_eq!(juice.ball_trail.active_count(), 0);
+
+        juice.on_goal(400.0, 300.0, "+1");
+        juice.on_paddle_hit_at(50.0, 300.0, false);
+        juice.on_wall_bounce();
+        juice.update(100.0, 200.0, 0.016);
+        assert!(!juice.screen_shake.is_active());
+        assert_eq!(juice.particles.active_count(), 0);
+        assert_eq!(juice.ball_trail.active_count(), 0);
+        // Non-motion feedback still happens
+        assert!(juice.hit_flash.is_active());
+        assert_eq!(juice.score_popups.len(), 2);
+    }
+
     #[test]
     fn test_juice_effects_particles_update() {
         let mut juice = JuiceEffects::new();
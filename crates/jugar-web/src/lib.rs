@@ -62,17 +62,25 @@
 #![allow(clippy::std_instead_of_core)] // std::cmp::Ordering is fine
 #![allow(clippy::missing_const_for_fn)] // Many functions can't be const yet
 
+pub mod a11y;
 pub mod ai;
 pub mod audio;
 pub mod compute;
+pub mod crash;
 pub mod demo;
+pub mod devices;
+#[cfg(feature = "yaml")]
+pub mod editor;
+pub mod flaky;
 pub mod input;
 pub mod juice;
 pub mod loadtest;
+pub mod offline;
 pub mod platform;
 pub mod render;
 pub mod simd;
 pub mod simulation;
+pub mod soak;
 pub mod time;
 pub mod trace;
 
@@ -80,6 +88,7 @@ pub mod trace;
 mod simulation_tests;
 
 // Re-export main types for convenience
+pub use a11y::{AccessibilityOverlay, AccessibilityUpdate, AriaElement};
 pub use ai::{
     DeterminismConfig, DifficultyProfile, FlowChannel, FlowTheoryConfig, ModelMetadata,
     PlayerMetrics, PongAI, PongAIModel,
@@ -89,17 +98,40 @@ pub use compute::{
     detect_compute_capability, ComputeBenchmarkResult, ComputeCapability, ComputeDemo,
     ComputeDemoState, ComputeTier, GpuShaderInfo, ShaderType, PARTICLE_PHYSICS_WGSL,
 };
+pub use crash::{
+    crash_report_json, crash_screen, crash_screen_action, install_crash_reporter,
+    install_panic_hook, is_crashed, last_crash, redact_message, CrashAction, CrashRecorder,
+    CrashReport, CRASH_INPUT_HISTORY, CRASH_TITLE, MAX_CRASH_MESSAGE_LEN,
+};
 pub use demo::{Attribution, DemoState, GameMode, PerformanceStats, SpeedMultiplier};
+pub use devices::{
+    check_layout, DeviceDescriptor, DeviceMatrix, DeviceSnapshot, LayoutViolation, MatrixReport,
+    SafeAreaInsets, UiRegion, GRID_COLUMNS, SNAPSHOT_WIDTH,
+};
+#[cfg(feature = "yaml")]
+pub use editor::{
+    BridgeResult, EditorBridge, EditorEvent, ImportSource, MAX_IMPORT_SIZE, OPEN_FILE_ACCEPT,
+};
+pub use flaky::{
+    FlakeDetector, FlakeRecord, FlakeVerdict, QuarantineReport, DEFAULT_FLAKE_RUNS,
+    DEFAULT_SEED_STRIDE,
+};
 pub use input::{
     process_input_events, translate_gamepad_axis, translate_gamepad_button, translate_key,
     translate_mouse_button, BrowserEventData, BrowserInputEvent, InputTranslationError,
+    PointerCapabilities, RelativeAim,
 };
 pub use loadtest::{
     AnomalyResult, ChaosConfig, ChaosResults, ChaosScenario, DriftDetector, DriftReport,
     FrameTimeReport, FrameTimeStats, LoadTestConfig, LoadTestResult, LoadTestSummary,
 };
+pub use offline::{
+    AssetKind, CacheStrategy, ManifestIcon, OfflineAsset, OfflineExport, OfflineExportBuilder,
+    OfflineExportError, ServiceWorkerPlan, WebAppManifest, MANIFEST_FILE, SERVICE_WORKER_FILE,
+};
 pub use platform::{
-    DebugInfo, FrameOutput, GameState, PongGame, WebConfig, WebGame, WebPlatform, WebPlatformError,
+    DebugInfo, FrameOutput, GameState, PongGame, PongSnapshot, WebConfig, WebGame, WebPlatform,
+    WebPlatformError, AUTOSAVE_INTERVAL_FRAMES, SNAPSHOT_VERSION,
 };
 pub use render::{
     convert_render_command, convert_render_queue, Canvas2DCommand, Color, RenderFrame, TextAlign,
@@ -114,6 +146,10 @@ pub use simulation::{
     check_invariants, FailureReplay, FuzzGenerator, GameStateSnapshot, InvariantViolation,
     MonteCarloConfig, TestResult, TestTier, TimestampedInput,
 };
+pub use soak::{
+    LeakFinding, SoakCheckpoint, SoakConfig, SoakMetric, SoakReport, SoakRunner, SoakSample,
+    DEFAULT_SOAK_SECONDS,
+};
 pub use time::{
     calculate_delta_time, clamp_delta_time, dom_timestamp_to_seconds, seconds_to_dom_timestamp,
     FrameTimer, DEFAULT_MAX_DELTA_TIME, TARGET_DT_120FPS, TARGET_DT_30FPS, TARGET_DT_60FPS,
@@ -0,0 +1,478 @@
+//! Offline, installable exports.
+//!
+//! An exported game is a folder of static files: the HTML page, the WASM
+//! module, its JavaScript glue and any game bundles. [`OfflineExportBuilder`]
+//! lists those files and produces the two extra files that make the folder a
+//! Progressive Web App a tablet can add to its home screen:
+//!
+//! - `manifest.webmanifest` ([`WebAppManifest`])
+//! - `sw.js`, a cache-first service worker ([`ServiceWorkerPlan`])
+//!
+//! In keeping with the privacy model, every asset must be a relative path on
+//! the game's own origin. The service worker precaches exactly those files,
+//! answers from the cache first and never touches cross-origin requests, so
+//! an installed game plays with the network switched off and phones nowhere.
+//!
+//! ```text
+//! let export = OfflineExportBuilder::new("Catch the Stars")
+//!     .asset("index.html")
+//!     .asset("jugar_web_bg.wasm")
+//!     .asset("jugar_web.js")
+//!     .asset("stars.jgb")
+//!     .icon("icon-512.png", 512)
+//!     .build()?;
+//! write("manifest.webmanifest", export.manifest_json());
+//! write("sw.js", export.service_worker_js());
+//! ```
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::render::Color;
+
+/// File name of the generated manifest.
+pub const MANIFEST_FILE: &str = "manifest.webmanifest";
+
+/// File name of the generated service worker.
+pub const SERVICE_WORKER_FILE: &str = "sw.js";
+
+/// Offline export errors
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum OfflineExportError {
+    /// The game has no name to show under its home screen icon
+    #[error("The game needs a name")]
+    MissingName,
+    /// An asset points at another website
+    #[error("Asset '{0}' is on another website; offline games can only use their own files")]
+    RemoteAsset(String),
+    /// An asset path escapes the export folder or is empty
+    #[error("Asset path '{0}' must stay inside the game folder")]
+    InvalidPath(String),
+    /// The start page isn't among the assets
+    #[error("Start page '{0}' is not in the asset list")]
+    MissingStartPage(String),
+}
+
+/// What an exported file is, judged by its extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AssetKind {
+    /// HTML page
+    Html,
+    /// WebAssembly module
+    Wasm,
+    /// JavaScript glue
+    Script,
+    /// Jugar game bundle (`.jgb` or `.jugar`)
+    Bundle,
+    /// Image (sprites, icons)
+    Image,
+    /// Sound or music
+    Audio,
+    /// Anything else (JSON, fonts, models)
+    Other,
+}
+
+impl AssetKind {
+    /// Classifies a path by its extension.
+    #[must_use]
+    pub fn from_path(path: &str) -> Self {
+        let extension = path
+            .rsplit_once('.')
+            .map(|(_, ext)| ext.to_ascii_lowercase())
+            .unwrap_or_default();
+        match extension.as_str() {
+            "html" | "htm" => Self::Html,
+            "wasm" => Self::Wasm,
+            "js" | "mjs" => Self::Script,
+            "jgb" | "jugar" => Self::Bundle,
+            "png" | "jpg" | "jpeg" | "gif" | "webp" | "svg" => Self::Image,
+            "ogg" | "mp3" | "wav" | "m4a" => Self::Audio,
+            _ => Self::Other,
+        }
+    }
+}
+
+/// One file that must be available offline.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OfflineAsset {
+    /// Path relative to the export folder, without a leading `./`
+    pub path: String,
+    /// File type
+    pub kind: AssetKind,
+}
+
+/// Home screen icon entry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestIcon {
+    /// Icon path relative to the manifest
+    pub src: String,
+    /// Pixel size as `"WxH"`
+    pub sizes: String,
+    /// MIME type
+    #[serde(rename = "type")]
+    pub mime_type: String,
+}
+
+/// Web app manifest (field names follow the W3C spec).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WebAppManifest {
+    /// Full name
+    pub name: String,
+    /// Name under the home screen icon
+    pub short_name: String,
+    /// Page opened when the icon is tapped
+    pub start_url: String,
+    /// URLs that belong to the app
+    pub scope: String,
+    /// Display mode (`fullscreen` hides the browser UI)
+    pub display: String,
+    /// Allowed screen orientation
+    pub orientation: String,
+    /// Splash screen background as `#rrggbb`
+    pub background_color: String,
+    /// Browser UI color as `#rrggbb`
+    pub theme_color: String,
+    /// Home screen icons
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub icons: Vec<ManifestIcon>,
+}
+
+/// How the service worker answers requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CacheStrategy {
+    /// Serve from the cache, falling back to the network on a miss
+    CacheFirst,
+}
+
+/// Description of the generated service worker.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServiceWorkerPlan {
+    /// Cache name; changes whenever the asset list does, so updates evict
+    /// the previous version on activation
+    pub cache_name: String,
+    /// Files cached at install time, relative to the worker
+    pub precache: Vec<String>,
+    /// Request handling
+    pub strategy: CacheStrategy,
+}
+
+impl ServiceWorkerPlan {
+    /// Renders the plan as service worker JavaScript.
+    ///
+    /// Only same-origin `GET` requests are answered; everything else is
+    /// left to the browser untouched.
+    #[must_use]
+    pub fn to_js(&self) -> String {
+        let cache = serde_json::to_string(&self.cache_name).unwrap_or_default();
+        let assets = serde_json::to_string(&self.precache).unwrap_or_else(|_| "[]".to_string());
+        format!(
+            "// Generated by jugar-web: {strategy} offline cache\n\
+             const CACHE = {cache};\n\
+             const ASSETS = {assets};\n\
+             self.addEventListener('install', (e) => e.waitUntil(caches.open(CACHE).then((c) => c.addAll(ASSETS)).then(() => self.skipWaiting())));\n\
+             self.addEventListener('activate', (e) => e.waitUntil(caches.keys().then((keys) => Promise.all(keys.filter((k) => k !== CACHE).map((k) => caches.delete(k)))).then(() => self.clients.claim())));\n\
+             self.addEventListener('fetch', (e) => {{\n\
+             \x20   if (e.request.method !== 'GET' || new URL(e.request.url).origin !== self.location.origin) return;\n\
+             \x20   e.respondWith(caches.match(e.request).then((hit) => hit || fetch(e.request)));\n\
+             }});\n",
+            strategy = match self.strategy {
+                CacheStrategy::CacheFirst => "cache-first",
+            },
+        )
+    }
+}
+
+/// Everything needed to make an exported folder installable.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OfflineExport {
+    /// Web app manifest
+    pub manifest: WebAppManifest,
+    /// Service worker description
+    pub service_worker: ServiceWorkerPlan,
+    /// Files the game needs, in the order given
+    pub assets: Vec<OfflineAsset>,
+}
+
+impl OfflineExport {
+    /// Serializes the manifest for [`MANIFEST_FILE`].
+    #[must_use]
+    pub fn manifest_json(&self) -> String {
+        serde_json::to_string_pretty(&self.manifest).unwrap_or_else(|_| "{}".to_string())
+    }
+
+    /// Generates the script for [`SERVICE_WORKER_FILE`].
+    #[must_use]
+    pub fn service_worker_js(&self) -> String {
+        self.service_worker.to_js()
+    }
+
+    /// Tags to add to the start page's `<head>`.
+    #[must_use]
+    pub fn head_tags(&self) -> String {
+        format!(
+            "<link rel=\"manifest\" href=\"{MANIFEST_FILE}\">\n\
+             <meta name=\"theme-color\" content=\"{}\">\n\
+             <script>if ('serviceWorker' in navigator) navigator.serviceWorker.register('{SERVICE_WORKER_FILE}');</script>\n",
+            self.manifest.theme_color
+        )
+    }
+}
+
+/// Builder for [`OfflineExport`]
+#[derive(Debug, Clone)]
+pub struct OfflineExportBuilder {
+    name: String,
+    short_name: Option<String>,
+    start_page: String,
+    background: Color,
+    theme: Color,
+    assets: Vec<String>,
+    icons: Vec<(String, u32)>,
+}
+
+impl OfflineExportBuilder {
+    /// Starts an export for the named game, opening `index.html`.
+    #[must_use]
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            short_name: None,
+            start_page: "index.html".to_string(),
+            background: Color::BLACK,
+            theme: Color::BLACK,
+            assets: Vec::new(),
+            icons: Vec::new(),
+        }
+    }
+
+    /// Sets the name under the home screen icon (defaults to the full name).
+    #[must_use]
+    pub fn short_name(mut self, short_name: impl Into<String>) -> Self {
+        self.short_name = Some(short_name.into());
+        self
+    }
+
+    /// Sets the page opened from the home screen.
+    #[must_use]
+    pub fn start_page(mut self, path: impl Into<String>) -> Self {
+        self.start_page = path.into();
+        self
+    }
+
+    /// Sets the splash screen and browser UI colors.
+    #[must_use]
+    pub const fn colors(mut self, background: Color, theme: Color) -> Self {
+        self.background = background;
+        self.theme = theme;
+        self
+    }
+
+    /// Adds a file the game needs offline.
+    #[must_use]
+    pub fn asset(mut self, path: impl Into<String>) -> Self {
+        self.assets.push(path.into());
+        self
+    }
+
+    /// Adds a square PNG home screen icon (also cached as an asset).
+    #[must_use]
+    pub fn icon(mut self, path: impl Into<String>, size: u32) -> Self {
+        self.icons.push((path.into(), size));
+        self
+    }
+
+    /// Validates the asset list and builds the export.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the name is blank, an asset is remote or leaves
+    /// the export folder, or the start page isn't listed.
+    pub fn build(self) -> Result<OfflineExport, OfflineExportError> {
+        let name = self.name.trim().to_string();
+        if name.is_empty() {
+            return Err(OfflineExportError::MissingName);
+        }
+        let start_page = local_path(&self.start_page)?;
+
+        let mut assets: Vec<OfflineAsset> = Vec::new();
+        let icon_paths = self.icons.iter().map(|(path, _)| path);
+        for raw in self.assets.iter().chain(icon_paths) {
+            let path = local_path(raw)?;
+            if !assets.iter().any(|a| a.path == path) {
+                assets.push(OfflineAsset {
+                    kind: AssetKind::from_path(&path),
+                    path,
+                });
+            }
+        }
+        if !assets.iter().any(|a| a.path == start_page) {
+            return Err(OfflineExportError::MissingStartPage(start_page));
+        }
+
+        let icons = self
+            .icons
+            .iter()
+            .map(|(path, size)| {
+                Ok(ManifestIcon {
+                    src: local_path(path)?,
+                    sizes: format!("{size}x{size}"),
+                    mime_type: "image/png".to_string(),
+                })
+            })
+            .collect::<Result<_, OfflineExportError>>()?;
+        let manifest = WebAppManifest {
+            short_name: self.short_name.unwrap_or_else(|| name.clone()),
+            name,
+            start_url: format!("./{start_page}"),
+            scope: "./".to_string(),
+            display: "fullscreen".to_string(),
+            orientation: "any".to_string(),
+            background_color: css_hex(self.background),
+            theme_color: css_hex(self.theme),
+            icons,
+        };
+
+        // The manifest itself is fetched by the browser and must load offline too
+        let precache: Vec<String> = core::iter::once(format!("./{MANIFEST_FILE}"))
+            .chain(assets.iter().map(|a| format!("./{}", a.path)))
+            .collect();
+        let service_worker = ServiceWorkerPlan {
+            cache_name: format!("jugar-{:016x}", fnv1a(&precache)),
+            precache,
+            strategy: CacheStrategy::CacheFirst,
+        };
+
+        Ok(OfflineExport {
+            manifest,
+            service_worker,
+            assets,
+        })
+    }
+}
+
+/// Normalizes an asset path to be relative to the export folder.
+fn local_path(raw: &str) -> Result<String, OfflineExportError> {
+    let trimmed = raw.trim();
+    if trimmed.contains("://") || trimmed.starts_with("//") {
+        return Err(OfflineExportError::RemoteAsset(trimmed.to_string()));
+    }
+    let path = trimmed.trim_start_matches("./").trim_start_matches('/');
+    if path.is_empty() || path.split('/').any(|part| part == "..") {
+        return Err(OfflineExportError::InvalidPath(trimmed.to_string()));
+    }
+    Ok(path.to_string())
+}
+
+/// Formats an opaque `#rrggbb` color for the manifest.
+fn css_hex(color: Color) -> String {
+    let [r, g, b, _] = color.to_rgba8();
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+/// FNV-1a over the precache list, so the cache name tracks its contents
+fn fnv1a(paths: &[String]) -> u64 {
+    const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+    paths
+        .iter()
+        .flat_map(|path| path.bytes().chain(core::iter::once(0)))
+        .fold(OFFSET, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+        })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn game() -> OfflineExportBuilder {
+        OfflineExportBuilder::new("Catch the Stars")
+            .asset("./index.html")
+            .asset("jugar_web_bg.wasm")
+            .asset("jugar_web.js")
+            .asset("stars.jgb")
+            .icon("icons/star-512.png", 512)
+    }
+
+    #[test]
+    fn test_manifest_and_asset_list() {
+        let export = game()
+            .short_name("Stars")
+            .colors(Color::BLACK, Color::rgb(1.0, 0.5, 0.0))
+            .build()
+            .unwrap();
+        let kinds: Vec<AssetKind> = export.assets.iter().map(|a| a.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                AssetKind::Html,
+                AssetKind::Wasm,
+                AssetKind::Script,
+                AssetKind::Bundle,
+                AssetKind::Image
+            ]
+        );
+        assert_eq!(export.manifest.short_name, "Stars");
+        assert_eq!(export.manifest.start_url, "./index.html");
+        assert_eq!(export.manifest.theme_color, "#ff8000");
+        assert_eq!(export.manifest.icons[0].sizes, "512x512");
+
+        let json: serde_json::Value = serde_json::from_str(&export.manifest_json()).unwrap();
+        assert_eq!(json["icons"][0]["type"], "image/png");
+        assert_eq!(json["display"], "fullscreen");
+        assert!(export.head_tags().contains(MANIFEST_FILE));
+    }
+
+    #[test]
+    fn test_service_worker_is_cache_first_and_same_origin() {
+        let export = game().build().unwrap();
+        let plan = &export.service_worker;
+        assert_eq!(plan.strategy, CacheStrategy::CacheFirst);
+        assert!(plan
+            .precache
+            .contains(&"./manifest.webmanifest".to_string()));
+        assert!(plan.precache.contains(&"./stars.jgb".to_string()));
+
+        let js = export.service_worker_js();
+        assert!(js.contains(&plan.cache_name));
+        assert!(js.contains("\"./jugar_web_bg.wasm\""));
+        assert!(js.contains("self.location.origin"));
+        assert!(js.contains("caches.match(e.request)"));
+
+        // A new asset means a new cache, so installed copies update
+        let updated = game().asset("level2.jgb").build().unwrap();
+        assert_ne!(updated.service_worker.cache_name, plan.cache_name);
+        assert_eq!(game().build().unwrap().service_worker, *plan);
+    }
+
+    #[test]
+    fn test_rejects_remote_and_escaping_assets() {
+        assert_eq!(
+            game().asset("https://cdn.example.com/track.js").build(),
+            Err(OfflineExportError::RemoteAsset(
+                "https://cdn.example.com/track.js".to_string()
+            ))
+        );
+        assert!(matches!(
+            game().asset("//cdn.example.com/a.js").build(),
+            Err(OfflineExportError::RemoteAsset(_))
+        ));
+        assert!(matches!(
+            game().asset("../secrets.json").build(),
+            Err(OfflineExportError::InvalidPath(_))
+        ));
+        assert_eq!(
+            game().start_page("play.html").build(),
+            Err(OfflineExportError::MissingStartPage(
+                "play.html".to_string()
+            ))
+        );
+        assert_eq!(
+            OfflineExportBuilder::new("  ").build(),
+            Err(OfflineExportError::MissingName)
+        );
+    }
+}
@@ -8,15 +8,21 @@ use glam::Vec2;
 use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
 
+use crate::a11y::{AccessibilityOverlay, AccessibilityUpdate, AriaElement};
 use crate::ai::PongAI;
 use crate::audio::{AudioEvent, ProceduralAudio};
+use crate::crash::{self, CrashAction};
 use crate::demo::{DemoState, GameMode, SpeedMultiplier};
-use crate::input::{process_input_events, InputTranslationError};
-use crate::juice::JuiceEffects;
+use crate::devices::UiRegion;
+use crate::input::{process_input_events, InputTranslationError, PointerCapabilities};
+use crate::juice::{JuiceEffects, ScorePopup};
 use crate::render::{Canvas2DCommand, Color, RenderFrame, TextAlign, TextBaseline};
 use crate::time::FrameTimer;
 use crate::trace::{GameTracer, TracerConfig};
-use jugar_input::{InputState, MouseButton};
+use jugar::ui::{Role, SemanticNode, WidgetId};
+use jugar_core::{AccessibilitySettings, Migrations, PoolStats, Rect, SchemaVersion};
+use jugar_input::{InputState, MotionPermission, MouseButton};
+use jugar_render::{ClipRecorder, Image};
 
 /// A clickable button rectangle.
 #[derive(Debug, Clone, Copy, Default)]
@@ -41,6 +47,14 @@ impl ButtonRect {
     fn contains(&self, px: f32, py: f32) -> bool {
         px >= self.x && px <= self.x + self.width && py >= self.y && py <= self.y + self.height
     }
+
+    /// Labelled screen region for layout checks.
+    const fn region(self, label: &'static str) -> UiRegion {
+        UiRegion {
+            label,
+            rect: Rect::new(self.x, self.y, self.width, self.height),
+        }
+    }
 }
 
 /// HUD button regions for click detection.
@@ -175,6 +189,26 @@ impl HudButtons {
             sound_toggle,
         }
     }
+
+    /// Every button as a labelled region.
+    fn regions(&self) -> Vec<UiRegion> {
+        vec![
+            self.mode_demo.region("Demo"),
+            self.mode_1p.region("1P"),
+            self.mode_2p.region("2P"),
+            self.speed_1x.region("1x"),
+            self.speed_5x.region("5x"),
+            self.speed_10x.region("10x"),
+            self.speed_50x.region("50x"),
+            self.speed_100x.region("100x"),
+            self.speed_1000x.region("1000x"),
+            self.ai_decrease.region("AI -"),
+            self.ai_increase.region("AI +"),
+            self.download.region("Download .apr"),
+            self.model_info.region("Info"),
+            self.sound_toggle.region("Sound"),
+        ]
+    }
 }
 
 /// Web platform configuration.
@@ -195,6 +229,12 @@ pub struct WebConfig {
     /// Enable AI opponent (replaces Player 2)
     #[serde(default = "default_ai_enabled")]
     pub ai_enabled: bool,
+    /// High contrast, large text and reduced motion preferences
+    #[serde(default)]
+    pub accessibility: AccessibilitySettings,
+    /// Pointer lock and touch support detected by JavaScript
+    #[serde(default)]
+    pub pointer: PointerCapabilities,
 }
 
 const fn default_ai_enabled() -> bool {
@@ -221,6 +261,11 @@ impl Default for WebConfig {
             target_fps: 60,
             debug: false,
             ai_enabled: true,
+            accessibility: AccessibilitySettings::new(),
+            pointer: PointerCapabilities {
+                pointer_lock: false,
+                touch: false,
+            },
         }
     }
 }
@@ -235,6 +280,11 @@ impl WebConfig {
             target_fps: 60,
             debug: false,
             ai_enabled: true,
+            accessibility: AccessibilitySettings::new(),
+            pointer: PointerCapabilities {
+                pointer_lock: false,
+                touch: false,
+            },
         }
     }
 
@@ -301,6 +351,43 @@ pub enum JsAction {
     EnterFullscreen,
     /// Exit fullscreen mode
     ExitFullscreen,
+    /// Read back the drawn canvas and pass it to `pushClipFrame`
+    CaptureClipFrame,
+    /// Lock the pointer to the canvas for relative mouse movement
+    RequestPointerLock,
+    /// Release the pointer lock
+    ExitPointerLock,
+    /// Ask for motion sensor access (`DeviceMotionEvent.requestPermission`
+    /// on iOS), answered with a `MotionPermission` input event
+    RequestMotionPermission,
+    /// Put text on the clipboard, answered with `ClipboardWritten`
+    CopyToClipboard {
+        /// Text to copy
+        text: String,
+    },
+    /// Read the clipboard as text, answered with `ClipboardText`
+    ReadClipboard {
+        /// Id to echo back in the answer
+        request: u32,
+    },
+    /// Show the open file dialog, answered with `FileOpened`
+    OpenFile {
+        /// Id to echo back in the answer
+        request: u32,
+        /// `accept` attribute for the file input
+        accept: String,
+        /// Files larger than this should be refused without reading
+        max_bytes: usize,
+    },
+    /// Download text as a file, answered with `FileSaved`
+    SaveFile {
+        /// Suggested file name
+        file_name: String,
+        /// MIME type of the download
+        mime_type: String,
+        /// File contents
+        contents: String,
+    },
 }
 
 /// Frame output returned to JavaScript.
@@ -314,11 +401,68 @@ pub struct FrameOutput {
     /// JavaScript actions to perform
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub actions: Vec<JsAction>,
+    /// Screen reader overlay, present only on frames where it changed
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub accessibility: Option<AccessibilityUpdate>,
     /// Debug information (only present if debug mode enabled)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub debug_info: Option<DebugInfo>,
 }
 
+/// Frames between autosaved snapshots (10 seconds at 60 FPS).
+pub const AUTOSAVE_INTERVAL_FRAMES: u64 = 600;
+
+/// Snapshot format version written by this release.
+///
+/// Version 0 is the untagged format from before snapshots were versioned.
+pub const SNAPSHOT_VERSION: SchemaVersion = 1;
+
+/// Autosaved game state offered for restore after a crash.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PongSnapshot {
+    /// Snapshot format version ([`SNAPSHOT_VERSION`])
+    pub version: SchemaVersion,
+    /// Left player score
+    pub left_score: u32,
+    /// Right player score
+    pub right_score: u32,
+    /// Game mode short label ("Demo", "1P", "2P")
+    pub game_mode: String,
+    /// Speed multiplier value
+    pub speed: u32,
+    /// AI difficulty level (0-9)
+    pub ai_difficulty: u8,
+}
+
+impl PongSnapshot {
+    /// Upgrades from every older snapshot version, as JSON documents.
+    #[must_use]
+    pub fn migrations() -> Migrations<serde_json::Value> {
+        Migrations::new("saved game", 0).step("add a version tag", |mut document| {
+            let fields = document
+                .as_object_mut()
+                .ok_or_else(|| "not an object".to_string())?;
+            let _ = fields.insert("version".to_string(), 1.into());
+            Ok(document)
+        })
+    }
+
+    /// Parses snapshot JSON from any supported version.
+    ///
+    /// Returns `None` if the JSON isn't a snapshot or was written by a
+    /// newer release.
+    #[must_use]
+    pub fn from_json(json: &str) -> Option<Self> {
+        let document: serde_json::Value = serde_json::from_str(json).ok()?;
+        let version = document
+            .get("version")
+            .map_or(Some(0), serde_json::Value::as_u64)
+            .and_then(|v| SchemaVersion::try_from(v).ok())?;
+        let document = Self::migrations().migrate(document, version).ok()?;
+        serde_json::from_value(document).ok()
+    }
+}
+
 /// Debug information for development.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DebugInfo {
@@ -351,6 +495,12 @@ pub struct DebugInfo {
     /// Frames dropped from trace buffer
     #[serde(skip_serializing_if = "Option::is_none")]
     pub trace_dropped: Option<u64>,
+    /// Times the render command buffer had to grow since start
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub render_allocations: Option<u64>,
+    /// Times the audio event buffer had to grow since start
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub audio_allocations: Option<u64>,
 }
 
 /// Trait for game implementations that can run on the web platform.
@@ -578,6 +728,11 @@ impl PongGame {
         self.game_mode
     }
 
+    /// Applies accessibility settings to the game's effects.
+    pub fn set_accessibility(&mut self, settings: &AccessibilitySettings) {
+        self.juice.apply_accessibility(settings);
+    }
+
     /// Sets the game mode.
     pub fn set_game_mode(&mut self, mode: GameMode) {
         self.game_mode = mode;
@@ -621,11 +776,22 @@ impl PongGame {
             self.audio.take_events()
         } else {
             // Clear events but don't return them
-            drop(self.audio.take_events());
+            self.audio.clear_events();
             Vec::new()
         }
     }
 
+    /// Returns a buffer from [`Self::take_audio_events`] for reuse.
+    pub fn recycle_audio_events(&mut self, events: Vec<AudioEvent>) {
+        self.audio.recycle_events(events);
+    }
+
+    /// Returns allocation counters of the audio event buffer.
+    #[must_use]
+    pub const fn audio_pool_stats(&self) -> PoolStats {
+        self.audio.pool_stats()
+    }
+
     /// Exports the AI model as JSON for download.
     #[must_use]
     pub fn export_ai_model(&self) -> String {
@@ -761,6 +927,53 @@ impl PongGame {
         self.is_fullscreen
     }
 
+    /// HUD buttons as last rendered (or laid out for the canvas size).
+    #[must_use]
+    pub fn hud_regions(&self) -> Vec<UiRegion> {
+        self.hud_buttons.regions()
+    }
+
+    /// HUD buttons and the score as screen reader nodes.
+    #[must_use]
+    pub fn semantics(&self) -> Vec<SemanticNode> {
+        let mut nodes: Vec<SemanticNode> = self
+            .hud_regions()
+            .into_iter()
+            .map(|region| SemanticNode {
+                id: WidgetId::new(region.label),
+                role: Role::Button,
+                label: region.label.to_string(),
+                value: None,
+                disabled: false,
+                bounds: region.rect,
+            })
+            .collect();
+        nodes.push(SemanticNode {
+            id: WidgetId::new("score"),
+            role: Role::Status,
+            label: "Score".to_string(),
+            value: Some(format!("{} to {}", self.left_score, self.right_score)),
+            disabled: false,
+            bounds: Rect::new(self.width / 4.0, 50.0, self.width / 2.0, 48.0),
+        });
+        nodes
+    }
+
+    /// Live entities: ball, both paddles and every active effect.
+    #[must_use]
+    pub fn entity_count(&self) -> usize {
+        3 + self.juice.ball_trail.active_count()
+            + self.juice.score_popups.len()
+            + self.juice.particles.active_count()
+    }
+
+    /// Bytes held by the game's growable buffers.
+    #[must_use]
+    pub fn retained_bytes(&self) -> usize {
+        self.juice.score_popups.capacity() * core::mem::size_of::<ScorePopup>()
+            + self.audio_pool_stats().peak * core::mem::size_of::<AudioEvent>()
+    }
+
     /// Sets ball position (for testing).
     #[cfg(test)]
     pub fn set_ball_position(&mut self, x: f32, y: f32) {
@@ -2714,6 +2927,18 @@ pub struct WebPlatform {
     canvas_offset_y: f32,
     /// Game tracer for replay recording (only active in debug mode)
     tracer: GameTracer,
+    /// Rolling recorder for "save the last 5 seconds" clips
+    clip: ClipRecorder,
+    /// Growth counters of the reused render command buffer
+    command_stats: PoolStats,
+    /// Pending pointer lock change (true = lock, false = release)
+    pointer_lock_request: Option<bool>,
+    /// Motion sensor permission prompt pending
+    motion_permission_requested: bool,
+    /// Screen reader overlay built from the HUD each frame
+    aria: AccessibilityOverlay,
+    /// Overlay elements last sent to JavaScript
+    aria_elements: Vec<AriaElement>,
 }
 
 #[wasm_bindgen]
@@ -2736,7 +2961,8 @@ impl WebPlatform {
         let mut timer = FrameTimer::new();
         timer.set_fixed_dt(fixed_dt);
 
-        let pong = PongGame::new(config.width as f32, config.height as f32, config.ai_enabled);
+        let mut pong = PongGame::new(config.width as f32, config.height as f32, config.ai_enabled);
+        pong.set_accessibility(&config.accessibility);
 
         // Use debug tracer in debug mode (Andon Cord), production tracer otherwise
         let tracer = if config.debug {
@@ -2756,6 +2982,12 @@ impl WebPlatform {
             canvas_offset_x: 0.0,
             canvas_offset_y: 0.0,
             tracer,
+            clip: ClipRecorder::default(),
+            command_stats: PoolStats::default(),
+            pointer_lock_request: None,
+            motion_permission_requested: false,
+            aria: AccessibilityOverlay::new(),
+            aria_elements: Vec::new(),
         })
     }
 
@@ -2777,6 +3009,12 @@ impl WebPlatform {
             canvas_offset_x: 0.0,
             canvas_offset_y: 0.0,
             tracer: GameTracer::production(), // Default to production mode
+            clip: ClipRecorder::default(),
+            command_stats: PoolStats::default(),
+            pointer_lock_request: None,
+            motion_permission_requested: false,
+            aria: AccessibilityOverlay::new(),
+            aria_elements: Vec::new(),
         }
     }
 
@@ -2802,6 +3040,12 @@ impl WebPlatform {
     /// JSON string with render commands for Canvas2D execution.
     #[wasm_bindgen]
     pub fn frame(&mut self, timestamp: f64, input_events_json: &str) -> String {
+        // Keep crash context current in case this frame panics
+        crash::note_frame(self.frame_count, input_events_json);
+        if self.frame_count % AUTOSAVE_INTERVAL_FRAMES == 0 {
+            crash::note_autosave(self.snapshot());
+        }
+
         // Begin trace frame recording
         self.tracer.begin_frame();
 
@@ -2819,14 +3063,20 @@ impl WebPlatform {
         // Clear input events for next frame (key presses persist, events don't)
         self.input.clear_events();
 
-        // Generate render commands
+        // Generate render commands into last frame's buffer
         self.render_frame.clear();
+        let capacity = self.render_frame.commands.capacity();
         self.pong.render(&mut self.render_frame);
 
         // Add debug info if enabled
         if self.config.debug {
             self.render_debug_info(dt);
         }
+        self.command_stats.frames += 1;
+        self.command_stats.peak = self.command_stats.peak.max(self.render_frame.len());
+        if self.render_frame.commands.capacity() > capacity {
+            self.command_stats.allocations += 1;
+        }
 
         // Take any pending audio events
         let audio_events = self.pong.take_audio_events();
@@ -2845,15 +3095,39 @@ impl WebPlatform {
             }
             self.pong.fullscreen_requested = false; // Consume the flag
         }
+        if self.clip.tick(dt as f32) {
+            actions.push(JsAction::CaptureClipFrame);
+        }
+        match self.pointer_lock_request.take() {
+            Some(true) => actions.push(JsAction::RequestPointerLock),
+            Some(false) => actions.push(JsAction::ExitPointerLock),
+            None => {}
+        }
+        if core::mem::take(&mut self.motion_permission_requested) {
+            actions.push(JsAction::RequestMotionPermission);
+        }
+
+        // Only send the overlay when it changed, so the DOM (and screen
+        // reader focus) is left alone on ordinary frames
+        let update = self.aria.update(&self.pong.semantics());
+        let accessibility =
+            if update.announcements.is_empty() && update.elements == self.aria_elements {
+                None
+            } else {
+                self.aria_elements.clone_from(&update.elements);
+                Some(update)
+            };
 
         // End trace frame (no state hash for now - can add deterministic hashing later)
         let _ = self.tracer.end_frame(None);
 
         // Build frame output with optional debug info
         let output = FrameOutput {
-            commands: self.render_frame.commands.clone(),
+            // Lent to the output and returned after serializing
+            commands: core::mem::take(&mut self.render_frame.commands),
             audio_events,
             actions,
+            accessibility,
             debug_info: if self.config.debug {
                 let stats = self.tracer.stats();
                 Some(DebugInfo {
@@ -2873,6 +3147,8 @@ impl WebPlatform {
                     )),
                     trace_inputs: Some(stats.total_inputs),
                     trace_dropped: Some(stats.frames_dropped),
+                    render_allocations: Some(self.command_stats.allocations),
+                    audio_allocations: Some(self.pong.audio_pool_stats().allocations),
                 })
             } else {
                 None
@@ -2880,8 +3156,12 @@ impl WebPlatform {
         };
         self.frame_count += 1;
 
-        // Serialize and return
-        serde_json::to_string(&output).unwrap_or_else(|_| r#"{"commands":[]}"#.to_string())
+        // Serialize, then take the buffers back so the next frame reuses them
+        let json =
+            serde_json::to_string(&output).unwrap_or_else(|_| r#"{"commands":[]}"#.to_string());
+        self.render_frame.commands = output.commands;
+        self.pong.recycle_audio_events(output.audio_events);
+        json
     }
 
     /// Handles canvas resize.
@@ -2912,10 +3192,28 @@ impl WebPlatform {
             "fps": self.timer.average_fps(),
             "frame_count": self.timer.frame_count(),
             "total_time": self.timer.total_time(),
+            "render_allocations": self.command_stats.allocations,
+            "audio_allocations": self.pong.audio_pool_stats().allocations,
         });
         stats.to_string()
     }
 
+    /// Returns the engine version and compiled-in subsystems as JSON.
+    #[wasm_bindgen(js_name = "getBuildInfo")]
+    #[must_use]
+    pub fn get_build_info() -> String {
+        let info = jugar::build_info();
+        let mut subsystems = info.subsystems();
+        if cfg!(feature = "yaml") {
+            subsystems.push("yaml");
+        }
+        serde_json::json!({
+            "version": info.version,
+            "subsystems": subsystems,
+        })
+        .to_string()
+    }
+
     /// Resets the timer (useful when tab becomes visible again).
     #[wasm_bindgen(js_name = "resetTimer")]
     pub fn reset_timer(&mut self) {
@@ -2982,6 +3280,111 @@ impl WebPlatform {
         });
     }
 
+    /// Updates accessibility settings from JSON, e.g. from `prefers-reduced-motion`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a JavaScript error if the JSON is invalid.
+    #[wasm_bindgen(js_name = "setAccessibility")]
+    pub fn set_accessibility(&mut self, settings_json: &str) -> Result<(), JsValue> {
+        let settings: AccessibilitySettings = serde_json::from_str(settings_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid accessibility settings: {e}")))?;
+        self.pong.set_accessibility(&settings);
+        self.config.accessibility = settings;
+        Ok(())
+    }
+
+    /// Starts keeping the last few seconds of gameplay.
+    ///
+    /// Frames emit a `CaptureClipFrame` action whenever the clip is due a
+    /// frame; JavaScript answers with `pushClipFrame`.
+    #[wasm_bindgen(js_name = "startClipRecording")]
+    pub fn start_clip_recording(&mut self) {
+        self.clip.start();
+    }
+
+    /// Stops clip recording, keeping the frames held so far.
+    #[wasm_bindgen(js_name = "stopClipRecording")]
+    pub fn stop_clip_recording(&mut self) {
+        self.clip.stop();
+    }
+
+    /// Adds a canvas readback (`getImageData(...).data`) to the clip.
+    ///
+    /// Returns false if the buffer doesn't match the size.
+    #[wasm_bindgen(js_name = "pushClipFrame")]
+    pub fn push_clip_frame(&mut self, width: u32, height: u32, rgba: &[u8]) -> bool {
+        let Some(frame) = Image::from_rgba(width, height, rgba.to_vec()) else {
+            return false;
+        };
+        self.clip.push(&frame);
+        true
+    }
+
+    /// Encodes the recorded clip as an animated PNG (empty if nothing is recorded).
+    #[wasm_bindgen(js_name = "clipApng")]
+    #[must_use]
+    pub fn clip_apng(&self) -> Vec<u8> {
+        self.clip.to_apng().unwrap_or_default()
+    }
+
+    /// Encodes a canvas readback as a PNG screenshot (empty on a size mismatch).
+    #[wasm_bindgen(js_name = "captureFrame")]
+    #[must_use]
+    pub fn capture_frame(width: u32, height: u32, rgba: &[u8]) -> Vec<u8> {
+        Image::from_rgba(width, height, rgba.to_vec())
+            .map(|image| image.to_png())
+            .unwrap_or_default()
+    }
+
+    /// Returns true if the browser reported pointer lock support.
+    #[wasm_bindgen(js_name = "supportsPointerLock")]
+    #[must_use]
+    #[allow(clippy::missing_const_for_fn)] // wasm_bindgen not compatible
+    pub fn supports_pointer_lock(&self) -> bool {
+        self.config.pointer.pointer_lock
+    }
+
+    /// Returns true while the pointer is locked to the canvas.
+    #[wasm_bindgen(js_name = "isPointerLocked")]
+    #[must_use]
+    #[allow(clippy::missing_const_for_fn)] // wasm_bindgen not compatible
+    pub fn is_pointer_locked(&self) -> bool {
+        self.input.is_pointer_locked()
+    }
+
+    /// Asks JavaScript to lock the pointer on the next frame.
+    ///
+    /// Returns false (and queues nothing) if pointer lock is unsupported,
+    /// so the game can fall back to touch-drag instead.
+    #[wasm_bindgen(js_name = "requestPointerLock")]
+    pub fn request_pointer_lock(&mut self) -> bool {
+        if !self.config.pointer.pointer_lock {
+            return false;
+        }
+        self.pointer_lock_request = Some(true);
+        true
+    }
+
+    /// Asks JavaScript to release the pointer lock on the next frame.
+    #[wasm_bindgen(js_name = "releasePointerLock")]
+    pub fn release_pointer_lock(&mut self) {
+        if self.input.is_pointer_locked() || self.pointer_lock_request.is_some() {
+            self.pointer_lock_request = Some(false);
+        }
+    }
+
+    /// Asks JavaScript to prompt for motion sensor access on the next frame.
+    ///
+    /// Call this from a tap: iOS only shows the prompt during a user gesture.
+    /// Does nothing once the player has answered.
+    #[wasm_bindgen(js_name = "requestMotionPermission")]
+    pub fn request_motion_permission(&mut self) {
+        if self.input.motion().permission() == MotionPermission::Unknown {
+            self.motion_permission_requested = true;
+        }
+    }
+
     /// Gets the current game mode as string.
     #[wasm_bindgen(js_name = "getGameMode")]
     #[must_use]
@@ -2989,6 +3392,60 @@ impl WebPlatform {
         self.pong.game_mode().short_label().to_string()
     }
 
+    /// Returns the current game state as snapshot JSON.
+    #[wasm_bindgen]
+    #[must_use]
+    pub fn snapshot(&self) -> String {
+        let snapshot = PongSnapshot {
+            version: SNAPSHOT_VERSION,
+            left_score: self.pong.left_score(),
+            right_score: self.pong.right_score(),
+            game_mode: self.get_game_mode(),
+            speed: self.get_speed(),
+            ai_difficulty: self.get_ai_difficulty(),
+        };
+        serde_json::to_string(&snapshot).unwrap_or_else(|_| "{}".to_string())
+    }
+
+    /// Restores a snapshot from `snapshot()` or a crash report.
+    ///
+    /// Snapshots from older releases are upgraded first. Returns false if
+    /// the JSON isn't a valid snapshot.
+    #[wasm_bindgen(js_name = "restoreSnapshot")]
+    pub fn restore_snapshot(&mut self, snapshot_json: &str) -> bool {
+        let Some(snapshot) = PongSnapshot::from_json(snapshot_json) else {
+            return false;
+        };
+        self.set_game_mode(&snapshot.game_mode);
+        self.set_speed(snapshot.speed);
+        self.set_ai_difficulty(snapshot.ai_difficulty);
+        self.pong.left_score = snapshot.left_score;
+        self.pong.right_score = snapshot.right_score;
+        true
+    }
+
+    /// Handles a tap on the crash recovery screen.
+    ///
+    /// If the tap hits a button, clears the crash and returns a new platform
+    /// built from `config_json`, with the autosaved snapshot restored when
+    /// the player chose to keep playing. Returns `None` if nothing crashed,
+    /// the tap missed or the config is invalid, so a page can pass every
+    /// click here.
+    #[wasm_bindgen]
+    #[must_use]
+    pub fn recover(config_json: &str, x: f32, y: f32) -> Option<Self> {
+        let config: WebConfig = serde_json::from_str(config_json).ok()?;
+        let (action, snapshot) =
+            crash::take_recovery(x, y, config.width as f32, config.height as f32)?;
+        let mut platform = Self::new(config_json).ok()?;
+        if action == CrashAction::Restore {
+            if let Some(snapshot) = snapshot {
+                let _ = platform.restore_snapshot(&snapshot);
+            }
+        }
+        Some(platform)
+    }
+
     fn render_debug_info(&mut self, dt: f64) {
         let fps = if dt > 0.0 { 1.0 / dt } else { 0.0 };
         let debug_text = format!("FPS: {:.0} | Frame: {}", fps, self.timer.frame_count());
@@ -3010,7 +3467,8 @@ impl WebPlatform {
     /// Creates a platform without wasm-bindgen (for testing).
     #[must_use]
     pub fn new_for_test(config: WebConfig) -> Self {
-        let pong = PongGame::new(config.width as f32, config.height as f32, config.ai_enabled);
+        let mut pong = PongGame::new(config.width as f32, config.height as f32, config.ai_enabled);
+        pong.set_accessibility(&config.accessibility);
         let tracer = if config.debug {
             GameTracer::debug()
         } else {
@@ -3028,6 +3486,12 @@ impl WebPlatform {
             canvas_offset_x: 0.0,
             canvas_offset_y: 0.0,
             tracer,
+            clip: ClipRecorder::default(),
+            command_stats: PoolStats::default(),
+            pointer_lock_request: None,
+            motion_permission_requested: false,
+            aria: AccessibilityOverlay::new(),
+            aria_elements: Vec::new(),
         }
     }
 
@@ -3055,6 +3519,19 @@ impl WebPlatform {
         &self.pong
     }
 
+    /// Returns the commands of the last rendered frame (for testing).
+    #[must_use]
+    pub const fn last_frame(&self) -> &RenderFrame {
+        &self.render_frame
+    }
+
+    /// Bytes held by the render buffer and the game's buffers.
+    #[must_use]
+    pub fn retained_bytes(&self) -> usize {
+        self.render_frame.commands.capacity() * core::mem::size_of::<Canvas2DCommand>()
+            + self.pong.retained_bytes()
+    }
+
     /// Returns a reference to the game tracer (for testing).
     #[must_use]
     pub const fn tracer(&self) -> &GameTracer {
@@ -3257,6 +3734,27 @@ mod tests {
         assert!(!result.is_empty());
     }
 
+    #[test]
+    fn test_frame_sends_aria_overlay_on_change() {
+        let mut platform = WebPlatform::new_for_test(WebConfig::default());
+        let first: serde_json::Value = serde_json::from_str(&platform.frame(0.0, "[]")).unwrap();
+        let overlay = &first["accessibility"];
+        assert!(overlay["elements"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|el| el["role"] == "button" && el["label"] == "Demo"));
+        assert_eq!(overlay["announcements"][0], "Score, 0 to 0");
+
+        let unchanged = platform.frame(16.667, "[]");
+        assert!(!unchanged.contains("accessibility"));
+
+        platform.pong.set_left_score(1);
+        let scored: serde_json::Value =
+            serde_json::from_str(&platform.frame(33.333, "[]")).unwrap();
+        assert_eq!(scored["accessibility"]["announcements"][0], "Score, 1 to 0");
+    }
+
     #[test]
     fn test_web_platform_frame_with_input() {
         let config = WebConfig::default();
@@ -3307,6 +3805,22 @@ mod tests {
         assert!(stats.contains("frame_count"));
     }
 
+    #[test]
+    fn test_frame_reuses_command_buffer() {
+        let mut platform = WebPlatform::new_for_test(WebConfig::default());
+        for frame in 0..240 {
+            let _ = platform.frame(f64::from(frame) * 16.667, "[]");
+        }
+        let command_stats = platform.command_stats;
+        assert_eq!(command_stats.frames, 240);
+        // Only warm-up growth, not an allocation per frame
+        assert!(command_stats.allocations_per_frame() < 0.05);
+        assert!(!platform.render_frame.commands.is_empty());
+
+        let stats: serde_json::Value = serde_json::from_str(&platform.get_stats()).unwrap();
+        assert_eq!(stats["render_allocations"], command_stats.allocations);
+    }
+
     #[test]
     fn test_web_platform_reset_timer() {
         let config = WebConfig::default();
@@ -3320,6 +3834,18 @@ mod tests {
         assert_eq!(platform.timer().frame_count(), 0);
     }
 
+    #[test]
+    fn test_web_config_accessibility() {
+        let config = WebConfig::from_json(r#"{"accessibility":{"reduced_motion":true}}"#).unwrap();
+        assert!(config.accessibility.reduced_motion);
+        assert!(!WebConfig::default().accessibility.reduced_motion);
+
+        let mut platform = WebPlatform::new_for_test(config);
+        let _ = platform.frame(0.0, "[]");
+        let _ = platform.frame(16.667, "[]");
+        assert!(platform.config().accessibility.reduced_motion);
+    }
+
     #[test]
     fn test_web_platform_debug_mode() {
         let config = WebConfig {
@@ -3421,6 +3947,7 @@ mod tests {
             }],
             audio_events: vec![],
             actions: vec![],
+            accessibility: None,
             debug_info: None,
         };
 
@@ -3436,6 +3963,7 @@ mod tests {
             commands: vec![],
             audio_events: vec![],
             actions: vec![],
+            accessibility: None,
             debug_info: Some(DebugInfo {
                 dt_ms: 16.667,
                 fps: 60.0,
@@ -3450,6 +3978,8 @@ mod tests {
                 trace_buffer_usage: Some("100/3600".to_string()),
                 trace_inputs: Some(42),
                 trace_dropped: Some(0),
+                render_allocations: Some(1),
+                audio_allocations: Some(0),
             }),
         };
 
@@ -3464,6 +3994,7 @@ mod tests {
             commands: vec![],
             audio_events: vec![AudioEvent::GameStart { volume: 0.7 }],
             actions: vec![],
+            accessibility: None,
             debug_info: None,
         };
 
@@ -3488,6 +4019,8 @@ mod tests {
             trace_buffer_usage: None,
             trace_inputs: None,
             trace_dropped: None,
+            render_allocations: None,
+            audio_allocations: None,
         };
 
         let json = serde_json::to_string(&info).unwrap();
@@ -4287,12 +4820,89 @@ mod tests {
         assert!(exit_json.contains("ExitFullscreen"));
     }
 
+    #[test]
+    fn test_clip_recording_requests_frames() {
+        let mut platform = WebPlatform::new_for_test(WebConfig::default());
+        let _ = platform.frame(0.0, "[]");
+        assert!(!platform.frame(16.0, "[]").contains("CaptureClipFrame"));
+
+        platform.start_clip_recording();
+        assert!(platform.frame(33.0, "[]").contains("CaptureClipFrame"));
+        assert!(platform.push_clip_frame(2, 2, &[255; 16]));
+        assert!(!platform.push_clip_frame(2, 2, &[255; 3]));
+        assert!(platform.clip_apng().starts_with(&[0x89, b'P', b'N', b'G']));
+
+        platform.stop_clip_recording();
+        assert!(!platform.frame(300.0, "[]").contains("CaptureClipFrame"));
+        assert!(WebPlatform::capture_frame(1, 1, &[0; 4]).starts_with(&[0x89]));
+        assert!(WebPlatform::capture_frame(1, 1, &[0; 3]).is_empty());
+    }
+
+    #[test]
+    fn test_pointer_lock_requests() {
+        let mut touch_only = WebPlatform::new_for_test(WebConfig::default());
+        assert!(!touch_only.supports_pointer_lock());
+        assert!(!touch_only.request_pointer_lock());
+        assert!(!touch_only.frame(0.0, "[]").contains("PointerLock"));
+
+        let config: WebConfig =
+            WebConfig::from_json(r#"{"pointer": {"pointer_lock": true}}"#).unwrap();
+        let mut platform = WebPlatform::new_for_test(config);
+        assert!(platform.request_pointer_lock());
+        let output = platform.frame(0.0, "[]");
+        assert!(output.contains("RequestPointerLock"));
+        assert!(!platform.frame(16.0, "[]").contains("RequestPointerLock"));
+
+        let locked =
+            r#"[{"event_type":"PointerLockChange","timestamp":20,"data":{"locked":true}}]"#;
+        let _ = platform.frame(33.0, locked);
+        assert!(platform.is_pointer_locked());
+        platform.release_pointer_lock();
+        assert!(platform.frame(50.0, "[]").contains("ExitPointerLock"));
+    }
+
+    #[test]
+    fn test_motion_permission_request() {
+        let mut platform = WebPlatform::new_for_test(WebConfig::default());
+        platform.request_motion_permission();
+        assert!(platform
+            .frame(0.0, "[]")
+            .contains("RequestMotionPermission"));
+        assert!(!platform
+            .frame(16.0, "[]")
+            .contains("RequestMotionPermission"));
+
+        let granted =
+            r#"[{"event_type":"MotionPermission","timestamp":20,"data":{"granted":true}}]"#;
+        let _ = platform.frame(33.0, granted);
+        platform.request_motion_permission();
+        assert!(!platform
+            .frame(50.0, "[]")
+            .contains("RequestMotionPermission"));
+    }
+
+    #[test]
+    fn test_build_info_json() {
+        let json: serde_json::Value = serde_json::from_str(&WebPlatform::get_build_info()).unwrap();
+        let subsystems = json["subsystems"].as_array().unwrap();
+        assert!(subsystems.iter().any(|name| name == "render"));
+        assert_eq!(
+            subsystems.iter().any(|name| name == "audio"),
+            cfg!(feature = "audio")
+        );
+        assert_eq!(
+            subsystems.iter().any(|name| name == "yaml"),
+            cfg!(feature = "yaml")
+        );
+    }
+
     #[test]
     fn test_frame_output_with_actions() {
         let output = FrameOutput {
             commands: vec![],
             audio_events: vec![],
             actions: vec![JsAction::DownloadAiModel],
+            accessibility: None,
             debug_info: None,
         };
 
@@ -4719,4 +5329,58 @@ mod tests {
         assert!(hud.download.width > 0.0);
         assert!(hud.download.height > 0.0);
     }
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let mut platform = WebPlatform::new_for_test(WebConfig::default());
+        platform.set_game_mode("2p");
+        platform.set_speed(10);
+        platform.pong.left_score = 4;
+        let snapshot = platform.snapshot();
+
+        let mut restored = WebPlatform::new_for_test(WebConfig::default());
+        assert!(restored.restore_snapshot(&snapshot));
+        assert_eq!(restored.get_game_mode(), "2P");
+        assert_eq!(restored.get_speed(), 10);
+        assert_eq!(restored.pong.left_score(), 4);
+        assert!(!restored.restore_snapshot("not json"));
+    }
+
+    #[test]
+    fn test_recover_after_crash() {
+        let config = r#"{"width":800,"height":600}"#;
+        crash::note_crash("boom", None);
+        assert!(WebPlatform::recover(config, 0.0, 0.0).is_none());
+        assert!(crash::is_crashed());
+
+        // Restart covers this point whether or not a snapshot was autosaved
+        let platform = WebPlatform::recover(config, 450.0, 440.0).unwrap();
+        assert_eq!(platform.config.width, 800);
+        assert!(!crash::is_crashed());
+        assert!(WebPlatform::recover(config, 450.0, 440.0).is_none());
+    }
+
+    #[test]
+    fn test_snapshot_loads_every_historical_version() {
+        // Version 0: autosaves written before snapshots carried a version
+        let v0 = r#"{"left_score":3,"right_score":5,"game_mode":"1P","speed":5,"ai_difficulty":7}"#;
+        let snapshot = PongSnapshot::from_json(v0).unwrap();
+        assert_eq!(snapshot.version, SNAPSHOT_VERSION);
+        assert_eq!((snapshot.left_score, snapshot.right_score), (3, 5));
+
+        let v1 = r#"{"version":1,"left_score":1,"right_score":0,"game_mode":"2P","speed":1,"ai_difficulty":0}"#;
+        assert_eq!(PongSnapshot::from_json(v1).unwrap().game_mode, "2P");
+
+        let mut platform = WebPlatform::new_for_test(WebConfig::default());
+        assert!(platform.restore_snapshot(v0));
+        assert_eq!(platform.pong.right_score(), 5);
+        assert_eq!(platform.get_ai_difficulty(), 7);
+
+        let future = v1.replace("\"version\":1", "\"version\":2");
+        assert!(PongSnapshot::from_json(&future).is_none());
+        let dry_run = PongSnapshot::migrations()
+            .dry_run(&serde_json::from_str(v0).unwrap(), 0)
+            .unwrap();
+        assert_eq!(dry_run.steps, vec!["add a version tag"]);
+    }
 }
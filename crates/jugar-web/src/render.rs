@@ -8,91 +8,8 @@
 
 use serde::{Deserialize, Serialize};
 
-/// A color represented as RGBA components (0.0 to 1.0).
-#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
-pub struct Color {
-    /// Red component (0.0 to 1.0)
-    pub r: f32,
-    /// Green component (0.0 to 1.0)
-    pub g: f32,
-    /// Blue component (0.0 to 1.0)
-    pub b: f32,
-    /// Alpha component (0.0 to 1.0)
-    pub a: f32,
-}
-
-impl Color {
-    /// Creates a new color from RGBA components.
-    #[must_use]
-    pub const fn new(r: f32, g: f32, b: f32, a: f32) -> Self {
-        Self { r, g, b, a }
-    }
-
-    /// Creates a color from RGBA array.
-    #[must_use]
-    pub const fn from_array(rgba: [f32; 4]) -> Self {
-        Self {
-            r: rgba[0],
-            g: rgba[1],
-            b: rgba[2],
-            a: rgba[3],
-        }
-    }
-
-    /// Converts to RGBA array.
-    #[must_use]
-    pub const fn to_array(self) -> [f32; 4] {
-        [self.r, self.g, self.b, self.a]
-    }
-
-    /// Converts to CSS rgba() string.
-    #[must_use]
-    pub fn to_css_rgba(self) -> String {
-        format!(
-            "rgba({}, {}, {}, {})",
-            (self.r * 255.0) as u8,
-            (self.g * 255.0) as u8,
-            (self.b * 255.0) as u8,
-            self.a
-        )
-    }
-
-    /// Black color.
-    pub const BLACK: Self = Self::new(0.0, 0.0, 0.0, 1.0);
-
-    /// White color.
-    pub const WHITE: Self = Self::new(1.0, 1.0, 1.0, 1.0);
-
-    /// Transparent color.
-    pub const TRANSPARENT: Self = Self::new(0.0, 0.0, 0.0, 0.0);
-
-    /// Red color.
-    pub const RED: Self = Self::new(1.0, 0.0, 0.0, 1.0);
-
-    /// Green color.
-    pub const GREEN: Self = Self::new(0.0, 1.0, 0.0, 1.0);
-
-    /// Blue color.
-    pub const BLUE: Self = Self::new(0.0, 0.0, 1.0, 1.0);
-}
-
-impl Default for Color {
-    fn default() -> Self {
-        Self::BLACK
-    }
-}
-
-impl From<[f32; 4]> for Color {
-    fn from(rgba: [f32; 4]) -> Self {
-        Self::from_array(rgba)
-    }
-}
-
-impl From<Color> for [f32; 4] {
-    fn from(color: Color) -> Self {
-        color.to_array()
-    }
-}
+/// RGBA color shared with jugar-render.
+pub use jugar_render::Color;
 
 /// Text alignment options for Canvas2D.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -207,6 +124,26 @@ pub enum Canvas2DCommand {
         line_width: f32,
     },
 
+    /// Fill a closed polygon.
+    FillPolygon {
+        /// Vertices as [x, y] pairs
+        points: Vec<[f32; 2]>,
+        /// Fill color
+        color: Color,
+    },
+
+    /// Stroke connected line segments.
+    StrokePolyline {
+        /// Points as [x, y] pairs
+        points: Vec<[f32; 2]>,
+        /// Stroke color
+        color: Color,
+        /// Line width
+        line_width: f32,
+        /// Connect the last point back to the first
+        closed: bool,
+    },
+
     /// Draw text.
     FillText {
         /// Text content
@@ -261,12 +198,67 @@ pub enum Canvas2DCommand {
         dst_height: f32,
     },
 
+    /// Draw a sprite with tint, flash-to-white, flips and rotation.
+    ///
+    /// Flips and rotation apply around the pivot, so the renderer translates
+    /// to it, rotates, scales by -1 on flipped axes and draws at the offset.
+    DrawSprite {
+        /// Texture ID
+        texture_id: u32,
+        /// Source region in texture as [x, y, width, height] (whole texture if absent)
+        source: Option<[f32; 4]>,
+        /// Destination X (top-left before rotation)
+        x: f32,
+        /// Destination Y (top-left before rotation)
+        y: f32,
+        /// Destination width
+        width: f32,
+        /// Destination height
+        height: f32,
+        /// Tint color (alpha is the sprite opacity)
+        color: Color,
+        /// Blend toward white (0.0 to 1.0)
+        flash: f32,
+        /// Mirror horizontally around the pivot
+        flip_x: bool,
+        /// Mirror vertically around the pivot
+        flip_y: bool,
+        /// Rotation around the pivot in radians
+        rotation: f32,
+        /// Pivot X as a fraction of the width
+        pivot_x: f32,
+        /// Pivot Y as a fraction of the height
+        pivot_y: f32,
+    },
+
     /// Save the current canvas state.
     Save,
 
     /// Restore the previously saved canvas state.
     Restore,
 
+    /// Save the canvas state and clip drawing to a rectangle.
+    ///
+    /// Undo with [`Canvas2DCommand::Restore`].
+    ClipRect {
+        /// X position
+        x: f32,
+        /// Y position
+        y: f32,
+        /// Width
+        width: f32,
+        /// Height
+        height: f32,
+    },
+
+    /// Save the canvas state and multiply global alpha.
+    ///
+    /// Undo with [`Canvas2DCommand::Restore`].
+    PushAlpha {
+        /// Alpha multiplier (0.0 to 1.0)
+        alpha: f32,
+    },
+
     /// Translate the canvas origin.
     Translate {
         /// X translation
@@ -402,6 +394,27 @@ impl RenderFrame {
         });
     }
 
+    /// Fills a closed polygon.
+    pub fn fill_polygon(&mut self, points: Vec<[f32; 2]>, color: Color) {
+        self.push(Canvas2DCommand::FillPolygon { points, color });
+    }
+
+    /// Strokes connected line segments.
+    pub fn stroke_polyline(
+        &mut self,
+        points: Vec<[f32; 2]>,
+        color: Color,
+        line_width: f32,
+        closed: bool,
+    ) {
+        self.push(Canvas2DCommand::StrokePolyline {
+            points,
+            color,
+            line_width,
+            closed,
+        });
+    }
+
     /// Strokes a rectangle outline.
     pub fn stroke_rect(
         &mut self,
@@ -472,8 +485,125 @@ pub fn convert_render_command(cmd: &jugar_render::RenderCommand) -> Option<Canva
             height: rect.height,
             color: Color::from_array(*color),
         }),
-        jugar_render::RenderCommand::DrawSprite { .. } => {
-            // Sprites require texture management which is handled separately
+        jugar_render::RenderCommand::DrawSprite {
+            texture_id,
+            position,
+            size,
+            source,
+            color,
+            params,
+            ..
+        } => Some(Canvas2DCommand::DrawSprite {
+            texture_id: *texture_id,
+            source: source.map(|r| [r.x, r.y, r.width, r.height]),
+            x: position.x,
+            y: position.y,
+            width: size.x,
+            height: size.y,
+            color: Color::from_array(*color),
+            flash: params.flash,
+            flip_x: params.flip_x,
+            flip_y: params.flip_y,
+            rotation: params.rotation,
+            pivot_x: params.pivot.x,
+            pivot_y: params.pivot.y,
+        }),
+        jugar_render::RenderCommand::DrawCircle {
+            center,
+            radius,
+            color,
+            outline,
+        } => Some(outline.map_or_else(
+            || Canvas2DCommand::FillCircle {
+                x: center.x,
+                y: center.y,
+                radius: *radius,
+                color: Color::from_array(*color),
+            },
+            |line_width| Canvas2DCommand::StrokeCircle {
+                x: center.x,
+                y: center.y,
+                radius: *radius,
+                color: Color::from_array(*color),
+                line_width,
+            },
+        )),
+        jugar_render::RenderCommand::DrawLine {
+            start,
+            end,
+            width,
+            color,
+        } => Some(Canvas2DCommand::Line {
+            x1: start.x,
+            y1: start.y,
+            x2: end.x,
+            y2: end.y,
+            color: Color::from_array(*color),
+            line_width: *width,
+        }),
+        jugar_render::RenderCommand::DrawPolygon {
+            points,
+            color,
+            outline,
+        } => {
+            let points = points.iter().map(|p| [p.x, p.y]).collect();
+            let color = Color::from_array(*color);
+            Some(match outline {
+                Some(line_width) => Canvas2DCommand::StrokePolyline {
+                    points,
+                    color,
+                    line_width: *line_width,
+                    closed: true,
+                },
+                None => Canvas2DCommand::FillPolygon { points, color },
+            })
+        }
+        jugar_render::RenderCommand::DrawPolyline {
+            points,
+            width,
+            color,
+            closed,
+        } => Some(Canvas2DCommand::StrokePolyline {
+            points: points.iter().map(|p| [p.x, p.y]).collect(),
+            color: Color::from_array(*color),
+            line_width: *width,
+            closed: *closed,
+        }),
+        jugar_render::RenderCommand::DrawText {
+            text,
+            position,
+            size,
+            color,
+        } => Some(Canvas2DCommand::FillText {
+            text: text.clone(),
+            x: position.x,
+            y: position.y,
+            font: format!("{size}px monospace"),
+            color: Color::from_array(*color),
+            align: TextAlign::Left,
+            baseline: TextBaseline::Top,
+        }),
+        jugar_render::RenderCommand::PushScissor { rect } => Some(Canvas2DCommand::ClipRect {
+            x: rect.x,
+            y: rect.y,
+            width: rect.width,
+            height: rect.height,
+        }),
+        jugar_render::RenderCommand::PopScissor | jugar_render::RenderCommand::PopOpacity => {
+            Some(Canvas2DCommand::Restore)
+        }
+        jugar_render::RenderCommand::PushOpacity { alpha } => {
+            Some(Canvas2DCommand::PushAlpha { alpha: *alpha })
+        }
+        jugar_render::RenderCommand::ScreenOverlay { color } => {
+            // Canvas2D clears with fillRect, so a translucent clear composites
+            Some(Canvas2DCommand::Clear {
+                color: Color::from_array(*color),
+            })
+        }
+        jugar_render::RenderCommand::Vignette { .. }
+        | jugar_render::RenderCommand::Filter { .. } => {
+            // Need a shader or compositor pass; not available in Canvas2D
             None
         }
     }
@@ -1025,17 +1155,156 @@ mod tests {
     }
 
     #[test]
-    fn test_convert_render_command_sprite_returns_none() {
+    fn test_convert_render_command_sprite_params() {
         use glam::Vec2;
         use jugar_core::Position;
         let cmd = jugar_render::RenderCommand::DrawSprite {
-            texture_id: 0,
-            position: Position::zero(),
-            size: Vec2::new(64.0, 64.0),
-            source: None,
+            texture_id: 3,
+            position: Position::new(10.0, 20.0),
+            size: Vec2::new(64.0, 32.0),
+            source: Some(jugar_core::Rect::new(16.0, 0.0, 16.0, 8.0)),
+            color: [1.0, 0.5, 0.5, 0.75],
+            filter: jugar_render::TextureFilter::Linear,
+            params: jugar_render::SpriteParams::new()
+                .with_flash(0.8)
+                .flipped(true, false)
+                .with_rotation(1.5)
+                .with_pivot(Vec2::new(0.5, 1.0)),
+        };
+        match convert_render_command(&cmd) {
+            Some(Canvas2DCommand::DrawSprite {
+                texture_id,
+                source,
+                x,
+                width,
+                color,
+                flash,
+                flip_x,
+                flip_y,
+                rotation,
+                pivot_y,
+                ..
+            }) => {
+                assert_eq!(texture_id, 3);
+                assert_eq!(source, Some([16.0, 0.0, 16.0, 8.0]));
+                assert!((x - 10.0).abs() < f32::EPSILON);
+                assert!((width - 64.0).abs() < f32::EPSILON);
+                assert!((color.a - 0.75).abs() < f32::EPSILON);
+                assert!((flash - 0.8).abs() < f32::EPSILON);
+                assert!(flip_x && !flip_y);
+                assert!((rotation - 1.5).abs() < f32::EPSILON);
+                assert!((pivot_y - 1.0).abs() < f32::EPSILON);
+            }
+            other => panic!("Expected DrawSprite, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_convert_opacity_group() {
+        assert_eq!(
+            convert_render_command(&jugar_render::RenderCommand::PushOpacity { alpha: 0.5 }),
+            Some(Canvas2DCommand::PushAlpha { alpha: 0.5 })
+        );
+        assert_eq!(
+            convert_render_command(&jugar_render::RenderCommand::PopOpacity),
+            Some(Canvas2DCommand::Restore)
+        );
+    }
+
+    #[test]
+    fn test_convert_primitives() {
+        use glam::Vec2;
+        let circle = jugar_render::RenderCommand::DrawCircle {
+            center: Vec2::new(10.0, 20.0),
+            radius: 5.0,
             color: [1.0, 1.0, 1.0, 1.0],
+            outline: Some(2.0),
+        };
+        assert!(matches!(
+            convert_render_command(&circle),
+            Some(Canvas2DCommand::StrokeCircle { radius, .. }) if (radius - 5.0).abs() < f32::EPSILON
+        ));
+        let line = jugar_render::RenderCommand::DrawLine {
+            start: Vec2::ZERO,
+            end: Vec2::new(3.0, 4.0),
+            width: 2.0,
+            color: [1.0, 0.0, 0.0, 1.0],
+        };
+        assert!(matches!(
+            convert_render_command(&line),
+            Some(Canvas2DCommand::Line { x2, line_width, .. })
+                if (x2 - 3.0).abs() < f32::EPSILON && (line_width - 2.0).abs() < f32::EPSILON
+        ));
+        let polygon = jugar_render::RenderCommand::DrawPolygon {
+            points: vec![Vec2::ZERO, Vec2::X, Vec2::Y],
+            color: [0.0, 1.0, 0.0, 1.0],
+            outline: None,
+        };
+        assert!(matches!(
+            convert_render_command(&polygon),
+            Some(Canvas2DCommand::FillPolygon { points, .. }) if points.len() == 3
+        ));
+        let polyline = jugar_render::RenderCommand::DrawPolyline {
+            points: vec![Vec2::ZERO, Vec2::X],
+            width: 1.0,
+            color: [0.0, 0.0, 1.0, 1.0],
+            closed: false,
+        };
+        assert!(matches!(
+            convert_render_command(&polyline),
+            Some(Canvas2DCommand::StrokePolyline { closed: false, .. })
+        ));
+    }
+
+    #[test]
+    fn test_polygon_serializes_points() {
+        let mut frame = RenderFrame::new();
+        frame.fill_polygon(vec![[0.0, 0.0], [1.0, 0.0], [0.0, 1.0]], Color::RED);
+        let json = frame.to_json().unwrap();
+        assert!(json.contains("\"type\":\"FillPolygon\""));
+        assert!(json.contains("[[0.0,0.0],[1.0,0.0],[0.0,1.0]]"));
+    }
+
+    #[test]
+    fn test_convert_draw_text() {
+        let text = jugar_render::RenderCommand::DrawText {
+            text: "Score: 3".to_string(),
+            position: glam::Vec2::new(10.0, 20.0),
+            size: 24.0,
+            color: [1.0; 4],
+        };
+        assert!(matches!(
+            convert_render_command(&text),
+            Some(Canvas2DCommand::FillText { ref font, baseline: TextBaseline::Top, .. }) if font == "24px monospace"
+        ));
+    }
+
+    #[test]
+    fn test_convert_scissor_pair() {
+        let viewport = jugar_render::Viewport::new(5120, 1440);
+        let commands = jugar_render::Compositor::new().compose(&viewport, &[], &[]);
+        let frame = convert_render_queue(&commands);
+        assert!(matches!(
+            frame.commands[0],
+            Canvas2DCommand::ClipRect { x, .. } if (x - viewport.safe_area.x).abs() < f32::EPSILON
+        ));
+        assert_eq!(frame.commands[1], Canvas2DCommand::Restore);
+    }
+
+    #[test]
+    fn test_convert_screen_effects() {
+        let overlay = jugar_render::RenderCommand::ScreenOverlay {
+            color: [0.0, 0.0, 0.0, 0.5],
+        };
+        assert!(matches!(
+            convert_render_command(&overlay),
+            Some(Canvas2DCommand::Clear { color }) if (color.a - 0.5).abs() < f32::EPSILON
+        ));
+        let vignette = jugar_render::RenderCommand::Vignette {
+            color: [0.0, 0.0, 0.0, 1.0],
+            intensity: 0.5,
         };
-        assert!(convert_render_command(&cmd).is_none());
+        assert!(convert_render_command(&vignette).is_none());
     }
 
     #[test]
@@ -1055,7 +1324,7 @@ mod tests {
     }
 
     #[test]
-    fn test_convert_render_queue_skips_sprites() {
+    fn test_convert_render_queue_keeps_sprites() {
         use glam::Vec2;
         use jugar_core::Position;
         let commands = vec![
@@ -1068,6 +1337,8 @@ mod tests {
                 size: Vec2::new(64.0, 64.0),
                 source: None,
                 color: [1.0, 1.0, 1.0, 1.0],
+                filter: jugar_render::TextureFilter::Linear,
+                params: jugar_render::SpriteParams::new(),
             },
             jugar_render::RenderCommand::DrawRect {
                 rect: jugar_core::Rect::new(0.0, 0.0, 100.0, 100.0),
@@ -1076,7 +1347,11 @@ mod tests {
         ];
 
         let frame = convert_render_queue(&commands);
-        assert_eq!(frame.len(), 2); // Sprite is skipped
+        assert_eq!(frame.len(), 3);
+        assert!(matches!(
+            frame.commands[1],
+            Canvas2DCommand::DrawSprite { .. }
+        ));
     }
 
     #[test]
@@ -10,6 +10,9 @@
 //! - **Tier 2**: WASM SIMD 128-bit
 //! - **Tier 3**: Scalar fallback
 //!
+//! Position/velocity integration uses the trueno-backed batch routines from
+//! `jugar_core::soa`, which the engine shares for its own entities.
+//!
 //! ## Usage
 //!
 //! ```rust,ignore
@@ -154,22 +157,8 @@ pub fn batch_update_positions(positions: &mut [f32], velocities: &[f32], dt: f32
         return;
     }
 
-    // Create trueno vectors for batch operation
-    let pos_vec = Vector::from_slice(positions);
-    let vel_vec = Vector::from_slice(velocities);
-
     // SIMD-accelerated: positions += velocities * dt
-    if let Ok(scaled_vel) = vel_vec.scale(dt) {
-        if let Ok(new_pos) = pos_vec.add(&scaled_vel) {
-            // Copy results back
-            let result_slice = new_pos.as_slice();
-            for (i, val) in result_slice.iter().enumerate() {
-                if i < positions.len() {
-                    positions[i] = *val;
-                }
-            }
-        }
-    }
+    jugar_core::batch_integrate(positions, velocities, dt);
 }
 
 /// SIMD-accelerated batch particle physics update.
@@ -197,38 +186,12 @@ pub fn batch_particle_update(
         return;
     }
 
-    // Create trueno vectors
-    let pos_x = Vector::from_slice(positions_x);
-    let pos_y = Vector::from_slice(positions_y);
-    let vel_x = Vector::from_slice(velocities_x);
-    let vel_y = Vector::from_slice(velocities_y);
-
     // SIMD-accelerated position update: pos += vel * dt
-    if let Ok(scaled_vx) = vel_x.scale(dt) {
-        if let Ok(new_pos_x) = pos_x.add(&scaled_vx) {
-            for (i, &val) in new_pos_x.as_slice().iter().enumerate() {
-                if i < positions_x.len() {
-                    positions_x[i] = val;
-                }
-            }
-        }
-    }
-
-    if let Ok(scaled_vy) = vel_y.scale(dt) {
-        if let Ok(new_pos_y) = pos_y.add(&scaled_vy) {
-            for (i, &val) in new_pos_y.as_slice().iter().enumerate() {
-                if i < positions_y.len() {
-                    positions_y[i] = val;
-                }
-            }
-        }
-    }
+    jugar_core::batch_integrate(positions_x, velocities_x, dt);
+    jugar_core::batch_integrate(positions_y, velocities_y, dt);
 
     // Apply gravity to Y velocities
-    let gravity_delta = gravity * dt;
-    for vy in velocities_y.iter_mut() {
-        *vy += gravity_delta;
-    }
+    jugar_core::batch_accelerate(velocities_y, gravity, dt);
 }
 
 /// Computes collision detection for a ball against multiple paddles.
@@ -132,7 +132,7 @@ impl Default for MonteCarloConfig {
 }
 
 /// Game state snapshot for failure replay
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct GameStateSnapshot {
     /// Ball X position
     pub ball_x: f64,
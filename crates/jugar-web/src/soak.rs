@@ -0,0 +1,503 @@
+//! Long-run soak testing.
+//!
+//! Leaks rarely show in a one-minute test: a score popup that is never
+//! removed or a sound that piles up every rally only hurts after a long
+//! classroom session. [`SoakRunner`] feeds a [`WebPlatform`] frames back to
+//! back with synthetic timestamps, so an hour of play takes seconds, and
+//! samples three metrics along the way:
+//!
+//! - live entities ([`PongGame::entity_count`](crate::PongGame::entity_count))
+//! - sounds still playing, from each frame's audio events
+//! - memory: WASM linear memory in the browser, retained buffer bytes natively
+//!
+//! After a warm-up every metric must level off: a peak that rises beyond the
+//! tolerance from one third of the run to the next, and again into the last
+//! third, is reported as a leak. Periodic checkpoints keep a game snapshot next to the metrics
+//! at that point, so a leak can be bisected by restoring the last checkpoint
+//! from before it started.
+//!
+//! ```
+//! use jugar_web::soak::{SoakConfig, SoakRunner};
+//! use jugar_web::{WebConfig, WebPlatform};
+//!
+//! let mut platform = WebPlatform::new_for_test(WebConfig::default());
+//! platform.set_game_mode("demo");
+//! let report = SoakRunner::new(SoakConfig::new(30.0).with_warmup(5.0)).run(&mut platform);
+//! assert!(report.passed(), "{}", report.render());
+//! ```
+
+use serde::Deserialize;
+
+use crate::audio::AudioEvent;
+use crate::platform::{GameState, WebPlatform};
+
+/// Simulated play time of a default soak run (one hour)
+pub const DEFAULT_SOAK_SECONDS: f64 = 3600.0;
+
+/// Frames per simulated second
+const SOAK_FRAME_RATE: f64 = 60.0;
+
+/// Size of one WASM linear memory page
+#[cfg(target_arch = "wasm32")]
+const WASM_PAGE_BYTES: usize = 65_536;
+
+/// A quantity that must not grow without bound
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SoakMetric {
+    /// Ball, paddles and active effects
+    Entities,
+    /// Sounds still playing
+    Sounds,
+    /// WASM memory (browser) or retained buffer bytes (native)
+    MemoryBytes,
+}
+
+impl SoakMetric {
+    /// Every metric, in report order
+    pub const ALL: [Self; 3] = [Self::Entities, Self::Sounds, Self::MemoryBytes];
+
+    /// Name used in reports
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Entities => "entities",
+            Self::Sounds => "sounds",
+            Self::MemoryBytes => "memory",
+        }
+    }
+}
+
+/// Metrics at one point of the run
+///
+/// Entity and sound counts are the peaks since the previous sample, so a
+/// short burst between samples is not missed.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SoakSample {
+    /// Frame the sample was taken after
+    pub frame: u64,
+    /// Simulated seconds since the run started
+    pub seconds: f64,
+    /// Most live entities since the previous sample
+    pub entities: usize,
+    /// Most sounds playing at once since the previous sample
+    pub sounds: usize,
+    /// Memory in bytes
+    pub memory_bytes: usize,
+}
+
+impl SoakSample {
+    /// Value of `metric` in this sample
+    #[must_use]
+    pub const fn value(&self, metric: SoakMetric) -> usize {
+        match metric {
+            SoakMetric::Entities => self.entities,
+            SoakMetric::Sounds => self.sounds,
+            SoakMetric::MemoryBytes => self.memory_bytes,
+        }
+    }
+}
+
+/// Game snapshot taken during the run, for bisecting a leak
+#[derive(Debug, Clone, PartialEq)]
+pub struct SoakCheckpoint {
+    /// Metrics when the snapshot was taken
+    pub sample: SoakSample,
+    /// [`WebPlatform::snapshot`] JSON, for [`WebPlatform::restore_snapshot`]
+    pub snapshot: String,
+}
+
+/// A metric that kept growing after the warm-up
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LeakFinding {
+    /// The growing metric
+    pub metric: SoakMetric,
+    /// Peak over the first third of the run after warm-up
+    pub baseline_peak: usize,
+    /// Peak over the last third
+    pub final_peak: usize,
+    /// Last checkpoint still within the limit, if any
+    ///
+    /// The leak started between this checkpoint and the next one.
+    pub last_good_checkpoint: Option<usize>,
+}
+
+/// How long to soak and how often to sample
+#[derive(Debug, Clone, PartialEq)]
+pub struct SoakConfig {
+    /// Simulated play time in seconds
+    pub duration_seconds: f64,
+    /// Simulated seconds between samples
+    pub sample_interval_seconds: f64,
+    /// Simulated seconds between checkpoints
+    pub checkpoint_interval_seconds: f64,
+    /// Simulated seconds ignored by the growth check while buffers fill
+    pub warmup_seconds: f64,
+    /// Growth allowed between thirds of the run (0.1 is 10%)
+    pub growth_tolerance: f64,
+}
+
+impl Default for SoakConfig {
+    fn default() -> Self {
+        Self {
+            duration_seconds: DEFAULT_SOAK_SECONDS,
+            sample_interval_seconds: 1.0,
+            checkpoint_interval_seconds: 300.0,
+            warmup_seconds: 60.0,
+            growth_tolerance: 0.1,
+        }
+    }
+}
+
+impl SoakConfig {
+    /// Soak for `duration_seconds` of simulated play
+    #[must_use]
+    pub fn new(duration_seconds: f64) -> Self {
+        Self {
+            duration_seconds,
+            ..Self::default()
+        }
+    }
+
+    /// Sets the simulated seconds between samples
+    #[must_use]
+    pub const fn with_sample_interval(mut self, seconds: f64) -> Self {
+        self.sample_interval_seconds = seconds;
+        self
+    }
+
+    /// Sets the simulated seconds between checkpoints
+    #[must_use]
+    pub const fn with_checkpoint_interval(mut self, seconds: f64) -> Self {
+        self.checkpoint_interval_seconds = seconds;
+        self
+    }
+
+    /// Sets the warm-up ignored by the growth check
+    #[must_use]
+    pub const fn with_warmup(mut self, seconds: f64) -> Self {
+        self.warmup_seconds = seconds;
+        self
+    }
+
+    /// Sets the growth allowed between thirds of the run
+    #[must_use]
+    pub const fn with_growth_tolerance(mut self, tolerance: f64) -> Self {
+        self.growth_tolerance = tolerance;
+        self
+    }
+
+    /// Frames between events that happen every `seconds` (at least one)
+    fn frames(seconds: f64) -> u64 {
+        ((seconds * SOAK_FRAME_RATE).round() as u64).max(1)
+    }
+}
+
+/// The part of a frame's output the soak runner reads
+#[derive(Deserialize)]
+struct FrameAudio {
+    #[serde(default)]
+    audio_events: Vec<AudioEvent>,
+}
+
+/// Runs a platform for a long stretch of simulated time
+#[derive(Debug, Clone, Default)]
+pub struct SoakRunner {
+    config: SoakConfig,
+}
+
+impl SoakRunner {
+    /// Runner for `config`
+    #[must_use]
+    pub const fn new(config: SoakConfig) -> Self {
+        Self { config }
+    }
+
+    /// Plays `platform` for the configured time
+    ///
+    /// Use demo mode (AI against AI) so the game keeps rallying by itself.
+    /// A finished match is restarted with Space so play goes on for the
+    /// whole run.
+    pub fn run(&self, platform: &mut WebPlatform) -> SoakReport {
+        let frame_ms = 1000.0 / SOAK_FRAME_RATE;
+        let total_frames = SoakConfig::frames(self.config.duration_seconds);
+        let sample_every = SoakConfig::frames(self.config.sample_interval_seconds);
+        // Checkpoints land on sampled frames so each has its metrics
+        let checkpoint_every = SoakConfig::frames(self.config.checkpoint_interval_seconds)
+            .div_ceil(sample_every)
+            * sample_every;
+
+        let mut samples = Vec::new();
+        let mut checkpoints = Vec::new();
+        // End times of the sounds still playing, in simulated seconds
+        let mut playing: Vec<f64> = Vec::new();
+        let mut peak = SoakSample::default();
+        let mut space_down = false;
+
+        for frame in 0..total_frames {
+            let timestamp = frame as f64 * frame_ms;
+            let seconds = timestamp / 1000.0;
+            let events = if space_down {
+                space_down = false;
+                space_event("KeyUp", timestamp)
+            } else if platform.pong().state() == GameState::GameOver {
+                space_down = true;
+                space_event("KeyDown", timestamp)
+            } else {
+                String::from("[]")
+            };
+            let output = platform.frame(timestamp, &events);
+
+            playing.retain(|&end| end > seconds);
+            if let Ok(audio) = serde_json::from_str::<FrameAudio>(&output) {
+                playing.extend(
+                    audio
+                        .audio_events
+                        .iter()
+                        .map(|event| seconds + f64::from(event.duration())),
+                );
+            }
+            peak.entities = peak.entities.max(platform.pong().entity_count());
+            peak.sounds = peak.sounds.max(playing.len());
+
+            let last = frame + 1 == total_frames;
+            if (frame + 1) % sample_every == 0 || last {
+                let sample = SoakSample {
+                    frame,
+                    seconds: seconds + frame_ms / 1000.0,
+                    memory_bytes: memory_bytes(platform),
+                    ..peak
+                };
+                samples.push(sample);
+                peak = SoakSample::default();
+                if (frame + 1) % checkpoint_every == 0 || last {
+                    checkpoints.push(SoakCheckpoint {
+                        sample,
+                        snapshot: platform.snapshot(),
+                    });
+                }
+            }
+        }
+
+        let leaks = SoakMetric::ALL
+            .into_iter()
+            .filter_map(|metric| find_leak(metric, &samples, &checkpoints, &self.config))
+            .collect();
+        SoakReport {
+            samples,
+            checkpoints,
+            leaks,
+        }
+    }
+}
+
+/// Everything a soak run measured
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SoakReport {
+    /// Samples in run order
+    pub samples: Vec<SoakSample>,
+    /// Checkpoints in run order
+    pub checkpoints: Vec<SoakCheckpoint>,
+    /// Metrics that kept growing
+    pub leaks: Vec<LeakFinding>,
+}
+
+impl SoakReport {
+    /// Returns true if no metric kept growing
+    #[must_use]
+    pub fn passed(&self) -> bool {
+        self.leaks.is_empty()
+    }
+
+    /// Plain-text summary with a bisect hint for each leak
+    #[must_use]
+    pub fn render(&self) -> String {
+        let seconds = self.samples.last().map_or(0.0, |sample| sample.seconds);
+        let mut lines = vec![format!(
+            "Soak: {seconds:.0}s simulated, {} samples, {} checkpoints",
+            self.samples.len(),
+            self.checkpoints.len()
+        )];
+        for metric in SoakMetric::ALL {
+            let values = self.samples.iter().map(|sample| sample.value(metric));
+            lines.push(format!(
+                "  {:<8}  start {}  peak {}  end {}",
+                metric.name(),
+                self.samples.first().map_or(0, |s| s.value(metric)),
+                values.max().unwrap_or(0),
+                self.samples.last().map_or(0, |s| s.value(metric)),
+            ));
+        }
+        for leak in &self.leaks {
+            let bisect = leak.last_good_checkpoint.map_or_else(
+                || "growing since the first checkpoint".to_string(),
+                |index| {
+                    format!(
+                        "restore checkpoint {index} ({:.0}s) to bisect",
+                        self.checkpoints[index].sample.seconds
+                    )
+                },
+            );
+            lines.push(format!(
+                "LEAK {}: peak {} -> {}; {bisect}",
+                leak.metric.name(),
+                leak.baseline_peak,
+                leak.final_peak
+            ));
+        }
+        if self.passed() {
+            lines.push("PASS no unbounded growth".to_string());
+        }
+        lines.join("\n") + "\n"
+    }
+}
+
+/// Input events pressing or releasing Space
+fn space_event(event_type: &str, timestamp: f64) -> String {
+    format!(r#"[{{"event_type":"{event_type}","timestamp":{timestamp},"data":{{"key":"Space"}}}}]"#)
+}
+
+/// Looks for a peak that keeps rising over the thirds of the run after
+/// warm-up
+///
+/// Growing into the second third and again into the last is a leak; a
+/// single step (a buffer doubling once) is not.
+fn find_leak(
+    metric: SoakMetric,
+    samples: &[SoakSample],
+    checkpoints: &[SoakCheckpoint],
+    config: &SoakConfig,
+) -> Option<LeakFinding> {
+    let settled: Vec<usize> = samples
+        .iter()
+        .filter(|sample| sample.seconds > config.warmup_seconds)
+        .map(|sample| sample.value(metric))
+        .collect();
+    let third = settled.len() / 3;
+    if third == 0 {
+        return None;
+    }
+    let peak = |values: &[usize]| values.iter().copied().max().unwrap_or(0);
+    // Small counts may wobble by one without leaking
+    let limit =
+        |peak: usize| (peak as f64 * (1.0 + config.growth_tolerance)).max(peak as f64 + 1.0);
+    let baseline_peak = peak(&settled[..third]);
+    let middle_peak = peak(&settled[third..2 * third]);
+    let final_peak = peak(&settled[2 * third..]);
+    if middle_peak as f64 <= limit(baseline_peak) || final_peak as f64 <= limit(middle_peak) {
+        return None;
+    }
+    let last_good_checkpoint = checkpoints
+        .iter()
+        .take_while(|checkpoint| checkpoint.sample.value(metric) as f64 <= limit(baseline_peak))
+        .count()
+        .checked_sub(1);
+    Some(LeakFinding {
+        metric,
+        baseline_peak,
+        final_peak,
+        last_good_checkpoint,
+    })
+}
+
+/// Current memory of the module
+#[cfg(target_arch = "wasm32")]
+fn memory_bytes(_platform: &WebPlatform) -> usize {
+    core::arch::wasm32::memory_size(0) * WASM_PAGE_BYTES
+}
+
+/// Bytes the platform's buffers hold (linear memory is not measurable natively)
+#[cfg(not(target_arch = "wasm32"))]
+fn memory_bytes(platform: &WebPlatform) -> usize {
+    platform.retained_bytes()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::platform::WebConfig;
+
+    fn sample(seconds: f64, entities: usize) -> SoakSample {
+        SoakSample {
+            seconds,
+            entities,
+            ..SoakSample::default()
+        }
+    }
+
+    #[test]
+    fn test_demo_soak_levels_off() {
+        let mut platform = WebPlatform::new_for_test(WebConfig::default());
+        platform.set_game_mode("demo");
+        let config = SoakConfig::new(180.0)
+            .with_warmup(20.0)
+            .with_checkpoint_interval(60.0);
+        let report = SoakRunner::new(config).run(&mut platform);
+
+        assert!(report.passed(), "{}", report.render());
+        assert_eq!(report.samples.len(), 180);
+        assert_eq!(report.checkpoints.len(), 3);
+        assert!((report.samples[179].seconds - 180.0).abs() < 1e-6);
+        assert!(report.samples.iter().all(|s| s.entities >= 3));
+        assert!(report.samples.iter().any(|s| s.sounds > 0));
+        assert!(report.samples.iter().all(|s| s.memory_bytes > 0));
+
+        let checkpoint = &report.checkpoints[1].snapshot;
+        let mut restored = WebPlatform::new_for_test(WebConfig::default());
+        assert!(restored.restore_snapshot(checkpoint));
+        assert!(report.render().contains("PASS no unbounded growth"));
+    }
+
+    #[test]
+    fn test_growth_points_to_last_good_checkpoint() {
+        let config = SoakConfig::new(100.0).with_warmup(10.0);
+        // Steady at 5 entities, then one more every second from 50s
+        let samples: Vec<_> = (1..=100)
+            .map(|t| sample(f64::from(t), 5 + (t.max(50) - 50) as usize))
+            .collect();
+        let checkpoints: Vec<_> = samples
+            .iter()
+            .skip(19)
+            .step_by(20)
+            .map(|&sample| SoakCheckpoint {
+                sample,
+                snapshot: String::new(),
+            })
+            .collect();
+
+        let leak = find_leak(SoakMetric::Entities, &samples, &checkpoints, &config).unwrap();
+        assert_eq!(leak.baseline_peak, 5);
+        assert_eq!(leak.final_peak, 55);
+        // Checkpoints at 20s and 40s are fine; 60s already has 15 entities
+        assert_eq!(leak.last_good_checkpoint, Some(1));
+        assert!(find_leak(SoakMetric::Sounds, &samples, &checkpoints, &config).is_none());
+
+        let report = SoakReport {
+            samples,
+            checkpoints,
+            leaks: vec![leak],
+        };
+        assert!(!report.passed());
+        assert!(report
+            .render()
+            .contains("LEAK entities: peak 5 -> 55; restore checkpoint 1 (40s) to bisect"));
+    }
+
+    #[test]
+    fn test_warmup_wobble_and_single_step_are_not_leaks() {
+        let config = SoakConfig::new(100.0).with_warmup(10.0);
+        // Fills up during warm-up, then wobbles between 2 and 3
+        let wobble = |t: u32| 2 + t as usize % 2;
+        let samples: Vec<_> = (1..=100)
+            .map(|t| sample(f64::from(t), if t <= 10 { 40 } else { wobble(t) }))
+            .collect();
+        assert!(find_leak(SoakMetric::Entities, &samples, &[], &config).is_none());
+        assert!(find_leak(SoakMetric::Entities, &samples[..11], &[], &config).is_none());
+
+        // A buffer that doubles once and then stays put
+        let samples: Vec<_> = (1..=100)
+            .map(|t| sample(f64::from(t), if t < 50 { wobble(t) } else { 20 }))
+            .collect();
+        assert!(find_leak(SoakMetric::Entities, &samples, &[], &config).is_none());
+    }
+}
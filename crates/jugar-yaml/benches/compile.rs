@@ -0,0 +1,38 @@
+//! Benchmarks for compiling representative YAML games.
+
+#![allow(missing_docs, unused_results)]
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use jugar_yaml::YamlCompiler;
+
+const FIRST_GAME: &str = r"
+character: bunny
+move: arrows
+background: grass
+when_touch:
+  target: star
+  sound: twinkle
+  score: 1
+";
+
+fn bench_compile(c: &mut Criterion) {
+    let mut group = c.benchmark_group("yaml_compile");
+    let compiler = YamlCompiler::new();
+    let games = [
+        ("first-game", FIRST_GAME),
+        ("catch-stars", include_str!("../templates/catch-stars.yaml")),
+        ("pong", include_str!("../templates/pong.yaml")),
+        ("maze", include_str!("../templates/maze.yaml")),
+    ];
+
+    for (name, yaml) in games {
+        group.bench_with_input(BenchmarkId::new("game", name), yaml, |b, yaml| {
+            b.iter(|| black_box(compiler.compile(black_box(yaml))));
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_compile);
+criterion_main!(benches);
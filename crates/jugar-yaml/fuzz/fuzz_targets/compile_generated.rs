@@ -0,0 +1,12 @@
+//! Games built from the vocabulary, within or just outside each level
+
+#![no_main]
+
+use jugar_yaml::fuzzing::{check_game, GeneratedGame};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|game: GeneratedGame| {
+    if let Err(violation) = check_game(&game) {
+        panic!("{violation}");
+    }
+});
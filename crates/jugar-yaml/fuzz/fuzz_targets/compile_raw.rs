@@ -0,0 +1,12 @@
+//! Arbitrary text, to find parser and normalizer panics
+
+#![no_main]
+
+use jugar_yaml::fuzzing::check_invariants;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|yaml: &str| {
+    if let Err(violation) = check_invariants(yaml) {
+        panic!("{violation}");
+    }
+});
@@ -3,6 +3,7 @@
 //! Per spec Section 6.3 and 11.3: WCAG 2.1 AA compliance validation.
 
 use crate::error::YamlError;
+use crate::safety::MAX_FLASH_RATE_HZ;
 
 /// Accessibility validation results
 #[derive(Debug, Clone, Default)]
@@ -97,6 +98,8 @@ pub enum AccessibilityCode {
     InconsistentNav,
     /// Small touch targets
     SmallTouchTargets,
+    /// Sounds without captions
+    MissingCaptions,
 }
 
 impl AccessibilityCode {
@@ -114,6 +117,7 @@ impl AccessibilityCode {
             Self::MissingAltText => "WCAG 1.1.1",
             Self::InconsistentNav => "WCAG 3.2.3",
             Self::SmallTouchTargets => "WCAG 2.5.5",
+            Self::MissingCaptions => "WCAG 1.2.2",
         }
     }
 }
@@ -178,6 +182,31 @@ impl AccessibilityValidator {
             });
         }
 
+        // Check contrast (0.0 means no colors were measured)
+        if game.min_contrast_ratio > 0.0 && game.min_contrast_ratio < self.min_contrast_ratio {
+            report.add_warning(AccessibilityWarning {
+                code: AccessibilityCode::LowContrast,
+                description: format!(
+                    "Colors should have a contrast of at least {:.1}:1 (found {:.1}:1)",
+                    self.min_contrast_ratio, game.min_contrast_ratio
+                ),
+                suggestion: "Pick a color that stands out more from the background".to_string(),
+            });
+        }
+
+        // Check flashing
+        if game.max_flash_rate > MAX_FLASH_RATE_HZ {
+            report.add_issue(AccessibilityIssue {
+                code: AccessibilityCode::FlashingContent,
+                description: format!(
+                    "The screen flashes {:.0} times a second (at most {MAX_FLASH_RATE_HZ:.0} is safe)",
+                    game.max_flash_rate
+                ),
+                wcag_ref: Some("WCAG 2.3.1".to_string()),
+                suggestion: "Slow down blinking so it happens less often".to_string(),
+            });
+        }
+
         // Check audio alternatives
         if game.has_audio_only_cues && !game.has_visual_alternatives {
             report.add_issue(AccessibilityIssue {
@@ -188,6 +217,20 @@ impl AccessibilityValidator {
             });
         }
 
+        // Check captions (every YAML game is a kid game, so sounds that
+        // carry meaning must be readable too)
+        if game.uncaptioned_sounds > 0 {
+            report.add_warning(AccessibilityWarning {
+                code: AccessibilityCode::MissingCaptions,
+                description: format!(
+                    "{} sound(s) play without a caption",
+                    game.uncaptioned_sounds
+                ),
+                suggestion: "Add 'caption:' next to each 'sound:' so players who can't hear know what happened"
+                    .to_string(),
+            });
+        }
+
         // Check time limits
         if game.has_time_limit && !game.time_limit_extendable {
             report.add_warning(AccessibilityWarning {
@@ -228,12 +271,18 @@ pub struct GameAccessibilityInfo {
     pub has_audio_only_cues: bool,
     /// Has visual alternatives for audio
     pub has_visual_alternatives: bool,
+    /// Number of sound effects played without a caption
+    pub uncaptioned_sounds: usize,
+    /// Color words from `color:` (or `colour:`) keys, in document order
+    pub declared_colors: Vec<String>,
     /// Has time limit
     pub has_time_limit: bool,
     /// Time limit is extendable
     pub time_limit_extendable: bool,
     /// Color contrast ratio (lowest found)
     pub min_contrast_ratio: f32,
+    /// Most full-screen flashes within one second (0.0 if not measured)
+    pub max_flash_rate: f32,
 }
 
 impl Default for GameAccessibilityInfo {
@@ -245,9 +294,12 @@ impl Default for GameAccessibilityInfo {
             min_touch_target: 44, // WCAG 2.5.5 default
             has_audio_only_cues: false,
             has_visual_alternatives: false,
+            uncaptioned_sounds: 0,
+            declared_colors: Vec::new(),
             has_time_limit: false,
             time_limit_extendable: false,
             min_contrast_ratio: 0.0,
+            max_flash_rate: 0.0,
         }
     }
 }
@@ -285,6 +337,10 @@ impl GameAccessibilityInfo {
             info.has_visual_alternatives = doc.get("background").is_some();
         }
 
+        // Check sound effects for captions
+        info.uncaptioned_sounds = count_uncaptioned_sounds(&doc);
+        collect_colors(&doc, &mut info.declared_colors);
+
         // Check characters for movement
         if let Some(chars) = doc.get("characters") {
             if let Some(mapping) = chars.as_mapping() {
@@ -308,6 +364,44 @@ impl GameAccessibilityInfo {
     }
 }
 
+/// Counts mappings that play a `sound` without a sibling `caption`
+fn count_uncaptioned_sounds(value: &serde_yaml::Value) -> usize {
+    match value {
+        serde_yaml::Value::Mapping(map) => {
+            let own = usize::from(map.contains_key("sound") && !map.contains_key("caption"));
+            own + map.values().map(count_uncaptioned_sounds).sum::<usize>()
+        }
+        serde_yaml::Value::Sequence(items) => items.iter().map(count_uncaptioned_sounds).sum(),
+        _ => 0,
+    }
+}
+
+/// Collects the values of `color` and `colour` keys, skipping repeats
+fn collect_colors(value: &serde_yaml::Value, colors: &mut Vec<String>) {
+    match value {
+        serde_yaml::Value::Mapping(map) => {
+            for (key, value) in map {
+                let is_color = matches!(key.as_str(), Some("color" | "colour"));
+                match value.as_str() {
+                    Some(color) if is_color => {
+                        let color = color.trim().to_lowercase();
+                        if !colors.contains(&color) {
+                            colors.push(color);
+                        }
+                    }
+                    _ => collect_colors(value, colors),
+                }
+            }
+        }
+        serde_yaml::Value::Sequence(items) => {
+            for item in items {
+                collect_colors(item, colors);
+            }
+        }
+        _ => {}
+    }
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used, clippy::expect_used)]
 mod tests {
@@ -416,6 +510,38 @@ mod tests {
                 .iter()
                 .any(|w| w.code == AccessibilityCode::SmallTouchTargets));
         }
+
+        #[test]
+        fn test_measured_contrast_and_flashing() {
+            let validator = AccessibilityValidator::new();
+            let info = GameAccessibilityInfo {
+                has_keyboard_nav: true,
+                min_contrast_ratio: 2.0,
+                max_flash_rate: 6.0,
+                ..Default::default()
+            };
+            let report = validator.validate(&info);
+            assert!(!report.passes_minimum);
+            assert!(report
+                .warnings
+                .iter()
+                .any(|w| w.code == AccessibilityCode::LowContrast));
+            assert!(report
+                .issues
+                .iter()
+                .any(|i| i.code == AccessibilityCode::FlashingContent));
+
+            // Unmeasured values are not reported
+            let report = validator.validate(&GameAccessibilityInfo {
+                has_keyboard_nav: true,
+                ..Default::default()
+            });
+            assert!(report.passes_minimum);
+            assert!(!report
+                .warnings
+                .iter()
+                .any(|w| w.code == AccessibilityCode::LowContrast));
+        }
     }
 
     mod yaml_extraction_tests {
@@ -455,6 +581,24 @@ when_touch:
             assert!(info.has_touch_input);
         }
 
+        #[test]
+        fn test_collects_declared_colors() {
+            let yaml = r"
+characters:
+  player:
+    type: bunny
+    color: Purple
+  friend:
+    type: cat
+    colour: yellow
+  pal:
+    type: dog
+    color: purple
+";
+            let info = GameAccessibilityInfo::from_yaml(yaml).unwrap();
+            assert_eq!(info.declared_colors, ["purple", "yellow"]);
+        }
+
         #[test]
         fn test_level1_defaults_accessible() {
             let yaml = "character: bunny";
@@ -480,6 +624,40 @@ background: sky
             assert!(report.passes_minimum);
         }
 
+        #[test]
+        fn test_warns_on_uncaptioned_sound() {
+            let validator = AccessibilityValidator::new();
+            let yaml = r"
+character: bunny
+when_touch:
+  target: star
+  sound: twinkle
+";
+            let report = validator.check_yaml(yaml).unwrap();
+            assert!(report.passes_minimum);
+            assert!(report
+                .warnings
+                .iter()
+                .any(|w| w.code == AccessibilityCode::MissingCaptions));
+        }
+
+        #[test]
+        fn test_captioned_sound_has_no_warning() {
+            let validator = AccessibilityValidator::new();
+            let yaml = r"
+character: bunny
+when_touch:
+  target: star
+  sound: twinkle
+  caption: sparkle!
+";
+            let report = validator.check_yaml(yaml).unwrap();
+            assert!(!report
+                .warnings
+                .iter()
+                .any(|w| w.code == AccessibilityCode::MissingCaptions));
+        }
+
         #[test]
         fn test_check_yaml_invalid() {
             let validator = AccessibilityValidator::new();
@@ -0,0 +1,563 @@
+//! Classroom mode.
+//!
+//! A teacher's workspace holding every student's game. Each student keeps
+//! their own tutorial progress, and a [`ClassroomPolicy`] lets the teacher
+//! override defaults for the whole class: turn off sharing, cap the schema
+//! level, or stretch time limits. [`Classroom::validate_all`] checks every
+//! project at once so the teacher sees who needs help, and
+//! [`Classroom::mastery_report`] shows which concepts the class finds hard
+//! without singling anyone out.
+
+use alloc::collections::BTreeMap;
+
+use crate::compiler::YamlCompiler;
+use crate::mastery::{MasteryError, MasteryReport};
+use crate::privacy::{DifferentialPrivacyConfig, PrivacyBudget};
+use crate::sandbox::ContentSandbox;
+use crate::schema::{detect_level, SchemaLevel};
+use crate::sharing::{BundleMetadata, GameBundle, ShareLinkGenerator};
+use crate::tutorial::{TutorialProgress, TutorialStage};
+
+/// Teacher overrides applied to every project in a classroom
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClassroomPolicy {
+    /// Whether students may create share links
+    pub sharing_enabled: bool,
+    /// Highest schema level students may use
+    pub max_level: SchemaLevel,
+    /// Multiplier applied to tutorial time limits (1.0 = default)
+    pub time_multiplier: f32,
+}
+
+impl Default for ClassroomPolicy {
+    fn default() -> Self {
+        Self {
+            sharing_enabled: true,
+            max_level: SchemaLevel::Level3,
+            time_multiplier: 1.0,
+        }
+    }
+}
+
+impl ClassroomPolicy {
+    /// Creates the default policy (sharing on, all levels, normal time)
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Turns off share links for the class
+    #[must_use]
+    pub const fn without_sharing(mut self) -> Self {
+        self.sharing_enabled = false;
+        self
+    }
+
+    /// Caps the schema level (e.g. Level 2 for an 8-10 class)
+    #[must_use]
+    pub const fn with_max_level(mut self, level: SchemaLevel) -> Self {
+        self.max_level = level;
+        self
+    }
+
+    /// Stretches time limits; values below 1.0 are treated as 1.0
+    #[must_use]
+    pub fn with_time_multiplier(mut self, multiplier: f32) -> Self {
+        self.time_multiplier = multiplier.max(1.0);
+        self
+    }
+
+    /// Returns true if `level` is allowed
+    #[must_use]
+    pub const fn allows_level(&self, level: SchemaLevel) -> bool {
+        level.number() <= self.max_level.number()
+    }
+
+    /// Time limit for a tutorial stage after the multiplier
+    #[must_use]
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    pub fn time_limit_seconds(&self, stage: TutorialStage) -> u32 {
+        (stage.estimated_seconds() as f32 * self.time_multiplier).ceil() as u32
+    }
+}
+
+/// Classroom error
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClassroomError {
+    /// A student with this nickname is already enrolled
+    DuplicateStudent(String),
+    /// No student with this nickname
+    UnknownStudent(String),
+    /// The teacher turned off sharing
+    SharingDisabled,
+    /// The project uses a level above the classroom cap
+    LevelNotAllowed {
+        /// Level the project needs
+        found: SchemaLevel,
+        /// Highest level allowed
+        allowed: SchemaLevel,
+    },
+    /// Building the share bundle failed
+    Bundle(String),
+}
+
+impl core::fmt::Display for ClassroomError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::DuplicateStudent(name) => write!(f, "'{name}' is already in this class"),
+            Self::UnknownStudent(name) => write!(f, "There's nobody called '{name}' in this class"),
+            Self::SharingDisabled => write!(f, "Your teacher has turned off sharing for now"),
+            Self::LevelNotAllowed { found, allowed } => write!(
+                f,
+                "This game uses Level {} but your class is using Level {} for now",
+                found.number(),
+                allowed.number()
+            ),
+            Self::Bundle(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl core::error::Error for ClassroomError {}
+
+/// One student's game and tutorial progress
+#[derive(Debug, Clone)]
+pub struct StudentProject {
+    /// Student nickname (never a real name)
+    pub nickname: String,
+    /// Tutorial progress, including the YAML being edited
+    pub tutorial: TutorialProgress,
+}
+
+impl StudentProject {
+    /// Creates an empty project at tutorial stage 1
+    #[must_use]
+    pub fn new(nickname: impl Into<String>) -> Self {
+        Self {
+            nickname: nickname.into(),
+            tutorial: TutorialProgress::new(),
+        }
+    }
+
+    /// The student's current game YAML
+    #[must_use]
+    pub fn yaml(&self) -> &str {
+        &self.tutorial.current_yaml
+    }
+}
+
+/// Validation outcome for one project
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProjectStatus {
+    /// Nothing written yet
+    Empty,
+    /// Compiles and respects the policy
+    Ok,
+    /// Compile or sandbox error (kid-friendly headline)
+    Error(String),
+    /// Uses a level above the classroom cap
+    OverLevel {
+        /// Level the project needs
+        found: SchemaLevel,
+        /// Highest level allowed
+        allowed: SchemaLevel,
+    },
+}
+
+/// Validation result for one project
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectReport {
+    /// Student nickname
+    pub nickname: String,
+    /// Detected schema level, if the YAML parsed
+    pub level: Option<SchemaLevel>,
+    /// Tutorial progress (0-100)
+    pub tutorial_percent: u8,
+    /// Validation outcome
+    pub status: ProjectStatus,
+}
+
+impl ProjectReport {
+    /// Returns true if the project needs the teacher's attention
+    #[must_use]
+    pub const fn needs_help(&self) -> bool {
+        matches!(
+            self.status,
+            ProjectStatus::Error(_) | ProjectStatus::OverLevel { .. }
+        )
+    }
+}
+
+/// Batch validation across a classroom
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClassroomReport {
+    /// One report per student, sorted by nickname
+    pub projects: Vec<ProjectReport>,
+}
+
+impl ClassroomReport {
+    /// Number of projects that compile and respect the policy
+    #[must_use]
+    pub fn passed(&self) -> usize {
+        self.projects
+            .iter()
+            .filter(|project| project.status == ProjectStatus::Ok)
+            .count()
+    }
+
+    /// Projects that need the teacher's attention
+    pub fn needs_help(&self) -> impl Iterator<Item = &ProjectReport> {
+        self.projects.iter().filter(|project| project.needs_help())
+    }
+
+    /// One-line summary for the teacher dashboard
+    #[must_use]
+    pub fn summary(&self) -> String {
+        format!(
+            "{} of {} games working, {} need help",
+            self.passed(),
+            self.projects.len(),
+            self.needs_help().count()
+        )
+    }
+}
+
+/// A teacher's workspace of student projects
+#[derive(Debug, Clone, Default)]
+pub struct Classroom {
+    /// Class name shown to the teacher
+    pub name: String,
+    /// Teacher overrides
+    pub policy: ClassroomPolicy,
+    students: BTreeMap<String, StudentProject>,
+}
+
+impl Classroom {
+    /// Creates an empty classroom with the default policy
+    #[must_use]
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            policy: ClassroomPolicy::default(),
+            students: BTreeMap::new(),
+        }
+    }
+
+    /// Sets the teacher policy
+    #[must_use]
+    pub const fn with_policy(mut self, policy: ClassroomPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Enrolls a student
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ClassroomError::DuplicateStudent`] if the nickname is taken
+    pub fn add_student(&mut self, nickname: impl Into<String>) -> Result<(), ClassroomError> {
+        let nickname = nickname.into();
+        if self.students.contains_key(&nickname) {
+            return Err(ClassroomError::DuplicateStudent(nickname));
+        }
+        let _ = self
+            .students
+            .insert(nickname.clone(), StudentProject::new(nickname));
+        Ok(())
+    }
+
+    /// Removes a student and their project
+    pub fn remove_student(&mut self, nickname: &str) -> Option<StudentProject> {
+        self.students.remove(nickname)
+    }
+
+    /// Looks up a student's project
+    #[must_use]
+    pub fn student(&self, nickname: &str) -> Option<&StudentProject> {
+        self.students.get(nickname)
+    }
+
+    /// Looks up a student's project for editing
+    pub fn student_mut(&mut self, nickname: &str) -> Option<&mut StudentProject> {
+        self.students.get_mut(nickname)
+    }
+
+    /// All projects, sorted by nickname
+    pub fn students(&self) -> impl Iterator<Item = &StudentProject> {
+        self.students.values()
+    }
+
+    /// Number of enrolled students
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.students.len()
+    }
+
+    /// Returns true if nobody is enrolled
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.students.is_empty()
+    }
+
+    /// Replaces a student's game YAML
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ClassroomError::UnknownStudent`] if the nickname isn't enrolled
+    pub fn update_yaml(
+        &mut self,
+        nickname: &str,
+        yaml: impl Into<String>,
+    ) -> Result<(), ClassroomError> {
+        self.students
+            .get_mut(nickname)
+            .ok_or_else(|| ClassroomError::UnknownStudent(nickname.to_string()))?
+            .tutorial
+            .update_yaml(yaml);
+        Ok(())
+    }
+
+    /// Validates one student's project against the compiler, sandbox and policy
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ClassroomError::UnknownStudent`] if the nickname isn't enrolled
+    pub fn validate(&self, nickname: &str) -> Result<ProjectReport, ClassroomError> {
+        self.students
+            .get(nickname)
+            .map(|project| self.check(project))
+            .ok_or_else(|| ClassroomError::UnknownStudent(nickname.to_string()))
+    }
+
+    /// Validates every project
+    #[must_use]
+    pub fn validate_all(&self) -> ClassroomReport {
+        ClassroomReport {
+            projects: self
+                .students
+                .values()
+                .map(|project| self.check(project))
+                .collect(),
+        }
+    }
+
+    /// Differentially private summary of the class's tutorial progress
+    ///
+    /// See [`crate::mastery`] for what is released and how it is protected.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MasteryError::CohortTooSmall`] if the class is too small to
+    /// stay anonymous, or [`MasteryError::Budget`] if the privacy budget is
+    /// used up.
+    pub fn mastery_report(
+        &self,
+        config: &DifferentialPrivacyConfig,
+        budget: &mut PrivacyBudget,
+    ) -> Result<MasteryReport, MasteryError> {
+        MasteryReport::from_progress(
+            self.students.values().map(|project| &project.tutorial),
+            config,
+            budget,
+        )
+    }
+
+    /// Creates a share link for a student's game, honoring the policy
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ClassroomError::SharingDisabled`] if the teacher turned
+    /// sharing off, [`ClassroomError::LevelNotAllowed`] if the game is above
+    /// the level cap, or [`ClassroomError::Bundle`] if bundling fails.
+    pub fn share(
+        &self,
+        nickname: &str,
+        links: &ShareLinkGenerator,
+    ) -> Result<String, ClassroomError> {
+        if !self.policy.sharing_enabled {
+            return Err(ClassroomError::SharingDisabled);
+        }
+        let project = self
+            .students
+            .get(nickname)
+            .ok_or_else(|| ClassroomError::UnknownStudent(nickname.to_string()))?;
+        if let Ok(found) = detect_level(project.yaml()) {
+            if !self.policy.allows_level(found) {
+                return Err(ClassroomError::LevelNotAllowed {
+                    found,
+                    allowed: self.policy.max_level,
+                });
+            }
+        }
+        let metadata = BundleMetadata::new(format!("{}'s game", project.nickname))
+            .with_creator(project.nickname.clone());
+        GameBundle::from_yaml(project.yaml(), metadata)
+            .and_then(|bundle| links.create_link(&bundle))
+            .map_err(|err| ClassroomError::Bundle(err.to_string()))
+    }
+
+    fn check(&self, project: &StudentProject) -> ProjectReport {
+        let yaml = project.yaml();
+        let tutorial_percent = project.tutorial.progress_percent();
+        let report = |level, status| ProjectReport {
+            nickname: project.nickname.clone(),
+            level,
+            tutorial_percent,
+            status,
+        };
+        if yaml.trim().is_empty() {
+            return report(None, ProjectStatus::Empty);
+        }
+
+        let level = match detect_level(yaml) {
+            Ok(level) => level,
+            Err(err) => return report(None, ProjectStatus::Error(err.to_kid_friendly().headline)),
+        };
+        if !self.policy.allows_level(level) {
+            return report(
+                Some(level),
+                ProjectStatus::OverLevel {
+                    found: level,
+                    allowed: self.policy.max_level,
+                },
+            );
+        }
+        let checked = ContentSandbox::for_level(level)
+            .validate_yaml(yaml)
+            .and_then(|()| YamlCompiler::new().compile(yaml));
+        match checked {
+            Ok(_) => report(Some(level), ProjectStatus::Ok),
+            Err(err) => report(
+                Some(level),
+                ProjectStatus::Error(err.to_kid_friendly().headline),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    const LEVEL2_YAML: &str = "game: maze\ncharacters:\n  hero:\n    type: bunny\n";
+
+    fn class_of(policy: ClassroomPolicy) -> Classroom {
+        let mut class = Classroom::new("Room 4").with_policy(policy);
+        class.add_student("ada").unwrap();
+        class.add_student("bo").unwrap();
+        class.add_student("cy").unwrap();
+        class
+    }
+
+    #[test]
+    fn test_students_are_unique() {
+        let mut class = Classroom::new("Room 4");
+        class.add_student("ada").unwrap();
+        assert_eq!(
+            class.add_student("ada"),
+            Err(ClassroomError::DuplicateStudent("ada".to_string()))
+        );
+        assert!(matches!(
+            class.update_yaml("zed", "character: bunny"),
+            Err(ClassroomError::UnknownStudent(_))
+        ));
+        assert!(class.remove_student("ada").is_some());
+        assert!(class.is_empty());
+    }
+
+    #[test]
+    fn test_validate_all_reports_each_student() {
+        let mut class = class_of(ClassroomPolicy::new());
+        class.update_yaml("ada", "character: bunny").unwrap();
+        class.update_yaml("bo", "character: [unclosed").unwrap();
+
+        let report = class.validate_all();
+        assert_eq!(report.projects.len(), 3);
+        assert_eq!(report.projects[0].status, ProjectStatus::Ok);
+        assert!(report.projects[1].needs_help());
+        assert_eq!(report.projects[2].status, ProjectStatus::Empty);
+        assert_eq!(report.passed(), 1);
+        assert_eq!(report.summary(), "1 of 3 games working, 1 need help");
+    }
+
+    #[test]
+    fn test_level_cap_is_enforced() {
+        let mut class = class_of(ClassroomPolicy::new().with_max_level(SchemaLevel::Level1));
+        class.update_yaml("ada", LEVEL2_YAML).unwrap();
+
+        let report = class.validate("ada").unwrap();
+        assert!(matches!(
+            report.status,
+            ProjectStatus::OverLevel {
+                found: SchemaLevel::Level2,
+                allowed: SchemaLevel::Level1
+            }
+        ));
+        assert!(matches!(
+            class.share("ada", &ShareLinkGenerator::default()),
+            Err(ClassroomError::LevelNotAllowed { .. })
+        ));
+    }
+
+    #[test]
+    fn test_sharing_override() {
+        let mut class = class_of(ClassroomPolicy::new());
+        class.update_yaml("ada", "character: bunny").unwrap();
+        let links = ShareLinkGenerator::default();
+        assert!(class.share("ada", &links).unwrap().contains('#'));
+
+        class.policy = class.policy.without_sharing();
+        assert_eq!(
+            class.share("ada", &links),
+            Err(ClassroomError::SharingDisabled)
+        );
+    }
+
+    #[test]
+    fn test_tutorial_progress_is_per_student() {
+        let mut class = class_of(ClassroomPolicy::new());
+        class.update_yaml("ada", "character: bunny").unwrap();
+        let next = class
+            .student_mut("ada")
+            .unwrap()
+            .tutorial
+            .advance()
+            .unwrap();
+        assert!(next.is_some());
+
+        let report = class.validate_all();
+        assert!(report.projects[0].tutorial_percent > 0);
+        assert_eq!(report.projects[1].tutorial_percent, 0);
+    }
+
+    #[test]
+    fn test_mastery_report_needs_a_full_class() {
+        let mut class = class_of(ClassroomPolicy::new());
+        let config = DifferentialPrivacyConfig::moderate_privacy();
+        let mut budget = PrivacyBudget::default();
+        assert!(matches!(
+            class.mastery_report(&config, &mut budget),
+            Err(MasteryError::CohortTooSmall { students: 3, .. })
+        ));
+
+        for nickname in ["di", "ed"] {
+            class.add_student(nickname).unwrap();
+        }
+        let report = class.mastery_report(&config, &mut budget).unwrap();
+        assert_eq!(report.concepts.len(), 4);
+        assert!(!report.to_json().unwrap().contains("ada"));
+    }
+
+    #[test]
+    fn test_extended_time_limits() {
+        let policy = ClassroomPolicy::new().with_time_multiplier(1.5);
+        assert_eq!(policy.time_limit_seconds(TutorialStage::HelloWorld), 45);
+        let shortened = ClassroomPolicy::new().with_time_multiplier(0.5);
+        assert_eq!(shortened.time_limit_seconds(TutorialStage::AddGoal), 60);
+    }
+}
@@ -2,12 +2,240 @@
 //!
 //! Transforms validated YAML into a `CompiledGame` ready for the Jugar runtime.
 
+use alloc::sync::Arc;
+use core::cell::{Cell, RefCell};
+use core::sync::atomic::{AtomicBool, Ordering};
+use core::time::Duration;
+use std::time::Instant;
+
 use crate::error::YamlError;
+use crate::extension::{CompilerExtension, CustomVocabulary, ExtensionError};
 use crate::schema::{
-    self, validate_level1, validate_level2, Level1Game, Level2Game, Level3Game, SchemaLevel,
+    self, validate_difficulty, validate_level1_with, validate_level2_with, validate_questions,
+    validate_world, DifficultyValue, Level1Game, Level2Game, Level2Item, Level2Question,
+    Level3Game, Level3World, SchemaLevel, SeedValue,
 };
 use crate::vocabulary::Vocabulary;
-use crate::{CompiledAction, CompiledEntity, CompiledGame, CompiledRule};
+use crate::{
+    CompiledAction, CompiledCondition, CompiledDifficulty, CompiledEntity, CompiledGame,
+    CompiledItem, CompiledQuestion, CompiledRule, CompiledWorld, SpawnAction, SpawnLocation,
+    SpeechLine, VisualEffect, MAX_ENTITIES,
+};
+
+/// Shared flag an editor sets to abandon a compile in progress
+///
+/// Clones share the flag, so the UI keeps one and hands the other to the
+/// compile.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    /// Creates a token that is not cancelled
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Asks every compile holding this token to stop
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns true once [`cancel`](Self::cancel) has been called
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Wall-clock time a compile may take
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CompileBudget {
+    limit: Option<Duration>,
+}
+
+impl CompileBudget {
+    /// No time limit
+    pub const UNLIMITED: Self = Self { limit: None };
+
+    /// Stop after this much time
+    #[must_use]
+    pub const fn new(limit: Duration) -> Self {
+        Self { limit: Some(limit) }
+    }
+
+    /// Stop after this many milliseconds
+    #[must_use]
+    pub const fn from_millis(ms: u64) -> Self {
+        Self::new(Duration::from_millis(ms))
+    }
+
+    /// The time limit, if any
+    #[must_use]
+    pub const fn limit(self) -> Option<Duration> {
+        self.limit
+    }
+}
+
+/// How far a compile got before it stopped
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CompileStage {
+    /// Reading the YAML and working out its level
+    Parse,
+    /// Checking words and ranges
+    Validate,
+    /// Building entities and rules
+    Build,
+}
+
+/// Result of [`YamlCompiler::compile_with`]
+#[derive(Debug, Clone)]
+pub enum CompileOutcome {
+    /// The game compiled
+    Compiled(Box<CompiledGame>),
+    /// The document has an error
+    Failed(YamlError),
+    /// The token was cancelled
+    Cancelled {
+        /// Stage that was about to run
+        stage: CompileStage,
+        /// Level detected before stopping, if it got that far
+        level: Option<SchemaLevel>,
+        /// Errors found before stopping
+        diagnostics: Vec<YamlError>,
+    },
+    /// The budget ran out
+    OutOfBudget {
+        /// Stage that was about to run
+        stage: CompileStage,
+        /// Level detected before stopping, if it got that far
+        level: Option<SchemaLevel>,
+        /// Errors found before stopping
+        diagnostics: Vec<YamlError>,
+    },
+}
+
+impl CompileOutcome {
+    /// The game, if it compiled
+    #[must_use]
+    pub const fn game(&self) -> Option<&CompiledGame> {
+        match self {
+            Self::Compiled(game) => Some(game),
+            _ => None,
+        }
+    }
+
+    /// Returns true if the compile stopped before finishing
+    #[must_use]
+    pub const fn is_stopped(&self) -> bool {
+        matches!(self, Self::Cancelled { .. } | Self::OutOfBudget { .. })
+    }
+
+    /// Errors found so far, including those from a stopped compile
+    #[must_use]
+    pub fn diagnostics(&self) -> &[YamlError] {
+        match self {
+            Self::Compiled(_) => &[],
+            Self::Failed(error) => core::slice::from_ref(error),
+            Self::Cancelled { diagnostics, .. } | Self::OutOfBudget { diagnostics, .. } => {
+                diagnostics
+            }
+        }
+    }
+}
+
+/// Cancellation and deadline checks threaded through a compile
+struct Gate<'a> {
+    cancel: Option<&'a CancelToken>,
+    deadline: Option<Instant>,
+    level: Cell<Option<SchemaLevel>>,
+    diagnostics: RefCell<Vec<YamlError>>,
+}
+
+impl Gate<'_> {
+    const fn open() -> Self {
+        Self {
+            cancel: None,
+            deadline: None,
+            level: Cell::new(None),
+            diagnostics: RefCell::new(Vec::new()),
+        }
+    }
+
+    fn check(&self, stage: CompileStage) -> Result<(), Halt> {
+        if self.cancel.is_some_and(CancelToken::is_cancelled) {
+            return Err(Halt::Cancelled(stage));
+        }
+        if self
+            .deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            return Err(Halt::OutOfBudget(stage));
+        }
+        Ok(())
+    }
+
+    /// Keeps an error from an independent check and carries on, so a
+    /// stopped compile can still report it
+    fn note(&self, result: Result<(), YamlError>) {
+        if let Err(error) = result {
+            self.diagnostics.borrow_mut().push(error);
+        }
+    }
+
+    /// Fails with the first noted error, if any
+    fn settle(&self) -> Result<(), Halt> {
+        let mut diagnostics = self.diagnostics.borrow_mut();
+        if diagnostics.is_empty() {
+            Ok(())
+        } else {
+            Err(Halt::Failed(diagnostics.remove(0)))
+        }
+    }
+
+    fn outcome(self, result: Result<CompiledGame, Halt>) -> CompileOutcome {
+        let level = self.level.get();
+        let diagnostics = self.diagnostics.into_inner();
+        match result {
+            Ok(game) => CompileOutcome::Compiled(Box::new(game)),
+            Err(Halt::Failed(error)) => CompileOutcome::Failed(error),
+            Err(Halt::Cancelled(stage)) => CompileOutcome::Cancelled {
+                stage,
+                level,
+                diagnostics,
+            },
+            Err(Halt::OutOfBudget(stage)) => CompileOutcome::OutOfBudget {
+                stage,
+                level,
+                diagnostics,
+            },
+        }
+    }
+}
+
+/// Why a gated compile stopped
+enum Halt {
+    Failed(YamlError),
+    Cancelled(CompileStage),
+    OutOfBudget(CompileStage),
+}
+
+impl Halt {
+    fn into_error(self) -> YamlError {
+        match self {
+            Self::Failed(error) => error,
+            Self::Cancelled(_) | Self::OutOfBudget(_) => YamlError::ValidationError {
+                message: "Compiling stopped before it finished".to_string(),
+            },
+        }
+    }
+}
+
+impl From<YamlError> for Halt {
+    fn from(error: YamlError) -> Self {
+        Self::Failed(error)
+    }
+}
 
 /// YAML game compiler
 #[derive(Debug, Default)]
@@ -15,19 +243,53 @@ pub struct YamlCompiler {
     /// Strict mode rejects any unknown fields (reserved for future use)
     #[allow(dead_code)]
     strict: bool,
+    /// Words added by host extensions
+    custom: CustomVocabulary,
 }
 
 impl YamlCompiler {
     /// Create a new compiler with default settings
     #[must_use]
     pub const fn new() -> Self {
-        Self { strict: false }
+        Self {
+            strict: false,
+            custom: CustomVocabulary::new(),
+        }
     }
 
     /// Create a strict compiler that rejects unknown fields
     #[must_use]
     pub const fn strict() -> Self {
-        Self { strict: true }
+        Self {
+            strict: true,
+            custom: CustomVocabulary::new(),
+        }
+    }
+
+    /// Register extra entity types, actions and words
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ExtensionError`] if any word clashes with the built-in
+    /// vocabulary or an earlier extension; nothing is registered then
+    pub fn register(&mut self, extension: &dyn CompilerExtension) -> Result<(), ExtensionError> {
+        self.custom.register(extension)
+    }
+
+    /// Sprite for an entity type added by an extension
+    #[must_use]
+    pub fn sprite_for(&self, entity_type: &str) -> Option<&str> {
+        self.custom.sprite(entity_type)
+    }
+
+    fn vocabulary(&self, level: SchemaLevel) -> Vocabulary {
+        let mut vocab = match level {
+            SchemaLevel::Level1 => Vocabulary::level1(),
+            SchemaLevel::Level2 => Vocabulary::level2(),
+            SchemaLevel::Level3 => Vocabulary::level3(),
+        };
+        self.custom.apply(&mut vocab);
+        vocab
     }
 
     /// Compile a YAML string into a game
@@ -36,11 +298,45 @@ impl YamlCompiler {
     ///
     /// Returns `YamlError` with kid-friendly message if compilation fails
     pub fn compile(&self, yaml: &str) -> Result<CompiledGame, YamlError> {
+        self.compile_gated(yaml, &Gate::open())
+            .map_err(Halt::into_error)
+    }
+
+    /// Compile with a cancellation token and a time budget
+    ///
+    /// Checks the token and the budget between stages and between entities
+    /// and rules, so an editor can abandon a stale compile as soon as the
+    /// user types again. Errors are reported from the stage that finds them
+    /// without waiting for later stages, and a stopped compile returns the
+    /// ones found before it stopped.
+    #[must_use]
+    pub fn compile_with(
+        &self,
+        yaml: &str,
+        cancel: &CancelToken,
+        budget: CompileBudget,
+    ) -> CompileOutcome {
+        let guard = Gate {
+            cancel: Some(cancel),
+            deadline: budget
+                .limit
+                .and_then(|limit| Instant::now().checked_add(limit)),
+            level: Cell::new(None),
+            diagnostics: RefCell::new(Vec::new()),
+        };
+        let result = self.compile_gated(yaml, &guard);
+        guard.outcome(result)
+    }
+
+    fn compile_gated(&self, yaml: &str, guard: &Gate<'_>) -> Result<CompiledGame, Halt> {
+        guard.check(CompileStage::Parse)?;
+
         // Normalize YAML (case-insensitive keys)
         let normalized = normalize_yaml(yaml)?;
 
         // Detect schema level
         let level = schema::detect_level(&normalized)?;
+        guard.level.set(Some(level));
 
         // Check nesting depth - per spec Section 9.1:
         // Level 1: max 2 levels (single-level nesting)
@@ -56,18 +352,19 @@ impl YamlCompiler {
 
         // Parse and validate based on level
         match level {
-            SchemaLevel::Level1 => self.compile_level1(&normalized),
-            SchemaLevel::Level2 => self.compile_level2(&normalized),
-            SchemaLevel::Level3 => self.compile_level3(&normalized),
+            SchemaLevel::Level1 => self.compile_level1(&normalized, guard),
+            SchemaLevel::Level2 => self.compile_level2(&normalized, guard),
+            SchemaLevel::Level3 => self.compile_level3(&normalized, guard),
         }
     }
 
-    #[allow(clippy::unused_self)]
-    fn compile_level1(&self, yaml: &str) -> Result<CompiledGame, YamlError> {
+    fn compile_level1(&self, yaml: &str, guard: &Gate<'_>) -> Result<CompiledGame, Halt> {
         let game: Level1Game = parse_yaml(yaml)?;
 
         // Validate
-        validate_level1(&game)?;
+        guard.check(CompileStage::Validate)?;
+        validate_level1_with(&game, &self.vocabulary(SchemaLevel::Level1))?;
+        guard.check(CompileStage::Build)?;
 
         // Build compiled game
         let mut entities = Vec::new();
@@ -80,6 +377,7 @@ impl YamlCompiler {
             position: None,
             movement: game.move_type.clone(),
             ai_model: None,
+            health: None,
         });
 
         // Convert when_touch to a rule
@@ -89,6 +387,9 @@ impl YamlCompiler {
             if let Some(sound) = &touch.sound {
                 actions.push(CompiledAction::PlaySound(sound.clone()));
             }
+            if let Some(caption) = &touch.caption {
+                actions.push(CompiledAction::Caption(caption.clone()));
+            }
 
             if let Some(score) = touch.score {
                 actions.push(CompiledAction::AddScore(i32::from(score)));
@@ -109,10 +410,15 @@ impl YamlCompiler {
                 position: None,
                 movement: None,
                 ai_model: None,
+                health: None,
             });
 
             rules.push(CompiledRule {
                 when: format!("player touches {}", touch.target),
+                condition: Some(CompiledCondition::Touches(
+                    "player".to_string(),
+                    touch.target.clone(),
+                )),
                 then: actions,
             });
         }
@@ -124,15 +430,23 @@ impl YamlCompiler {
             rules,
             background: game.background,
             music: game.music,
+            win_when: None,
+            lose_when: None,
+            items: Vec::new(),
+            templates: Vec::new(),
+            world: None,
+            questions: Vec::new(),
+            difficulty: None,
         })
     }
 
-    #[allow(clippy::unused_self)]
-    fn compile_level2(&self, yaml: &str) -> Result<CompiledGame, YamlError> {
+    fn compile_level2(&self, yaml: &str, guard: &Gate<'_>) -> Result<CompiledGame, Halt> {
         let game: Level2Game = parse_yaml(yaml)?;
 
         // Validate
-        validate_level2(&game)?;
+        guard.check(CompileStage::Validate)?;
+        validate_level2_with(&game, &self.vocabulary(SchemaLevel::Level2))?;
+        guard.check(CompileStage::Build)?;
 
         let mut entities = Vec::new();
         let mut rules = Vec::new();
@@ -140,12 +454,14 @@ impl YamlCompiler {
         // Compile characters
         if let Some(characters) = &game.characters {
             for (name, char_def) in characters {
+                guard.check(CompileStage::Build)?;
                 entities.push(CompiledEntity {
                     id: name.clone(),
                     entity_type: char_def.char_type.clone(),
                     position: None,
                     movement: char_def.move_type.clone(),
                     ai_model: char_def.pattern.as_ref().map(|p| format!("builtin:{p}")),
+                    health: char_def.health,
                 });
             }
         }
@@ -159,6 +475,7 @@ impl YamlCompiler {
                     position: None,
                     movement: game.move_type.clone(),
                     ai_model: None,
+                    health: None,
                 });
             }
         }
@@ -166,9 +483,11 @@ impl YamlCompiler {
         // Compile rules
         if let Some(yaml_rules) = &game.rules {
             for rule in yaml_rules {
-                let actions = compile_level2_actions(&rule.then);
+                guard.check(CompileStage::Build)?;
+                let actions = compile_level2_actions(&rule.then, &self.custom);
                 rules.push(CompiledRule {
                     when: rule.when.clone(),
+                    condition: parse_condition(&rule.when),
                     then: actions,
                 });
             }
@@ -180,6 +499,9 @@ impl YamlCompiler {
             if let Some(sound) = &touch.sound {
                 actions.push(CompiledAction::PlaySound(sound.clone()));
             }
+            if let Some(caption) = &touch.caption {
+                actions.push(CompiledAction::Caption(caption.clone()));
+            }
             if let Some(score) = touch.score {
                 actions.push(CompiledAction::AddScore(i32::from(score)));
             }
@@ -190,34 +512,74 @@ impl YamlCompiler {
                 position: None,
                 movement: None,
                 ai_model: None,
+                health: None,
             });
 
             rules.push(CompiledRule {
                 when: format!("player touches {}", touch.target),
+                condition: Some(CompiledCondition::Touches(
+                    "player".to_string(),
+                    touch.target.clone(),
+                )),
                 then: actions,
             });
         }
 
-        Ok(CompiledGame {
+        let win_when = match &game.win_when {
+            Some(text) => Some(compile_condition("win_when", text)?),
+            None => game.score_goal.map(CompiledCondition::ScoreReaches),
+        };
+        let lose_when = match &game.lose_when {
+            Some(text) => Some(compile_condition("lose_when", text)?),
+            None => game.lives.map(|_| CompiledCondition::LivesEmpty),
+        };
+
+        let mut compiled = CompiledGame {
             name: game.game.unwrap_or_else(|| "my-game".to_string()),
             level: SchemaLevel::Level2,
             entities,
             rules,
             background: game.background,
             music: game.music,
-        })
+            win_when,
+            lose_when,
+            items: compile_items(game.items.as_ref()),
+            templates: compile_templates(game.templates.as_ref()),
+            world: None,
+            questions: compile_questions(game.questions.as_deref()),
+            difficulty: None,
+        };
+        resolve_item_names(&mut compiled)?;
+        Ok(compiled)
     }
 
-    #[allow(clippy::unused_self)]
-    fn compile_level3(&self, yaml: &str) -> Result<CompiledGame, YamlError> {
+    fn compile_level3(&self, yaml: &str, guard: &Gate<'_>) -> Result<CompiledGame, Halt> {
         let game: Level3Game = parse_yaml(yaml)?;
 
+        // Validate; the checks are independent, so keep going after an
+        // error and stop between them if the user has moved on
+        guard.check(CompileStage::Validate)?;
+        if let Some(world) = &game.world {
+            guard.note(validate_world(world));
+            guard.check(CompileStage::Validate)?;
+        }
+        if let Some(difficulty) = &game.difficulty {
+            guard.note(validate_difficulty(difficulty));
+            guard.check(CompileStage::Validate)?;
+        }
+        guard.note(validate_questions(
+            game.questions.as_deref().unwrap_or_default(),
+        ));
+        guard.settle()?;
+        guard.check(CompileStage::Build)?;
+
         let mut entities = Vec::new();
         let mut rules = Vec::new();
 
         // Compile entities
         if let Some(entity_defs) = &game.entities {
             for (name, entity_def) in entity_defs {
+                guard.check(CompileStage::Build)?;
                 entities.push(CompiledEntity {
                     id: name.clone(),
                     entity_type: entity_def.sprite.clone().unwrap_or_default(),
@@ -231,6 +593,11 @@ impl YamlCompiler {
                         .as_ref()
                         .and_then(|c| c.move_keys.clone()),
                     ai_model: entity_def.ai.clone(),
+                    health: entity_def
+                        .components
+                        .as_ref()
+                        .and_then(|c| c.health)
+                        .and_then(|h| u32::try_from(h).ok()),
                 });
             }
         }
@@ -238,12 +605,14 @@ impl YamlCompiler {
         // Level 2 compatibility: characters
         if let Some(characters) = &game.characters {
             for (name, char_def) in characters {
+                guard.check(CompileStage::Build)?;
                 entities.push(CompiledEntity {
                     id: name.clone(),
                     entity_type: char_def.char_type.clone(),
                     position: None,
                     movement: char_def.move_type.clone(),
                     ai_model: char_def.pattern.as_ref().map(|p| format!("builtin:{p}")),
+                    health: char_def.health,
                 });
             }
         }
@@ -251,22 +620,44 @@ impl YamlCompiler {
         // Compile rules
         if let Some(yaml_rules) = &game.rules {
             for rule in yaml_rules {
-                let actions = compile_level2_actions(&rule.then);
+                guard.check(CompileStage::Build)?;
+                let actions = compile_level2_actions(&rule.then, &self.custom);
                 rules.push(CompiledRule {
                     when: rule.when.clone(),
+                    condition: parse_condition(&rule.when),
                     then: actions,
                 });
             }
         }
 
-        Ok(CompiledGame {
+        let win_when = game
+            .win_when
+            .as_deref()
+            .map(|text| compile_condition("win_when", text))
+            .transpose()?;
+        let lose_when = game
+            .lose_when
+            .as_deref()
+            .map(|text| compile_condition("lose_when", text))
+            .transpose()?;
+
+        let mut compiled = CompiledGame {
             name: game.game.unwrap_or_else(|| "my-game".to_string()),
             level: SchemaLevel::Level3,
             entities,
             rules,
             background: game.background,
             music: game.music,
-        })
+            win_when,
+            lose_when,
+            items: compile_items(game.items.as_ref()),
+            templates: compile_templates(game.templates.as_ref()),
+            world: game.world.as_ref().map(compile_world),
+            questions: compile_questions(game.questions.as_deref()),
+            difficulty: game.difficulty.as_ref().map(compile_difficulty),
+        };
+        resolve_item_names(&mut compiled)?;
+        Ok(compiled)
     }
 }
 
@@ -310,7 +701,7 @@ fn extract_unknown_field(message: &str) -> Option<String> {
 }
 
 /// Normalize YAML for case-insensitive parsing
-fn normalize_yaml(yaml: &str) -> Result<String, YamlError> {
+pub(crate) fn normalize_yaml(yaml: &str) -> Result<String, YamlError> {
     // Parse as generic value
     let value: serde_yaml::Value =
         serde_yaml::from_str(yaml).map_err(|e| YamlError::SyntaxError {
@@ -394,8 +785,200 @@ fn calculate_depth(value: &serde_yaml::Value) -> u8 {
     }
 }
 
+/// Example conditions shown when a win/lose condition is not understood
+const CONDITION_EXAMPLES: [&str; 6] = [
+    "score reaches 10",
+    "player health reaches 0",
+    "lives reach 0",
+    "player touches door",
+    "player has key",
+    "time reaches 60",
+];
+
+/// Compile a `win_when` / `lose_when` sentence
+fn compile_condition(field: &str, text: &str) -> Result<CompiledCondition, YamlError> {
+    parse_condition(text).ok_or_else(|| YamlError::InvalidEnumValue {
+        field: field.to_string(),
+        value: text.to_string(),
+        valid_options: CONDITION_EXAMPLES
+            .iter()
+            .map(|e| (*e).to_string())
+            .collect(),
+    })
+}
+
+/// Parse a condition sentence, if it is one the runtime can check
+///
+/// Keywords are case-insensitive; entity and item names keep their spelling.
+fn parse_condition(text: &str) -> Option<CompiledCondition> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let lower: Vec<String> = words.iter().map(|w| w.to_lowercase()).collect();
+    let keys: Vec<&str> = lower.iter().map(String::as_str).collect();
+
+    match keys.as_slice() {
+        ["score", "reaches" | "is", n] => n.parse().ok().map(CompiledCondition::ScoreReaches),
+        ["time", "reaches" | "is", n] => n.parse().ok().map(CompiledCondition::TimeReaches),
+        ["lives", "reach" | "reaches" | "are", "0"]
+        | ["no", "lives", ..]
+        | ["lives", "run", "out"] => Some(CompiledCondition::LivesEmpty),
+        [_, "health", "reaches" | "is", "0"] | [_, "has", "no", "health"] => {
+            Some(CompiledCondition::HealthEmpty(words[0].to_string()))
+        }
+        [_, "touches", _] => Some(CompiledCondition::Touches(
+            words[0].to_string(),
+            words[2].to_string(),
+        )),
+        [_, "has", count, _] => {
+            let count = match *count {
+                "a" | "an" | "the" => Some(1),
+                n => n.parse().ok(),
+            };
+            count.map(|count| CompiledCondition::HasItem(words[3].to_string(), count))
+        }
+        [_, "has", _] => Some(CompiledCondition::HasItem(words[2].to_string(), 1)),
+        _ => None,
+    }
+}
+
+/// Compile entity templates, sorted by id
+fn compile_templates(
+    templates: Option<&std::collections::HashMap<String, schema::Level2Character>>,
+) -> Vec<CompiledEntity> {
+    let mut compiled: Vec<CompiledEntity> = templates
+        .into_iter()
+        .flatten()
+        .map(|(name, template)| CompiledEntity {
+            id: name.clone(),
+            entity_type: template.char_type.clone(),
+            position: None,
+            movement: template.move_type.clone(),
+            ai_model: template.pattern.as_ref().map(|p| format!("builtin:{p}")),
+            health: template.health,
+        })
+        .collect();
+    compiled.sort_by(|a, b| a.id.cmp(&b.id));
+    compiled
+}
+
+/// Compile item definitions, sorted by id
+fn compile_items(
+    items: Option<&std::collections::HashMap<String, Level2Item>>,
+) -> Vec<CompiledItem> {
+    let mut compiled: Vec<CompiledItem> = items
+        .into_iter()
+        .flatten()
+        .map(|(id, item)| CompiledItem {
+            id: id.clone(),
+            item_type: item.item_type.clone(),
+            max: item.max,
+            start: item.start.unwrap_or(0),
+        })
+        .collect();
+    compiled.sort_by(|a, b| a.id.cmp(&b.id));
+    compiled
+}
+
+/// Compile a validated `world:` section
+fn compile_world(world: &Level3World) -> CompiledWorld {
+    CompiledWorld {
+        kind: world.world_type.clone(),
+        algorithm: world.algorithm.clone(),
+        seed: match world.seed {
+            Some(SeedValue::Number(seed)) => Some(seed),
+            Some(SeedValue::Auto(_)) | None => None,
+        },
+        size: world.size.map(Into::into),
+        gravity: world.gravity.unwrap_or(0.0),
+        jump: world.jump,
+    }
+}
+
+/// Compile a validated `difficulty:` setting
+fn compile_difficulty(difficulty: &DifficultyValue) -> CompiledDifficulty {
+    match difficulty {
+        DifficultyValue::Level(level) => {
+            CompiledDifficulty::Fixed(u8::try_from(*level).unwrap_or(u8::MAX))
+        }
+        DifficultyValue::Mode(_) => CompiledDifficulty::Adaptive,
+    }
+}
+
+/// Compile validated questions, turning each answer into a choice index
+fn compile_questions(questions: Option<&[Level2Question]>) -> Vec<CompiledQuestion> {
+    questions
+        .unwrap_or_default()
+        .iter()
+        .map(|question| CompiledQuestion {
+            ask: question.ask.clone(),
+            choices: question.choices.clone(),
+            answer: question
+                .choices
+                .iter()
+                .position(|choice| *choice == question.answer)
+                .unwrap_or_default(),
+        })
+        .collect()
+}
+
+/// Point item actions and conditions at defined items
+///
+/// "player has 3 gems" finds the item `gem`; unknown items are an error.
+fn resolve_item_names(game: &mut CompiledGame) -> Result<(), YamlError> {
+    let ids: Vec<String> = game.items.iter().map(|item| item.id.clone()).collect();
+    let resolve = |name: &mut String| -> Result<(), YamlError> {
+        if ids.contains(name) {
+            return Ok(());
+        }
+        if let Some(singular) = name
+            .strip_suffix('s')
+            .filter(|s| ids.iter().any(|id| id == s))
+        {
+            *name = singular.to_string();
+            return Ok(());
+        }
+        Err(YamlError::InvalidEnumValue {
+            field: "items".to_string(),
+            value: name.clone(),
+            valid_options: ids.clone(),
+        })
+    };
+
+    for rule in &mut game.rules {
+        // Rule triggers are free text, so an unknown item just leaves the
+        // trigger unchecked instead of failing the game
+        let known = match &mut rule.condition {
+            Some(CompiledCondition::HasItem(name, _)) => resolve(name).is_ok(),
+            _ => true,
+        };
+        if !known {
+            rule.condition = None;
+        }
+        for action in &mut rule.then {
+            if let CompiledAction::GiveItem(name, _) | CompiledAction::RemoveItem(name, _) = action
+            {
+                resolve(name)?;
+            }
+        }
+    }
+    for condition in [&mut game.win_when, &mut game.lose_when] {
+        if let Some(CompiledCondition::HasItem(name, _)) = condition {
+            resolve(name)?;
+        }
+    }
+    Ok(())
+}
+
+/// Seconds a speech bubble stays up when `for:` is missing
+const DEFAULT_SAY_SECONDS: f32 = 3.0;
+
+/// Copies alive at once when `max:` is missing
+const DEFAULT_SPAWN_MAX: u32 = 10;
+
 /// Compile Level 2 actions
-fn compile_level2_actions(actions: &[schema::Level2Action]) -> Vec<CompiledAction> {
+fn compile_level2_actions(
+    actions: &[schema::Level2Action],
+    custom: &CustomVocabulary,
+) -> Vec<CompiledAction> {
     actions
         .iter()
         .filter_map(|action| match action {
@@ -405,16 +988,69 @@ fn compile_level2_actions(actions: &[schema::Level2Action]) -> Vec<CompiledActio
             schema::Level2Action::LoseLife { lose_life } => {
                 Some(CompiledAction::LoseLife(*lose_life))
             }
+            schema::Level2Action::Damage { damage, amount } => {
+                Some(CompiledAction::Damage(damage.clone(), *amount))
+            }
+            schema::Level2Action::Heal { heal, amount } => {
+                Some(CompiledAction::Heal(heal.clone(), *amount))
+            }
+            schema::Level2Action::GiveItem { give_item, amount } => {
+                Some(CompiledAction::GiveItem(give_item.clone(), *amount))
+            }
+            schema::Level2Action::RemoveItem {
+                remove_item,
+                amount,
+            } => Some(CompiledAction::RemoveItem(remove_item.clone(), *amount)),
+            schema::Level2Action::Say {
+                say,
+                by,
+                duration,
+                voice,
+            } => Some(CompiledAction::Say(SpeechLine {
+                text: say.clone(),
+                speaker: by.clone(),
+                seconds: duration
+                    .as_ref()
+                    .and_then(schema::TimeSpan::seconds)
+                    .unwrap_or(DEFAULT_SAY_SECONDS)
+                    .min(schema::MAX_SAY_SECONDS),
+                voice: *voice,
+            })),
+            schema::Level2Action::Spawn {
+                spawn,
+                at,
+                every,
+                max,
+            } => Some(CompiledAction::Spawn(SpawnAction {
+                template: spawn.clone(),
+                at: at
+                    .as_deref()
+                    .and_then(SpawnLocation::parse)
+                    .unwrap_or(SpawnLocation::Random),
+                every: every
+                    .as_ref()
+                    .and_then(schema::TimeSpan::seconds)
+                    .map(|s| s.clamp(schema::MIN_SPAWN_SECONDS, schema::MAX_SPAWN_SECONDS)),
+                max: max.map_or(DEFAULT_SPAWN_MAX, |max| {
+                    max.min(u32::try_from(MAX_ENTITIES).unwrap_or(u32::MAX))
+                }),
+            })),
             schema::Level2Action::Play { play } => Some(CompiledAction::PlaySound(play.clone())),
             schema::Level2Action::Show { show } => Some(CompiledAction::Show(show.clone())),
+            schema::Level2Action::SetMusic { set_music, to } => Some(
+                CompiledAction::SetMusicParameter(set_music.clone(), to.clamp(0.0, 1.0)),
+            ),
             schema::Level2Action::EntityAction { entity, action } => match action.as_str() {
                 "respawn" | "new_place" => Some(CompiledAction::Respawn(entity.clone())),
                 "disappear" => Some(CompiledAction::Disappear(entity.clone())),
-                _ => None,
+                effect => VisualEffect::parse(effect)
+                    .map(|effect| CompiledAction::Effect(entity.clone(), effect)),
             },
             schema::Level2Action::Simple(s) => {
                 if s == "stop" || s == "stop_game" {
                     Some(CompiledAction::StopGame)
+                } else if custom.has_action(s) {
+                    Some(CompiledAction::Custom(s.clone()))
                 } else {
                     None
                 }
@@ -427,6 +1063,7 @@ fn compile_level2_actions(actions: &[schema::Level2Action]) -> Vec<CompiledActio
 #[allow(clippy::unwrap_used)]
 mod tests {
     use super::*;
+    use jugar_core::AccessibilitySettings;
 
     #[test]
     fn test_compile_minimal_game() {
@@ -550,6 +1187,32 @@ lives: 3
         assert_eq!(game.entities.len(), 2);
     }
 
+    #[test]
+    fn test_compile_set_music_action() {
+        let compiler = YamlCompiler::new();
+        let yaml = r"
+characters:
+  player:
+    type: bunny
+rules:
+  - when: enemy is near
+    then:
+      - set_music: danger
+        to: 0.8
+";
+        let game = compiler.compile(yaml).unwrap();
+        let rule = game
+            .rules
+            .iter()
+            .find(|r| r.when == "enemy is near")
+            .unwrap();
+        assert!(matches!(
+            &rule.then[..],
+            [CompiledAction::SetMusicParameter(name, value)]
+                if name == "danger" && (value - 0.8).abs() < f32::EPSILON
+        ));
+    }
+
     #[test]
     fn test_compile_level3() {
         let compiler = YamlCompiler::new();
@@ -569,4 +1232,417 @@ entities:
         let game = result.unwrap();
         assert_eq!(game.level, SchemaLevel::Level3);
     }
+
+    #[test]
+    fn test_compile_health_and_conditions() {
+        let compiler = YamlCompiler::new();
+        let yaml = r"
+characters:
+  Hero:
+    type: knight
+    health: 5
+rules:
+  - when: Hero touches lava
+    then:
+      - damage: Hero
+        amount: 2
+  - when: Hero touches apple
+    then:
+      - heal: Hero
+win_when: score reaches 10
+lose_when: Hero health reaches 0
+";
+        let game = compiler.compile(yaml).unwrap();
+        assert_eq!(game.entities[0].health, Some(5));
+        assert!(matches!(
+            &game.rules[0].then[..],
+            [CompiledAction::Damage(who, 2)] if who == "Hero"
+        ));
+        assert!(matches!(
+            &game.rules[1].then[..],
+            [CompiledAction::Heal(who, 1)] if who == "Hero"
+        ));
+        assert_eq!(game.win_when, Some(CompiledCondition::ScoreReaches(10)));
+        assert_eq!(
+            game.lose_when,
+            Some(CompiledCondition::HealthEmpty("Hero".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_conditions_default_to_score_goal_and_lives() {
+        let yaml = "characters:\n  player:\n    type: bunny\nscore_goal: 20\nlives: 3\n";
+        let game = YamlCompiler::new().compile(yaml).unwrap();
+        assert_eq!(game.win_when, Some(CompiledCondition::ScoreReaches(20)));
+        assert_eq!(game.lose_when, Some(CompiledCondition::LivesEmpty));
+
+        let game = YamlCompiler::new().compile("character: bunny").unwrap();
+        assert_eq!(game.win_when, None);
+    }
+
+    #[test]
+    fn test_compile_condition_phrases() {
+        assert_eq!(
+            compile_condition("win_when", "Player TOUCHES door").unwrap(),
+            CompiledCondition::Touches("Player".to_string(), "door".to_string())
+        );
+        assert_eq!(
+            compile_condition("lose_when", "no lives left").unwrap(),
+            CompiledCondition::LivesEmpty
+        );
+        assert_eq!(
+            compile_condition("lose_when", "time reaches 60").unwrap(),
+            CompiledCondition::TimeReaches(60)
+        );
+        assert!(matches!(
+            compile_condition("win_when", "the moon is happy"),
+            Err(YamlError::InvalidEnumValue { field, .. }) if field == "win_when"
+        ));
+    }
+
+    #[test]
+    fn test_health_out_of_range_rejected() {
+        let yaml = "characters:\n  player:\n    type: bunny\n    health: 500\n";
+        assert!(matches!(
+            YamlCompiler::new().compile(yaml),
+            Err(YamlError::OutOfRange { max: 100, .. })
+        ));
+        let yaml =
+            "rules:\n  - when: a touches b\n    then:\n      - damage: a\n        amount: 0\n";
+        assert!(YamlCompiler::new().compile(yaml).is_err());
+    }
+
+    #[test]
+    fn test_compile_items_and_inventory() {
+        let yaml = r"
+characters:
+  player:
+    type: knight
+items:
+  key:
+    type: key
+    max: 1
+  gem:
+    type: gem
+    start: 2
+rules:
+  - when: player touches chest
+    then:
+      - give_item: key
+  - when: player has key
+    then:
+      - remove_item: key
+      - show: door opens
+win_when: player has 5 gems
+";
+        let game = YamlCompiler::new().compile(yaml).unwrap();
+        assert_eq!(
+            game.items.iter().map(|i| i.id.as_str()).collect::<Vec<_>>(),
+            vec!["gem", "key"]
+        );
+        assert!(matches!(
+            &game.rules[0].then[..],
+            [CompiledAction::GiveItem(item, 1)] if item == "key"
+        ));
+        assert_eq!(
+            game.rules[1].condition,
+            Some(CompiledCondition::HasItem("key".to_string(), 1))
+        );
+        assert_eq!(
+            game.win_when,
+            Some(CompiledCondition::HasItem("gem".to_string(), 5))
+        );
+
+        let mut inventory = game.inventory();
+        assert_eq!(inventory.count("gem"), 2);
+        assert_eq!(inventory.add("key", 2), 1);
+    }
+
+    #[test]
+    fn test_unknown_item_rejected() {
+        let yaml = "rules:\n  - when: player touches chest\n    then:\n      - give_item: sword\n";
+        assert!(matches!(
+            YamlCompiler::new().compile(yaml),
+            Err(YamlError::InvalidEnumValue { field, .. }) if field == "items"
+        ));
+    }
+
+    #[test]
+    fn test_compile_say_action() {
+        let yaml = r#"
+characters:
+  bunny:
+    type: bunny
+rules:
+  - when: bunny touches star
+    then:
+      - say: "Hello!"
+        by: bunny
+        for: 2s
+        voice: true
+      - say: The end
+"#;
+        let game = YamlCompiler::new().compile(yaml).unwrap();
+        let lines: Vec<&SpeechLine> = game.rules[0]
+            .then
+            .iter()
+            .filter_map(|action| match action {
+                CompiledAction::Say(line) => Some(line),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].speaker.as_deref(), Some("bunny"));
+        assert!((lines[0].seconds - 2.0).abs() < f32::EPSILON && lines[0].voice);
+        assert_eq!(lines[1].speaker, None);
+        assert!((lines[1].seconds - DEFAULT_SAY_SECONDS).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_effects_respect_reduced_motion() {
+        let yaml = r"
+characters:
+  bunny:
+    type: bunny
+rules:
+  - when: bunny touches star
+    then:
+      - entity: bunny
+        action: shake
+      - entity: bunny
+        action: fade
+";
+        let effects = |game: &CompiledGame| -> Vec<VisualEffect> {
+            game.rules[0]
+                .then
+                .iter()
+                .filter_map(|action| match action {
+                    CompiledAction::Effect(entity, effect) if entity == "bunny" => Some(*effect),
+                    _ => None,
+                })
+                .collect()
+        };
+        let mut game = YamlCompiler::new().compile(yaml).unwrap();
+        assert_eq!(
+            effects(&game),
+            vec![VisualEffect::Shake, VisualEffect::Fade]
+        );
+
+        game.apply_accessibility(&AccessibilitySettings::new().with_high_contrast(true));
+        assert_eq!(
+            effects(&game),
+            vec![VisualEffect::Shake, VisualEffect::Fade]
+        );
+        game.apply_accessibility(&AccessibilitySettings::new().with_reduced_motion(true));
+        assert_eq!(effects(&game), vec![VisualEffect::Fade, VisualEffect::Fade]);
+    }
+
+    #[test]
+    fn test_say_is_checked() {
+        let game = |line: &str| {
+            format!("characters:\n  bunny:\n    type: bunny\nrules:\n  - when: bunny touches star\n    then:\n{line}")
+        };
+        let compiler = YamlCompiler::new();
+        assert!(matches!(
+            compiler.compile(&game("      - say: I will kill you\n")),
+            Err(YamlError::UnknownWord { .. })
+        ));
+        assert!(matches!(
+            compiler.compile(&game("      - say: hi\n        by: dragon\n")),
+            Err(YamlError::InvalidEnumValue { field, .. }) if field == "by"
+        ));
+        assert!(matches!(
+            compiler.compile(&game("      - say: hi\n        for: forever\n")),
+            Err(YamlError::InvalidEnumValue { field, .. }) if field == "for"
+        ));
+        assert!(compiler
+            .compile(&game("      - say: hi\n        for: 1.5 seconds\n"))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_compile_spawn_action() {
+        let yaml = r"
+characters:
+  player:
+    type: bunny
+templates:
+  star:
+    type: star
+    move: fall
+rules:
+  - when: game starts
+    then:
+      - spawn: star
+        at: top
+        every: 2s
+        max: 5
+  - when: player touches star
+    then:
+      - spawn: star
+        at: 100, 200
+";
+        let game = YamlCompiler::new().compile(yaml).unwrap();
+        assert_eq!(game.templates.len(), 1);
+        assert_eq!(game.templates[0].movement.as_deref(), Some("fall"));
+
+        let spawns: Vec<&SpawnAction> = game
+            .rules
+            .iter()
+            .flat_map(|rule| &rule.then)
+            .filter_map(|action| match action {
+                CompiledAction::Spawn(spawn) => Some(spawn),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(spawns[0].at, SpawnLocation::Top);
+        assert_eq!(spawns[0].every, Some(2.0));
+        assert_eq!(spawns[0].max, 5);
+        assert!(spawns[0].can_spawn(4, 10));
+        assert!(!spawns[0].can_spawn(5, 10));
+        assert!(!spawns[0].can_spawn(0, MAX_ENTITIES));
+        assert_eq!(spawns[1].at, SpawnLocation::Point(100.0, 200.0));
+        assert_eq!((spawns[1].every, spawns[1].max), (None, DEFAULT_SPAWN_MAX));
+    }
+
+    #[test]
+    fn test_spawn_is_checked() {
+        let game = |action: &str| {
+            format!("characters:\n  player:\n    type: bunny\ntemplates:\n  rock:\n    type: asteroid\nrules:\n  - when: game starts\n    then:\n{action}")
+        };
+        let field_of = |result: Result<CompiledGame, YamlError>| match result {
+            Err(YamlError::InvalidEnumValue { field, .. }) => field,
+            other => format!("{other:?}"),
+        };
+        let compiler = YamlCompiler::new();
+        assert_eq!(
+            field_of(compiler.compile(&game("      - spawn: star\n"))),
+            "spawn"
+        );
+        assert_eq!(
+            field_of(compiler.compile(&game("      - spawn: rock\n        at: moon\n"))),
+            "at"
+        );
+        assert_eq!(
+            field_of(compiler.compile(&game("      - spawn: rock\n        every: 0.01s\n"))),
+            "every"
+        );
+        assert!(compiler
+            .compile(&game("      - spawn: rock\n        at: player\n"))
+            .is_ok());
+    }
+
+    struct School;
+
+    impl CompilerExtension for School {
+        fn name(&self) -> &'static str {
+            "school"
+        }
+
+        fn entity_types(&self) -> Vec<crate::CustomEntityType> {
+            vec![crate::CustomEntityType::new("dinosaur", "dino.png")]
+        }
+
+        fn actions(&self) -> Vec<String> {
+            vec!["roar".to_string()]
+        }
+    }
+
+    #[test]
+    fn test_extension_words_compile() {
+        let yaml = "characters:\n  rex:\n    type: dinosaur\nrules:\n  - when: game starts\n    then:\n      - roar\n";
+        assert!(YamlCompiler::new().compile("character: dinosaur").is_err());
+        assert!(YamlCompiler::new().compile(yaml).is_err());
+
+        let mut compiler = YamlCompiler::new();
+        compiler.register(&School).unwrap();
+        assert_eq!(compiler.sprite_for("dinosaur"), Some("dino.png"));
+        let game = compiler.compile("character: dinosaur").unwrap();
+        assert_eq!(game.entities[0].entity_type, "dinosaur");
+
+        let game = compiler.compile(yaml).unwrap();
+        assert!(matches!(
+            game.rules[0].then.as_slice(),
+            [CompiledAction::Custom(action)] if action == "roar"
+        ));
+        assert!(compiler.register(&School).is_err());
+    }
+
+    #[test]
+    fn test_compile_with_budget_and_cancel() {
+        let compiler = YamlCompiler::new();
+        let yaml = "characters:\n  hero:\n    type: knight\nlives: 3\n";
+
+        let outcome = compiler.compile_with(yaml, &CancelToken::new(), CompileBudget::UNLIMITED);
+        assert_eq!(outcome.game().unwrap().entities.len(), 1);
+
+        let token = CancelToken::new();
+        token.cancel();
+        assert!(matches!(
+            compiler.compile_with(yaml, &token, CompileBudget::UNLIMITED),
+            CompileOutcome::Cancelled {
+                stage: CompileStage::Parse,
+                level: None,
+                ..
+            }
+        ));
+
+        let outcome =
+            compiler.compile_with(yaml, &CancelToken::new(), CompileBudget::from_millis(0));
+        assert!(outcome.is_stopped());
+        assert!(matches!(
+            outcome,
+            CompileOutcome::OutOfBudget {
+                stage: CompileStage::Parse,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_compile_with_reports_errors() {
+        let outcome = YamlCompiler::new().compile_with(
+            "character: dinosaur",
+            &CancelToken::new(),
+            CompileBudget::new(Duration::from_secs(5)),
+        );
+        assert!(matches!(
+            outcome,
+            CompileOutcome::Failed(YamlError::InvalidEnumValue { .. })
+        ));
+    }
+
+    #[test]
+    fn test_stopped_compile_keeps_diagnostics() {
+        let token = CancelToken::new();
+        let guard = Gate {
+            cancel: Some(&token),
+            deadline: None,
+            level: Cell::new(Some(SchemaLevel::Level3)),
+            diagnostics: RefCell::new(Vec::new()),
+        };
+        guard.note(validate_difficulty(&DifficultyValue::Mode(
+            "impossible".to_string(),
+        )));
+        token.cancel();
+        let result = guard
+            .check(CompileStage::Validate)
+            .and_then(|()| Ok(YamlCompiler::new().compile("character: bunny")?));
+        let outcome = guard.outcome(result);
+        assert!(matches!(
+            outcome,
+            CompileOutcome::Cancelled {
+                stage: CompileStage::Validate,
+                level: Some(SchemaLevel::Level3),
+                ..
+            }
+        ));
+        assert_eq!(outcome.diagnostics().len(), 1);
+
+        let failed = YamlCompiler::new()
+            .compile("character: dinosaur")
+            .unwrap_err();
+        let outcome = CompileOutcome::Failed(failed.clone());
+        assert_eq!(outcome.diagnostics(), [failed]);
+    }
 }
@@ -0,0 +1,299 @@
+//! Golden-file tests for kid-facing error text
+//!
+//! Every [`YamlError`] variant and runtime quota message is rendered with
+//! [`KidFriendlyError::snapshot`] and compared against a committed file in
+//! `goldens/errors/`. Wording is product surface, so a change here should
+//! show up in review as a golden diff.
+//!
+//! After a deliberate wording change, regenerate the goldens with:
+//!
+//! ```text
+//! UPDATE_GOLDENS=1 cargo test -p jugar-yaml golden
+//! ```
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use alloc::collections::BTreeSet;
+use core::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::{KidFriendlyError, YamlError};
+use crate::sandbox::{QuotaExceeded, QuotaKind};
+
+/// Environment variable that rewrites goldens instead of comparing
+const UPDATE_ENV: &str = "UPDATE_GOLDENS";
+
+/// Number of [`YamlError`] variants; see [`variant_index`]
+const YAML_ERROR_VARIANTS: usize = 9;
+
+fn golden_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("goldens/errors")
+}
+
+fn updating() -> bool {
+    std::env::var_os(UPDATE_ENV).is_some()
+}
+
+/// Position of a variant in declaration order
+///
+/// No wildcard arm: a new variant fails to compile here until it gets a
+/// golden case below.
+const fn variant_index(error: &YamlError) -> usize {
+    match error {
+        YamlError::SyntaxError { .. } => 0,
+        YamlError::UnknownWord { .. } => 1,
+        YamlError::NestingTooDeep { .. } => 2,
+        YamlError::MissingRequired { .. } => 3,
+        YamlError::OutOfRange { .. } => 4,
+        YamlError::InvalidEnumValue { .. } => 5,
+        YamlError::FileNotFound { .. } => 6,
+        YamlError::IncompatibleModel { .. } => 7,
+        YamlError::ValidationError { .. } => 8,
+    }
+}
+
+fn words(words: &[&str]) -> Vec<String> {
+    words.iter().map(|w| (*w).to_string()).collect()
+}
+
+/// Compiler errors, one or more per variant
+fn yaml_error_cases() -> Vec<(&'static str, YamlError)> {
+    let syntax = |message: &str, line, column| YamlError::SyntaxError {
+        message: message.to_string(),
+        line,
+        column,
+    };
+    vec![
+        (
+            "syntax_out_of_place",
+            syntax("expected a mapping, found a sequence", Some(2), Some(1)),
+        ),
+        (
+            "syntax_indentation",
+            syntax(
+                "mapping values are not allowed in this context",
+                Some(4),
+                Some(9),
+            ),
+        ),
+        ("syntax_value", syntax("invalid scalar", Some(1), None)),
+        (
+            "syntax_duplicate",
+            syntax("duplicate entry with key \"character\"", Some(5), Some(1)),
+        ),
+        (
+            "syntax_unknown",
+            syntax("control characters are not allowed", None, None),
+        ),
+        (
+            "unknown_word",
+            YamlError::UnknownWord {
+                word: "dinosaur".to_string(),
+                suggestions: words(&["dragon", "dog"]),
+                line: Some(3),
+            },
+        ),
+        (
+            "unknown_word_no_suggestions",
+            YamlError::UnknownWord {
+                word: "xyz".to_string(),
+                suggestions: Vec::new(),
+                line: None,
+            },
+        ),
+        (
+            "unknown_word_many_suggestions",
+            YamlError::UnknownWord {
+                word: "bunnie".to_string(),
+                suggestions: words(&["bee", "bird", "bunny", "boat", "bomb", "bounce", "buzz"]),
+                line: Some(1),
+            },
+        ),
+        (
+            "nesting_too_deep",
+            YamlError::NestingTooDeep { max: 3, found: 5 },
+        ),
+        (
+            "missing_required",
+            YamlError::MissingRequired {
+                field: "character".to_string(),
+                example: "bunny".to_string(),
+            },
+        ),
+        (
+            "out_of_range",
+            YamlError::OutOfRange {
+                field: "score".to_string(),
+                min: -9,
+                max: 9,
+                value: 12,
+            },
+        ),
+        (
+            "invalid_enum_value",
+            YamlError::InvalidEnumValue {
+                field: "background".to_string(),
+                value: "moon".to_string(),
+                valid_options: words(&["sky", "grass", "space", "underwater", "forest", "snow"]),
+            },
+        ),
+        (
+            "file_not_found",
+            YamlError::FileNotFound {
+                path: "sprites/rocket.png".to_string(),
+            },
+        ),
+        (
+            "incompatible_model",
+            YamlError::IncompatibleModel {
+                model: "chaser.apr".to_string(),
+                reason: "it needs 12 inputs but your game gives 4".to_string(),
+            },
+        ),
+        (
+            "validation_error",
+            YamlError::ValidationError {
+                message: "Your game has more than 100 characters.".to_string(),
+            },
+        ),
+    ]
+}
+
+/// Every kid-facing error, named by its golden file
+fn all_cases() -> Vec<(&'static str, KidFriendlyError)> {
+    let quota = |kind, limit| QuotaExceeded {
+        kind,
+        limit,
+        dropped: 3,
+    };
+    let mut cases: Vec<(&'static str, KidFriendlyError)> = yaml_error_cases()
+        .into_iter()
+        .map(|(name, error)| (name, error.to_kid_friendly()))
+        .collect();
+    cases.extend([
+        ("quota_spawn", quota(QuotaKind::Spawn, 20).to_kid_friendly()),
+        ("quota_sound", quota(QuotaKind::Sound, 8).to_kid_friendly()),
+        ("quota_rule", quota(QuotaKind::Rule, 64).to_kid_friendly()),
+    ]);
+    cases
+}
+
+/// Short line diff: line counts and the first line that differs
+fn describe_diff(expected: &str, actual: &str) -> String {
+    let expected_lines: Vec<&str> = expected.lines().collect();
+    let actual_lines: Vec<&str> = actual.lines().collect();
+    let mut out = format!(
+        "expected {} lines, got {}\n",
+        expected_lines.len(),
+        actual_lines.len()
+    );
+    let count = expected_lines.len().max(actual_lines.len());
+    if let Some(i) = (0..count).find(|&i| expected_lines.get(i) != actual_lines.get(i)) {
+        let _ = writeln!(out, "first difference at line {}:", i + 1);
+        let _ = writeln!(
+            out,
+            "- {}",
+            expected_lines.get(i).unwrap_or(&"<end of file>")
+        );
+        let _ = writeln!(out, "+ {}", actual_lines.get(i).unwrap_or(&"<end of file>"));
+    }
+    out
+}
+
+/// Compares `actual` with the golden `name`, or rewrites it when updating
+fn check_golden(dir: &Path, name: &str, actual: &str, update: bool) -> Result<(), String> {
+    let path = dir.join(format!("{name}.txt"));
+    if update {
+        fs::create_dir_all(dir).map_err(|e| format!("{}: {e}", dir.display()))?;
+        return fs::write(&path, actual).map_err(|e| format!("{}: {e}", path.display()));
+    }
+    let expected = fs::read_to_string(&path)
+        .map_err(|_| format!("{name}: no golden at {}", path.display()))?;
+    if expected == actual {
+        Ok(())
+    } else {
+        Err(format!("{name}: {}", describe_diff(&expected, actual)))
+    }
+}
+
+/// Golden files on disk with no matching case
+fn stale_goldens(dir: &Path, names: &BTreeSet<&str>) -> Vec<PathBuf> {
+    fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension().is_some_and(|ext| ext == "txt")
+                && path
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .is_some_and(|stem| !names.contains(stem))
+        })
+        .collect()
+}
+
+#[test]
+fn test_golden_error_messages() {
+    let dir = golden_dir();
+    let update = updating();
+    let cases = all_cases();
+    let mut failures: Vec<String> = cases
+        .iter()
+        .filter_map(|(name, error)| check_golden(&dir, name, &error.snapshot(), update).err())
+        .collect();
+
+    let names: BTreeSet<&str> = cases.iter().map(|(name, _)| *name).collect();
+    for path in stale_goldens(&dir, &names) {
+        if update {
+            fs::remove_file(&path).unwrap();
+        } else {
+            failures.push(format!("{}: golden has no test case", path.display()));
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "{} golden mismatch(es); if the new wording is intended, rerun with {UPDATE_ENV}=1\n\n{}",
+        failures.len(),
+        failures.join("\n")
+    );
+}
+
+#[test]
+fn test_golden_cases_cover_every_variant() {
+    let covered: BTreeSet<usize> = yaml_error_cases()
+        .iter()
+        .map(|(_, error)| variant_index(error))
+        .collect();
+    let missing: Vec<usize> = (0..YAML_ERROR_VARIANTS)
+        .filter(|i| !covered.contains(i))
+        .collect();
+    assert!(missing.is_empty(), "variants without a golden: {missing:?}");
+}
+
+#[test]
+fn test_golden_case_names_are_unique() {
+    let cases = all_cases();
+    let names: BTreeSet<&str> = cases.iter().map(|(name, _)| *name).collect();
+    assert_eq!(names.len(), cases.len());
+}
+
+#[test]
+fn test_golden_mismatch_reports_first_difference() {
+    let dir = std::env::temp_dir().join(format!("jugar-goldens-{}", std::process::id()));
+    check_golden(&dir, "case", "a\nb\nc\n", true).unwrap();
+    assert!(check_golden(&dir, "case", "a\nb\nc\n", false).is_ok());
+
+    let err = check_golden(&dir, "case", "a\nB\nc\n", false).unwrap_err();
+    assert!(err.contains("first difference at line 2"));
+    assert!(err.contains("- b\n+ B"));
+
+    let err = check_golden(&dir, "missing", "a\n", false).unwrap_err();
+    assert!(err.contains("no golden"));
+
+    let names = BTreeSet::from(["other"]);
+    assert_eq!(stale_goldens(&dir, &names), vec![dir.join("case.txt")]);
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -278,6 +278,53 @@ impl KidFriendlyError {
 
         output
     }
+
+    /// Canonical text form for golden-file tests
+    ///
+    /// Lists every field on its own line, then the [`render`](Self::render)
+    /// output with each line prefixed by `|` so blank lines show up in
+    /// diffs.
+    #[must_use]
+    pub fn snapshot(&self) -> String {
+        use core::fmt::Write;
+        let mut output = String::new();
+
+        let _ = writeln!(
+            output,
+            "helper: {} {} \"{}\"",
+            self.helper.name(),
+            self.helper.emoji(),
+            self.helper.phrase()
+        );
+        let _ = writeln!(output, "headline: {}", self.headline);
+        let _ = writeln!(output, "explanation: {}", self.explanation);
+        match &self.location {
+            Some(ErrorLocation {
+                line,
+                column: Some(column),
+            }) => {
+                let _ = writeln!(output, "location: line {line}, column {column}");
+            }
+            Some(ErrorLocation { line, column: None }) => {
+                let _ = writeln!(output, "location: line {line}");
+            }
+            None => output.push_str("location: none\n"),
+        }
+        output.push_str("suggestions:\n");
+        for suggestion in &self.suggestions {
+            let _ = writeln!(output, "  - {suggestion}");
+        }
+        output.push_str("rendered:\n");
+        for line in self.render().lines() {
+            let _ = writeln!(
+                output,
+                "  |{}{line}",
+                if line.is_empty() { "" } else { " " }
+            );
+        }
+
+        output
+    }
 }
 
 /// Location in the source YAML
@@ -314,6 +361,17 @@ impl HelperCharacter {
         }
     }
 
+    /// Get the lowercase name of this character
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Owl => "owl",
+            Self::Robot => "robot",
+            Self::Bunny => "bunny",
+            Self::Dragon => "dragon",
+        }
+    }
+
     /// Get a friendly phrase from this character
     #[must_use]
     pub const fn phrase(&self) -> &'static str {
@@ -369,6 +427,28 @@ mod tests {
         assert!(!HelperCharacter::Dragon.phrase().is_empty());
     }
 
+    #[test]
+    fn test_snapshot_lists_every_field() {
+        let err = KidFriendlyError {
+            headline: "Test headline".to_string(),
+            explanation: "Test explanation".to_string(),
+            location: Some(ErrorLocation {
+                line: 5,
+                column: None,
+            }),
+            suggestions: vec!["Try this".to_string()],
+            helper: HelperCharacter::Bunny,
+        };
+
+        let snapshot = err.snapshot();
+        assert!(snapshot.starts_with("helper: bunny 🐰"));
+        assert!(snapshot.contains("location: line 5\n"));
+        assert!(snapshot.contains("suggestions:\n  - Try this\n"));
+        assert!(snapshot.contains("  | 🐰 Test headline\n"));
+        // Blank render lines keep their prefix
+        assert!(snapshot.contains("\n  |\n"));
+    }
+
     #[test]
     fn test_kid_friendly_error_render_with_location() {
         let err = KidFriendlyError {
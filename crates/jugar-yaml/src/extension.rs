@@ -0,0 +1,307 @@
+//! Host extensions for the YAML compiler.
+//!
+//! A [`CompilerExtension`] teaches [`YamlCompiler`](crate::YamlCompiler)
+//! extra entity types, actions and vocabulary words, so a school can add
+//! "dinosaur" with its own sprite without forking the crate. Everything is
+//! checked when the extension is registered: a word that clashes with the
+//! built-in vocabulary or another extension is rejected up front rather
+//! than silently changing what existing games mean.
+
+use alloc::collections::BTreeMap;
+
+use crate::vocabulary::Vocabulary;
+
+/// An entity type added by an extension
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CustomEntityType {
+    /// Word used in YAML (`character: dinosaur`)
+    pub name: String,
+    /// Sprite the runtime draws for it
+    pub sprite: String,
+}
+
+impl CustomEntityType {
+    /// Creates an entity type
+    #[must_use]
+    pub fn new(name: impl Into<String>, sprite: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            sprite: sprite.into(),
+        }
+    }
+}
+
+/// Extra words a host registers with the compiler
+///
+/// Every method has an empty default, so an extension only implements what
+/// it adds.
+pub trait CompilerExtension {
+    /// Name shown when one of its words clashes
+    fn name(&self) -> &str;
+
+    /// New characters, usable anywhere a built-in character is
+    fn entity_types(&self) -> Vec<CustomEntityType> {
+        Vec::new()
+    }
+
+    /// New actions, usable as `- roar` in a rule's `then:` list
+    fn actions(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// New words as `(category, word)`, e.g. `("backgrounds", "volcano")`
+    fn words(&self) -> Vec<(String, String)> {
+        Vec::new()
+    }
+}
+
+/// Error registering an extension
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExtensionError {
+    /// The word is already built in
+    BuiltIn(String),
+    /// Another extension already registered the word
+    Taken {
+        /// The clashing word
+        word: String,
+        /// Extension that registered it first
+        owner: String,
+    },
+    /// The word is empty or uses characters other than a-z, 0-9, `_`, `-`
+    InvalidWord(String),
+    /// The category doesn't exist in the vocabulary
+    UnknownCategory(String),
+}
+
+impl core::fmt::Display for ExtensionError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::BuiltIn(word) => write!(f, "'{word}' is already a built-in word"),
+            Self::Taken { word, owner } => {
+                write!(f, "'{word}' was already added by the '{owner}' extension")
+            }
+            Self::InvalidWord(word) => write!(
+                f,
+                "'{word}' can only use lowercase letters, numbers, '_' and '-'"
+            ),
+            Self::UnknownCategory(category) => {
+                write!(f, "There is no '{category}' word list to add to")
+            }
+        }
+    }
+}
+
+impl core::error::Error for ExtensionError {}
+
+/// Category extension entity types are added to
+const ENTITY_CATEGORY: &str = "characters";
+
+/// Category extension actions are added to
+const ACTION_CATEGORY: &str = "actions";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CustomWord {
+    category: String,
+    owner: String,
+}
+
+/// Everything registered by extensions, merged
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CustomVocabulary {
+    words: BTreeMap<String, CustomWord>,
+    sprites: BTreeMap<String, String>,
+}
+
+impl CustomVocabulary {
+    /// Creates an empty set
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            words: BTreeMap::new(),
+            sprites: BTreeMap::new(),
+        }
+    }
+
+    /// Adds an extension's words, or none of them if any clash
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ExtensionError`] for the first word that is malformed,
+    /// built in, already registered or in an unknown category
+    pub fn register(&mut self, extension: &dyn CompilerExtension) -> Result<(), ExtensionError> {
+        let entity_types = extension.entity_types();
+        let added: Vec<(String, String)> = entity_types
+            .iter()
+            .map(|entity| (ENTITY_CATEGORY.to_string(), entity.name.clone()))
+            .chain(
+                extension
+                    .actions()
+                    .into_iter()
+                    .map(|action| (ACTION_CATEGORY.to_string(), action)),
+            )
+            .chain(extension.words())
+            .collect();
+
+        let built_in = Vocabulary::level3();
+        let mut seen = Vec::with_capacity(added.len());
+        for (category, word) in &added {
+            if word.is_empty()
+                || !word
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-')
+            {
+                return Err(ExtensionError::InvalidWord(word.clone()));
+            }
+            if built_in.words_in_category(category).is_empty() {
+                return Err(ExtensionError::UnknownCategory(category.clone()));
+            }
+            if built_in.contains(word) {
+                return Err(ExtensionError::BuiltIn(word.clone()));
+            }
+            if let Some(existing) = self.words.get(word) {
+                return Err(ExtensionError::Taken {
+                    word: word.clone(),
+                    owner: existing.owner.clone(),
+                });
+            }
+            if seen.contains(&word) {
+                return Err(ExtensionError::Taken {
+                    word: word.clone(),
+                    owner: extension.name().to_string(),
+                });
+            }
+            seen.push(word);
+        }
+
+        for (category, word) in added {
+            let _ = self.words.insert(
+                word,
+                CustomWord {
+                    category,
+                    owner: extension.name().to_string(),
+                },
+            );
+        }
+        for entity in entity_types {
+            let _ = self.sprites.insert(entity.name, entity.sprite);
+        }
+        Ok(())
+    }
+
+    /// Sprite for an extension entity type
+    #[must_use]
+    pub fn sprite(&self, entity_type: &str) -> Option<&str> {
+        self.sprites.get(entity_type).map(String::as_str)
+    }
+
+    /// Returns true if an extension registered this action
+    #[must_use]
+    pub fn has_action(&self, action: &str) -> bool {
+        self.words
+            .get(action)
+            .is_some_and(|word| word.category == ACTION_CATEGORY)
+    }
+
+    /// Returns true if nothing is registered
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Adds the registered words to a built-in vocabulary
+    pub fn apply(&self, vocab: &mut Vocabulary) {
+        for (word, custom) in &self.words {
+            vocab.add_word(&custom.category, word);
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    struct Dinosaurs;
+
+    impl CompilerExtension for Dinosaurs {
+        fn name(&self) -> &'static str {
+            "dinosaurs"
+        }
+
+        fn entity_types(&self) -> Vec<CustomEntityType> {
+            vec![CustomEntityType::new("dinosaur", "sprites/dino.png")]
+        }
+
+        fn actions(&self) -> Vec<String> {
+            vec!["roar".to_string()]
+        }
+
+        fn words(&self) -> Vec<(String, String)> {
+            vec![("backgrounds".to_string(), "volcano".to_string())]
+        }
+    }
+
+    struct Words(&'static str, Vec<(&'static str, &'static str)>);
+
+    impl CompilerExtension for Words {
+        fn name(&self) -> &str {
+            self.0
+        }
+
+        fn words(&self) -> Vec<(String, String)> {
+            self.1
+                .iter()
+                .map(|(category, word)| ((*category).to_string(), (*word).to_string()))
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_register_adds_words_to_vocabulary() {
+        let mut custom = CustomVocabulary::new();
+        custom.register(&Dinosaurs).unwrap();
+
+        let mut vocab = Vocabulary::level1();
+        custom.apply(&mut vocab);
+        assert!(vocab.is_valid_for_category("dinosaur", "characters"));
+        assert!(vocab.is_valid_for_category("volcano", "backgrounds"));
+        assert_eq!(custom.sprite("dinosaur"), Some("sprites/dino.png"));
+        assert!(custom.has_action("roar"));
+        assert!(!custom.has_action("volcano"));
+    }
+
+    #[test]
+    fn test_conflicts_rejected_at_registration() {
+        let mut custom = CustomVocabulary::new();
+        assert_eq!(
+            custom.register(&Words("shadow", vec![("characters", "bunny")])),
+            Err(ExtensionError::BuiltIn("bunny".to_string()))
+        );
+        custom.register(&Dinosaurs).unwrap();
+        assert_eq!(
+            custom.register(&Words("copycat", vec![("characters", "dinosaur")])),
+            Err(ExtensionError::Taken {
+                word: "dinosaur".to_string(),
+                owner: "dinosaurs".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_failed_registration_adds_nothing() {
+        let mut custom = CustomVocabulary::new();
+        let bad = Words(
+            "mixed",
+            vec![("backgrounds", "jungle"), ("sounds", "Boom!")],
+        );
+        assert_eq!(
+            custom.register(&bad),
+            Err(ExtensionError::InvalidWord("Boom!".to_string()))
+        );
+        assert!(custom.is_empty());
+        assert_eq!(
+            custom.register(&Words("lost", vec![("spells", "zap")])),
+            Err(ExtensionError::UnknownCategory("spells".to_string()))
+        );
+    }
+}
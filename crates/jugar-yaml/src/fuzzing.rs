@@ -0,0 +1,613 @@
+//! Structured game generators for fuzzing and property tests.
+//!
+//! Random bytes make poor YAML: nearly every input dies in the parser and
+//! the validator never runs. [`GeneratedGame`] builds documents from the
+//! real vocabulary instead, either within a schema level or just outside
+//! it (one misspelled word, one number out of range, ...), so fuzzers spend
+//! their time where kids' mistakes actually land. Editors can reuse the
+//! generators to test their own tooling against the same inputs.
+//!
+//! [`check_invariants`] holds the properties every document must satisfy,
+//! generated or not:
+//!
+//! - the compiler never panics
+//! - every error converts to a complete [`KidFriendlyError`]
+//! - compiling the [`format_game`] output gives the same result
+//!
+//! ```
+//! use arbitrary::{Arbitrary, Unstructured};
+//! use jugar_yaml::fuzzing::{check_game, GeneratedGame};
+//!
+//! let bytes = [7u8; 64];
+//! let game = GeneratedGame::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
+//! check_game(&game).unwrap();
+//! ```
+
+use core::mem::{discriminant, Discriminant};
+
+use arbitrary::{Arbitrary, Unstructured};
+use serde_yaml::{Mapping, Value};
+use thiserror::Error;
+
+use crate::{
+    format_game, CompiledGame, KidFriendlyError, SchemaLevel, Vocabulary, YamlCompiler, YamlError,
+};
+
+/// Names used for characters, entities and rule targets
+const NAMES: [&str; 4] = ["player", "enemy", "friend", "helper"];
+
+/// Game names that pass every content check
+const GAME_NAMES: [&str; 4] = [
+    "catch-the-stars",
+    "space-adventure",
+    "bunny-hop",
+    "star-chase",
+];
+
+/// A mistake that takes a document just outside its schema level
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flaw {
+    /// A vocabulary word with a typo
+    Misspelled,
+    /// A number past its limit
+    OutOfRange,
+    /// A required field left out
+    MissingField,
+    /// Nesting deeper than any level allows
+    TooDeep,
+    /// A list or word where a different kind of value belongs
+    WrongType,
+    /// A line the YAML parser can't read
+    BrokenSyntax,
+}
+
+/// Whether a generated document should compile
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shape {
+    /// Valid for its level
+    Within,
+    /// Invalid in exactly one way
+    JustOutside(Flaw),
+}
+
+impl Flaw {
+    /// Every flaw, in declaration order
+    pub const ALL: [Self; 6] = [
+        Self::Misspelled,
+        Self::OutOfRange,
+        Self::MissingField,
+        Self::TooDeep,
+        Self::WrongType,
+        Self::BrokenSyntax,
+    ];
+}
+
+impl<'a> Arbitrary<'a> for Flaw {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        u.choose(&Self::ALL).copied()
+    }
+}
+
+impl<'a> Arbitrary<'a> for Shape {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        // Half the documents are valid, the rest split between the flaws
+        if u.arbitrary()? {
+            Ok(Self::JustOutside(Flaw::arbitrary(u)?))
+        } else {
+            Ok(Self::Within)
+        }
+    }
+}
+
+/// A generated game document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GeneratedGame {
+    /// Level the document targets
+    pub level: SchemaLevel,
+    /// Whether it should compile
+    pub shape: Shape,
+    /// The document
+    pub yaml: String,
+}
+
+impl<'a> Arbitrary<'a> for GeneratedGame {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        let level = *u.choose(&[
+            SchemaLevel::Level1,
+            SchemaLevel::Level2,
+            SchemaLevel::Level3,
+        ])?;
+        let shape = Shape::arbitrary(u)?;
+        generate(level, shape, u)
+    }
+}
+
+/// Generates a document for `level` with the given shape
+///
+/// # Errors
+///
+/// Returns an error only if `u` can't supply a choice; an exhausted
+/// `Unstructured` falls back to the first option instead.
+pub fn generate(
+    level: SchemaLevel,
+    shape: Shape,
+    u: &mut Unstructured<'_>,
+) -> arbitrary::Result<GeneratedGame> {
+    let mut game = match level {
+        SchemaLevel::Level1 => level1(u)?,
+        SchemaLevel::Level2 => level2(u)?,
+        SchemaLevel::Level3 => level3(u)?,
+    };
+    let broken = if let Shape::JustOutside(flaw) = shape {
+        apply_flaw(&mut game, level, flaw)
+    } else {
+        false
+    };
+    let mut yaml = serde_yaml::to_string(&Value::Mapping(game)).unwrap_or_default();
+    if broken {
+        yaml.push_str("extra: [unclosed\n");
+    }
+    Ok(GeneratedGame { level, shape, yaml })
+}
+
+/// A broken property of the compiler
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum InvariantViolation {
+    /// The compiler panicked
+    #[error("compiler panicked on:\n{yaml}")]
+    Panicked {
+        /// Input
+        yaml: String,
+    },
+
+    /// An error had no kid-friendly explanation
+    #[error("error isn't kid-friendly ({reason}): {error}")]
+    NotKidFriendly {
+        /// The compiler error
+        error: String,
+        /// What was missing
+        reason: &'static str,
+    },
+
+    /// Formatting changed the compile result
+    #[error("compile result changed after formatting:\n{yaml}\nformatted:\n{formatted}")]
+    RoundTrip {
+        /// Input
+        yaml: String,
+        /// Formatted input
+        formatted: String,
+    },
+
+    /// A generated document compiled differently than its shape says
+    #[error("expected {expected} for {level:?} document:\n{yaml}\ngot: {outcome}")]
+    UnexpectedOutcome {
+        /// Level the document targets
+        level: SchemaLevel,
+        /// What should have happened
+        expected: &'static str,
+        /// What happened
+        outcome: String,
+        /// Input
+        yaml: String,
+    },
+}
+
+/// Checks the properties every document must satisfy
+///
+/// # Errors
+///
+/// Returns the first property that doesn't hold.
+pub fn check_invariants(yaml: &str) -> Result<(), InvariantViolation> {
+    let first = compile_guarded(yaml)?;
+    if let Err(error) = &first {
+        check_kid_friendly(&error.to_kid_friendly(), &error.to_string())?;
+    }
+    match format_game(yaml) {
+        Ok(formatted) => {
+            let second = compile_guarded(&formatted)?;
+            if summary(&first) != summary(&second)
+                || format_game(&formatted).ok().as_ref() != Some(&formatted)
+            {
+                return Err(InvariantViolation::RoundTrip {
+                    yaml: yaml.to_string(),
+                    formatted,
+                });
+            }
+        }
+        // A document the formatter can't read must not compile either
+        Err(_) if first.is_ok() => {
+            return Err(InvariantViolation::RoundTrip {
+                yaml: yaml.to_string(),
+                formatted: String::new(),
+            });
+        }
+        Err(_) => {}
+    }
+    Ok(())
+}
+
+/// Checks [`check_invariants`], then that the document compiles at its
+/// level if it's [`Shape::Within`] and is rejected otherwise
+///
+/// # Errors
+///
+/// Returns the first property that doesn't hold.
+pub fn check_game(game: &GeneratedGame) -> Result<(), InvariantViolation> {
+    check_invariants(&game.yaml)?;
+    let outcome = compile_guarded(&game.yaml)?;
+    let unexpected = |expected, outcome: String| InvariantViolation::UnexpectedOutcome {
+        level: game.level,
+        expected,
+        outcome,
+        yaml: game.yaml.clone(),
+    };
+    match (game.shape, outcome) {
+        (Shape::Within, Ok(compiled)) if compiled.level != game.level => Err(unexpected(
+            "the same level",
+            format!("{:?}", compiled.level),
+        )),
+        (Shape::Within, Err(error)) => Err(unexpected("success", error.to_string())),
+        (Shape::JustOutside(_), Ok(compiled)) => {
+            Err(unexpected("an error", format!("{:?}", compiled.level)))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Compiles, turning a panic into a violation
+fn compile_guarded(yaml: &str) -> Result<crate::Result<CompiledGame>, InvariantViolation> {
+    std::panic::catch_unwind(|| YamlCompiler::new().compile(yaml)).map_err(|_| {
+        InvariantViolation::Panicked {
+            yaml: yaml.to_string(),
+        }
+    })
+}
+
+fn check_kid_friendly(friendly: &KidFriendlyError, error: &str) -> Result<(), InvariantViolation> {
+    let reason = if friendly.headline.trim().is_empty() {
+        "empty headline"
+    } else if friendly.headline.contains('\n') {
+        "headline spans lines"
+    } else if friendly.explanation.trim().is_empty() {
+        "empty explanation"
+    } else {
+        return Ok(());
+    };
+    Err(InvariantViolation::NotKidFriendly {
+        error: error.to_string(),
+        reason,
+    })
+}
+
+/// What must survive formatting: the outcome, not line numbers
+///
+/// Errors compare by kind only, since characters are checked in hash
+/// order and the first bad one may differ between runs
+fn summary(result: &crate::Result<CompiledGame>) -> Result<String, Discriminant<YamlError>> {
+    match result {
+        Ok(game) => {
+            let mut ids: Vec<&str> = game.entities.iter().map(|e| e.id.as_str()).collect();
+            ids.sort_unstable();
+            Ok(format!(
+                "{} {:?} {ids:?} {} rules",
+                game.name,
+                game.level,
+                game.rules.len()
+            ))
+        }
+        Err(error) => Err(discriminant(error)),
+    }
+}
+
+fn word(
+    u: &mut Unstructured<'_>,
+    vocab: &Vocabulary,
+    categories: &[&str],
+) -> arbitrary::Result<String> {
+    let words: Vec<String> = categories
+        .iter()
+        .flat_map(|category| vocab.words_in_category(category))
+        .collect();
+    Ok(u.choose(&words)?.clone())
+}
+
+fn insert(map: &mut Mapping, key: &str, value: impl Into<Value>) {
+    let _ = map.insert(Value::from(key), value.into());
+}
+
+fn level1(u: &mut Unstructured<'_>) -> arbitrary::Result<Mapping> {
+    let vocab = Vocabulary::level1();
+    let mut game = Mapping::new();
+    insert(&mut game, "character", word(u, &vocab, &["characters"])?);
+    if u.arbitrary()? {
+        insert(&mut game, "game", *u.choose(&GAME_NAMES)?);
+    }
+    for (key, category) in [
+        ("move", "movement"),
+        ("background", "backgrounds"),
+        ("music", "music"),
+        ("color", "colors"),
+    ] {
+        if u.arbitrary()? {
+            insert(&mut game, key, word(u, &vocab, &[category])?);
+        }
+    }
+    if u.arbitrary()? {
+        let mut touch = Mapping::new();
+        insert(&mut touch, "target", word(u, &vocab, &["targets"])?);
+        if u.arbitrary()? {
+            insert(&mut touch, "sound", word(u, &vocab, &["sounds"])?);
+        }
+        if u.arbitrary()? {
+            insert(&mut touch, "score", u.int_in_range(-9i64..=9)?);
+        }
+        if u.arbitrary()? {
+            insert(
+                &mut touch,
+                "target_action",
+                word(u, &vocab, &["target_actions"])?,
+            );
+        }
+        insert(&mut game, "when_touch", touch);
+    }
+    Ok(game)
+}
+
+fn level2(u: &mut Unstructured<'_>) -> arbitrary::Result<Mapping> {
+    let vocab = Vocabulary::level2();
+    let mut game = Mapping::new();
+    if u.arbitrary()? {
+        insert(&mut game, "game", *u.choose(&GAME_NAMES)?);
+    }
+    let mut characters = Mapping::new();
+    for name in NAMES.iter().take(u.int_in_range(1..=3)?) {
+        let mut character = Mapping::new();
+        insert(
+            &mut character,
+            "type",
+            word(u, &vocab, &["characters", "characters_l2"])?,
+        );
+        for (key, category) in [
+            ("move", "movement"),
+            ("speed", "speed"),
+            ("pattern", "patterns"),
+        ] {
+            if u.arbitrary()? {
+                insert(&mut character, key, word(u, &vocab, &[category])?);
+            }
+        }
+        if u.arbitrary()? {
+            insert(&mut character, "health", u.int_in_range(1u64..=100)?);
+        }
+        insert(&mut characters, name, character);
+    }
+    insert(&mut game, "characters", characters);
+    if u.arbitrary()? {
+        insert(&mut game, "lives", u.int_in_range(1u64..=9)?);
+    }
+    if u.arbitrary()? {
+        insert(&mut game, "score_goal", u.int_in_range(1u64..=1000)?);
+    }
+    if u.arbitrary()? {
+        insert(
+            &mut game,
+            "win_when",
+            format!("score reaches {}", u.int_in_range(1..=100)?),
+        );
+    }
+    if u.arbitrary()? {
+        let background = word(u, &vocab, &["backgrounds"])?;
+        insert(&mut game, "background", background);
+    }
+    insert(&mut game, "rules", rules(u, &vocab)?);
+    Ok(game)
+}
+
+fn level3(u: &mut Unstructured<'_>) -> arbitrary::Result<Mapping> {
+    let vocab = Vocabulary::level3();
+    let mut game = Mapping::new();
+    insert(&mut game, "version", 1);
+    if u.arbitrary()? {
+        insert(&mut game, "game", *u.choose(&GAME_NAMES)?);
+    }
+    let mut entities = Mapping::new();
+    for name in NAMES.iter().take(u.int_in_range(1..=3)?) {
+        let mut components = Mapping::new();
+        let position = vec![
+            Value::from(u.int_in_range(0u64..=800)?),
+            Value::from(u.int_in_range(0u64..=600)?),
+        ];
+        insert(&mut components, "position", position);
+        if u.arbitrary()? {
+            insert(&mut components, "health", u.int_in_range(1u64..=100)?);
+        }
+        let mut entity = Mapping::new();
+        insert(
+            &mut entity,
+            "sprite",
+            word(u, &vocab, &["characters", "characters_l2"])?,
+        );
+        insert(&mut entity, "components", components);
+        insert(&mut entities, name, entity);
+    }
+    insert(&mut game, "entities", entities);
+    if u.arbitrary()? {
+        let mut world = Mapping::new();
+        insert(&mut world, "type", "procedural");
+        insert(&mut world, "algorithm", "wfc");
+        insert(&mut world, "seed", u.int_in_range(0u64..=9999)?);
+        let size = u.int_in_range(8u64..=64)?;
+        insert(
+            &mut world,
+            "size",
+            vec![Value::from(size), Value::from(size)],
+        );
+        insert(&mut game, "world", world);
+    }
+    if u.arbitrary()? {
+        insert(&mut game, "lives", u.int_in_range(1u64..=9)?);
+    }
+    if u.arbitrary()? {
+        insert(
+            &mut game,
+            "win_when",
+            format!("score reaches {}", u.int_in_range(1..=100)?),
+        );
+    }
+    insert(&mut game, "rules", rules(u, &vocab)?);
+    Ok(game)
+}
+
+fn rules(u: &mut Unstructured<'_>, vocab: &Vocabulary) -> arbitrary::Result<Vec<Value>> {
+    let mut rules = Vec::new();
+    for _ in 0..u.int_in_range(0..=3)? {
+        let mut rule = Mapping::new();
+        let when = if u.arbitrary()? {
+            format!("player touches {}", word(u, vocab, &["targets"])?)
+        } else {
+            format!("score reaches {}", u.int_in_range(1..=100)?)
+        };
+        insert(&mut rule, "when", when);
+        let mut then = Vec::new();
+        for _ in 0..u.int_in_range(1..=3)? {
+            let mut action = Mapping::new();
+            match u.int_in_range(0..=3)? {
+                0 => insert(&mut action, "add_score", u.int_in_range(1u64..=100)?),
+                1 => insert(&mut action, "lose_life", 1),
+                2 => insert(&mut action, "play", word(u, vocab, &["sounds"])?),
+                _ => insert(&mut action, "show", "win"),
+            }
+            then.push(Value::Mapping(action));
+        }
+        insert(&mut rule, "then", then);
+        rules.push(Value::Mapping(rule));
+    }
+    Ok(rules)
+}
+
+/// The word with its last letter doubled, e.g. "bunnyy"
+fn misspell(word: &str) -> String {
+    let last = word.chars().last().unwrap_or('x');
+    format!("{word}{last}")
+}
+
+/// Breaks `game` in one way; returns true if the text must be garbled too
+fn apply_flaw(game: &mut Mapping, level: SchemaLevel, flaw: Flaw) -> bool {
+    match (flaw, level) {
+        (Flaw::Misspelled, SchemaLevel::Level1) => {
+            if let Some(Value::String(character)) = game.get_mut("character") {
+                *character = misspell(character);
+            }
+        }
+        (Flaw::Misspelled, SchemaLevel::Level2) => {
+            if let Some(Value::Mapping(characters)) = game.get_mut("characters") {
+                for (_, character) in characters.iter_mut() {
+                    if let Some(Value::String(kind)) = character.get_mut("type") {
+                        *kind = misspell(kind);
+                    }
+                }
+            }
+        }
+        (Flaw::Misspelled, SchemaLevel::Level3) => insert(game, "win_when", "score reachs 10"),
+        (Flaw::OutOfRange, SchemaLevel::Level1) => {
+            let mut touch = Mapping::new();
+            insert(&mut touch, "target", "star");
+            insert(&mut touch, "score", 10);
+            insert(game, "when_touch", touch);
+        }
+        (Flaw::OutOfRange, SchemaLevel::Level2) => insert(game, "lives", 10),
+        (Flaw::OutOfRange, SchemaLevel::Level3) => insert(game, "lives", 300),
+        (Flaw::MissingField, SchemaLevel::Level1) => {
+            let _ = game.remove("character");
+        }
+        (Flaw::MissingField, _) => {
+            let mut rule = Mapping::new();
+            insert(&mut rule, "when", "player touches star");
+            insert(game, "rules", vec![Value::Mapping(rule)]);
+        }
+        (Flaw::TooDeep, _) => {
+            let mut deep = Value::from("sky");
+            for key in ["a", "b", "c", "d", "e", "f", "g", "h"] {
+                let mut map = Mapping::new();
+                insert(&mut map, key, deep);
+                deep = Value::Mapping(map);
+            }
+            insert(game, "background", deep);
+        }
+        (Flaw::WrongType, SchemaLevel::Level1) => {
+            insert(game, "character", vec![Value::from("bunny")]);
+        }
+        (Flaw::WrongType, _) => insert(game, "lives", "lots"),
+        (Flaw::BrokenSyntax, _) => return true,
+    }
+    false
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const LEVELS: [SchemaLevel; 3] = [
+        SchemaLevel::Level1,
+        SchemaLevel::Level2,
+        SchemaLevel::Level3,
+    ];
+
+    #[test]
+    fn test_every_level_and_flaw_from_empty_input() {
+        // An exhausted Unstructured picks the first option everywhere
+        for level in LEVELS {
+            let game = generate(level, Shape::Within, &mut Unstructured::new(&[])).unwrap();
+            check_game(&game).unwrap();
+            for flaw in Flaw::ALL {
+                let game =
+                    generate(level, Shape::JustOutside(flaw), &mut Unstructured::new(&[])).unwrap();
+                check_game(&game).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn test_invariants_hold_for_hand_written_input() {
+        for yaml in [
+            "character: bunny",
+            "Character: Bunny",
+            "",
+            ":",
+            "- - - -",
+            "character: [",
+            "characters:\n  player:\n    type: bunny\nlives: 0",
+            "version: 1\nwin_when: nobody wins",
+        ] {
+            check_invariants(yaml).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_check_game_catches_wrong_shape() {
+        let game = GeneratedGame {
+            level: SchemaLevel::Level1,
+            shape: Shape::Within,
+            yaml: "character: dinosaur".to_string(),
+        };
+        assert!(matches!(
+            check_game(&game),
+            Err(InvariantViolation::UnexpectedOutcome { .. })
+        ));
+    }
+
+    proptest! {
+        #[test]
+        fn property_generated_games_hold_invariants(bytes in proptest::collection::vec(any::<u8>(), 0..512)) {
+            let game = GeneratedGame::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
+            prop_assert!(check_game(&game).is_ok(), "{:?}", check_game(&game));
+        }
+
+        #[test]
+        fn property_arbitrary_text_never_panics(yaml in "[a-z_:\\- \\n\\[\\]{},0-9]{0,200}") {
+            prop_assert!(check_invariants(&yaml).is_ok(), "{:?}", check_invariants(&yaml));
+        }
+    }
+}
@@ -0,0 +1,524 @@
+//! Undo and redo for YAML editing sessions.
+//!
+//! [`EditHistory`] owns the text being edited and records every change as a
+//! splice (a byte range replaced with new text), so memory grows with the
+//! size of the edits rather than the document. Typing is coalesced one word
+//! at a time, so undo takes back "bunny" instead of "y". Once the stored
+//! edits pass the byte budget, the oldest steps are forgotten.
+//!
+//! Kids can also name a checkpoint ("before I broke it") and jump back to
+//! it, and [`EditHistory::record_preview`] remembers the last text that
+//! compiled so there is always a working game to return to. Both jumps are
+//! ordinary edits, so they can be undone too.
+//!
+//! ```
+//! use jugar_yaml::EditHistory;
+//!
+//! let mut history = EditHistory::new("character: bunny");
+//! history.set_text("character: cat");
+//! assert!(history.undo());
+//! assert_eq!(history.text(), "character: bunny");
+//! assert!(history.redo());
+//! assert_eq!(history.text(), "character: cat");
+//! ```
+
+use alloc::collections::VecDeque;
+use core::ops::Range;
+
+use crate::preview::PreviewResult;
+
+/// Default bytes of edits kept for undo and redo
+pub const DEFAULT_HISTORY_BYTES: usize = 64 * 1024;
+
+/// Most named checkpoints kept; the oldest is dropped first
+pub const MAX_CHECKPOINTS: usize = 16;
+
+/// History error
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HistoryError {
+    /// The range is outside the text or splits a character
+    InvalidRange {
+        /// Range start in bytes
+        start: usize,
+        /// Range end in bytes
+        end: usize,
+        /// Text length in bytes
+        len: usize,
+    },
+    /// No checkpoint with this name
+    UnknownCheckpoint(String),
+}
+
+impl core::fmt::Display for HistoryError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::InvalidRange { start, end, len } => {
+                write!(f, "Can't edit {start}..{end} of a {len}-byte text")
+            }
+            Self::UnknownCheckpoint(name) => write!(f, "There's no save point called '{name}'"),
+        }
+    }
+}
+
+impl core::error::Error for HistoryError {}
+
+/// One undo step: `removed` at `start` was replaced by `inserted`
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TextEdit {
+    start: usize,
+    removed: String,
+    inserted: String,
+}
+
+impl TextEdit {
+    fn apply(&self, text: &mut String) {
+        text.replace_range(self.start..self.start + self.removed.len(), &self.inserted);
+    }
+
+    fn revert(&self, text: &mut String) {
+        text.replace_range(self.start..self.start + self.inserted.len(), &self.removed);
+    }
+
+    fn bytes(&self) -> usize {
+        self.removed.len() + self.inserted.len()
+    }
+
+    /// Folds a keystroke that continues this one into it
+    ///
+    /// Single-character inserts extend a run until a word ends (whitespace
+    /// was typed); single-character backspaces and deletes extend a run
+    /// until a line is joined.
+    fn merge(&mut self, next: &Self) -> bool {
+        let single = |text: &str| text.chars().count() == 1;
+        if self.removed.is_empty() && next.removed.is_empty() {
+            let continues = next.start == self.start + self.inserted.len()
+                && single(&next.inserted)
+                && !self.inserted.ends_with(char::is_whitespace);
+            if continues {
+                self.inserted.push_str(&next.inserted);
+            }
+            return continues;
+        }
+        if self.inserted.is_empty()
+            && next.inserted.is_empty()
+            && single(&next.removed)
+            && next.removed != "\n"
+        {
+            if next.start + next.removed.len() == self.start {
+                // Backspace
+                self.start = next.start;
+                self.removed.insert_str(0, &next.removed);
+                return true;
+            }
+            if next.start == self.start {
+                // Forward delete
+                self.removed.push_str(&next.removed);
+                return true;
+            }
+        }
+        false
+    }
+}
+
+/// A named snapshot of the text
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Checkpoint {
+    name: String,
+    text: String,
+}
+
+/// Undo/redo history of a YAML document
+#[derive(Debug, Clone)]
+pub struct EditHistory {
+    text: String,
+    undo: VecDeque<TextEdit>,
+    redo: Vec<TextEdit>,
+    /// Bytes held by `undo` and `redo`
+    bytes: usize,
+    max_bytes: usize,
+    /// The next edit starts a new undo step
+    sealed: bool,
+    checkpoints: VecDeque<Checkpoint>,
+    last_working: Option<String>,
+}
+
+impl Default for EditHistory {
+    fn default() -> Self {
+        Self::new("")
+    }
+}
+
+impl EditHistory {
+    /// Starts a history for `text` with the default memory budget
+    #[must_use]
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            bytes: 0,
+            max_bytes: DEFAULT_HISTORY_BYTES,
+            sealed: true,
+            checkpoints: VecDeque::new(),
+            last_working: None,
+        }
+    }
+
+    /// Sets the bytes of edits kept for undo and redo
+    ///
+    /// The most recent step is always kept, however large.
+    #[must_use]
+    pub fn with_max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = max_bytes;
+        self.trim();
+        self
+    }
+
+    /// The current text
+    #[must_use]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Replaces `range` (in bytes) with `text`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`HistoryError::InvalidRange`] if the range is reversed, past
+    /// the end, or not on character boundaries.
+    pub fn replace(&mut self, range: Range<usize>, text: &str) -> Result<(), HistoryError> {
+        let valid = range.start <= range.end
+            && self.text.is_char_boundary(range.start)
+            && self.text.is_char_boundary(range.end);
+        if !valid {
+            return Err(HistoryError::InvalidRange {
+                start: range.start,
+                end: range.end,
+                len: self.text.len(),
+            });
+        }
+        if range.is_empty() && text.is_empty() {
+            return Ok(());
+        }
+        self.push(TextEdit {
+            start: range.start,
+            removed: self.text[range].to_string(),
+            inserted: text.to_string(),
+        });
+        Ok(())
+    }
+
+    /// Inserts `text` at byte offset `at`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`HistoryError::InvalidRange`] if `at` is not a character
+    /// boundary of the text.
+    pub fn insert(&mut self, at: usize, text: &str) -> Result<(), HistoryError> {
+        self.replace(at..at, text)
+    }
+
+    /// Deletes the bytes in `range`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`HistoryError::InvalidRange`] for a range [`Self::replace`]
+    /// would reject.
+    pub fn delete(&mut self, range: Range<usize>) -> Result<(), HistoryError> {
+        self.replace(range, "")
+    }
+
+    /// Replaces the whole text, recording only the part that changed
+    ///
+    /// For editors that report the full document on every keystroke.
+    pub fn set_text(&mut self, text: &str) {
+        let prefix: usize = self
+            .text
+            .chars()
+            .zip(text.chars())
+            .take_while(|(old, new)| old == new)
+            .map(|(old, _)| old.len_utf8())
+            .sum();
+        let suffix: usize = self.text[prefix..]
+            .chars()
+            .rev()
+            .zip(text[prefix..].chars().rev())
+            .take_while(|(old, new)| old == new)
+            .map(|(old, _)| old.len_utf8())
+            .sum();
+        let removed = &self.text[prefix..self.text.len() - suffix];
+        let inserted = &text[prefix..text.len() - suffix];
+        if removed.is_empty() && inserted.is_empty() {
+            return;
+        }
+        let edit = TextEdit {
+            start: prefix,
+            removed: removed.to_string(),
+            inserted: inserted.to_string(),
+        };
+        self.push(edit);
+    }
+
+    /// Ends the current undo step, so the next edit starts a new one
+    ///
+    /// Editors call this when the cursor jumps or typing pauses.
+    pub fn seal(&mut self) {
+        self.sealed = true;
+    }
+
+    /// Returns true if there is an edit to undo
+    #[must_use]
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    /// Returns true if there is an undone edit to redo
+    #[must_use]
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Takes back the last undo step; returns false if there is none
+    pub fn undo(&mut self) -> bool {
+        let Some(edit) = self.undo.pop_back() else {
+            return false;
+        };
+        edit.revert(&mut self.text);
+        self.redo.push(edit);
+        self.sealed = true;
+        true
+    }
+
+    /// Re-applies the last undone step; returns false if there is none
+    pub fn redo(&mut self) -> bool {
+        let Some(edit) = self.redo.pop() else {
+            return false;
+        };
+        edit.apply(&mut self.text);
+        self.undo.push_back(edit);
+        self.sealed = true;
+        true
+    }
+
+    /// Saves the current text under `name`, replacing any checkpoint with
+    /// that name
+    pub fn checkpoint(&mut self, name: impl Into<String>) {
+        let name = name.into();
+        self.checkpoints
+            .retain(|checkpoint| checkpoint.name != name);
+        if self.checkpoints.len() == MAX_CHECKPOINTS {
+            let _ = self.checkpoints.pop_front();
+        }
+        self.checkpoints.push_back(Checkpoint {
+            name,
+            text: self.text.clone(),
+        });
+        self.sealed = true;
+    }
+
+    /// Checkpoint names, oldest first
+    pub fn checkpoints(&self) -> impl Iterator<Item = &str> {
+        self.checkpoints
+            .iter()
+            .map(|checkpoint| checkpoint.name.as_str())
+    }
+
+    /// Goes back to a checkpoint as one undoable step
+    ///
+    /// # Errors
+    ///
+    /// Returns [`HistoryError::UnknownCheckpoint`] if there is no
+    /// checkpoint called `name`.
+    pub fn restore(&mut self, name: &str) -> Result<(), HistoryError> {
+        let text = self
+            .checkpoints
+            .iter()
+            .find(|checkpoint| checkpoint.name == name)
+            .map(|checkpoint| checkpoint.text.clone())
+            .ok_or_else(|| HistoryError::UnknownCheckpoint(name.to_string()))?;
+        self.jump_to(&text);
+        Ok(())
+    }
+
+    /// Remembers the current text if `result` is a successful compile of it
+    ///
+    /// Pass every [`PreviewResult`] for this history's text; errors and
+    /// debounced results are ignored.
+    pub fn record_preview(&mut self, result: &PreviewResult) {
+        if result.is_success() {
+            self.last_working = Some(self.text.clone());
+        }
+    }
+
+    /// The last text that compiled, if any
+    #[must_use]
+    pub fn last_working(&self) -> Option<&str> {
+        self.last_working.as_deref()
+    }
+
+    /// Goes back to the last text that compiled as one undoable step
+    ///
+    /// Returns false if nothing has compiled yet.
+    pub fn restore_last_working(&mut self) -> bool {
+        let Some(text) = self.last_working.clone() else {
+            return false;
+        };
+        self.jump_to(&text);
+        true
+    }
+
+    /// Replaces the text as a step of its own
+    fn jump_to(&mut self, text: &str) {
+        self.sealed = true;
+        self.set_text(text);
+        self.sealed = true;
+    }
+
+    fn push(&mut self, edit: TextEdit) {
+        edit.apply(&mut self.text);
+        self.bytes -= self.redo.drain(..).map(|edit| edit.bytes()).sum::<usize>();
+
+        let merged = match self.undo.back_mut() {
+            Some(last) if !self.sealed => {
+                let before = last.bytes();
+                let merged = last.merge(&edit);
+                self.bytes = self.bytes - before + last.bytes();
+                merged
+            }
+            _ => false,
+        };
+        if !merged {
+            self.bytes += edit.bytes();
+            self.undo.push_back(edit);
+        }
+        self.sealed = false;
+        self.trim();
+    }
+
+    /// Forgets the oldest steps until the edits fit the budget
+    fn trim(&mut self) {
+        while self.bytes > self.max_bytes && self.undo.len() > 1 {
+            if let Some(oldest) = self.undo.pop_front() {
+                self.bytes -= oldest.bytes();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::preview::LivePreview;
+
+    fn typed(history: &mut EditHistory, text: &str) {
+        for c in text.chars() {
+            let end = history.text().len();
+            history.insert(end, &c.to_string()).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_typing_undoes_a_word_at_a_time() {
+        let mut history = EditHistory::new("character: ");
+        typed(&mut history, "big bunny");
+        assert!(history.undo());
+        assert_eq!(history.text(), "character: big ");
+        assert!(history.undo());
+        assert_eq!(history.text(), "character: ");
+        assert!(!history.undo());
+
+        assert!(history.redo());
+        assert!(history.redo());
+        assert_eq!(history.text(), "character: big bunny");
+        assert!(!history.can_redo());
+
+        // Backspacing a word is one step too
+        for _ in 0..5 {
+            let end = history.text().len();
+            history.delete(end - 1..end).unwrap();
+        }
+        assert_eq!(history.text(), "character: big ");
+        assert!(history.undo());
+        assert_eq!(history.text(), "character: big bunny");
+    }
+
+    #[test]
+    fn test_set_text_records_the_change_and_clears_redo() {
+        let mut history = EditHistory::new("character: bunny\nlives: 3\n");
+        history.set_text("character: 🐰\nlives: 3\n");
+        history.seal();
+        history.set_text("character: 🐰\nlives: 5\n");
+        assert!(history.undo());
+        assert!(history.can_redo());
+
+        history.set_text("character: 🐰\nlives: 1\n");
+        assert!(!history.can_redo());
+        assert!(history.undo());
+        assert!(history.undo());
+        assert_eq!(history.text(), "character: bunny\nlives: 3\n");
+
+        assert!(matches!(
+            history.insert(100, "x"),
+            Err(HistoryError::InvalidRange { start: 100, .. })
+        ));
+    }
+
+    #[test]
+    fn test_memory_budget_drops_oldest_steps() {
+        let mut history = EditHistory::new("").with_max_bytes(10);
+        for word in ["aaaa", "bbbb", "cccc"] {
+            let end = history.text().len();
+            history.insert(end, word).unwrap();
+        }
+        assert!(history.undo());
+        assert!(history.undo());
+        assert_eq!(history.text(), "aaaa");
+        // The first step no longer fits
+        assert!(!history.undo());
+
+        // An oversized edit is still undoable
+        history.set_text("a much longer document");
+        assert!(history.undo());
+        assert_eq!(history.text(), "aaaa");
+    }
+
+    #[test]
+    fn test_checkpoints_restore_as_undoable_steps() {
+        let mut history = EditHistory::new("character: bunny");
+        history.checkpoint("before I broke it");
+        history.set_text("character: [oops");
+        history.checkpoint("broken");
+
+        history.restore("before I broke it").unwrap();
+        assert_eq!(history.text(), "character: bunny");
+        assert!(history.undo());
+        assert_eq!(history.text(), "character: [oops");
+        assert_eq!(
+            history.checkpoints().collect::<Vec<_>>(),
+            ["before I broke it", "broken"]
+        );
+        assert_eq!(
+            history.restore("nope"),
+            Err(HistoryError::UnknownCheckpoint("nope".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_live_preview_remembers_the_last_working_game() {
+        let mut history = EditHistory::new("character: bunny");
+        let mut preview = LivePreview::new();
+        assert!(!history.restore_last_working());
+
+        let result = preview.on_history_change(&mut history);
+        assert!(result.is_success());
+        assert_eq!(history.last_working(), Some("character: bunny"));
+
+        history.set_text("character: [bunny");
+        history.record_preview(&preview.compile_now(history.text()));
+        assert_eq!(history.last_working(), Some("character: bunny"));
+
+        assert!(history.restore_last_working());
+        assert_eq!(history.text(), "character: bunny");
+    }
+}
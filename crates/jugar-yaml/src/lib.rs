@@ -29,9 +29,18 @@
 #![warn(clippy::pedantic)]
 #![allow(clippy::module_name_repetitions)]
 
+extern crate alloc;
+
 pub mod accessibility;
+pub mod classroom;
 pub mod compiler;
 pub mod error;
+pub mod extension;
+#[cfg(feature = "fuzzing")]
+pub mod fuzzing;
+pub mod history;
+pub mod mastery;
+pub mod merge;
 pub mod migration;
 #[allow(
     clippy::std_instead_of_core,
@@ -45,14 +54,34 @@ pub mod safety;
 pub mod sandbox;
 pub mod scaffolding;
 pub mod schema;
+pub mod scoreboard;
 pub mod scripting;
 pub mod sharing;
+pub mod telemetry;
 pub mod tutorial;
 pub mod vocabulary;
 
-pub use accessibility::{AccessibilityCode, AccessibilityReport, AccessibilityValidator};
-pub use compiler::YamlCompiler;
+#[cfg(test)]
+mod diagnostics_tests;
+
+pub use accessibility::{
+    AccessibilityCode, AccessibilityReport, AccessibilityValidator, GameAccessibilityInfo,
+};
+pub use classroom::{
+    Classroom, ClassroomError, ClassroomPolicy, ClassroomReport, ProjectReport, ProjectStatus,
+    StudentProject,
+};
+pub use compiler::{CancelToken, CompileBudget, CompileOutcome, CompileStage, YamlCompiler};
 pub use error::{HelperCharacter, KidFriendlyError, YamlError};
+pub use extension::{CompilerExtension, CustomEntityType, CustomVocabulary, ExtensionError};
+pub use history::{EditHistory, HistoryError, DEFAULT_HISTORY_BYTES, MAX_CHECKPOINTS};
+pub use mastery::{
+    Concept, ConceptMastery, MasteryError, MasteryReport, MASTERY_METRIC, MIN_COHORT_SIZE,
+    STRUGGLE_HINTS,
+};
+pub use merge::{
+    diff_games, merge_games, ChangeKind, GameChange, GameDiff, MergeConflict, MergeResult,
+};
 pub use migration::{
     HintCategory, MigratableGame, Migrate, MigratedGame, MigratedLevel2Game, MigratedLevel3Game,
     MigrationError, MigrationHint,
@@ -62,20 +91,42 @@ pub use preview::{
     DEFAULT_DEBOUNCE_MS,
 };
 pub use privacy::{
-    ComplianceLevel, DifferentialPrivacy, DifferentialPrivacyConfig, LocalAnalytics,
-    NoisyAnalytics, PrivacyConfig, PrivacyValidator, RetentionMetrics,
+    BudgetReport, ComplianceLevel, DifferentialPrivacy, DifferentialPrivacyConfig, LocalAnalytics,
+    MetricBudget, NoisyAnalytics, PrivacyBudget, PrivacyBudgetError, PrivacyConfig,
+    PrivacyValidator, RetentionMetrics, DEFAULT_PRIVACY_BUDGET, PRIVACY_BUDGET_KEY,
 };
 pub use safety::{FlashInfo, PhotosensitivityGuard, SafetyResult};
-pub use sandbox::{ContentFilter, ContentSandbox, SandboxError, MAX_ENTITIES, MAX_YAML_SIZE};
+pub use sandbox::{
+    ContentFilter, ContentSandbox, QuotaExceeded, QuotaKind, RuntimeGuard, RuntimeQuotas,
+    SandboxError, MAX_ENTITIES, MAX_RULES_PER_FRAME, MAX_SOUNDS_PER_SECOND, MAX_SPAWNS_PER_SECOND,
+    MAX_YAML_SIZE,
+};
 pub use scaffolding::{Correction, Intent, Scaffold, ScaffoldedError, ScaffoldingEngine};
-pub use schema::{Level1Game, Level2Game, Level3Game, SchemaLevel};
+pub use schema::{json_schema, Level1Game, Level2Game, Level3Game, SchemaLevel};
+pub use scoreboard::{
+    HighScoreTable, ScoreChallenge, ScoreEntry, ScoreOrder, Scoreboard, ScoreboardError,
+    StorageScope, DEFAULT_MAX_ENTRIES, MAX_NICKNAME_CHARS, SCOREBOARD_KEY_PREFIX,
+};
 pub use scripting::{
     Level4Game, ScriptBlock, ScriptLanguage, ScriptSandbox, ScriptValidationResult, ScriptValidator,
 };
-pub use sharing::{BundleError, BundleMetadata, GameBundle, ShareLinkGenerator};
-pub use tutorial::{GameTemplate, TemplateCatalog, TutorialError, TutorialProgress, TutorialStage};
+pub use sharing::{
+    AssetType, BundleError, BundleMetadata, EmbeddedAsset, GameBundle, ShareLinkGenerator,
+    SyncFrame, SyncPackage, SyncReceiver, BUNDLE_VERSION, SYNC_FRAME_CAPACITY, SYNC_FRAME_PREFIX,
+    THUMBNAIL_NAME,
+};
+pub use telemetry::{
+    TelemetryBuffer, TelemetryConsent, TelemetryError, TelemetryEvent, TelemetryExport,
+    DEFAULT_TELEMETRY_CAPACITY, TELEMETRY_METRIC,
+};
+pub use tutorial::{
+    tutorial_achievements, GameTemplate, TemplateCatalog, TemplateStage, TutorialError,
+    TutorialProgress, TutorialStage, TUTORIAL_GRADUATE_ACHIEVEMENT,
+};
 pub use vocabulary::Vocabulary;
 
+use jugar_core::{AccessibilitySettings, Inventory};
+
 /// Result type for jugar-yaml operations
 pub type Result<T> = core::result::Result<T, YamlError>;
 
@@ -98,6 +149,19 @@ pub fn compile_game(yaml: &str) -> Result<CompiledGame> {
     compiler.compile(yaml)
 }
 
+/// Rewrite a YAML game in canonical form
+///
+/// Keys are lowercased (`Colour` becomes `color`) and layout is
+/// normalized; comments are not kept. Compiling the result gives the same
+/// game as compiling the original.
+///
+/// # Errors
+///
+/// Returns `YamlError::SyntaxError` if the YAML can't be parsed
+pub fn format_game(yaml: &str) -> Result<String> {
+    compiler::normalize_yaml(yaml)
+}
+
 /// A compiled game ready for the Jugar runtime
 #[derive(Debug, Clone)]
 pub struct CompiledGame {
@@ -113,6 +177,49 @@ pub struct CompiledGame {
     pub background: Option<String>,
     /// Music setting
     pub music: Option<String>,
+    /// Condition that wins the game
+    pub win_when: Option<CompiledCondition>,
+    /// Condition that loses the game
+    pub lose_when: Option<CompiledCondition>,
+    /// Collectible items, sorted by id
+    pub items: Vec<CompiledItem>,
+    /// Templates that spawn actions copy, sorted by id
+    pub templates: Vec<CompiledEntity>,
+    /// Level 3 world layout and platformer physics
+    pub world: Option<CompiledWorld>,
+    /// Quiz questions, in the order they are asked
+    pub questions: Vec<CompiledQuestion>,
+    /// AI difficulty; `None` leaves each model at its default
+    pub difficulty: Option<CompiledDifficulty>,
+}
+
+impl CompiledGame {
+    /// The player's starting inventory, with carry limits from `items:`
+    #[must_use]
+    pub fn inventory(&self) -> Inventory {
+        let mut inventory = Inventory::new();
+        for item in &self.items {
+            if let Some(max) = item.max {
+                inventory = inventory.with_limit(item.id.clone(), max);
+            }
+            let _ = inventory.add(&item.id, item.start);
+        }
+        inventory
+    }
+
+    /// Adjusts rule actions for the player's accessibility settings
+    ///
+    /// With reduced motion on, motion effects (blink, shake, spin, grow,
+    /// shrink) become fades so the rule still gives visible feedback.
+    pub fn apply_accessibility(&mut self, settings: &AccessibilitySettings) {
+        for rule in &mut self.rules {
+            for action in &mut rule.then {
+                if let CompiledAction::Effect(_, effect) = action {
+                    *effect = effect.for_settings(settings);
+                }
+            }
+        }
+    }
 }
 
 /// A compiled entity from YAML
@@ -128,6 +235,149 @@ pub struct CompiledEntity {
     pub movement: Option<String>,
     /// AI model path if specified
     pub ai_model: Option<String>,
+    /// Starting health if the entity can be hurt
+    pub health: Option<u32>,
+}
+
+impl CompiledEntity {
+    /// The player controls for this entity's `move:` value, if any
+    #[must_use]
+    pub fn controls(&self) -> Option<ControlScheme> {
+        self.movement.as_deref().and_then(ControlScheme::parse)
+    }
+}
+
+/// How the player steers a character, from `move:`
+///
+/// Each scheme names the `jugar-input` state the runtime reads for it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ControlScheme {
+    /// Arrow keys or WASD (`InputState::key`)
+    Arrows,
+    /// Tap a spot to move there (`InputState::primary_pointer`)
+    Touch,
+    /// Moves by itself; no player input
+    Auto,
+    /// Follows a held finger or mouse (`InputState::touches`)
+    Drag,
+    /// A tap anywhere jumps (`TouchPhase::Started`)
+    TapToJump,
+    /// Tilting the device steers (`InputState::tilt`)
+    Tilt,
+}
+
+impl ControlScheme {
+    /// Reads a `move:` value; movement patterns like "zigzag" return `None`
+    #[must_use]
+    pub fn parse(text: &str) -> Option<Self> {
+        match text.trim().to_lowercase().replace('_', "-").as_str() {
+            "arrows" | "wasd" | "keyboard" => Some(Self::Arrows),
+            "touch" => Some(Self::Touch),
+            "auto" => Some(Self::Auto),
+            "drag" => Some(Self::Drag),
+            "tap-to-jump" => Some(Self::TapToJump),
+            "tilt" => Some(Self::Tilt),
+            _ => None,
+        }
+    }
+
+    /// Scheme to use when this one's input is missing
+    ///
+    /// Tilt falls back to dragging on devices without an orientation sensor
+    /// (desktops, or when the player declines motion access).
+    #[must_use]
+    pub const fn fallback(self) -> Option<Self> {
+        match self {
+            Self::Tilt => Some(Self::Drag),
+            _ => None,
+        }
+    }
+
+    /// Returns true if the scheme is driven by touch or the mouse
+    #[must_use]
+    pub const fn uses_pointer(self) -> bool {
+        matches!(self, Self::Touch | Self::Drag | Self::TapToJump)
+    }
+}
+
+/// A collectible item from YAML
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompiledItem {
+    /// Item identifier
+    pub id: String,
+    /// Item type (key, gem, etc.)
+    pub item_type: String,
+    /// Most the player can carry
+    pub max: Option<u32>,
+    /// How many the player starts with
+    pub start: u32,
+}
+
+/// A compiled `world:` section
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompiledWorld {
+    /// World type ("static", "procedural", "sidescroll")
+    pub kind: Option<String>,
+    /// Layout generator for procedural worlds ("maze", "dungeon", "arena")
+    pub algorithm: Option<String>,
+    /// Generation seed; `None` lets the runtime pick one
+    pub seed: Option<u64>,
+    /// Size in tiles (width, height)
+    pub size: Option<(u32, u32)>,
+    /// Downward pull in tiles per second squared (0 = top-down)
+    pub gravity: f32,
+    /// Jump height of tap-to-jump characters in tiles
+    pub jump: Option<f32>,
+}
+
+impl CompiledWorld {
+    /// Returns true if the layout is generated rather than hand-placed
+    #[must_use]
+    pub fn is_procedural(&self) -> bool {
+        self.algorithm.is_some() || self.kind.as_deref() == Some("procedural")
+    }
+
+    /// Returns true if characters fall and jump (a side view)
+    #[must_use]
+    pub fn has_gravity(&self) -> bool {
+        self.gravity > 0.0
+    }
+}
+
+/// A compiled `difficulty:` setting
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompiledDifficulty {
+    /// The same level all game (1-10)
+    Fixed(u8),
+    /// Follows the player's performance at runtime, within any parental cap
+    Adaptive,
+}
+
+impl CompiledDifficulty {
+    /// Returns true if the runtime should adjust difficulty as the player goes
+    #[must_use]
+    pub const fn is_adaptive(self) -> bool {
+        matches!(self, Self::Adaptive)
+    }
+}
+
+/// A compiled quiz or flashcard question
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompiledQuestion {
+    /// Question text (already content-filtered)
+    pub ask: String,
+    /// Answers to pick from, in the order written
+    pub choices: Vec<String>,
+    /// Index of the right answer in `choices`
+    pub answer: usize,
+}
+
+impl CompiledQuestion {
+    /// Returns true if picking `choice` (an index into `choices`) is right
+    #[must_use]
+    pub const fn is_correct(&self, choice: usize) -> bool {
+        choice == self.answer
+    }
 }
 
 /// A compiled rule from YAML
@@ -135,6 +385,8 @@ pub struct CompiledEntity {
 pub struct CompiledRule {
     /// Trigger condition
     pub when: String,
+    /// The trigger as a condition, if it is one the runtime can check
+    pub condition: Option<CompiledCondition>,
     /// Actions to execute
     pub then: Vec<CompiledAction>,
 }
@@ -144,18 +396,179 @@ pub struct CompiledRule {
 pub enum CompiledAction {
     /// Play a sound effect
     PlaySound(String),
+    /// Show a caption for a sound
+    Caption(String),
+    /// Set an adaptive music parameter to a value
+    SetMusicParameter(String, f32),
     /// Add to score
     AddScore(i32),
     /// Lose a life
     LoseLife(i32),
+    /// Take health from an entity
+    Damage(String, u32),
+    /// Give health to an entity
+    Heal(String, u32),
+    /// Add items to the player's inventory
+    GiveItem(String, u32),
+    /// Remove items from the player's inventory
+    RemoveItem(String, u32),
+    /// Show a timed speech bubble
+    Say(SpeechLine),
+    /// Create entities from a template
+    Spawn(SpawnAction),
     /// Make entity disappear
     Disappear(String),
     /// Move entity to new random position
     Respawn(String),
+    /// Play a visual effect on an entity
+    Effect(String, VisualEffect),
     /// Show a message or screen
     Show(String),
     /// Stop the game
     StopGame,
+    /// An action registered by a host extension
+    Custom(String),
+}
+
+/// A visual effect from the `effects` words
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VisualEffect {
+    /// Flicker on and off
+    Blink,
+    /// Wobble in place
+    Shake,
+    /// Get bigger
+    Grow,
+    /// Get smaller
+    Shrink,
+    /// Turn around once
+    Spin,
+    /// Fade out and back in
+    Fade,
+}
+
+impl VisualEffect {
+    /// Reads an effect word
+    #[must_use]
+    pub fn parse(text: &str) -> Option<Self> {
+        match text.trim().to_lowercase().as_str() {
+            "blink" => Some(Self::Blink),
+            "shake" => Some(Self::Shake),
+            "grow" => Some(Self::Grow),
+            "shrink" => Some(Self::Shrink),
+            "spin" => Some(Self::Spin),
+            "fade" => Some(Self::Fade),
+            _ => None,
+        }
+    }
+
+    /// Returns true if the effect moves, resizes or flashes the entity
+    #[must_use]
+    pub const fn is_motion(self) -> bool {
+        !matches!(self, Self::Fade)
+    }
+
+    /// The effect to show for the player's settings
+    #[must_use]
+    pub const fn for_settings(self, settings: &AccessibilitySettings) -> Self {
+        if settings.reduced_motion && self.is_motion() {
+            Self::Fade
+        } else {
+            self
+        }
+    }
+}
+
+/// A line of dialog for a speech bubble
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpeechLine {
+    /// Text to show (already content-filtered)
+    pub text: String,
+    /// Entity that says it (`None` for the narrator)
+    pub speaker: Option<String>,
+    /// Seconds the bubble stays up
+    pub seconds: f32,
+    /// Also read the line aloud with text-to-speech
+    pub voice: bool,
+}
+
+/// Where spawned entities appear
+#[derive(Debug, Clone, PartialEq)]
+pub enum SpawnLocation {
+    /// Anywhere on screen
+    Random,
+    /// Random spot along the top edge
+    Top,
+    /// Random spot along the bottom edge
+    Bottom,
+    /// Random spot along the left edge
+    Left,
+    /// Random spot along the right edge
+    Right,
+    /// A fixed point
+    Point(f32, f32),
+    /// On top of another entity
+    Near(String),
+}
+
+impl SpawnLocation {
+    /// Reads an `at:` value ("random", "top", "100, 200", "player")
+    #[must_use]
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim();
+        if let Some((x, y)) = text.split_once(',') {
+            return Some(Self::Point(x.trim().parse().ok()?, y.trim().parse().ok()?));
+        }
+        match text.to_lowercase().as_str() {
+            "" => None,
+            "random" | "anywhere" => Some(Self::Random),
+            "top" => Some(Self::Top),
+            "bottom" => Some(Self::Bottom),
+            "left" => Some(Self::Left),
+            "right" => Some(Self::Right),
+            _ if text.contains(char::is_whitespace) => None,
+            _ => Some(Self::Near(text.to_string())),
+        }
+    }
+}
+
+/// A compiled `spawn:` action
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpawnAction {
+    /// Template to copy
+    pub template: String,
+    /// Where copies appear
+    pub at: SpawnLocation,
+    /// Keep spawning on this interval (seconds) after the rule fires
+    pub every: Option<f32>,
+    /// Most copies alive at once
+    pub max: u32,
+}
+
+impl SpawnAction {
+    /// Returns true if another copy fits under this action's limit and the
+    /// sandbox entity cap
+    #[must_use]
+    pub fn can_spawn(&self, alive: usize, total_entities: usize) -> bool {
+        alive < usize::try_from(self.max).unwrap_or(usize::MAX) && total_entities < MAX_ENTITIES
+    }
+}
+
+/// A compiled win or lose condition
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CompiledCondition {
+    /// Score reaches a value
+    ScoreReaches(u32),
+    /// An entity's health drops to zero
+    HealthEmpty(String),
+    /// No lives are left
+    LivesEmpty,
+    /// One entity touches another
+    Touches(String, String),
+    /// Seconds played reach a value
+    TimeReaches(u32),
+    /// The player carries at least this many of an item
+    HasItem(String, u32),
 }
 
 #[cfg(test)]
@@ -207,6 +620,34 @@ when_touch:
             assert_eq!(game.background, Some("space".to_string()));
         }
 
+        #[test]
+        fn test_level1_touch_controls() {
+            let controls = |move_type: &str| {
+                compile_game(&format!("character: bunny\nmove: {move_type}"))
+                    .map(|game| game.entities[0].controls())
+            };
+            assert_eq!(controls("drag").unwrap(), Some(ControlScheme::Drag));
+            assert_eq!(
+                controls("tap-to-jump").unwrap(),
+                Some(ControlScheme::TapToJump)
+            );
+            assert_eq!(controls("tilt").unwrap(), Some(ControlScheme::Tilt));
+            assert!(matches!(
+                controls("shake"),
+                Err(YamlError::InvalidEnumValue { field, .. }) if field == "move"
+            ));
+        }
+
+        #[test]
+        fn test_tilt_falls_back_to_drag() {
+            assert_eq!(ControlScheme::Tilt.fallback(), Some(ControlScheme::Drag));
+            assert_eq!(ControlScheme::Arrows.fallback(), None);
+            assert!(ControlScheme::Drag.uses_pointer());
+            assert!(!ControlScheme::Tilt.uses_pointer());
+            assert_eq!(ControlScheme::parse("zigzag"), None);
+            assert_eq!(ControlScheme::parse("WASD"), Some(ControlScheme::Arrows));
+        }
+
         #[test]
         fn test_level1_valid_characters() {
             // All 10 Level 1 characters must be accepted
@@ -438,6 +879,26 @@ world:
             let result = compile_game(yaml);
             assert!(result.is_ok(), "Level 3 procedural world should compile");
         }
+
+        #[test]
+        fn test_level3_difficulty() {
+            let game = |difficulty: &str| {
+                compile_game(&format!(
+                    "game: ghosts\nversion: 1\ndifficulty: {difficulty}\nentities:\n  ghost:\n    sprite: ghost\n    ai: builtin:chase\n"
+                ))
+            };
+            let adaptive = game("adaptive").unwrap();
+            assert_eq!(adaptive.difficulty, Some(CompiledDifficulty::Adaptive));
+            assert!(adaptive
+                .difficulty
+                .is_some_and(CompiledDifficulty::is_adaptive));
+            assert_eq!(
+                game("3").unwrap().difficulty,
+                Some(CompiledDifficulty::Fixed(3))
+            );
+            assert!(game("11").is_err());
+            assert!(game("sometimes").is_err());
+        }
     }
 
     mod error_handling_tests {
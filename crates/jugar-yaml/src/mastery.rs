@@ -0,0 +1,404 @@
+//! Concept-mastery reports for teachers.
+//!
+//! Summarizes a classroom's tutorial progress by concept (who is still
+//! learning conditionals, how many hints a lesson takes) without exposing
+//! any one child. Reports only come out of the privacy pipeline:
+//!
+//! - classes smaller than [`MIN_COHORT_SIZE`] get no report, and groups of
+//!   fewer students than that are hidden within one (k-anonymity)
+//! - every released count gets Laplace noise, and each report charges its
+//!   epsilon to the [`PrivacyBudget`]
+//! - nicknames never appear; students are only counted
+
+use alloc::collections::BTreeMap;
+use core::fmt::Write;
+
+use serde::{Deserialize, Serialize};
+
+use crate::privacy::{
+    DifferentialPrivacy, DifferentialPrivacyConfig, PrivacyBudget, PrivacyBudgetError,
+};
+use crate::tutorial::{TutorialProgress, TutorialStage};
+
+/// Privacy budget metric charged by mastery reports
+pub const MASTERY_METRIC: &str = "classroom_mastery";
+
+/// Fewest students a class or group needs before it is reported (the k in
+/// k-anonymity)
+pub const MIN_COHORT_SIZE: usize = 5;
+
+/// Hints on one lesson that count as struggling with it
+pub const STRUGGLE_HINTS: u8 = 2;
+
+/// Hints counted per student, bounding one child's effect on hint totals
+const MAX_HINTS_COUNTED: u8 = 3;
+
+/// Noisy histograms each report releases; the epsilon is split between them
+const RELEASES: f64 = 3.0;
+
+/// Programming concept taught by a tutorial stage
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Concept {
+    /// Putting a character on screen ("Hello World")
+    Characters,
+    /// Giving the game a goal ("Add a Goal")
+    Goals,
+    /// Reacting to events with `when_` blocks ("Add Feedback")
+    Conditionals,
+    /// Obstacles and lives ("Make It Challenging")
+    Challenge,
+}
+
+impl Concept {
+    /// The concept a tutorial stage teaches
+    #[must_use]
+    pub const fn for_stage(stage: TutorialStage) -> Self {
+        match stage {
+            TutorialStage::HelloWorld => Self::Characters,
+            TutorialStage::AddGoal => Self::Goals,
+            TutorialStage::AddFeedback => Self::Conditionals,
+            TutorialStage::MakeChallenging => Self::Challenge,
+        }
+    }
+
+    /// The tutorial stage that teaches this concept
+    #[must_use]
+    pub const fn stage(self) -> TutorialStage {
+        match self {
+            Self::Characters => TutorialStage::HelloWorld,
+            Self::Goals => TutorialStage::AddGoal,
+            Self::Conditionals => TutorialStage::AddFeedback,
+            Self::Challenge => TutorialStage::MakeChallenging,
+        }
+    }
+
+    /// Name shown to teachers
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Characters => "Characters",
+            Self::Goals => "Goals",
+            Self::Conditionals => "Conditionals",
+            Self::Challenge => "Challenge",
+        }
+    }
+
+    /// All concepts in teaching order
+    #[must_use]
+    pub const fn all() -> [Self; 4] {
+        [
+            Self::Characters,
+            Self::Goals,
+            Self::Conditionals,
+            Self::Challenge,
+        ]
+    }
+}
+
+/// Mastery report error
+#[derive(Debug, Clone, PartialEq)]
+pub enum MasteryError {
+    /// The class is too small to report on anonymously
+    CohortTooSmall {
+        /// Students in the class
+        students: usize,
+        /// Students needed
+        required: usize,
+    },
+    /// The privacy budget refused the report
+    Budget(PrivacyBudgetError),
+}
+
+impl core::fmt::Display for MasteryError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::CohortTooSmall { students, required } => write!(
+                f,
+                "Reports need at least {required} students so nobody stands out; this class has {students}"
+            ),
+            Self::Budget(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl core::error::Error for MasteryError {}
+
+impl From<PrivacyBudgetError> for MasteryError {
+    fn from(err: PrivacyBudgetError) -> Self {
+        Self::Budget(err)
+    }
+}
+
+/// Noisy class-wide numbers for one concept
+///
+/// Group figures are `None` when fewer than [`MIN_COHORT_SIZE`] students
+/// are working on the concept.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConceptMastery {
+    /// The concept
+    pub concept: Concept,
+    /// Students who have finished its lesson
+    pub mastered: u64,
+    /// Students working on its lesson now
+    pub learning: Option<u64>,
+    /// Of those, students who needed [`STRUGGLE_HINTS`] or more hints
+    pub struggling: Option<u64>,
+    /// Hints used so far by students working on it, on average
+    pub average_hints: Option<f64>,
+}
+
+impl ConceptMastery {
+    /// Share of learners who are struggling (0.0-1.0)
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn struggle_rate(&self) -> Option<f64> {
+        let learning = self.learning.filter(|&learning| learning > 0)?;
+        self.struggling
+            .map(|struggling| (struggling as f64 / learning as f64).min(1.0))
+    }
+}
+
+/// Differentially private concept-mastery summary of a classroom
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MasteryReport {
+    /// Epsilon charged for the report
+    pub epsilon: f64,
+    /// Students in the class
+    pub students: u64,
+    /// One entry per concept, in teaching order
+    pub concepts: Vec<ConceptMastery>,
+}
+
+impl MasteryReport {
+    /// Aggregates tutorial progress into a report, charging `config.epsilon`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MasteryError::CohortTooSmall`] for fewer than
+    /// [`MIN_COHORT_SIZE`] students, or [`MasteryError::Budget`] if the
+    /// budget is used up. Nothing is charged on error.
+    #[allow(clippy::cast_sign_loss, clippy::cast_precision_loss)]
+    pub(crate) fn from_progress<'a>(
+        progress: impl IntoIterator<Item = &'a TutorialProgress>,
+        config: &DifferentialPrivacyConfig,
+        budget: &mut PrivacyBudget,
+    ) -> Result<Self, MasteryError> {
+        let progress: Vec<&TutorialProgress> = progress.into_iter().collect();
+        if progress.len() < MIN_COHORT_SIZE {
+            return Err(MasteryError::CohortTooSmall {
+                students: progress.len(),
+                required: MIN_COHORT_SIZE,
+            });
+        }
+        budget.charge(MASTERY_METRIC, config.epsilon)?;
+
+        // Each student lands in one bucket per histogram, so the buckets
+        // compose in parallel and only the histograms split the epsilon
+        let mut learning: BTreeMap<Concept, u64> = BTreeMap::new();
+        let mut struggling: BTreeMap<Concept, u64> = BTreeMap::new();
+        let mut hints: BTreeMap<Concept, u64> = BTreeMap::new();
+        let mut graduated = 0;
+        for student in &progress {
+            if student.is_complete() {
+                graduated += 1;
+                continue;
+            }
+            let concept = Concept::for_stage(student.current_stage);
+            *learning.entry(concept).or_insert(0) += 1;
+            if student.hints_shown >= STRUGGLE_HINTS {
+                *struggling.entry(concept).or_insert(0) += 1;
+            }
+            *hints.entry(concept).or_insert(0) +=
+                u64::from(student.hints_shown.min(MAX_HINTS_COUNTED));
+        }
+
+        let epsilon = config.epsilon / RELEASES;
+        let counts = DifferentialPrivacy::new(epsilon, config.sensitivity);
+        let hint_sums =
+            DifferentialPrivacy::new(epsilon, config.sensitivity * f64::from(MAX_HINTS_COUNTED));
+        let noisy = |noise: &DifferentialPrivacy, value: u64| -> u64 {
+            noise.add_laplace_noise_u64(value).max(0) as u64
+        };
+
+        let graduated = noisy(&counts, graduated);
+        let learners: Vec<u64> = Concept::all()
+            .iter()
+            .map(|concept| noisy(&counts, learning.get(concept).copied().unwrap_or(0)))
+            .collect();
+        let concepts = Concept::all()
+            .iter()
+            .enumerate()
+            .map(|(index, &concept)| {
+                let shown = learning.get(&concept).copied().unwrap_or(0) >= MIN_COHORT_SIZE as u64;
+                let struggling = noisy(&counts, struggling.get(&concept).copied().unwrap_or(0));
+                let hints = noisy(&hint_sums, hints.get(&concept).copied().unwrap_or(0));
+                ConceptMastery {
+                    concept,
+                    mastered: learners[index + 1..].iter().sum::<u64>() + graduated,
+                    learning: shown.then_some(learners[index]),
+                    struggling: shown.then_some(struggling),
+                    average_hints: shown.then_some(hints as f64 / learners[index].max(1) as f64),
+                }
+            })
+            .collect();
+
+        Ok(Self {
+            epsilon: config.epsilon,
+            students: learners.iter().sum::<u64>() + graduated,
+            concepts,
+        })
+    }
+
+    /// The concept with the highest share of struggling learners
+    #[must_use]
+    pub fn most_struggled(&self) -> Option<&ConceptMastery> {
+        self.concepts
+            .iter()
+            .filter_map(|concept| concept.struggle_rate().map(|rate| (concept, rate)))
+            .filter(|&(_, rate)| rate > 0.0)
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(concept, _)| concept)
+    }
+
+    /// Plain-language summary for the teacher dashboard
+    #[must_use]
+    pub fn summary(&self) -> String {
+        let mut text = format!(
+            "About {} students (numbers are rounded for privacy)\n",
+            self.students
+        );
+        for concept in &self.concepts {
+            let _ = write!(
+                text,
+                "- {}: {} mastered",
+                concept.concept.name(),
+                concept.mastered
+            );
+            match (concept.learning, concept.struggling, concept.average_hints) {
+                (Some(learning), Some(struggling), Some(hints)) => {
+                    let _ = writeln!(
+                        text,
+                        ", {learning} learning, {struggling} struggling, {hints:.1} hints on average"
+                    );
+                }
+                _ => text.push_str(", too few learning to show more\n"),
+            }
+        }
+        if let Some(concept) = self.most_struggled() {
+            let _ = writeln!(text, "Most help needed with: {}", concept.concept.name());
+        }
+        text
+    }
+
+    /// Serializes the report
+    ///
+    /// # Errors
+    ///
+    /// Returns error if serialization fails
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    /// Epsilon high enough that noise rounds away, so counts can be checked
+    fn exact() -> DifferentialPrivacyConfig {
+        DifferentialPrivacyConfig::new(1000.0, 1.0)
+    }
+
+    fn student(stage: TutorialStage, hints: u8) -> TutorialProgress {
+        let mut progress = TutorialProgress::new();
+        progress.completed_stages = TutorialStage::all()
+            .into_iter()
+            .filter(|&done| done < stage)
+            .collect();
+        progress.current_stage = stage;
+        progress.hints_shown = hints;
+        progress
+    }
+
+    fn graduate() -> TutorialProgress {
+        let mut progress = student(TutorialStage::MakeChallenging, 0);
+        progress
+            .completed_stages
+            .push(TutorialStage::MakeChallenging);
+        progress
+    }
+
+    fn class() -> Vec<TutorialProgress> {
+        let mut class: Vec<TutorialProgress> = [3, 3, 2, 2, 1, 0]
+            .into_iter()
+            .map(|hints| student(TutorialStage::AddFeedback, hints))
+            .collect();
+        class.push(student(TutorialStage::AddGoal, 3));
+        class.push(student(TutorialStage::AddGoal, 0));
+        class.push(graduate());
+        class
+    }
+
+    #[test]
+    fn test_small_classes_get_no_report() {
+        let class = &class()[..4];
+        let mut budget = PrivacyBudget::default();
+        let err = MasteryReport::from_progress(class, &exact(), &mut budget).unwrap_err();
+        assert_eq!(
+            err,
+            MasteryError::CohortTooSmall {
+                students: 4,
+                required: MIN_COHORT_SIZE
+            }
+        );
+        assert_eq!(budget.queries(MASTERY_METRIC), 0);
+    }
+
+    #[test]
+    fn test_report_counts_concepts_and_hides_small_groups() {
+        let mut budget = PrivacyBudget::new(2000.0);
+        let report = MasteryReport::from_progress(&class(), &exact(), &mut budget).unwrap();
+        assert_eq!(report.students, 9);
+        assert_eq!(budget.queries(MASTERY_METRIC), 1);
+
+        let mastered: Vec<u64> = report.concepts.iter().map(|c| c.mastered).collect();
+        assert_eq!(mastered, [9, 7, 1, 1]);
+
+        let conditionals = &report.concepts[2];
+        assert_eq!(conditionals.learning, Some(6));
+        assert_eq!(conditionals.struggling, Some(4));
+        // 3 + 3 + 2 + 2 + 1 + 0 hints over 6 students
+        assert!((conditionals.average_hints.unwrap() - 11.0 / 6.0).abs() < 1e-9);
+
+        // Only two students are on Goals, so nothing about them is shown
+        let goals = &report.concepts[1];
+        assert_eq!(
+            (goals.learning, goals.struggling, goals.average_hints),
+            (None, None, None)
+        );
+        assert_eq!(
+            report.most_struggled().unwrap().concept,
+            Concept::Conditionals
+        );
+    }
+
+    #[test]
+    fn test_export_goes_through_budget_without_names() {
+        let mut budget = PrivacyBudget::new(1.0);
+        let config = DifferentialPrivacyConfig::moderate_privacy();
+        let report = MasteryReport::from_progress(&class(), &config, &mut budget).unwrap();
+        assert!((report.epsilon - 1.0).abs() < f64::EPSILON);
+        assert!(report.summary().contains("Conditionals"));
+
+        let json = report.to_json().unwrap();
+        assert!(json.contains("\"conditionals\""));
+        assert!(!json.contains("hints_shown"));
+
+        assert!(matches!(
+            MasteryReport::from_progress(&class(), &config, &mut budget),
+            Err(MasteryError::Budget(_))
+        ));
+    }
+}
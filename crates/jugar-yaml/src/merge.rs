@@ -0,0 +1,533 @@
+//! Semantic diff and three-way merge of YAML games.
+//!
+//! Remixes drift from the game they came from. [`diff_games`] compares two
+//! versions by what they mean (characters, rules, settings) rather than line
+//! by line, and [`merge_games`] folds the original's later improvements into
+//! a remix. When both sides changed the same thing the remix's version is
+//! kept and a [`MergeConflict`] explains it in words a kid can follow: "You
+//! both changed the bunny's speed".
+//!
+//! Games are compared down to the properties of a character or rule. Rules
+//! and quiz questions are matched by their `when:` and `ask:` text, so
+//! moving them around is not a change. Merged YAML is written back out
+//! fresh, so comments are not kept.
+//!
+//! ```
+//! use jugar_yaml::merge_games;
+//!
+//! let original = "character: bunny\nbackground: grass\n";
+//! let remix = "character: cat\nbackground: grass\n";
+//! let updated = "character: bunny\nbackground: space\n";
+//!
+//! let merged = merge_games(original, remix, updated).unwrap();
+//! assert!(merged.is_clean());
+//! assert_eq!(merged.yaml, "character: cat\nbackground: space\n");
+//! ```
+
+use serde_yaml::{Mapping, Value};
+
+use crate::error::YamlError;
+
+/// How deep games are compared: section, item, property
+const MAX_DEPTH: usize = 3;
+
+/// Fields that name the items of a list, and what the items are called
+const LIST_KEYS: [(&str, &str); 2] = [("when", "rule"), ("ask", "question")];
+
+/// Kind of change between two versions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    /// Only in the newer version
+    Added,
+    /// Only in the older version
+    Removed,
+    /// In both, with different values
+    Changed,
+}
+
+/// One change between two versions of a game
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameChange {
+    /// Keys from the top of the document (`["characters", "player", "speed"]`)
+    pub path: Vec<String>,
+    /// What changed, in words (`the bunny's speed`)
+    pub subject: String,
+    /// Kind of change
+    pub kind: ChangeKind,
+    /// Value in the older version
+    pub before: Option<String>,
+    /// Value in the newer version
+    pub after: Option<String>,
+}
+
+impl GameChange {
+    /// Kid-friendly description ("Changed the bunny's speed from slow to fast")
+    #[must_use]
+    pub fn describe(&self) -> String {
+        let text = match (self.kind, &self.before, &self.after) {
+            (ChangeKind::Added, _, _) => format!("added {}", self.subject),
+            (ChangeKind::Removed, _, _) => format!("removed {}", self.subject),
+            // Only single values read well inline
+            (ChangeKind::Changed, Some(before), Some(after))
+                if !before.contains('\n') && !after.contains('\n') =>
+            {
+                format!("changed {} from {before} to {after}", self.subject)
+            }
+            (ChangeKind::Changed, _, _) => format!("changed {}", self.subject),
+        };
+        capitalize(&text)
+    }
+}
+
+/// Changes between two versions of a game, in document order
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GameDiff {
+    /// All changes
+    pub changes: Vec<GameChange>,
+}
+
+impl GameDiff {
+    /// Returns true if the versions mean the same game
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Kid-friendly descriptions of every change
+    #[must_use]
+    pub fn describe(&self) -> Vec<String> {
+        self.changes.iter().map(GameChange::describe).collect()
+    }
+}
+
+/// Something the remix and the updated original both changed differently
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergeConflict {
+    /// Keys from the top of the document
+    pub path: Vec<String>,
+    /// What both sides changed, in words
+    pub subject: String,
+    /// Value in the original the remix started from
+    pub base: Option<String>,
+    /// Value in the remix (kept in the merge)
+    pub ours: Option<String>,
+    /// Value in the updated original
+    pub theirs: Option<String>,
+}
+
+impl MergeConflict {
+    /// Kid-friendly description ("You both changed the bunny's speed")
+    #[must_use]
+    pub fn describe(&self) -> String {
+        let subject = &self.subject;
+        match (&self.base, &self.ours, &self.theirs) {
+            (_, None, _) => format!("You removed {subject}, but the original changed it"),
+            (_, _, None) => format!("The original removed {subject}, but you changed it"),
+            (None, _, _) => format!("You both added {subject}"),
+            _ => format!("You both changed {subject}"),
+        }
+    }
+}
+
+/// Result of a three-way merge
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergeResult {
+    /// The merged game; conflicts keep the remix's version
+    pub yaml: String,
+    /// Places where both sides changed the same thing
+    pub conflicts: Vec<MergeConflict>,
+}
+
+impl MergeResult {
+    /// Returns true if nothing conflicted
+    #[must_use]
+    pub fn is_clean(&self) -> bool {
+        self.conflicts.is_empty()
+    }
+}
+
+/// Compares two versions of a game by meaning
+///
+/// # Errors
+///
+/// Returns [`YamlError::SyntaxError`] if either version is not valid YAML.
+pub fn diff_games(before: &str, after: &str) -> Result<GameDiff, YamlError> {
+    let (before, after) = (parse(before)?, parse(after)?);
+    let mut diff = GameDiff::default();
+    diff_node(
+        Some(&before),
+        Some(&after),
+        &mut Vec::new(),
+        "the game",
+        &mut diff.changes,
+    );
+    Ok(diff)
+}
+
+/// Merges an updated original into a remix of it
+///
+/// `base` is the original the remix started from, `ours` the remix and
+/// `theirs` the updated original. Changes made on one side only are taken;
+/// where both sides changed the same thing the remix wins and a
+/// [`MergeConflict`] is reported.
+///
+/// # Errors
+///
+/// Returns [`YamlError::SyntaxError`] if any version is not valid YAML.
+pub fn merge_games(base: &str, ours: &str, theirs: &str) -> Result<MergeResult, YamlError> {
+    let (base, ours, theirs) = (parse(base)?, parse(ours)?, parse(theirs)?);
+    let mut conflicts = Vec::new();
+    let merged = merge_node(
+        Some(&base),
+        Some(&ours),
+        Some(&theirs),
+        &mut Vec::new(),
+        "the game",
+        &mut conflicts,
+    );
+    let yaml = match merged {
+        Some(Value::Null) | None => String::new(),
+        Some(value) => serde_yaml::to_string(&value).map_err(|e| YamlError::SyntaxError {
+            message: e.to_string(),
+            line: None,
+            column: None,
+        })?,
+    };
+    Ok(MergeResult { yaml, conflicts })
+}
+
+fn parse(yaml: &str) -> Result<Value, YamlError> {
+    serde_yaml::from_str(yaml).map_err(|e| YamlError::SyntaxError {
+        message: e.to_string(),
+        line: e.location().map(|l| l.line()),
+        column: e.location().map(|l| l.column()),
+    })
+}
+
+/// How a compared value holds its items
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Shape {
+    Map,
+    /// A list of rules or questions, with what its items are called
+    List(&'static str),
+}
+
+/// Named items of a mapping or of a list of rules or questions
+#[derive(Debug)]
+struct Items<'a> {
+    shape: Shape,
+    entries: Vec<(String, &'a Value)>,
+}
+
+impl<'a> Items<'a> {
+    fn of(value: &'a Value) -> Option<Self> {
+        match value {
+            Value::Mapping(map) => Some(Self {
+                shape: Shape::Map,
+                entries: map.iter().map(|(k, v)| (render(k), v)).collect(),
+            }),
+            Value::Sequence(list) => {
+                let (field, noun) = LIST_KEYS
+                    .into_iter()
+                    .find(|(field, _)| list.first().and_then(|item| item.get(field)).is_some())?;
+                let mut names: Vec<&str> = Vec::new();
+                let mut entries = Vec::new();
+                for item in list {
+                    let name = item.get(field).and_then(Value::as_str)?;
+                    // Repeated names are told apart by position
+                    let repeats = names.iter().filter(|seen| **seen == name).count();
+                    names.push(name);
+                    let key = if repeats == 0 {
+                        name.to_string()
+                    } else {
+                        format!("{name} #{}", repeats + 1)
+                    };
+                    entries.push((key, item));
+                }
+                Some(Self {
+                    shape: Shape::List(noun),
+                    entries,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    fn get(&self, key: &str) -> Option<&'a Value> {
+        self.entries
+            .iter()
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| *value)
+    }
+}
+
+/// Items of `a` and `b` if they hold the same shape of items
+fn matching_items<'a>(a: &'a Value, b: &'a Value) -> Option<(Items<'a>, Items<'a>)> {
+    let (a, b) = (Items::of(a)?, Items::of(b)?);
+    (a.shape == b.shape).then_some((a, b))
+}
+
+/// Keys of `first` in order, then the keys only `rest` have
+fn union_keys(first: &Items<'_>, rest: &[Option<&Items<'_>>]) -> Vec<String> {
+    let mut keys: Vec<String> = first.entries.iter().map(|(key, _)| key.clone()).collect();
+    for items in rest.iter().flatten() {
+        for (key, _) in &items.entries {
+            if !keys.contains(key) {
+                keys.push(key.clone());
+            }
+        }
+    }
+    keys
+}
+
+/// Names the item `key` of `parent` in words
+fn subject_of(
+    parent: &str,
+    depth: usize,
+    shape: Shape,
+    key: &str,
+    value: Option<&Value>,
+) -> String {
+    let words = key.replace('_', " ");
+    match (depth, shape) {
+        (0, _) => match key {
+            "win_when" => "how to win".to_string(),
+            "lose_when" => "how to lose".to_string(),
+            "game" => "the game's name".to_string(),
+            _ => format!("the {words}"),
+        },
+        (_, Shape::List(noun)) => format!("the {noun} '{key}'"),
+        (1, Shape::Map) => value
+            .and_then(|v| v.get("type"))
+            .and_then(Value::as_str)
+            .map_or_else(
+                || format!("{parent}'s {words}"),
+                |kind| format!("the {kind}"),
+            ),
+        (_, Shape::Map) if key == "then" => format!("what {parent} does"),
+        (_, Shape::Map) if parent.ends_with('\'') => format!("the {words} of {parent}"),
+        (_, Shape::Map) => format!("{parent}'s {words}"),
+    }
+}
+
+fn diff_node(
+    before: Option<&Value>,
+    after: Option<&Value>,
+    path: &mut Vec<String>,
+    subject: &str,
+    changes: &mut Vec<GameChange>,
+) {
+    if before == after {
+        return;
+    }
+    if let (Some(b), Some(a)) = (before, after) {
+        if path.len() < MAX_DEPTH {
+            if let Some((b_items, a_items)) = matching_items(b, a) {
+                for key in union_keys(&a_items, &[Some(&b_items)]) {
+                    let (b, a) = (b_items.get(&key), a_items.get(&key));
+                    let child = subject_of(subject, path.len(), a_items.shape, &key, a.or(b));
+                    path.push(key);
+                    diff_node(b, a, path, &child, changes);
+                    let _ = path.pop();
+                }
+                return;
+            }
+        }
+    }
+    let kind = match (before, after) {
+        (None, _) => ChangeKind::Added,
+        (_, None) => ChangeKind::Removed,
+        _ => ChangeKind::Changed,
+    };
+    changes.push(GameChange {
+        path: path.clone(),
+        subject: subject.to_string(),
+        kind,
+        before: before.map(render),
+        after: after.map(render),
+    });
+}
+
+fn merge_node(
+    base: Option<&Value>,
+    ours: Option<&Value>,
+    theirs: Option<&Value>,
+    path: &mut Vec<String>,
+    subject: &str,
+    conflicts: &mut Vec<MergeConflict>,
+) -> Option<Value> {
+    if ours == theirs || theirs == base {
+        return ours.cloned();
+    }
+    if ours == base {
+        return theirs.cloned();
+    }
+    if let (Some(o), Some(t)) = (ours, theirs) {
+        if path.len() < MAX_DEPTH {
+            if let Some((o_items, t_items)) = matching_items(o, t) {
+                let b_items = base
+                    .and_then(Items::of)
+                    .filter(|items| items.shape == o_items.shape);
+                let mut merged = Vec::new();
+                for key in union_keys(&o_items, &[Some(&t_items), b_items.as_ref()]) {
+                    let b = b_items.as_ref().and_then(|items| items.get(&key));
+                    let (o, t) = (o_items.get(&key), t_items.get(&key));
+                    let child = subject_of(subject, path.len(), o_items.shape, &key, o.or(t).or(b));
+                    path.push(key.clone());
+                    if let Some(value) = merge_node(b, o, t, path, &child, conflicts) {
+                        merged.push((key, value));
+                    }
+                    let _ = path.pop();
+                }
+                return Some(rebuild(o_items.shape, merged));
+            }
+        }
+    }
+    conflicts.push(MergeConflict {
+        path: path.clone(),
+        subject: subject.to_string(),
+        base: base.map(render),
+        ours: ours.map(render),
+        theirs: theirs.map(render),
+    });
+    ours.cloned()
+}
+
+fn rebuild(shape: Shape, entries: Vec<(String, Value)>) -> Value {
+    match shape {
+        Shape::Map => Value::Mapping(
+            entries
+                .into_iter()
+                .map(|(key, value)| (Value::String(key), value))
+                .collect::<Mapping>(),
+        ),
+        Shape::List(_) => Value::Sequence(entries.into_iter().map(|(_, value)| value).collect()),
+    }
+}
+
+/// A value as kids would write it; lists and maps end in a newline
+fn render(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Null => "nothing".to_string(),
+        Value::Bool(flag) => flag.to_string(),
+        Value::Number(number) => number.to_string(),
+        _ => serde_yaml::to_string(value).unwrap_or_default(),
+    }
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    chars.next().map_or_else(String::new, |first| {
+        first.to_uppercase().chain(chars).collect()
+    })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    const ORIGINAL: &str = r"
+game: hop-hop
+characters:
+  player:
+    type: bunny
+    speed: slow
+  slime:
+    type: slime
+    move: auto
+rules:
+  - when: player touches coin
+    then:
+      - add_score: 10
+  - when: player touches slime
+    then:
+      - lose_life: 1
+lives: 3
+";
+
+    #[test]
+    fn test_diff_describes_entities_and_rules() {
+        let after = ORIGINAL
+            .replace("speed: slow", "speed: fast")
+            .replace("  slime:\n    type: slime\n    move: auto\n", "")
+            .replace("lives: 3", "lives: 3\nmusic: upbeat");
+        let diff = diff_games(ORIGINAL, &after).unwrap();
+        assert_eq!(
+            diff.describe(),
+            [
+                "Changed the bunny's speed from slow to fast",
+                "Removed the slime",
+                "Added the music",
+            ]
+        );
+        assert_eq!(diff.changes[0].path, ["characters", "player", "speed"]);
+
+        // Moving rules around is not a change
+        let reordered = diff_games(
+            "rules:\n  - when: a\n    then: [x]\n  - when: b\n    then: [y]\n",
+            "rules:\n  - when: b\n    then: [y]\n  - when: a\n    then: [z]\n",
+        )
+        .unwrap();
+        assert_eq!(reordered.describe(), ["Changed what the rule 'a' does"]);
+        assert_eq!(reordered.changes[0].before.as_deref(), Some("- x\n"));
+        assert!(diff_games(ORIGINAL, ORIGINAL).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_merge_takes_upstream_improvements() {
+        let remix = ORIGINAL
+            .replace("type: bunny", "type: cat")
+            .replace("lose_life: 1", "lose_life: 2");
+        let updated = ORIGINAL
+            .replace("speed: slow", "speed: medium")
+            .replace("lives: 3", "lives: 5")
+            .replace(
+                "rules:\n",
+                "rules:\n  - when: score reaches 100\n    then:\n      - play: victory\n",
+            );
+
+        let merged = merge_games(ORIGINAL, &remix, &updated).unwrap();
+        assert!(merged.is_clean(), "{:?}", merged.conflicts);
+        let game: Value = serde_yaml::from_str(&merged.yaml).unwrap();
+        assert_eq!(game["characters"]["player"]["type"], "cat");
+        assert_eq!(game["characters"]["player"]["speed"], "medium");
+        assert_eq!(game["lives"], 5);
+        let rules = game["rules"].as_sequence().unwrap();
+        assert_eq!(rules.len(), 3);
+        assert_eq!(rules[1]["then"][0]["lose_life"], 2);
+        assert_eq!(rules[2]["when"], "score reaches 100");
+    }
+
+    #[test]
+    fn test_merge_conflicts_keep_the_remix() {
+        let remix = ORIGINAL
+            .replace("speed: slow", "speed: fast")
+            .replace("  slime:\n    type: slime\n    move: auto\n", "");
+        let updated = ORIGINAL
+            .replace("speed: slow", "speed: medium")
+            .replace("move: auto", "move: wander");
+
+        let merged = merge_games(ORIGINAL, &remix, &updated).unwrap();
+        let described: Vec<String> = merged
+            .conflicts
+            .iter()
+            .map(MergeConflict::describe)
+            .collect();
+        assert_eq!(
+            described,
+            [
+                "You both changed the bunny's speed",
+                "You removed the slime, but the original changed it",
+            ]
+        );
+        assert_eq!(merged.conflicts[0].ours.as_deref(), Some("fast"));
+        assert_eq!(merged.conflicts[0].theirs.as_deref(), Some("medium"));
+
+        let game: Value = serde_yaml::from_str(&merged.yaml).unwrap();
+        assert_eq!(game["characters"]["player"]["speed"], "fast");
+        assert!(game["characters"].get("slime").is_none());
+        assert!(merge_games("a: [", ORIGINAL, ORIGINAL).is_err());
+    }
+}
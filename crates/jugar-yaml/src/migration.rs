@@ -485,6 +485,7 @@ mod tests {
                 when_touch: Some(Level1TouchEvent {
                     target: "star".to_string(),
                     sound: Some("ding".to_string()),
+                    caption: None,
                     score: Some(1),
                     target_action: None,
                 }),
@@ -7,10 +7,12 @@
 //! - Debouncing rapid changes
 //! - Hot-reload game state
 //! - Kid-friendly error display
+//! - Remembering the last working text in an [`EditHistory`]
 
 use std::time::{Duration, Instant};
 
-use crate::compiler::YamlCompiler;
+use crate::compiler::{CancelToken, CompileBudget, CompileOutcome, YamlCompiler};
+use crate::history::EditHistory;
 use crate::{CompiledGame, YamlError};
 
 /// Default debounce delay in milliseconds
@@ -126,7 +128,7 @@ pub enum PreviewResult {
     /// Compilation succeeded
     Success {
         /// The compiled game
-        game: CompiledGame,
+        game: Box<CompiledGame>,
         /// Compilation time
         compile_time: Duration,
     },
@@ -162,7 +164,7 @@ impl PreviewResult {
     #[must_use]
     pub fn game(&self) -> Option<&CompiledGame> {
         match self {
-            Self::Success { game, .. } => Some(game),
+            Self::Success { game, .. } => Some(game.as_ref()),
             _ => None,
         }
     }
@@ -250,6 +252,16 @@ impl LivePreview {
         self.compile_and_update(yaml)
     }
 
+    /// Handle a change made through an [`EditHistory`]
+    ///
+    /// Like [`Self::on_yaml_change`], and the history remembers the text if
+    /// it compiles so the kid can get back to it.
+    pub fn on_history_change(&mut self, history: &mut EditHistory) -> PreviewResult {
+        let result = self.on_yaml_change(history.text());
+        history.record_preview(&result);
+        result
+    }
+
     /// Force immediate compilation (bypasses debounce)
     pub fn compile_now(&mut self, yaml: &str) -> PreviewResult {
         self.debouncer.reset();
@@ -265,19 +277,58 @@ impl LivePreview {
         }
     }
 
+    /// Compile with a cancellation token and a time budget (bypasses debounce)
+    ///
+    /// Returns `None` if the compile was cancelled or ran out of time
+    /// before finding anything wrong; the last valid game and errors are
+    /// kept so the preview doesn't flicker while the user is still typing.
+    /// Errors found before stopping are shown straight away.
+    pub fn compile_with(
+        &mut self,
+        yaml: &str,
+        cancel: &CancelToken,
+        budget: CompileBudget,
+    ) -> Option<PreviewResult> {
+        self.debouncer.reset();
+        let start = Instant::now();
+        let result = match self.compiler.compile_with(yaml, cancel, budget) {
+            CompileOutcome::Compiled(game) => Ok(*game),
+            CompileOutcome::Failed(error) => Err(error),
+            CompileOutcome::Cancelled { diagnostics, .. }
+            | CompileOutcome::OutOfBudget { diagnostics, .. } => {
+                if diagnostics.is_empty() {
+                    return None;
+                }
+                self.last_errors.clone_from(&diagnostics);
+                return Some(PreviewResult::Error {
+                    errors: diagnostics,
+                });
+            }
+        };
+        Some(self.record(result, start))
+    }
+
     /// Internal compilation logic
     fn compile_and_update(&mut self, yaml: &str) -> PreviewResult {
         let start = Instant::now();
+        let result = self.compiler.compile(yaml);
+        self.record(result, start)
+    }
+
+    fn record(&mut self, result: Result<CompiledGame, YamlError>, start: Instant) -> PreviewResult {
         self.compilation_count += 1;
 
-        match self.compiler.compile(yaml) {
+        match result {
             Ok(game) => {
                 let compile_time = start.elapsed();
                 self.last_valid_game = Some(game.clone());
                 self.last_errors.clear();
                 self.success_count += 1;
 
-                PreviewResult::Success { game, compile_time }
+                PreviewResult::Success {
+                    game: Box::new(game),
+                    compile_time,
+                }
             }
             Err(error) => {
                 self.last_errors = vec![error];
@@ -480,13 +531,20 @@ mod tests {
                 rules: Vec::new(),
                 background: None,
                 music: None,
+                win_when: None,
+                lose_when: None,
+                items: Vec::new(),
+                templates: Vec::new(),
+                world: None,
+                questions: Vec::new(),
+                difficulty: None,
             }
         }
 
         #[test]
         fn test_success_result() {
             let result = PreviewResult::Success {
-                game: mock_game(),
+                game: Box::new(mock_game()),
                 compile_time: Duration::from_millis(10),
             };
             assert!(result.is_success());
@@ -632,6 +690,23 @@ mod tests {
             preview.set_debounce_delay(300);
             assert_eq!(preview.debounce_delay(), Duration::from_millis(300));
         }
+
+        #[test]
+        fn test_cancelled_compile_keeps_last_game() {
+            let mut preview = LivePreview::new();
+            let token = CancelToken::new();
+            let result = preview.compile_with("character: bunny", &token, CompileBudget::UNLIMITED);
+            assert!(result.unwrap().is_success());
+
+            token.cancel();
+            let stale = preview.compile_with("character: cat", &token, CompileBudget::UNLIMITED);
+            assert!(stale.is_none());
+            assert_eq!(preview.compilation_count(), 1);
+            assert_eq!(
+                preview.last_valid_game().unwrap().entities[0].entity_type,
+                "bunny"
+            );
+        }
     }
 
     mod preview_stats_tests {
@@ -11,6 +11,10 @@
 //! - All data stays on-device by default
 //! - Differential privacy noise injection for aggregate statistics
 
+use alloc::collections::BTreeMap;
+use core::fmt::Write;
+
+use jugar_core::Storage;
 use serde::{Deserialize, Serialize};
 
 /// COPPA compliance level
@@ -655,6 +659,286 @@ impl RetentionMetrics {
     }
 }
 
+// ============================================================================
+// PRIVACY BUDGET
+// Sequential composition: epsilons of repeated queries add up, so the total
+// spent on a device is capped and every release is recorded.
+// ============================================================================
+
+/// Storage key for the persisted privacy budget ledger
+pub const PRIVACY_BUDGET_KEY: &str = "jugar.privacy.budget";
+
+/// Default total epsilon a device may spend across all metrics
+pub const DEFAULT_PRIVACY_BUDGET: f64 = 3.0;
+
+/// Slack for floating point accumulation when comparing against the limit
+const BUDGET_TOLERANCE: f64 = 1e-9;
+
+/// Error from the privacy budget ledger
+#[derive(Debug, Clone, PartialEq)]
+pub enum PrivacyBudgetError {
+    /// Query would spend more epsilon than remains
+    Exhausted {
+        /// Metric being queried
+        metric: String,
+        /// Epsilon the query needs
+        requested: f64,
+        /// Epsilon left in the budget
+        remaining: f64,
+    },
+    /// Epsilon must be positive and finite
+    InvalidEpsilon(f64),
+    /// Stored ledger couldn't be read
+    Corrupt(String),
+    /// Ledger couldn't be written
+    Storage(String),
+}
+
+impl core::fmt::Display for PrivacyBudgetError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Exhausted {
+                metric,
+                requested,
+                remaining,
+            } => write!(
+                f,
+                "Privacy budget used up: '{metric}' needs ε {requested:.2}, only {remaining:.2} left"
+            ),
+            Self::InvalidEpsilon(epsilon) => write!(f, "Invalid epsilon: {epsilon}"),
+            Self::Corrupt(message) => write!(f, "Privacy ledger is corrupted: {message}"),
+            Self::Storage(message) => write!(f, "Couldn't save privacy ledger: {message}"),
+        }
+    }
+}
+
+impl core::error::Error for PrivacyBudgetError {}
+
+/// Ledger of differential privacy epsilon spent per metric
+///
+/// Every noisy release of a metric costs its epsilon. Once the total reaches
+/// the limit, further queries are refused so repeated averaging can't undo
+/// the noise.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PrivacyBudget {
+    limit: f64,
+    spent: BTreeMap<String, f64>,
+    queries: BTreeMap<String, u32>,
+}
+
+impl Default for PrivacyBudget {
+    fn default() -> Self {
+        Self::new(DEFAULT_PRIVACY_BUDGET)
+    }
+}
+
+impl PrivacyBudget {
+    /// Creates an empty ledger with a total epsilon limit
+    #[must_use]
+    pub const fn new(limit: f64) -> Self {
+        Self {
+            limit,
+            spent: BTreeMap::new(),
+            queries: BTreeMap::new(),
+        }
+    }
+
+    /// Total epsilon allowed
+    #[must_use]
+    pub const fn limit(&self) -> f64 {
+        self.limit
+    }
+
+    /// Epsilon spent on one metric
+    #[must_use]
+    pub fn spent(&self, metric: &str) -> f64 {
+        self.spent.get(metric).copied().unwrap_or(0.0)
+    }
+
+    /// Number of answered queries for one metric
+    #[must_use]
+    pub fn queries(&self, metric: &str) -> u32 {
+        self.queries.get(metric).copied().unwrap_or(0)
+    }
+
+    /// Epsilon spent across all metrics
+    #[must_use]
+    pub fn total_spent(&self) -> f64 {
+        self.spent.values().sum()
+    }
+
+    /// Epsilon left before queries are refused
+    #[must_use]
+    pub fn remaining(&self) -> f64 {
+        (self.limit - self.total_spent()).max(0.0)
+    }
+
+    /// Returns true if a query costing `epsilon` would be allowed
+    #[must_use]
+    pub fn can_afford(&self, epsilon: f64) -> bool {
+        self.total_spent() + epsilon <= self.limit + BUDGET_TOLERANCE
+    }
+
+    /// Records `epsilon` spent on `metric`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PrivacyBudgetError::InvalidEpsilon`] for a non-positive
+    /// epsilon and [`PrivacyBudgetError::Exhausted`] if the budget can't
+    /// cover it; nothing is recorded in either case.
+    pub fn charge(&mut self, metric: &str, epsilon: f64) -> Result<(), PrivacyBudgetError> {
+        if !epsilon.is_finite() || epsilon <= 0.0 {
+            return Err(PrivacyBudgetError::InvalidEpsilon(epsilon));
+        }
+        if !self.can_afford(epsilon) {
+            return Err(PrivacyBudgetError::Exhausted {
+                metric: metric.to_string(),
+                requested: epsilon,
+                remaining: self.remaining(),
+            });
+        }
+        *self.spent.entry(metric.to_string()).or_insert(0.0) += epsilon;
+        *self.queries.entry(metric.to_string()).or_insert(0) += 1;
+        Ok(())
+    }
+
+    /// Releases a noisy count, charging the config's epsilon first
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the budget refuses the charge.
+    pub fn query(
+        &mut self,
+        metric: &str,
+        value: u64,
+        config: &DifferentialPrivacyConfig,
+    ) -> Result<i64, PrivacyBudgetError> {
+        self.charge(metric, config.epsilon)?;
+        let noise_gen = DifferentialPrivacy::new(config.epsilon, config.sensitivity);
+        Ok(noise_gen.add_laplace_noise_u64(value))
+    }
+
+    /// Summary of spending for parents and teachers
+    #[must_use]
+    pub fn report(&self) -> BudgetReport {
+        let metrics = self
+            .spent
+            .iter()
+            .map(|(metric, &epsilon)| MetricBudget {
+                metric: metric.clone(),
+                epsilon,
+                queries: self.queries(metric),
+            })
+            .collect();
+        BudgetReport {
+            limit: self.limit,
+            spent: self.total_spent(),
+            remaining: self.remaining(),
+            metrics,
+        }
+    }
+
+    /// Loads the ledger from storage, or starts a fresh one
+    ///
+    /// The given limit replaces the stored one so a lowered limit takes
+    /// effect immediately; past spending is kept.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PrivacyBudgetError::Corrupt`] if the stored ledger isn't
+    /// valid JSON. It is not silently reset, since that would refill the
+    /// budget.
+    pub fn load(storage: &dyn Storage, limit: f64) -> Result<Self, PrivacyBudgetError> {
+        let Some(json) = storage.get(PRIVACY_BUDGET_KEY) else {
+            return Ok(Self::new(limit));
+        };
+        let mut budget: Self =
+            serde_json::from_str(&json).map_err(|e| PrivacyBudgetError::Corrupt(e.to_string()))?;
+        budget.limit = limit;
+        Ok(budget)
+    }
+
+    /// Writes the ledger to storage
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PrivacyBudgetError::Storage`] if the backend rejects it.
+    pub fn save(&self, storage: &mut dyn Storage) -> Result<(), PrivacyBudgetError> {
+        let json =
+            serde_json::to_string(self).map_err(|e| PrivacyBudgetError::Storage(e.to_string()))?;
+        storage
+            .set(PRIVACY_BUDGET_KEY, &json)
+            .map_err(|e| PrivacyBudgetError::Storage(e.to_string()))
+    }
+}
+
+/// Epsilon spent on one metric
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricBudget {
+    /// Metric name
+    pub metric: String,
+    /// Epsilon spent
+    pub epsilon: f64,
+    /// Answered queries
+    pub queries: u32,
+}
+
+/// Privacy budget report for parents and teachers
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BudgetReport {
+    /// Total epsilon allowed
+    pub limit: f64,
+    /// Total epsilon spent
+    pub spent: f64,
+    /// Epsilon left
+    pub remaining: f64,
+    /// Spending per metric, sorted by name
+    pub metrics: Vec<MetricBudget>,
+}
+
+impl BudgetReport {
+    /// Returns true once no more queries can be answered
+    #[must_use]
+    pub fn is_exhausted(&self) -> bool {
+        self.remaining <= BUDGET_TOLERANCE
+    }
+
+    /// Plain-language summary
+    #[must_use]
+    pub fn summary(&self) -> String {
+        let percent = if self.limit > 0.0 {
+            (self.spent / self.limit * 100.0).min(100.0)
+        } else {
+            100.0
+        };
+        let mut text = format!(
+            "Privacy budget: {:.2} of {:.2} used ({percent:.0}%)\n",
+            self.spent, self.limit
+        );
+        if self.metrics.is_empty() {
+            text.push_str("No statistics have been shared.\n");
+        }
+        for metric in &self.metrics {
+            let _ = writeln!(
+                text,
+                "- {}: {} {}, ε {:.2}",
+                metric.metric,
+                metric.queries,
+                if metric.queries == 1 {
+                    "query"
+                } else {
+                    "queries"
+                },
+                metric.epsilon
+            );
+        }
+        if self.is_exhausted() {
+            text.push_str("Budget used up: no more statistics will be shared.\n");
+        }
+        text
+    }
+}
+
 /// Privacy validator for YAML game definitions
 #[derive(Debug, Clone, Default)]
 pub struct PrivacyValidator {
@@ -1279,4 +1563,82 @@ analytics: enabled
             assert!((metrics.epsilon - 1.0).abs() < f64::EPSILON);
         }
     }
+
+    mod privacy_budget_tests {
+        use super::*;
+        use jugar_core::MemoryStorage;
+
+        #[test]
+        fn test_charges_accumulate_per_metric() {
+            let mut budget = PrivacyBudget::new(2.0);
+            budget.charge("play_count", 0.5).unwrap();
+            budget.charge("play_count", 0.5).unwrap();
+            budget.charge("levels_completed", 0.25).unwrap();
+
+            assert!((budget.spent("play_count") - 1.0).abs() < 1e-9);
+            assert_eq!(budget.queries("play_count"), 2);
+            assert!((budget.remaining() - 0.75).abs() < 1e-9);
+        }
+
+        #[test]
+        fn test_refuses_queries_past_budget() {
+            let mut budget = PrivacyBudget::new(1.0);
+            let config = DifferentialPrivacyConfig::moderate_privacy();
+            assert!(budget.query("play_count", 10, &config).is_ok());
+
+            let refused = budget.query("play_count", 10, &config);
+            assert!(matches!(
+                refused,
+                Err(PrivacyBudgetError::Exhausted { ref metric, .. }) if metric == "play_count"
+            ));
+            // Refused queries aren't recorded
+            assert_eq!(budget.queries("play_count"), 1);
+            assert!(budget.report().is_exhausted());
+        }
+
+        #[test]
+        fn test_rejects_invalid_epsilon() {
+            let mut budget = PrivacyBudget::default();
+            assert!(matches!(
+                budget.charge("x", 0.0),
+                Err(PrivacyBudgetError::InvalidEpsilon(_))
+            ));
+            assert!(budget.charge("x", f64::NAN).is_err());
+            assert!(budget.report().metrics.is_empty());
+        }
+
+        #[test]
+        fn test_persists_through_storage() {
+            let mut storage = MemoryStorage::new();
+            let mut budget = PrivacyBudget::load(&storage, 1.0).unwrap();
+            budget.charge("play_time", 0.6).unwrap();
+            budget.save(&mut storage).unwrap();
+
+            // Reloading keeps past spending, so the budget can't be refilled
+            let mut reloaded = PrivacyBudget::load(&storage, 1.0).unwrap();
+            assert_eq!(reloaded, budget);
+            assert!(reloaded.charge("play_time", 0.6).is_err());
+        }
+
+        #[test]
+        fn test_corrupt_ledger_is_an_error() {
+            let mut storage = MemoryStorage::new();
+            storage.set(PRIVACY_BUDGET_KEY, "not json").unwrap();
+            assert!(matches!(
+                PrivacyBudget::load(&storage, 1.0),
+                Err(PrivacyBudgetError::Corrupt(_))
+            ));
+        }
+
+        #[test]
+        fn test_report_summary() {
+            let mut budget = PrivacyBudget::new(2.0);
+            assert!(budget.report().summary().contains("No statistics"));
+
+            budget.charge("play_count", 1.0).unwrap();
+            let summary = budget.report().summary();
+            assert!(summary.contains("1.00 of 2.00 used (50%)"));
+            assert!(summary.contains("play_count: 1 query"));
+        }
+    }
 }
@@ -3,8 +3,11 @@
 //! Per spec Section 9.1: All uploaded content is sandboxed
 //! to prevent malicious or inappropriate content.
 
-use crate::error::YamlError;
+use crate::error::{HelperCharacter, KidFriendlyError, YamlError};
 use crate::schema::SchemaLevel;
+use crate::CompiledAction;
+
+pub use jugar_core::{ContentFilter, ContentViolation};
 
 /// Maximum YAML file size (64 KB per spec)
 pub const MAX_YAML_SIZE: usize = 64 * 1024;
@@ -171,60 +174,6 @@ impl SandboxError {
     }
 }
 
-/// Content filter for inappropriate words
-#[derive(Debug, Clone)]
-pub struct ContentFilter {
-    /// Blocked words (case-insensitive matching)
-    blocked_words: Vec<String>,
-}
-
-impl Default for ContentFilter {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
-impl ContentFilter {
-    /// Create a new content filter with default blocklist
-    #[must_use]
-    pub fn new() -> Self {
-        Self {
-            blocked_words: default_blocklist(),
-        }
-    }
-
-    /// Check text for content violations
-    #[must_use]
-    pub fn check(&self, text: &str) -> Option<ContentViolation> {
-        let text_lower = text.to_lowercase();
-
-        for blocked in &self.blocked_words {
-            if text_lower.contains(blocked) {
-                return Some(ContentViolation {
-                    word: blocked.clone(),
-                    reason: "This word isn't allowed in games for kids".to_string(),
-                });
-            }
-        }
-
-        None
-    }
-
-    /// Add a word to the blocklist
-    pub fn block_word(&mut self, word: impl Into<String>) {
-        self.blocked_words.push(word.into().to_lowercase());
-    }
-}
-
-/// A content violation detected by the filter
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct ContentViolation {
-    /// The word that triggered the violation
-    pub word: String,
-    /// Reason for blocking
-    pub reason: String,
-}
-
 /// Calculate the nesting depth of a YAML value
 #[must_use]
 pub fn calculate_depth(value: &serde_yaml::Value) -> u8 {
@@ -324,19 +273,209 @@ fn collect_strings(value: &serde_yaml::Value, output: &mut String) {
     }
 }
 
-/// Default blocklist for content filtering
-/// This is a minimal list focused on child safety
-fn default_blocklist() -> Vec<String> {
-    // Minimal blocklist - in production this would be more comprehensive
-    // but loaded from an external source for easy updates
-    vec![
-        "kill".to_string(),
-        "death".to_string(),
-        "blood".to_string(),
-        "gore".to_string(),
-        "violent".to_string(),
-        "weapon".to_string(),
-    ]
+/// Most entities a running game can spawn in one second
+pub const MAX_SPAWNS_PER_SECOND: u32 = 30;
+
+/// Most sounds a running game can start in one second
+pub const MAX_SOUNDS_PER_SECOND: u32 = 16;
+
+/// Most rules a running game can fire in one frame
+pub const MAX_RULES_PER_FRAME: u32 = 100;
+
+/// Something a running game does that is rate limited
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QuotaKind {
+    /// Creating an entity
+    Spawn,
+    /// Starting a sound
+    Sound,
+    /// Running a rule's actions
+    Rule,
+}
+
+impl QuotaKind {
+    const ALL: [Self; 3] = [Self::Spawn, Self::Sound, Self::Rule];
+
+    const fn index(self) -> usize {
+        match self {
+            Self::Spawn => 0,
+            Self::Sound => 1,
+            Self::Rule => 2,
+        }
+    }
+}
+
+/// Runtime limits, complementing the static caps checked at load time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RuntimeQuotas {
+    /// Entities spawned per second
+    pub spawns_per_second: u32,
+    /// Sounds started per second
+    pub sounds_per_second: u32,
+    /// Rules fired per frame
+    pub rules_per_frame: u32,
+}
+
+impl Default for RuntimeQuotas {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RuntimeQuotas {
+    /// Default limits
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            spawns_per_second: MAX_SPAWNS_PER_SECOND,
+            sounds_per_second: MAX_SOUNDS_PER_SECOND,
+            rules_per_frame: MAX_RULES_PER_FRAME,
+        }
+    }
+
+    /// Limit for one kind of work
+    #[must_use]
+    pub const fn limit(&self, kind: QuotaKind) -> u32 {
+        match kind {
+            QuotaKind::Spawn => self.spawns_per_second,
+            QuotaKind::Sound => self.sounds_per_second,
+            QuotaKind::Rule => self.rules_per_frame,
+        }
+    }
+}
+
+/// Work a game tried to do past its quota
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuotaExceeded {
+    /// What was limited
+    pub kind: QuotaKind,
+    /// The limit that was hit
+    pub limit: u32,
+    /// How many were skipped since the last report
+    pub dropped: u32,
+}
+
+impl QuotaExceeded {
+    /// Convert to a kid-friendly message
+    #[must_use]
+    pub fn to_kid_friendly(&self) -> KidFriendlyError {
+        let (explanation, suggestion) = match self.kind {
+            QuotaKind::Spawn => (
+                format!(
+                    "Only {} new things can appear each second, so I skipped some.",
+                    self.limit
+                ),
+                "Try a bigger 'every' time on your spawn, like 'every: 1s'",
+            ),
+            QuotaKind::Sound => (
+                format!(
+                    "Only {} sounds can start each second, so I skipped some.",
+                    self.limit
+                ),
+                "Try playing sounds only when something special happens",
+            ),
+            QuotaKind::Rule => (
+                format!(
+                    "Only {} rules can happen at the same moment, so I skipped some.",
+                    self.limit
+                ),
+                "Try making your 'when' rules happen less often",
+            ),
+        };
+        KidFriendlyError {
+            headline: "Your game is doing too much at once!".to_string(),
+            explanation,
+            location: None,
+            suggestions: vec![suggestion.to_string()],
+            helper: HelperCharacter::Dragon,
+        }
+    }
+}
+
+/// Enforces [`RuntimeQuotas`] while a game runs
+///
+/// Call [`begin_frame`](Self::begin_frame) once per frame and ask
+/// [`allow`](Self::allow) before each spawn, sound or rule. Work over the
+/// limit is skipped instead of slowing the game down, and
+/// [`take_exceeded`](Self::take_exceeded) reports what was skipped.
+#[derive(Debug, Clone)]
+pub struct RuntimeGuard {
+    quotas: RuntimeQuotas,
+    /// Seconds since the current one-second window started
+    window: f32,
+    used: [u32; 3],
+    dropped: [u32; 3],
+}
+
+impl Default for RuntimeGuard {
+    fn default() -> Self {
+        Self::new(RuntimeQuotas::new())
+    }
+}
+
+impl RuntimeGuard {
+    /// Creates a guard with the given limits
+    #[must_use]
+    pub const fn new(quotas: RuntimeQuotas) -> Self {
+        Self {
+            quotas,
+            window: 0.0,
+            used: [0; 3],
+            dropped: [0; 3],
+        }
+    }
+
+    /// Starts a frame `dt` seconds after the last one
+    pub fn begin_frame(&mut self, dt: f32) {
+        self.used[QuotaKind::Rule.index()] = 0;
+        self.window += dt.max(0.0);
+        if self.window >= 1.0 {
+            self.window = 0.0;
+            self.used[QuotaKind::Spawn.index()] = 0;
+            self.used[QuotaKind::Sound.index()] = 0;
+        }
+    }
+
+    /// Counts one unit of work, returning false if it is over the limit
+    pub fn allow(&mut self, kind: QuotaKind) -> bool {
+        let i = kind.index();
+        if self.used[i] < self.quotas.limit(kind) {
+            self.used[i] += 1;
+            true
+        } else {
+            self.dropped[i] = self.dropped[i].saturating_add(1);
+            false
+        }
+    }
+
+    /// Counts a compiled action, returning false if it should be skipped
+    ///
+    /// Only spawns and sounds are limited; other actions are always allowed.
+    pub fn allow_action(&mut self, action: &CompiledAction) -> bool {
+        match action {
+            CompiledAction::Spawn(_) => self.allow(QuotaKind::Spawn),
+            CompiledAction::PlaySound(_) => self.allow(QuotaKind::Sound),
+            _ => true,
+        }
+    }
+
+    /// Limits hit since the last call, one per kind
+    pub fn take_exceeded(&mut self) -> Vec<QuotaExceeded> {
+        QuotaKind::ALL
+            .iter()
+            .filter_map(|&kind| {
+                let dropped = core::mem::take(&mut self.dropped[kind.index()]);
+                if dropped == 0 {
+                    return None;
+                }
+                Some(QuotaExceeded {
+                    kind,
+                    limit: self.quotas.limit(kind),
+                    dropped,
+                })
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -481,36 +620,6 @@ characters:
     mod content_filter_tests {
         use super::*;
 
-        #[test]
-        fn test_filter_blocks_violent_words() {
-            let filter = ContentFilter::new();
-            let result = filter.check("I want to kill the monster");
-            assert!(result.is_some());
-            assert_eq!(result.unwrap().word, "kill");
-        }
-
-        #[test]
-        fn test_filter_case_insensitive() {
-            let filter = ContentFilter::new();
-            let result = filter.check("BLOOD and gore");
-            assert!(result.is_some());
-        }
-
-        #[test]
-        fn test_filter_allows_friendly_content() {
-            let filter = ContentFilter::new();
-            let result = filter.check("bunny catches stars in the sky");
-            assert!(result.is_none());
-        }
-
-        #[test]
-        fn test_custom_blocked_word() {
-            let mut filter = ContentFilter::new();
-            filter.block_word("badword");
-            let result = filter.check("this has a badword");
-            assert!(result.is_some());
-        }
-
         #[test]
         fn test_sandbox_validates_content() {
             let sandbox = ContentSandbox::new();
@@ -622,4 +731,69 @@ outer:
             assert!(matches!(yaml_err, YamlError::UnknownWord { .. }));
         }
     }
+
+    mod runtime_quota_tests {
+        use super::*;
+
+        #[test]
+        fn test_spawns_limited_per_second() {
+            let mut guard = RuntimeGuard::new(RuntimeQuotas {
+                spawns_per_second: 3,
+                ..RuntimeQuotas::new()
+            });
+            let mut spawned = 0;
+            for _ in 0..10 {
+                guard.begin_frame(1.0 / 60.0);
+                if guard.allow(QuotaKind::Spawn) {
+                    spawned += 1;
+                }
+            }
+            assert_eq!(spawned, 3);
+
+            guard.begin_frame(1.0);
+            assert!(guard.allow(QuotaKind::Spawn));
+        }
+
+        #[test]
+        fn test_rules_reset_every_frame() {
+            let mut guard = RuntimeGuard::new(RuntimeQuotas {
+                rules_per_frame: 2,
+                ..RuntimeQuotas::new()
+            });
+            guard.begin_frame(0.016);
+            assert!(guard.allow(QuotaKind::Rule));
+            assert!(guard.allow(QuotaKind::Rule));
+            assert!(!guard.allow(QuotaKind::Rule));
+            guard.begin_frame(0.016);
+            assert!(guard.allow(QuotaKind::Rule));
+        }
+
+        #[test]
+        fn test_exceeded_reported_once() {
+            let mut guard = RuntimeGuard::new(RuntimeQuotas {
+                sounds_per_second: 1,
+                ..RuntimeQuotas::new()
+            });
+            let pop = CompiledAction::PlaySound("pop".to_string());
+            assert!(guard.allow_action(&pop));
+            assert!(!guard.allow_action(&pop));
+            assert!(!guard.allow_action(&pop));
+            assert!(guard.allow_action(&CompiledAction::AddScore(1)));
+
+            let exceeded = guard.take_exceeded();
+            assert_eq!(
+                exceeded,
+                vec![QuotaExceeded {
+                    kind: QuotaKind::Sound,
+                    limit: 1,
+                    dropped: 2,
+                }]
+            );
+            assert!(guard.take_exceeded().is_empty());
+
+            let message = exceeded[0].to_kid_friendly();
+            assert_eq!(message.headline, "Your game is doing too much at once!");
+            assert!(message.explanation.contains("sounds"));
+        }
+    }
 }
@@ -580,6 +580,18 @@ impl ScaffoldingEngine {
         if attempted_lower.contains("key") || attempted_lower.contains("arrow") {
             return "arrows";
         }
+        if attempted_lower.contains("jump") {
+            return "tap-to-jump";
+        }
+        if attempted_lower.contains("drag")
+            || attempted_lower.contains("finger")
+            || attempted_lower.contains("swipe")
+        {
+            return "drag";
+        }
+        if attempted_lower.contains("tilt") || attempted_lower.contains("gyro") {
+            return "tilt";
+        }
         if attempted_lower.contains("touch") || attempted_lower.contains("tap") {
             return "touch";
         }
@@ -890,6 +902,19 @@ mod tests {
             assert!(example.contains("arrows") || example.contains("touch"));
         }
 
+        #[test]
+        fn test_working_example_for_touch_controls() {
+            let example = |style: &str| {
+                ScaffoldingEngine::generate_working_example(&Intent::SetMovement {
+                    style: style.to_string(),
+                })
+            };
+
+            assert!(example("finger").contains("move: drag"));
+            assert!(example("tap_jump").contains("move: tap-to-jump"));
+            assert!(example("gyro").contains("move: tilt"));
+        }
+
         #[test]
         fn test_working_example_for_event() {
             let intent = Intent::DefineEvent {
@@ -6,8 +6,11 @@
 //! - Level 3 (Ages 11+): Full power with .apr models
 
 use crate::error::YamlError;
+use crate::sandbox::{ContentFilter, SandboxError, MAX_ENTITIES};
 use crate::vocabulary::Vocabulary;
+use crate::SpawnLocation;
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 
 /// Schema level for the game
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -22,6 +25,16 @@ pub enum SchemaLevel {
 }
 
 impl SchemaLevel {
+    /// Get the level number (1-3)
+    #[must_use]
+    pub const fn number(self) -> u8 {
+        match self {
+            Self::Level1 => 1,
+            Self::Level2 => 2,
+            Self::Level3 => 3,
+        }
+    }
+
     /// Get maximum allowed nesting depth for this schema level
     ///
     /// Per spec Section 9.1:
@@ -86,11 +99,12 @@ pub fn detect_level(yaml: &str) -> Result<SchemaLevel, YamlError> {
 
 fn has_level3_features(value: &serde_yaml::Value) -> bool {
     if let serde_yaml::Value::Mapping(map) = value {
-        // Level 3 indicators: assets, entities, world, version
+        // Level 3 indicators: assets, entities, world, version, difficulty
         return map.contains_key("assets")
             || map.contains_key("entities")
             || map.contains_key("world")
-            || map.contains_key("version");
+            || map.contains_key("version")
+            || map.contains_key("difficulty");
     }
     false
 }
@@ -100,7 +114,12 @@ fn has_level2_features(value: &serde_yaml::Value) -> bool {
         // Level 2 indicators: characters (plural), rules, lives
         return map.contains_key("characters")
             || map.contains_key("rules")
-            || map.contains_key("lives");
+            || map.contains_key("lives")
+            || map.contains_key("items")
+            || map.contains_key("templates")
+            || map.contains_key("win_when")
+            || map.contains_key("lose_when")
+            || map.contains_key("questions");
     }
     false
 }
@@ -118,7 +137,7 @@ pub struct Level1Game {
     /// Main character type
     pub character: String,
 
-    /// Movement type: arrows, touch, auto
+    /// Movement type: arrows, touch, auto, drag, tap-to-jump, tilt
     #[serde(default, rename = "move")]
     pub move_type: Option<String>,
 
@@ -153,6 +172,10 @@ pub struct Level1TouchEvent {
     #[serde(default)]
     pub sound: Option<String>,
 
+    /// Caption shown when the sound plays
+    #[serde(default)]
+    pub caption: Option<String>,
+
     /// Score change (-9 to +9)
     #[serde(default)]
     pub score: Option<i8>,
@@ -180,12 +203,27 @@ pub struct Level2Game {
     /// Game rules with when/then structure (Level 2 feature)
     pub rules: Option<Vec<Level2Rule>>,
 
+    /// Collectible items the player can carry (Level 2 feature)
+    pub items: Option<std::collections::HashMap<String, Level2Item>>,
+
+    /// Entity templates that `spawn:` actions create copies of (Level 2 feature)
+    pub templates: Option<std::collections::HashMap<String, Level2Character>>,
+
     /// Number of lives (1-9 for Level 2)
     pub lives: Option<u8>,
 
     /// Score goal to win
     pub score_goal: Option<u32>,
 
+    /// Condition that wins the game ("score reaches 10")
+    pub win_when: Option<String>,
+
+    /// Condition that loses the game ("player health reaches 0")
+    pub lose_when: Option<String>,
+
+    /// Quiz questions asked in order (Level 2 feature)
+    pub questions: Option<Vec<Level2Question>>,
+
     /// Background setting from vocabulary
     pub background: Option<String>,
 
@@ -218,6 +256,39 @@ pub struct Level2Character {
     /// Movement pattern for AI
     #[serde(default)]
     pub pattern: Option<String>,
+
+    /// Starting health (1-100)
+    #[serde(default)]
+    pub health: Option<u32>,
+}
+
+/// Collectible item definition for Level 2
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct Level2Item {
+    /// Item type (key, gem, potion, etc.)
+    #[serde(rename = "type")]
+    pub item_type: String,
+
+    /// Most the player can carry (1-99)
+    #[serde(default)]
+    pub max: Option<u32>,
+
+    /// How many the player starts with
+    #[serde(default)]
+    pub start: Option<u32>,
+}
+
+/// Quiz or flashcard question for Level 2
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct Level2Question {
+    /// Question text ("What color is the sky?")
+    pub ask: String,
+
+    /// Answers to pick from (2-4)
+    pub choices: Vec<String>,
+
+    /// The right answer; must be one of the choices
+    pub answer: String,
 }
 
 /// Rule for Level 2
@@ -254,6 +325,73 @@ pub enum Level2Action {
         /// Message or screen to show
         show: String,
     },
+    /// Set an adaptive music parameter (e.g. danger level)
+    SetMusic {
+        /// Parameter name
+        set_music: String,
+        /// New value (0.0 to 1.0)
+        to: f32,
+    },
+    /// Take health away from a character
+    Damage {
+        /// Character that gets hurt
+        damage: String,
+        /// Health to take away
+        #[serde(default = "default_amount")]
+        amount: u32,
+    },
+    /// Give health back to a character
+    Heal {
+        /// Character that gets better
+        heal: String,
+        /// Health to give back
+        #[serde(default = "default_amount")]
+        amount: u32,
+    },
+    /// Put an item in the player's inventory
+    GiveItem {
+        /// Item name from `items:`
+        give_item: String,
+        /// How many to give
+        #[serde(default = "default_amount")]
+        amount: u32,
+    },
+    /// Take an item out of the player's inventory
+    RemoveItem {
+        /// Item name from `items:`
+        remove_item: String,
+        /// How many to take
+        #[serde(default = "default_amount")]
+        amount: u32,
+    },
+    /// Show a speech bubble (`- say: "Hello!"` with optional `by`, `for`, `voice`)
+    Say {
+        /// Line of dialog
+        say: String,
+        /// Character that says it (narrator if missing)
+        #[serde(default)]
+        by: Option<String>,
+        /// How long the bubble stays up ("3s" or 3)
+        #[serde(default, rename = "for")]
+        duration: Option<TimeSpan>,
+        /// Also read the line aloud
+        #[serde(default)]
+        voice: bool,
+    },
+    /// Create an entity from a template (`- spawn: star` with optional `at`, `every`, `max`)
+    Spawn {
+        /// Template name from `templates:`
+        spawn: String,
+        /// Where it appears (random, top, bottom, left, right, "x, y" or a character)
+        #[serde(default)]
+        at: Option<String>,
+        /// Keep spawning on this interval
+        #[serde(default)]
+        every: Option<TimeSpan>,
+        /// Most alive at once
+        #[serde(default)]
+        max: Option<u32>,
+    },
     /// Entity action (respawn, blink, etc.)
     EntityAction {
         /// Target entity name
@@ -265,6 +403,47 @@ pub enum Level2Action {
     Simple(String),
 }
 
+/// A time written in YAML (`3s`, `1.5 seconds` or just `3`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum TimeSpan {
+    /// Plain number of seconds
+    Seconds(f32),
+    /// Text like "3s" or "1.5 seconds"
+    Text(String),
+}
+
+impl TimeSpan {
+    /// The duration in seconds, if it can be read
+    #[must_use]
+    pub fn seconds(&self) -> Option<f32> {
+        match self {
+            Self::Seconds(seconds) => Some(*seconds),
+            Self::Text(text) => {
+                let number = text
+                    .trim()
+                    .trim_end_matches("seconds")
+                    .trim_end_matches("second")
+                    .trim_end_matches('s')
+                    .trim();
+                number.parse().ok()
+            }
+        }
+        .filter(|seconds: &f32| seconds.is_finite() && *seconds > 0.0)
+    }
+
+    fn text(&self) -> String {
+        match self {
+            Self::Seconds(seconds) => seconds.to_string(),
+            Self::Text(text) => text.clone(),
+        }
+    }
+}
+
+const fn default_amount() -> u32 {
+    1
+}
+
 /// Level 3 Game Schema (Ages 11+)
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Level3Game {
@@ -300,6 +479,10 @@ pub struct Level3Game {
     #[serde(default)]
     pub ui: Option<std::collections::HashMap<String, Level3UiElement>>,
 
+    /// AI difficulty: a level from 1 to 10, or `adaptive` to follow the player
+    #[serde(default)]
+    pub difficulty: Option<DifficultyValue>,
+
     /// Level 2 compatibility: character definitions
     #[serde(default)]
     pub characters: Option<std::collections::HashMap<String, Level2Character>>,
@@ -309,6 +492,21 @@ pub struct Level3Game {
     /// Level 2 compatibility: number of lives
     #[serde(default)]
     pub lives: Option<u8>,
+    /// Level 2 compatibility: collectible items
+    #[serde(default)]
+    pub items: Option<std::collections::HashMap<String, Level2Item>>,
+    /// Level 2 compatibility: entity templates
+    #[serde(default)]
+    pub templates: Option<std::collections::HashMap<String, Level2Character>>,
+    /// Level 2 compatibility: win condition
+    #[serde(default)]
+    pub win_when: Option<String>,
+    /// Level 2 compatibility: lose condition
+    #[serde(default)]
+    pub lose_when: Option<String>,
+    /// Level 2 compatibility: quiz questions
+    #[serde(default)]
+    pub questions: Option<Vec<Level2Question>>,
     /// Level 2 compatibility: background setting
     #[serde(default)]
     pub background: Option<String>,
@@ -355,6 +553,14 @@ pub struct Level3World {
     /// Tile distribution
     #[serde(default)]
     pub tiles: Option<std::collections::HashMap<String, f32>>,
+
+    /// Downward pull for platformers, in tiles per second squared
+    #[serde(default)]
+    pub gravity: Option<f32>,
+
+    /// How high tap-to-jump characters jump, in tiles
+    #[serde(default)]
+    pub jump: Option<f32>,
 }
 
 /// Seed value can be "auto" or a number
@@ -367,6 +573,16 @@ pub enum SeedValue {
     Number(u64),
 }
 
+/// Difficulty can be "adaptive" or a level
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum DifficultyValue {
+    /// Fixed level
+    Level(u32),
+    /// Named mode ("adaptive")
+    Mode(String),
+}
+
 /// Entity definition for Level 3
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Level3Entity {
@@ -465,8 +681,15 @@ pub struct Level3UiElement {
 ///
 /// Returns validation errors
 pub fn validate_level1(game: &Level1Game) -> Result<(), YamlError> {
-    let vocab = Vocabulary::level1();
+    validate_level1_with(game, &Vocabulary::level1())
+}
 
+/// Validate a Level 1 game against a custom vocabulary
+///
+/// # Errors
+///
+/// Returns validation errors
+pub fn validate_level1_with(game: &Level1Game, vocab: &Vocabulary) -> Result<(), YamlError> {
     // Validate character
     if !vocab.is_valid_for_category(&game.character, "characters") {
         let _suggestions = vocab.suggest_similar(&game.character, 5);
@@ -488,6 +711,17 @@ pub fn validate_level1(game: &Level1Game) -> Result<(), YamlError> {
         }
     }
 
+    // Validate movement if present
+    if let Some(move_type) = &game.move_type {
+        if !vocab.is_valid_for_category(move_type, "movement") {
+            return Err(YamlError::InvalidEnumValue {
+                field: "move".to_string(),
+                value: move_type.clone(),
+                valid_options: vocab.words_in_category("movement"),
+            });
+        }
+    }
+
     // Validate music if present
     if let Some(music) = &game.music {
         if !vocab.is_valid_for_category(music, "music") {
@@ -535,14 +769,274 @@ pub fn validate_level1(game: &Level1Game) -> Result<(), YamlError> {
     Ok(())
 }
 
+/// Highest health a Level 2 character can have
+pub const MAX_HEALTH: u32 = 100;
+
+/// Most of one item the player can carry
+pub const MAX_ITEMS: u32 = 99;
+
+/// Longest line a `say:` bubble can hold
+pub const MAX_SAY_CHARS: usize = 120;
+
+/// Longest a `say:` bubble can stay up (seconds)
+pub const MAX_SAY_SECONDS: f32 = 30.0;
+
+/// Shortest `every:` interval for spawning (seconds)
+pub const MIN_SPAWN_SECONDS: f32 = 0.25;
+
+/// Longest `every:` interval for spawning (seconds)
+pub const MAX_SPAWN_SECONDS: f32 = 60.0;
+
+/// Most choices a quiz question can offer
+pub const MAX_CHOICES: usize = 4;
+
+/// Smallest procedural world side (tiles)
+pub const MIN_WORLD_SIZE: u32 = 5;
+
+/// Largest procedural world side (tiles)
+pub const MAX_WORLD_SIZE: u32 = 64;
+
+/// Strongest `gravity:` (tiles per second squared)
+pub const MAX_GRAVITY: f32 = 100.0;
+
+/// Highest `jump:` (tiles)
+pub const MAX_JUMP: f32 = 20.0;
+
+/// Highest fixed `difficulty:` level
+pub const MAX_DIFFICULTY: u32 = 10;
+
+/// Reads a time, rejecting values outside `min..=max` seconds
+fn check_time_span(field: &str, span: &TimeSpan, min: f32, max: f32) -> Result<f32, YamlError> {
+    span.seconds()
+        .filter(|seconds| (min..=max).contains(seconds))
+        .ok_or_else(|| YamlError::InvalidEnumValue {
+            field: field.to_string(),
+            value: span.text(),
+            valid_options: vec!["1s".to_string(), "2s".to_string(), "5s".to_string()],
+        })
+}
+
+fn check_range(field: &str, value: u32, max: u32) -> Result<(), YamlError> {
+    if (1..=max).contains(&value) {
+        Ok(())
+    } else {
+        Err(YamlError::OutOfRange {
+            field: field.to_string(),
+            min: 1,
+            max: i64::from(max),
+            value: i64::from(value),
+        })
+    }
+}
+
+/// Rounds a number for an `OutOfRange` report, saturating at the ends of
+/// `i64` and reporting NaN as 0
+#[allow(clippy::cast_possible_truncation)]
+fn whole_number(value: f32) -> i64 {
+    // 2^63, the first float past i64::MAX
+    const LIMIT: f32 = 9_223_372_036_854_775_808.0;
+    let value = value.round();
+    if value.is_nan() {
+        0
+    } else if value >= LIMIT {
+        i64::MAX
+    } else if value < -LIMIT {
+        i64::MIN
+    } else {
+        value as i64
+    }
+}
+
+fn validate_say(
+    game: &Level2Game,
+    text: &str,
+    by: Option<&str>,
+    duration: Option<&TimeSpan>,
+) -> Result<(), YamlError> {
+    check_text("say", text)?;
+    if let (Some(speaker), Some(characters)) = (by, &game.characters) {
+        if !characters.contains_key(speaker) {
+            let mut names: Vec<String> = characters.keys().cloned().collect();
+            names.sort();
+            return Err(YamlError::InvalidEnumValue {
+                field: "by".to_string(),
+                value: speaker.to_string(),
+                valid_options: names,
+            });
+        }
+    }
+    if let Some(duration) = duration {
+        let _ = check_time_span("for", duration, 0.0, MAX_SAY_SECONDS)?;
+    }
+    Ok(())
+}
+
+/// Checks quiz questions: short, clean text and an answer among 2-4 choices
+///
+/// # Errors
+///
+/// Returns the first problem found
+pub fn validate_questions(questions: &[Level2Question]) -> Result<(), YamlError> {
+    for (index, question) in questions.iter().enumerate() {
+        let field = format!("questions[{index}]");
+        check_text(&format!("{field}.ask"), &question.ask)?;
+        let choices = question.choices.len();
+        if !(2..=MAX_CHOICES).contains(&choices) {
+            return Err(YamlError::OutOfRange {
+                field: format!("{field}.choices"),
+                min: 2,
+                max: i64::try_from(MAX_CHOICES).unwrap_or(i64::MAX),
+                value: i64::try_from(choices).unwrap_or(i64::MAX),
+            });
+        }
+        for choice in &question.choices {
+            check_text(&format!("{field}.choices"), choice)?;
+        }
+        if !question.choices.contains(&question.answer) {
+            return Err(YamlError::InvalidEnumValue {
+                field: format!("{field}.answer"),
+                value: question.answer.clone(),
+                valid_options: question.choices.clone(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Checks that shown text fits a speech bubble and passes the content filter
+fn check_text(field: &str, text: &str) -> Result<(), YamlError> {
+    let length = text.chars().count();
+    if length == 0 || length > MAX_SAY_CHARS {
+        return Err(YamlError::OutOfRange {
+            field: format!("{field} (letters)"),
+            min: 1,
+            max: i64::try_from(MAX_SAY_CHARS).unwrap_or(i64::MAX),
+            value: i64::try_from(length).unwrap_or(i64::MAX),
+        });
+    }
+    if let Some(violation) = ContentFilter::new().check(text) {
+        return Err(SandboxError::ContentViolation(violation).into_yaml_error());
+    }
+    Ok(())
+}
+
+/// Checks a Level 3 `world:` section's size, gravity and jump
+///
+/// # Errors
+///
+/// Returns `YamlError::OutOfRange` for the first value out of bounds
+pub fn validate_world(world: &Level3World) -> Result<(), YamlError> {
+    for (axis, side) in ["width", "height"].iter().zip(world.size.iter().flatten()) {
+        if !(MIN_WORLD_SIZE..=MAX_WORLD_SIZE).contains(side) {
+            return Err(YamlError::OutOfRange {
+                field: format!("world.size ({axis})"),
+                min: i64::from(MIN_WORLD_SIZE),
+                max: i64::from(MAX_WORLD_SIZE),
+                value: i64::from(*side),
+            });
+        }
+    }
+    let limits = [
+        ("world.gravity", world.gravity, MAX_GRAVITY),
+        ("world.jump", world.jump, MAX_JUMP),
+    ];
+    for (field, value, max) in limits {
+        if let Some(value) = value.filter(|value| !(0.0..=max).contains(value)) {
+            return Err(YamlError::OutOfRange {
+                field: field.to_string(),
+                min: 0,
+                max: whole_number(max),
+                value: whole_number(value),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Checks a Level 3 `difficulty:` is a level from 1 to 10 or `adaptive`
+///
+/// # Errors
+///
+/// Returns `YamlError::OutOfRange` for a level out of bounds, or
+/// `YamlError::InvalidEnumValue` for any other word
+pub fn validate_difficulty(difficulty: &DifficultyValue) -> Result<(), YamlError> {
+    match difficulty {
+        DifficultyValue::Level(level) => check_range("difficulty", *level, MAX_DIFFICULTY),
+        DifficultyValue::Mode(mode) if mode == "adaptive" => Ok(()),
+        DifficultyValue::Mode(mode) => Err(YamlError::InvalidEnumValue {
+            field: "difficulty".to_string(),
+            value: mode.clone(),
+            valid_options: vec!["adaptive".to_string(), "1".to_string(), "10".to_string()],
+        }),
+    }
+}
+
+fn validate_spawn(
+    game: &Level2Game,
+    template: &str,
+    at: Option<&str>,
+    every: Option<&TimeSpan>,
+    max: Option<u32>,
+) -> Result<(), YamlError> {
+    let templates = game.templates.as_ref();
+    if !templates.is_some_and(|templates| templates.contains_key(template)) {
+        let mut names: Vec<String> = templates
+            .into_iter()
+            .flatten()
+            .map(|(name, _)| name.clone())
+            .collect();
+        names.sort();
+        return Err(YamlError::InvalidEnumValue {
+            field: "spawn".to_string(),
+            value: template.to_string(),
+            valid_options: names,
+        });
+    }
+    if let Some(at) = at {
+        let location = SpawnLocation::parse(at);
+        let known = match &location {
+            Some(SpawnLocation::Near(name)) => game
+                .characters
+                .as_ref()
+                .is_some_and(|characters| characters.contains_key(name)),
+            Some(_) => true,
+            None => false,
+        };
+        if !known {
+            return Err(YamlError::InvalidEnumValue {
+                field: "at".to_string(),
+                value: at.to_string(),
+                valid_options: ["random", "top", "bottom", "left", "right", "100, 200"]
+                    .iter()
+                    .map(|option| (*option).to_string())
+                    .collect(),
+            });
+        }
+    }
+    if let Some(every) = every {
+        let _ = check_time_span("every", every, MIN_SPAWN_SECONDS, MAX_SPAWN_SECONDS)?;
+    }
+    if let Some(max) = max {
+        check_range("max", max, u32::try_from(MAX_ENTITIES).unwrap_or(u32::MAX))?;
+    }
+    Ok(())
+}
+
 /// Validate a Level 2 game
 ///
 /// # Errors
 ///
 /// Returns validation errors
 pub fn validate_level2(game: &Level2Game) -> Result<(), YamlError> {
-    let vocab = Vocabulary::level2();
+    validate_level2_with(game, &Vocabulary::level2())
+}
 
+/// Validate a Level 2 game against a custom vocabulary
+///
+/// # Errors
+///
+/// Returns validation errors
+pub fn validate_level2_with(game: &Level2Game, vocab: &Vocabulary) -> Result<(), YamlError> {
     // Validate characters
     if let Some(characters) = &game.characters {
         for (name, char_def) in characters {
@@ -583,9 +1077,87 @@ pub fn validate_level2(game: &Level2Game) -> Result<(), YamlError> {
                     });
                 }
             }
+
+            if let Some(health) = char_def.health {
+                check_range(&format!("characters.{name}.health"), health, MAX_HEALTH)?;
+            }
+        }
+    }
+
+    // Validate templates
+    for (name, template) in game.templates.iter().flatten() {
+        let kind = &template.char_type;
+        let categories = ["characters", "characters_l2", "targets", "items"];
+        if !categories
+            .iter()
+            .any(|category| vocab.is_valid_for_category(kind, category))
+        {
+            return Err(YamlError::InvalidEnumValue {
+                field: format!("templates.{name}.type"),
+                value: kind.clone(),
+                valid_options: categories
+                    .iter()
+                    .flat_map(|category| vocab.words_in_category(category))
+                    .collect(),
+            });
         }
     }
 
+    // Validate items
+    for (name, item) in game.items.iter().flatten() {
+        if !vocab.is_valid_for_category(&item.item_type, "targets")
+            && !vocab.is_valid_for_category(&item.item_type, "items")
+        {
+            return Err(YamlError::InvalidEnumValue {
+                field: format!("items.{name}.type"),
+                value: item.item_type.clone(),
+                valid_options: [
+                    vocab.words_in_category("targets"),
+                    vocab.words_in_category("items"),
+                ]
+                .concat(),
+            });
+        }
+        let max = item.max.unwrap_or(MAX_ITEMS);
+        check_range(&format!("items.{name}.max"), max, MAX_ITEMS)?;
+        if let Some(start) = item.start {
+            if start > max {
+                return Err(YamlError::OutOfRange {
+                    field: format!("items.{name}.start"),
+                    min: 0,
+                    max: i64::from(max),
+                    value: i64::from(start),
+                });
+            }
+        }
+    }
+
+    // Damage, heal and item amounts stay within range
+    for rule in game.rules.iter().flatten() {
+        for action in &rule.then {
+            match action {
+                Level2Action::Damage { amount, .. } | Level2Action::Heal { amount, .. } => {
+                    check_range("amount", *amount, MAX_HEALTH)?;
+                }
+                Level2Action::GiveItem { amount, .. } | Level2Action::RemoveItem { amount, .. } => {
+                    check_range("amount", *amount, MAX_ITEMS)?;
+                }
+                Level2Action::Say {
+                    say, by, duration, ..
+                } => validate_say(game, say, by.as_deref(), duration.as_ref())?,
+                Level2Action::Spawn {
+                    spawn,
+                    at,
+                    every,
+                    max,
+                } => validate_spawn(game, spawn, at.as_deref(), every.as_ref(), *max)?,
+                _ => {}
+            }
+        }
+    }
+
+    validate_questions(game.questions.as_deref().unwrap_or_default())?;
+
     // Validate lives range (1-9 for Level 2)
     if let Some(lives) = game.lives {
         if !(1..=9).contains(&lives) {
@@ -601,6 +1173,319 @@ pub fn validate_level2(game: &Level2Game) -> Result<(), YamlError> {
     Ok(())
 }
 
+/// JSON Schema URI the exported schemas declare
+pub const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Trigger phrasings shown as autocomplete examples
+const CONDITION_HINTS: [&str; 4] = [
+    "score reaches 10",
+    "player health reaches 0",
+    "player touches star",
+    "time reaches 60",
+];
+
+/// Machine-readable JSON Schema for a level
+///
+/// Vocabulary words are inlined as `enum`s so editors can validate and
+/// autocomplete without loading the vocabulary separately. Fields the
+/// compiler checks only loosely (free text, runtime names) get `examples`
+/// instead.
+#[must_use]
+pub fn json_schema(level: SchemaLevel) -> serde_json::Value {
+    let (vocab, properties, required) = match level {
+        SchemaLevel::Level1 => {
+            let vocab = Vocabulary::level1();
+            let properties = level1_properties(&vocab);
+            (vocab, properties, vec!["character"])
+        }
+        SchemaLevel::Level2 => {
+            let vocab = Vocabulary::level2();
+            let properties = level2_properties(&vocab);
+            (vocab, properties, Vec::new())
+        }
+        SchemaLevel::Level3 => {
+            let vocab = Vocabulary::level3();
+            let properties = level3_properties(&vocab);
+            (vocab, properties, Vec::new())
+        }
+    };
+    let mut schema = json!({
+        "$schema": JSON_SCHEMA_DIALECT,
+        "title": format!("Jugar Level {} game (ages {})", level.number(), level.age_range()),
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": level != SchemaLevel::Level1,
+    });
+    if level != SchemaLevel::Level1 {
+        schema["$defs"] = level2_defs(&vocab);
+    }
+    schema
+}
+
+fn word_enum(vocab: &Vocabulary, categories: &[&str]) -> serde_json::Value {
+    let words: Vec<String> = categories
+        .iter()
+        .flat_map(|category| vocab.words_in_category(category))
+        .collect();
+    json!({ "type": "string", "enum": words })
+}
+
+fn word_examples(vocab: &Vocabulary, categories: &[&str]) -> serde_json::Value {
+    let words: Vec<String> = categories
+        .iter()
+        .flat_map(|category| vocab.words_in_category(category))
+        .collect();
+    json!({ "type": "string", "examples": words })
+}
+
+fn count(min: u32, max: u32) -> serde_json::Value {
+    json!({ "type": "integer", "minimum": min, "maximum": max })
+}
+
+fn map_of(value: &serde_json::Value) -> serde_json::Value {
+    json!({ "type": "object", "additionalProperties": value })
+}
+
+fn level1_properties(vocab: &Vocabulary) -> serde_json::Value {
+    json!({
+        "game": { "type": "string", "pattern": "^[A-Za-z0-9-]{3,20}$" },
+        "character": word_enum(vocab, &["characters"]),
+        "move": word_enum(vocab, &["movement"]),
+        "background": word_enum(vocab, &["backgrounds"]),
+        "music": word_enum(vocab, &["music"]),
+        "when_touch": {
+            "type": "object",
+            "properties": {
+                "target": word_enum(vocab, &["targets"]),
+                "sound": word_enum(vocab, &["sounds"]),
+                "caption": { "type": "string" },
+                "score": { "type": "integer", "minimum": -9, "maximum": 9 },
+                "target_action": word_enum(vocab, &["target_actions"]),
+            },
+            "required": ["target"],
+        },
+        "color": word_enum(vocab, &["colors"]),
+        "colour": word_enum(vocab, &["colors"]),
+    })
+}
+
+fn level2_properties(vocab: &Vocabulary) -> serde_json::Value {
+    let mut properties = level1_properties(vocab);
+    if let Some(map) = properties.as_object_mut() {
+        let _ = map.remove("color");
+        let _ = map.remove("colour");
+    }
+    properties["character"] = word_enum(vocab, &["characters", "characters_l2"]);
+    properties["characters"] = map_of(&json!({ "$ref": "#/$defs/character" }));
+    properties["templates"] = map_of(&json!({ "$ref": "#/$defs/template" }));
+    properties["items"] = map_of(&json!({ "$ref": "#/$defs/item" }));
+    properties["rules"] = json!({ "type": "array", "items": { "$ref": "#/$defs/rule" } });
+    properties["lives"] = count(1, 9);
+    properties["score_goal"] = json!({ "type": "integer", "minimum": 0 });
+    properties["win_when"] = json!({ "type": "string", "examples": CONDITION_HINTS });
+    properties["lose_when"] = json!({ "type": "string", "examples": CONDITION_HINTS });
+    properties["questions"] = json!({ "type": "array", "items": { "$ref": "#/$defs/question" } });
+    properties
+}
+
+fn level3_properties(vocab: &Vocabulary) -> serde_json::Value {
+    let paths = map_of(&json!({ "type": "string" }));
+    let mut properties = level2_properties(vocab);
+    if let Some(map) = properties.as_object_mut() {
+        for level2_only in ["character", "move", "when_touch", "score_goal"] {
+            let _ = map.remove(level2_only);
+        }
+    }
+    properties["version"] = json!({ "type": "integer", "minimum": 1 });
+    properties["assets"] = json!({
+        "type": "object",
+        "properties": { "sprites": paths, "sounds": paths, "models": paths },
+    });
+    properties["world"] = json!({
+        "type": "object",
+        "properties": {
+            "type": word_enum(vocab, &["world"]),
+            "algorithm": word_enum(vocab, &["world"]),
+            "seed": { "oneOf": [{ "const": "auto" }, { "type": "integer", "minimum": 0 }] },
+            "size": point("integer"),
+            "tiles": map_of(&json!({ "type": "number", "minimum": 0 })),
+            "gravity": { "type": "number", "minimum": 0, "maximum": MAX_GRAVITY },
+            "jump": { "type": "number", "minimum": 0, "maximum": MAX_JUMP },
+        },
+    });
+    properties["difficulty"] = json!({
+        "oneOf": [
+            { "const": "adaptive" },
+            { "type": "integer", "minimum": 1, "maximum": MAX_DIFFICULTY },
+        ],
+    });
+    properties["entities"] = map_of(&json!({
+        "type": "object",
+        "properties": {
+            "sprite": { "type": "string" },
+            "ai": { "type": "string" },
+            "components": {
+                "type": "object",
+                "properties": {
+                    "position": point("number"),
+                    "health": { "type": "integer" },
+                    "damage": { "type": "integer" },
+                    "speed": { "type": "number" },
+                    "inventory": { "type": "array", "items": { "type": "string" } },
+                },
+            },
+            "controls": {
+                "type": "object",
+                "properties": {
+                    "move": word_examples(vocab, &["movement"]),
+                    "attack": { "type": "string" },
+                },
+            },
+        },
+    }));
+    properties["physics"] = json!({
+        "type": "object",
+        "properties": {
+            "type": word_examples(vocab, &["physics"]),
+            "collision": word_enum(vocab, &["physics"]),
+        },
+    });
+    properties["camera"] = json!({
+        "type": "object",
+        "properties": {
+            "follow": { "type": "string" },
+            "zoom": { "type": "number", "exclusiveMinimum": 0 },
+        },
+    });
+    properties["ui"] = map_of(&json!({
+        "type": "object",
+        "properties": {
+            "anchor": word_enum(vocab, &["ui_anchors"]),
+            "bind": { "type": "string" },
+        },
+    }));
+    properties
+}
+
+fn point(kind: &str) -> serde_json::Value {
+    json!({ "type": "array", "items": { "type": kind }, "minItems": 2, "maxItems": 2 })
+}
+
+fn level2_defs(vocab: &Vocabulary) -> serde_json::Value {
+    let max_entities = u32::try_from(MAX_ENTITIES).unwrap_or(u32::MAX);
+    let time_span = json!({
+        "oneOf": [
+            { "type": "number", "exclusiveMinimum": 0 },
+            { "type": "string", "pattern": "^\\s*[0-9.]+\\s*(s|seconds?)?\\s*$" },
+        ],
+    });
+    let character = |types: &[&str]| {
+        json!({
+            "type": "object",
+            "properties": {
+                "type": word_enum(vocab, types),
+                "move": word_examples(vocab, &["movement"]),
+                "speed": word_enum(vocab, &["speed"]),
+                "pattern": word_enum(vocab, &["patterns"]),
+                "health": count(1, MAX_HEALTH),
+            },
+            "required": ["type"],
+        })
+    };
+    let action = |key: &str, value: serde_json::Value, extra: serde_json::Value| {
+        let mut properties = extra;
+        properties[key] = value;
+        json!({ "type": "object", "properties": properties, "required": [key] })
+    };
+    let amount = |max: u32| json!({ "amount": count(1, max) });
+    let actions = vec![
+        action("add_score", json!({ "type": "integer" }), json!({})),
+        action("lose_life", json!({ "type": "integer" }), json!({})),
+        action("play", word_examples(vocab, &["sounds"]), json!({})),
+        action("show", word_examples(vocab, &["game_states"]), json!({})),
+        action(
+            "set_music",
+            json!({ "type": "string" }),
+            json!({ "to": { "type": "number", "minimum": 0, "maximum": 1 } }),
+        ),
+        action("damage", json!({ "type": "string" }), amount(MAX_HEALTH)),
+        action("heal", json!({ "type": "string" }), amount(MAX_HEALTH)),
+        action("give_item", json!({ "type": "string" }), amount(MAX_ITEMS)),
+        action(
+            "remove_item",
+            json!({ "type": "string" }),
+            amount(MAX_ITEMS),
+        ),
+        action(
+            "say",
+            json!({ "type": "string", "minLength": 1, "maxLength": MAX_SAY_CHARS }),
+            json!({
+                "by": { "type": "string" },
+                "for": time_span,
+                "voice": { "type": "boolean" },
+            }),
+        ),
+        action(
+            "spawn",
+            json!({ "type": "string" }),
+            json!({
+                "at": {
+                    "type": "string",
+                    "examples": ["random", "top", "bottom", "left", "right", "100, 200"],
+                },
+                "every": time_span,
+                "max": count(1, max_entities),
+            }),
+        ),
+        json!({
+            "type": "object",
+            "properties": {
+                "entity": { "type": "string" },
+                "action": word_examples(vocab, &["effects"]),
+            },
+            "required": ["entity", "action"],
+        }),
+        json!({ "type": "string" }),
+    ];
+    json!({
+        "character": character(&["characters", "characters_l2"]),
+        "template": character(&["characters", "characters_l2", "targets", "items"]),
+        "item": {
+            "type": "object",
+            "properties": {
+                "type": word_enum(vocab, &["targets", "items"]),
+                "max": count(1, MAX_ITEMS),
+                "start": count(0, MAX_ITEMS),
+            },
+            "required": ["type"],
+        },
+        "rule": {
+            "type": "object",
+            "properties": {
+                "when": { "type": "string", "examples": CONDITION_HINTS },
+                "then": { "type": "array", "items": { "$ref": "#/$defs/action" } },
+            },
+            "required": ["when", "then"],
+        },
+        "action": { "anyOf": actions },
+        "question": {
+            "type": "object",
+            "properties": {
+                "ask": { "type": "string", "minLength": 1, "maxLength": MAX_SAY_CHARS },
+                "choices": {
+                    "type": "array",
+                    "items": { "type": "string", "minLength": 1, "maxLength": MAX_SAY_CHARS },
+                    "minItems": 2,
+                    "maxItems": MAX_CHOICES,
+                },
+                "answer": { "type": "string" },
+            },
+            "required": ["ask", "choices", "answer"],
+        },
+    })
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod tests {
@@ -831,6 +1716,21 @@ mod tests {
         assert!(validate_level2(&game).is_ok());
     }
 
+    #[test]
+    fn test_validate_world_reports_odd_numbers() {
+        for (gravity, reported) in [(250.6, 251), (f32::INFINITY, i64::MAX), (f32::NAN, 0)] {
+            let world = Level3World {
+                gravity: Some(gravity),
+                ..Default::default()
+            };
+            let err = validate_world(&world).unwrap_err();
+            assert!(matches!(
+                err,
+                YamlError::OutOfRange { max: 100, value, .. } if value == reported
+            ));
+        }
+    }
+
     #[test]
     fn test_validate_level2_invalid_character_type() {
         let mut characters = std::collections::HashMap::new();
@@ -1046,4 +1946,94 @@ entities:
         assert!(player.components.is_some());
         assert!(player.controls.is_some());
     }
+
+    fn schema_fields(level: SchemaLevel) -> Vec<String> {
+        let mut fields: Vec<String> = json_schema(level)["properties"]
+            .as_object()
+            .unwrap()
+            .keys()
+            .cloned()
+            .collect();
+        fields.sort();
+        fields
+    }
+
+    fn struct_fields<T: Default + Serialize>() -> Vec<String> {
+        let mut fields: Vec<String> = serde_json::to_value(T::default())
+            .unwrap()
+            .as_object()
+            .unwrap()
+            .keys()
+            .cloned()
+            .collect();
+        fields.sort();
+        fields
+    }
+
+    #[test]
+    fn test_json_schema_covers_every_field() {
+        assert_eq!(
+            schema_fields(SchemaLevel::Level1),
+            struct_fields::<Level1Game>()
+        );
+        assert_eq!(
+            schema_fields(SchemaLevel::Level2),
+            struct_fields::<Level2Game>()
+        );
+        assert_eq!(
+            schema_fields(SchemaLevel::Level3),
+            struct_fields::<Level3Game>()
+        );
+    }
+
+    #[test]
+    fn test_json_schema_level1_inlines_vocabulary() {
+        let schema = json_schema(SchemaLevel::Level1);
+        assert_eq!(schema["$schema"], JSON_SCHEMA_DIALECT);
+        assert_eq!(schema["required"], json!(["character"]));
+        assert_eq!(schema["additionalProperties"], json!(false));
+        let characters = schema["properties"]["character"]["enum"]
+            .as_array()
+            .unwrap();
+        assert!(characters.contains(&json!("bunny")));
+        assert!(!characters.contains(&json!("rocket")));
+        assert_eq!(
+            schema["properties"]["when_touch"]["properties"]["score"]["maximum"],
+            json!(9)
+        );
+        assert!(schema.get("$defs").is_none());
+    }
+
+    #[test]
+    fn test_json_schema_level2_actions() {
+        let schema = json_schema(SchemaLevel::Level2);
+        let actions = schema["$defs"]["action"]["anyOf"].as_array().unwrap();
+        let say = actions
+            .iter()
+            .find(|action| action["required"] == json!(["say"]))
+            .unwrap();
+        assert_eq!(say["properties"]["say"]["maxLength"], json!(MAX_SAY_CHARS));
+        assert_eq!(
+            schema["$defs"]["character"]["properties"]["health"]["maximum"],
+            json!(MAX_HEALTH)
+        );
+        let characters = schema["properties"]["character"]["enum"]
+            .as_array()
+            .unwrap();
+        assert!(characters.contains(&json!("rocket")));
+    }
+
+    #[test]
+    fn test_json_schema_level3_drops_level1_shortcuts() {
+        let level2 = schema_fields(SchemaLevel::Level2);
+        let level3 = schema_fields(SchemaLevel::Level3);
+        assert!(level2.contains(&"when_touch".to_string()));
+        assert!(!level3.contains(&"when_touch".to_string()));
+        let schema = json_schema(SchemaLevel::Level3);
+        let anchors = schema["properties"]["ui"]["additionalProperties"]["properties"]["anchor"]
+            ["enum"]
+            .as_array()
+            .unwrap();
+        assert!(anchors.contains(&json!("top_left")));
+    }
 }
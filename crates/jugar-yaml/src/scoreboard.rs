@@ -0,0 +1,455 @@
+//! Scores and high-score tables.
+//!
+//! A [`Scoreboard`] holds a game's named scores ("coins", "stars") for the
+//! current run and any number of [`HighScoreTable`]s. Tables are saved
+//! through [`jugar_core::Storage`]; the browser host backs it with
+//! `sessionStorage` or `localStorage` depending on the board's
+//! [`StorageScope`], so a classroom computer can forget scores when the tab
+//! closes. A table's best entry can travel inside a
+//! [`GameBundle`](crate::GameBundle) as a [`ScoreChallenge`] for whoever
+//! plays the shared game next.
+
+use alloc::collections::BTreeMap;
+use core::fmt;
+
+use jugar_core::Storage;
+use serde::{Deserialize, Serialize};
+
+/// Prefix of every scoreboard storage key
+pub const SCOREBOARD_KEY_PREFIX: &str = "jugar.scores.";
+
+/// Entries kept per table unless configured otherwise
+pub const DEFAULT_MAX_ENTRIES: usize = 10;
+
+/// Longest player nickname kept in a table, in characters
+pub const MAX_NICKNAME_CHARS: usize = 12;
+
+/// Where a scoreboard is saved
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StorageScope {
+    /// Forgotten when the browser tab closes
+    Session,
+    /// Kept between visits
+    #[default]
+    Persistent,
+}
+
+impl StorageScope {
+    /// Web Storage object backing this scope
+    #[must_use]
+    pub const fn web_storage(self) -> &'static str {
+        match self {
+            Self::Session => "sessionStorage",
+            Self::Persistent => "localStorage",
+        }
+    }
+}
+
+/// Which scores rank first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScoreOrder {
+    /// Points: bigger wins
+    #[default]
+    HigherIsBetter,
+    /// Times and move counts: smaller wins
+    LowerIsBetter,
+}
+
+impl ScoreOrder {
+    /// Returns true if `a` ranks strictly above `b`
+    #[must_use]
+    pub const fn beats(self, a: i64, b: i64) -> bool {
+        match self {
+            Self::HigherIsBetter => a > b,
+            Self::LowerIsBetter => a < b,
+        }
+    }
+}
+
+/// One row of a high-score table
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScoreEntry {
+    /// Player nickname
+    pub nickname: String,
+    /// Score
+    pub score: i64,
+}
+
+/// Best scores, best first, capped at a number of entries
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HighScoreTable {
+    max_entries: usize,
+    #[serde(default)]
+    order: ScoreOrder,
+    entries: Vec<ScoreEntry>,
+}
+
+impl Default for HighScoreTable {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_ENTRIES)
+    }
+}
+
+impl HighScoreTable {
+    /// Creates an empty table keeping at least one entry
+    #[must_use]
+    pub fn new(max_entries: usize) -> Self {
+        Self {
+            max_entries: max_entries.max(1),
+            order: ScoreOrder::HigherIsBetter,
+            entries: Vec::new(),
+        }
+    }
+
+    /// Sets which scores rank first
+    #[must_use]
+    pub const fn with_order(mut self, order: ScoreOrder) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Which scores rank first
+    #[must_use]
+    pub const fn order(&self) -> ScoreOrder {
+        self.order
+    }
+
+    /// Most entries kept
+    #[must_use]
+    pub const fn max_entries(&self) -> usize {
+        self.max_entries
+    }
+
+    /// Returns true if the score would get onto the table
+    #[must_use]
+    pub fn qualifies(&self, score: i64) -> bool {
+        self.entries.len() < self.max_entries
+            || self
+                .entries
+                .last()
+                .is_some_and(|last| self.order.beats(score, last.score))
+    }
+
+    /// Adds a score; returns its rank (0 is best) if it made the table
+    ///
+    /// Ties rank below existing entries, so the first to reach a score keeps
+    /// the higher spot.
+    pub fn submit(&mut self, nickname: &str, score: i64) -> Option<usize> {
+        if !self.qualifies(score) {
+            return None;
+        }
+        let rank = self
+            .entries
+            .iter()
+            .position(|entry| self.order.beats(score, entry.score))
+            .unwrap_or(self.entries.len());
+        self.entries.insert(
+            rank,
+            ScoreEntry {
+                nickname: nickname.trim().chars().take(MAX_NICKNAME_CHARS).collect(),
+                score,
+            },
+        );
+        self.entries.truncate(self.max_entries);
+        Some(rank)
+    }
+
+    /// Top entry
+    #[must_use]
+    pub fn best(&self) -> Option<&ScoreEntry> {
+        self.entries.first()
+    }
+
+    /// Entries, best first
+    #[must_use]
+    pub fn entries(&self) -> &[ScoreEntry] {
+        &self.entries
+    }
+
+    /// Number of entries
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if nobody has scored yet
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Removes every entry
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+/// Error loading or saving a scoreboard
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScoreboardError {
+    /// Stored tables couldn't be read
+    Corrupt(String),
+    /// Tables couldn't be written
+    Storage(String),
+}
+
+impl fmt::Display for ScoreboardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Corrupt(message) => write!(f, "High scores are corrupted: {message}"),
+            Self::Storage(message) => write!(f, "Couldn't save high scores: {message}"),
+        }
+    }
+}
+
+impl core::error::Error for ScoreboardError {}
+
+/// A game's current scores and high-score tables
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Scoreboard {
+    game: String,
+    #[serde(skip)]
+    scope: StorageScope,
+    #[serde(skip)]
+    scores: BTreeMap<String, i64>,
+    tables: BTreeMap<String, HighScoreTable>,
+}
+
+impl Scoreboard {
+    /// Creates an empty, persistent scoreboard for a game
+    #[must_use]
+    pub fn new(game: impl Into<String>) -> Self {
+        Self {
+            game: game.into(),
+            scope: StorageScope::Persistent,
+            scores: BTreeMap::new(),
+            tables: BTreeMap::new(),
+        }
+    }
+
+    /// Sets where the tables are saved
+    #[must_use]
+    pub const fn with_scope(mut self, scope: StorageScope) -> Self {
+        self.scope = scope;
+        self
+    }
+
+    /// Game the scores belong to
+    #[must_use]
+    pub fn game(&self) -> &str {
+        &self.game
+    }
+
+    /// Where the tables are saved
+    #[must_use]
+    pub const fn scope(&self) -> StorageScope {
+        self.scope
+    }
+
+    /// Storage key of this game's tables
+    #[must_use]
+    pub fn storage_key(&self) -> String {
+        format!("{SCOREBOARD_KEY_PREFIX}{}", self.game)
+    }
+
+    /// A named score for this run (zero if never set)
+    #[must_use]
+    pub fn score(&self, name: &str) -> i64 {
+        self.scores.get(name).copied().unwrap_or(0)
+    }
+
+    /// Sets a named score
+    pub fn set_score(&mut self, name: &str, value: i64) {
+        let _ = self.scores.insert(name.to_string(), value);
+    }
+
+    /// Adds to a named score and returns the new value
+    pub fn add_score(&mut self, name: &str, delta: i64) -> i64 {
+        let score = self.scores.entry(name.to_string()).or_insert(0);
+        *score = score.saturating_add(delta);
+        *score
+    }
+
+    /// Named scores for this run, sorted by name
+    pub fn scores(&self) -> impl Iterator<Item = (&str, i64)> {
+        self.scores
+            .iter()
+            .map(|(name, &value)| (name.as_str(), value))
+    }
+
+    /// Clears this run's scores, keeping the tables
+    pub fn reset_scores(&mut self) {
+        self.scores.clear();
+    }
+
+    /// Adds or replaces a high-score table
+    pub fn add_table(&mut self, name: &str, table: HighScoreTable) {
+        let _ = self.tables.insert(name.to_string(), table);
+    }
+
+    /// A high-score table
+    #[must_use]
+    pub fn table(&self, name: &str) -> Option<&HighScoreTable> {
+        self.tables.get(name)
+    }
+
+    /// Submits to a table, creating a default one if needed; returns the rank
+    pub fn submit(&mut self, table: &str, nickname: &str, score: i64) -> Option<usize> {
+        self.tables
+            .entry(table.to_string())
+            .or_default()
+            .submit(nickname, score)
+    }
+
+    /// Loads the game's tables from storage, or starts empty
+    ///
+    /// Run scores always start at zero.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ScoreboardError::Corrupt`] if the stored tables aren't
+    /// valid JSON.
+    pub fn load(
+        storage: &dyn Storage,
+        game: impl Into<String>,
+        scope: StorageScope,
+    ) -> Result<Self, ScoreboardError> {
+        let empty = Self::new(game).with_scope(scope);
+        let Some(json) = storage.get(&empty.storage_key()) else {
+            return Ok(empty);
+        };
+        let stored: Self =
+            serde_json::from_str(&json).map_err(|e| ScoreboardError::Corrupt(e.to_string()))?;
+        Ok(Self {
+            tables: stored.tables,
+            ..empty
+        })
+    }
+
+    /// Writes the tables to storage
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ScoreboardError::Storage`] if the backend rejects it.
+    pub fn save(&self, storage: &mut dyn Storage) -> Result<(), ScoreboardError> {
+        let json =
+            serde_json::to_string(self).map_err(|e| ScoreboardError::Storage(e.to_string()))?;
+        storage
+            .set(&self.storage_key(), &json)
+            .map_err(|e| ScoreboardError::Storage(e.to_string()))
+    }
+
+    /// The best entry of a table as a challenge for other players
+    #[must_use]
+    pub fn challenge(&self, table: &str) -> Option<ScoreChallenge> {
+        let high_scores = self.tables.get(table)?;
+        high_scores.best().map(|best| ScoreChallenge {
+            table: table.to_string(),
+            nickname: best.nickname.clone(),
+            score: best.score,
+            order: high_scores.order(),
+        })
+    }
+}
+
+/// A creator's best score carried inside a shared game
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScoreChallenge {
+    /// Table the score came from
+    pub table: String,
+    /// Creator's nickname
+    pub nickname: String,
+    /// Score to beat
+    pub score: i64,
+    /// Which scores rank first
+    #[serde(default)]
+    pub order: ScoreOrder,
+}
+
+impl ScoreChallenge {
+    /// Returns true if a score beats the challenge
+    #[must_use]
+    pub const fn is_beaten_by(&self, score: i64) -> bool {
+        self.order.beats(score, self.score)
+    }
+
+    /// Kid-friendly line for the title screen
+    #[must_use]
+    pub fn message(&self) -> String {
+        format!("Can you beat {}'s score of {}?", self.nickname, self.score)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use jugar_core::MemoryStorage;
+
+    #[test]
+    fn test_table_keeps_best_entries() {
+        let mut table = HighScoreTable::new(3);
+        assert_eq!(table.submit("Ace", 50), Some(0));
+        assert_eq!(table.submit("Bo", 80), Some(0));
+        assert_eq!(table.submit("Cy", 50), Some(2));
+        assert!(!table.qualifies(10));
+        assert_eq!(table.submit("Di", 10), None);
+        assert_eq!(table.submit("  SuperLongNickname  ", 60), Some(1));
+
+        let names: Vec<_> = table
+            .entries()
+            .iter()
+            .map(|e| e.nickname.as_str())
+            .collect();
+        assert_eq!(names, ["Bo", "SuperLongNic", "Ace"]);
+        assert_eq!(table.len(), 3);
+    }
+
+    #[test]
+    fn test_lower_is_better() {
+        let mut table = HighScoreTable::new(2).with_order(ScoreOrder::LowerIsBetter);
+        let _ = table.submit("Ace", 40);
+        assert_eq!(table.submit("Bo", 35), Some(0));
+        assert_eq!(table.best().unwrap().nickname, "Bo");
+        assert!(!table.qualifies(45));
+    }
+
+    #[test]
+    fn test_save_and_load_keeps_tables_only() {
+        let mut storage = MemoryStorage::new();
+        let mut board = Scoreboard::new("space").with_scope(StorageScope::Session);
+        assert_eq!(board.add_score("coins", 5), 5);
+        let _ = board.submit("level 1", "Ace", 120);
+        board.save(&mut storage).unwrap();
+        assert!(storage.get("jugar.scores.space").is_some());
+
+        let loaded = Scoreboard::load(&storage, "space", StorageScope::Session).unwrap();
+        assert_eq!(loaded.score("coins"), 0);
+        assert_eq!(loaded.table("level 1").unwrap().best().unwrap().score, 120);
+        assert_eq!(loaded.scope().web_storage(), "sessionStorage");
+
+        storage.set("jugar.scores.space", "not json").unwrap();
+        assert!(matches!(
+            Scoreboard::load(&storage, "space", StorageScope::Persistent),
+            Err(ScoreboardError::Corrupt(_))
+        ));
+    }
+
+    #[test]
+    fn test_challenge_from_best_entry() {
+        let mut board = Scoreboard::new("race");
+        board.add_table(
+            "laps",
+            HighScoreTable::default().with_order(ScoreOrder::LowerIsBetter),
+        );
+        assert!(board.challenge("laps").is_none());
+        let _ = board.submit("laps", "Zoom", 42);
+
+        let challenge = board.challenge("laps").unwrap();
+        assert!(challenge.is_beaten_by(41));
+        assert!(!challenge.is_beaten_by(42));
+        assert_eq!(challenge.message(), "Can you beat Zoom's score of 42?");
+    }
+}
@@ -6,24 +6,50 @@
 //! - YAML game definition
 //! - Referenced assets (sprites, sounds)
 //! - Metadata (creator nickname, version)
+//! - Optional score challenge (the creator's best score)
 //! - Integrity checksum
+//!
+//! Bundles carry a format version. [`GameBundle::from_json`] upgrades older
+//! bundles through [`GameBundle::migrations`], so games shared from an
+//! earlier release still open.
+//!
+//! Without a network, a bundle travels between devices as a series of
+//! QR-code-sized [`SyncFrame`]s.
 
 use crate::error::YamlError;
 use crate::privacy::PrivacyValidator;
+use crate::scoreboard::ScoreChallenge;
+use alloc::collections::BTreeMap;
 use base64::Engine;
 use core::hash::{Hash, Hasher};
+use jugar_core::{MigrationReport, Migrations, SchemaVersion, VersionError};
 use serde::{Deserialize, Serialize};
 
 /// Maximum bundle size (1 MB for sharing)
 pub const MAX_BUNDLE_SIZE: usize = 1024 * 1024;
 
+/// Asset name of the bundle thumbnail
+pub const THUMBNAIL_NAME: &str = "thumbnail.png";
+
+/// Bundle format version written by this release
+///
+/// - 1: original format, checksummed with the std hasher
+/// - 2: CRC32 checksum, stable across Rust releases
+pub const BUNDLE_VERSION: u8 = 2;
+
 /// Bundle file magic number
 pub const BUNDLE_MAGIC: &[u8; 4] = b"JGB1";
 
+/// Prefix of every offline sync frame
+pub const SYNC_FRAME_PREFIX: &str = "JGS1";
+
+/// Payload characters per sync frame (fits a QR code tablets scan reliably)
+pub const SYNC_FRAME_CAPACITY: usize = 1000;
+
 /// A shareable game bundle
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GameBundle {
-    /// Bundle version
+    /// Bundle format version ([`BUNDLE_VERSION`])
     pub version: u8,
     /// Game definition (YAML)
     pub game_yaml: String,
@@ -31,6 +57,9 @@ pub struct GameBundle {
     pub metadata: BundleMetadata,
     /// Embedded assets (base64 encoded)
     pub assets: Vec<EmbeddedAsset>,
+    /// Creator's best score for players to beat
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub challenge: Option<ScoreChallenge>,
     /// CRC32 checksum of contents
     pub checksum: u32,
 }
@@ -198,6 +227,19 @@ pub struct EmbeddedAsset {
     pub original_size: usize,
 }
 
+impl EmbeddedAsset {
+    /// Create an asset from raw file bytes
+    #[must_use]
+    pub fn from_bytes(name: impl Into<String>, asset_type: AssetType, bytes: &[u8]) -> Self {
+        Self {
+            name: name.into(),
+            asset_type,
+            data_base64: base64::engine::general_purpose::STANDARD.encode(bytes),
+            original_size: bytes.len(),
+        }
+    }
+}
+
 /// Types of embeddable assets
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -210,6 +252,8 @@ pub enum AssetType {
     Music,
     /// AI model (.apr)
     AiModel,
+    /// Preview image for galleries and share links (PNG)
+    Thumbnail,
 }
 
 impl GameBundle {
@@ -252,10 +296,11 @@ impl GameBundle {
         }
 
         let mut bundle = Self {
-            version: 1,
+            version: BUNDLE_VERSION,
             game_yaml,
             metadata,
             assets: Vec::new(),
+            challenge: None,
             checksum: 0,
         };
 
@@ -287,6 +332,32 @@ impl GameBundle {
     /// Calculate CRC32 checksum of bundle contents
     #[must_use]
     pub fn calculate_checksum(&self) -> u32 {
+        let mut hasher = crc32fast::Hasher::new();
+        let mut field = |bytes: &[u8]| {
+            hasher.update(&(bytes.len() as u64).to_le_bytes());
+            hasher.update(bytes);
+        };
+
+        field(&[self.version]);
+        field(self.game_yaml.as_bytes());
+        field(self.metadata.title.as_bytes());
+
+        for asset in &self.assets {
+            field(asset.name.as_bytes());
+            field(asset.data_base64.as_bytes());
+        }
+
+        if let Some(challenge) = &self.challenge {
+            field(challenge.table.as_bytes());
+            field(challenge.nickname.as_bytes());
+            field(&challenge.score.to_le_bytes());
+        }
+
+        hasher.finalize()
+    }
+
+    /// Checksum of version 1 bundles, kept to verify them before upgrading
+    fn legacy_checksum(&self) -> u32 {
         let mut hasher = std::collections::hash_map::DefaultHasher::new();
 
         self.version.hash(&mut hasher);
@@ -298,6 +369,12 @@ impl GameBundle {
             asset.data_base64.hash(&mut hasher);
         }
 
+        if let Some(challenge) = &self.challenge {
+            challenge.table.hash(&mut hasher);
+            challenge.nickname.hash(&mut hasher);
+            challenge.score.hash(&mut hasher);
+        }
+
         #[allow(clippy::cast_possible_truncation)]
         let hash = hasher.finish() as u32;
         hash
@@ -324,6 +401,20 @@ impl GameBundle {
         size + 100 // overhead for JSON structure
     }
 
+    /// Upgrades from every older bundle version, as JSON documents
+    #[must_use]
+    pub fn migrations() -> Migrations<serde_json::Value> {
+        Migrations::new("game bundle", 1).step("switch to a CRC32 checksum", |document| {
+            let mut bundle: Self = serde_json::from_value(document).map_err(|e| e.to_string())?;
+            if bundle.checksum != bundle.legacy_checksum() {
+                return Err("checksum doesn't match".to_string());
+            }
+            bundle.version = 2;
+            bundle.checksum = bundle.calculate_checksum();
+            serde_json::to_value(bundle).map_err(|e| e.to_string())
+        })
+    }
+
     /// Export bundle to JSON
     ///
     /// # Errors
@@ -335,14 +426,17 @@ impl GameBundle {
         })
     }
 
-    /// Import bundle from JSON
+    /// Import bundle from JSON, upgrading older bundle versions
     ///
     /// # Errors
     ///
-    /// Returns error if deserialization fails or bundle is invalid
+    /// Returns error if deserialization fails, the bundle is from a newer
+    /// release, or the bundle is invalid
     pub fn from_json(json: &str) -> Result<Self, BundleError> {
+        let (document, version) = parse_versioned(json)?;
+        let document = Self::migrations().migrate(document, version)?;
         let bundle: Self =
-            serde_json::from_str(json).map_err(|e| BundleError::DeserializationError {
+            serde_json::from_value(document).map_err(|e| BundleError::DeserializationError {
                 message: e.to_string(),
             })?;
 
@@ -354,6 +448,21 @@ impl GameBundle {
         Ok(bundle)
     }
 
+    /// Checks that a bundle loads, reporting the upgrades it needs
+    ///
+    /// Nothing is written; use this to validate old `.jugar` files before
+    /// rewriting them.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error [`from_json`](Self::from_json) would.
+    pub fn check_upgrade(json: &str) -> Result<MigrationReport, BundleError> {
+        let (document, version) = parse_versioned(json)?;
+        let report = Self::migrations().dry_run(&document, version)?;
+        let _ = Self::from_json(json)?;
+        Ok(report)
+    }
+
     /// Export bundle to base64 (for sharing via URL/QR code)
     ///
     /// # Errors
@@ -394,6 +503,135 @@ impl GameBundle {
     pub const fn metadata(&self) -> &BundleMetadata {
         &self.metadata
     }
+
+    /// Embeds a PNG thumbnail, replacing any previous one
+    ///
+    /// # Errors
+    ///
+    /// Returns error if bundle would exceed size limit
+    pub fn set_thumbnail(&mut self, png: &[u8]) -> Result<(), BundleError> {
+        let previous = self
+            .assets
+            .iter()
+            .position(|asset| asset.asset_type == AssetType::Thumbnail)
+            .map(|index| self.assets.remove(index));
+        let result = self.add_asset(EmbeddedAsset::from_bytes(
+            THUMBNAIL_NAME,
+            AssetType::Thumbnail,
+            png,
+        ));
+        if result.is_err() {
+            self.assets.extend(previous);
+            self.checksum = self.calculate_checksum();
+        }
+        result
+    }
+
+    /// Attaches the creator's best score as a challenge, or removes it
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the nickname looks like a real name or an email
+    pub fn set_challenge(&mut self, challenge: Option<ScoreChallenge>) -> Result<(), BundleError> {
+        if let Some(challenge) = &challenge {
+            let issue = if challenge.nickname.contains('@') {
+                Some(MetadataIssue::NicknameContainsEmail)
+            } else if looks_like_real_name(&challenge.nickname) {
+                Some(MetadataIssue::NicknameLooksLikeName)
+            } else {
+                None
+            };
+            if let Some(issue) = issue {
+                return Err(BundleError::InvalidMetadata {
+                    message: issue.message().to_string(),
+                });
+            }
+        }
+        self.challenge = challenge;
+        self.checksum = self.calculate_checksum();
+        Ok(())
+    }
+
+    /// The creator's score challenge, if any
+    #[must_use]
+    pub const fn challenge(&self) -> Option<&ScoreChallenge> {
+        self.challenge.as_ref()
+    }
+
+    /// Decoded PNG thumbnail, if one is embedded and valid
+    #[must_use]
+    pub fn thumbnail(&self) -> Option<Vec<u8>> {
+        self.assets
+            .iter()
+            .find(|asset| asset.asset_type == AssetType::Thumbnail)
+            .and_then(|asset| {
+                base64::engine::general_purpose::STANDARD
+                    .decode(&asset.data_base64)
+                    .ok()
+            })
+    }
+
+    /// Decoded contents of the embedded assets of one type
+    ///
+    /// Used to mount `.apr` models for `bundle:` references.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if an asset isn't valid base64
+    pub fn decoded_assets(
+        &self,
+        asset_type: AssetType,
+    ) -> Result<Vec<(String, Vec<u8>)>, BundleError> {
+        self.assets
+            .iter()
+            .filter(|asset| asset.asset_type == asset_type)
+            .map(|asset| {
+                base64::engine::general_purpose::STANDARD
+                    .decode(&asset.data_base64)
+                    .map(|bytes| (asset.name.clone(), bytes))
+                    .map_err(|e| BundleError::DeserializationError {
+                        message: format!("{}: {e}", asset.name),
+                    })
+            })
+            .collect()
+    }
+
+    /// Splits the bundle into QR-code-sized frames for offline sharing
+    ///
+    /// Two devices can swap a game with no network: one shows the frames as
+    /// QR codes, the other scans them into a [`SyncReceiver`]. Frames carry
+    /// the same data as a share link, nothing more.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if serialization fails
+    pub fn export_sync_package(&self) -> Result<SyncPackage, BundleError> {
+        let json = serde_json::to_string(self).map_err(|e| BundleError::SerializationError {
+            message: e.to_string(),
+        })?;
+        let encoded = base64::engine::general_purpose::URL_SAFE.encode(json);
+        let chunks: Vec<&str> = encoded
+            .as_bytes()
+            .chunks(SYNC_FRAME_CAPACITY)
+            .filter_map(|chunk| core::str::from_utf8(chunk).ok())
+            .collect();
+        let total = u16::try_from(chunks.len()).map_err(|_| BundleError::BundleTooLarge {
+            size: encoded.len(),
+            max: SYNC_FRAME_CAPACITY * usize::from(u16::MAX),
+        })?;
+
+        Ok(SyncPackage {
+            frames: (0..total)
+                .zip(chunks)
+                .map(|(index, payload)| SyncFrame {
+                    id: self.checksum,
+                    index,
+                    total,
+                    payload: payload.to_string(),
+                })
+                .collect(),
+        })
+    }
 }
 
 /// Bundle-related errors
@@ -433,6 +671,8 @@ pub enum BundleError {
     },
     /// Bundle integrity check failed
     IntegrityError,
+    /// Bundle version can't be read by this release
+    Version(VersionError),
 }
 
 impl core::fmt::Display for BundleError {
@@ -452,12 +692,35 @@ impl core::fmt::Display for BundleError {
             Self::SerializationError { message } => write!(f, "Export failed: {message}"),
             Self::DeserializationError { message } => write!(f, "Import failed: {message}"),
             Self::IntegrityError => write!(f, "Game file is corrupted"),
+            Self::Version(err) => write!(f, "{err}"),
         }
     }
 }
 
 impl core::error::Error for BundleError {}
 
+impl From<VersionError> for BundleError {
+    fn from(err: VersionError) -> Self {
+        Self::Version(err)
+    }
+}
+
+/// Parses bundle JSON and reads its version tag
+fn parse_versioned(json: &str) -> Result<(serde_json::Value, SchemaVersion), BundleError> {
+    let document: serde_json::Value =
+        serde_json::from_str(json).map_err(|e| BundleError::DeserializationError {
+            message: e.to_string(),
+        })?;
+    let version = document
+        .get("version")
+        .and_then(serde_json::Value::as_u64)
+        .and_then(|v| SchemaVersion::try_from(v).ok())
+        .ok_or_else(|| BundleError::DeserializationError {
+            message: "missing bundle version".to_string(),
+        })?;
+    Ok((document, version))
+}
+
 impl From<BundleError> for YamlError {
     fn from(err: BundleError) -> Self {
         Self::ValidationError {
@@ -533,6 +796,205 @@ impl ShareLinkGenerator {
     }
 }
 
+/// One frame of an offline sync package
+///
+/// Text form: `JGS1:<id>:<n>/<total>:<payload>` with a 1-based `n`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncFrame {
+    /// Checksum of the bundle this frame belongs to
+    pub id: u32,
+    /// Position in the package (0-based)
+    pub index: u16,
+    /// Number of frames in the package
+    pub total: u16,
+    /// Slice of the URL-safe base64 bundle
+    pub payload: String,
+}
+
+impl SyncFrame {
+    /// Text encoded into the QR code
+    #[must_use]
+    pub fn to_text(&self) -> String {
+        format!(
+            "{SYNC_FRAME_PREFIX}:{:08x}:{}/{}:{}",
+            self.id,
+            self.index + 1,
+            self.total,
+            self.payload
+        )
+    }
+
+    /// Parses scanned QR text
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the text isn't a sync frame
+    pub fn parse(text: &str) -> Result<Self, BundleError> {
+        let malformed = || BundleError::DeserializationError {
+            message: "That code isn't a Jugar game".to_string(),
+        };
+        let mut parts = text.trim().splitn(4, ':');
+        if parts.next() != Some(SYNC_FRAME_PREFIX) {
+            return Err(malformed());
+        }
+        let id = parts
+            .next()
+            .and_then(|id| u32::from_str_radix(id, 16).ok())
+            .ok_or_else(malformed)?;
+        let (number, total) = parts
+            .next()
+            .and_then(|position| position.split_once('/'))
+            .and_then(|(n, total)| Some((n.parse::<u16>().ok()?, total.parse::<u16>().ok()?)))
+            .ok_or_else(malformed)?;
+        let payload = parts.next().ok_or_else(malformed)?;
+        if number == 0 || number > total {
+            return Err(malformed());
+        }
+
+        Ok(Self {
+            id,
+            index: number - 1,
+            total,
+            payload: payload.to_string(),
+        })
+    }
+}
+
+/// A bundle split into frames for offline sharing
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncPackage {
+    frames: Vec<SyncFrame>,
+}
+
+impl SyncPackage {
+    /// Frames in order
+    #[must_use]
+    pub fn frames(&self) -> &[SyncFrame] {
+        &self.frames
+    }
+
+    /// Text of each frame, ready to render as QR codes
+    #[must_use]
+    pub fn to_texts(&self) -> Vec<String> {
+        self.frames.iter().map(SyncFrame::to_text).collect()
+    }
+
+    /// Number of frames
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Returns true if the package has no frames
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+}
+
+/// Reassembles scanned sync frames into a bundle
+///
+/// Frames may arrive in any order and repeats are ignored, so the sender
+/// can simply cycle through its QR codes.
+#[derive(Debug, Clone, Default)]
+pub struct SyncReceiver {
+    id: Option<u32>,
+    total: u16,
+    payloads: BTreeMap<u16, String>,
+}
+
+impl SyncReceiver {
+    /// Creates an empty receiver
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accepts one scanned frame; returns true if it was new
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the text isn't a sync frame or belongs to a
+    /// different game than the frames already scanned
+    pub fn accept(&mut self, text: &str) -> Result<bool, BundleError> {
+        let frame = SyncFrame::parse(text)?;
+        match self.id {
+            Some(id) if id != frame.id || self.total != frame.total => {
+                return Err(BundleError::DeserializationError {
+                    message: "That code is from a different game".to_string(),
+                });
+            }
+            Some(_) => {}
+            None => {
+                self.id = Some(frame.id);
+                self.total = frame.total;
+            }
+        }
+        Ok(self.payloads.insert(frame.index, frame.payload).is_none())
+    }
+
+    /// Frames received so far
+    #[must_use]
+    pub fn received(&self) -> usize {
+        self.payloads.len()
+    }
+
+    /// Frames expected (0 until the first scan)
+    #[must_use]
+    pub const fn total(&self) -> u16 {
+        self.total
+    }
+
+    /// 1-based numbers of frames still to scan
+    #[must_use]
+    pub fn missing(&self) -> Vec<u16> {
+        (0..self.total)
+            .filter(|index| !self.payloads.contains_key(index))
+            .map(|index| index + 1)
+            .collect()
+    }
+
+    /// Returns true once every frame has been scanned
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.id.is_some() && self.payloads.len() == usize::from(self.total)
+    }
+
+    /// Reassembles and verifies the bundle
+    ///
+    /// The bundle's checksum must match the frame id, and the game is
+    /// re-checked for personal information before it is accepted.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if frames are missing, the data is corrupt, or the game
+    /// fails the privacy check
+    pub fn finish(&self) -> Result<GameBundle, BundleError> {
+        if !self.is_complete() {
+            return Err(BundleError::DeserializationError {
+                message: format!(
+                    "Still need {} more code(s)",
+                    usize::from(self.total) - self.payloads.len()
+                ),
+            });
+        }
+        let encoded: String = self.payloads.values().map(String::as_str).collect();
+        let bundle = GameBundle::from_base64(&encoded)?;
+        if Some(bundle.checksum) != self.id {
+            return Err(BundleError::IntegrityError);
+        }
+        if !PrivacyValidator::new()
+            .validate_yaml(&bundle.game_yaml)
+            .is_compliant()
+        {
+            return Err(BundleError::PrivacyViolation {
+                message: "Game contains potentially unsafe content".to_string(),
+            });
+        }
+        Ok(bundle)
+    }
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used, clippy::expect_used)]
 mod tests {
@@ -551,7 +1013,7 @@ mod tests {
             let metadata = BundleMetadata::new("My Game");
             let bundle = GameBundle::from_yaml(yaml, metadata).unwrap();
 
-            assert_eq!(bundle.version, 1);
+            assert_eq!(bundle.version, BUNDLE_VERSION);
             assert_eq!(bundle.game_yaml, "character: bunny");
             assert_eq!(bundle.metadata.title, "My Game");
         }
@@ -667,6 +1129,56 @@ mod tests {
             assert_eq!(bundle.assets.len(), 1);
         }
 
+        #[test]
+        fn test_decoded_assets_by_type() {
+            let mut bundle =
+                GameBundle::from_yaml("character: bunny", BundleMetadata::new("Test")).unwrap();
+            bundle
+                .add_asset(EmbeddedAsset::from_bytes(
+                    "ghost.apr",
+                    AssetType::AiModel,
+                    b"APNR",
+                ))
+                .unwrap();
+            bundle
+                .add_asset(EmbeddedAsset::from_bytes(
+                    "bunny.png",
+                    AssetType::Sprite,
+                    b"png",
+                ))
+                .unwrap();
+
+            let models = bundle.decoded_assets(AssetType::AiModel).unwrap();
+            assert_eq!(models, vec![("ghost.apr".to_string(), b"APNR".to_vec())]);
+        }
+
+        #[test]
+        fn test_thumbnail_is_replaced_and_survives_export() {
+            let mut bundle =
+                GameBundle::from_yaml("character: bunny", BundleMetadata::new("Test")).unwrap();
+            assert!(bundle.thumbnail().is_none());
+
+            bundle.set_thumbnail(b"first").unwrap();
+            bundle.set_thumbnail(b"\x89PNG").unwrap();
+            assert_eq!(
+                bundle.decoded_assets(AssetType::Thumbnail).unwrap().len(),
+                1
+            );
+
+            let restored = GameBundle::from_json(&bundle.to_json().unwrap()).unwrap();
+            assert_eq!(restored.thumbnail(), Some(b"\x89PNG".to_vec()));
+        }
+
+        #[test]
+        fn test_oversized_thumbnail_keeps_previous() {
+            let mut bundle =
+                GameBundle::from_yaml("character: bunny", BundleMetadata::new("Test")).unwrap();
+            bundle.set_thumbnail(b"small").unwrap();
+            assert!(bundle.set_thumbnail(&vec![0; MAX_BUNDLE_SIZE]).is_err());
+            assert_eq!(bundle.thumbnail(), Some(b"small".to_vec()));
+            assert!(bundle.verify());
+        }
+
         #[test]
         fn test_asset_size_limit() {
             let yaml = "character: bunny";
@@ -770,6 +1282,236 @@ mod tests {
         }
     }
 
+    mod challenge_tests {
+        use super::*;
+        use crate::scoreboard::Scoreboard;
+
+        #[test]
+        fn test_challenge_survives_export() {
+            let mut board = Scoreboard::new("bunny");
+            let _ = board.submit("carrots", "Hopper", 30);
+            let mut bundle =
+                GameBundle::from_yaml("character: bunny", BundleMetadata::new("Hop")).unwrap();
+            bundle.set_challenge(board.challenge("carrots")).unwrap();
+
+            let imported = GameBundle::from_base64(&bundle.to_base64().unwrap()).unwrap();
+            let challenge = imported.challenge().unwrap();
+            assert_eq!(challenge.score, 30);
+            assert!(challenge.is_beaten_by(31));
+        }
+
+        #[test]
+        fn test_tampered_challenge_fails_integrity() {
+            let mut board = Scoreboard::new("bunny");
+            let _ = board.submit("carrots", "Hopper", 30);
+            let mut bundle =
+                GameBundle::from_yaml("character: bunny", BundleMetadata::new("Hop")).unwrap();
+            bundle.set_challenge(board.challenge("carrots")).unwrap();
+
+            let json = bundle
+                .to_json()
+                .unwrap()
+                .replace("\"score\": 30", "\"score\": 1");
+            assert!(matches!(
+                GameBundle::from_json(&json),
+                Err(BundleError::IntegrityError)
+            ));
+        }
+
+        #[test]
+        fn test_challenge_nickname_checked() {
+            let mut board = Scoreboard::new("bunny");
+            let _ = board.submit("carrots", "John Smith", 30);
+            let mut bundle =
+                GameBundle::from_yaml("character: bunny", BundleMetadata::new("Hop")).unwrap();
+            assert!(bundle.set_challenge(board.challenge("carrots")).is_err());
+            assert!(bundle.challenge().is_none());
+            assert!(bundle.verify());
+        }
+    }
+
+    mod sync_tests {
+        use super::*;
+
+        fn big_bundle() -> GameBundle {
+            let mut bundle =
+                GameBundle::from_yaml("character: bunny", BundleMetadata::new("Sync")).unwrap();
+            bundle
+                .add_asset(EmbeddedAsset::from_bytes(
+                    "jump.ogg",
+                    AssetType::Sound,
+                    &[7; 2000],
+                ))
+                .unwrap();
+            bundle
+        }
+
+        #[test]
+        fn test_small_bundle_fits_one_frame() {
+            let bundle =
+                GameBundle::from_yaml("character: bunny", BundleMetadata::new("Test")).unwrap();
+            let package = bundle.export_sync_package().unwrap();
+            assert_eq!(package.len(), 1);
+            assert!(package.to_texts()[0].starts_with("JGS1:"));
+        }
+
+        #[test]
+        fn test_frames_reassemble_in_any_order() {
+            let bundle = big_bundle();
+            let texts = bundle.export_sync_package().unwrap().to_texts();
+            assert!(texts.len() > 2);
+            assert!(texts
+                .iter()
+                .all(|text| text.len() <= SYNC_FRAME_CAPACITY + 32));
+
+            let mut receiver = SyncReceiver::new();
+            for text in texts.iter().rev() {
+                assert!(receiver.accept(text).unwrap());
+            }
+            // Repeats are harmless
+            assert!(!receiver.accept(&texts[0]).unwrap());
+            assert!(receiver.missing().is_empty());
+
+            let restored = receiver.finish().unwrap();
+            assert!(restored.verify());
+            assert_eq!(restored.game_yaml, bundle.game_yaml);
+            assert_eq!(restored.assets.len(), 1);
+        }
+
+        #[test]
+        fn test_missing_frames_are_reported() {
+            let texts = big_bundle().export_sync_package().unwrap().to_texts();
+            let mut receiver = SyncReceiver::new();
+            let _ = receiver.accept(&texts[0]).unwrap();
+
+            assert!(!receiver.is_complete());
+            assert_eq!(receiver.missing().first(), Some(&2));
+            assert!(receiver.finish().is_err());
+        }
+
+        #[test]
+        fn test_frames_from_another_game_are_rejected() {
+            let texts = big_bundle().export_sync_package().unwrap().to_texts();
+            let other = GameBundle::from_yaml("character: cat", BundleMetadata::new("Other"))
+                .unwrap()
+                .export_sync_package()
+                .unwrap()
+                .to_texts();
+
+            let mut receiver = SyncReceiver::new();
+            let _ = receiver.accept(&texts[0]).unwrap();
+            assert!(receiver.accept(&other[0]).is_err());
+            assert!(receiver.accept("https://example.com").is_err());
+            assert!(SyncFrame::parse("JGS1:00000001:0/1:abc").is_err());
+        }
+
+        #[test]
+        fn test_corrupted_payload_fails_verification() {
+            let mut frames = big_bundle()
+                .export_sync_package()
+                .unwrap()
+                .frames()
+                .to_vec();
+            frames[1].payload = frames[1].payload.chars().rev().collect();
+
+            let mut receiver = SyncReceiver::new();
+            for frame in &frames {
+                let _ = receiver.accept(&frame.to_text()).unwrap();
+            }
+            assert!(receiver.finish().is_err());
+        }
+    }
+
+    mod versioning_tests {
+        use super::*;
+
+        /// Version 1 bundle as written before score challenges existed
+        const V1_BUNDLE: &str = r#"{
+            "version": 1,
+            "game_yaml": "character: bunny\n",
+            "metadata": {
+                "creator_nickname": null,
+                "created_at": 0,
+                "title": "Bunny Hop",
+                "description": "",
+                "schema_level": 1,
+                "tags": []
+            },
+            "assets": [],
+            "checksum": 1480726113
+        }"#;
+
+        /// Version 1 bundle with a thumbnail and a score challenge
+        const V1_BUNDLE_WITH_CHALLENGE: &str = r#"{
+            "version": 1,
+            "game_yaml": "character: bunny\n",
+            "metadata": {
+                "creator_nickname": "StarKid",
+                "created_at": 1700000000,
+                "title": "Star Catcher",
+                "description": "Catch them all",
+                "schema_level": 1,
+                "tags": ["stars"]
+            },
+            "assets": [{
+                "name": "thumbnail.png",
+                "asset_type": "thumbnail",
+                "data_base64": "iVBORw0KGgo=",
+                "original_size": 8
+            }],
+            "challenge": {"table": "score", "nickname": "StarKid", "score": 120},
+            "checksum": 1298490875
+        }"#;
+
+        #[test]
+        fn test_loads_every_historical_version() {
+            let bundle = GameBundle::from_json(V1_BUNDLE).unwrap();
+            assert_eq!(bundle.version, BUNDLE_VERSION);
+            assert_eq!(bundle.metadata.title, "Bunny Hop");
+            assert!(bundle.verify());
+
+            let bundle = GameBundle::from_json(V1_BUNDLE_WITH_CHALLENGE).unwrap();
+            assert_eq!(bundle.challenge.as_ref().map(|c| c.score), Some(120));
+            assert!(bundle.thumbnail().is_some());
+            assert!(bundle.verify());
+
+            let current = GameBundle::from_yaml("character: bunny", BundleMetadata::new("Now"))
+                .unwrap()
+                .to_json()
+                .unwrap();
+            assert!(GameBundle::from_json(&current).unwrap().verify());
+        }
+
+        #[test]
+        fn test_check_upgrade_is_a_dry_run() {
+            let report = GameBundle::check_upgrade(V1_BUNDLE).unwrap();
+            assert_eq!((report.from, report.to), (1, 2));
+            assert_eq!(report.steps.len(), 1);
+
+            let upgraded = GameBundle::from_json(V1_BUNDLE).unwrap().to_json().unwrap();
+            assert!(GameBundle::check_upgrade(&upgraded).unwrap().is_current());
+        }
+
+        #[test]
+        fn test_rejects_tampered_and_future_bundles() {
+            let tampered = V1_BUNDLE.replace("Bunny Hop", "Bunny Flop");
+            assert!(matches!(
+                GameBundle::from_json(&tampered),
+                Err(BundleError::Version(VersionError::Failed { from: 1, .. }))
+            ));
+
+            let future = V1_BUNDLE.replace("\"version\": 1", "\"version\": 9");
+            let err = GameBundle::from_json(&future).unwrap_err();
+            assert!(matches!(
+                err,
+                BundleError::Version(VersionError::TooNew { found: 9, .. })
+            ));
+            assert!(err.to_string().contains("newer Jugar"));
+
+            assert!(GameBundle::from_json(r#"{"game_yaml": ""}"#).is_err());
+        }
+    }
+
     mod helper_function_tests {
         use super::*;
 
@@ -0,0 +1,366 @@
+//! Local-only telemetry.
+//!
+//! Per spec Section 9.2: no tracking, all data stays on-device by default.
+//! Typed events are kept in a bounded ring buffer on the device. They only
+//! leave it through an explicit export after a parent or teacher grants
+//! consent, and the export contains differentially private counts rather
+//! than raw events.
+
+use alloc::collections::{BTreeMap, VecDeque};
+
+use serde::{Deserialize, Serialize};
+
+use crate::privacy::{
+    DifferentialPrivacy, DifferentialPrivacyConfig, PrivacyBudget, PrivacyBudgetError,
+};
+use crate::sandbox::ContentFilter;
+
+/// Default number of events kept on the device
+pub const DEFAULT_TELEMETRY_CAPACITY: usize = 256;
+
+/// Privacy budget metric charged by telemetry exports
+pub const TELEMETRY_METRIC: &str = "telemetry";
+
+/// Label used in place of error codes rejected by the content filter
+const FILTERED_LABEL: &str = "filtered";
+
+/// A telemetry event
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum TelemetryEvent {
+    /// A game was compiled
+    GameCompiled {
+        /// Schema level (1-3)
+        level: u8,
+        /// Whether compilation succeeded
+        success: bool,
+    },
+    /// A kid-friendly error was shown
+    ErrorShown {
+        /// Error code (e.g. `unknown_word`), never the child's text
+        code: String,
+    },
+    /// A tutorial step was completed
+    TutorialStepCompleted {
+        /// Stage number (1-4)
+        stage: u8,
+    },
+}
+
+impl TelemetryEvent {
+    /// Event name as exported
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::GameCompiled { .. } => "game_compiled",
+            Self::ErrorShown { .. } => "error_shown",
+            Self::TutorialStepCompleted { .. } => "tutorial_step_completed",
+        }
+    }
+
+    /// Aggregation key, or `None` if the content filter rejects the event
+    fn key(&self, filter: &ContentFilter) -> Option<String> {
+        match self {
+            Self::GameCompiled { level, success } => Some(format!(
+                "game_compiled.level_{level}.{}",
+                if *success { "ok" } else { "failed" }
+            )),
+            Self::ErrorShown { code } => filter
+                .check(code)
+                .is_none()
+                .then(|| format!("error_shown.{code}")),
+            Self::TutorialStepCompleted { stage } => {
+                Some(format!("tutorial_step_completed.stage_{stage}"))
+            }
+        }
+    }
+}
+
+/// Whether telemetry may leave the device
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TelemetryConsent {
+    /// Nobody has been asked yet
+    #[default]
+    NotAsked,
+    /// A parent or teacher allowed exports
+    Granted,
+    /// A parent or teacher refused exports
+    Denied,
+}
+
+/// Telemetry error
+#[derive(Debug, Clone, PartialEq)]
+pub enum TelemetryError {
+    /// Export attempted without consent
+    ConsentRequired,
+    /// The privacy budget refused the export
+    Budget(PrivacyBudgetError),
+}
+
+impl core::fmt::Display for TelemetryError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::ConsentRequired => write!(f, "A grown-up needs to allow sharing first"),
+            Self::Budget(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl core::error::Error for TelemetryError {}
+
+impl From<PrivacyBudgetError> for TelemetryError {
+    fn from(err: PrivacyBudgetError) -> Self {
+        Self::Budget(err)
+    }
+}
+
+/// Bounded on-device event buffer
+///
+/// When full, the oldest event is dropped.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TelemetryBuffer {
+    events: VecDeque<TelemetryEvent>,
+    capacity: usize,
+    dropped: u64,
+    consent: TelemetryConsent,
+}
+
+impl Default for TelemetryBuffer {
+    fn default() -> Self {
+        Self::new(DEFAULT_TELEMETRY_CAPACITY)
+    }
+}
+
+impl TelemetryBuffer {
+    /// Creates an empty buffer holding at most `capacity` events
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            events: VecDeque::with_capacity(capacity),
+            capacity,
+            dropped: 0,
+            consent: TelemetryConsent::NotAsked,
+        }
+    }
+
+    /// Records an event, evicting the oldest if full
+    pub fn record(&mut self, event: TelemetryEvent) {
+        if self.events.len() == self.capacity {
+            let _ = self.events.pop_front();
+            self.dropped = self.dropped.saturating_add(1);
+        }
+        self.events.push_back(event);
+    }
+
+    /// Buffered events, oldest first
+    pub fn events(&self) -> impl Iterator<Item = &TelemetryEvent> {
+        self.events.iter()
+    }
+
+    /// Number of buffered events
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Returns true if nothing is buffered
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Maximum number of buffered events
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Events evicted because the buffer was full
+    #[must_use]
+    pub const fn dropped(&self) -> u64 {
+        self.dropped
+    }
+
+    /// Deletes all buffered events
+    pub fn clear(&mut self) {
+        self.events.clear();
+        self.dropped = 0;
+    }
+
+    /// Current consent state
+    #[must_use]
+    pub const fn consent(&self) -> TelemetryConsent {
+        self.consent
+    }
+
+    /// Records a parent or teacher allowing exports
+    pub const fn grant_consent(&mut self) {
+        self.consent = TelemetryConsent::Granted;
+    }
+
+    /// Records a parent or teacher refusing exports; buffered events are deleted
+    pub fn deny_consent(&mut self) {
+        self.consent = TelemetryConsent::Denied;
+        self.clear();
+    }
+
+    /// Produces a differentially private export and empties the buffer
+    ///
+    /// Events are counted per key; error codes rejected by `filter` are
+    /// counted under `error_shown.filtered`. Each count gets Laplace noise and
+    /// the export charges `config.epsilon` to `budget`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TelemetryError::ConsentRequired`] unless consent was granted,
+    /// or [`TelemetryError::Budget`] if the budget is used up. The buffer is
+    /// left untouched on error.
+    pub fn export(
+        &mut self,
+        filter: &ContentFilter,
+        config: &DifferentialPrivacyConfig,
+        budget: &mut PrivacyBudget,
+    ) -> Result<TelemetryExport, TelemetryError> {
+        if self.consent != TelemetryConsent::Granted {
+            return Err(TelemetryError::ConsentRequired);
+        }
+        budget.charge(TELEMETRY_METRIC, config.epsilon)?;
+
+        let mut raw: BTreeMap<String, u64> = BTreeMap::new();
+        for event in &self.events {
+            let key = event
+                .key(filter)
+                .unwrap_or_else(|| format!("{}.{FILTERED_LABEL}", event.name()));
+            *raw.entry(key).or_insert(0) += 1;
+        }
+
+        let noise_gen = DifferentialPrivacy::new(config.epsilon, config.sensitivity);
+        let counts = raw
+            .into_iter()
+            .map(|(key, count)| (key, noise_gen.add_laplace_noise_u64(count).max(0)))
+            .collect();
+        self.clear();
+
+        Ok(TelemetryExport {
+            epsilon: config.epsilon,
+            counts,
+        })
+    }
+}
+
+/// Consented telemetry export with noisy counts
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TelemetryExport {
+    /// Epsilon used for the noise
+    pub epsilon: f64,
+    /// Noisy event counts by key, clamped to zero
+    pub counts: BTreeMap<String, i64>,
+}
+
+impl TelemetryExport {
+    /// Serializes the export blob
+    ///
+    /// # Errors
+    ///
+    /// Returns error if serialization fails
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn compiled(success: bool) -> TelemetryEvent {
+        TelemetryEvent::GameCompiled { level: 1, success }
+    }
+
+    #[test]
+    fn test_ring_buffer_evicts_oldest() {
+        let mut buffer = TelemetryBuffer::new(2);
+        buffer.record(TelemetryEvent::TutorialStepCompleted { stage: 1 });
+        buffer.record(compiled(true));
+        buffer.record(compiled(false));
+
+        assert_eq!(buffer.len(), 2);
+        assert_eq!(buffer.dropped(), 1);
+        assert_eq!(buffer.events().next(), Some(&compiled(true)));
+    }
+
+    #[test]
+    fn test_export_requires_consent() {
+        let mut buffer = TelemetryBuffer::default();
+        buffer.record(compiled(true));
+        let mut budget = PrivacyBudget::default();
+        let config = DifferentialPrivacyConfig::moderate_privacy();
+
+        let result = buffer.export(&ContentFilter::new(), &config, &mut budget);
+        assert_eq!(result, Err(TelemetryError::ConsentRequired));
+        assert_eq!(buffer.len(), 1);
+        assert!(budget.report().metrics.is_empty());
+    }
+
+    #[test]
+    fn test_deny_consent_deletes_events() {
+        let mut buffer = TelemetryBuffer::default();
+        buffer.record(compiled(true));
+        buffer.deny_consent();
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.consent(), TelemetryConsent::Denied);
+    }
+
+    #[test]
+    fn test_export_aggregates_and_filters() {
+        let mut filter = ContentFilter::new();
+        filter.block_word("secretname");
+
+        let mut buffer = TelemetryBuffer::default();
+        buffer.grant_consent();
+        buffer.record(compiled(true));
+        buffer.record(compiled(true));
+        buffer.record(TelemetryEvent::ErrorShown {
+            code: "unknown_word".to_string(),
+        });
+        buffer.record(TelemetryEvent::ErrorShown {
+            code: "hello_secretname".to_string(),
+        });
+
+        let config = DifferentialPrivacyConfig::weak_privacy();
+        let mut budget = PrivacyBudget::new(config.epsilon);
+        let export = buffer.export(&filter, &config, &mut budget).unwrap();
+
+        assert!(export.counts.contains_key("game_compiled.level_1.ok"));
+        assert!(export.counts.contains_key("error_shown.unknown_word"));
+        assert!(export.counts.contains_key("error_shown.filtered"));
+        assert!(export.counts.values().all(|&count| count >= 0));
+
+        let blob = export.to_json().unwrap();
+        assert!(!blob.contains("secretname"));
+        assert!(buffer.is_empty());
+        assert_eq!(budget.queries(TELEMETRY_METRIC), 1);
+    }
+
+    #[test]
+    fn test_export_refused_when_budget_exhausted() {
+        let mut buffer = TelemetryBuffer::default();
+        buffer.grant_consent();
+        buffer.record(compiled(true));
+        let mut budget = PrivacyBudget::new(0.5);
+        let config = DifferentialPrivacyConfig::moderate_privacy();
+
+        let result = buffer.export(&ContentFilter::new(), &config, &mut budget);
+        assert!(matches!(result, Err(TelemetryError::Budget(_))));
+        assert_eq!(buffer.len(), 1);
+    }
+
+    #[test]
+    fn test_event_serde_is_tagged() {
+        let json =
+            serde_json::to_string(&TelemetryEvent::TutorialStepCompleted { stage: 2 }).unwrap();
+        assert_eq!(json, r#"{"event":"tutorial_step_completed","stage":2}"#);
+    }
+}
@@ -1,9 +1,14 @@
 //! Tutorial progression system.
 //!
 //! Per spec Section 10.1: Staged tutorial for introducing game concepts.
+//! Each completed stage earns an achievement badge to reinforce learning.
 
 use crate::error::YamlError;
 use crate::schema::SchemaLevel;
+use jugar_core::{Achievement, AchievementUnlocked, Achievements, Events};
+
+/// Achievement earned for finishing every tutorial stage
+pub const TUTORIAL_GRADUATE_ACHIEVEMENT: &str = "tutorial.graduate";
 
 /// Tutorial stage representing progression through learning
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
@@ -91,6 +96,30 @@ impl TutorialStage {
         ]
     }
 
+    /// Id of the achievement earned by completing this stage
+    #[must_use]
+    pub const fn achievement_id(self) -> &'static str {
+        match self {
+            Self::HelloWorld => "tutorial.hello_world",
+            Self::AddGoal => "tutorial.add_goal",
+            Self::AddFeedback => "tutorial.add_feedback",
+            Self::MakeChallenging => "tutorial.make_challenging",
+        }
+    }
+
+    /// The achievement earned by completing this stage
+    #[must_use]
+    pub fn achievement(self) -> Achievement {
+        let name = match self {
+            Self::HelloWorld => "First Steps",
+            Self::AddGoal => "Goal Getter",
+            Self::AddFeedback => "Sound Maker",
+            Self::MakeChallenging => "Game Designer",
+        };
+        Achievement::new(self.achievement_id(), name)
+            .with_description(format!("Finish the '{}' lesson", self.name()))
+    }
+
     /// Get the schema level appropriate for this tutorial stage
     #[must_use]
     pub const fn schema_level(self) -> SchemaLevel {
@@ -161,6 +190,26 @@ impl TutorialProgress {
         }
     }
 
+    /// Advances like [`Self::advance`], unlocking the finished stage's
+    /// achievement (and the graduate badge after the last stage)
+    ///
+    /// # Errors
+    ///
+    /// Returns error if current stage is not complete
+    pub fn advance_and_reward(
+        &mut self,
+        achievements: &mut Achievements,
+        events: &mut Events<AchievementUnlocked>,
+    ) -> Result<Option<TutorialStage>, TutorialError> {
+        let finished = self.current_stage;
+        let next = self.advance()?;
+        let _ = achievements.unlock(finished.achievement_id(), events);
+        if next.is_none() {
+            let _ = achievements.unlock(TUTORIAL_GRADUATE_ACHIEVEMENT, events);
+        }
+        Ok(next)
+    }
+
     /// Update the current YAML
     pub fn update_yaml(&mut self, yaml: impl Into<String>) {
         self.current_yaml = yaml.into();
@@ -196,6 +245,20 @@ impl TutorialProgress {
     }
 }
 
+/// Registry of the tutorial's achievements: one per stage plus a graduate badge
+#[must_use]
+pub fn tutorial_achievements() -> Achievements {
+    let mut achievements = Achievements::new("tutorial");
+    for stage in TutorialStage::all() {
+        achievements.register(stage.achievement());
+    }
+    achievements.with(
+        Achievement::new(TUTORIAL_GRADUATE_ACHIEVEMENT, "Tutorial Graduate")
+            .with_description("Finish every lesson")
+            .with_icon("trophy"),
+    )
+}
+
 /// Result of checking YAML against stage requirements
 #[derive(Debug, Clone)]
 pub struct StageCheckResult {
@@ -383,6 +446,19 @@ pub struct GameTemplate {
     pub preview_path: Option<String>,
     /// The YAML template content
     pub yaml: String,
+    /// Step-by-step build-up of the template, simplest first
+    pub stages: Vec<TemplateStage>,
+}
+
+/// One step of building a template from scratch
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateStage {
+    /// Short stage title
+    pub title: String,
+    /// What to add in this stage
+    pub instructions: String,
+    /// The game so far; always compiles
+    pub yaml: String,
 }
 
 impl GameTemplate {
@@ -396,6 +472,7 @@ impl GameTemplate {
             level,
             preview_path: None,
             yaml: String::new(),
+            stages: Vec::new(),
         }
     }
 
@@ -419,6 +496,22 @@ impl GameTemplate {
         self.preview_path = Some(path.into());
         self
     }
+
+    /// Add a tutorial stage after the existing ones
+    #[must_use]
+    pub fn with_stage(
+        mut self,
+        title: impl Into<String>,
+        instructions: impl Into<String>,
+        yaml: impl Into<String>,
+    ) -> Self {
+        self.stages.push(TemplateStage {
+            title: title.into(),
+            instructions: instructions.into(),
+            yaml: yaml.into(),
+        });
+        self
+    }
 }
 
 /// Template catalog with built-in game templates
@@ -476,6 +569,72 @@ impl TemplateCatalog {
                 .with_yaml(DUNGEON_TEMPLATE),
         );
 
+        // Endless Maze (Level 3) - procedural maze layout
+        catalog.templates.push(
+            GameTemplate::new("maze-explorer", "Endless Maze", SchemaLevel::Level3)
+                .with_description("A brand new maze every time you play!")
+                .with_yaml(include_str!("../templates/maze-explorer.yaml"))
+                .with_stage(
+                    "Build a Maze",
+                    "Add a world with algorithm: maze and a player to explore it.",
+                    MAZE_STAGE_WORLD,
+                )
+                .with_stage(
+                    "Hide a Gem",
+                    "Add a gem item and a rule that gives it to the player.",
+                    MAZE_STAGE_GEM,
+                )
+                .with_stage(
+                    "Watch Out!",
+                    "Add a ghost, lives and a way to win.",
+                    include_str!("../templates/maze-explorer.yaml"),
+                ),
+        );
+
+        // Hop Hop (Level 3) - gravity and jumping
+        catalog.templates.push(
+            GameTemplate::new("simple-platformer", "Hop Hop", SchemaLevel::Level3)
+                .with_description("Jump over the slimes and grab the coins!")
+                .with_yaml(include_str!("../templates/platformer.yaml"))
+                .with_stage(
+                    "Turn On Gravity",
+                    "Add a sidescroll world with gravity and a player that uses tap-to-jump.",
+                    PLATFORMER_STAGE_GRAVITY,
+                )
+                .with_stage(
+                    "Coins!",
+                    "Spawn coins and add score when the player touches one.",
+                    PLATFORMER_STAGE_COINS,
+                )
+                .with_stage(
+                    "Slimes",
+                    "Add a slime to jump over, lives and a score to win.",
+                    include_str!("../templates/platformer.yaml"),
+                ),
+        );
+
+        // Quiz Time (Level 2) - questions and choices
+        catalog.templates.push(
+            GameTemplate::new("quiz", "Quiz Time", SchemaLevel::Level2)
+                .with_description("Write your own quiz and tap the right answers!")
+                .with_yaml(include_str!("../templates/quiz.yaml"))
+                .with_stage(
+                    "Ask a Question",
+                    "Add a question with its choices and the right answer.",
+                    QUIZ_STAGE_QUESTION,
+                )
+                .with_stage(
+                    "More Questions",
+                    "Add more questions and a character to cheer you on.",
+                    QUIZ_STAGE_MORE,
+                )
+                .with_stage(
+                    "Win the Quiz",
+                    "Add a rule for a high score and a way to win.",
+                    include_str!("../templates/quiz.yaml"),
+                ),
+        );
+
         catalog
     }
 
@@ -643,6 +802,122 @@ background: dungeon
 music: epic
 "#;
 
+// Tutorial stages for the maze, platformer and quiz templates
+
+const MAZE_STAGE_WORLD: &str = r"game: endless-maze
+version: 1
+
+world:
+  type: procedural
+  algorithm: maze
+  seed: random
+  size: [21, 15]
+
+characters:
+  player:
+    type: robot
+    move: arrows
+";
+
+const MAZE_STAGE_GEM: &str = r"game: endless-maze
+version: 1
+
+world:
+  type: procedural
+  algorithm: maze
+  seed: random
+  size: [21, 15]
+
+characters:
+  player:
+    type: robot
+    move: arrows
+  gem:
+    type: gem
+    move: none
+
+items:
+  gem:
+    type: gem
+    max: 1
+
+rules:
+  - when: player touches gem
+    then:
+      - give_item: gem
+      - entity: gem
+        action: disappear
+";
+
+const PLATFORMER_STAGE_GRAVITY: &str = r"game: hop-hop
+version: 1
+
+world:
+  type: sidescroll
+  gravity: 20
+  jump: 3
+
+characters:
+  player:
+    type: bunny
+    move: tap-to-jump
+";
+
+const PLATFORMER_STAGE_COINS: &str = r"game: hop-hop
+version: 1
+
+world:
+  type: sidescroll
+  gravity: 20
+  jump: 3
+
+characters:
+  player:
+    type: bunny
+    move: tap-to-jump
+
+templates:
+  coin:
+    type: coin
+    move: none
+
+rules:
+  - when: time reaches 1
+    then:
+      - spawn: coin
+        at: right
+        every: 2s
+  - when: player touches coin
+    then:
+      - add_score: 10
+      - entity: coin
+        action: disappear
+";
+
+const QUIZ_STAGE_QUESTION: &str = r#"game: quiz-time
+
+questions:
+  - ask: "What color is the sky?"
+    choices: [blue, green, purple]
+    answer: blue
+"#;
+
+const QUIZ_STAGE_MORE: &str = r#"game: quiz-time
+
+characters:
+  player:
+    type: cat
+    move: touch
+
+questions:
+  - ask: "What color is the sky?"
+    choices: [blue, green, purple]
+    answer: blue
+  - ask: "Which animal says moo?"
+    choices: [duck, cow, cat]
+    answer: cow
+"#;
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used, clippy::expect_used)]
 mod tests {
@@ -871,6 +1146,39 @@ mod tests {
 
             assert!(progress.is_complete());
         }
+
+        #[test]
+        fn test_stages_earn_achievements() {
+            let mut events = Events::new();
+            let mut achievements = tutorial_achievements();
+            let mut progress = TutorialProgress::new();
+            assert_eq!(achievements.len(), 5);
+
+            progress.update_yaml("");
+            assert!(progress
+                .advance_and_reward(&mut achievements, &mut events)
+                .is_err());
+            assert!(events.is_empty());
+
+            for stage in TutorialStage::all() {
+                progress.update_yaml(stage.example_yaml());
+                let _ = progress
+                    .advance_and_reward(&mut achievements, &mut events)
+                    .unwrap();
+            }
+            let names: Vec<_> = events.drain().map(|unlocked| unlocked.name).collect();
+            assert_eq!(
+                names,
+                [
+                    "First Steps",
+                    "Goal Getter",
+                    "Sound Maker",
+                    "Game Designer",
+                    "Tutorial Graduate"
+                ]
+            );
+            assert!(achievements.is_unlocked(TUTORIAL_GRADUATE_ACHIEVEMENT));
+        }
     }
 
     mod template_tests {
@@ -957,6 +1265,67 @@ mod tests {
             assert!(DUNGEON_TEMPLATE.contains("generator: procedural"));
             assert!(DUNGEON_TEMPLATE.contains("boss:"));
         }
+
+        fn compiled_stages(id: &str) -> Vec<crate::CompiledGame> {
+            let catalog = TemplateCatalog::with_defaults();
+            let template = catalog.get(id).unwrap();
+            assert!(!template.stages.is_empty());
+            // The last stage is the finished template
+            assert_eq!(template.stages.last().unwrap().yaml, template.yaml);
+            template
+                .stages
+                .iter()
+                .map(|stage| {
+                    let game = crate::compile_game(&stage.yaml).unwrap();
+                    assert_eq!(game.level, template.level, "{id} stage {:?}", stage.title);
+                    game
+                })
+                .collect()
+        }
+
+        #[test]
+        fn test_maze_explorer_stages_compile() {
+            let stages = compiled_stages("maze-explorer");
+            let game = stages.last().unwrap();
+            let world = game.world.as_ref().unwrap();
+            assert!(world.is_procedural());
+            assert_eq!(world.algorithm.as_deref(), Some("maze"));
+            assert_eq!(world.seed, None);
+            assert_eq!(world.size, Some((21, 15)));
+            assert!(!world.has_gravity());
+            assert!(game.items.iter().any(|item| item.id == "gem"));
+        }
+
+        #[test]
+        fn test_simple_platformer_stages_compile() {
+            let stages = compiled_stages("simple-platformer");
+            for game in &stages {
+                let world = game.world.as_ref().unwrap();
+                assert!(world.has_gravity());
+                assert!(!world.is_procedural());
+                assert!(world
+                    .jump
+                    .is_some_and(|jump| (jump - 3.0).abs() < f32::EPSILON));
+            }
+            let player = stages[0]
+                .entities
+                .iter()
+                .find(|entity| entity.id == "player")
+                .unwrap();
+            assert_eq!(player.movement.as_deref(), Some("tap-to-jump"));
+        }
+
+        #[test]
+        fn test_quiz_stages_compile() {
+            let stages = compiled_stages("quiz");
+            let counts: Vec<usize> = stages.iter().map(|game| game.questions.len()).collect();
+            assert_eq!(counts, [1, 2, 3]);
+            let question = &stages[2].questions[1];
+            assert_eq!(question.ask, "Which animal says moo?");
+            assert_eq!(question.choices[question.answer], "cow");
+            assert!(question.is_correct(1));
+            assert!(stages.iter().all(|game| game.world.is_none()));
+        }
     }
 
     mod tutorial_error_tests {
@@ -111,7 +111,7 @@ impl Vocabulary {
             },
             VocabularyCategory {
                 name: "movement".to_string(),
-                words: vec!["arrows", "touch", "auto"]
+                words: vec!["arrows", "touch", "auto", "drag", "tap-to-jump", "tilt"]
                     .into_iter()
                     .map(String::from)
                     .collect(),
@@ -204,6 +204,15 @@ impl Vocabulary {
                     .map(String::from)
                     .collect(),
             },
+            VocabularyCategory {
+                name: "items".to_string(),
+                words: vec![
+                    "key", "potion", "sword", "shield", "bomb", "map", "shell", "flower",
+                ]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+            },
             VocabularyCategory {
                 name: "schema_l2".to_string(),
                 words: vec![
@@ -220,6 +229,30 @@ impl Vocabulary {
                     "lose_life",
                     "play",
                     "show",
+                    "health",
+                    "damage",
+                    "heal",
+                    "amount",
+                    "win_when",
+                    "lose_when",
+                    "items",
+                    "give_item",
+                    "remove_item",
+                    "max",
+                    "start",
+                    "say",
+                    "by",
+                    "for",
+                    "voice",
+                    "templates",
+                    "spawn",
+                    "at",
+                    "every",
+                    "questions",
+                    "ask",
+                    "choices",
+                    "answer",
+                    "random",
                 ]
                 .into_iter()
                 .map(String::from)
@@ -242,10 +275,20 @@ impl Vocabulary {
         let level3_categories = vec![
             VocabularyCategory {
                 name: "world".to_string(),
-                words: vec!["static", "procedural", "grid", "wfc", "noise"]
-                    .into_iter()
-                    .map(String::from)
-                    .collect(),
+                words: vec![
+                    "static",
+                    "procedural",
+                    "sidescroll",
+                    "grid",
+                    "maze",
+                    "dungeon",
+                    "arena",
+                    "wfc",
+                    "noise",
+                ]
+                .into_iter()
+                .map(String::from)
+                .collect(),
             },
             VocabularyCategory {
                 name: "physics".to_string(),
@@ -279,6 +322,8 @@ impl Vocabulary {
                     "seed",
                     "size",
                     "tiles",
+                    "gravity",
+                    "jump",
                     "entities",
                     "sprite",
                     "ai",
@@ -295,6 +340,7 @@ impl Vocabulary {
                     "anchor",
                     "bind",
                     "version",
+                    "difficulty",
                 ]
                 .into_iter()
                 .map(String::from)
@@ -328,6 +374,19 @@ impl Vocabulary {
         self.categories.push(category);
     }
 
+    /// Add a word to a category, creating the category if needed
+    pub(crate) fn add_word(&mut self, category: &str, word: &str) {
+        let word = word.to_lowercase();
+        let _ = self.words.insert(word.clone());
+        match self.categories.iter_mut().find(|c| c.name == category) {
+            Some(existing) => existing.words.push(word),
+            None => self.categories.push(VocabularyCategory {
+                name: category.to_string(),
+                words: vec![word],
+            }),
+        }
+    }
+
     /// Check if a word is in the vocabulary
     #[must_use]
     pub fn contains(&self, word: &str) -> bool {
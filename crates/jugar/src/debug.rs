@@ -0,0 +1,130 @@
+//! Debug overlays for engine subsystems.
+//!
+//! Bridges physics, AI and UI state into [`DebugDraw`], which lives in
+//! jugar-render and knows nothing about the other crates.
+
+use glam::Vec2;
+
+#[cfg(feature = "ai")]
+use jugar_ai::AiOutputs;
+use jugar_physics::{BodyHandle, PhysicsWorld, Shape};
+use jugar_render::{DebugCategory, DebugDraw};
+use jugar_ui::UiContainer;
+
+/// Draws collider outlines, velocity vectors and contacts
+#[allow(clippy::cast_possible_truncation)]
+pub fn draw_physics(world: &PhysicsWorld, debug: &mut DebugDraw) {
+    if !debug.is_enabled() {
+        return;
+    }
+    for index in 0..world.body_count() {
+        let Some(body) = world.get_body(BodyHandle(index as u32)) else {
+            continue;
+        };
+        let position = body.position.as_vec2();
+        match body.shape {
+            Some(Shape::Circle { radius }) => {
+                debug.circle(DebugCategory::Colliders, position, radius);
+            }
+            Some(Shape::Rect { half_extents }) => {
+                let (min, max) = (position - half_extents, position + half_extents);
+                debug.polygon(
+                    DebugCategory::Colliders,
+                    vec![min, Vec2::new(max.x, min.y), max, Vec2::new(min.x, max.y)],
+                );
+            }
+            None => {}
+        }
+        if !body.is_static {
+            debug.arrow(DebugCategory::Velocities, position, body.velocity.as_vec2());
+        }
+    }
+    for contact in world.contacts() {
+        let (Some(a), Some(b)) = (world.get_body(contact.a), world.get_body(contact.b)) else {
+            continue;
+        };
+        // Contacts carry no point, so mark the midpoint between the bodies
+        let midpoint = (a.position.as_vec2() + b.position.as_vec2()) * 0.5;
+        debug.point(DebugCategory::Contacts, midpoint, 0.1);
+        debug.arrow(DebugCategory::Contacts, midpoint, contact.normal);
+    }
+}
+
+/// Draws an AI agent's steering output and optional path
+#[cfg(feature = "ai")]
+pub fn draw_agent(position: Vec2, outputs: &AiOutputs, path: &[Vec2], debug: &mut DebugDraw) {
+    debug.arrow(
+        DebugCategory::Steering,
+        position,
+        outputs.movement * outputs.speed,
+    );
+    debug.path(DebugCategory::Paths, path);
+}
+
+/// Draws the bounds of every visible widget
+pub fn draw_ui(ui: &UiContainer, debug: &mut DebugDraw) {
+    if !debug.is_active(DebugCategory::UiBounds) {
+        return;
+    }
+    for (_, element) in ui.sorted_for_render() {
+        debug.rect(DebugCategory::UiBounds, ui.calculate_widget_bounds(element));
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use jugar_core::{Position, UiElement, Velocity};
+    use jugar_physics::RigidBody;
+
+    #[test]
+    fn test_draw_physics_colliders_and_velocity() {
+        let mut world = PhysicsWorld::new();
+        let _ = world.add_body(
+            RigidBody::new(Position::new(0.0, 0.0))
+                .with_velocity(Velocity::new(1.0, 0.0))
+                .with_shape(Shape::circle(1.0)),
+        );
+        let _ = world.add_body(
+            RigidBody::new_static(Position::new(5.0, 0.0)).with_shape(Shape::rect(2.0, 2.0)),
+        );
+        let mut debug = DebugDraw::new();
+        draw_physics(&world, &mut debug);
+        assert!(debug.commands().is_empty(), "disabled draws nothing");
+
+        debug.set_enabled(true);
+        draw_physics(&world, &mut debug);
+        // Two colliders and one velocity arrow (static bodies have none)
+        assert_eq!(debug.commands().len(), 3);
+
+        debug.clear();
+        debug.set_category(DebugCategory::Velocities, false);
+        draw_physics(&world, &mut debug);
+        assert_eq!(debug.commands().len(), 2);
+    }
+
+    #[test]
+    fn test_draw_ui_bounds() {
+        let mut ui = UiContainer::new(800.0, 600.0);
+        let _ = ui.add_widget("a", UiElement::new(Vec2::new(100.0, 50.0)));
+        let mut debug = DebugDraw::new();
+        debug.set_enabled(true);
+        draw_ui(&ui, &mut debug);
+        assert_eq!(debug.commands().len(), 1);
+    }
+
+    #[cfg(feature = "ai")]
+    #[test]
+    fn test_draw_agent() {
+        let mut debug = DebugDraw::new();
+        debug.set_enabled(true);
+        let outputs = AiOutputs {
+            movement: Vec2::X,
+            speed: 1.0,
+            action: false,
+        };
+        draw_agent(Vec2::ZERO, &outputs, &[Vec2::ZERO, Vec2::ONE], &mut debug);
+        assert_eq!(debug.commands().len(), 2);
+    }
+}
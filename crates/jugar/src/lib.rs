@@ -14,6 +14,19 @@
 //! - **Audio**: Spatial 2D audio system
 //! - **Procedural Generation**: Noise, dungeons, and WFC
 //!
+//! ## Cargo Features
+//!
+//! Every subsystem is compiled into each game's WASM unless left out. The
+//! default features enable all of them; small games can depend on jugar with
+//! `default-features = false` and pick what they use:
+//!
+//! - `ai`: GOAP planner and behavior trees ([`ai`], [`draw_agent`])
+//! - `audio`: spatial audio ([`audio`], [`JugarEngine::audio`])
+//! - `procgen`: noise, dungeons and WFC ([`procgen`])
+//! - `widgets`: dialogs, toasts, speech bubbles and scroll views in [`ui`]
+//!
+//! [`build_info`] reports what a build contains.
+//!
 //! ## Example
 //!
 //! ```rust,ignore
@@ -28,29 +41,45 @@
 #![forbid(unsafe_code)]
 #![warn(missing_docs)]
 
+extern crate alloc;
+
+mod debug;
+mod tiled;
+
 use core::fmt;
 
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 // Re-export all crates
+#[cfg(feature = "ai")]
 pub use jugar_ai as ai;
+#[cfg(feature = "audio")]
 pub use jugar_audio as audio;
 pub use jugar_core as game_core;
 pub use jugar_input as input;
 pub use jugar_physics as physics;
+#[cfg(feature = "procgen")]
 pub use jugar_procgen as procgen;
 pub use jugar_render as render;
 pub use jugar_ui as ui;
 
+#[cfg(feature = "ai")]
+pub use debug::draw_agent;
+pub use debug::{draw_physics, draw_ui};
+pub use tiled::{
+    LevelBodies, Properties, PropertyValue, SpawnPoint, TileLayer, TiledLevel, TriggerVolume,
+};
+
 /// Prelude for common imports
 pub mod prelude {
     pub use crate::{JugarConfig, JugarEngine, LoopControl};
 
     // Core types
     pub use jugar_core::{
-        Anchor, Camera, Entity, FrameResult, GameLoop, GameLoopConfig, GameState, Position, Rect,
-        ScaleMode, Sprite, UiElement, Velocity, World,
+        AccessibilitySettings, Added, Anchor, Camera, Changed, ColorVision, Entity, FrameResult,
+        GameLoop, GameLoopConfig, GameState, Name, Position, Rect, ScaleMode, Sprite, Tags, Tick,
+        TransformSoa, UiElement, Velocity, World,
     };
 
     // Input
@@ -61,24 +90,30 @@ pub mod prelude {
 
     // Render
     pub use jugar_render::{
-        calculate_anchored_position, AspectRatio, RenderCommand, RenderQueue, Viewport,
+        calculate_anchored_position, AspectRatio, ClipRecorder, Color, ColorRemap, DebugCategory,
+        DebugDraw, Image, Palette, RenderCommand, RenderQueue, SpriteSize, Viewport, ZIndex,
     };
 
     // UI
     pub use jugar_ui::{Button, ButtonState as UiButtonState, Label, UiContainer, WidgetId};
 
     // Physics
-    pub use jugar_physics::{BodyHandle, PhysicsBackend, PhysicsWorld, RigidBody};
+    pub use jugar_physics::{BodyHandle, PhysicsBackend, PhysicsBodyRef, PhysicsWorld, RigidBody};
 
     // Audio
-    pub use jugar_audio::{AudioChannel, AudioHandle, AudioListener, AudioSystem, SoundSource};
+    #[cfg(feature = "audio")]
+    pub use jugar_audio::{
+        AudioChannel, AudioEmitter, AudioHandle, AudioListener, AudioSystem, SoundSource,
+    };
 
     // AI
+    #[cfg(feature = "ai")]
     pub use jugar_ai::{
-        Action, BehaviorNode, Goal, NodeStatus, Planner, Selector, Sequence, WorldState,
+        Action, BehaviorNode, Goal, NodeStatus, PlanStats, Planner, Selector, Sequence, WorldState,
     };
 
     // Procgen
+    #[cfg(feature = "procgen")]
     pub use jugar_procgen::{
         Direction, Dungeon, DungeonGenerator, DungeonTile, Rng, Room, ValueNoise, Wfc,
     };
@@ -87,6 +122,70 @@ pub mod prelude {
     pub use glam::Vec2;
 }
 
+/// Subsystems compiled into this build of the engine
+///
+/// Returned by [`build_info`]; print it in a game's about screen or a build
+/// script to check that unused subsystems were left out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[allow(clippy::struct_excessive_bools)] // One per optional cargo feature
+pub struct BuildInfo {
+    /// Engine version
+    pub version: &'static str,
+    /// GOAP planner and behavior trees
+    pub ai: bool,
+    /// Spatial audio
+    pub audio: bool,
+    /// Procedural generation
+    pub procgen: bool,
+    /// Dialogs, toasts, speech bubbles and scroll views
+    pub widgets: bool,
+}
+
+impl BuildInfo {
+    /// Names of the compiled-in subsystems, always-present ones first
+    #[must_use]
+    pub fn subsystems(&self) -> Vec<&'static str> {
+        let optional = [
+            ("ai", self.ai),
+            ("audio", self.audio),
+            ("procgen", self.procgen),
+            ("widgets", self.widgets),
+        ];
+        ["core", "input", "physics", "render", "ui"]
+            .into_iter()
+            .chain(
+                optional
+                    .into_iter()
+                    .filter(|&(_, enabled)| enabled)
+                    .map(|(name, _)| name),
+            )
+            .collect()
+    }
+}
+
+impl fmt::Display for BuildInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "jugar {} [{}]",
+            self.version,
+            self.subsystems().join(", ")
+        )
+    }
+}
+
+/// Reports the engine version and which optional subsystems are compiled in
+#[must_use]
+pub const fn build_info() -> BuildInfo {
+    BuildInfo {
+        version: env!("CARGO_PKG_VERSION"),
+        ai: cfg!(feature = "ai"),
+        audio: cfg!(feature = "audio"),
+        procgen: cfg!(feature = "procgen"),
+        widgets: cfg!(feature = "widgets"),
+    }
+}
+
 /// Jugar engine errors
 #[derive(Error, Debug, Clone, PartialEq, Eq)]
 pub enum JugarError {
@@ -96,6 +195,9 @@ pub enum JugarError {
     /// Runtime error
     #[error("Runtime error: {0}")]
     RuntimeError(String),
+    /// Level map could not be imported
+    #[error("Map import failed: {0}")]
+    MapImport(String),
 }
 
 /// Result type for Jugar operations
@@ -118,6 +220,9 @@ pub struct JugarConfig {
     pub vsync: bool,
     /// Application title
     pub title: String,
+    /// How independent systems (physics, audio) run each frame
+    #[serde(default)]
+    pub execution: jugar_core::ExecutionMode,
 }
 
 impl Default for JugarConfig {
@@ -130,6 +235,8 @@ impl Default for JugarConfig {
             max_delta: 0.25,
             vsync: true,
             title: "Jugar Game".to_string(),
+            // Per-frame jobs are too small to pay for spawning threads
+            execution: jugar_core::ExecutionMode::Sequential,
         }
     }
 }
@@ -160,6 +267,16 @@ impl JugarConfig {
         self
     }
 
+    /// Sets how systems run; the default
+    /// [`jugar_core::ExecutionMode::Sequential`] gives single-threaded
+    /// deterministic stepping. Parallel modes spawn threads for every
+    /// stage, so they only pay off when the frame's jobs are heavy.
+    #[must_use]
+    pub const fn with_execution(mut self, execution: jugar_core::ExecutionMode) -> Self {
+        self.execution = execution;
+        self
+    }
+
     /// Mobile portrait preset
     #[must_use]
     pub fn mobile_portrait() -> Self {
@@ -229,11 +346,22 @@ pub struct JugarEngine {
     time: Time,
     viewport: render::Viewport,
     input: input::InputState,
+    #[cfg(feature = "audio")]
     audio: audio::AudioSystem,
+    #[cfg(feature = "audio")]
+    audio_emitters: audio::EmitterSync,
     world: jugar_core::World,
+    transforms: jugar_core::TransformSoa,
     physics: physics::PhysicsWorld,
+    physics_sync: physics::PhysicsSync,
     ui: ui::UiContainer,
+    debug: render::DebugDraw,
+    sprites: render::SpriteExtractor,
+    accessibility: jugar_core::AccessibilitySettings,
+    colors: render::ColorRemap,
+    recorder: render::ClipRecorder,
     game_loop: jugar_core::GameLoop,
+    schedule: jugar_core::Schedule,
     running: bool,
 }
 
@@ -250,17 +378,32 @@ impl JugarEngine {
             target_fps: config.target_fps,
         };
         let game_loop = jugar_core::GameLoop::new(loop_config);
+        let mut world = jugar_core::World::new();
+        let physics_sync = physics::PhysicsSync::new(&mut world);
+        #[cfg(feature = "audio")]
+        let audio_emitters = audio::EmitterSync::new(&mut world);
 
         Self {
             config,
             time: Time::default(),
             viewport,
             input: input::InputState::new(),
+            #[cfg(feature = "audio")]
             audio: audio::AudioSystem::new(),
-            world: jugar_core::World::new(),
+            #[cfg(feature = "audio")]
+            audio_emitters,
+            world,
+            transforms: jugar_core::TransformSoa::new(),
             physics: physics::PhysicsWorld::new(),
+            physics_sync,
             ui: ui::UiContainer::new(ui_width, ui_height),
+            debug: render::DebugDraw::new(),
+            sprites: render::SpriteExtractor::new(),
+            accessibility: jugar_core::AccessibilitySettings::new(),
+            colors: render::ColorRemap::default(),
+            recorder: render::ClipRecorder::default(),
             game_loop,
+            schedule: jugar_core::Schedule::default(),
             running: false,
         }
     }
@@ -302,12 +445,14 @@ impl JugarEngine {
     }
 
     /// Gets the audio system
+    #[cfg(feature = "audio")]
     #[must_use]
     pub const fn audio(&self) -> &audio::AudioSystem {
         &self.audio
     }
 
     /// Gets the audio system mutably
+    #[cfg(feature = "audio")]
     #[allow(clippy::missing_const_for_fn)]
     pub fn audio_mut(&mut self) -> &mut audio::AudioSystem {
         &mut self.audio
@@ -325,6 +470,26 @@ impl JugarEngine {
         &mut self.world
     }
 
+    /// Moves every entity with a `Position` and `Velocity` over `dt` seconds
+    ///
+    /// The transforms are copied into struct-of-arrays columns, integrated
+    /// in SIMD-width batches and written back, which keeps 10k-entity
+    /// scenes well inside a 60fps frame.
+    pub fn integrate_world(&mut self, dt: f32) {
+        self.transforms.gather(&self.world);
+        self.transforms.integrate(dt);
+        self.transforms.scatter(&mut self.world);
+    }
+
+    /// Transform columns from the last [`integrate_world`](Self::integrate_world)
+    ///
+    /// Feed them to [`render::Viewport::world_to_screen_batch`] to place
+    /// sprites without a per-entity conversion.
+    #[must_use]
+    pub const fn transforms(&self) -> &jugar_core::TransformSoa {
+        &self.transforms
+    }
+
     /// Gets the physics world
     #[must_use]
     pub const fn physics(&self) -> &physics::PhysicsWorld {
@@ -349,6 +514,113 @@ impl JugarEngine {
         &mut self.ui
     }
 
+    /// Gets the debug overlay recorder
+    #[must_use]
+    pub const fn debug(&self) -> &render::DebugDraw {
+        &self.debug
+    }
+
+    /// Gets the debug overlay recorder mutably (e.g. to toggle categories)
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn debug_mut(&mut self) -> &mut render::DebugDraw {
+        &mut self.debug
+    }
+
+    /// Gets the player's accessibility settings
+    #[must_use]
+    pub const fn accessibility(&self) -> &jugar_core::AccessibilitySettings {
+        &self.accessibility
+    }
+
+    /// Replaces the accessibility settings (high contrast, large text, reduced motion,
+    /// color vision)
+    pub fn set_accessibility(&mut self, settings: jugar_core::AccessibilitySettings) {
+        self.accessibility = settings;
+        self.colors
+            .set_active(&render::Palette::colors_for(&self.accessibility));
+    }
+
+    /// Registers the palette the game draws with (the kid crayons by default)
+    ///
+    /// Its colors are swapped for the active colorblind-safe or
+    /// high-contrast palette by [`JugarEngine::remap_colors`].
+    pub fn set_game_palette(&mut self, palette: render::Palette) {
+        self.colors = render::ColorRemap::new(palette);
+        self.colors
+            .set_active(&render::Palette::colors_for(&self.accessibility));
+    }
+
+    /// Swaps the game's colors in a frame's render queue for the active palette
+    pub fn remap_colors(&self, queue: &mut render::RenderQueue) {
+        self.colors.apply(queue);
+    }
+
+    /// Renders a frame's queue to an RGBA image at viewport size (a screenshot)
+    ///
+    /// Uses the software rasterizer, so it works headlessly; the active
+    /// color remap is applied as on screen.
+    #[must_use]
+    pub fn capture_frame(&self, queue: &render::RenderQueue) -> render::Image {
+        self.rasterize_frame(queue, self.viewport.width)
+    }
+
+    fn rasterize_frame(&self, queue: &render::RenderQueue, width: u32) -> render::Image {
+        let mut commands = queue.commands().to_vec();
+        for command in &mut commands {
+            if let Some(color) = command.color_mut() {
+                *color = self.colors.map(*color);
+            }
+        }
+        let scene = glam::Vec2::new(self.viewport.width as f32, self.viewport.height as f32);
+        let width = width.clamp(1, self.viewport.width.max(1));
+        let height = (u64::from(self.viewport.height) * u64::from(width)
+            / u64::from(self.viewport.width.max(1)))
+        .max(1) as u32;
+        render::rasterize(&commands, scene, width, height)
+    }
+
+    /// Gets the rolling clip recorder
+    #[must_use]
+    pub const fn recorder(&self) -> &render::ClipRecorder {
+        &self.recorder
+    }
+
+    /// Gets the rolling clip recorder mutably (e.g. to start or stop it)
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn recorder_mut(&mut self) -> &mut render::ClipRecorder {
+        &mut self.recorder
+    }
+
+    /// Adds this frame to the clip if the recorder is due a frame
+    ///
+    /// Call once per frame after building the queue. Frames are rasterized
+    /// straight at clip size, so recording costs little when it is off or
+    /// between captures.
+    pub fn record_frame(&mut self, queue: &render::RenderQueue) {
+        if self.recorder.tick(self.time.delta) {
+            let frame = self.rasterize_frame(queue, self.recorder.max_width());
+            self.recorder.push(&frame);
+        }
+    }
+
+    /// Records physics and UI debug overlays for this frame
+    ///
+    /// Does nothing unless debug drawing is enabled. Flush the recorder
+    /// into the frame's render queue afterwards.
+    pub fn draw_debug(&mut self) {
+        self.debug.clear();
+        draw_physics(&self.physics, &mut self.debug);
+        draw_ui(&self.ui, &mut self.debug);
+    }
+
+    /// Draws the world's sprites into a frame's render queue
+    ///
+    /// Sprites are culled to the camera's view and drawn in [`render::ZIndex`]
+    /// order; see [`render::SpriteExtractor`].
+    pub fn draw_sprites(&mut self, queue: &mut render::RenderQueue) -> render::ExtractStats {
+        self.sprites.extract(&self.world, &self.viewport, queue)
+    }
+
     /// Gets the game loop
     #[must_use]
     pub const fn game_loop(&self) -> &jugar_core::GameLoop {
@@ -388,13 +660,8 @@ impl JugarEngine {
             self.time.fixed_delta = self.config.fixed_timestep;
             self.time.frame += 1;
 
-            // Run physics for each tick
-            for _ in 0..frame_result.physics_ticks {
-                let _ = self.physics.step(self.config.fixed_timestep);
-            }
-
-            // Update audio
-            self.audio.update(self.time.delta);
+            // Run physics for each tick, alongside the audio update
+            self.update_systems(frame_result.physics_ticks);
 
             // Call user callback
             if callback(self) == LoopControl::Exit {
@@ -415,13 +682,45 @@ impl JugarEngine {
         // Update game loop and get physics ticks
         let frame_result = self.game_loop.update(self.time.elapsed);
 
-        // Run physics for each tick
-        for _ in 0..frame_result.physics_ticks {
-            let _ = self.physics.step(self.config.fixed_timestep);
+        // Run physics for each tick, alongside the audio update
+        self.update_systems(frame_result.physics_ticks);
+        self.input.advance_frame();
+    }
+
+    /// Runs the frame's independent systems as jobs
+    fn update_systems(&mut self, physics_ticks: u32) {
+        let fixed_timestep = self.config.fixed_timestep;
+        self.physics_sync
+            .pre_step(&mut self.world, &mut self.physics);
+        let bodies = &mut self.physics;
+        let mut graph = jugar_core::JobGraph::new();
+        graph.add(
+            jugar_core::Job::new("physics", move || {
+                for _ in 0..physics_ticks {
+                    let _ = bodies.step(fixed_timestep);
+                }
+            })
+            .writes::<physics::PhysicsWorld>(),
+        );
+        #[cfg(feature = "audio")]
+        {
+            self.audio_emitters.update(&mut self.world, &mut self.audio);
+            let delta = self.time.delta;
+            let sounds = &mut self.audio;
+            graph.add(
+                jugar_core::Job::new("audio", move || sounds.update(delta))
+                    .writes::<audio::AudioSystem>(),
+            );
         }
+        // No job names a dependency or shares a resource, so planning can't fail
+        self.schedule = graph.run(self.config.execution).unwrap_or_default();
+        self.physics_sync.post_step(&mut self.world, &self.physics);
+    }
 
-        self.audio.update(self.time.delta);
-        self.input.advance_frame();
+    /// How the last frame's systems were staged, for race checks
+    #[must_use]
+    pub const fn schedule(&self) -> &jugar_core::Schedule {
+        &self.schedule
     }
 
     /// Stops the engine
@@ -469,7 +768,8 @@ pub fn create_ultrawide_game() -> JugarEngine {
 #[allow(
     clippy::unwrap_used,
     clippy::expect_used,
-    clippy::field_reassign_with_default
+    clippy::field_reassign_with_default,
+    clippy::float_cmp
 )]
 mod tests {
     use super::*;
@@ -550,6 +850,58 @@ mod tests {
         assert_eq!(engine.time().frame, 10);
     }
 
+    #[test]
+    fn test_systems_run_as_jobs_in_either_mode() {
+        let simulate = |execution| {
+            let mut engine = JugarEngine::new(JugarConfig::default().with_execution(execution));
+            let handle = engine.physics_mut().add_body(
+                physics::RigidBody::new(jugar_core::Position::new(0.0, 10.0))
+                    .with_velocity(jugar_core::Velocity::new(3.0, 0.0)),
+            );
+            for _ in 0..30 {
+                engine.step(1.0 / 60.0);
+            }
+            let schedule = engine.schedule().clone();
+            (
+                engine.physics().get_body(handle).unwrap().position,
+                schedule,
+            )
+        };
+
+        let (sequential, schedule) = simulate(jugar_core::ExecutionMode::Sequential);
+        let (parallel, _) = simulate(jugar_core::ExecutionMode::Parallel { threads: 2 });
+        assert_eq!(sequential, parallel);
+        assert!(sequential.x > 0.0);
+        assert_eq!(schedule.stage_of("physics"), Some(0));
+        #[cfg(feature = "audio")]
+        assert_eq!(schedule.stage_of("audio"), Some(0));
+        assert!(schedule.conflicts().is_empty());
+    }
+
+    #[test]
+    fn test_step_syncs_entity_bodies() {
+        let mut engine = JugarEngine::default();
+        let ball = engine.world_mut().spawn();
+        engine.world_mut().add_component(
+            ball,
+            physics::RigidBody::new(jugar_core::Position::new(0.0, 10.0)),
+        );
+        for _ in 0..30 {
+            engine.step(1.0 / 60.0);
+        }
+        assert_eq!(engine.physics().body_count(), 1);
+        let y = engine
+            .world()
+            .get_component::<jugar_core::Position>(ball)
+            .unwrap()
+            .y;
+        assert!(y < 10.0);
+
+        engine.world_mut().despawn(ball).unwrap();
+        engine.step(1.0 / 60.0);
+        assert_eq!(engine.physics().body_count(), 0);
+    }
+
     #[test]
     fn test_engine_run_exit() {
         let mut engine = JugarEngine::default();
@@ -606,17 +958,161 @@ mod tests {
         let _ = engine.viewport_mut();
         let _ = engine.input();
         let _ = engine.input_mut();
-        let _ = engine.audio();
-        let _ = engine.audio_mut();
+        #[cfg(feature = "audio")]
+        {
+            let _ = engine.audio();
+            let _ = engine.audio_mut();
+        }
         let _ = engine.world();
         let _ = engine.world_mut();
         let _ = engine.physics();
         let _ = engine.physics_mut();
         let _ = engine.ui();
         let _ = engine.ui_mut();
+        let _ = engine.accessibility();
         let _ = engine.game_loop();
     }
 
+    #[test]
+    fn test_engine_accessibility_settings() {
+        let mut engine = JugarEngine::default();
+        assert!(engine.accessibility().allows_screen_shake());
+
+        engine.set_accessibility(
+            prelude::AccessibilitySettings::new()
+                .with_reduced_motion(true)
+                .with_high_contrast(true),
+        );
+        assert!(!engine.accessibility().allows_particles());
+        assert_eq!(
+            render::Palette::colors_for(engine.accessibility()).name,
+            "high-contrast"
+        );
+    }
+
+    #[test]
+    fn test_engine_remaps_game_colors() {
+        let mut engine = JugarEngine::default();
+        let purple = render::Color::named("purple").unwrap();
+        let mut queue = render::RenderQueue::new();
+        queue.text("hi", prelude::Vec2::ZERO, 16.0, purple.to_array());
+
+        engine.remap_colors(&mut queue);
+        assert!(matches!(
+            &queue.commands()[0],
+            render::RenderCommand::DrawText { color, .. } if *color == purple.to_array()
+        ));
+
+        engine.set_accessibility(
+            prelude::AccessibilitySettings::new()
+                .with_color_vision(prelude::ColorVision::Tritanopia),
+        );
+        engine.remap_colors(&mut queue);
+        let safe = render::Palette::colorblind_safe(prelude::ColorVision::Tritanopia)
+            .get("purple")
+            .unwrap();
+        assert!(matches!(
+            &queue.commands()[0],
+            render::RenderCommand::DrawText { color, .. } if *color == safe.to_array()
+        ));
+    }
+
+    #[test]
+    fn test_integrate_world_batches_movers() {
+        let mut engine = JugarEngine::new(JugarConfig::default());
+        let mut movers = Vec::new();
+        for i in 0..10_000 {
+            let world = engine.world_mut();
+            let entity = world.spawn();
+            world.add_component(entity, jugar_core::Position::new(i as f32, 0.0));
+            world.add_component(entity, jugar_core::Velocity::new(2.0, 4.0));
+            movers.push(entity);
+        }
+
+        engine.integrate_world(0.5);
+        assert_eq!(engine.transforms().len(), 10_000);
+        let moved = engine
+            .world()
+            .get_component::<jugar_core::Position>(movers[42])
+            .unwrap();
+        assert!((moved.x - 43.0).abs() < 1e-4);
+        assert!((moved.y - 2.0).abs() < 1e-4);
+
+        let transforms = engine.transforms();
+        let mut screen_xs = vec![0.0; transforms.len()];
+        let mut screen_ys = vec![0.0; transforms.len()];
+        engine.viewport().world_to_screen_batch(
+            transforms.xs(),
+            transforms.ys(),
+            &jugar_core::Camera::new(),
+            &mut screen_xs,
+            &mut screen_ys,
+        );
+        let expected = engine
+            .viewport()
+            .world_to_screen(glam::Vec2::new(43.0, 2.0), &jugar_core::Camera::new());
+        assert!((screen_xs[42] - expected.x).abs() < 1e-3);
+        assert!((screen_ys[42] - expected.y).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_draw_sprites_from_world() {
+        let mut engine = JugarEngine::default();
+        let world = engine.world_mut();
+        let hero = world.spawn();
+        world.add_component(hero, jugar_core::Position::new(0.0, 0.0));
+        world.add_component(hero, jugar_core::Sprite::new(7));
+        world.add_component(hero, render::SpriteSize(glam::Vec2::splat(32.0)));
+
+        let mut queue = render::RenderQueue::new();
+        let stats = engine.draw_sprites(&mut queue);
+        assert_eq!(stats.drawn, 1);
+        assert!(matches!(
+            queue.commands(),
+            [render::RenderCommand::DrawSprite { texture_id: 7, .. }]
+        ));
+    }
+
+    #[test]
+    fn test_capture_frame_and_clip() {
+        let mut engine = JugarEngine::new(JugarConfig::new(64, 32));
+        let mut queue = render::RenderQueue::new();
+        queue.push(render::RenderCommand::Clear {
+            color: [1.0, 0.0, 0.0, 1.0],
+        });
+
+        let shot = engine.capture_frame(&queue);
+        assert_eq!((shot.width(), shot.height()), (64, 32));
+        assert_eq!(shot.pixel(10, 10), Some([255, 0, 0, 255]));
+
+        engine.record_frame(&queue);
+        assert!(engine.recorder().is_empty());
+        *engine.recorder_mut() = render::ClipRecorder::new(1.0, 10).with_max_width(16);
+        engine.recorder_mut().start();
+        for _ in 0..30 {
+            engine.step(1.0 / 60.0);
+            engine.record_frame(&queue);
+        }
+        assert!(engine.recorder().len() >= 5);
+        let latest = engine.recorder().latest().unwrap();
+        assert_eq!((latest.width(), latest.height()), (16, 8));
+        assert!(engine.recorder().to_apng().is_some());
+    }
+
+    #[test]
+    fn test_build_info_lists_default_subsystems() {
+        let info = build_info();
+        assert_eq!(info.version, env!("CARGO_PKG_VERSION"));
+        let subsystems = info.subsystems();
+        assert_eq!(
+            &subsystems[..5],
+            ["core", "input", "physics", "render", "ui"]
+        );
+        assert_eq!(subsystems.contains(&"audio"), cfg!(feature = "audio"));
+        assert_eq!(subsystems.contains(&"widgets"), cfg!(feature = "widgets"));
+        assert!(info.to_string().starts_with("jugar "));
+    }
+
     #[test]
     fn test_loop_control() {
         assert_eq!(LoopControl::Continue, LoopControl::Continue);
@@ -630,4 +1126,19 @@ mod tests {
         assert!(time.delta.abs() < f32::EPSILON);
         assert_eq!(time.frame, 0);
     }
+
+    #[test]
+    fn test_engine_debug_draw_toggle() {
+        let mut engine = JugarEngine::default();
+        let _ = engine.physics_mut().add_body(
+            physics::RigidBody::new(jugar_core::Position::new(0.0, 0.0))
+                .with_shape(physics::Shape::circle(1.0)),
+        );
+        engine.draw_debug();
+        assert!(engine.debug().commands().is_empty());
+
+        engine.debug_mut().set_enabled(true);
+        engine.draw_debug();
+        assert!(!engine.debug().commands().is_empty());
+    }
 }
@@ -0,0 +1,928 @@
+//! Tiled map import.
+//!
+//! Loads maps saved by the Tiled editor, as JSON (`.tmj`) or XML (`.tmx`
+//! with CSV layer data), into a [`TiledLevel`]. Tile layers become
+//! [`TileMap`]s, collision layers become static colliders, trigger objects
+//! become sensor volumes and every other object becomes a [`SpawnPoint`].
+//! Positions stay in map pixels with y pointing down, as in the editor.
+
+use alloc::collections::BTreeMap;
+
+use glam::Vec2;
+use serde::Deserialize;
+
+use jugar_core::{Position, Rect, TileMap};
+use jugar_physics::{BodyHandle, PhysicsWorld, RigidBody, Shape};
+
+use crate::{JugarError, Result};
+
+/// Bits Tiled uses for flip and rotation flags in a gid
+const GID_FLAGS: u32 = 0xF000_0000;
+
+/// A custom property value
+#[derive(Debug, Clone, PartialEq)]
+pub enum PropertyValue {
+    /// Boolean property
+    Bool(bool),
+    /// Integer property
+    Int(i64),
+    /// Float property
+    Float(f64),
+    /// String, color, file or any other property
+    String(String),
+}
+
+impl PropertyValue {
+    /// Returns the value if it is a bool
+    #[must_use]
+    pub const fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Returns the value if it is numeric
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub const fn as_f64(&self) -> Option<f64> {
+        match self {
+            Self::Int(value) => Some(*value as f64),
+            Self::Float(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Returns the value if it is a string
+    #[must_use]
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+/// Custom properties by name
+pub type Properties = BTreeMap<String, PropertyValue>;
+
+/// A visible tile layer
+#[derive(Debug, Clone, PartialEq)]
+pub struct TileLayer {
+    /// Layer name
+    pub name: String,
+    /// Tiles with flip flags removed
+    pub map: TileMap,
+    /// Layer properties
+    pub properties: Properties,
+}
+
+/// An object that should spawn an entity
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpawnPoint {
+    /// Object name
+    pub name: String,
+    /// Prefab to spawn, from the object class
+    pub prefab: String,
+    /// Top-left corner
+    pub position: Vec2,
+    /// Object size (zero for points)
+    pub size: Vec2,
+    /// Object properties
+    pub properties: Properties,
+}
+
+/// An area that reports overlaps instead of colliding
+#[derive(Debug, Clone, PartialEq)]
+pub struct TriggerVolume {
+    /// Object name
+    pub name: String,
+    /// Area covered
+    pub rect: Rect,
+    /// Object properties
+    pub properties: Properties,
+}
+
+/// Bodies created by [`TiledLevel::add_to_world`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LevelBodies {
+    /// Static colliders, in the order of [`TiledLevel::colliders`]
+    pub colliders: Vec<BodyHandle>,
+    /// Sensors, in the order of [`TiledLevel::triggers`]
+    pub triggers: Vec<BodyHandle>,
+}
+
+/// A map imported from Tiled
+#[derive(Debug, Clone, PartialEq)]
+pub struct TiledLevel {
+    /// Width in tiles
+    pub width: u32,
+    /// Height in tiles
+    pub height: u32,
+    /// Size of one tile in pixels
+    pub tile_size: Vec2,
+    /// Map properties
+    pub properties: Properties,
+    /// Tile layers, bottom to top
+    pub layers: Vec<TileLayer>,
+    /// Objects to spawn
+    pub spawns: Vec<SpawnPoint>,
+    /// Trigger volumes
+    pub triggers: Vec<TriggerVolume>,
+    /// Solid areas, with runs of collision tiles merged per row
+    pub colliders: Vec<Rect>,
+}
+
+impl TiledLevel {
+    /// Imports a map, detecting JSON or TMX from the first character
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the map is malformed or uses unsupported features
+    pub fn parse(source: &str) -> Result<Self> {
+        if source.trim_start().starts_with('<') {
+            Self::from_tmx(source)
+        } else {
+            Self::from_json(source)
+        }
+    }
+
+    /// Imports a map saved as Tiled JSON
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the JSON is malformed, the map is infinite or a
+    /// layer uses base64 data
+    pub fn from_json(source: &str) -> Result<Self> {
+        let map: JsonMap = serde_json::from_str(source).map_err(|e| import_error(e.to_string()))?;
+        build(map.into_raw()?)
+    }
+
+    /// Imports a map saved as Tiled XML
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the XML is malformed, the map is infinite or a
+    /// layer uses base64 data
+    pub fn from_tmx(source: &str) -> Result<Self> {
+        let root = parse_xml(source)?;
+        if root.name != "map" {
+            return Err(import_error("root element is not <map>"));
+        }
+        build(tmx_map(&root)?)
+    }
+
+    /// Finds a tile layer by name
+    #[must_use]
+    pub fn layer(&self, name: &str) -> Option<&TileLayer> {
+        self.layers.iter().find(|layer| layer.name == name)
+    }
+
+    /// Spawn points for one prefab
+    pub fn spawns_of<'a>(&'a self, prefab: &'a str) -> impl Iterator<Item = &'a SpawnPoint> {
+        self.spawns
+            .iter()
+            .filter(move |spawn| spawn.prefab == prefab)
+    }
+
+    /// Adds static colliders and trigger sensors to a physics world
+    pub fn add_to_world(&self, world: &mut PhysicsWorld) -> LevelBodies {
+        let colliders = self
+            .colliders
+            .iter()
+            .map(|rect| world.add_body(static_body(rect)))
+            .collect();
+        let triggers = self
+            .triggers
+            .iter()
+            .map(|trigger| world.add_body(static_body(&trigger.rect).with_sensor(true)))
+            .collect();
+        LevelBodies {
+            colliders,
+            triggers,
+        }
+    }
+
+    fn add_layers(&mut self, layers: Vec<RawLayer>) -> Result<()> {
+        for layer in layers {
+            match layer {
+                RawLayer::Tiles {
+                    name,
+                    width,
+                    height,
+                    data,
+                    properties,
+                } => {
+                    if data.len() != (width as usize) * (height as usize) {
+                        return Err(import_error(format!(
+                            "layer '{name}' has {} tiles, expected {width}x{height}",
+                            data.len()
+                        )));
+                    }
+                    let mut map = TileMap::new(width, height, self.tile_size);
+                    for (i, gid) in (0u32..).zip(data) {
+                        map.set(i % width, i / width, gid & !GID_FLAGS);
+                    }
+                    if is_collision(&name, &properties) {
+                        self.colliders.extend(solid_runs(&map));
+                    }
+                    self.layers.push(TileLayer {
+                        name,
+                        map,
+                        properties,
+                    });
+                }
+                RawLayer::Objects {
+                    name,
+                    objects,
+                    properties,
+                } => self.add_objects(is_collision(&name, &properties), objects),
+                RawLayer::Group(children) => self.add_layers(children)?,
+            }
+        }
+        Ok(())
+    }
+
+    fn add_objects(&mut self, collision: bool, objects: Vec<RawObject>) {
+        for object in objects {
+            // Tile objects are anchored at their bottom-left corner
+            let y = if object.gid.is_some() {
+                object.y - object.height
+            } else {
+                object.y
+            };
+            let rect = Rect::new(object.x, y, object.width, object.height);
+            let trigger =
+                object.kind.eq_ignore_ascii_case("trigger") || flag(&object.properties, "trigger");
+            if collision {
+                if rect.width > 0.0 && rect.height > 0.0 {
+                    self.colliders.push(rect);
+                }
+            } else if trigger {
+                self.triggers.push(TriggerVolume {
+                    name: object.name,
+                    rect,
+                    properties: object.properties,
+                });
+            } else {
+                self.spawns.push(SpawnPoint {
+                    name: object.name,
+                    prefab: object.kind,
+                    position: Vec2::new(rect.x, rect.y),
+                    size: Vec2::new(rect.width, rect.height),
+                    properties: object.properties,
+                });
+            }
+        }
+    }
+}
+
+fn import_error(message: impl Into<String>) -> JugarError {
+    JugarError::MapImport(message.into())
+}
+
+fn flag(properties: &Properties, name: &str) -> bool {
+    properties.get(name).and_then(PropertyValue::as_bool) == Some(true)
+}
+
+fn is_collision(name: &str, properties: &Properties) -> bool {
+    name.eq_ignore_ascii_case("collision")
+        || name.eq_ignore_ascii_case("collisions")
+        || flag(properties, "collision")
+}
+
+fn static_body(rect: &Rect) -> RigidBody {
+    let (x, y) = rect.center();
+    RigidBody::new_static(Position::new(x, y)).with_shape(Shape::rect(rect.width, rect.height))
+}
+
+/// Merges horizontal runs of solid tiles into rectangles
+#[allow(clippy::cast_precision_loss)]
+fn solid_runs(map: &TileMap) -> Vec<Rect> {
+    let mut rects = Vec::new();
+    for y in 0..map.height {
+        let mut x = 0;
+        while x < map.width {
+            if map.get(x, y) == 0 {
+                x += 1;
+                continue;
+            }
+            let start = x;
+            while x < map.width && map.get(x, y) != 0 {
+                x += 1;
+            }
+            let first = map.tile_rect(start, y);
+            rects.push(Rect::new(
+                first.x,
+                first.y,
+                (x - start) as f32 * map.tile_size.x,
+                first.height,
+            ));
+        }
+    }
+    rects
+}
+
+fn build(raw: RawMap) -> Result<TiledLevel> {
+    if raw.infinite {
+        return Err(import_error("infinite maps are not supported"));
+    }
+    let mut level = TiledLevel {
+        width: raw.width,
+        height: raw.height,
+        tile_size: Vec2::new(raw.tile_width, raw.tile_height),
+        properties: raw.properties,
+        layers: Vec::new(),
+        spawns: Vec::new(),
+        triggers: Vec::new(),
+        colliders: Vec::new(),
+    };
+    level.add_layers(raw.layers)?;
+    Ok(level)
+}
+
+/// Format-independent map contents
+struct RawMap {
+    width: u32,
+    height: u32,
+    tile_width: f32,
+    tile_height: f32,
+    infinite: bool,
+    properties: Properties,
+    layers: Vec<RawLayer>,
+}
+
+enum RawLayer {
+    Tiles {
+        name: String,
+        width: u32,
+        height: u32,
+        data: Vec<u32>,
+        properties: Properties,
+    },
+    Objects {
+        name: String,
+        objects: Vec<RawObject>,
+        properties: Properties,
+    },
+    Group(Vec<Self>),
+}
+
+struct RawObject {
+    name: String,
+    kind: String,
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+    gid: Option<u32>,
+    properties: Properties,
+}
+
+// ---------------------------------------------------------------------------
+// JSON
+// ---------------------------------------------------------------------------
+
+#[derive(Deserialize)]
+struct JsonMap {
+    width: u32,
+    height: u32,
+    tilewidth: f32,
+    tileheight: f32,
+    #[serde(default)]
+    infinite: bool,
+    #[serde(default)]
+    layers: Vec<JsonLayer>,
+    #[serde(default)]
+    properties: Vec<JsonProperty>,
+}
+
+#[derive(Deserialize)]
+struct JsonLayer {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    width: u32,
+    #[serde(default)]
+    height: u32,
+    #[serde(default)]
+    data: Option<serde_json::Value>,
+    #[serde(default)]
+    objects: Vec<JsonObject>,
+    #[serde(default)]
+    layers: Vec<Self>,
+    #[serde(default)]
+    properties: Vec<JsonProperty>,
+}
+
+#[derive(Deserialize)]
+struct JsonObject {
+    #[serde(default)]
+    name: String,
+    #[serde(default, rename = "type")]
+    kind: String,
+    #[serde(default)]
+    class: String,
+    x: f32,
+    y: f32,
+    #[serde(default)]
+    width: f32,
+    #[serde(default)]
+    height: f32,
+    #[serde(default)]
+    gid: Option<u32>,
+    #[serde(default)]
+    properties: Vec<JsonProperty>,
+}
+
+#[derive(Deserialize)]
+struct JsonProperty {
+    name: String,
+    #[serde(default, rename = "type")]
+    kind: String,
+    value: serde_json::Value,
+}
+
+impl JsonMap {
+    fn into_raw(self) -> Result<RawMap> {
+        Ok(RawMap {
+            width: self.width,
+            height: self.height,
+            tile_width: self.tilewidth,
+            tile_height: self.tileheight,
+            infinite: self.infinite,
+            properties: json_properties(self.properties),
+            layers: json_layers(self.layers)?,
+        })
+    }
+}
+
+fn json_layers(layers: Vec<JsonLayer>) -> Result<Vec<RawLayer>> {
+    let mut raw = Vec::new();
+    for layer in layers {
+        let properties = json_properties(layer.properties);
+        match layer.kind.as_str() {
+            "tilelayer" => {
+                let data = match layer.data {
+                    Some(serde_json::Value::Array(values)) => values
+                        .iter()
+                        .map(|value| {
+                            value
+                                .as_u64()
+                                .and_then(|gid| u32::try_from(gid).ok())
+                                .ok_or_else(|| import_error("tile ids must be unsigned integers"))
+                        })
+                        .collect::<Result<Vec<_>>>()?,
+                    Some(_) => return Err(unsupported_encoding(&layer.name)),
+                    None => Vec::new(),
+                };
+                raw.push(RawLayer::Tiles {
+                    name: layer.name,
+                    width: layer.width,
+                    height: layer.height,
+                    data,
+                    properties,
+                });
+            }
+            "objectgroup" => raw.push(RawLayer::Objects {
+                name: layer.name,
+                objects: layer.objects.into_iter().map(json_object).collect(),
+                properties,
+            }),
+            "group" => raw.push(RawLayer::Group(json_layers(layer.layers)?)),
+            _ => {}
+        }
+    }
+    Ok(raw)
+}
+
+fn json_object(object: JsonObject) -> RawObject {
+    RawObject {
+        name: object.name,
+        kind: if object.kind.is_empty() {
+            object.class
+        } else {
+            object.kind
+        },
+        x: object.x,
+        y: object.y,
+        width: object.width,
+        height: object.height,
+        gid: object.gid,
+        properties: json_properties(object.properties),
+    }
+}
+
+fn json_properties(properties: Vec<JsonProperty>) -> Properties {
+    use serde_json::Value;
+
+    properties
+        .into_iter()
+        .map(|property| {
+            let value = match (property.kind.as_str(), property.value) {
+                (_, Value::Bool(value)) => PropertyValue::Bool(value),
+                ("float", Value::Number(n)) => PropertyValue::Float(n.as_f64().unwrap_or(0.0)),
+                (_, Value::Number(n)) => n.as_i64().map_or_else(
+                    || PropertyValue::Float(n.as_f64().unwrap_or(0.0)),
+                    PropertyValue::Int,
+                ),
+                (_, Value::String(s)) => PropertyValue::String(s),
+                (_, other) => PropertyValue::String(other.to_string()),
+            };
+            (property.name, value)
+        })
+        .collect()
+}
+
+fn unsupported_encoding(layer: &str) -> JugarError {
+    import_error(format!(
+        "layer '{layer}' uses compressed or base64 data; save the map with CSV layer format"
+    ))
+}
+
+// ---------------------------------------------------------------------------
+// TMX
+// ---------------------------------------------------------------------------
+
+#[derive(Default)]
+struct XmlNode {
+    name: String,
+    attrs: BTreeMap<String, String>,
+    children: Vec<Self>,
+    text: String,
+}
+
+impl XmlNode {
+    fn attr(&self, key: &str) -> Option<&str> {
+        self.attrs.get(key).map(String::as_str)
+    }
+
+    fn string(&self, key: &str) -> String {
+        self.attr(key).unwrap_or_default().to_string()
+    }
+
+    fn number<T: core::str::FromStr>(&self, key: &str, default: T) -> Result<T> {
+        self.attr(key).map_or(Ok(default), |value| {
+            value
+                .trim()
+                .parse()
+                .map_err(|_| import_error(format!("<{}> has invalid {key}=\"{value}\"", self.name)))
+        })
+    }
+
+    fn child(&self, name: &str) -> Option<&Self> {
+        self.children.iter().find(|child| child.name == name)
+    }
+}
+
+fn tmx_map(root: &XmlNode) -> Result<RawMap> {
+    Ok(RawMap {
+        width: root.number("width", 0)?,
+        height: root.number("height", 0)?,
+        tile_width: root.number("tilewidth", 0.0)?,
+        tile_height: root.number("tileheight", 0.0)?,
+        infinite: root.attr("infinite") == Some("1"),
+        properties: tmx_properties(root)?,
+        layers: tmx_layers(root)?,
+    })
+}
+
+fn tmx_layers(parent: &XmlNode) -> Result<Vec<RawLayer>> {
+    let mut raw = Vec::new();
+    for node in &parent.children {
+        match node.name.as_str() {
+            "layer" => raw.push(RawLayer::Tiles {
+                name: node.string("name"),
+                width: node.number("width", 0)?,
+                height: node.number("height", 0)?,
+                data: tmx_tile_data(node)?,
+                properties: tmx_properties(node)?,
+            }),
+            "objectgroup" => {
+                let objects = node
+                    .children
+                    .iter()
+                    .filter(|child| child.name == "object")
+                    .map(tmx_object)
+                    .collect::<Result<Vec<_>>>()?;
+                raw.push(RawLayer::Objects {
+                    name: node.string("name"),
+                    objects,
+                    properties: tmx_properties(node)?,
+                });
+            }
+            "group" => raw.push(RawLayer::Group(tmx_layers(node)?)),
+            _ => {}
+        }
+    }
+    Ok(raw)
+}
+
+fn tmx_tile_data(layer: &XmlNode) -> Result<Vec<u32>> {
+    let Some(data) = layer.child("data") else {
+        return Ok(Vec::new());
+    };
+    match data.attr("encoding") {
+        Some("csv") => data
+            .text
+            .split(',')
+            .map(str::trim)
+            .filter(|gid| !gid.is_empty())
+            .map(|gid| {
+                gid.parse()
+                    .map_err(|_| import_error(format!("invalid tile id '{gid}'")))
+            })
+            .collect(),
+        None => data
+            .children
+            .iter()
+            .filter(|child| child.name == "tile")
+            .map(|tile| tile.number("gid", 0))
+            .collect(),
+        Some(_) => Err(unsupported_encoding(layer.attr("name").unwrap_or_default())),
+    }
+}
+
+fn tmx_object(node: &XmlNode) -> Result<RawObject> {
+    let kind = node.attr("type").or_else(|| node.attr("class"));
+    Ok(RawObject {
+        name: node.string("name"),
+        kind: kind.unwrap_or_default().to_string(),
+        x: node.number("x", 0.0)?,
+        y: node.number("y", 0.0)?,
+        width: node.number("width", 0.0)?,
+        height: node.number("height", 0.0)?,
+        gid: node
+            .attr("gid")
+            .map(|_| node.number("gid", 0))
+            .transpose()?,
+        properties: tmx_properties(node)?,
+    })
+}
+
+fn tmx_properties(node: &XmlNode) -> Result<Properties> {
+    let Some(properties) = node.child("properties") else {
+        return Ok(Properties::new());
+    };
+    let mut out = Properties::new();
+    for property in properties.children.iter().filter(|p| p.name == "property") {
+        let raw = property
+            .attr("value")
+            .map_or_else(|| property.text.clone(), str::to_string);
+        let value = match property.attr("type").unwrap_or("string") {
+            "bool" => PropertyValue::Bool(raw == "true"),
+            "int" => PropertyValue::Int(property.number("value", 0)?),
+            "float" => PropertyValue::Float(property.number("value", 0.0)?),
+            _ => PropertyValue::String(raw),
+        };
+        let _ = out.insert(property.string("name"), value);
+    }
+    Ok(out)
+}
+
+/// Parses the small XML subset Tiled writes
+fn parse_xml(source: &str) -> Result<XmlNode> {
+    let mut stack = vec![XmlNode::default()];
+    let mut rest = source;
+    while let Some(open) = rest.find('<') {
+        if let Some(top) = stack.last_mut() {
+            top.text.push_str(&unescape(&rest[..open]));
+        }
+        rest = &rest[open..];
+        let skip_to = |end: &str| {
+            rest.find(end)
+                .map(|i| i + end.len())
+                .ok_or_else(|| import_error("unterminated XML markup"))
+        };
+        if rest.starts_with("<?") {
+            rest = &rest[skip_to("?>")?..];
+        } else if rest.starts_with("<!--") {
+            rest = &rest[skip_to("-->")?..];
+        } else if rest.starts_with("<!") {
+            rest = &rest[skip_to(">")?..];
+        } else if let Some(closing) = rest.strip_prefix("</") {
+            let end = closing
+                .find('>')
+                .ok_or_else(|| import_error("unterminated closing tag"))?;
+            let name = closing[..end].trim();
+            let node = stack
+                .pop()
+                .filter(|node| node.name == name && !name.is_empty());
+            let (Some(node), Some(parent)) = (node, stack.last_mut()) else {
+                return Err(import_error(format!("unexpected </{name}>")));
+            };
+            parent.children.push(node);
+            rest = &closing[end + 1..];
+        } else {
+            let end = tag_end(rest).ok_or_else(|| import_error("unterminated tag"))?;
+            let body = &rest[1..end];
+            let self_closing = body.ends_with('/');
+            let node = parse_tag(body.trim_end_matches('/'))?;
+            if self_closing {
+                if let Some(top) = stack.last_mut() {
+                    top.children.push(node);
+                }
+            } else {
+                stack.push(node);
+            }
+            rest = &rest[end + 1..];
+        }
+    }
+    let document = stack.pop().filter(|_| stack.is_empty());
+    document
+        .and_then(|document| document.children.into_iter().next())
+        .ok_or_else(|| import_error("unbalanced XML document"))
+}
+
+/// Finds the `>` closing a tag, ignoring any inside quoted attributes
+fn tag_end(tag: &str) -> Option<usize> {
+    let mut quote = None;
+    for (i, c) in tag.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), _) if q == c => quote = None,
+            (None, '>') => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn parse_tag(body: &str) -> Result<XmlNode> {
+    let body = body.trim();
+    let name_end = body.find(char::is_whitespace).unwrap_or(body.len());
+    let mut node = XmlNode {
+        name: body[..name_end].to_string(),
+        ..XmlNode::default()
+    };
+    let mut rest = body[name_end..].trim_start();
+    while !rest.is_empty() {
+        let eq = rest
+            .find('=')
+            .ok_or_else(|| import_error(format!("malformed attribute in <{}>", node.name)))?;
+        let key = rest[..eq].trim().to_string();
+        let value = rest[eq + 1..].trim_start();
+        let quote = value
+            .chars()
+            .next()
+            .filter(|c| *c == '"' || *c == '\'')
+            .ok_or_else(|| import_error(format!("unquoted attribute {key}")))?;
+        let close = value[1..]
+            .find(quote)
+            .ok_or_else(|| import_error(format!("unterminated attribute {key}")))?;
+        let _ = node.attrs.insert(key, unescape(&value[1..=close]));
+        rest = value[close + 2..].trim_start();
+    }
+    Ok(node)
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::float_cmp)]
+mod tests {
+    use super::*;
+
+    const JSON_MAP: &str = r#"{
+        "width": 4, "height": 3, "tilewidth": 16, "tileheight": 16,
+        "infinite": false,
+        "properties": [{"name": "music", "type": "string", "value": "cave.ogg"}],
+        "layers": [
+            {"type": "tilelayer", "name": "ground", "width": 4, "height": 3,
+             "data": [1, 2, 0, 0, 0, 0, 0, 0, 3, 3, 3, 2147483651]},
+            {"type": "tilelayer", "name": "walls", "width": 4, "height": 3,
+             "properties": [{"name": "collision", "type": "bool", "value": true}],
+             "data": [0, 0, 0, 0, 0, 0, 0, 0, 5, 5, 0, 5]},
+            {"type": "group", "name": "actors", "layers": [
+                {"type": "objectgroup", "name": "things", "objects": [
+                    {"id": 1, "name": "hero", "type": "player", "x": 8, "y": 20,
+                     "width": 0, "height": 0, "point": true,
+                     "properties": [{"name": "lives", "type": "int", "value": 3}]},
+                    {"id": 2, "name": "coin", "class": "pickup", "gid": 7,
+                     "x": 32, "y": 32, "width": 16, "height": 16},
+                    {"id": 3, "name": "exit", "type": "trigger", "x": 48, "y": 0,
+                     "width": 16, "height": 32}
+                ]}
+            ]}
+        ]
+    }"#;
+
+    const TMX_MAP: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!-- exported from Tiled -->
+<map version="1.10" orientation="orthogonal" width="3" height="2" tilewidth="8" tileheight="8" infinite="0">
+ <properties>
+  <property name="title" value="A &amp; B"/>
+  <property name="gravity" type="float" value="9.5"/>
+ </properties>
+ <tileset firstgid="1" source="tiles.tsx"/>
+ <layer id="1" name="Collision" width="3" height="2">
+  <data encoding="csv">
+0,0,0,
+4,4,4
+</data>
+ </layer>
+ <objectgroup id="2" name="objects">
+  <object id="1" name="door" x="16" y="0" width="8" height="8">
+   <properties>
+    <property name="trigger" type="bool" value="true"/>
+    <property name="note" value="x > y"/>
+   </properties>
+  </object>
+  <object id="2" name="slime" type="enemy" x="4" y="4"/>
+ </objectgroup>
+</map>"#;
+
+    #[test]
+    fn test_json_import() {
+        let level = TiledLevel::from_json(JSON_MAP).unwrap();
+        assert_eq!((level.width, level.height), (4, 3));
+        assert_eq!(
+            level.properties["music"],
+            PropertyValue::String("cave.ogg".to_string())
+        );
+
+        let ground = &level.layer("ground").unwrap().map;
+        assert_eq!(ground.get(1, 0), 2);
+        assert_eq!(ground.get(3, 2), 3, "flip flags are stripped");
+
+        assert_eq!(
+            level.colliders,
+            vec![
+                Rect::new(0.0, 32.0, 32.0, 16.0),
+                Rect::new(48.0, 32.0, 16.0, 16.0)
+            ]
+        );
+
+        assert_eq!(level.spawns.len(), 2);
+        let hero = level.spawns_of("player").next().unwrap();
+        assert_eq!(hero.position, Vec2::new(8.0, 20.0));
+        assert_eq!(hero.properties["lives"], PropertyValue::Int(3));
+        let coin = level.spawns_of("pickup").next().unwrap();
+        assert_eq!(coin.position, Vec2::new(32.0, 16.0));
+
+        assert_eq!(level.triggers.len(), 1);
+        assert_eq!(level.triggers[0].rect, Rect::new(48.0, 0.0, 16.0, 32.0));
+    }
+
+    #[test]
+    fn test_tmx_import() {
+        let level = TiledLevel::parse(TMX_MAP).unwrap();
+        assert_eq!(level.tile_size, Vec2::splat(8.0));
+        assert_eq!(
+            level.properties["title"],
+            PropertyValue::String("A & B".to_string())
+        );
+        assert_eq!(level.properties["gravity"].as_f64(), Some(9.5));
+        assert_eq!(level.colliders, vec![Rect::new(0.0, 8.0, 24.0, 8.0)]);
+
+        assert_eq!(level.triggers.len(), 1);
+        assert_eq!(level.triggers[0].name, "door");
+        assert_eq!(level.triggers[0].properties["note"].as_str(), Some("x > y"));
+
+        assert_eq!(level.spawns.len(), 1);
+        assert_eq!(level.spawns[0].prefab, "enemy");
+        assert_eq!(level.spawns[0].size, Vec2::ZERO);
+    }
+
+    #[test]
+    fn test_add_to_world() {
+        let level = TiledLevel::from_json(JSON_MAP).unwrap();
+        let mut world = PhysicsWorld::new();
+        let bodies = level.add_to_world(&mut world);
+        assert_eq!(bodies.colliders.len(), 2);
+        assert_eq!(bodies.triggers.len(), 1);
+
+        let wall = world.get_body(bodies.colliders[0]).unwrap();
+        assert!(wall.is_static);
+        assert_eq!(wall.position.as_vec2(), Vec2::new(16.0, 40.0));
+        assert!(world.get_body(bodies.triggers[0]).unwrap().is_sensor);
+    }
+
+    #[test]
+    fn test_unsupported_maps_are_rejected() {
+        let base64 = r#"{"width": 1, "height": 1, "tilewidth": 8, "tileheight": 8,
+            "layers": [{"type": "tilelayer", "name": "g", "width": 1, "height": 1,
+                        "encoding": "base64", "data": "AQAAAA=="}]}"#;
+        assert!(matches!(
+            TiledLevel::from_json(base64),
+            Err(JugarError::MapImport(message)) if message.contains("CSV")
+        ));
+
+        let infinite = r#"{"width": 1, "height": 1, "tilewidth": 8, "tileheight": 8,
+            "infinite": true}"#;
+        assert!(TiledLevel::from_json(infinite).is_err());
+
+        assert!(TiledLevel::from_tmx("<map><layer></map>").is_err());
+        let short = r#"<map width="2" height="2" tilewidth="8" tileheight="8">
+            <layer name="g" width="2" height="2"><data encoding="csv">1,2,3</data></layer></map>"#;
+        assert!(TiledLevel::from_tmx(short).is_err());
+    }
+}
@@ -224,6 +224,21 @@ impl MaterialProperties {
         // POKA-YOKE: density_milli is NonZeroU32, so mass is always positive
         self.density() * volume_m3
     }
+
+    /// Convert to an engine material for `jugar_physics` contact resolution
+    ///
+    /// Ice keeps its slipperiness and rubber its bounce whatever they touch.
+    #[must_use]
+    pub const fn to_physics_material(&self) -> jugar_physics::PhysicsMaterial {
+        use jugar_physics::{CombineRule, PhysicsMaterial};
+
+        let material = PhysicsMaterial::new(self.friction_dynamic, self.bounciness);
+        match self.preset {
+            MaterialPreset::Ice => material.with_friction_combine(CombineRule::Min),
+            MaterialPreset::Rubber => material.with_restitution_combine(CombineRule::Max),
+            MaterialPreset::Wood | MaterialPreset::Metal | MaterialPreset::Custom => material,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -334,6 +349,17 @@ mod tests {
         }
     }
 
+    mod physics_material_tests {
+        use super::*;
+
+        #[test]
+        fn test_to_physics_material() {
+            let ice = MaterialProperties::from_preset(MaterialPreset::Ice).to_physics_material();
+            assert!((ice.friction - 0.03).abs() < f32::EPSILON);
+            assert_eq!(ice.friction_combine, jugar_physics::CombineRule::Min);
+        }
+    }
+
     mod serialization_tests {
         use super::*;
 
@@ -18,6 +18,9 @@ const BALL_SIZE: f32 = 20.0;
 const BALL_SPEED: f32 = 400.0;
 const PADDLE_MARGIN: f32 = 50.0;
 
+/// Playfield in world units, centered on the origin
+const ARENA: Rect = Rect::new(-960.0, -540.0, 1920.0, 1080.0);
+
 /// Game-specific components
 mod components {
     use glam::Vec2;
@@ -67,14 +70,16 @@ struct PongGame {
     score: Score,
     game_width: f32,
     game_height: f32,
+    camera: Camera,
 }
 
 impl PongGame {
     fn new(engine: &mut JugarEngine) -> Self {
-        // Calculate game bounds based on viewport safe area
-        let viewport = engine.viewport();
-        let game_width = viewport.safe_area.width;
-        let game_height = viewport.safe_area.height;
+        // The arena is the same size on every screen; the camera scales it
+        // to fit, from mobile portrait to 32:9 ultrawide
+        let game_width = ARENA.width;
+        let game_height = ARENA.height;
+        let camera = engine.viewport().fit_world_rect(ARENA);
 
         let world = engine.world_mut();
 
@@ -114,12 +119,16 @@ impl PongGame {
             score: Score::default(),
             game_width,
             game_height,
+            camera,
         }
     }
 
     fn update(&mut self, engine: &mut JugarEngine) -> LoopControl {
         let dt = engine.time().delta;
 
+        // Refit every frame so window resizes and rotations keep the arena in view
+        self.camera = engine.viewport().fit_world_rect(ARENA);
+
         // Handle escape first
         if engine.input().key(KeyCode::Escape).just_pressed() {
             return LoopControl::Exit;
@@ -164,8 +173,7 @@ impl PongGame {
 
     fn update_playing(&mut self, engine: &mut JugarEngine, dt: f32) {
         let half_height = self.game_height / 2.0 - PADDLE_HEIGHT / 2.0;
-        let viewport_width = engine.viewport().width as f32;
-        let viewport_height = engine.viewport().height as f32;
+        let viewport = engine.viewport();
 
         // Calculate player inputs from input state
         let (p1_vel, p2_vel) = {
@@ -181,9 +189,9 @@ impl PongGame {
 
             // Touch input for player 1 (left half of screen)
             for touch in &input.touches {
-                if touch.position.x < viewport_width / 2.0 {
-                    let center_y = viewport_height / 2.0;
-                    if touch.position.y < center_y {
+                let touch_pos = viewport.screen_to_world(touch.position, &self.camera);
+                if touch_pos.x < 0.0 {
+                    if touch_pos.y > 0.0 {
                         p1_vel = PADDLE_SPEED;
                     } else {
                         p1_vel = -PADDLE_SPEED;
@@ -201,9 +209,9 @@ impl PongGame {
 
             // Touch input for player 2 (right half of screen)
             for touch in &input.touches {
-                if touch.position.x >= viewport_width / 2.0 {
-                    let center_y = viewport_height / 2.0;
-                    if touch.position.y < center_y {
+                let touch_pos = viewport.screen_to_world(touch.position, &self.camera);
+                if touch_pos.x >= 0.0 {
+                    if touch_pos.y > 0.0 {
                         p2_vel = PADDLE_SPEED;
                     } else {
                         p2_vel = -PADDLE_SPEED;
@@ -492,6 +500,20 @@ mod tests {
         assert!((p2_pos.y).abs() < f32::EPSILON);
     }
 
+    #[test]
+    fn test_arena_fits_every_aspect_ratio() {
+        for (width, height) in [(1080, 1920), (1920, 1080), (5120, 1440), (3840, 1080)] {
+            let mut engine = JugarEngine::new(JugarConfig::new(width, height));
+            let game = PongGame::new(&mut engine);
+
+            assert!((game.game_width - ARENA.width).abs() < f32::EPSILON);
+            let visible = engine.viewport().visible_world_rect(&game.camera);
+            assert!(visible.x <= ARENA.x + 0.01 && visible.y <= ARENA.y + 0.01);
+            assert!(visible.width >= ARENA.width - 0.01);
+            assert!(visible.height >= ARENA.height - 0.01);
+        }
+    }
+
     #[test]
     fn test_reset_ball() {
         let config = JugarConfig::default();